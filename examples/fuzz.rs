@@ -0,0 +1,86 @@
+//! Solves a corpus of pathological LPs with every solver and reports any
+//! that panicked, produced NaN, or called a case unbounded or infeasible:
+//! every case has an optimum, so those statuses are wrong answers.
+//!
+//!     cargo run --example fuzz -- [cases per pathology] [first seed]
+//!
+//! Exits with status 1 when a failure was found; rerun a failing case with
+//! its seed to reproduce it.
+
+use std::collections::BTreeMap;
+use std::process::ExitCode;
+
+use ipsolver::fuzz::{run_case, FuzzCase, FuzzOutcome, Pathology};
+use ipsolver::interior::{LogVerbosity, SolverOptions, StepStrategy};
+use ipsolver::model::SolverMethod;
+
+const METHODS: [SolverMethod; 5] = [
+    SolverMethod::AffineScaling,
+    SolverMethod::LogBarrier,
+    SolverMethod::SelfDual,
+    SolverMethod::Simplex,
+    SolverMethod::RevisedSimplex,
+];
+
+fn main() -> ExitCode {
+    let mut args = std::env::args().skip(1);
+    let cases: u64 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(25);
+    let first_seed: u64 = args.next().and_then(|arg| arg.parse().ok()).unwrap_or(1);
+    // Panics are reported below with their case; the default hook would
+    // print each one a second time.
+    std::panic::set_hook(Box::new(|_| {}));
+
+    let options = SolverOptions {
+        log_verbosity: LogVerbosity::Off,
+        ..SolverOptions::default()
+    };
+    let mut tally: BTreeMap<String, usize> = BTreeMap::new();
+    let mut failures = 0;
+    for pathology in Pathology::ALL {
+        for seed in first_seed..first_seed + cases {
+            let m = 2 + (seed % 4) as usize;
+            let n = m + 1 + (seed % 3) as usize;
+            let case = FuzzCase::generate(pathology, seed, m, n);
+            for method in METHODS {
+                let strategies: &[StepStrategy] = if method == SolverMethod::AffineScaling {
+                    &StepStrategy::ALL
+                } else {
+                    &[StepStrategy::LongStep]
+                };
+                for &strategy in strategies {
+                    let outcome = run_case(&case, method, strategy, &options);
+                    let key = match &outcome {
+                        FuzzOutcome::Status(status) => status.label().to_string(),
+                        FuzzOutcome::Rejected(_) => "Rejected".to_string(),
+                        FuzzOutcome::NonFinite { .. } => "NaN or infinity".to_string(),
+                        FuzzOutcome::Panicked(_) => "Panicked".to_string(),
+                    };
+                    *tally.entry(key).or_default() += 1;
+                    if outcome.is_failure() {
+                        failures += 1;
+                        println!(
+                            "FAIL {} seed {} ({}×{}), {} / {}: {:?}",
+                            pathology.label(),
+                            seed,
+                            m,
+                            n,
+                            method.label(),
+                            strategy.label(),
+                            outcome
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for (outcome, count) in &tally {
+        println!("{:>6}  {}", count, outcome);
+    }
+    if failures == 0 {
+        ExitCode::SUCCESS
+    } else {
+        println!("{} failures", failures);
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,97 @@
+// Service worker that keeps the solver usable without a network: the page and
+// every asset it references are cached on install, and later requests are
+// answered from the cache first.
+//
+// Trunk puts a content hash in the names of the wasm, js and css files, so a
+// cached asset never goes stale; only the page itself is fetched from the
+// network first so that a new build is picked up when one is reachable.
+
+const CACHE = "ipsolver-offline";
+// The solver worker is loaded by script, not referenced from the page.
+const SHELL = [
+    "./",
+    "manifest.webmanifest",
+    "icon.svg",
+    "solver_worker.js",
+    "solver_worker_bg.wasm",
+];
+
+// Same-origin asset paths referenced by the generated index.html.
+function referencedAssets(html) {
+    const pattern = /["']([^"'\s]+\.(?:js|wasm|css))["']/g;
+    const assets = new Set();
+    for (const match of html.matchAll(pattern)) {
+        const url = new URL(match[1], self.registration.scope);
+        if (url.origin === self.location.origin) {
+            assets.add(url.href);
+        }
+    }
+    return [...assets];
+}
+
+async function precache() {
+    const cache = await caches.open(CACHE);
+    const page = await fetch("./", { cache: "no-cache" });
+    const assets = referencedAssets(await page.clone().text());
+    await cache.put("./", page);
+    await cache.addAll([...SHELL.slice(1), ...assets]);
+    // Drop the hashed files of earlier builds.
+    const keep = new Set([...SHELL, ...assets].map((path) => new URL(path, self.registration.scope).href));
+    for (const request of await cache.keys()) {
+        if (!keep.has(request.url)) {
+            await cache.delete(request);
+        }
+    }
+}
+
+self.addEventListener("install", (event) => {
+    event.waitUntil(precache().then(() => self.skipWaiting()));
+});
+
+self.addEventListener("activate", (event) => {
+    event.waitUntil(self.clients.claim());
+});
+
+async function fromNetworkFirst(request) {
+    const cache = await caches.open(CACHE);
+    try {
+        const response = await fetch(request);
+        if (response.ok) {
+            await cache.put("./", response.clone());
+        }
+        return response;
+    } catch (error) {
+        const cached = await cache.match("./");
+        if (cached) {
+            return cached;
+        }
+        throw error;
+    }
+}
+
+async function fromCacheFirst(request) {
+    const cache = await caches.open(CACHE);
+    const cached = await cache.match(request);
+    if (cached) {
+        return cached;
+    }
+    const response = await fetch(request);
+    // Fonts and other cross-origin files come back opaque; keep them too so
+    // the page looks the same offline.
+    if (response.ok || response.type === "opaque") {
+        await cache.put(request, response.clone());
+    }
+    return response;
+}
+
+self.addEventListener("fetch", (event) => {
+    const request = event.request;
+    if (request.method !== "GET") {
+        return;
+    }
+    if (request.mode === "navigate") {
+        event.respondWith(fromNetworkFirst(request));
+    } else {
+        event.respondWith(fromCacheFirst(request));
+    }
+});
@@ -0,0 +1,218 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::equilibration::Scaling;
+use crate::interior::{run_to_convergence, InteriorPointProblem, SolverOptions, StepStrategy};
+use crate::model::{SolverMethod, StartOptions};
+use crate::report::SolveReport;
+use crate::transportation::TransportationProblem;
+
+/// Iterations the interior-point relaxation may take before giving up.
+pub const RELAXATION_ITERATION_LIMIT: usize = 1000;
+
+/// Assign each row (worker) to at most one column (task) and vice versa at
+/// minimum total cost. Rectangular tables leave the surplus side unassigned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignmentProblem {
+    pub costs: DMatrix<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assignment {
+    /// Column assigned to each row, `None` for rows left over.
+    pub columns: Vec<Option<usize>>,
+    pub cost: f64,
+}
+
+/// The LP relaxation of the assignment problem, solved by affine scaling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Relaxation {
+    pub report: SolveReport,
+    /// x_ij for the original rows and columns.
+    pub flows: DMatrix<f64>,
+    pub cost: f64,
+    /// Largest distance of any x_ij from the nearest integer.
+    pub fractionality: f64,
+}
+
+impl AssignmentProblem {
+    /// Exact optimum by the Hungarian algorithm, O(n^3) via row and column
+    /// potentials u, v with c_ij - u_i - v_j >= 0 on every cell and equality
+    /// on the assigned ones.
+    pub fn hungarian(&self) -> Assignment {
+        let (rows, cols) = self.costs.shape();
+        let n = rows.max(cols);
+        // Zero-cost padding squares the table; padded cells mean "unassigned".
+        let cost = |i: usize, j: usize| {
+            if i < rows && j < cols {
+                self.costs[(i, j)]
+            } else {
+                0.0
+            }
+        };
+
+        // 1-based arrays; index 0 is the virtual column the augmenting path
+        // starts from. p[j] is the row matched to column j.
+        let mut u = vec![0.0; n + 1];
+        let mut v = vec![0.0; n + 1];
+        let mut p = vec![0usize; n + 1];
+        let mut way = vec![0usize; n + 1];
+        for i in 1..=n {
+            p[0] = i;
+            let mut j0 = 0;
+            let mut min_slack = vec![f64::INFINITY; n + 1];
+            let mut used = vec![false; n + 1];
+            loop {
+                used[j0] = true;
+                let i0 = p[j0];
+                let mut delta = f64::INFINITY;
+                let mut j1 = 0;
+                for j in 1..=n {
+                    if used[j] {
+                        continue;
+                    }
+                    let reduced = cost(i0 - 1, j - 1) - u[i0] - v[j];
+                    if reduced < min_slack[j] {
+                        min_slack[j] = reduced;
+                        way[j] = j0;
+                    }
+                    if min_slack[j] < delta {
+                        delta = min_slack[j];
+                        j1 = j;
+                    }
+                }
+                for j in 0..=n {
+                    if used[j] {
+                        u[p[j]] += delta;
+                        v[j] -= delta;
+                    } else {
+                        min_slack[j] -= delta;
+                    }
+                }
+                j0 = j1;
+                if p[j0] == 0 {
+                    break;
+                }
+            }
+            // Flip the augmenting path back to the virtual column.
+            loop {
+                let j1 = way[j0];
+                p[j0] = p[j1];
+                j0 = j1;
+                if j0 == 0 {
+                    break;
+                }
+            }
+        }
+
+        let mut columns = vec![None; rows];
+        for (j, &row) in p.iter().enumerate().skip(1) {
+            let (i, j) = (row - 1, j - 1);
+            if i < rows && j < cols {
+                columns[i] = Some(j);
+            }
+        }
+        let cost = columns
+            .iter()
+            .enumerate()
+            .filter_map(|(i, j)| j.map(|j| self.costs[(i, j)]))
+            .sum();
+        Assignment { columns, cost }
+    }
+
+    /// The same problem as a transportation table with unit supplies and
+    /// demands; its LP relaxation has an integral optimum.
+    pub fn to_transportation(&self) -> TransportationProblem {
+        TransportationProblem {
+            supply: vec![1.0; self.costs.nrows()],
+            demand: vec![1.0; self.costs.ncols()],
+            costs: self.costs.clone(),
+        }
+    }
+
+    /// Solves the LP relaxation 0 <= x_ij <= 1 with the affine-scaling
+    /// routine, starting from the uniform fractional assignment.
+    pub fn solve_relaxation(&self, alpha: f64, options: &SolverOptions) -> Relaxation {
+        let model = self.to_transportation().to_validated(
+            SolverMethod::AffineScaling,
+            StartOptions {
+                alpha,
+                mu: 1.0,
+                find_start: false,
+                step_strategy: StepStrategy::default(),
+                scaling: Scaling::default(),
+            },
+        );
+        let layout = model
+            .transportation
+            .clone()
+            .expect("transportation model carries its layout");
+        let mut problem = InteriorPointProblem::new(
+            model.a.clone(),
+            model.b.clone(),
+            model.internal_c(),
+            DVector::from_vec(model.initial.clone()),
+            alpha,
+            options.clone(),
+        )
+        .expect("transportation model has matching dimensions");
+        let (mut report, _) = run_to_convergence(&mut problem, RELAXATION_ITERATION_LIMIT, None);
+        report.negate_objective();
+
+        let (rows, cols) = self.costs.shape();
+        let flows = layout
+            .flows(&report.solution)
+            .view((0, 0), (rows, cols))
+            .into_owned();
+        let cost = self.costs.component_mul(&flows).sum();
+        let fractionality = flows
+            .iter()
+            .map(|x| (x - x.round()).abs())
+            .fold(0.0, f64::max);
+        Relaxation {
+            report,
+            flows,
+            cost,
+            fractionality,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::LogVerbosity;
+
+    #[test]
+    fn hungarian_finds_the_cheapest_matching() {
+        let problem = AssignmentProblem {
+            costs: DMatrix::from_row_slice(3, 3, &[4.0, 1.0, 3.0, 2.0, 0.0, 5.0, 3.0, 2.0, 2.0]),
+        };
+        let assignment = problem.hungarian();
+        assert_eq!(assignment.columns, vec![Some(1), Some(0), Some(2)]);
+        assert_eq!(assignment.cost, 5.0);
+    }
+
+    #[test]
+    fn rectangular_tables_leave_a_column_over() {
+        let problem = AssignmentProblem {
+            costs: DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 3.0, 2.0, 4.0, 6.0]),
+        };
+        let assignment = problem.hungarian();
+        assert_eq!(assignment.columns, vec![Some(1), Some(0)]);
+        assert_eq!(assignment.cost, 4.0);
+    }
+
+    #[test]
+    fn relaxation_matches_the_integral_optimum() {
+        let problem = AssignmentProblem {
+            costs: DMatrix::from_row_slice(3, 3, &[4.0, 1.0, 3.0, 2.0, 0.0, 5.0, 3.0, 2.0, 2.0]),
+        };
+        let options = SolverOptions {
+            log_verbosity: LogVerbosity::Off,
+            ..SolverOptions::default()
+        };
+        let relaxation = problem.solve_relaxation(0.5, &options);
+        assert!((relaxation.cost - 5.0).abs() < 1e-3, "{}", relaxation.cost);
+        assert!(relaxation.fractionality < 1e-2);
+    }
+}
@@ -0,0 +1,431 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::hsd::NEAR_OPTIMAL;
+use crate::interior::{
+    check_finite, estimate_duals, improving_ray, InteriorPointError, SolverOptions,
+};
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+use crate::solver::{starting_point, Solver};
+use crate::stopping::StopReason;
+use crate::theory::STOPPING_TOLERANCE;
+
+/// n mu stops shrinking at this fraction of the gap tolerance; on the
+/// central path the duality gap is n mu.
+const MU_FLOOR: f64 = 1e-2;
+
+/// Once mu sits at its floor, a step that leaves the worst residual above
+/// this fraction of its current value has stalled.
+const STALLED: f64 = 0.9;
+
+#[derive(Clone, PartialEq)]
+pub struct BarrierIteration {
+    pub mu: f64,
+    pub barrier_objective: f64,
+    pub gradient: DVector<f64>,
+    pub newton_direction: DVector<f64>,
+    pub step_length: f64,
+    pub current_x: DVector<f64>,
+    /// Newton step at fixed mu, moving towards the mu-center rather than
+    /// along the central path.
+    pub centering: bool,
+}
+
+impl fmt::Debug for BarrierIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.centering { " (centering)" } else { "" };
+        writeln!(
+            f,
+            "BarrierIteration{}: mu = {}, barrier objective = {}, step = {}",
+            kind,
+            pretty::entry(self.mu),
+            pretty::entry(self.barrier_objective),
+            pretty::entry(self.step_length)
+        )?;
+        writeln!(f, "{}", pretty::vector("gradient", &self.gradient))?;
+        writeln!(
+            f,
+            "{}",
+            pretty::vector("Newton direction", &self.newton_direction)
+        )?;
+        write!(f, "{}", pretty::vector("x", &self.current_x))
+    }
+}
+
+/// Log-barrier problem: maximize c^T x + mu * sum(ln x_i) subject to A x = b,
+/// with mu shrunk by `mu_decrease` after every Newton step so the iterates
+/// follow the central path towards the LP optimum.
+pub struct BarrierProblem {
+    pub a_matrix: DMatrix<f64>,
+    pub b_vector: DVector<f64>,
+    pub c_vector: DVector<f64>,
+    pub x_vector: DVector<f64>,
+    pub alpha: f64,
+    pub mu: f64,
+    pub mu_decrease: f64,
+    pub options: SolverOptions,
+    /// Newton steps taken, for naming the step that broke down.
+    pub iterations: usize,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
+}
+
+impl BarrierProblem {
+    /// The barrier method on `model` from the interior point `x`.
+    pub fn new(model: &ValidatedProblem, x: DVector<f64>, options: &SolverOptions) -> Self {
+        BarrierProblem {
+            a_matrix: model.a.clone(),
+            b_vector: model.b.clone(),
+            c_vector: model.internal_c(),
+            x_vector: x,
+            alpha: model.options.alpha,
+            mu: model.options.mu,
+            mu_decrease: 0.5,
+            options: options.clone(),
+            iterations: 0,
+            finished: false,
+        }
+    }
+
+    /// Report at the current iterate, with the duals the direction implies.
+    pub fn report(&self, status: SolveStatus, iterations: usize) -> SolveReport {
+        let mut report =
+            SolveReport::new(status, iterations, self.x_vector.clone(), &self.c_vector);
+        report.duals = estimate_duals(&self.a_matrix, &self.c_vector, &self.x_vector);
+        report.check_feasibility(&self.a_matrix, &self.b_vector, self.options.norm);
+        report
+    }
+}
+
+/// Tolerance on the relative residuals and duality gap: the stopping
+/// criteria's `duality_gap`, or `STOPPING_TOLERANCE` when unset.
+pub(crate) fn gap_tolerance(options: &SolverOptions) -> f64 {
+    options.stopping.duality_gap.unwrap_or(STOPPING_TOLERANCE)
+}
+
+/// Floor under mu at an iterate of n entries with objective `objective`:
+/// the mu-centers there pass the gap check, and a smaller mu only loses
+/// accuracy in the normal matrix.
+pub(crate) fn mu_floor(tolerance: f64, objective: f64, n: usize) -> f64 {
+    MU_FLOOR * tolerance * (1.0 + objective.abs()) / n as f64
+}
+
+/// Largest relative primal residual, dual infeasibility and duality gap of
+/// `x` and the dual estimate y for maximizing with gradient `g`, or `None`
+/// when A X^2 A^T has no Cholesky factor. y is dual feasible once
+/// A^T y >= g. Each row and column is measured against its own terms: with
+/// rows scaled 1e12 apart, a norm over all of them hides the small ones.
+pub(crate) fn relative_residuals(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    g: &DVector<f64>,
+    x: &DVector<f64>,
+) -> Option<(f64, f64, f64)> {
+    let y = estimate_duals(a, g, x)?;
+    let objective = g.dot(x);
+    let row_terms = a.abs() * x.abs() + b.abs();
+    let primal = (a * x - b)
+        .iter()
+        .zip(row_terms.iter())
+        .fold(0.0_f64, |worst, (r, t)| worst.max(r.abs() / (1.0 + t)));
+    let column_terms = a.abs().transpose() * y.abs() + g.abs();
+    let dual = (g - a.transpose() * &y)
+        .iter()
+        .zip(column_terms.iter())
+        .fold(0.0_f64, |worst, (r, t)| worst.max(r.max(0.0) / (1.0 + t)));
+    let gap = (b.dot(&y) - objective).abs() / (1.0 + objective.abs());
+    Some((primal, dual, gap))
+}
+
+/// Whether a step from an iterate whose worst residual is `current` to one
+/// whose worst is `next` has stalled short of the tolerance. With mu at its
+/// floor, badly scaled data can leave the residuals there for good: a
+/// nearly optimal x the step no longer improves is as close as the normal
+/// matrix gets.
+pub(crate) fn has_stalled(current: f64, next: Option<f64>) -> bool {
+    current <= NEAR_OPTIMAL && next.is_none_or(|next| next > STALLED * current)
+}
+
+/// The largest of the residuals `relative_residuals` returns.
+pub(crate) fn worst_of((primal, dual, gap): (f64, f64, f64)) -> f64 {
+    primal.max(dual).max(gap)
+}
+
+pub fn barrier_objective(c: &DVector<f64>, x: &DVector<f64>, mu: f64) -> f64 {
+    c.dot(x) + mu * x.iter().map(|&xi| xi.max(1e-8).ln()).sum::<f64>()
+}
+
+pub fn calculate_barrier_gradient(c: &DVector<f64>, x: &DVector<f64>, mu: f64) -> DVector<f64> {
+    DVector::from_iterator(
+        x.len(),
+        c.iter()
+            .zip(x.iter())
+            .map(|(&ci, &xi)| ci + mu / xi.max(1e-8)),
+    )
+}
+
+/// Newton direction for the barrier subproblem, keeping A (x + dx) = b.
+///
+/// With H = -mu X^{-2}, the KKT system reduces to
+/// (A X^2 A^T) y = A X^2 g - mu (b - A x) and dx = X^2 (g - A^T y) / mu.
+pub fn calculate_newton_direction(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    x: &DVector<f64>,
+    gradient: &DVector<f64>,
+    mu: f64,
+) -> Result<DVector<f64>, InteriorPointError> {
+    let d_squared = DMatrix::from_diagonal(&x.component_mul(x));
+
+    // Solved by LU rather than through the inverse, which with rows of A
+    // scaled far apart loses every digit and returns garbage y.
+    let normal = a * &d_squared * a.transpose();
+    let residual = b - a * x;
+    let y = normal
+        .lu()
+        .solve(&(a * &d_squared * gradient - residual * mu))
+        .ok_or_else(|| InteriorPointError::singular("Cannot invert (A X^2 A^T)"))?;
+
+    Ok(d_squared * (gradient - a.transpose() * y) / mu)
+}
+
+/// Largest step t in (0, 1] such that x + t * dx stays strictly positive,
+/// damped by alpha whenever the full Newton step would leave the orthant.
+pub fn calculate_step_length(x: &DVector<f64>, dx: &DVector<f64>, alpha: f64) -> f64 {
+    let mut max_step = f64::INFINITY;
+    for (&xi, &dxi) in x.iter().zip(dx.iter()) {
+        if dxi < 0.0 {
+            max_step = max_step.min(-xi / dxi);
+        }
+    }
+    if max_step.is_infinite() {
+        1.0
+    } else {
+        (alpha * max_step).min(1.0)
+    }
+}
+
+impl Solver for BarrierProblem {
+    type Iteration = BarrierIteration;
+    type Error = InteriorPointError;
+
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        Ok(BarrierProblem::new(
+            model,
+            starting_point(model, options)?,
+            options,
+        ))
+    }
+
+    fn step(&mut self) -> Result<BarrierIteration, InteriorPointError> {
+        let result = perform_barrier_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.x_vector.clone()
+    }
+}
+
+pub fn perform_barrier_iteration(
+    problem: &mut BarrierProblem,
+) -> Result<BarrierIteration, InteriorPointError> {
+    barrier_step(problem, false)
+}
+
+/// Newton step on the barrier subproblem for the current mu, leaving mu
+/// unchanged so the iterate moves towards the point on the central path.
+pub fn perform_barrier_centering_step(
+    problem: &mut BarrierProblem,
+) -> Result<BarrierIteration, InteriorPointError> {
+    barrier_step(problem, true)
+}
+
+fn barrier_step(
+    problem: &mut BarrierProblem,
+    centering: bool,
+) -> Result<BarrierIteration, InteriorPointError> {
+    if problem.options.logs_full() {
+        tracing::debug!(mu = problem.mu, "Barrier iteration start");
+        logging::dump_vector!("x", &problem.x_vector);
+    }
+
+    if let Some(column) = problem.x_vector.iter().position(|&xi| xi <= 0.0) {
+        return Err(InteriorPointError::NotInterior {
+            column,
+            value: problem.x_vector[column],
+        });
+    }
+
+    if problem
+        .options
+        .stopping
+        .out_of_iterations(problem.iterations)
+    {
+        return Err(InteriorPointError::Stopped(StopReason::IterationLimit));
+    }
+    // Off the central path n mu says nothing about the gap, so x and the
+    // dual estimate are measured directly.
+    let (a, b, c) = (&problem.a_matrix, &problem.b_vector, &problem.c_vector);
+    let tolerance = gap_tolerance(&problem.options);
+    let residuals = relative_residuals(a, b, c, &problem.x_vector);
+    if let Some((primal, dual, gap)) = residuals {
+        if worst_of((primal, dual, gap)) <= tolerance {
+            tracing::info!(
+                primal,
+                dual,
+                gap,
+                "Barrier converged: residuals {:.3e}, {:.3e}, gap {:.3e}",
+                primal,
+                dual,
+                gap
+            );
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+    }
+
+    let mu = problem.mu;
+    let floor = mu_floor(tolerance, c.dot(&problem.x_vector), problem.x_vector.len());
+
+    let gradient = calculate_barrier_gradient(c, &problem.x_vector, mu);
+    let dx = calculate_newton_direction(a, b, &problem.x_vector, &gradient, mu)?;
+    if let Some(ray) = improving_ray(a, c, &dx) {
+        tracing::warn!("The Newton direction is an improving ray: the objective is unbounded");
+        return Err(InteriorPointError::Unbounded(ray));
+    }
+
+    let step = calculate_step_length(&problem.x_vector, &dx, problem.alpha);
+    let new_x = &problem.x_vector + &dx * step;
+    check_finite(
+        problem.iterations,
+        &[("Newton direction", dx.as_slice()), ("x", new_x.as_slice())],
+    )?;
+
+    if let Some(current) = residuals.map(worst_of) {
+        let next = relative_residuals(a, b, c, &new_x).map(worst_of);
+        if mu * problem.mu_decrease <= floor && has_stalled(current, next) {
+            tracing::info!(
+                worst = current,
+                "Barrier stalled at mu = {:.3e}: worst residual {:.3e}",
+                mu,
+                current
+            );
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+    }
+
+    problem.x_vector = new_x.clone();
+    problem.iterations += 1;
+    if !centering {
+        problem.mu = (mu * problem.mu_decrease).max(floor.min(mu));
+    }
+
+    if problem.options.logs_full() {
+        logging::dump_vector!("Newton direction", &dx);
+        logging::dump_vector!("Updated x", &new_x);
+    }
+    if problem.options.logs_summary() {
+        tracing::info!(
+            step,
+            mu = problem.mu,
+            "Barrier step t = {:.4}, next mu = {:.3e}",
+            step,
+            problem.mu
+        );
+    }
+
+    Ok(BarrierIteration {
+        mu,
+        barrier_objective: barrier_objective(&problem.c_vector, &new_x, mu),
+        gradient,
+        newton_direction: dx,
+        step_length: step,
+        current_x: new_x,
+        centering,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::interior::LogVerbosity;
+    use crate::model::{ConstraintSign, SolverMethod};
+    use crate::stopping::StoppingCriteria;
+    use crate::test_models::{assert_optimum, covering, solve, wyndor};
+
+    #[test]
+    fn reaches_the_wyndor_optimum() {
+        let report = solve(&wyndor(), SolverMethod::LogBarrier);
+        assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-4);
+        let duals = report.duals.expect("the barrier estimates duals");
+        assert!((duals - nalgebra::DVector::from_vec(vec![0.0, 1.5, 1.0])).amax() < 1e-3);
+    }
+
+    #[test]
+    fn minimizes_over_big_m_artificials() {
+        let report = solve(&covering(), SolverMethod::LogBarrier);
+        assert_optimum(&report, 9.0, &[3.0, 1.0], 1e-4);
+    }
+
+    #[test]
+    fn keeps_going_until_the_gap_closes() {
+        // Damped steps leave the iterate off the central path, where n mu
+        // fell below 1e-8 at Z = 2.00048.
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 2.0])
+            .constraint(&[1.0, 1.0], ConstraintSign::GreaterEqual, 2.0)
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 5.0)
+            .minimize()
+            .model()
+            .unwrap();
+        let report = solve(&model, SolverMethod::LogBarrier);
+        assert_optimum(&report, 2.0, &[2.0, 0.0], 1e-6);
+    }
+
+    #[test]
+    fn reports_an_improving_ray_as_unbounded() {
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 1.0])
+            .constraint(&[1.0, -1.0], ConstraintSign::LessEqual, 1.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let report = solve(&model, SolverMethod::LogBarrier);
+        assert_eq!(
+            report.status,
+            SolveStatus::Unbounded,
+            "{:?}",
+            report.warnings
+        );
+    }
+
+    #[test]
+    fn honors_the_iteration_limit() {
+        let options = SolverOptions {
+            log_verbosity: LogVerbosity::Off,
+            stopping: StoppingCriteria {
+                max_iterations: Some(3),
+                ..StoppingCriteria::default()
+            },
+            ..SolverOptions::default()
+        };
+        let mut problem = BarrierProblem::init(&wyndor(), &options).unwrap();
+        for _ in 0..3 {
+            problem.step().unwrap();
+        }
+        assert!(matches!(
+            problem.step(),
+            Err(InteriorPointError::Stopped(StopReason::IterationLimit))
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+use crate::interior::{
+    run_to_convergence, InteriorPointError, InteriorPointIteration, InteriorPointProblem,
+    SolverOptions,
+};
+use crate::model::ValidatedProblem;
+use crate::report::SolveReport;
+
+// `solve_batch_parallel` hands problems to other threads and their reports
+// back; this stops the core types from picking up an `Rc` or a `RefCell`
+// unnoticed.
+const _: () = {
+    const fn send_sync<T: Send + Sync>() {}
+    send_sync::<ValidatedProblem>();
+    send_sync::<SolverOptions>();
+    send_sync::<InteriorPointProblem>();
+    send_sync::<InteriorPointIteration>();
+    send_sync::<InteriorPointError>();
+    send_sync::<SolveReport>();
+};
+
+/// One report per problem `solve_batch` ran, in the order they were given.
+/// Reports of a batch can be compared row by row; `summary` gives the
+/// figures for the batch as a whole.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchResults {
+    pub reports: Vec<SolveReport>,
+}
+
+/// Iteration and time statistics over a batch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchSummary {
+    pub problems: usize,
+    /// How many problems ended with each status, in order of first
+    /// appearance.
+    pub statuses: Vec<(String, usize)>,
+    pub mean_iterations: f64,
+    pub median_iterations: f64,
+    pub max_iterations: usize,
+    pub total_ms: f64,
+    /// Index of the problem that took longest.
+    pub slowest: Option<usize>,
+}
+
+/// Runs affine scaling on every problem from where it stands, each within
+/// its own iteration budget, one after the other. Objectives are c^T x of
+/// the problems as given, so a minimization set up on -c reports -Z.
+pub fn solve_batch(problems: Vec<InteriorPointProblem>) -> BatchResults {
+    BatchResults {
+        reports: problems.into_iter().map(solve_one).collect(),
+    }
+}
+
+/// `solve_batch` with the problems spread over every core, for answer keys
+/// of hundreds of problems. The reports come back in the order given.
+#[cfg(feature = "parallel")]
+pub fn solve_batch_parallel(problems: Vec<InteriorPointProblem>) -> BatchResults {
+    use rayon::prelude::*;
+
+    BatchResults {
+        reports: problems.into_par_iter().map(solve_one).collect(),
+    }
+}
+
+fn solve_one(mut problem: InteriorPointProblem) -> SolveReport {
+    let limit = problem.remaining_iterations();
+    run_to_convergence(&mut problem, limit, None).0
+}
+
+impl BatchResults {
+    pub fn summary(&self) -> BatchSummary {
+        let mut statuses: Vec<(String, usize)> = vec![];
+        for report in &self.reports {
+            let label = report.status.label();
+            match statuses.iter_mut().find(|(status, _)| status == label) {
+                Some((_, count)) => *count += 1,
+                None => statuses.push((label.to_string(), 1)),
+            }
+        }
+
+        let mut iterations: Vec<usize> = self.reports.iter().map(|r| r.iterations).collect();
+        iterations.sort_unstable();
+        let count = iterations.len();
+        let median_iterations = match count {
+            0 => 0.0,
+            _ if count % 2 == 1 => iterations[count / 2] as f64,
+            _ => (iterations[count / 2 - 1] + iterations[count / 2]) as f64 / 2.0,
+        };
+        let slowest = (0..count).max_by(|&i, &j| {
+            let time = |k: usize| self.reports[k].timings.total_ms;
+            time(i).total_cmp(&time(j))
+        });
+
+        BatchSummary {
+            problems: count,
+            statuses,
+            mean_iterations: iterations.iter().sum::<usize>() as f64 / count.max(1) as f64,
+            median_iterations,
+            max_iterations: iterations.last().copied().unwrap_or(0),
+            total_ms: self.reports.iter().map(|r| r.timings.total_ms).sum(),
+            slowest,
+        }
+    }
+}
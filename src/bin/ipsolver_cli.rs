@@ -0,0 +1,173 @@
+//! Solves one problem file from the command line, for scripts and grading.
+//!
+//!     ipsolver-cli [--iterations] [--method NAME] [--max-iterations N] FILE
+//!
+//! FILE holds one problem in the course-pack format, e.g. a minimal repro
+//! export. Prints the status, the objective, the original variables and
+//! any warnings of the report the app would show; `--iterations` first
+//! prints one line per step with the objective and x.
+//! Ctrl-C stops the run after the step under way and prints the report so
+//! far; a second Ctrl-C exits at once.
+//! Exits with 0 at an optimum, 1 when the run stopped without one and 2 when
+//! the arguments or the file are not usable.
+
+use std::process::ExitCode;
+use std::sync::OnceLock;
+
+use ipsolver::cancel::CancellationToken;
+use ipsolver::course_pack::{method_from_name, parse_pack_problem};
+use ipsolver::interior::{LogVerbosity, SolverOptions, SOLVE_ITERATION_LIMIT};
+use ipsolver::race::RaceSolver;
+use ipsolver::report::{SolveReport, SolveStatus};
+
+/// The token Ctrl-C cancels. The signal handler reads it, so it is set
+/// before the handler is installed and never changes after.
+static INTERRUPT: OnceLock<CancellationToken> = OnceLock::new();
+
+const USAGE: &str =
+    "usage: ipsolver-cli [--iterations] [--method NAME] [--max-iterations N] FILE";
+
+struct Args {
+    path: String,
+    iterations: bool,
+    method: Option<String>,
+    max_iterations: usize,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = std::env::args().skip(1);
+    let mut path = None;
+    let mut iterations = false;
+    let mut method = None;
+    let mut max_iterations = SOLVE_ITERATION_LIMIT;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--iterations" => iterations = true,
+            "--method" => method = Some(args.next().ok_or("--method needs a name")?),
+            "--max-iterations" => {
+                max_iterations = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .ok_or("--max-iterations needs a count")?
+            }
+            "-h" | "--help" => return Err(USAGE.to_string()),
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err("only one problem file can be given".to_string()),
+        }
+    }
+    Ok(Args {
+        path: path.ok_or(USAGE)?,
+        iterations,
+        method,
+        max_iterations,
+    })
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(SolveStatus::Optimal) => ExitCode::SUCCESS,
+        Ok(_) => ExitCode::from(1),
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<SolveStatus, String> {
+    let args = parse_args()?;
+    // Installed before the setup, which can itself take a while: a Ctrl-C
+    // during it stops the run before the first step.
+    let cancel = CancellationToken::new();
+    cancel_on_interrupt(&cancel);
+    let text = std::fs::read_to_string(&args.path)
+        .map_err(|e| format!("cannot read {}: {}", args.path, e))?;
+    let problem = parse_pack_problem(&text).map_err(|e| format!("{}: {}", args.path, e))?;
+    let mut model = problem.to_validated();
+    if let Some(name) = &args.method {
+        model.method = method_from_name(name).ok_or(format!("unknown method \"{}\"", name))?;
+        model.options.find_start &= model.method.is_interior();
+    }
+    let options = SolverOptions {
+        log_verbosity: LogVerbosity::Off,
+        ..SolverOptions::default()
+    };
+
+    let variables = model.augmentation.original_variables;
+    let labels = &model.variable_labels[..variables];
+    let mut solver = RaceSolver::new(model.method, &model, &options)?;
+    // The solvers maximize; report the objective in the file's sense.
+    let objective = |solver: &RaceSolver| model.sense.sign() * solver.objective();
+    let values = |solver: &RaceSolver| {
+        let x = solver.current_x();
+        x.iter()
+            .take(variables)
+            .map(|val| format!("{:.6}", val))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+
+    if args.iterations {
+        println!("# {}: k objective {}", model.method.label(), labels.join(" "));
+        println!("0 {:.6} {}", objective(&solver), values(&solver));
+    }
+    let mut steps = 0;
+    let status = loop {
+        if cancel.is_cancelled() {
+            break SolveStatus::Cancelled;
+        }
+        if steps == args.max_iterations {
+            break SolveStatus::IterationLimit;
+        }
+        if let Err(status) = solver.step() {
+            break status;
+        }
+        steps += 1;
+        if args.iterations {
+            println!("{} {:.6} {}", steps, objective(&solver), values(&solver));
+        }
+    };
+
+    let mut report = solver.report(status, steps);
+    report.complete(&model, &options);
+    print_report(&report, labels);
+    Ok(report.status)
+}
+
+/// Makes Ctrl-C cancel `token` instead of killing the process, so the run
+/// can still print where it got to. A second Ctrl-C exits with 130.
+fn cancel_on_interrupt(token: &CancellationToken) {
+    extern "C" fn on_interrupt(_signal: libc::c_int) {
+        match INTERRUPT.get() {
+            Some(token) if !token.is_cancelled() => token.cancel(),
+            // SAFETY: `_exit` is async-signal-safe.
+            _ => unsafe { libc::_exit(130) },
+        }
+    }
+    if INTERRUPT.set(token.clone()).is_ok() {
+        let handler = on_interrupt as extern "C" fn(libc::c_int);
+        // SAFETY: the handler only loads an initialized `OnceLock` and
+        // stores to an atomic, both async-signal-safe.
+        unsafe {
+            libc::signal(libc::SIGINT, handler as libc::sighandler_t);
+        }
+    }
+}
+
+/// Status, iterations, objective and the original variables of `report`,
+/// then its warnings.
+fn print_report(report: &SolveReport, labels: &[String]) {
+    match &report.status {
+        SolveStatus::Failed(reason) => println!("status: {} ({})", report.status.label(), reason),
+        status => println!("status: {}", status.label()),
+    }
+    println!("iterations: {}", report.iterations);
+    println!("objective: {:.6}", report.objective);
+    for (label, val) in labels.iter().zip(report.solution.iter()) {
+        println!("{} = {:.6}", label, val);
+    }
+    for warning in &report.warnings {
+        println!("warning: {}", warning);
+    }
+}
@@ -0,0 +1,8 @@
+use gloo_worker::Registrable;
+use ipsolver::logging;
+use ipsolver::worker::SolverWorker;
+
+fn main() {
+    logging::init();
+    SolverWorker::registrar().register();
+}
@@ -0,0 +1,357 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::equilibration::Scaling;
+use crate::interior::{InteriorPointProblem, SolverOptions, StepStrategy};
+use crate::model::{
+    big_m_form, default_variable_labels, ConstraintSign, Sense, SolverMethod, StartOptions,
+    ValidatedProblem,
+};
+use crate::solver::Solver;
+
+/// Assembles an LP row by row, for programs that would otherwise lay out A,
+/// b and c by hand:
+///
+/// ```
+/// use ipsolver::builder::InteriorPointProblemBuilder;
+/// use ipsolver::interior::{solve, SolverOptions};
+/// use ipsolver::model::ConstraintSign;
+///
+/// let model = InteriorPointProblemBuilder::new()
+///     .objective(&[3.0, 5.0])
+///     .constraint(&[1.0, 0.0], ConstraintSign::LessEqual, 4.0)
+///     .constraint(&[0.0, 2.0], ConstraintSign::LessEqual, 12.0)
+///     .constraint(&[3.0, 2.0], ConstraintSign::LessEqual, 18.0)
+///     .maximize()
+///     .model()
+///     .unwrap();
+/// let run = solve(&model, &SolverOptions::default()).unwrap();
+/// assert!((run.objective - 36.0).abs() < 1e-3);
+/// ```
+///
+/// Every variable is nonnegative; `bounds` tightens that. `model` checks
+/// the pieces fit together and adds the slack, surplus and artificial
+/// columns the way the form does.
+#[derive(Clone, Debug)]
+pub struct InteriorPointProblemBuilder {
+    objective: Vec<f64>,
+    sense: Sense,
+    constraints: Vec<(Vec<f64>, ConstraintSign, f64)>,
+    /// (variable, lower, upper) in the order they were given.
+    bounds: Vec<(usize, f64, f64)>,
+    variable_labels: Vec<String>,
+    start: Option<Vec<f64>>,
+    alpha: f64,
+    step_strategy: StepStrategy,
+    scaling: Scaling,
+    options: SolverOptions,
+}
+
+impl Default for InteriorPointProblemBuilder {
+    fn default() -> Self {
+        Self {
+            objective: vec![],
+            sense: Sense::Maximize,
+            constraints: vec![],
+            bounds: vec![],
+            variable_labels: vec![],
+            start: None,
+            alpha: 0.5,
+            step_strategy: StepStrategy::default(),
+            scaling: Scaling::default(),
+            options: SolverOptions::default(),
+        }
+    }
+}
+
+impl InteriorPointProblemBuilder {
+    /// An empty maximization with the form's defaults: α = 0.5, long steps,
+    /// no scaling and Phase 1 for the start.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Coefficients of c; their count fixes the number of variables.
+    pub fn objective(mut self, coefficients: &[f64]) -> Self {
+        self.objective = coefficients.to_vec();
+        self
+    }
+
+    pub fn maximize(mut self) -> Self {
+        self.sense = Sense::Maximize;
+        self
+    }
+
+    pub fn minimize(mut self) -> Self {
+        self.sense = Sense::Minimize;
+        self
+    }
+
+    /// Adds the row `coefficients · x (sign) rhs`.
+    pub fn constraint(mut self, coefficients: &[f64], sign: ConstraintSign, rhs: f64) -> Self {
+        self.constraints.push((coefficients.to_vec(), sign, rhs));
+        self
+    }
+
+    /// Keeps variable `variable` (from 0) within `lower..=upper`; pass
+    /// `f64::INFINITY` for no upper bound. Each finite bound beyond
+    /// x >= 0 becomes one more constraint row.
+    pub fn bounds(mut self, variable: usize, lower: f64, upper: f64) -> Self {
+        self.bounds.push((variable, lower, upper));
+        self
+    }
+
+    /// Names of the variables, x1, x2, ... by default.
+    pub fn variable_labels(mut self, labels: &[&str]) -> Self {
+        self.variable_labels = labels.iter().map(|label| label.to_string()).collect();
+        self
+    }
+
+    /// Starts affine scaling at `x` instead of the analytic center Phase 1
+    /// finds; the slack columns are set to match.
+    pub fn start(mut self, x: &[f64]) -> Self {
+        self.start = Some(x.to_vec());
+        self
+    }
+
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn step_strategy(mut self, strategy: StepStrategy) -> Self {
+        self.step_strategy = strategy;
+        self
+    }
+
+    pub fn scaling(mut self, scaling: Scaling) -> Self {
+        self.scaling = scaling;
+        self
+    }
+
+    /// Options `build` sets the run up with.
+    pub fn options(mut self, options: SolverOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// The validated, Big-M augmented model, for `interior::solve` or any
+    /// other method.
+    pub fn model(&self) -> Result<ValidatedProblem, String> {
+        let n = self.objective.len();
+        if n == 0 {
+            return Err("the objective has no coefficients".to_string());
+        }
+        let finite = |values: &[f64], what: &str| {
+            if values.iter().all(|val| val.is_finite()) {
+                Ok(())
+            } else {
+                Err(format!("{} must be finite numbers", what))
+            }
+        };
+        finite(&self.objective, "the objective coefficients")?;
+        if !(self.alpha > 0.0 && self.alpha <= 1.0) {
+            return Err("α must be in (0, 1]".to_string());
+        }
+
+        let mut rows = Vec::with_capacity(self.constraints.len() + self.bounds.len());
+        let mut constraint_labels = Vec::with_capacity(rows.capacity());
+        for (i, (coefficients, sign, rhs)) in self.constraints.iter().enumerate() {
+            let label = format!("C{}", i + 1);
+            if coefficients.len() != n {
+                return Err(format!(
+                    "{} has {} coefficients for {} variables",
+                    label,
+                    coefficients.len(),
+                    n
+                ));
+            }
+            finite(coefficients, &format!("the coefficients of {}", label))?;
+            finite(&[*rhs], &format!("the right-hand side of {}", label))?;
+            rows.push((coefficients.clone(), *sign, *rhs));
+            constraint_labels.push(label);
+        }
+
+        let mut variable_labels = self.variable_labels.clone();
+        if !variable_labels.is_empty() && variable_labels.len() != n {
+            return Err(format!(
+                "{} variable labels for {} variables",
+                variable_labels.len(),
+                n
+            ));
+        }
+        let name = |j: usize| {
+            self.variable_labels
+                .get(j)
+                .cloned()
+                .unwrap_or_else(|| format!("x{}", j + 1))
+        };
+        for &(j, lower, upper) in &self.bounds {
+            if j >= n {
+                return Err(format!(
+                    "bounds given for x{}, which is not a variable",
+                    j + 1
+                ));
+            }
+            if lower.is_nan() || upper.is_nan() || lower > upper {
+                return Err(format!("the bounds of {} are empty", name(j)));
+            }
+            if lower < 0.0 {
+                return Err(format!(
+                    "{} cannot go below 0: every variable is nonnegative",
+                    name(j)
+                ));
+            }
+            let unit = |j: usize| (0..n).map(|k| if k == j { 1.0 } else { 0.0 }).collect();
+            if lower > 0.0 {
+                rows.push((unit(j), ConstraintSign::GreaterEqual, lower));
+                constraint_labels.push(format!("{} lower bound", name(j)));
+            }
+            if upper.is_finite() {
+                rows.push((unit(j), ConstraintSign::LessEqual, upper));
+                constraint_labels.push(format!("{} upper bound", name(j)));
+            }
+        }
+        if rows.is_empty() {
+            return Err("the problem has no constraints".to_string());
+        }
+
+        let initial = match &self.start {
+            Some(x) if x.len() != n => {
+                return Err(format!(
+                    "the start has {} entries for {} variables",
+                    x.len(),
+                    n
+                ))
+            }
+            Some(x) if x.iter().any(|&val| !(val > 0.0 && val.is_finite())) => {
+                return Err("the start must be strictly positive".to_string())
+            }
+            Some(x) => x.clone(),
+            None => vec![1.0; n],
+        };
+
+        let m = rows.len();
+        let a = DMatrix::from_fn(m, n, |i, j| rows[i].0[j]);
+        let b = DVector::from_iterator(m, rows.iter().map(|row| row.2));
+        let signs: Vec<ConstraintSign> = rows.iter().map(|row| row.1).collect();
+        let form = big_m_form(
+            &a,
+            &b,
+            &DVector::from_column_slice(&self.objective),
+            &signs,
+            &initial,
+            self.sense,
+        );
+        let defaults = default_variable_labels(&form.augmentation, form.a.ncols());
+        if variable_labels.is_empty() {
+            variable_labels = defaults;
+        } else {
+            variable_labels.extend(defaults.into_iter().skip(n));
+        }
+        Ok(ValidatedProblem {
+            method: SolverMethod::AffineScaling,
+            sense: self.sense,
+            variable_labels,
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            quadratic: None,
+            initial: form.initial,
+            constraint_signs: signs,
+            integer_variables: vec![false; n],
+            constraint_labels,
+            constraint_groups: vec![None; m],
+            options: StartOptions {
+                alpha: self.alpha,
+                mu: 1.0,
+                find_start: self.start.is_none(),
+                step_strategy: self.step_strategy,
+                scaling: self.scaling,
+            },
+            is_augmented: false,
+            augmentation: form.augmentation,
+            transportation: None,
+            regression: None,
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        })
+    }
+
+    /// The model set up for affine scaling with `options`, ready to step.
+    /// Its c is in maximization form; use `model` with `interior::solve` for
+    /// answers in the sense the builder was given.
+    pub fn build(&self) -> Result<InteriorPointProblem, String> {
+        InteriorPointProblem::init(&self.model()?, &self.options)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_models::{assert_optimum, solve, wyndor};
+
+    #[test]
+    fn wyndor_from_rows_has_the_textbook_optimum() {
+        let model = wyndor();
+        assert_eq!(model.a.shape(), (3, 5));
+        assert_eq!(model.constraint_labels, ["C1", "C2", "C3"]);
+        assert_optimum(
+            &solve(&model, SolverMethod::Simplex),
+            36.0,
+            &[2.0, 6.0],
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn bounds_become_labelled_rows() {
+        // max x1 + x2 with 1 <= x1 <= 3, x2 <= 2: optimal at (3, 2).
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 1.0])
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 10.0)
+            .bounds(0, 1.0, 3.0)
+            .bounds(1, 0.0, 2.0)
+            .variable_labels(&["a", "b"])
+            .model()
+            .unwrap();
+        assert_eq!(
+            model.constraint_labels,
+            ["C1", "a lower bound", "a upper bound", "b upper bound"]
+        );
+        assert_optimum(
+            &solve(&model, SolverMethod::Simplex),
+            5.0,
+            &[3.0, 2.0],
+            1e-9,
+        );
+    }
+
+    #[test]
+    fn rejects_pieces_that_do_not_fit() {
+        let base = InteriorPointProblemBuilder::new().objective(&[1.0, 1.0]);
+        let short_row = base
+            .clone()
+            .constraint(&[1.0], ConstraintSign::LessEqual, 1.0)
+            .model();
+        assert_eq!(
+            short_row.unwrap_err(),
+            "C1 has 1 coefficients for 2 variables"
+        );
+        assert!(base.clone().model().is_err(), "no constraints");
+        let negative = base
+            .clone()
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 1.0)
+            .bounds(0, -1.0, 1.0)
+            .model();
+        assert!(negative.is_err());
+        let boundary_start = base
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 1.0)
+            .start(&[0.0, 0.5])
+            .model();
+        assert_eq!(
+            boundary_start.unwrap_err(),
+            "the start must be strictly positive"
+        );
+    }
+}
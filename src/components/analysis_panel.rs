@@ -0,0 +1,262 @@
+use nalgebra::DVector;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext, ProblemContext, SettingsContext};
+use super::exercise_view::ExercisePanel;
+use super::log_chart::LogChart;
+use super::path_plot::PathPlot;
+use super::sensitivity_view::SensitivityView;
+use crate::convergence::{error_curve, estimate_rate};
+use crate::dual_simplex::{basic_solution, optimal_basis};
+use crate::exercises;
+use crate::model::ValidatedProblem;
+use crate::multistart::{Agreement, MultiStartReport};
+
+/// A context the panel subscribes to changed.
+pub enum Msg {
+    Problem(ProblemContext),
+    Settings(SettingsContext),
+    History(HistoryContext),
+    RunMultiStart,
+}
+
+/// Dashboards built on the current run: sensitivity ranges, self-check
+/// exercises, the path and boundary plots, and the error against the
+/// exact optimum. Hidden while the run has an error.
+pub struct AnalysisPanel {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    history: HistoryContext,
+    /// x* of the current model from the simplex, in the space of the path;
+    /// `None` for models the simplex cannot solve exactly.
+    exact: Option<DVector<f64>>,
+    /// Runs from several starts, on request; cleared when the model changes.
+    multi_start: Option<MultiStartReport>,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+        ContextHandle<HistoryContext>,
+    ),
+}
+
+impl Component for AnalysisPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::Problem);
+        let (settings, settings_handle) = subscribe(ctx, Msg::Settings);
+        let (history, history_handle) = subscribe(ctx, Msg::History);
+        let exact = problem.model.as_deref().and_then(exact_optimum);
+        Self {
+            problem,
+            settings,
+            history,
+            exact,
+            multi_start: None,
+            _handles: (problem_handle, settings_handle, history_handle),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Problem(problem) => {
+                if problem.model != self.problem.model {
+                    self.exact = problem.model.as_deref().and_then(exact_optimum);
+                    self.multi_start = None;
+                }
+                self.problem = problem;
+            }
+            Msg::Settings(settings) => self.settings = settings,
+            Msg::History(history) => self.history = history,
+            Msg::RunMultiStart => {
+                self.multi_start = self
+                    .problem
+                    .model
+                    .as_deref()
+                    .and_then(|model| MultiStartReport::run(model, &self.settings.options));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.problem.error.is_some() {
+            return html! {};
+        }
+        html! {
+            <>
+                { self.render_sensitivity() }
+                { self.render_exercises() }
+                { self.render_path_plot() }
+                { self.render_boundary_chart() }
+                { self.render_error_curve() }
+                { self.render_multi_start(ctx) }
+            </>
+        }
+    }
+}
+
+impl AnalysisPanel {
+    fn render_sensitivity(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        match &report.sensitivity {
+            Some(sensitivity) => html! {
+                <SensitivityView
+                    sensitivity={sensitivity.clone()}
+                    variable_labels={model.variable_labels.clone()}
+                    constraint_labels={model.constraint_labels.clone()}
+                    presolved={model.presolve.is_some()}
+                    notation={self.settings.notation.clone()}
+                />
+            },
+            None => html! {},
+        }
+    }
+
+    fn render_exercises(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        html! { <ExercisePanel questions={exercises::generate(model, report)} /> }
+    }
+
+    /// The (x1, x2) plot, only for models with two decision variables.
+    fn render_path_plot(&self) -> Html {
+        let Some(model) = &self.problem.model else {
+            return html! {};
+        };
+        let path = &self.history.path;
+        if model.augmentation.original_variables != 2 || path.is_empty() {
+            return html! {};
+        }
+        let (a, b) = model.original_rows();
+        let active_rows = path
+            .last()
+            .map(|x| self.problem.active_rows(x))
+            .unwrap_or_default();
+        html! {
+            <PathPlot
+                a={a}
+                b={b}
+                constraint_labels={model.constraint_labels.clone()}
+                path={path.iter().map(|x| (x[0], x[1])).collect::<Vec<_>>()}
+                active_rows={active_rows}
+            />
+        }
+    }
+
+    /// min_i x_i across iterations; only interior methods stay off the boundary,
+    /// so simplex runs have no chart.
+    fn render_boundary_chart(&self) -> Html {
+        if !self.history.interior {
+            return html! {};
+        }
+        let distances: Vec<f64> = self.history.path.iter().map(|x| x.min()).collect();
+        html! {
+            <LogChart
+                title="Distance to boundary: min xᵢ per iteration (log scale)"
+                values={distances}
+            />
+        }
+    }
+
+    /// ‖x_k - x*‖ against the simplex optimum, for interior runs on linear
+    /// models, with the rate read off its tail.
+    fn render_error_curve(&self) -> Html {
+        let Some(exact) = &self.exact else {
+            return html! {};
+        };
+        if !self.history.interior || self.history.path.is_empty() {
+            return html! {};
+        }
+        let norm = self.settings.options.norm;
+        let errors = error_curve(&self.history.path, exact, norm);
+        if errors.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="error-curve">
+                <LogChart
+                    title={format!("Error to the exact optimum: {} per iteration (log scale)", norm.format("x_k − x*"))}
+                    values={errors.clone()}
+                />
+                <p class="race-hint">
+                    { format!("{}. x* is the simplex vertex; when the optimum is not unique the interior method heads for the middle of the optimal face and the error levels off.", estimate_rate(&errors).describe()) }
+                </p>
+            </div>
+        }
+    }
+
+    /// Affine scaling from the user's point, the analytic center and box
+    /// corners, for linear models.
+    fn render_multi_start(&self, ctx: &Context<Self>) -> Html {
+        let Some(model) = &self.problem.model else {
+            return html! {};
+        };
+        if model.quadratic.is_some() {
+            return html! {};
+        }
+        let variables = model.augmentation.original_variables;
+        html! {
+            <details class="race-panel multi-start-panel">
+                <summary>{ "Multi-start check" }</summary>
+                <p class="race-hint">
+                    { "Runs affine scaling from your starting point, the analytic center and the corners of a box around it, with the step rule and scaling of the form, and compares where the runs end." }
+                </p>
+                <button onclick={ctx.link().callback(|_| Msg::RunMultiStart)}>
+                    { if self.multi_start.is_some() { "Run again" } else { "Run from every start" } }
+                </button>
+                {
+                    match &self.multi_start {
+                        None => html! {},
+                        Some(report) => html! {
+                            <>
+                                <p class={if matches!(report.agreement, Agreement::Unique) { "race-hint" } else { "report-warning" }}>
+                                    { report.describe() }
+                                </p>
+                                <div class="race-history">
+                                    <table>
+                                        <thead>
+                                            <tr>
+                                                <th>{ "Start" }</th>
+                                                <th>{ "Status" }</th>
+                                                <th>{ "Iterations" }</th>
+                                                <th>{ "Objective" }</th>
+                                                <th>{ model.variable_labels.iter().take(variables).cloned().collect::<Vec<_>>().join(", ") }</th>
+                                            </tr>
+                                        </thead>
+                                        <tbody>
+                                            { for report.runs.iter().map(|run| html! {
+                                                <tr>
+                                                    <td>{ &run.label }</td>
+                                                    <td>{ run.status.label() }</td>
+                                                    <td>{ run.iterations }</td>
+                                                    <td>{ format!("{:.6}", run.objective) }</td>
+                                                    <td>{ run.x.iter().take(variables).map(|val| format!("{:.4}", val)).collect::<Vec<_>>().join(", ") }</td>
+                                                </tr>
+                                            }) }
+                                        </tbody>
+                                    </table>
+                                </div>
+                            </>
+                        },
+                    }
+                }
+            </details>
+        }
+    }
+}
+
+/// The optimal vertex of `model` from the revised simplex, for linear models
+/// without integer variables.
+fn exact_optimum(model: &ValidatedProblem) -> Option<DVector<f64>> {
+    if model.quadratic.is_some() || model.has_integer_variables() {
+        return None;
+    }
+    let basis = optimal_basis(model).ok()?;
+    basic_solution(&model.a, &model.b, &basis)
+}
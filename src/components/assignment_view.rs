@@ -0,0 +1,314 @@
+use nalgebra::DMatrix;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::assignment::{Assignment, AssignmentProblem, Relaxation};
+use crate::interior::SolverOptions;
+use crate::notation::Notation;
+use crate::report::now_ms;
+
+/// Largest table the panel offers.
+const MAX_SIDE: usize = 6;
+
+/// Relaxation costs within this of the Hungarian optimum count as equal.
+const COST_TOLERANCE: f64 = 1e-3;
+
+/// x_ij above this is drawn as (part of) an assignment.
+const FLOW_EPSILON: f64 = 1e-3;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub options: SolverOptions,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+}
+
+pub enum Msg {
+    SetRows(usize),
+    SetColumns(usize),
+    UpdateCost(usize, usize, f64),
+    UpdateAlpha(f64),
+    Solve,
+}
+
+/// Both answers to one assignment problem, with how long each took.
+struct Comparison {
+    hungarian: Assignment,
+    hungarian_ms: f64,
+    relaxation: Relaxation,
+    relaxation_ms: f64,
+}
+
+/// Assignment problems solved exactly by the Hungarian algorithm and, for
+/// comparison, as an LP relaxation by the interior-point routine.
+pub struct AssignmentPanel {
+    rows: usize,
+    columns: usize,
+    costs: Vec<Vec<f64>>,
+    alpha: f64,
+    result: Option<Comparison>,
+}
+
+impl Component for AssignmentPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            rows: 4,
+            columns: 4,
+            costs: vec![
+                vec![9.0, 2.0, 7.0, 8.0],
+                vec![6.0, 4.0, 3.0, 7.0],
+                vec![5.0, 8.0, 1.0, 8.0],
+                vec![7.0, 6.0, 9.0, 4.0],
+            ],
+            alpha: 0.9,
+            result: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetRows(count) => {
+                self.rows = count.clamp(1, MAX_SIDE);
+                self.resize();
+            }
+            Msg::SetColumns(count) => {
+                self.columns = count.clamp(1, MAX_SIDE);
+                self.resize();
+            }
+            Msg::UpdateCost(i, j, val) => {
+                if i >= self.costs.len() || j >= self.costs[i].len() {
+                    return false;
+                }
+                self.costs[i][j] = val;
+            }
+            Msg::UpdateAlpha(a) => {
+                self.alpha = a.clamp(0.0, 1.0);
+            }
+            Msg::Solve => {
+                let problem = AssignmentProblem {
+                    costs: DMatrix::from_fn(self.rows, self.columns, |i, j| self.costs[i][j]),
+                };
+                let start = now_ms();
+                let hungarian = problem.hungarian();
+                let hungarian_ms = now_ms() - start;
+                let start = now_ms();
+                let relaxation = problem.solve_relaxation(self.alpha, &ctx.props().options);
+                let relaxation_ms = now_ms() - start;
+                self.result = Some(Comparison {
+                    hungarian,
+                    hungarian_ms,
+                    relaxation,
+                    relaxation_ms,
+                });
+                return true;
+            }
+        }
+        // Any edit invalidates the last comparison.
+        self.result = None;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let solvable = self.alpha > 0.0 && self.costs.iter().flatten().all(|c| c.is_finite());
+        html! {
+            <div class="input-form assignment-panel">
+                <div class="size-selectors">
+                    <div>
+                        <label>{"Workers: "}
+                            <input
+                                type="number"
+                                min="1"
+                                max={MAX_SIDE.to_string()}
+                                value={self.rows.to_string()}
+                                oninput={link.callback(|e: InputEvent| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    Msg::SetRows(input.value().parse().unwrap_or(1))
+                                })}
+                            />
+                        </label>
+                    </div>
+                    <div>
+                        <label>{"Tasks: "}
+                            <input
+                                type="number"
+                                min="1"
+                                max={MAX_SIDE.to_string()}
+                                value={self.columns.to_string()}
+                                oninput={link.callback(|e: InputEvent| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    Msg::SetColumns(input.value().parse().unwrap_or(1))
+                                })}
+                            />
+                        </label>
+                    </div>
+                </div>
+
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Cost" }</th>
+                            { for (1..=self.columns).map(|j| html! { <th>{ format!("T{}", j) }</th> }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for (0..self.rows).map(|i| html! {
+                                <tr>
+                                    <th>{ format!("W{}", i + 1) }</th>
+                                    {
+                                        for (0..self.columns).map(|j| html! {
+                                            <td>
+                                                <input
+                                                    type="number"
+                                                    step="0.1"
+                                                    value={self.costs[i][j].to_string()}
+                                                    oninput={link.callback(move |e: InputEvent| {
+                                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                                        Msg::UpdateCost(i, j, input.value().parse().unwrap_or(0.0))
+                                                    })}
+                                                />
+                                            </td>
+                                        })
+                                    }
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+
+                <div class="alpha-selector">
+                    <label>{ format!("Step Size ({}) for the relaxation: ", ctx.props().notation.step()) }
+                        <input
+                            type="number"
+                            min="0"
+                            max="1"
+                            step="0.1"
+                            value={self.alpha.to_string()}
+                            oninput={link.callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::UpdateAlpha(input.value().parse().unwrap_or(0.9))
+                            })}
+                        />
+                    </label>
+                </div>
+
+                <button disabled={!solvable} onclick={link.callback(|_| Msg::Solve)}>
+                    { "Solve Both" }
+                </button>
+
+                { self.render_result(&ctx.props().notation) }
+            </div>
+        }
+    }
+}
+
+impl AssignmentPanel {
+    fn resize(&mut self) {
+        self.costs.resize(self.rows, vec![0.0; self.columns]);
+        for row in self.costs.iter_mut() {
+            row.resize(self.columns, 0.0);
+        }
+    }
+
+    fn render_result(&self, notation: &Notation) -> Html {
+        let Some(result) = &self.result else {
+            return html! {};
+        };
+        let hungarian = &result.hungarian;
+        let relaxation = &result.relaxation;
+        let gap = relaxation.cost - hungarian.cost;
+
+        let verdict = if gap.abs() > COST_TOLERANCE {
+            format!(
+                "The relaxation stopped {:.4} away from the optimum; try more iterations or a larger {}.",
+                gap,
+                notation.step()
+            )
+        } else if relaxation.fractionality > FLOW_EPSILON {
+            "Same optimal cost, but the interior point sits between several optimal assignments: with ties it converges towards the centre of the optimal face rather than a vertex.".to_string()
+        } else {
+            "Same optimal cost and an integral plan: the assignment polytope has only integral vertices, so the LP relaxation is exact.".to_string()
+        };
+
+        html! {
+            <div class="assignment-result">
+                <div class="matrix-container">
+                    <div class="matrix-box">
+                        <h4>{ "Hungarian algorithm" }</h4>
+                        <div class="barrier-summary">
+                            <span>{ format!("Cost = {:.4}", hungarian.cost) }</span>
+                            <span>{ format!("{:.2} ms", result.hungarian_ms) }</span>
+                        </div>
+                        <ul class="assignment-list">
+                            {
+                                for hungarian.columns.iter().enumerate().map(|(i, column)| html! {
+                                    <li>
+                                        {
+                                            match column {
+                                                Some(j) => format!("W{} → T{} ({})", i + 1, j + 1, self.costs[i][*j]),
+                                                None => format!("W{} unassigned", i + 1),
+                                            }
+                                        }
+                                    </li>
+                                })
+                            }
+                        </ul>
+                    </div>
+                    <div class="matrix-box">
+                        <h4>{ "LP relaxation (affine scaling)" }</h4>
+                        <div class="barrier-summary">
+                            <span>{ format!("Cost = {:.4}", relaxation.cost) }</span>
+                            <span>{ format!("{} iterations", relaxation.report.iterations) }</span>
+                            <span>{ format!("{:.2} ms", result.relaxation_ms) }</span>
+                            <span>{ relaxation.report.status.label() }</span>
+                        </div>
+                        { self.render_flows(relaxation, hungarian) }
+                    </div>
+                </div>
+                <p class="assignment-verdict">{ verdict }</p>
+            </div>
+        }
+    }
+
+    /// x_ij of the relaxation, with the Hungarian choices outlined.
+    fn render_flows(&self, relaxation: &Relaxation, hungarian: &Assignment) -> Html {
+        let (rows, columns) = relaxation.flows.shape();
+        html! {
+            <table class="transportation-table assignment-flows">
+                <thead>
+                    <tr>
+                        <th></th>
+                        { for (1..=columns).map(|j| html! { <th>{ format!("T{}", j) }</th> }) }
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        for (0..rows).map(|i| html! {
+                            <tr>
+                                <th>{ format!("W{}", i + 1) }</th>
+                                {
+                                    for (0..columns).map(|j| {
+                                        let x = relaxation.flows[(i, j)];
+                                        let mut class = classes!();
+                                        if hungarian.columns[i] == Some(j) {
+                                            class.push("hungarian-choice");
+                                        }
+                                        if x > FLOW_EPSILON {
+                                            class.push("flow-used");
+                                        }
+                                        html! { <td class={class}>{ format!("{:.3}", x) }</td> }
+                                    })
+                                }
+                            </tr>
+                        })
+                    }
+                </tbody>
+            </table>
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use nalgebra::DVector;
+use yew::prelude::*;
+
+use crate::model::{Augmentation, ConstraintCheck, ConstraintSign};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub augmentation: Augmentation,
+    pub variable_labels: Vec<String>,
+    pub constraint_labels: Vec<String>,
+    pub constraint_signs: Vec<ConstraintSign>,
+    /// The optimum in the augmented space, slacks and artificials included.
+    pub solution: DVector<f64>,
+    pub checks: Vec<ConstraintCheck>,
+}
+
+/// The solution of an auto-augmented model side by side: every column the
+/// solver saw on the left, and on the right the user's variables with each
+/// original inequality evaluated, so it is plain that the slacks did their
+/// job and the constraints as typed hold.
+pub struct AugmentedComparisonView;
+
+impl Component for AugmentedComparisonView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let violated = props.checks.iter().filter(|check| !check.holds).count();
+
+        html! {
+            <div class="augmented-comparison">
+                <h3>{ "Augmented vs Original" }</h3>
+                <div class="augmented-columns">
+                    <div class="matrix-box">
+                        <h4>{ "Augmented space" }</h4>
+                        { self.render_augmented(props) }
+                    </div>
+                    <div class="matrix-box">
+                        <h4>{ "Original variables" }</h4>
+                        { self.render_original(props) }
+                    </div>
+                </div>
+                {
+                    if violated == 0 {
+                        html! { <p class="augmented-verdict holds">{ format!("All {} original constraints hold.", props.checks.len()) }</p> }
+                    } else {
+                        html! {
+                            <p class="augmented-verdict violated">
+                                { format!("{} of {} original constraints are violated: the augmented optimum does not carry back to the model as entered.", violated, props.checks.len()) }
+                            </p>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl AugmentedComparisonView {
+    /// Each solver column with its value and what augmentation added it for.
+    fn render_augmented(&self, props: &Props) -> Html {
+        let row_label = |row: usize| {
+            props
+                .constraint_labels
+                .get(row)
+                .cloned()
+                .unwrap_or_else(|| format!("Row {}", row + 1))
+        };
+        let role = |col: usize| {
+            if col < props.augmentation.original_variables {
+                return "decision".to_string();
+            }
+            if let Some(&(row, _)) = props
+                .augmentation
+                .slack_columns
+                .iter()
+                .find(|&&(_, slack)| slack == col)
+            {
+                // A row negated for its right-hand side swaps <= and >=.
+                let negated = props
+                    .augmentation
+                    .row_multipliers
+                    .get(row)
+                    .is_some_and(|&m| m < 0.0);
+                let greater =
+                    props.constraint_signs.get(row) == Some(&ConstraintSign::GreaterEqual);
+                let kind = if greater != negated {
+                    "surplus"
+                } else {
+                    "slack"
+                };
+                return format!("{} of {}", kind, row_label(row));
+            }
+            match props
+                .augmentation
+                .artificial_columns
+                .iter()
+                .find(|&&(_, artificial)| artificial == col)
+            {
+                Some(&(row, _)) => format!("artificial of {}", row_label(row)),
+                None => String::new(),
+            }
+        };
+        html! {
+            <table class="transportation-table">
+                <thead>
+                    <tr>
+                        <th>{ "Variable" }</th>
+                        <th>{ "Value" }</th>
+                        <th>{ "Role" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        for props.solution.iter().enumerate().map(|(j, value)| html! {
+                            <tr class={classes!((j >= props.augmentation.original_variables).then_some("augmented-added"))}>
+                                <th>{ props.variable_labels.get(j).cloned().unwrap_or_else(|| format!("x{}", j + 1)) }</th>
+                                <td>{ format!("{:.4}", value) }</td>
+                                <td>{ role(j) }</td>
+                            </tr>
+                        })
+                    }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// The user's variables, then every original row as LHS (sign) RHS.
+    fn render_original(&self, props: &Props) -> Html {
+        let original = props
+            .augmentation
+            .original_variables
+            .min(props.solution.len());
+        html! {
+            <>
+                <table class="transportation-table">
+                    <tbody>
+                        {
+                            for (0..original).map(|j| html! {
+                                <tr>
+                                    <th>{ props.variable_labels.get(j).cloned().unwrap_or_else(|| format!("x{}", j + 1)) }</th>
+                                    <td>{ format!("{:.4}", props.solution[j]) }</td>
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Constraint" }</th>
+                            <th>{ "a x" }</th>
+                            <th />
+                            <th>{ "b" }</th>
+                            <th>{ "Slack" }</th>
+                            <th />
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for props.checks.iter().map(|check| html! {
+                                <tr class={if check.holds { "constraint-holds" } else { "constraint-violated" }}>
+                                    <th>{ &check.label }</th>
+                                    <td>{ format!("{:.4}", check.lhs) }</td>
+                                    <td>{ check.sign.symbol() }</td>
+                                    <td>{ format!("{:.4}", check.rhs) }</td>
+                                    <td>
+                                        {
+                                            check
+                                                .slack_column
+                                                .and_then(|col| props.solution.get(col))
+                                                .map_or_else(|| "—".to_string(), |slack| format!("{:.4}", slack))
+                                        }
+                                    </td>
+                                    <td>{ if check.holds { "✓" } else { "✗" } }</td>
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+            </>
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::barrier::BarrierIteration;
+use crate::notation::Notation;
+use yew::prelude::*;
+
+use super::interior_view::{centering_tag, InteriorPointView};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub iteration: usize,
+
+    #[prop_or_default]
+    pub iteration_data: Option<BarrierIteration>,
+
+    #[prop_or_default]
+    pub active_rows: Vec<usize>,
+
+    #[prop_or_default]
+    pub constraint_labels: Vec<String>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+pub struct BarrierView;
+
+impl Component for BarrierView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let it = props.iteration_data.as_ref();
+
+        let gradient = it.map(|iter| &iter.gradient);
+        let newton_direction = it.map(|iter| &iter.newton_direction);
+        let current_x = it.map(|iter| &iter.current_x);
+        let mu = props.notation.barrier();
+        let compact = props.compact;
+
+        html! {
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Iteration {}", props.iteration) }{ centering_tag(it.is_some_and(|iter| iter.centering)) }</h3>
+
+                { InteriorPointView::render_active(&props.active_rows, &props.constraint_labels) }
+
+                {
+                    if let Some(iter) = it {
+                        html! {
+                            <div class="barrier-summary">
+                                <span>{ format!("{} = {:.6}", mu, iter.mu) }</span>
+                                <span>{ format!("c^T x + {} Σ ln x = {:.4}", mu, iter.barrier_objective) }</span>
+                                <span>{ format!("step t = {:.4}", iter.step_length) }</span>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <div class="matrix-container">
+                    { InteriorPointView::vector_card(&format!("g = c + {} X^{{-1}} 1", mu), gradient, compact) }
+                    { InteriorPointView::vector_card("Newton direction Δx", newton_direction, compact) }
+                    { InteriorPointView::vector_card(&format!("Current {}", props.notation.iterate(props.iteration)), current_x, compact) }
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use gloo_file::callbacks::{read_as_text, FileReader};
+use web_sys::HtmlInputElement;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, SettingsContext};
+use crate::batch::{solve_batch, BatchSummary};
+use crate::course_pack::parse_course_pack;
+use crate::interior::InteriorPointProblem;
+use crate::report::SolveReport;
+use crate::solver::Solver;
+
+pub enum Msg {
+    SettingsChanged(SettingsContext),
+    LoadFile(web_sys::File),
+    Loaded(Result<String, String>),
+    Clear,
+}
+
+/// A problem of the batch and how its run went; `Err` when it could not be
+/// set up for affine scaling.
+struct BatchRow {
+    name: String,
+    outcome: Result<SolveReport, String>,
+}
+
+/// Solves every problem of an uploaded course pack by affine scaling with
+/// the current settings and tabulates the outcomes with summary statistics.
+pub struct BatchPanel {
+    settings: SettingsContext,
+    _settings_handle: ContextHandle<SettingsContext>,
+    rows: Vec<BatchRow>,
+    summary: Option<BatchSummary>,
+    error: Option<String>,
+    /// Keeps a file read in flight alive.
+    _reader: Option<FileReader>,
+}
+
+impl Component for BatchPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (settings, settings_handle) = subscribe(ctx, Msg::SettingsChanged);
+        Self {
+            settings,
+            _settings_handle: settings_handle,
+            rows: vec![],
+            summary: None,
+            error: None,
+            _reader: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SettingsChanged(settings) => {
+                self.settings = settings;
+                return false;
+            }
+            Msg::LoadFile(file) => {
+                let link = ctx.link().clone();
+                self._reader = Some(read_as_text(&file.into(), move |result| {
+                    link.send_message(Msg::Loaded(result.map_err(|e| e.to_string())))
+                }));
+                return false;
+            }
+            Msg::Loaded(result) => {
+                self._reader = None;
+                match result {
+                    Ok(text) => self.run(&text),
+                    Err(e) => self.error = Some(format!("Could not read the file: {}", e)),
+                }
+            }
+            Msg::Clear => {
+                self.rows.clear();
+                self.summary = None;
+                self.error = None;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="race-panel batch-panel">
+                <h3>{ "Batch Solve" }</h3>
+                <p class="race-hint">
+                    { "Load a course pack to solve all of its problems by affine scaling with the current settings." }
+                </p>
+                <div class="race-controls">
+                    <input
+                        type="file"
+                        accept=".json,application/json"
+                        onchange={link.batch_callback(|e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            input.files().and_then(|files| files.get(0)).map(Msg::LoadFile)
+                        })}
+                    />
+                    <button disabled={self.rows.is_empty() && self.error.is_none()} onclick={link.callback(|_| Msg::Clear)}>
+                        { "Clear" }
+                    </button>
+                </div>
+                {
+                    if let Some(error) = &self.error {
+                        html! { <p class="report-warning">{ error }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                { self.render_summary() }
+                { self.render_table() }
+            </div>
+        }
+    }
+}
+
+impl BatchPanel {
+    /// Sets up every problem of the pack in `text` and solves the ones that
+    /// could be.
+    fn run(&mut self, text: &str) {
+        self.rows.clear();
+        self.summary = None;
+        let pack = match parse_course_pack(text) {
+            Ok(pack) => pack,
+            Err(e) => {
+                self.error = Some(e);
+                return;
+            }
+        };
+        self.error = None;
+
+        let mut problems = vec![];
+        let mut setups = Vec::with_capacity(pack.problems.len());
+        for problem in &pack.problems {
+            let model = problem.to_validated();
+            let setup = InteriorPointProblem::init(&model, &self.settings.options).map(|run| {
+                problems.push(run);
+                !model.sense.is_maximize()
+            });
+            setups.push((problem.name.clone(), setup));
+        }
+
+        let results = solve_batch(problems);
+        self.summary = Some(results.summary());
+        let mut reports = results.reports.into_iter();
+        self.rows = setups
+            .into_iter()
+            .map(|(name, setup)| {
+                let outcome = setup.map(|minimize| {
+                    let mut report = reports.next().expect("one report per problem");
+                    // The solvers maximize; show Z in the problem's own sense.
+                    if minimize {
+                        report.negate_objective();
+                    }
+                    report
+                });
+                BatchRow { name, outcome }
+            })
+            .collect();
+    }
+
+    fn render_summary(&self) -> Html {
+        let Some(summary) = &self.summary else {
+            return html! {};
+        };
+        let skipped = self.rows.iter().filter(|row| row.outcome.is_err()).count();
+        let statuses = summary
+            .statuses
+            .iter()
+            .map(|(status, count)| format!("{} {}", count, status))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let slowest = summary
+            .slowest
+            .and_then(|k| self.rows.iter().filter(|row| row.outcome.is_ok()).nth(k))
+            .map_or("—".to_string(), |row| row.name.clone());
+        html! {
+            <div class="barrier-summary batch-summary">
+                <span>{ format!("{} solved", summary.problems) }</span>
+                {
+                    if skipped > 0 {
+                        html! { <span>{ format!("{} not set up", skipped) }</span> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <span>{ statuses }</span>
+                <span>{ format!("iterations: mean {:.1}, median {:.1}, max {}", summary.mean_iterations, summary.median_iterations, summary.max_iterations) }</span>
+                <span>{ format!("{:.2} ms in total, slowest: {}", summary.total_ms, slowest) }</span>
+            </div>
+        }
+    }
+
+    fn render_table(&self) -> Html {
+        if self.rows.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="race-history">
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "Problem" }</th>
+                            <th>{ "Status" }</th>
+                            <th>{ "Iterations" }</th>
+                            <th>{ "Z" }</th>
+                            <th>{ "‖Ax − b‖" }</th>
+                            <th>{ "ms" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        for self.rows.iter().map(|row| match &row.outcome {
+                            Ok(report) => html! {
+                                <tr>
+                                    <td>{ &row.name }</td>
+                                    <td>{ report.status.label() }</td>
+                                    <td>{ report.iterations }</td>
+                                    <td>{ format!("{:.6}", report.objective) }</td>
+                                    <td>{ report.residual.map_or("—".to_string(), |(_, residual)| format!("{:.2e}", residual)) }</td>
+                                    <td>{ format!("{:.2}", report.timings.total_ms) }</td>
+                                </tr>
+                            },
+                            Err(e) => html! {
+                                <tr class="expected-off">
+                                    <td>{ &row.name }</td>
+                                    <td colspan="5">{ format!("Not set up: {}", e) }</td>
+                                </tr>
+                            },
+                        })
+                    }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use nalgebra::DVector;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use crate::interior::{LogVerbosity, SolverOptions};
+use crate::model::{SolverMethod, ValidatedProblem, ACTIVE_SLACK_THRESHOLD};
+use crate::norm::Norm;
+use crate::notation::Notation;
+use crate::palette::Palette;
+use crate::precision::Precision;
+use crate::report::{SolveReport, SolveStatus, Timings};
+use crate::schedule::AlphaSchedule;
+use crate::stopping::StoppingCriteria;
+
+/// The submitted model and how its run ended. Shared through `Rc`, so
+/// providing it again on every render costs a pointer copy; consumers are
+/// only notified when the contents differ.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ProblemContext {
+    pub model: Option<Rc<ValidatedProblem>>,
+    pub report: Option<Rc<SolveReport>>,
+    /// Set when the run failed; panels that show results hide themselves.
+    pub error: Option<String>,
+}
+
+impl ProblemContext {
+    /// Sense of the model being solved; a fresh app reports as maximization.
+    pub fn maximize(&self) -> bool {
+        self.model
+            .as_ref()
+            .is_none_or(|model| model.sense.is_maximize())
+    }
+
+    pub fn constraint_labels(&self) -> Vec<String> {
+        self.model
+            .as_ref()
+            .map(|model| model.constraint_labels.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn constraint_groups(&self) -> Vec<Option<String>> {
+        self.model
+            .as_ref()
+            .map(|model| model.constraint_groups.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn active_rows(&self, x: &DVector<f64>) -> Vec<usize> {
+        self.model
+            .as_ref()
+            .map(|model| model.active_constraints(x, ACTIVE_SLACK_THRESHOLD))
+            .unwrap_or_default()
+    }
+}
+
+/// Display and logging preferences. The app owns them; consumers send
+/// edits back through `on_change`.
+#[derive(Clone, PartialEq)]
+pub struct SettingsContext {
+    pub options: SolverOptions,
+    /// Symbols the headers use.
+    pub notation: Notation,
+    /// Small-screen iteration cards.
+    pub compact: bool,
+    pub palette: Palette,
+    /// Whether each new step is also read aloud.
+    pub narrate: bool,
+    pub on_change: Callback<SettingChange>,
+}
+
+pub enum SettingChange {
+    LogVerbosity(LogVerbosity),
+    Precision(Precision),
+    HideProjection(bool),
+    CrossCheck(bool),
+    ScaleFloor(f64),
+    NormalShift(f64),
+    Stopping(StoppingCriteria),
+    Schedule(AlphaSchedule),
+    Norm(Norm),
+    Notation(Notation),
+    Narration(bool),
+    Compact(bool),
+    Palette(Palette),
+}
+
+/// Iterates of the active run, for plots and anything else that follows the
+/// path rather than the per-step matrices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryContext {
+    /// Standard-form x after each step, oldest first.
+    pub path: Rc<Vec<DVector<f64>>>,
+    /// Whether the run uses an interior method, whose iterates stay off the
+    /// boundary.
+    pub interior: bool,
+    pub done: bool,
+    /// Solver time of the run so far.
+    pub timings: Timings,
+    /// Runs finished this session, oldest first.
+    pub runs: Rc<Vec<RunRecord>>,
+    /// Notes attached to iterations of the active run, by index.
+    pub notes: Rc<BTreeMap<usize, String>>,
+}
+
+/// Summary of a finished run for the session history.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunRecord {
+    pub method: SolverMethod,
+    /// Constraints and variables of the model as entered.
+    pub constraints: usize,
+    pub variables: usize,
+    pub status: SolveStatus,
+    /// In the model's own sense, after presolve is undone.
+    pub objective: f64,
+    pub iterations: usize,
+    pub total_ms: f64,
+    /// Notes attached to iterations of the run, by index.
+    pub notes: Vec<(usize, String)>,
+}
+
+impl RunRecord {
+    pub fn new(model: &ValidatedProblem, report: &SolveReport) -> Self {
+        Self {
+            method: model.method,
+            constraints: model.constraint_labels.len(),
+            variables: model.augmentation.original_variables,
+            status: report.status.clone(),
+            objective: report.objective,
+            iterations: report.iterations,
+            total_ms: report.timings.total_ms,
+            notes: vec![],
+        }
+    }
+}
+
+/// Reads a context `T` and subscribes to it, sending `on_change` whenever the
+/// provider's value changes. The handle keeps the subscription alive, so the
+/// component stores it next to the value.
+///
+/// Panics outside the provider: every consumer is rendered inside `App`.
+pub fn subscribe<COMP, T>(
+    ctx: &Context<COMP>,
+    on_change: impl Fn(T) -> COMP::Message + 'static,
+) -> (T, ContextHandle<T>)
+where
+    COMP: Component,
+    T: Clone + PartialEq + 'static,
+{
+    ctx.link()
+        .context::<T>(ctx.link().callback(on_change))
+        .unwrap_or_else(|| panic!("{} is not provided", std::any::type_name::<T>()))
+}
@@ -0,0 +1,157 @@
+use web_sys::HtmlTextAreaElement;
+use yew::prelude::*;
+
+use crate::course_pack::{parse_course_pack, CoursePack};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub pack: Option<CoursePack>,
+    /// Names of the pack's problems solved in this browser.
+    pub completed: Vec<String>,
+    /// Index of the problem loaded into the form.
+    pub active: Option<usize>,
+    /// The parsed pack together with the JSON it came from.
+    pub on_import: Callback<(CoursePack, String)>,
+    pub on_select: Callback<usize>,
+    pub on_remove: Callback<()>,
+}
+
+pub enum Msg {
+    SetText(String),
+    Import,
+}
+
+/// Sidebar listing the problems of an imported course pack, with the
+/// instructor's notes for the selected one and a tick for each completed one.
+pub struct CoursePackPanel {
+    text: String,
+    error: Option<String>,
+}
+
+impl Component for CoursePackPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            text: String::new(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetText(text) => {
+                self.text = text;
+                self.error = None;
+            }
+            Msg::Import => match parse_course_pack(&self.text) {
+                Ok(pack) => {
+                    ctx.props()
+                        .on_import
+                        .emit((pack, std::mem::take(&mut self.text)));
+                }
+                Err(e) => self.error = Some(e),
+            },
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let link = ctx.link();
+
+        html! {
+            <aside class="course-pack-sidebar">
+                {
+                    if let Some(pack) = &props.pack {
+                        Self::render_pack(ctx, pack)
+                    } else {
+                        html! {
+                            <>
+                                <h3>{ "Course Pack" }</h3>
+                                <p class="race-hint">{ "Paste a course pack from your instructor to get its problems listed here." }</p>
+                            </>
+                        }
+                    }
+                }
+                <details open={props.pack.is_none()}>
+                    <summary>{ if props.pack.is_some() { "Import another pack" } else { "Import JSON" } }</summary>
+                    <textarea
+                        rows="6"
+                        placeholder="{ \"title\": ..., \"problems\": [ ... ] }"
+                        value={self.text.clone()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlTextAreaElement = e.target_unchecked_into();
+                            Msg::SetText(input.value())
+                        })}
+                    />
+                    <button disabled={self.text.trim().is_empty()} onclick={link.callback(|_| Msg::Import)}>
+                        { "Import" }
+                    </button>
+                    {
+                        if let Some(error) = &self.error {
+                            html! { <p class="report-warning">{ error }</p> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </details>
+            </aside>
+        }
+    }
+}
+
+impl CoursePackPanel {
+    fn render_pack(ctx: &Context<Self>, pack: &CoursePack) -> Html {
+        let props = ctx.props();
+        let done = pack
+            .problems
+            .iter()
+            .filter(|problem| props.completed.contains(&problem.name))
+            .count();
+        let active = props.active.and_then(|k| pack.problems.get(k));
+        let on_remove = props.on_remove.reform(|_| ());
+
+        html! {
+            <>
+                <h3>{ &pack.title }</h3>
+                <p class="course-pack-progress">
+                    { format!("{} of {} completed", done, pack.problems.len()) }
+                </p>
+                <ul class="course-pack-list">
+                    {
+                        for pack.problems.iter().enumerate().map(|(k, problem)| {
+                            let completed = props.completed.contains(&problem.name);
+                            let mut class = classes!("course-pack-item");
+                            if props.active == Some(k) {
+                                class.push("active");
+                            }
+                            if completed {
+                                class.push("completed");
+                            }
+                            html! {
+                                <li class={class} onclick={props.on_select.reform(move |_| k)}>
+                                    <span class="course-pack-check">{ if completed { "✓" } else { "" } }</span>
+                                    { &problem.name }
+                                </li>
+                            }
+                        })
+                    }
+                </ul>
+                {
+                    match active {
+                        Some(problem) if !problem.notes.is_empty() => html! {
+                            <div class="course-pack-notes">
+                                <h4>{ &problem.name }</h4>
+                                <p>{ &problem.notes }</p>
+                            </div>
+                        },
+                        _ => html! {},
+                    }
+                }
+                <button class="course-pack-remove" onclick={on_remove}>{ "Remove pack" }</button>
+            </>
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use nalgebra::DVector;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext, ProblemContext, SettingsContext};
+use super::export_menu::ExportMenu;
+use super::log_chart::LogChart;
+use super::sensitivity_view::SensitivityView;
+use crate::model::{ValidatedProblem, ACTIVE_SLACK_THRESHOLD};
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+
+/// A context the dashboard subscribes to changed.
+pub enum Msg {
+    Problem(ProblemContext),
+    Settings(SettingsContext),
+    History(HistoryContext),
+}
+
+/// Everything about an optimal run on one page: the solution and duals,
+/// which constraints are active, the sensitivity ranges, how the run
+/// converged and the downloads. The app offers it once a run is optimal,
+/// so nobody has to scroll back past the iteration cards.
+pub struct AnalysisDashboard {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    history: HistoryContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+        ContextHandle<HistoryContext>,
+    ),
+}
+
+impl Component for AnalysisDashboard {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::Problem);
+        let (settings, settings_handle) = subscribe(ctx, Msg::Settings);
+        let (history, history_handle) = subscribe(ctx, Msg::History);
+        Self {
+            problem,
+            settings,
+            history,
+            _handles: (problem_handle, settings_handle, history_handle),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Problem(problem) => self.problem = problem,
+            Msg::Settings(settings) => self.settings = settings,
+            Msg::History(history) => self.history = history,
+        }
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return self.render_empty();
+        };
+        if self.problem.error.is_some() || report.status != SolveStatus::Optimal {
+            return self.render_empty();
+        }
+        html! {
+            <div class="analysis-dashboard">
+                <h3>{ "Analysis Dashboard" }</h3>
+                <div class="barrier-summary">
+                    <span class="report-status optimal">{ report.status.label() }</span>
+                    <span>{ format!("{} Z = {:.4}", if self.problem.maximize() { "max" } else { "min" }, report.objective) }</span>
+                    <span>{ model.method.label() }</span>
+                    <span>{ format!("{} iterations", report.iterations) }</span>
+                    <span>{ format!("{:.2} ms", report.timings.total_ms) }</span>
+                </div>
+                <div class="dashboard-grid">
+                    { Self::render_solution(model, report) }
+                    { self.render_constraints(model, report) }
+                </div>
+                { self.render_sensitivity(model, report) }
+                { self.render_convergence(model) }
+                <div class="dashboard-exports">
+                    <h4>{ "Export" }</h4>
+                    <ExportMenu />
+                </div>
+            </div>
+        }
+    }
+}
+
+impl AnalysisDashboard {
+    fn render_empty(&self) -> Html {
+        html! {
+            <div class="no-problem-message">
+                <div class="message-icon">{ "📋" }</div>
+                <h3>{ "No Optimum Yet" }</h3>
+                <p>{ "The dashboard fills in once a run reaches an optimal solution." }</p>
+            </div>
+        }
+    }
+
+    /// The decision variables, with the bounds presolve took out restored.
+    fn render_solution(model: &ValidatedProblem, report: &SolveReport) -> Html {
+        let x = report
+            .original_solution
+            .as_ref()
+            .unwrap_or(&report.solution);
+        let n = model.augmentation.original_variables.min(x.len());
+        html! {
+            <div class="matrix-box">
+                <h4>{ "Solution" }</h4>
+                <table class="transportation-table">
+                    <tbody>
+                        {
+                            for (0..n).map(|j| html! {
+                                <tr>
+                                    <th>{ model.variable_labels.get(j).cloned().unwrap_or_else(|| format!("x{}", j + 1)) }</th>
+                                    <td>{ pretty::entry(x[j]) }</td>
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+
+    /// Each row with its dual and whether it binds at the optimum.
+    fn render_constraints(&self, model: &ValidatedProblem, report: &SolveReport) -> Html {
+        let slacks = model.constraint_slacks(&report.solution);
+        let dual = |i: usize| {
+            report
+                .duals
+                .as_ref()
+                .and_then(|y| y.get(i))
+                .map_or_else(|| "—".to_string(), |&value| pretty::entry(value))
+        };
+        html! {
+            <div class="matrix-box">
+                <h4>{ "Constraint activity" }</h4>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Constraint" }</th>
+                            <th>{ self.settings.notation.dual() }</th>
+                            <th>{ "Relative slack" }</th>
+                            <th />
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for slacks.iter().enumerate().map(|(i, slack)| {
+                                let (state, class) = match slack {
+                                    None => ("equality", "dashboard-active"),
+                                    Some(s) if *s < ACTIVE_SLACK_THRESHOLD => ("active", "dashboard-active"),
+                                    Some(_) => ("slack", "dashboard-slack"),
+                                };
+                                html! {
+                                    <tr class={class}>
+                                        <th>{ model.constraint_labels.get(i).cloned().unwrap_or_else(|| format!("Row {}", i + 1)) }</th>
+                                        <td>{ dual(i) }</td>
+                                        <td>{ slack.map_or_else(|| "—".to_string(), |s| format!("{:.4}", s)) }</td>
+                                        <td>{ state }</td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+
+    fn render_sensitivity(&self, model: &ValidatedProblem, report: &SolveReport) -> Html {
+        match &report.sensitivity {
+            Some(sensitivity) => html! {
+                <SensitivityView
+                    sensitivity={sensitivity.clone()}
+                    variable_labels={model.variable_labels.clone()}
+                    constraint_labels={model.constraint_labels.clone()}
+                    presolved={model.presolve.is_some()}
+                    notation={self.settings.notation.clone()}
+                />
+            },
+            None => html! {},
+        }
+    }
+
+    /// |c^T x_k - c^T x_final| per step, and the distance to the boundary
+    /// for interior runs.
+    fn render_convergence(&self, model: &ValidatedProblem) -> Html {
+        let path = &self.history.path;
+        let Some(last) = path.last() else {
+            return html! {};
+        };
+        let objective = |x: &DVector<f64>| {
+            let n = model.c.len().min(x.len());
+            model.c.rows(0, n).dot(&x.rows(0, n))
+        };
+        let target = objective(last);
+        let gaps: Vec<f64> = path[..path.len() - 1]
+            .iter()
+            .map(|x| (objective(x) - target).abs())
+            .collect();
+        html! {
+            <div class="dashboard-charts">
+                <LogChart
+                    title="Objective gap to the final iterate: |cᵀx_k − cᵀx*| (log scale)"
+                    values={gaps}
+                />
+                {
+                    if self.history.interior {
+                        html! {
+                            <LogChart
+                                title="Distance to boundary: min xᵢ per iteration (log scale)"
+                                values={path.iter().map(|x| x.min()).collect::<Vec<f64>>()}
+                            />
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
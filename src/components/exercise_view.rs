@@ -0,0 +1,144 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::exercises::{Answer, Question, Response};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub questions: Vec<Question>,
+}
+
+pub enum Msg {
+    SetResponse(usize, Response),
+    Check(usize),
+}
+
+/// Self-check questions about the last solve, each checked against the
+/// computed results.
+pub struct ExercisePanel {
+    responses: Vec<Option<Response>>,
+    /// `None` until the question is checked.
+    results: Vec<Option<bool>>,
+}
+
+impl ExercisePanel {
+    fn blank(count: usize) -> Self {
+        Self {
+            responses: vec![None; count],
+            results: vec![None; count],
+        }
+    }
+}
+
+impl Component for ExercisePanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        Self::blank(ctx.props().questions.len())
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().questions != old_props.questions {
+            *self = Self::blank(ctx.props().questions.len());
+        }
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetResponse(k, response) => {
+                if k >= self.responses.len() {
+                    return false;
+                }
+                self.responses[k] = Some(response);
+                self.results[k] = None;
+            }
+            Msg::Check(k) => {
+                let (Some(question), Some(Some(response))) =
+                    (ctx.props().questions.get(k), self.responses.get(k))
+                else {
+                    return false;
+                };
+                self.results[k] = Some(question.check(response));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let questions = &ctx.props().questions;
+        if questions.is_empty() {
+            return html! {};
+        }
+        let correct = self.results.iter().filter(|r| **r == Some(true)).count();
+
+        html! {
+            <div class="solve-report exercise-panel">
+                <h3>{ "Check Yourself" }</h3>
+                <p class="race-hint">
+                    { format!("{} of {} answered correctly. Numbers are accepted to about two decimals.", correct, questions.len()) }
+                </p>
+                <ol class="exercise-list">
+                    { for questions.iter().enumerate().map(|(k, question)| self.render_question(ctx, k, question)) }
+                </ol>
+            </div>
+        }
+    }
+}
+
+impl ExercisePanel {
+    fn render_question(&self, ctx: &Context<Self>, k: usize, question: &Question) -> Html {
+        let link = ctx.link();
+        let input = match &question.answer {
+            Answer::Number(_) => html! {
+                <input
+                    type="number"
+                    step="any"
+                    oninput={link.callback(move |e: InputEvent| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::SetResponse(k, Response::Number(input.value().parse().unwrap_or(f64::NAN)))
+                    })}
+                />
+            },
+            Answer::Choice { options, .. } => html! {
+                <select onchange={link.callback(move |e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    Msg::SetResponse(k, Response::Choice(select.value().parse().unwrap_or(usize::MAX)))
+                })}>
+                    <option value="" selected={self.responses[k].is_none()} disabled={true}>{ "Choose…" }</option>
+                    {
+                        for options.iter().enumerate().map(|(i, option)| html! {
+                            <option value={i.to_string()}>{ option }</option>
+                        })
+                    }
+                </select>
+            },
+        };
+        let verdict = match self.results[k] {
+            Some(true) => html! { <span class="exercise-verdict correct">{ "✓ Correct" }</span> },
+            Some(false) => html! { <span class="exercise-verdict wrong">{ "✗ Not quite" }</span> },
+            None => html! {},
+        };
+
+        html! {
+            <li class="exercise-question">
+                <p>{ &question.prompt }</p>
+                <div class="exercise-answer">
+                    { input }
+                    <button disabled={self.responses[k].is_none()} onclick={link.callback(move |_| Msg::Check(k))}>
+                        { "Check" }
+                    </button>
+                    { verdict }
+                </div>
+                {
+                    if self.results[k].is_some() {
+                        html! { <p class="exercise-explanation">{ &question.explanation }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </li>
+        }
+    }
+}
@@ -0,0 +1,211 @@
+use gloo_file::callbacks::{read_as_text, FileReader};
+use web_sys::{HtmlInputElement, HtmlTextAreaElement};
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext};
+use super::scroll_to_iteration;
+use crate::expected::{parse_expected_csv, Deviation, ExpectedIterations};
+
+/// Largest |Δ| a hand calculation may be off by before its row is flagged;
+/// textbook tables round to three or four decimals.
+const DEFAULT_TOLERANCE: f64 = 1e-3;
+
+pub enum Msg {
+    HistoryChanged(HistoryContext),
+    SetText(String),
+    LoadFile(web_sys::File),
+    Loaded(Result<String, String>),
+    SetTolerance(f64),
+    Clear,
+}
+
+/// Expected x per iteration, pasted or loaded as CSV, next to the run's own
+/// iterates, with the first row where they part flagged.
+pub struct ExpectedIterationsPanel {
+    history: HistoryContext,
+    _history_handle: ContextHandle<HistoryContext>,
+    text: String,
+    expected: Option<Result<ExpectedIterations, String>>,
+    tolerance: f64,
+    /// Keeps a file read in flight alive.
+    _reader: Option<FileReader>,
+}
+
+impl Component for ExpectedIterationsPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (history, history_handle) = subscribe(ctx, Msg::HistoryChanged);
+        Self {
+            history,
+            _history_handle: history_handle,
+            text: String::new(),
+            expected: None,
+            tolerance: DEFAULT_TOLERANCE,
+            _reader: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::HistoryChanged(history) => self.history = history,
+            Msg::SetText(text) => {
+                self.expected = (!text.trim().is_empty()).then(|| parse_expected_csv(&text));
+                self.text = text;
+            }
+            Msg::LoadFile(file) => {
+                let link = ctx.link().clone();
+                self._reader = Some(read_as_text(&file.into(), move |result| {
+                    link.send_message(Msg::Loaded(result.map_err(|e| e.to_string())))
+                }));
+                return false;
+            }
+            Msg::Loaded(result) => {
+                self._reader = None;
+                match result {
+                    Ok(text) => ctx.link().send_message(Msg::SetText(text)),
+                    Err(e) => self.expected = Some(Err(format!("Could not read the file: {}", e))),
+                }
+            }
+            Msg::SetTolerance(tolerance) => self.tolerance = tolerance,
+            Msg::Clear => {
+                self.text.clear();
+                self.expected = None;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <details class="race-panel expected-panel">
+                <summary>{ "Compare with expected iterations" }</summary>
+                <p class="race-hint">
+                    { "One row of x per iteration, as CSV. Row k is compared with the x of the card \"Iteration k\"; add a header starting with \"iteration\" to number rows yourself. Rows may stop after the decision variables." }
+                </p>
+                <div class="expected-input">
+                    <input
+                        type="file"
+                        accept=".csv,.txt,text/csv,text/plain"
+                        onchange={link.batch_callback(|e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            input.files().and_then(|files| files.get(0)).map(Msg::LoadFile)
+                        })}
+                    />
+                    <textarea
+                        rows="4"
+                        placeholder="iteration, x1, x2\n0, 2.0, 2.0\n1, 2.5, 4.0"
+                        value={self.text.clone()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlTextAreaElement = e.target_unchecked_into();
+                            Msg::SetText(input.value())
+                        })}
+                    />
+                    <label>{ "Tolerance: " }
+                        <input
+                            type="number"
+                            min="0"
+                            step="any"
+                            value={self.tolerance.to_string()}
+                            onchange={link.callback(|e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetTolerance(input.value().parse().unwrap_or(DEFAULT_TOLERANCE).max(0.0))
+                            })}
+                        />
+                    </label>
+                    <button disabled={self.expected.is_none()} onclick={link.callback(|_| Msg::Clear)}>
+                        { "Clear" }
+                    </button>
+                </div>
+                {
+                    match &self.expected {
+                        None => html! {},
+                        Some(Err(e)) => html! { <p class="report-warning">{ e }</p> },
+                        Some(Ok(expected)) => self.render_comparison(&expected.compare(&self.history.path)),
+                    }
+                }
+            </details>
+        }
+    }
+}
+
+impl ExpectedIterationsPanel {
+    fn render_comparison(&self, deviations: &[Deviation]) -> Html {
+        let tolerance = self.tolerance;
+        let first_off = deviations
+            .iter()
+            .find(|row| row.actual.is_some() && !row.within(tolerance));
+        let summary = match first_off {
+            Some(row) => {
+                let (j, gap) = row.worst.unwrap_or_default();
+                format!(
+                    "First divergence at iteration {}: x{} is off by {:.3e}.",
+                    row.iteration,
+                    j + 1,
+                    gap
+                )
+            }
+            None if deviations.iter().all(|row| row.actual.is_some()) => {
+                format!("Every iteration agrees to within {}.", tolerance)
+            }
+            None => format!(
+                "The iterations so far agree to within {}; the run has not reached the rest.",
+                tolerance
+            ),
+        };
+        let values = |x: &[f64]| {
+            x.iter()
+                .map(|val| format!("{:.4}", val))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        html! {
+            <>
+                <p class={if first_off.is_some() { "report-warning" } else { "race-hint" }}>{ summary }</p>
+                <div class="race-history">
+                    <table>
+                        <thead>
+                            <tr>
+                                <th>{ "Iteration" }</th>
+                                <th>{ "Expected x" }</th>
+                                <th>{ "App x" }</th>
+                                <th>{ "max |Δ|" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                        {
+                            for deviations.iter().map(|row| {
+                                let index = row.iteration;
+                                let off = row.actual.is_some() && !row.within(tolerance);
+                                let first = first_off.is_some_and(|first| first.iteration == index);
+                                html! {
+                                    <tr class={classes!(off.then_some("expected-off"), first.then_some("expected-first"))}>
+                                        <td>
+                                            <button class="history-jump" disabled={row.actual.is_none()} onclick={Callback::from(move |_| scroll_to_iteration(index))}>
+                                                { index }
+                                            </button>
+                                        </td>
+                                        <td>{ values(&row.expected) }</td>
+                                        <td>{ row.actual.as_deref().map_or("not reached".to_string(), values) }</td>
+                                        <td>
+                                            {
+                                                match row.worst {
+                                                    Some((j, gap)) => format!("{:.3e} (x{})", gap, j + 1),
+                                                    None => "—".to_string(),
+                                                }
+                                            }
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                        </tbody>
+                    </table>
+                </div>
+            </>
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use gloo_file::ObjectUrl;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext, ProblemContext, SettingsContext};
+use crate::export::{self, ExportRun, BUNDLE_FILE, EXPORTERS};
+
+/// Context updates and the download buttons.
+pub enum Msg {
+    Problem(ProblemContext),
+    Settings(SettingsContext),
+    History(HistoryContext),
+    /// Downloads the format at this index of `EXPORTERS`.
+    Export(usize),
+    /// Downloads every format in one zip.
+    Bundle,
+}
+
+/// One download per registered export format, plus all of them zipped.
+/// Lists `EXPORTERS`, so new formats show up here without changes.
+pub struct ExportMenu {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    history: HistoryContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+        ContextHandle<HistoryContext>,
+    ),
+    /// Object URL of the last download, revoked when replaced.
+    url: Option<ObjectUrl>,
+}
+
+impl Component for ExportMenu {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::Problem);
+        let (settings, settings_handle) = subscribe(ctx, Msg::Settings);
+        let (history, history_handle) = subscribe(ctx, Msg::History);
+        Self {
+            problem,
+            settings,
+            history,
+            _handles: (problem_handle, settings_handle, history_handle),
+            url: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Problem(problem) => self.problem = problem,
+            Msg::Settings(settings) => self.settings = settings,
+            Msg::History(history) => self.history = history,
+            Msg::Export(index) => {
+                let exporter = EXPORTERS[index];
+                let Some(text) = self.with_run(|run| exporter.export(run)).flatten() else {
+                    log::info!("{}: nothing to export for this run", exporter.label());
+                    return false;
+                };
+                self.url =
+                    export::download(text.as_bytes(), exporter.file_name(), exporter.mime_type());
+                return false;
+            }
+            Msg::Bundle => {
+                let Some(bytes) = self.with_run(export::bundle) else {
+                    return false;
+                };
+                log::info!(
+                    "Bundle of {} iterations, {} bytes",
+                    self.history.path.len(),
+                    bytes.len()
+                );
+                self.url = export::download(&bytes, BUNDLE_FILE, "application/zip");
+                return false;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.problem.model.is_none() {
+            return html! {};
+        }
+        let link = ctx.link();
+        html! {
+            <details class="export-menu">
+                <summary>{ "Export" }</summary>
+                <div class="export-menu-items">
+                    <button
+                        title="A zip of every format below, for handing in the whole run at once."
+                        onclick={link.callback(|_| Msg::Bundle)}
+                    >
+                        { "Everything (zip)" }
+                    </button>
+                    {
+                        for EXPORTERS.iter().enumerate().map(|(index, exporter)| html! {
+                            <button onclick={link.callback(move |_| Msg::Export(index))}>
+                                { exporter.label() }
+                            </button>
+                        })
+                    }
+                </div>
+            </details>
+        }
+    }
+}
+
+impl ExportMenu {
+    /// Calls `f` on the current run, if a model is loaded.
+    fn with_run<T>(&self, f: impl FnOnce(&ExportRun) -> T) -> Option<T> {
+        let model = self.problem.model.as_deref()?;
+        Some(f(&ExportRun {
+            model,
+            options: &self.settings.options,
+            report: self.problem.report.as_deref(),
+            path: &self.history.path,
+            notes: &self.history.notes,
+        }))
+    }
+}
@@ -0,0 +1,79 @@
+use nalgebra::DVector;
+use yew::prelude::*;
+
+use crate::fractional::FractionalLayout;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub layout: FractionalLayout,
+    /// The optimum (y, t) of the transformed LP.
+    pub solution: DVector<f64>,
+}
+
+/// The optimum of a ratio objective read back from its Charnes–Cooper LP:
+/// t, y and x = y / t, with both parts of the ratio at x.
+pub struct FractionalView;
+
+impl Component for FractionalView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let layout = &props.layout;
+        let back = layout.solve_back(&props.solution);
+        let y = |j: usize| props.solution.get(j).copied().unwrap_or(0.0);
+
+        html! {
+            <div class="fractional-view">
+                <h3>{ "Ratio Objective" }</h3>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Variable" }</th>
+                            <th>{ "y = t x" }</th>
+                            <th>{ "x = y / t" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for layout.variable_labels.iter().enumerate().map(|(j, label)| html! {
+                                <tr>
+                                    <th>{ label }</th>
+                                    <td>{ format!("{:.4}", y(j)) }</td>
+                                    <td>{ back.x.as_ref().map_or_else(|| "—".to_string(), |x| format!("{:.4}", x[j])) }</td>
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+                {
+                    if back.x.is_some() {
+                        html! {
+                            <div class="barrier-summary">
+                                <span>{ format!("t = {:.6}", back.t) }</span>
+                                <span>{ format!("numerator = {:.4}", back.numerator) }</span>
+                                <span>{ format!("denominator = {:.4}", back.denominator) }</span>
+                                <span>{ format!("ratio = {:.6}", back.ratio()) }</span>
+                            </div>
+                        }
+                    } else {
+                        html! {
+                            <p class="race-hint">
+                                { format!("t = {:.2e}: the ratio is only approached as x grows without bound, so x = y / t cannot be recovered.", back.t) }
+                            </p>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
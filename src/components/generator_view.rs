@@ -0,0 +1,104 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::generator::{generate, GeneratedProblem, GeneratorOptions};
+use crate::report::now_ms;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub on_generate: Callback<GeneratedProblem>,
+    /// Most variables the form takes.
+    #[prop_or(10)]
+    pub max_variables: usize,
+}
+
+pub enum Msg {
+    SetConstraints(usize),
+    SetVariables(usize),
+    /// Percent of nonzero entries.
+    SetDensity(f64),
+    Generate,
+}
+
+/// "Generate example": fills the form with a random LP of the chosen size
+/// whose optimum is known, and shows that optimum to check the run against.
+pub struct GeneratorPanel {
+    options: GeneratorOptions,
+    last: Option<GeneratedProblem>,
+    error: Option<String>,
+}
+
+impl Component for GeneratorPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            options: GeneratorOptions::default(),
+            last: None,
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetConstraints(m) => self.options.constraints = m.clamp(1, 10),
+            Msg::SetVariables(n) => self.options.variables = n.clamp(1, ctx.props().max_variables),
+            Msg::SetDensity(percent) => self.options.density = (percent / 100.0).clamp(0.05, 1.0),
+            Msg::Generate => {
+                // A new seed each time; the notes name it so the problem
+                // can be made again.
+                self.options.seed = now_ms() as u64;
+                match generate(&self.options) {
+                    Ok(problem) => {
+                        ctx.props().on_generate.emit(problem.clone());
+                        self.last = Some(problem);
+                        self.error = None;
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let number = |to_msg: fn(f64) -> Msg| {
+            link.batch_callback(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                input.value().parse::<f64>().ok().map(to_msg)
+            })
+        };
+        html! {
+            <div class="generator-panel">
+                <label>{ "Constraints: " }
+                    <input type="number" min="1" max="10" value={self.options.constraints.to_string()}
+                        onchange={number(|m| Msg::SetConstraints(m as usize))} />
+                </label>
+                <label>{ "Variables: " }
+                    <input type="number" min="1" max={ctx.props().max_variables.to_string()} value={self.options.variables.to_string()}
+                        onchange={number(|n| Msg::SetVariables(n as usize))} />
+                </label>
+                <label title="Share of the entries of A below the first row that are nonzero.">{ "Density %: " }
+                    <input type="number" min="5" max="100" step="5" value={format!("{:.0}", self.options.density * 100.0)}
+                        onchange={number(Msg::SetDensity)} />
+                </label>
+                <button onclick={link.callback(|_| Msg::Generate)}>{ "Generate example" }</button>
+                {
+                    if let Some(error) = &self.error {
+                        html! { <span class="report-warning">{ error }</span> }
+                    } else if let Some(problem) = &self.last {
+                        html! {
+                            <span class="generator-optimum" title="Built from an optimal primal and dual pair, so any solver should reach this value.">
+                                { format!("Known optimum: Z* = {} at x* = ({})", problem.objective, problem.x_star.iter().map(|val| val.to_string()).collect::<Vec<_>>().join(", ")) }
+                            </span>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
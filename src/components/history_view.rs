@@ -0,0 +1,94 @@
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext};
+
+pub enum Msg {
+    HistoryChanged(HistoryContext),
+}
+
+/// Every run finished since the page was opened, newest first, to compare
+/// methods and edits of a model without keeping notes.
+pub struct SessionHistory {
+    history: HistoryContext,
+    _history_handle: ContextHandle<HistoryContext>,
+}
+
+impl Component for SessionHistory {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (history, history_handle) = subscribe(ctx, Msg::HistoryChanged);
+        Self {
+            history,
+            _history_handle: history_handle,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::HistoryChanged(history) => {
+                self.history = history;
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let runs = &self.history.runs;
+        html! {
+            <div class="race-panel session-history">
+                <h3>{ "Session History" }</h3>
+                {
+                    if runs.is_empty() {
+                        html! { <p class="race-hint">{ "Runs appear here once they finish; the list is kept until the page is reloaded." }</p> }
+                    } else {
+                        html! {
+                            <div class="race-history">
+                                <table>
+                                    <thead>
+                                        <tr>
+                                            <th>{ "#" }</th>
+                                            <th>{ "Method" }</th>
+                                            <th>{ "Size" }</th>
+                                            <th>{ "Status" }</th>
+                                            <th>{ "Z" }</th>
+                                            <th>{ "Iterations" }</th>
+                                            <th>{ "Solver time" }</th>
+                                        </tr>
+                                    </thead>
+                                    <tbody>
+                                    {
+                                        for runs.iter().enumerate().rev().map(|(k, run)| html! {
+                                            <>
+                                            <tr>
+                                                <td>{ k + 1 }</td>
+                                                <td>{ run.method.label() }</td>
+                                                <td>{ format!("{} × {}", run.constraints, run.variables) }</td>
+                                                <td>{ run.status.label() }</td>
+                                                <td>{ format!("{:.4}", run.objective) }</td>
+                                                <td>{ run.iterations }</td>
+                                                <td>{ format!("{:.2} ms", run.total_ms) }</td>
+                                            </tr>
+                                            {
+                                                for run.notes.iter().map(|(index, text)| html! {
+                                                    <tr class="run-note">
+                                                        <td></td>
+                                                        <td colspan="6">{ format!("Iteration {}: {}", index, text) }</td>
+                                                    </tr>
+                                                })
+                                            }
+                                            </>
+                                        })
+                                    }
+                                    </tbody>
+                                </table>
+                            </div>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::hsd::{HsdIteration, Indicator};
+use crate::notation::Notation;
+use yew::prelude::*;
+
+use super::interior_view::InteriorPointView;
+
+/// Decades of τ/κ either side of 1 the indicator bar spans.
+const RATIO_DECADES: f64 = 8.0;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub iteration: usize,
+
+    #[prop_or_default]
+    pub iteration_data: Option<HsdIteration>,
+
+    #[prop_or_default]
+    pub active_rows: Vec<usize>,
+
+    #[prop_or_default]
+    pub constraint_labels: Vec<String>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+pub struct HsdView;
+
+impl Component for HsdView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let it = props.iteration_data.as_ref();
+        let compact = props.compact;
+
+        html! {
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Iteration {}", props.iteration) }</h3>
+
+                { InteriorPointView::render_active(&props.active_rows, &props.constraint_labels) }
+
+                {
+                    if let Some(iter) = it {
+                        html! {
+                            <>
+                                <div class="barrier-summary">
+                                    <span>{ format!("{} = {:.3e}", props.notation.barrier(), iter.mu) }</span>
+                                    <span>{ format!("step t = {:.4}", iter.step_length) }</span>
+                                    <span>{ format!("primal residual {:.2e}", iter.primal_residual) }</span>
+                                    <span>{ format!("dual residual {:.2e}", iter.dual_residual) }</span>
+                                    <span>{ format!("gap {:.2e}", iter.gap) }</span>
+                                </div>
+                                { render_indicator(iter) }
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <div class="matrix-container">
+                    { InteriorPointView::vector_card(&format!("Current {} = x / τ", props.notation.iterate(props.iteration)), it.map(|iter| &iter.current_x), compact) }
+                    { InteriorPointView::vector_card("Dual estimate y / τ", it.map(|iter| &iter.duals), compact) }
+                </div>
+            </div>
+        }
+    }
+}
+
+/// τ and κ with a bar placing τ/κ on a log scale: right of the middle the
+/// run is heading for an optimum, left of it for a certificate.
+fn render_indicator(iter: &HsdIteration) -> Html {
+    let indicator = iter.indicator();
+    let decades = (iter.tau / iter.kappa)
+        .log10()
+        .clamp(-RATIO_DECADES, RATIO_DECADES);
+    let position = 50.0 + 50.0 * decades / RATIO_DECADES;
+    let class = match indicator {
+        Indicator::Optimum => "hsd-optimum",
+        Indicator::Certificate => "hsd-certificate",
+        Indicator::Undecided => "hsd-undecided",
+    };
+    html! {
+        <div class={classes!("hsd-indicator", class)}>
+            <span>{ format!("τ = {:.3e}", iter.tau) }</span>
+            <span>{ format!("κ = {:.3e}", iter.kappa) }</span>
+            <div class="hsd-meter" title="log₁₀(τ/κ)">
+                <span class="hsd-marker" style={format!("left: {:.1}%", position)} />
+            </div>
+            <span class="hsd-verdict">{ indicator.describe() }</span>
+        </div>
+    }
+}
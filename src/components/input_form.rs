@@ -1,83 +1,214 @@
-use nalgebra::{DMatrix, DVector};
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
 use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
 
-#[derive(Clone)]
-pub enum InputFormData {
-    InteriorPointInput(
-        DMatrix<f64>,
-        DVector<f64>,
-        DVector<f64>,
-        f64,
-        Vec<f64>,
-        bool,
-        bool,
-    ),
+use crate::course_pack::PackProblem;
+use crate::equilibration::Scaling;
+use crate::fractional::{charnes_cooper, FractionalLayout, FractionalObjective};
+use crate::interior::StepStrategy;
+use crate::model::{
+    big_m_form, default_variable_labels, weighted_objective, Augmentation, ConstraintSign, Goal,
+    Sense, SolverMethod, StandardForm, StartOptions, ValidatedProblem,
+};
+use crate::notation::Notation;
+use crate::presolve::{BoundKind, Presolve};
+use crate::reformulation::{
+    reformulate, AffineExpression, LinearModel, Placement, Rewrite, Term, TermKind,
+};
+use crate::symmetry::{self, Symmetry};
+
+/// Editing -> Validated -> Submitted. Every edit re-runs validation, so the
+/// form is either `Editing` with the reasons it cannot be solved yet, or
+/// `Validated` with the typed model; submitting moves it to `Submitted`.
+#[derive(Clone, PartialEq)]
+pub enum FormState {
+    Editing(Vec<String>),
+    Validated(ValidatedProblem),
+    Submitted(ValidatedProblem),
 }
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
-    pub on_submit: Callback<InputFormData>,
+    pub on_submit: Callback<ValidatedProblem>,
     pub on_size_change: Callback<(usize, usize)>,
     #[prop_or(10)]
     pub max_variables: usize,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+
+    /// Course-pack problem to fill the form with; replaces the form's
+    /// contents whenever it changes.
+    #[prop_or_default]
+    pub preset: Option<PackProblem>,
+
+    /// Labels of constraints the last failed run pointed at, each with why;
+    /// their rows are highlighted.
+    #[prop_or_default]
+    pub flagged_constraints: Vec<(String, String)>,
+}
+
+/// Group the symmetry-breaking rows are added to.
+const SYMMETRY_GROUP: &str = "Symmetry breaking";
+
+/// Label of form row `i` in the validated model.
+fn constraint_label(i: usize) -> String {
+    format!("C{}", i + 1)
 }
 
 pub struct InputForm {
     variables: usize,
     constraints: usize,
 
-    objective_coeffs: Vec<f64>,
+    /// One row per goal; a single row is a plain objective.
+    objective_coeffs: Vec<Vec<f64>>,
+    goal_weights: Vec<f64>,
 
     constraint_coeffs: Vec<Vec<f64>>,
-    constraint_signs: Vec<String>,
+    constraint_signs: Vec<ConstraintSign>,
     rhs_values: Vec<f64>,
+    /// Group name of each row; blank rows are ungrouped.
+    constraint_groups: Vec<String>,
+
+    /// |·|, max and min terms, rewritten into linear rows on validation.
+    terms: Vec<Term>,
+
+    /// The objective row is the numerator of (c^T x + c0) / (d^T x + d0),
+    /// solved by the Charnes–Cooper transformation.
+    fractional: bool,
+    numerator_constant: f64,
+    denominator: AffineExpression,
 
     maximization: bool,
 
     alpha: f64,
+    step_strategy: StepStrategy,
+    scaling: Scaling,
     initial_feasible: Vec<f64>,
+    find_start: bool,
+
+    integer_vars: Vec<bool>,
 
     augmented_model: bool,
+
+    presolve_bounds: bool,
+
+    solver_method: SolverMethod,
+    initial_mu: f64,
+
+    q_coeffs: Vec<Vec<f64>>,
+
+    /// Interchangeable variables of the grid, found on every edit.
+    symmetry: Option<Symmetry>,
+
+    state: FormState,
 }
 
 pub enum Msg {
     SetVariables(usize),
     SetConstraints(usize),
-    UpdateObjectiveCoeff(usize, f64),
+    UpdateObjectiveCoeff(usize, usize, f64),
+    UpdateGoalWeight(usize, f64),
+    AddGoal,
+    RemoveGoal(usize),
     UpdateConstraintCoeff(usize, usize, f64),
     UpdateRHSValue(usize, f64),
+    UpdateConstraintGroup(usize, String),
+    AddTerm,
+    RemoveTerm(usize),
+    SetTermKind(usize, TermKind),
+    /// `None` puts the term in the objective.
+    SetTermPlacement(usize, Option<ConstraintSign>),
+    /// The weight of an objective term or the right-hand side of a constraint.
+    UpdateTermValue(usize, f64),
+    UpdateTermCoeff(usize, usize, usize, f64),
+    UpdateTermConstant(usize, usize, f64),
+    AddTermExpression(usize),
+    RemoveTermExpression(usize, usize),
+    SetFractional(bool),
+    UpdateNumeratorConstant(f64),
+    UpdateDenominatorCoeff(usize, f64),
+    UpdateDenominatorConstant(f64),
     ToggleOptimizationType,
     UpdateAlpha(f64),
+    SetStepStrategy(StepStrategy),
+    SetScaling(Scaling),
     UpdateInitialPoint(usize, f64),
+    SetFindStart(bool),
+    SetPresolveBounds(bool),
+    SetInteger(usize, bool),
     Submit,
     SetAugmentedModel(bool),
-    UpdateConstraintSign(usize, String),
+    UpdateConstraintSign(usize, ConstraintSign),
+    AugmentInconsistentRows,
+    /// Appends x_j >= x_k rows ordering each group of `symmetry`.
+    AddSymmetryBreaking,
+    SetSolverMethod(SolverMethod),
+    UpdateInitialMu(f64),
+    UpdateQCoeff(usize, usize, f64),
 }
 
 impl Component for InputForm {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         let variables = 2;
         let constraints = 2;
-        Self {
+        let mut form = Self {
             variables,
             constraints,
-            objective_coeffs: vec![0.0; variables],
+            objective_coeffs: vec![vec![0.0; variables]],
+            goal_weights: vec![1.0],
             constraint_coeffs: vec![vec![0.0; variables]; constraints],
-            constraint_signs: vec!["<=".to_string(); constraints],
+            constraint_signs: vec![ConstraintSign::LessEqual; constraints],
             rhs_values: vec![0.0; constraints],
+            constraint_groups: vec![String::new(); constraints],
+            terms: vec![],
+            fractional: false,
+            numerator_constant: 0.0,
+            denominator: AffineExpression {
+                coefficients: vec![0.0; variables],
+                constant: 1.0,
+            },
             maximization: true,
             alpha: 0.5,
+            step_strategy: StepStrategy::default(),
+            scaling: Scaling::default(),
             initial_feasible: vec![1.0; variables],
+            find_start: true,
+            integer_vars: vec![false; variables],
+            q_coeffs: vec![vec![0.0; variables]; variables],
             augmented_model: false,
+            presolve_bounds: true,
+            solver_method: SolverMethod::AffineScaling,
+            initial_mu: 1.0,
+            symmetry: None,
+            state: FormState::Editing(vec![]),
+        };
+        if let Some(problem) = &ctx.props().preset {
+            form.load(problem);
         }
+        form.revalidate();
+        form
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().preset != old_props.preset {
+            if let Some(problem) = &ctx.props().preset {
+                self.load(problem);
+                self.revalidate();
+                ctx.props()
+                    .on_size_change
+                    .emit((self.variables, self.constraints));
+            }
+        }
+        true
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
+        let is_edit = !matches!(msg, Msg::Submit);
+        let changed = match msg {
             Msg::SetVariables(v) => {
                 let v = v.min(ctx.props().max_variables);
                 self.variables = v;
@@ -95,9 +226,31 @@ impl Component for InputForm {
                     .emit((self.variables, self.constraints));
                 true
             }
-            Msg::UpdateObjectiveCoeff(j, val) => {
-                if j < self.objective_coeffs.len() {
-                    self.objective_coeffs[j] = val;
+            Msg::UpdateObjectiveCoeff(k, j, val) => {
+                if k < self.objective_coeffs.len() && j < self.objective_coeffs[k].len() {
+                    self.objective_coeffs[k][j] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::UpdateGoalWeight(k, weight) => {
+                if k < self.goal_weights.len() {
+                    self.goal_weights[k] = weight;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::AddGoal => {
+                self.objective_coeffs.push(vec![0.0; self.variables]);
+                self.goal_weights.push(1.0);
+                true
+            }
+            Msg::RemoveGoal(k) => {
+                if self.objective_coeffs.len() > 1 && k < self.objective_coeffs.len() {
+                    self.objective_coeffs.remove(k);
+                    self.goal_weights.remove(k);
                     true
                 } else {
                     false
@@ -119,12 +272,122 @@ impl Component for InputForm {
                     false
                 }
             }
+            Msg::UpdateConstraintGroup(i, name) => match self.constraint_groups.get_mut(i) {
+                Some(group) => {
+                    *group = name.trim().to_string();
+                    true
+                }
+                None => false,
+            },
+            Msg::AddTerm => {
+                self.terms.push(Term::new(TermKind::Abs, self.variables));
+                true
+            }
+            Msg::RemoveTerm(k) => {
+                if k < self.terms.len() {
+                    self.terms.remove(k);
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::SetTermKind(k, kind) => match self.terms.get_mut(k) {
+                Some(term) => {
+                    term.kind = kind;
+                    if kind == TermKind::Abs {
+                        term.expressions.truncate(1);
+                    }
+                    true
+                }
+                None => false,
+            },
+            Msg::SetTermPlacement(k, sign) => match self.terms.get_mut(k) {
+                Some(term) => {
+                    term.placement = match sign {
+                        Some(sign) => Placement::Constraint(sign, 0.0),
+                        None => Placement::Objective(1.0),
+                    };
+                    true
+                }
+                None => false,
+            },
+            Msg::UpdateTermValue(k, val) => match self.terms.get_mut(k) {
+                Some(term) => {
+                    term.placement = match term.placement {
+                        Placement::Objective(_) => Placement::Objective(val),
+                        Placement::Constraint(sign, _) => Placement::Constraint(sign, val),
+                    };
+                    true
+                }
+                None => false,
+            },
+            Msg::UpdateTermCoeff(k, e, j, val) => {
+                match self
+                    .terms
+                    .get_mut(k)
+                    .and_then(|term| term.expressions.get_mut(e))
+                    .and_then(|expression| expression.coefficients.get_mut(j))
+                {
+                    Some(coefficient) => {
+                        *coefficient = val;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Msg::UpdateTermConstant(k, e, val) => {
+                match self
+                    .terms
+                    .get_mut(k)
+                    .and_then(|term| term.expressions.get_mut(e))
+                {
+                    Some(expression) => {
+                        expression.constant = val;
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Msg::AddTermExpression(k) => match self.terms.get_mut(k) {
+                Some(term) if term.kind != TermKind::Abs => {
+                    term.expressions
+                        .push(AffineExpression::zeros(self.variables));
+                    true
+                }
+                _ => false,
+            },
+            Msg::RemoveTermExpression(k, e) => match self.terms.get_mut(k) {
+                Some(term) if term.expressions.len() > 1 && e < term.expressions.len() => {
+                    term.expressions.remove(e);
+                    true
+                }
+                _ => false,
+            },
+            Msg::SetFractional(val) => {
+                self.fractional = val;
+                true
+            }
+            Msg::UpdateNumeratorConstant(val) => {
+                self.numerator_constant = val;
+                true
+            }
+            Msg::UpdateDenominatorCoeff(j, val) => match self.denominator.coefficients.get_mut(j) {
+                Some(coeff) => {
+                    *coeff = val;
+                    true
+                }
+                None => false,
+            },
+            Msg::UpdateDenominatorConstant(val) => {
+                self.denominator.constant = val;
+                true
+            }
             Msg::ToggleOptimizationType => {
                 self.maximization = !self.maximization;
                 true
             }
             Msg::UpdateAlpha(a) => {
-                self.alpha = a.max(0.0).min(1.0);
+                self.alpha = a.clamp(0.0, 1.0);
                 true
             }
             Msg::UpdateInitialPoint(idx, val) => {
@@ -135,55 +398,64 @@ impl Component for InputForm {
                     false
                 }
             }
-            Msg::Submit => {
-                let (a, b, c) = self.create_matrix_form();
-                
-                // Prepare initial feasible point based on mode
-                let initial_point = if self.augmented_model {
-                    // Already augmented - use user input as is
-                    self.initial_feasible.clone()
-                } else {
-                    // Auto-augment mode - extend initial point for slack variables
-                    let slack_count = self.constraint_signs.iter()
-                        .filter(|&sign| sign == "<=" || sign == ">=")
-                        .count();
-                    
-                    let mut extended_initial = self.initial_feasible.clone();
-                    // Add positive initial values for slack variables
-                    for _ in 0..slack_count {
-                        extended_initial.push(1.0);
-                    }
-                    extended_initial
-                };
-                
-                let data = InputFormData::InteriorPointInput(
-                    a,
-                    b,
-                    c,
-                    self.alpha,
-                    initial_point,
-                    self.maximization,
-                    self.augmented_model,
-                );
-                ctx.props().on_submit.emit(data);
+            Msg::SetPresolveBounds(val) => {
+                self.presolve_bounds = val;
                 true
             }
+            Msg::SetStepStrategy(strategy) => {
+                self.step_strategy = strategy;
+                true
+            }
+            Msg::SetScaling(scaling) => {
+                self.scaling = scaling;
+                true
+            }
+            Msg::SetFindStart(val) => {
+                self.find_start = val;
+                true
+            }
+            Msg::UpdateQCoeff(i, j, val) => {
+                if i < self.q_coeffs.len() && j < self.q_coeffs[i].len() {
+                    self.q_coeffs[i][j] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::SetInteger(idx, val) => {
+                if idx < self.integer_vars.len() {
+                    self.integer_vars[idx] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Submit => match &self.state {
+                FormState::Validated(problem) | FormState::Submitted(problem) => {
+                    let problem = problem.clone();
+                    ctx.props().on_submit.emit(problem.clone());
+                    self.state = FormState::Submitted(problem);
+                    true
+                }
+                FormState::Editing(_) => false,
+            },
             Msg::SetAugmentedModel(val) => {
+                // Signs are left as entered; validation flags inequality rows
+                // in augmented mode and offers to fix them.
                 self.augmented_model = val;
-                // When switching to augmented mode, set all constraint signs to "="
-                if val {
-                    for i in 0..self.constraint_signs.len() {
-                        self.constraint_signs[i] = "=".to_string();
-                    }
+                true
+            }
+            Msg::AugmentInconsistentRows => {
+                let rows = self.inconsistent_rows();
+                if rows.is_empty() || self.variables + rows.len() > ctx.props().max_variables {
+                    false
                 } else {
-                    // When switching back to auto-augment, ensure signs are not "="
-                    for i in 0..self.constraint_signs.len() {
-                        if self.constraint_signs[i] == "=" {
-                            self.constraint_signs[i] = "<=".to_string();
-                        }
-                    }
+                    self.add_slack_columns(&rows);
+                    ctx.props()
+                        .on_size_change
+                        .emit((self.variables, self.constraints));
+                    true
                 }
-                true
             }
             Msg::UpdateConstraintSign(i, sign) => {
                 if i < self.constraint_signs.len() {
@@ -193,7 +465,33 @@ impl Component for InputForm {
                     false
                 }
             }
+            Msg::AddSymmetryBreaking => match self.symmetry.take() {
+                Some(symmetry) if !self.augmented_model => {
+                    self.add_breaking_rows(&symmetry);
+                    ctx.props()
+                        .on_size_change
+                        .emit((self.variables, self.constraints));
+                    true
+                }
+                symmetry => {
+                    self.symmetry = symmetry;
+                    false
+                }
+            },
+            Msg::SetSolverMethod(method) => {
+                self.solver_method = method;
+                true
+            }
+            Msg::UpdateInitialMu(mu) => {
+                self.initial_mu = mu.max(1e-6);
+                true
+            }
+        };
+
+        if changed && is_edit {
+            self.revalidate();
         }
+        changed
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
@@ -271,84 +569,206 @@ impl Component for InputForm {
 
                 <div class="objective-function">
                 {
-                    for (0..self.variables).map(|j| {
+                    for self.objective_coeffs.iter().enumerate().map(|(k, row)| {
+                        let goals = self.objective_coeffs.len();
                         html! {
-                            <span>
-                                {if j > 0 { " + " } else { "" }}
+                            <div class="goal-row">
+                                {
+                                    if goals > 1 {
+                                        html! {
+                                            <label class="goal-weight">
+                                                { format!("G{}  w = ", k + 1) }
+                                                <input
+                                                    type="number"
+                                                    min="0"
+                                                    step="0.1"
+                                                    value={self.goal_weights[k].to_string()}
+                                                    oninput={link.callback(move |e: InputEvent| {
+                                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                                        Msg::UpdateGoalWeight(k, input.value().parse().unwrap_or(0.0))
+                                                    })}
+                                                />
+                                                { " × (" }
+                                            </label>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                                {
+                                    for row.iter().enumerate().map(|(j, coeff)| {
+                                        html! {
+                                            <span>
+                                                {if j > 0 { " + " } else { "" }}
+                                                <input
+                                                    type="number"
+                                                    step="0.1"
+                                                    value={coeff.to_string()}
+                                                    oninput={link.callback(move |e: InputEvent| {
+                                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                                        Msg::UpdateObjectiveCoeff(
+                                                            k,
+                                                            j,
+                                                            input.value().parse().unwrap_or(0.0)
+                                                        )
+                                                    })}
+                                                />
+                                                { format!("x{}", j + 1) }
+                                            </span>
+                                        }
+                                    })
+                                }
+                                {
+                                    if goals > 1 {
+                                        html! {
+                                            <>
+                                                { " )" }
+                                                <button class="goal-remove" onclick={link.callback(move |_| Msg::RemoveGoal(k))}>{ "✕" }</button>
+                                            </>
+                                        }
+                                    } else {
+                                        html! {}
+                                    }
+                                }
+                            </div>
+                        }
+                    })
+                }
+                { self.render_denominator(ctx) }
+                {
+                    if self.fractional {
+                        html! {}
+                    } else {
+                        html! { <button class="goal-add" onclick={link.callback(|_| Msg::AddGoal)}>{ "Add Goal" }</button> }
+                    }
+                }
+                </div>
+
+                <div class="integer-variables">
+                    <span>{ "Integer variables:" }</span>
+                    {
+                        for (0..self.variables).map(|j| html! {
+                            <label>
                                 <input
-                                    type="number"
-                                    step="0.1"
-                                    value={self.objective_coeffs[j].to_string()}
-                                    oninput={link.callback(move |e: InputEvent| {
+                                    type="checkbox"
+                                    checked={self.integer_vars[j]}
+                                    onchange={link.callback(move |e: Event| {
                                         let input: HtmlInputElement = e.target_unchecked_into();
-                                        Msg::UpdateObjectiveCoeff(
-                                            j,
-                                            input.value().parse().unwrap_or(0.0)
-                                        )
+                                        Msg::SetInteger(j, input.checked())
                                     })}
                                 />
                                 { format!("x{}", j + 1) }
-                            </span>
+                            </label>
+                        })
+                    }
+                </div>
+
+                { self.render_constraints(ctx) }
+
+                { self.render_terms(ctx) }
+
+                <label class="presolve-toggle">
+                    <input
+                        type="checkbox"
+                        checked={self.presolve_bounds}
+                        disabled={self.augmented_model || self.solver_method == SolverMethod::QuadraticBarrier}
+                        onchange={link.callback(|e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            Msg::SetPresolveBounds(input.checked())
+                        })}
+                    />
+                    { "Presolve: turn single-variable rows (e.g. x3 <= 7) into bounds and merge duplicate columns" }
+                </label>
+
+                <div class="solver-method">
+                    <label>{"Method: "}
+                        <select
+                            onchange={link.callback(|e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                match select.value().as_str() {
+                                    "barrier" => Msg::SetSolverMethod(SolverMethod::LogBarrier),
+                                    "simplex" => Msg::SetSolverMethod(SolverMethod::Simplex),
+                                    "revised" => Msg::SetSolverMethod(SolverMethod::RevisedSimplex),
+                                    "qp" => Msg::SetSolverMethod(SolverMethod::QuadraticBarrier),
+                                    "self-dual" => Msg::SetSolverMethod(SolverMethod::SelfDual),
+                                    _ => Msg::SetSolverMethod(SolverMethod::AffineScaling),
+                                }
+                            })}>
+                            <option value="affine" selected={self.solver_method == SolverMethod::AffineScaling}>
+                                {"Affine Scaling"}
+                            </option>
+                            <option value="barrier" selected={self.solver_method == SolverMethod::LogBarrier}>
+                                {"Log Barrier (central path)"}
+                            </option>
+                            <option value="simplex" selected={self.solver_method == SolverMethod::Simplex}>
+                                {"Primal Simplex (tableau)"}
+                            </option>
+                            <option value="revised" selected={self.solver_method == SolverMethod::RevisedSimplex}>
+                                {"Revised Simplex (LU)"}
+                            </option>
+                            <option value="qp" selected={self.solver_method == SolverMethod::QuadraticBarrier}>
+                                {"Quadratic Program (barrier)"}
+                            </option>
+                            <option value="self-dual" selected={self.solver_method == SolverMethod::SelfDual}>
+                                {"Self-Dual Embedding (τ/κ)"}
+                            </option>
+                        </select>
+                    </label>
+                    {
+                        if matches!(self.solver_method, SolverMethod::LogBarrier | SolverMethod::QuadraticBarrier) {
+                            html! {
+                                <label>{ format!("Initial {}: ", ctx.props().notation.barrier()) }
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        step="0.1"
+                                        value={self.initial_mu.to_string()}
+                                        oninput={link.callback(|e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateInitialMu(input.value().parse().unwrap_or(1.0))
+                                        })}
+                                    />
+                                </label>
+                            }
+                        } else {
+                            html! {}
                         }
-                    })
-                }
+                    }
                 </div>
 
-                <div class="constraints">
-                                    {
-                                        for (0..self.constraints).map(|i| {
-                                            html! {
-                                                <div class="constraint-row">
-                                                    {
-                                                        for (0..self.variables).map(|j| {
-                                                            html! {
-                                                                <span>
-                                                                    { if j > 0 { " + " } else { "" } }
-                                                                    <input
-                                                                        type="number"
-                                                                        step="0.1"
-                                                                        value={self.constraint_coeffs[i][j].to_string()}
-                                                                        oninput={link.callback(move |e: InputEvent| {
-                                                                            let input: HtmlInputElement = e.target_unchecked_into();
-                                                                            Msg::UpdateConstraintCoeff(i, j, input.value().parse().unwrap_or(0.0))
-                                                                        })}
-                                                                    />
-                                                                    { format!("x{}", j+1) }
-                                                                </span>
-                                                            }
-                                                        })
-                                                    }
-                                                    // Insert your sign dropdown here:
-                                                    <select
-                                                        key={format!("constraint-{}-{}", i, self.augmented_model)}
-                                                        value={self.constraint_signs[i].clone()}
-                                                        disabled={self.augmented_model}
-                                                        oninput={link.callback(move |e: InputEvent| {
-                                                            let select: HtmlSelectElement = e.target_unchecked_into();
-                                                            Msg::UpdateConstraintSign(i, select.value())
-                                                        })}
-                                                    >
-                                                        <option value="<=">{"<="}</option>
-                                                        <option value=">=">{">="}</option>
-                                                        <option value="=">{"="}</option>
-                                                    </select>
+                {
+                    if self.solver_method == SolverMethod::QuadraticBarrier {
+                        html! {
+                            <div class="q-matrix-input">
+                                <h4>{"Quadratic term Q (objective ½ xᵀQx + cᵀx)"}</h4>
+                                {
+                                    for (0..self.variables).map(|i| html! {
+                                        <div class="q-matrix-row">
+                                            {
+                                                for (0..self.variables).map(|j| html! {
                                                     <input
                                                         type="number"
                                                         step="0.1"
-                                                        value={self.rhs_values[i].to_string()}
+                                                        value={self.q_coeffs[i][j].to_string()}
                                                         oninput={link.callback(move |e: InputEvent| {
                                                             let input: HtmlInputElement = e.target_unchecked_into();
-                                                            Msg::UpdateRHSValue(i, input.value().parse().unwrap_or(0.0))
+                                                            Msg::UpdateQCoeff(i, j, input.value().parse().unwrap_or(0.0))
                                                         })}
                                                     />
-                                                </div>
+                                                })
                                             }
-                                        })
-                                    }
-                                </div>
+                                        </div>
+                                    })
+                                }
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
 
                 <div class="alpha-selector">
-                    <label>{"Step Size (α): "}
+                    <label>{ format!("Step Size ({}): ", ctx.props().notation.step()) }
                         <input
                             type="number"
                             min="0"
@@ -361,10 +781,62 @@ impl Component for InputForm {
                             })}
                         />
                     </label>
+                    {
+                        if self.solver_method == SolverMethod::AffineScaling {
+                            html! {
+                                <>
+                                <label>{ "Step rule: " }
+                                    <select onchange={link.callback(|e: Event| {
+                                        let select: HtmlSelectElement = e.target_unchecked_into();
+                                        let k = select.value().parse::<usize>().unwrap_or(1);
+                                        Msg::SetStepStrategy(StepStrategy::ALL[k.min(StepStrategy::ALL.len() - 1)])
+                                    })}>
+                                        {
+                                            for StepStrategy::ALL.iter().enumerate().map(|(k, strategy)| html! {
+                                                <option value={k.to_string()} selected={*strategy == self.step_strategy}>
+                                                    { strategy.label() }
+                                                </option>
+                                            })
+                                        }
+                                    </select>
+                                </label>
+                                <label title="Rescales rows and columns of A before the first step; x and Z are still shown in the units entered.">{ "Equilibrate: " }
+                                    <select onchange={link.callback(|e: Event| {
+                                        let select: HtmlSelectElement = e.target_unchecked_into();
+                                        let k = select.value().parse::<usize>().unwrap_or(0);
+                                        Msg::SetScaling(Scaling::ALL[k.min(Scaling::ALL.len() - 1)])
+                                    })}>
+                                        {
+                                            for Scaling::ALL.iter().enumerate().map(|(k, scaling)| html! {
+                                                <option value={k.to_string()} selected={*scaling == self.scaling}>
+                                                    { scaling.label() }
+                                                </option>
+                                            })
+                                        }
+                                    </select>
+                                </label>
+                                </>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
                 </div>
 
                 <div class="initial-point-input">
                     <h4>{"Initial Feasible Point (x > 0)"}</h4>
+                    <label class="find-start">
+                        <input
+                            type="checkbox"
+                            checked={self.find_start}
+                            onchange={link.callback(|e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetFindStart(input.checked())
+                            })}
+                        />
+                        { "Start at the analytic center of the feasible region (found by Phase 1 and Newton centering)" }
+                    </label>
+                    <br />
                     {
                         for (0..self.variables).map(|idx| {
                             html! {
@@ -373,6 +845,7 @@ impl Component for InputForm {
                                     <input
                                         type="number"
                                         step="0.1"
+                                        disabled={self.find_start}
                                         value={self.initial_feasible[idx].to_string()}
                                         oninput={link.callback(move |e: InputEvent| {
                                             let input: HtmlInputElement = e.target_unchecked_into();
@@ -388,8 +861,31 @@ impl Component for InputForm {
                     }
                 </div>
 
-                <button onclick={link.callback(|_| Msg::Submit)}>
-                    {"Solve"}
+                {
+                    if let FormState::Editing(errors) = &self.state {
+                        html! {
+                            <ul class="validation-errors">
+                                { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                            </ul>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                { self.render_sign_fix(ctx) }
+
+                { self.render_presolve() }
+
+                { self.render_reformulation() }
+
+                { self.render_symmetry(ctx) }
+
+                <button
+                    disabled={matches!(self.state, FormState::Editing(_))}
+                    onclick={link.callback(|_| Msg::Submit)}
+                >
+                    { if matches!(self.state, FormState::Submitted(_)) { "Solve Again" } else { "Solve" } }
                 </button>
             </div>
         }
@@ -398,22 +894,473 @@ impl Component for InputForm {
 
 impl InputForm {
     fn resize(&mut self) {
-        self.objective_coeffs.resize(self.variables, 0.0);
+        for row in self.objective_coeffs.iter_mut() {
+            row.resize(self.variables, 0.0);
+        }
 
         self.constraint_coeffs
             .resize(self.constraints, vec![0.0; self.variables]);
         for row in self.constraint_coeffs.iter_mut() {
             row.resize(self.variables, 0.0);
         }
-        let default_sign = if self.augmented_model { "=".to_string() } else { "<=".to_string() };
-        self.constraint_signs
-            .resize(self.constraints, default_sign);
+        let default_sign = if self.augmented_model {
+            ConstraintSign::Equal
+        } else {
+            ConstraintSign::LessEqual
+        };
+        self.constraint_signs.resize(self.constraints, default_sign);
         self.rhs_values.resize(self.constraints, 0.0);
+        self.constraint_groups
+            .resize(self.constraints, String::new());
+        for term in self.terms.iter_mut() {
+            for expression in term.expressions.iter_mut() {
+                expression.coefficients.resize(self.variables, 0.0);
+            }
+        }
+        self.denominator.coefficients.resize(self.variables, 0.0);
 
         self.initial_feasible.resize(self.variables, 1.0);
+        self.integer_vars.resize(self.variables, false);
+
+        self.q_coeffs
+            .resize(self.variables, vec![0.0; self.variables]);
+        for row in self.q_coeffs.iter_mut() {
+            row.resize(self.variables, 0.0);
+        }
+    }
+
+    /// Replaces the whole model with `problem`; settings the pack leaves out
+    /// keep their current values.
+    fn load(&mut self, problem: &PackProblem) {
+        self.variables = problem.c.len();
+        self.constraints = problem.b.len();
+        self.objective_coeffs = vec![problem.c.clone()];
+        self.goal_weights = vec![1.0];
+        self.constraint_coeffs = problem.a.clone();
+        self.constraint_signs = problem.signs.clone();
+        self.rhs_values = problem.b.clone();
+        self.constraint_groups = problem.groups.clone();
+        self.terms.clear();
+        self.fractional = false;
+        self.denominator = AffineExpression {
+            coefficients: vec![0.0; self.variables],
+            constant: 1.0,
+        };
+        self.maximization = problem.sense.is_maximize();
+        self.initial_feasible = problem
+            .initial
+            .clone()
+            .unwrap_or_else(|| vec![1.0; self.variables]);
+        self.integer_vars = (0..self.variables)
+            .map(|j| problem.integer.contains(&j))
+            .collect();
+        self.q_coeffs = problem
+            .q
+            .clone()
+            .unwrap_or_else(|| vec![vec![0.0; self.variables]; self.variables]);
+        self.augmented_model = false;
+
+        let settings = &problem.settings;
+        self.solver_method = settings.method.unwrap_or(if problem.q.is_some() {
+            SolverMethod::QuadraticBarrier
+        } else {
+            self.solver_method
+        });
+        self.alpha = settings.alpha.unwrap_or(self.alpha);
+        self.step_strategy = settings.step_strategy.unwrap_or(self.step_strategy);
+        self.scaling = settings.scaling.unwrap_or(self.scaling);
+        self.initial_mu = settings.mu.unwrap_or(self.initial_mu);
+        // Without a given start the all-ones point is rarely feasible.
+        self.find_start = settings.find_start.unwrap_or(problem.initial.is_none());
+    }
+
+    fn revalidate(&mut self) {
+        self.state = match self.validate() {
+            Ok(problem) => FormState::Validated(problem),
+            Err(errors) => FormState::Editing(errors),
+        };
+        self.symmetry = self.detect_symmetry();
+    }
+
+    /// Symmetry of the grid as entered. Terms, ratio objectives and Q add
+    /// structure the swap test does not look at, so those models are left
+    /// alone.
+    fn detect_symmetry(&self) -> Option<Symmetry> {
+        if !self.terms.is_empty()
+            || self.fractional
+            || self.solver_method == SolverMethod::QuadraticBarrier
+        {
+            return None;
+        }
+        symmetry::detect(
+            &DMatrix::from_fn(self.constraints, self.variables, |i, j| {
+                self.constraint_coeffs[i][j]
+            }),
+            &DVector::from_vec(self.rhs_values.clone()),
+            &self.objective(),
+            &self.constraint_signs,
+            &self.integer_vars,
+        )
     }
 
-    fn create_matrix_form(&self) -> (DMatrix<f64>, DVector<f64>, DVector<f64>) {
+    /// Appends the ordering rows of `symmetry` in their own group.
+    fn add_breaking_rows(&mut self, symmetry: &Symmetry) {
+        for (coefficients, sign, rhs) in symmetry.breaking_rows(self.variables) {
+            self.constraints += 1;
+            self.resize();
+            let i = self.constraints - 1;
+            self.constraint_coeffs[i] = coefficients;
+            self.constraint_signs[i] = sign;
+            self.rhs_values[i] = rhs;
+            self.constraint_groups[i] = SYMMETRY_GROUP.to_string();
+        }
+    }
+
+    /// Checks that the model is well-formed and builds the solver input.
+    fn validate(&self) -> Result<ValidatedProblem, Vec<String>> {
+        let mut errors = vec![];
+
+        if self.variables == 0 || self.constraints == 0 {
+            errors.push("The model needs at least one variable and one constraint.".to_string());
+            return Err(errors);
+        }
+
+        let all_values = self
+            .objective_coeffs
+            .iter()
+            .flatten()
+            .chain(self.goal_weights.iter())
+            .chain(self.constraint_coeffs.iter().flatten())
+            .chain(self.rhs_values.iter())
+            .chain(self.initial_feasible.iter())
+            .chain([&self.numerator_constant, &self.denominator.constant])
+            .chain(self.denominator.coefficients.iter());
+        let terms_finite = self.terms.iter().all(|term| {
+            let value = match term.placement {
+                Placement::Objective(weight) => weight,
+                Placement::Constraint(_, rhs) => rhs,
+            };
+            value.is_finite()
+                && term
+                    .expressions
+                    .iter()
+                    .all(|e| e.constant.is_finite() && e.coefficients.iter().all(|v| v.is_finite()))
+        });
+        if all_values.clone().any(|v| !v.is_finite()) || !terms_finite {
+            errors.push("All coefficients must be finite numbers.".to_string());
+        }
+
+        if self.objective_coeffs.len() > 1 {
+            for (k, row) in self.objective_coeffs.iter().enumerate() {
+                if row.iter().all(|&v| v == 0.0) {
+                    errors.push(format!("Goal {} has no nonzero coefficient.", k + 1));
+                }
+                if self.goal_weights[k] <= 0.0 {
+                    errors.push(format!("Goal {} needs a positive weight.", k + 1));
+                }
+            }
+        }
+        let objective_terms = self
+            .terms
+            .iter()
+            .any(|term| matches!(term.placement, Placement::Objective(_)));
+        let constant_ratio = self.fractional && self.numerator_constant != 0.0;
+        if self.objective().iter().all(|&v| v == 0.0) && !objective_terms && !constant_ratio {
+            errors.push("The objective function has no nonzero coefficient.".to_string());
+        }
+
+        for (i, row) in self.constraint_coeffs.iter().enumerate() {
+            if row.iter().all(|&v| v == 0.0) {
+                errors.push(format!("Constraint {} has no nonzero coefficient.", i + 1));
+            }
+        }
+
+        let inconsistent = self.inconsistent_rows();
+        if !inconsistent.is_empty() {
+            errors.push(format!(
+                "In \"Already Augmented\" mode every constraint must be an equality, but {} {} <= or >=.",
+                row_names(&inconsistent),
+                if inconsistent.len() == 1 { "uses" } else { "use" }
+            ));
+        }
+
+        if self.solver_method.is_interior() {
+            let checked_initial = if self.find_start {
+                None
+            } else {
+                self.initial_feasible.iter().position(|&v| v <= 0.0)
+            };
+            if let Some(idx) = checked_initial {
+                errors.push(format!(
+                    "The initial point must be strictly positive (x{} = {}).",
+                    idx + 1,
+                    self.initial_feasible[idx]
+                ));
+            }
+            if self.alpha <= 0.0 {
+                errors.push("The step size α must be greater than 0.".to_string());
+            }
+        }
+
+        let quadratic = if self.solver_method == SolverMethod::QuadraticBarrier {
+            match self.quadratic_matrix() {
+                Ok(q) => Some(q),
+                Err(message) => {
+                    errors.push(message);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if self.augmented_model && !self.terms.is_empty() {
+            errors.push(
+                "|·|, max and min terms add rows and columns, which needs Auto-Augment."
+                    .to_string(),
+            );
+        }
+
+        if self.fractional {
+            errors.extend(self.fractional_errors(objective_terms));
+        }
+
+        let (linear, fractional) = match self.linear_model() {
+            Ok((linear, _, fractional)) => (Some(linear), fractional),
+            Err(message) => {
+                errors.push(message);
+                (None, None)
+            }
+        };
+        let presolve = match &linear {
+            Some(linear) => self.presolve(linear).unwrap_or_else(|message| {
+                errors.push(message);
+                None
+            }),
+            None => None,
+        };
+
+        let Some(linear) = linear.filter(|_| errors.is_empty()) else {
+            return Err(errors);
+        };
+
+        let StandardForm {
+            a,
+            b,
+            c,
+            initial,
+            augmentation,
+        } = self.create_matrix_form(&linear, presolve.as_ref());
+
+        // Slack and artificial columns carry no quadratic cost.
+        let quadratic = quadratic.map(|q| {
+            let n = a.ncols();
+            let mut padded = DMatrix::zeros(n, n);
+            padded.view_mut((0, 0), q.shape()).copy_from(&q);
+            padded
+        });
+
+        // Columns the terms added are not integer.
+        let mut integer_vars = self.integer_vars.clone();
+        integer_vars.resize(linear.a.ncols(), false);
+        let mut variable_labels = default_variable_labels(&augmentation, a.ncols());
+        // Rows the terms and a ratio objective appended belong to no group.
+        let group = |i: usize| {
+            self.constraint_groups
+                .get(i)
+                .filter(|name| !name.is_empty())
+                .cloned()
+        };
+        let (constraint_signs, constraint_labels, constraint_groups, integer_variables) =
+            match &presolve {
+                Some(presolve) => {
+                    // Shifted variables are x'_j = x_j - l_j.
+                    for (k, &j) in presolve.kept_columns.iter().enumerate() {
+                        let prime = if presolve.lower[j] != 0.0 { "'" } else { "" };
+                        variable_labels[k] = format!("{}{}", linear.variable_labels[j], prime);
+                    }
+                    let rows = presolve.row_sources();
+                    let signs = rows
+                        .iter()
+                        .enumerate()
+                        .map(|(r, &i)| {
+                            if r < presolve.kept_rows.len() {
+                                linear.signs[i]
+                            } else {
+                                ConstraintSign::LessEqual
+                            }
+                        })
+                        .collect();
+                    (
+                        signs,
+                        rows.iter()
+                            .map(|&i| linear.constraint_labels[i].clone())
+                            .collect(),
+                        rows.iter().map(|&i| group(i)).collect(),
+                        presolve
+                            .kept_columns
+                            .iter()
+                            .map(|&j| integer_vars[j])
+                            .collect(),
+                    )
+                }
+                None => {
+                    variable_labels[..linear.a.ncols()].clone_from_slice(&linear.variable_labels);
+                    (
+                        linear.signs.clone(),
+                        linear.constraint_labels.clone(),
+                        (0..linear.a.nrows()).map(group).collect(),
+                        integer_vars,
+                    )
+                }
+            };
+
+        Ok(ValidatedProblem {
+            method: self.solver_method,
+            sense: self.sense(),
+            a,
+            b,
+            quadratic,
+            c,
+            initial,
+            constraint_signs,
+            integer_variables,
+            variable_labels,
+            constraint_labels,
+            constraint_groups,
+            options: StartOptions {
+                alpha: self.alpha,
+                mu: self.initial_mu,
+                find_start: self.find_start && self.solver_method.is_interior(),
+                step_strategy: self.step_strategy,
+                scaling: self.scaling,
+            },
+            is_augmented: self.augmented_model,
+            augmentation,
+            transportation: None,
+            regression: None,
+            fractional,
+            presolve,
+            goals: self.goals(),
+        })
+    }
+
+    /// The goal rows with their weights, or none for a single objective.
+    fn goals(&self) -> Vec<Goal> {
+        if self.objective_coeffs.len() < 2 {
+            return vec![];
+        }
+        self.objective_coeffs
+            .iter()
+            .zip(&self.goal_weights)
+            .map(|(row, &weight)| Goal {
+                weight,
+                coefficients: DVector::from_vec(row.clone()),
+            })
+            .collect()
+    }
+
+    /// The objective the solvers see: the weighted sum of the goals, or the
+    /// single row as entered.
+    fn objective(&self) -> DVector<f64> {
+        match self.goals().as_slice() {
+            [] => DVector::from_vec(self.objective_coeffs[0].clone()),
+            goals => weighted_objective(goals),
+        }
+    }
+
+    /// Rows that are inequalities although the model claims to be augmented.
+    fn inconsistent_rows(&self) -> Vec<usize> {
+        if !self.augmented_model {
+            return vec![];
+        }
+        (0..self.constraints)
+            .filter(|&i| self.constraint_signs[i] != ConstraintSign::Equal)
+            .collect()
+    }
+
+    /// Turns each listed inequality row into an equality by appending a slack
+    /// (for <=) or surplus (for >=) column, valued so the current initial
+    /// point still satisfies the row when it can.
+    fn add_slack_columns(&mut self, rows: &[usize]) {
+        for &i in rows {
+            let coefficient = match self.constraint_signs[i] {
+                ConstraintSign::LessEqual => 1.0,
+                ConstraintSign::GreaterEqual => -1.0,
+                ConstraintSign::Equal => continue,
+            };
+            let lhs: f64 = (0..self.variables)
+                .map(|j| self.constraint_coeffs[i][j] * self.initial_feasible[j])
+                .sum();
+            let slack = coefficient * (self.rhs_values[i] - lhs);
+
+            self.variables += 1;
+            self.resize();
+            let j = self.variables - 1;
+            self.constraint_coeffs[i][j] = coefficient;
+            self.initial_feasible[j] = if slack > 1e-6 { slack } else { 1.0 };
+            self.constraint_signs[i] = ConstraintSign::Equal;
+        }
+    }
+
+    fn render_sign_fix(&self, ctx: &Context<Self>) -> Html {
+        let rows = self.inconsistent_rows();
+        if rows.is_empty() {
+            return html! {};
+        }
+        let link = ctx.link();
+        let fits = self.variables + rows.len() <= ctx.props().max_variables;
+        html! {
+            <div class="sign-fix">
+                <p>
+                    { format!(
+                        "{} {}, but augmented mode expects A x = b. Either:",
+                        row_names(&rows),
+                        if rows.len() == 1 { "is an inequality" } else { "are inequalities" }
+                    ) }
+                </p>
+                <button onclick={link.callback(|_| Msg::SetAugmentedModel(false))}>
+                    { "Switch to Auto-Augment" }
+                </button>
+                <button
+                    disabled={!fits}
+                    title={if fits { "" } else { "Too many variables for the form" }}
+                    onclick={link.callback(|_| Msg::AugmentInconsistentRows)}
+                >
+                    { format!("Add slack/surplus columns to {}", row_names(&rows)) }
+                </button>
+            </div>
+        }
+    }
+
+    /// The Q grid symmetrized as (Q + Q^T) / 2, rejected unless it is positive
+    /// semidefinite so the barrier subproblems stay convex.
+    fn quadratic_matrix(&self) -> Result<DMatrix<f64>, String> {
+        let n = self.variables;
+        let q = DMatrix::from_fn(n, n, |i, j| self.q_coeffs[i][j]);
+        if q.iter().any(|v| !v.is_finite()) {
+            return Err("All Q entries must be finite numbers.".to_string());
+        }
+        let q = (&q + q.transpose()) * 0.5;
+        let smallest = SymmetricEigen::new(q.clone())
+            .eigenvalues
+            .iter()
+            .copied()
+            .fold(f64::INFINITY, f64::min);
+        if smallest < -1e-9 {
+            return Err(format!(
+                "Q must be positive semidefinite for a convex QP (smallest eigenvalue {:.4}).",
+                smallest
+            ));
+        }
+        Ok(q)
+    }
+
+    fn create_matrix_form(
+        &self,
+        linear: &LinearModel,
+        presolve: Option<&Presolve>,
+    ) -> StandardForm {
         if self.augmented_model {
             // Already augmented - just create matrices directly
             let m = self.constraints;
@@ -425,68 +1372,627 @@ impl InputForm {
                     a_data.push(self.constraint_coeffs[i][j]);
                 }
             }
-            let a_matrix = DMatrix::from_row_slice(m, n, &a_data);
 
-            let b_vector = DVector::from_iterator(m, self.rhs_values.iter().copied());
+            StandardForm {
+                a: DMatrix::from_row_slice(m, n, &a_data),
+                b: DVector::from_iterator(m, self.rhs_values.iter().copied()),
+                c: self.objective(),
+                // Already augmented - use user input as is
+                initial: self.initial_feasible.clone(),
+                augmentation: Augmentation {
+                    original_variables: n,
+                    row_multipliers: vec![1.0; m],
+                    ..Augmentation::default()
+                },
+            }
+        } else {
+            Self::create_big_m_form(linear, self.sense(), presolve)
+        }
+    }
 
-            let c_vector = DVector::from_vec(self.objective_coeffs.clone());
+    fn create_big_m_form(
+        linear: &LinearModel,
+        sense: Sense,
+        presolve: Option<&Presolve>,
+    ) -> StandardForm {
+        match presolve {
+            Some(presolve) => {
+                let reduced = presolve.reduce(
+                    &linear.a,
+                    &linear.b,
+                    &linear.c,
+                    &linear.signs,
+                    &linear.initial,
+                );
+                big_m_form(
+                    &reduced.a,
+                    &reduced.b,
+                    &reduced.c,
+                    &reduced.signs,
+                    &reduced.initial,
+                    sense,
+                )
+            }
+            None => big_m_form(
+                &linear.a,
+                &linear.b,
+                &linear.c,
+                &linear.signs,
+                &linear.initial,
+                sense,
+            ),
+        }
+    }
 
-            (a_matrix, b_vector, c_vector)
+    fn sense(&self) -> Sense {
+        if self.maximization {
+            Sense::Maximize
         } else {
-            // Auto-augment: convert inequalities to equalities by adding slack variables
-            let m = self.constraints;
-            
-            // Count how many slack variables we need
-            let slack_count = self.constraint_signs.iter()
-                .filter(|&sign| sign == "<=" || sign == ">=")
-                .count();
-            
-            let n = self.variables + slack_count;
-            
-            // Build the augmented matrix A and vector b
-            let mut a_data = Vec::with_capacity(m * n);
-            let mut b_data = Vec::with_capacity(m);
-            
-            let mut slack_index = 0;
-            
-            for i in 0..m {
-                let sign = &self.constraint_signs[i];
-                
-                // Determine multiplier for >= constraints
-                let multiplier = if sign == ">=" { -1.0 } else { 1.0 };
-                
-                // Add original variable coefficients
-                for j in 0..self.variables {
-                    a_data.push(multiplier * self.constraint_coeffs[i][j]);
-                }
-                
-                // Add slack variable coefficients
-                for s in 0..slack_count {
-                    if (sign == "<=" || sign == ">=") && s == slack_index {
-                        a_data.push(1.0);  // This slack variable belongs to this constraint
+            Sense::Minimize
+        }
+    }
+
+    /// What a ratio objective rules out: the substitution y = t x breaks
+    /// goals, objective terms, integrality and a quadratic objective.
+    fn fractional_errors(&self, objective_terms: bool) -> Vec<String> {
+        let mut errors = vec![];
+        if self.augmented_model {
+            errors.push(
+                "A ratio objective adds the column t and a normalization row, which needs Auto-Augment."
+                    .to_string(),
+            );
+        }
+        if self.objective_coeffs.len() > 1 {
+            errors
+                .push("A ratio objective takes a single numerator, not several goals.".to_string());
+        }
+        if objective_terms {
+            errors.push(
+                "|·|, max and min terms can only be constraints when the objective is a ratio."
+                    .to_string(),
+            );
+        }
+        if self.integer_vars.iter().any(|&integer| integer) {
+            errors.push(
+                "Integer variables cannot keep their integrality through y = t x.".to_string(),
+            );
+        }
+        if self.solver_method == SolverMethod::QuadraticBarrier {
+            errors.push("A ratio objective is linear-fractional, not quadratic.".to_string());
+        }
+        errors
+    }
+
+    /// The grid as rows over x1..xn, with the |·|, max and min terms
+    /// rewritten into extra rows and columns and, for a ratio objective,
+    /// the Charnes–Cooper model over (y, t).
+    fn linear_model(
+        &self,
+    ) -> Result<(LinearModel, Vec<Rewrite>, Option<FractionalLayout>), String> {
+        let grid = LinearModel {
+            a: DMatrix::from_fn(self.constraints, self.variables, |i, j| {
+                self.constraint_coeffs[i][j]
+            }),
+            b: DVector::from_vec(self.rhs_values.clone()),
+            c: self.objective(),
+            signs: self.constraint_signs.clone(),
+            initial: self.initial_feasible.clone(),
+            variable_labels: (1..=self.variables).map(|j| format!("x{}", j)).collect(),
+            constraint_labels: (0..self.constraints).map(constraint_label).collect(),
+        };
+        let (linear, rewrites) = reformulate(grid, &self.terms, self.sense())?;
+        if !self.fractional {
+            return Ok((linear, rewrites, None));
+        }
+        let objective = FractionalObjective {
+            numerator: AffineExpression {
+                coefficients: self.objective_coeffs[0].clone(),
+                constant: self.numerator_constant,
+            },
+            denominator: self.denominator.clone(),
+        };
+        let (linear, layout) = charnes_cooper(linear, &objective)?;
+        Ok((linear, rewrites, Some(layout)))
+    }
+
+    /// Singleton rows as bounds, when presolve applies: auto-augmented
+    /// linear models only, since the shift would change a quadratic's linear
+    /// term.
+    fn presolve(&self, linear: &LinearModel) -> Result<Option<Presolve>, String> {
+        if !self.presolve_bounds
+            || self.augmented_model
+            || self.solver_method == SolverMethod::QuadraticBarrier
+        {
+            return Ok(None);
+        }
+        let mut integer_vars = self.integer_vars.clone();
+        integer_vars.resize(linear.a.ncols(), false);
+        Presolve::extract(
+            &linear.a,
+            &linear.b,
+            &linear.c,
+            &linear.signs,
+            &integer_vars,
+        )
+    }
+
+    /// Which rows presolve turned into bounds, which columns it merged, and
+    /// how much smaller A got.
+    fn render_presolve(&self) -> Html {
+        let (FormState::Validated(problem) | FormState::Submitted(problem)) = &self.state else {
+            return html! {};
+        };
+        let Some(presolve) = &problem.presolve else {
+            return html! {};
+        };
+        let reduced_rows = presolve.row_sources().len();
+        let (rows, columns) = self
+            .linear_model()
+            .map_or((self.constraints, self.variables), |(linear, _, _)| {
+                linear.a.shape()
+            });
+        html! {
+            <div class="presolve-summary">
+                <p>
+                    { format!(
+                        "Presolve: {} × {} constraint matrix becomes {} × {} before augmentation.",
+                        rows,
+                        columns,
+                        reduced_rows,
+                        presolve.kept_columns.len()
+                    ) }
+                </p>
+                <ul>
+                    {
+                        for presolve.extractions.iter().map(|e| {
+                            let effect = match e.kind {
+                                BoundKind::Lower => format!("lower bound, solved as x{}' = x{} - {}", e.variable + 1, e.variable + 1, e.value),
+                                BoundKind::Upper => "upper bound, kept as a single bound row".to_string(),
+                                BoundKind::Fixed => "fixed value, column removed".to_string(),
+                                BoundKind::Redundant => "redundant, dropped".to_string(),
+                            };
+                            html! {
+                                <li>
+                                    { format!("C{}: x{} {} {} → {}", e.row + 1, e.variable + 1, e.sign.symbol(), e.value, effect) }
+                                </li>
+                            }
+                        })
+                    }
+                    {
+                        for presolve.merges.iter().map(|merge| html! {
+                            <li>
+                                { format!(
+                                    "column x{removed} = {ratio} × column x{kept}, costs included → merged: x{kept} stands for x{kept} + {ratio}·x{removed}, and x{removed} is reported as 0 instead of tying degenerately with x{kept}",
+                                    removed = merge.removed + 1,
+                                    kept = merge.kept + 1,
+                                    ratio = merge.ratio
+                                ) }
+                            </li>
+                        })
+                    }
+                </ul>
+            </div>
+        }
+    }
+
+    /// The groups of interchangeable variables and rows, why they matter,
+    /// and a button ordering each group.
+    fn render_symmetry(&self, ctx: &Context<Self>) -> Html {
+        let Some(symmetry) = &self.symmetry else {
+            return html! {};
+        };
+        let names = |group: &[usize], name: fn(usize) -> String| {
+            group
+                .iter()
+                .map(|&k| name(k))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let variable = |j: usize| format!("x{}", j + 1);
+        let orderings: Vec<String> = symmetry
+            .variable_groups
+            .iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|&j| variable(j))
+                    .collect::<Vec<_>>()
+                    .join(" ≥ ")
+            })
+            .collect();
+        html! {
+            <div class="presolve-summary symmetry-summary">
+                <p>{ "Symmetry: swapping two variables of a group, together with the constraints they appear in, leaves the model unchanged." }</p>
+                <ul>
+                    {
+                        for symmetry.variable_groups.iter().map(|group| html! {
+                            <li>{ format!("{} are interchangeable", names(group, variable)) }</li>
+                        })
+                    }
+                    {
+                        for symmetry.constraint_groups.iter().map(|group| html! {
+                            <li>{ format!("{} trade places with them", names(group, constraint_label)) }</li>
+                        })
+                    }
+                </ul>
+                <p class="symmetry-note">
+                    { "Mirror images of a point have the same objective, so an optimum that is not itself symmetric is not unique: the simplex method can end at any of its mirror images, often after degenerate pivots between them, and interior methods head for the middle of the optimal face instead of a vertex." }
+                </p>
+                <button
+                    disabled={self.augmented_model}
+                    title={if self.augmented_model { "Augmented mode expects A x = b; switch to Auto-Augment to add ≥ rows." } else { "Keeps one of the mirror-image optima and cuts the others off." }}
+                    onclick={ctx.link().callback(|_| Msg::AddSymmetryBreaking)}
+                >
+                    { format!("Add symmetry-breaking constraints {}", orderings.join(", ")) }
+                </button>
+            </div>
+        }
+    }
+
+    /// |·|, max and min terms, each with its expressions and where it is
+    /// used.
+    /// The constraint rows, with each named group folded into its own
+    /// section in the order the groups first appear.
+    fn render_constraints(&self, ctx: &Context<Self>) -> Html {
+        let mut sections: Vec<(&str, Vec<usize>)> = vec![];
+        for (i, name) in self.constraint_groups.iter().enumerate() {
+            match sections
+                .iter_mut()
+                .find(|(group, _)| *group == name.as_str())
+            {
+                Some((_, rows)) => rows.push(i),
+                None => sections.push((name.as_str(), vec![i])),
+            }
+        }
+
+        html! {
+            <div class="constraints">
+                {
+                    for sections.into_iter().map(|(name, rows)| {
+                        let count = rows.len();
+                        let rows = html! { for rows.into_iter().map(|i| self.render_constraint_row(ctx, i)) };
+                        if name.is_empty() {
+                            rows
+                        } else {
+                            html! {
+                                <details class="constraint-group-section" open=true>
+                                    <summary>
+                                        { name }
+                                        <span class="constraint-group-count">
+                                            { format!("{} {}", count, if count == 1 { "row" } else { "rows" }) }
+                                        </span>
+                                    </summary>
+                                    { rows }
+                                </details>
+                            }
+                        }
+                    })
+                }
+            </div>
+        }
+    }
+
+    fn render_constraint_row(&self, ctx: &Context<Self>, i: usize) -> Html {
+        let link = ctx.link();
+        let label = constraint_label(i);
+        let flagged = ctx
+            .props()
+            .flagged_constraints
+            .iter()
+            .find(|(flagged, _)| *flagged == label)
+            .map(|(_, why)| why.clone());
+        html! {
+            <div
+                class={classes!("constraint-row", flagged.is_some().then_some("flagged-row"))}
+                title={flagged.clone()}
+            >
+                {
+                    for (0..self.variables).map(|j| {
+                        html! {
+                            <span>
+                                { if j > 0 { " + " } else { "" } }
+                                <input
+                                    type="number"
+                                    step="0.1"
+                                    value={self.constraint_coeffs[i][j].to_string()}
+                                    oninput={link.callback(move |e: InputEvent| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        Msg::UpdateConstraintCoeff(i, j, input.value().parse().unwrap_or(0.0))
+                                    })}
+                                />
+                                { format!("x{}", j+1) }
+                            </span>
+                        }
+                    })
+                }
+                // Insert your sign dropdown here:
+                <select
+                    key={format!("constraint-{}-{}", i, self.augmented_model)}
+                    value={self.constraint_signs[i].symbol()}
+                    oninput={link.callback(move |e: InputEvent| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let sign = ConstraintSign::from_symbol(&select.value())
+                            .unwrap_or(ConstraintSign::LessEqual);
+                        Msg::UpdateConstraintSign(i, sign)
+                    })}
+                >
+                    <option value="<=">{"<="}</option>
+                    <option value=">=">{">="}</option>
+                    <option value="=">{"="}</option>
+                </select>
+                <input
+                    type="number"
+                    step="0.1"
+                    value={self.rhs_values[i].to_string()}
+                    oninput={link.callback(move |e: InputEvent| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::UpdateRHSValue(i, input.value().parse().unwrap_or(0.0))
+                    })}
+                />
+                <input
+                    type="text"
+                    class="constraint-group"
+                    placeholder="group"
+                    value={self.constraint_groups[i].clone()}
+                    onchange={link.callback(move |e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::UpdateConstraintGroup(i, input.value())
+                    })}
+                />
+            </div>
+        }
+    }
+
+    /// The ratio toggle and, when it is on, the numerator's constant and the
+    /// denominator row under the objective.
+    fn render_denominator(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let toggle = html! {
+            <label class="fractional-toggle">
+                <input
+                    type="checkbox"
+                    checked={self.fractional}
+                    disabled={self.objective_coeffs.len() > 1}
+                    onchange={link.callback(|e: Event| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        Msg::SetFractional(input.checked())
+                    })}
+                />
+                { "Ratio objective (c^T x + c0) / (d^T x + d0)" }
+            </label>
+        };
+        if !self.fractional {
+            return toggle;
+        }
+
+        html! {
+            <div class="fractional-objective">
+                { toggle }
+                <div class="goal-row">
+                    { "numerator + " }
+                    <input
+                        type="number"
+                        step="0.1"
+                        value={self.numerator_constant.to_string()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            Msg::UpdateNumeratorConstant(input.value().parse().unwrap_or(0.0))
+                        })}
+                    />
+                </div>
+                <div class="goal-row fractional-denominator">
+                    { "÷ (" }
+                    {
+                        for self.denominator.coefficients.iter().enumerate().map(|(j, coeff)| html! {
+                            <span>
+                                { if j > 0 { " + " } else { "" } }
+                                <input
+                                    type="number"
+                                    step="0.1"
+                                    value={coeff.to_string()}
+                                    oninput={link.callback(move |e: InputEvent| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        Msg::UpdateDenominatorCoeff(j, input.value().parse().unwrap_or(0.0))
+                                    })}
+                                />
+                                { format!("x{}", j + 1) }
+                            </span>
+                        })
+                    }
+                    { " + " }
+                    <input
+                        type="number"
+                        step="0.1"
+                        value={self.denominator.constant.to_string()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            Msg::UpdateDenominatorConstant(input.value().parse().unwrap_or(0.0))
+                        })}
+                    />
+                    { " )" }
+                </div>
+            </div>
+        }
+    }
+
+    fn render_terms(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="piecewise-terms">
+                <h4>{ "|·|, max and min terms" }</h4>
+                {
+                    for self.terms.iter().enumerate().map(|(k, term)| {
+                        let (placement, value) = match term.placement {
+                            Placement::Objective(weight) => ("objective", weight),
+                            Placement::Constraint(sign, rhs) => (sign.symbol(), rhs),
+                        };
+                        let removable = term.expressions.len() > 1;
+                        html! {
+                            <div class="term-row">
+                                <span class="term-name">{ format!("T{}", k + 1) }</span>
+                                <select onchange={link.callback(move |e: Event| {
+                                    let select: HtmlSelectElement = e.target_unchecked_into();
+                                    let kind = select.value().parse::<usize>().unwrap_or(0);
+                                    Msg::SetTermKind(k, TermKind::ALL[kind.min(TermKind::ALL.len() - 1)])
+                                })}>
+                                    {
+                                        for TermKind::ALL.iter().enumerate().map(|(index, kind)| html! {
+                                            <option value={index.to_string()} selected={*kind == term.kind}>
+                                                { kind.label() }
+                                            </option>
+                                        })
+                                    }
+                                </select>
+                                { " (" }
+                                {
+                                    for term.expressions.iter().enumerate().map(|(e, expression)| html! {
+                                        <span class="term-expression">
+                                            { if e > 0 { ", " } else { "" } }
+                                            {
+                                                for expression.coefficients.iter().enumerate().map(|(j, coeff)| html! {
+                                                    <span>
+                                                        { if j > 0 { " + " } else { "" } }
+                                                        <input
+                                                            type="number"
+                                                            step="0.1"
+                                                            value={coeff.to_string()}
+                                                            oninput={link.callback(move |ev: InputEvent| {
+                                                                let input: HtmlInputElement = ev.target_unchecked_into();
+                                                                Msg::UpdateTermCoeff(k, e, j, input.value().parse().unwrap_or(0.0))
+                                                            })}
+                                                        />
+                                                        { format!("x{}", j + 1) }
+                                                    </span>
+                                                })
+                                            }
+                                            { " + " }
+                                            <input
+                                                type="number"
+                                                step="0.1"
+                                                value={expression.constant.to_string()}
+                                                oninput={link.callback(move |ev: InputEvent| {
+                                                    let input: HtmlInputElement = ev.target_unchecked_into();
+                                                    Msg::UpdateTermConstant(k, e, input.value().parse().unwrap_or(0.0))
+                                                })}
+                                            />
+                                            {
+                                                if removable {
+                                                    html! {
+                                                        <button class="goal-remove" onclick={link.callback(move |_| Msg::RemoveTermExpression(k, e))}>{ "✕" }</button>
+                                                    }
+                                                } else {
+                                                    html! {}
+                                                }
+                                            }
+                                        </span>
+                                    })
+                                }
+                                {
+                                    if term.kind == TermKind::Abs {
+                                        html! {}
+                                    } else {
+                                        html! {
+                                            <button class="term-add-expression" onclick={link.callback(move |_| Msg::AddTermExpression(k))}>{ "+" }</button>
+                                        }
+                                    }
+                                }
+                                { ") " }
+                                <select onchange={link.callback(move |e: Event| {
+                                    let select: HtmlSelectElement = e.target_unchecked_into();
+                                    Msg::SetTermPlacement(k, ConstraintSign::from_symbol(&select.value()))
+                                })}>
+                                    <option value="objective" selected={placement == "objective"}>{ "in Z, weight" }</option>
+                                    <option value="<=" selected={placement == "<="}>{ "<=" }</option>
+                                    <option value=">=" selected={placement == ">="}>{ ">=" }</option>
+                                </select>
+                                <input
+                                    type="number"
+                                    step="0.1"
+                                    value={value.to_string()}
+                                    oninput={link.callback(move |e: InputEvent| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        Msg::UpdateTermValue(k, input.value().parse().unwrap_or(0.0))
+                                    })}
+                                />
+                                <button class="goal-remove" onclick={link.callback(move |_| Msg::RemoveTerm(k))}>{ "✕" }</button>
+                            </div>
+                        }
+                    })
+                }
+                <button class="goal-add" onclick={link.callback(|_| Msg::AddTerm)}>{ "Add Term" }</button>
+            </div>
+        }
+    }
+
+    /// The variables and rows each term became, and the whole model the
+    /// solver receives before augmentation.
+    fn render_reformulation(&self) -> Html {
+        if (self.terms.is_empty() && !self.fractional)
+            || matches!(self.state, FormState::Editing(_))
+        {
+            return html! {};
+        }
+        let Ok((linear, rewrites, fractional)) = self.linear_model() else {
+            return html! {};
+        };
+        html! {
+            <div class="presolve-summary reformulation-summary">
+                {
+                    if rewrites.is_empty() {
+                        html! {}
                     } else {
-                        a_data.push(0.0);  // Other slack variables are 0 for this constraint
+                        html! { <p>{ "Terms rewritten as linear rows:" }</p> }
                     }
                 }
-                
-                // Advance slack index if we used a slack variable
-                if sign == "<=" || sign == ">=" {
-                    slack_index += 1;
-                }
-                
-                // Add RHS value
-                b_data.push(multiplier * self.rhs_values[i]);
-            }
-            
-            let a_matrix = DMatrix::from_row_slice(m, n, &a_data);
-            let b_vector = DVector::from_vec(b_data);
-            
-            // Extend objective function with zeros for slack variables
-            let mut c_vec = self.objective_coeffs.clone();
-            c_vec.resize(n, 0.0);
-            let c_vector = DVector::from_vec(c_vec);
-            
-            (a_matrix, b_vector, c_vector)
+                <ul>
+                    {
+                        for rewrites.iter().map(|rewrite| html! {
+                            <li>
+                                { &rewrite.term }
+                                {
+                                    if rewrite.variables.is_empty() {
+                                        html! {}
+                                    } else {
+                                        html! { { format!(" → new variables {}", rewrite.variables.join(", ")) } }
+                                    }
+                                }
+                                <ul>
+                                    { for rewrite.rows.iter().map(|row| html! { <li><code>{ row }</code></li> }) }
+                                </ul>
+                            </li>
+                        })
+                    }
+                </ul>
+                {
+                    if let Some(layout) = &fractional {
+                        html! {
+                            <>
+                                <p>{ "Charnes–Cooper transformation:" }</p>
+                                <ol class="fractional-steps">
+                                    { for layout.steps.iter().map(|step| html! { <li><code>{ step }</code></li> }) }
+                                </ol>
+                            </>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <details>
+                    <summary>{ "Transformed model" }</summary>
+                    <pre class="reformulated-model">
+                        { linear.describe_objective(self.sense()) }
+                        { "\n" }
+                        { linear.describe_rows().join("\n") }
+                    </pre>
+                </details>
+            </div>
         }
     }
 }
+
+fn row_names(rows: &[usize]) -> String {
+    rows.iter()
+        .map(|i| format!("C{}", i + 1))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,181 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::inspector::{InspectorState, StateEdit};
+use crate::interior::StepStrategy;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// State of the affine-scaling run; `None` when no such run is active.
+    pub state: Option<InspectorState>,
+    /// Why the last edit was refused.
+    #[prop_or_default]
+    pub error: Option<String>,
+    pub on_edit: Callback<StateEdit>,
+    pub on_close: Callback<()>,
+}
+
+/// Developer panel showing everything the affine-scaling run holds, toggled
+/// with Alt+Shift+I. Every entry of A, b, c and x can be overwritten; the
+/// next step uses the edited state, and an edit reopens a finished run.
+pub struct StateInspector;
+
+impl Component for StateInspector {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        html! {
+            <aside class="inspector-overlay" aria-label="Solver state inspector">
+                <header>
+                    <strong>{ "Solver state" }</strong>
+                    <button onclick={props.on_close.reform(|_| ())} title="Close (Alt+Shift+I)">{ "×" }</button>
+                </header>
+                {
+                    match &props.state {
+                        None => html! { <p>{ "Start an affine-scaling run to inspect it." }</p> },
+                        Some(state) => self.render_state(ctx, state),
+                    }
+                }
+            </aside>
+        }
+    }
+}
+
+impl StateInspector {
+    fn render_state(&self, ctx: &Context<Self>, state: &InspectorState) -> Html {
+        let props = ctx.props();
+        let on_edit = props.on_edit.clone();
+        let flag = |on: bool| if on { "yes" } else { "no" };
+        html! {
+            <>
+                {
+                    if let Some(error) = &props.error {
+                        html! { <p class="inspector-error">{ error }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if state.scaled {
+                        html! { <p>{ "A, b, c and x are equilibrated: entries here are in the scaled units the steps use." }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <table>
+                    <tr><th>{ "iterations" }</th><td>{ state.iterations }</td></tr>
+                    <tr><th>{ "stop reason" }</th><td>{ state.stop_reason.map_or("none", |reason| reason.label()) }</td></tr>
+                    <tr><th>{ "α" }</th><td>{ number_input(state.alpha, on_edit.reform(StateEdit::Alpha)) }</td></tr>
+                    <tr><th>{ "adaptive fraction" }</th><td>{ number_input(state.adaptive_fraction, on_edit.reform(StateEdit::AdaptiveFraction)) }</td></tr>
+                    <tr>
+                        <th>{ "strategy" }</th>
+                        <td>
+                            <select onchange={on_edit.reform(|e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                StateEdit::Strategy(StepStrategy::from_key(&select.value()).unwrap_or_default())
+                            })}>
+                                {
+                                    for StepStrategy::ALL.iter().map(|strategy| html! {
+                                        <option value={strategy.key()} selected={*strategy == state.strategy}>{ strategy.label() }</option>
+                                    })
+                                }
+                            </select>
+                        </td>
+                    </tr>
+                    <tr>
+                        <th>{ "is_augmented" }</th>
+                        <td>
+                            <input
+                                type="checkbox"
+                                checked={state.is_augmented}
+                                onchange={on_edit.reform(|e: Event| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    StateEdit::IsAugmented(input.checked())
+                                })}
+                            />
+                        </td>
+                    </tr>
+                    <tr><th>{ "sparse A" }</th><td>{ flag(state.sparse) }</td></tr>
+                    <tr><th>{ "double-double x" }</th><td>{ flag(state.extended) }</td></tr>
+                </table>
+
+                <h4>{ "A | b" }</h4>
+                <table class="inspector-grid">
+                    {
+                        for (0..state.a.nrows()).map(|row| html! {
+                            <tr>
+                                {
+                                    for (0..state.a.ncols()).map(|col| html! {
+                                        <td>{ number_input(state.a[(row, col)], on_edit.reform(move |value| StateEdit::A { row, col, value })) }</td>
+                                    })
+                                }
+                                <td class="inspector-rhs">{ number_input(state.b[row], on_edit.reform(move |value| StateEdit::B { row, value })) }</td>
+                            </tr>
+                        })
+                    }
+                </table>
+
+                <h4>{ "c" }</h4>
+                <table class="inspector-grid">
+                    <tr>
+                        {
+                            for (0..state.c.len()).map(|col| html! {
+                                <td>{ number_input(state.c[col], on_edit.reform(move |value| StateEdit::C { col, value })) }</td>
+                            })
+                        }
+                    </tr>
+                </table>
+
+                <h4>{ "x" }</h4>
+                <table class="inspector-grid">
+                    <tr>
+                        {
+                            for (0..state.x.len()).map(|col| html! {
+                                <td>{ number_input(state.x[col], on_edit.reform(move |value| StateEdit::X { col, value })) }</td>
+                            })
+                        }
+                    </tr>
+                </table>
+
+                <details>
+                    <summary>{ "Options" }</summary>
+                    <pre>{ &state.options }</pre>
+                </details>
+                <details>
+                    <summary>{ "Text dump" }</summary>
+                    <pre>{ state.dump() }</pre>
+                </details>
+                <details>
+                    <summary>{ "JSON" }</summary>
+                    <pre>{ &state.json }</pre>
+                </details>
+            </>
+        }
+    }
+}
+
+/// Number box that sends the parsed value when it changes; text that does
+/// not parse is sent as NaN so the edit is refused with a message.
+fn number_input(value: f64, on_change: Callback<f64>) -> Html {
+    html! {
+        <input
+            type="number"
+            step="any"
+            value={value.to_string()}
+            onchange={on_change.reform(|e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                input.value().parse().unwrap_or(f64::NAN)
+            })}
+        />
+    }
+}
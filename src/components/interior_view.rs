@@ -1,4 +1,10 @@
-use crate::interior::InteriorPointIteration;
+use super::matrix_view::{MatrixData, MatrixView, VectorView};
+use crate::interior::{
+    InteriorPointIteration, Regularization, StepChoice, StepStrategy, CONDITION_WARNING,
+};
+use crate::norm::Residuals;
+use crate::notation::Notation;
+use crate::pretty;
 use nalgebra::{DMatrix, DVector};
 use yew::prelude::*;
 
@@ -8,6 +14,22 @@ pub struct Props {
 
     #[prop_or_default]
     pub iteration_data: Option<InteriorPointIteration>,
+
+    /// Constraints whose slack is below the active threshold at this iterate.
+    #[prop_or_default]
+    pub active_rows: Vec<usize>,
+
+    #[prop_or_default]
+    pub constraint_labels: Vec<String>,
+    /// Constraint group of each row, for block shading of A~.
+    #[prop_or_default]
+    pub constraint_groups: Vec<Option<String>>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
 }
 
 pub struct InteriorPointView;
@@ -31,44 +53,84 @@ impl Component for InteriorPointView {
         let d_matrix = it.map(|iter| &iter.d_matrix);
         let a_tilde = it.map(|iter| &iter.a_tilde_matrix);
         let c_tilde = it.map(|iter| &iter.c_tilde_vector);
+        let l_matrix = it.map(|iter| &iter.l_matrix);
         let p_matrix = it.map(|iter| &iter.p_matrix);
         let cp_vector = it.map(|iter| &iter.cp_vector);
         let current_x = it.map(|iter| &iter.current_x);
+        let x = props.notation.iterate(props.iteration);
+        let compact = props.compact;
+        // The sparse path leaves D, A~, L and P empty; the dense one only P,
+        // when it was not formed for display.
+        let sparse = it.is_some_and(|iter| iter.d_matrix.is_empty());
+        let projection_shown = it.is_some_and(|iter| !iter.p_matrix.is_empty());
 
         html! {
-            <div class="interior-point-view">
-                <h3>{ format!("Iteration {}", props.iteration) }</h3>
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Iteration {}", props.iteration) }{ centering_tag(it.is_some_and(|iter| iter.centering)) }</h3>
+
+                { Self::render_active(&props.active_rows, &props.constraint_labels) }
+
+                { Self::render_condition(it.map(|iter| iter.condition)) }
+
+                { Self::render_regularization(it.map(|iter| &iter.regularization)) }
+
+                { Self::render_residuals(it.map(|iter| &iter.residuals), &x) }
+
+                { Self::render_duality_gap(it.map(|iter| iter.duality_gap), &x, &props.notation) }
+
+                { Self::render_step(it.and_then(|iter| iter.step.as_ref()), it.map_or(1.0, |iter| iter.condition), &props.notation) }
+
+                {
+                    if sparse {
+                        html! {
+                            <p class="race-hint">
+                                { "A is mostly zeros, so D, A~, L and P were not formed: P c~ = c~ - A~^T y with (A~ A~^T) y = A~ c~, solved on a sparse copy of A." }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
 
                 <div class="matrix-container">
-                    <div class="matrix-box">
-                        <h4>{"D = diag(x)"}</h4>
-                        { Self::render_matrix(d_matrix) }
-                    </div>
-
-                    <div class="matrix-box">
-                        <h4>{"A~ = A * D"}</h4>
-                        { Self::render_matrix(a_tilde) }
-                    </div>
-
-                    <div class="matrix-box">
-                        <h4>{"c~ = D * c"}</h4>
-                        { Self::render_vector(c_tilde) }
-                    </div>
-
-                    <div class="matrix-box">
-                        <h4>{"P = I - A~^T (A~ A~^T)^{-1} A~"}</h4>
-                        { Self::render_matrix(p_matrix) }
-                    </div>
-
-                    <div class="matrix-box">
-                        <h4>{"P c~"}</h4>
-                        { Self::render_vector(cp_vector) }
-                    </div>
-
-                    <div class="matrix-box">
-                        <h4>{"Current x"}</h4>
-                        { Self::render_vector(current_x) }
-                    </div>
+                    {
+                        if sparse {
+                            html! {}
+                        } else {
+                            html! {
+                                <>
+                                    { Self::matrix_card(&format!("D = diag({})", x), d_matrix, Self::render_matrix(d_matrix), compact) }
+                                    { Self::matrix_card("A~ = A * D", a_tilde, Self::render_matrix_rows(a_tilde, &props.active_rows, &props.constraint_groups), compact) }
+                                </>
+                            }
+                        }
+                    }
+                    { Self::vector_card("c~ = D * c", c_tilde, compact) }
+                    {
+                        if sparse {
+                            html! {}
+                        } else {
+                            html! {
+                                <>
+                                    { Self::matrix_card("L, where A~ A~^T = L L^T", l_matrix, Self::render_matrix(l_matrix), compact) }
+                                    {
+                                        if projection_shown {
+                                            Self::matrix_card("P = I - A~^T (A~ A~^T)^{-1} A~", p_matrix, Self::render_matrix(p_matrix), compact)
+                                        } else {
+                                            html! {
+                                                <p class="race-hint">
+                                                    { "P was not formed: with A~^T = Q R, P c~ = c~ - Q (Q^T c~)." }
+                                                </p>
+                                            }
+                                        }
+                                    }
+                                </>
+                            }
+                        }
+                    }
+                    { Self::vector_card(if it.is_some_and(|iter| iter.centering) { "P 1 (centering)" } else { "P c~" }, cp_vector, compact) }
+                    { Self::vector_card(&format!("Current {}", x), current_x, compact) }
+                    { Self::vector_card(&format!("Dual estimate {}", props.notation.dual()), it.map(|iter| &iter.duals), compact) }
                 </div>
             </div>
         }
@@ -76,48 +138,256 @@ impl Component for InteriorPointView {
 }
 
 impl InteriorPointView {
-    fn render_matrix(matrix_opt: Option<&DMatrix<f64>>) -> Html {
-        if let Some(mat) = matrix_opt {
-            let (rows, cols) = mat.shape();
-            html! {
-                <table class="matrix">
-                    <tbody>
+    /// The step rule and the numbers it produced this iteration.
+    fn render_step(step: Option<&StepChoice>, condition: f64, notation: &Notation) -> Html {
+        let Some(step) = step else {
+            return html! {};
+        };
+        let fraction = match step.strategy {
+            StepStrategy::ShortStep => format!("radius {} = {:.2}", notation.step(), step.fraction),
+            StepStrategy::LongStep => format!(
+                "{} = {:.2} of the way to the boundary",
+                notation.step(),
+                step.fraction
+            ),
+            StepStrategy::Adaptive => {
+                format!("fraction {:.3} of the way to the boundary", step.fraction)
+            }
+            StepStrategy::LineSearch => format!(
+                "{:.3} of the way to the boundary after {} trial{}",
+                step.fraction,
+                step.trials.len(),
+                if step.trials.len() == 1 { "" } else { "s" }
+            ),
+            StepStrategy::Scheduled => format!(
+                "{} = {:.3} after {} rejected step{}",
+                notation.step(),
+                step.fraction,
+                step.trials.len().saturating_sub(1),
+                if step.trials.len() == 2 { "" } else { "s" }
+            ),
+        };
+        let trials: Vec<String> = step.trials.iter().map(|t| format!("{:.4}", t)).collect();
+        html! {
+            <div class="barrier-summary">
+                <span>{ step.strategy.label() }</span>
+                <span>{ fraction }</span>
+                <span>{ format!("t = {:.4}", step.length) }</span>
+                <span>{ format!("ratio test: t ≤ {:.4}", step.to_boundary) }</span>
+                {
+                    if trials.len() > 1 {
+                        html! { <span title="Step lengths the line search tried, longest first; the last was taken.">{ format!("tried t = {}", trials.join(", ")) }</span> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <span>{ format!("cond(A~ A~^T) ≈ {}", pretty::entry(condition)) }</span>
+            </div>
+        }
+    }
+
+    /// Banner for an iteration whose normal matrix is close to singular.
+    fn render_condition(condition: Option<f64>) -> Html {
+        match condition {
+            Some(condition) if condition > CONDITION_WARNING => html! {
+                <div class="condition-warning" role="alert">
+                    { format!(
+                        "A~ A~^T is ill-conditioned (cond ≈ {}): P c~ keeps only about {} correct digits, so the step may drift off A x = b.",
+                        pretty::entry(condition),
+                        (16.0 - condition.log10()).max(0.0).floor()
+                    ) }
+                </div>
+            },
+            _ => html! {},
+        }
+    }
+
+    /// Floor of D and diagonal shift the step used.
+    fn render_regularization(regularization: Option<&Regularization>) -> Html {
+        let Some(regularization) = regularization else {
+            return html! {};
+        };
+        let shift = match regularization.applied_shift {
+            Some(applied) => format!(
+                "A~ A~^T shifted by {} × its largest entry (up to {} allowed)",
+                pretty::entry(applied),
+                pretty::entry(regularization.shift)
+            ),
+            None => "no shift: P comes from a QR factorization".to_string(),
+        };
+        html! {
+            <p class="race-hint regularization-note">
+                { format!("D floored at {}; {}.", pretty::entry(regularization.floor), shift) }
+            </p>
+        }
+    }
+
+    /// KKT residuals after the step and ‖Δx‖, in the norm chosen in the
+    /// settings.
+    fn render_residuals(residuals: Option<&Residuals>, x: &str) -> Html {
+        let Some(residuals) = residuals else {
+            return html! {};
+        };
+        let norm = residuals.norm;
+        let rows = [
+            (
+                "Primal",
+                norm.format(&format!("A {} - b", x)),
+                residuals.primal,
+            ),
+            ("Dual", norm.format("min(Aᵀy - c, 0)"), residuals.dual),
+            (
+                "Complementarity",
+                norm.format(&format!("{} ∘ (Aᵀy - c)", x)),
+                residuals.complementarity,
+            ),
+            ("Step", norm.format(&format!("Δ{}", x)), residuals.step),
+        ];
+        html! {
+            <table class="kkt-table">
+                <tbody>
                     {
-                        for (0..rows).map(|r| html!{
+                        for rows.into_iter().map(|(name, formula, value)| html! {
                             <tr>
-                            {
-                                for (0..cols).map(|c| html! {
-                                    <td>{ format!("{:.4}", mat[(r, c)]) }</td>
-                                })
-                            }
+                                <th>{ name }</th>
+                                <td class="kkt-formula">{ formula }</td>
+                                <td>{ pretty::entry(value) }</td>
                             </tr>
                         })
                     }
-                    </tbody>
-                </table>
-            }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// b^T y - c^T x, the number convergence is judged by.
+    fn render_duality_gap(gap: Option<f64>, x: &str, notation: &Notation) -> Html {
+        let Some(gap) = gap else {
+            return html! {};
+        };
+        html! {
+            <p class="race-hint regularization-note">
+                { format!(
+                    "Duality gap b^T {} - c^T {} = {}{}",
+                    notation.dual(),
+                    x,
+                    pretty::entry(gap),
+                    if gap < 0.0 { " (negative: the dual estimate is not yet dual feasible)." } else { "." }
+                ) }
+            </p>
+        }
+    }
+
+    pub(crate) fn render_matrix(matrix_opt: Option<&DMatrix<f64>>) -> Html {
+        Self::render_matrix_rows(matrix_opt, &[], &[])
+    }
+
+    /// Like `render_matrix`, with `highlighted` rows marked as active
+    /// constraints and rows shaded by constraint group.
+    pub(crate) fn render_matrix_rows(
+        matrix_opt: Option<&DMatrix<f64>>,
+        highlighted: &[usize],
+        groups: &[Option<String>],
+    ) -> Html {
+        if let Some(mat) = matrix_opt {
+            html! { <MatrixView data={MatrixData::from(mat)} highlighted={highlighted.to_vec()} row_groups={groups.to_vec()} /> }
+        } else {
+            html! { <p>{"(Not available)"}</p> }
+        }
+    }
+
+    pub(crate) fn render_vector(vec_opt: Option<&DVector<f64>>) -> Html {
+        if let Some(v) = vec_opt {
+            html! { <VectorView data={MatrixData::from(v)} /> }
         } else {
             html! { <p>{"(Not available)"}</p> }
         }
     }
 
-    fn render_vector(vec_opt: Option<&DVector<f64>>) -> Html {
+    /// A vector on one line, for compact cards.
+    pub(crate) fn render_vector_inline(vec_opt: Option<&DVector<f64>>) -> Html {
         if let Some(v) = vec_opt {
+            let entries: Vec<String> = v.iter().map(|val| format!("{:.4}", val)).collect();
+            html! { <code class="vector-inline">{ format!("({})", entries.join(", ")) }</code> }
+        } else {
+            html! { <p>{"(Not available)"}</p> }
+        }
+    }
+
+    /// Card holding `body`, a rendering of `matrix`. Compact cards fold it
+    /// behind a tap and give its size in the summary.
+    pub(crate) fn matrix_card(
+        title: &str,
+        matrix: Option<&DMatrix<f64>>,
+        body: Html,
+        compact: bool,
+    ) -> Html {
+        if compact {
+            let size = matrix
+                .map(|mat| format!(" ({}×{})", mat.nrows(), mat.ncols()))
+                .unwrap_or_default();
             html! {
-                <table class="vector">
-                    <tbody>
-                    {
-                        for (0..v.len()).map(|i| html!{
-                            <tr>
-                                <td>{ format!("{:.4}", v[i]) }</td>
-                            </tr>
-                        })
-                    }
-                    </tbody>
-                </table>
+                <details class="matrix-box matrix-fold">
+                    <summary>{ format!("{}{}", title, size) }</summary>
+                    { body }
+                </details>
             }
         } else {
-            html! { <p>{"(Not available)"}</p> }
+            html! {
+                <div class="matrix-box">
+                    <h4>{ title }</h4>
+                    { body }
+                </div>
+            }
+        }
+    }
+
+    /// Card holding a vector as a column, or on one line when compact.
+    pub(crate) fn vector_card(title: &str, vector: Option<&DVector<f64>>, compact: bool) -> Html {
+        if compact {
+            html! {
+                <div class="matrix-box vector-card">
+                    <h4>{ title }</h4>
+                    { Self::render_vector_inline(vector) }
+                </div>
+            }
+        } else {
+            html! {
+                <div class="matrix-box">
+                    <h4>{ title }</h4>
+                    { Self::render_vector(vector) }
+                </div>
+            }
+        }
+    }
+
+    /// One-line list of the constraints the iterate is currently pressing against.
+    pub(crate) fn render_active(rows: &[usize], labels: &[String]) -> Html {
+        if rows.is_empty() {
+            return html! {};
         }
+        let names: Vec<String> = rows
+            .iter()
+            .map(|&i| {
+                labels
+                    .get(i)
+                    .cloned()
+                    .unwrap_or_else(|| format!("C{}", i + 1))
+            })
+            .collect();
+        html! {
+            <div class="active-constraints">
+                { format!("Near-active constraints: {}", names.join(", ")) }
+            </div>
+        }
+    }
+}
+
+/// Marks centering iterations in the heading.
+pub(crate) fn centering_tag(centering: bool) -> Html {
+    if centering {
+        html! { <span class="centering-tag">{ "centering" }</span> }
+    } else {
+        html! {}
     }
 }
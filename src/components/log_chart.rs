@@ -0,0 +1,97 @@
+use yew::prelude::*;
+
+const WIDTH: f64 = 480.0;
+const HEIGHT: f64 = 240.0;
+const PAD: f64 = 40.0;
+
+/// Values below this are drawn at the floor of the log axis.
+const LOG_FLOOR: f64 = 1e-12;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub title: AttrValue,
+    /// One positive value per iteration, oldest first.
+    pub values: Vec<f64>,
+}
+
+/// A per-iteration quantity on a log10 axis, such as the distance to the
+/// boundary or the error against the exact optimum.
+pub struct LogChart;
+
+impl Component for LogChart {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let values = &props.values;
+        if values.is_empty() {
+            return html! {};
+        }
+
+        let logs: Vec<f64> = values.iter().map(|&d| d.max(LOG_FLOOR).log10()).collect();
+        let top = logs.iter().fold(f64::MIN, |acc, &v| acc.max(v)).ceil();
+        let mut bottom = logs.iter().fold(f64::MAX, |acc, &v| acc.min(v)).floor();
+        if bottom >= top {
+            bottom = top - 1.0;
+        }
+
+        let span = (values.len() - 1).max(1) as f64;
+        let to_svg = |k: usize, v: f64| {
+            (
+                PAD + k as f64 / span * (WIDTH - 2.0 * PAD),
+                PAD + (top - v) / (top - bottom) * (HEIGHT - 2.0 * PAD),
+            )
+        };
+        let points: Vec<(f64, f64)> = logs
+            .iter()
+            .enumerate()
+            .map(|(k, &v)| to_svg(k, v))
+            .collect();
+        let polyline = points
+            .iter()
+            .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // One gridline per decade, thinned out so at most ~6 are labelled.
+        let decades = (top - bottom) as i32;
+        let stride = (decades / 6).max(1);
+        let gridlines = (0..=decades).step_by(stride as usize).map(|k| {
+            let exponent = bottom + k as f64;
+            let (_, y) = to_svg(0, exponent);
+            html! {
+                <g class="gridline">
+                    <line x1={PAD.to_string()} y1={y.to_string()} x2={(WIDTH - PAD).to_string()} y2={y.to_string()} />
+                    <text x={(PAD - 6.0).to_string()} y={(y + 4.0).to_string()} text-anchor="end">
+                        { format!("1e{}", exponent) }
+                    </text>
+                </g>
+            }
+        });
+
+        html! {
+            <div class="log-chart">
+                <h4>{ props.title.clone() }</h4>
+                <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} width={WIDTH.to_string()} height={HEIGHT.to_string()}>
+                    { for gridlines }
+                    <polyline class="log-line" points={polyline} />
+                    {
+                        for points.iter().zip(values.iter()).map(|((x, y), value)| html! {
+                            <circle class="log-point" cx={x.to_string()} cy={y.to_string()} r="3">
+                                <title>{ format!("{:.3e}", value) }</title>
+                            </circle>
+                        })
+                    }
+                    <text class="axis-label" x={(WIDTH / 2.0).to_string()} y={(HEIGHT - 8.0).to_string()} text-anchor="middle">
+                        { "iteration" }
+                    </text>
+                </svg>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use gloo_timers::callback::Interval;
+use web_sys::HtmlSelectElement;
+use yew::prelude::*;
+
+use crate::logging::{self, LogRecord, LEVELS};
+
+/// How often the console looks for new records.
+const POLL_MS: u32 = 500;
+
+pub enum Msg {
+    Poll,
+    /// Index into `LEVELS` of the least severe level shown.
+    SetFilter(usize),
+    Clear,
+}
+
+/// The solver's log in the page, for readers without the browser's
+/// developer tools. Shows what `logging` has kept, newest last, down to a
+/// chosen level; the Log setting decides what is kept in the first place.
+pub struct LogConsole {
+    records: Vec<LogRecord>,
+    /// `logging::next_seq` when `records` was read.
+    seen: u64,
+    filter: usize,
+    _poll: Interval,
+}
+
+impl Component for LogConsole {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        Self {
+            records: logging::records(),
+            seen: logging::next_seq(),
+            filter: LEVELS.len() - 1,
+            _poll: Interval::new(POLL_MS, move || link.send_message(Msg::Poll)),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Poll => {
+                let seq = logging::next_seq();
+                if seq == self.seen {
+                    return false;
+                }
+                self.seen = seq;
+                self.records = logging::records();
+            }
+            Msg::SetFilter(filter) => self.filter = filter.min(LEVELS.len() - 1),
+            Msg::Clear => {
+                logging::clear();
+                self.records.clear();
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let shown: Vec<&LogRecord> = self
+            .records
+            .iter()
+            .filter(|record| record.level <= LEVELS[self.filter])
+            .collect();
+        html! {
+            <div class="log-console-panel">
+                <div class="log-console-controls">
+                    <h3>{ "Log" }</h3>
+                    <label>{ "Show: " }
+                        <select onchange={link.callback(|e: Event| {
+                            let select: HtmlSelectElement = e.target_unchecked_into();
+                            Msg::SetFilter(select.value().parse().unwrap_or(0))
+                        })}>
+                            {
+                                for LEVELS.iter().enumerate().map(|(k, level)| html! {
+                                    <option value={k.to_string()} selected={k == self.filter}>
+                                        { if k == 0 { format!("{} only", level) } else { format!("{} and above", level) } }
+                                    </option>
+                                })
+                            }
+                        </select>
+                    </label>
+                    <button disabled={self.records.is_empty()} onclick={link.callback(|_| Msg::Clear)}>
+                        { "Clear" }
+                    </button>
+                    <span class="log-console-count">
+                        { format!("{} of {} records", shown.len(), self.records.len()) }
+                    </span>
+                </div>
+                <div class="log-console">
+                    {
+                        if shown.is_empty() {
+                            html! { <p class="log-console-empty">{ "Nothing logged yet. Step a problem, or set Log to Full matrices for the most detail." }</p> }
+                        } else {
+                            html! { for shown.iter().map(|record| render_record(record)) }
+                        }
+                    }
+                </div>
+            </div>
+        }
+    }
+}
+
+fn render_record(record: &LogRecord) -> Html {
+    let level = record.level.as_str().to_lowercase();
+    let fields = record
+        .fields
+        .iter()
+        .map(|(name, value)| format!("{}={}", name, value))
+        .collect::<Vec<_>>()
+        .join(" ");
+    html! {
+        <div key={record.seq.to_string()} class={classes!("log-line", format!("log-{}", level))}>
+            <span class="log-level">{ record.level.as_str() }</span>
+            <span class="log-target" title={record.target.clone()}>{ &record.context }</span>
+            <pre class="log-message">{ &record.message }</pre>
+            {
+                if fields.is_empty() {
+                    html! {}
+                } else {
+                    html! { <span class="log-fields">{ fields }</span> }
+                }
+            }
+        </div>
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+use nalgebra::{DMatrix, DVector};
+use yew::prelude::*;
+
+/// Values shown by a matrix or vector table, shared rather than copied, with
+/// a digest of the shape and every entry. Props compare by digest only, so
+/// an unchanged table is skipped without walking its entries again.
+#[derive(Clone, Debug)]
+pub struct MatrixData {
+    values: Rc<DMatrix<f64>>,
+    digest: u64,
+}
+
+impl MatrixData {
+    pub fn new(values: DMatrix<f64>) -> Self {
+        let mut hasher = DefaultHasher::new();
+        values.shape().hash(&mut hasher);
+        for val in values.iter() {
+            val.to_bits().hash(&mut hasher);
+        }
+        Self {
+            digest: hasher.finish(),
+            values: Rc::new(values),
+        }
+    }
+}
+
+impl From<&DMatrix<f64>> for MatrixData {
+    fn from(matrix: &DMatrix<f64>) -> Self {
+        Self::new(matrix.clone())
+    }
+}
+
+impl From<&DVector<f64>> for MatrixData {
+    fn from(vector: &DVector<f64>) -> Self {
+        Self::new(DMatrix::from_column_slice(
+            vector.len(),
+            1,
+            vector.as_slice(),
+        ))
+    }
+}
+
+impl PartialEq for MatrixData {
+    fn eq(&self, other: &Self) -> bool {
+        self.digest == other.digest
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct MatrixProps {
+    pub data: MatrixData,
+    /// Rows marked as active constraints.
+    #[prop_or_default]
+    pub highlighted: Vec<usize>,
+    /// Constraint group of each row; rows of one group are shaded as a
+    /// block, with a rule where the next group starts.
+    #[prop_or_default]
+    pub row_groups: Vec<Option<String>>,
+}
+
+/// A matrix as a table with four decimals per entry.
+pub struct MatrixView;
+
+impl Component for MatrixView {
+    type Message = ();
+    type Properties = MatrixProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let mat = &props.data.values;
+        let (rows, cols) = mat.shape();
+        let group = |r: usize| props.row_groups.get(r).cloned().flatten();
+        // Index of the block each row is in, counting a change of group.
+        let blocks: Vec<usize> = (0..rows)
+            .scan(0, |block, r| {
+                if r > 0 && group(r) != group(r - 1) {
+                    *block += 1;
+                }
+                Some(*block)
+            })
+            .collect();
+        html! {
+            <table class="matrix">
+                <tbody>
+                {
+                    for (0..rows).map(|r| html!{
+                        <tr
+                            class={classes!(
+                                props.highlighted.contains(&r).then_some("active-row"),
+                                (group(r).is_some() && blocks[r] % 2 == 1).then_some("group-alt"),
+                                (r > 0 && blocks[r] != blocks[r - 1]).then_some("group-start"),
+                            )}
+                            title={group(r)}
+                        >
+                        {
+                            for (0..cols).map(|c| html! {
+                                <td>{ format!("{:.4}", mat[(r, c)]) }</td>
+                            })
+                        }
+                        </tr>
+                    })
+                }
+                </tbody>
+            </table>
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct VectorProps {
+    pub data: MatrixData,
+}
+
+/// A vector as a one-column table.
+pub struct VectorView;
+
+impl Component for VectorView {
+    type Message = ();
+    type Properties = VectorProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let v = &ctx.props().data.values;
+        html! {
+            <table class="vector">
+                <tbody>
+                {
+                    for v.iter().map(|val| html!{
+                        <tr>
+                            <td>{ format!("{:.4}", val) }</td>
+                        </tr>
+                    })
+                }
+                </tbody>
+            </table>
+        }
+    }
+}
@@ -1,279 +1,2131 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use gloo_file::ObjectUrl;
+use gloo_worker::{Spawnable, WorkerBridge};
 use log;
-use nalgebra::{DMatrix, DVector};
+use nalgebra::DVector;
+use wasm_bindgen::closure::Closure;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, KeyboardEvent};
 use yew::prelude::*;
 
-use crate::interior::{
-    perform_interior_point_iteration, InteriorPointError, InteriorPointIteration,
-    InteriorPointProblem,
-};
+use crate::course_pack::{self, CoursePack};
+use crate::crash;
+use crate::export;
+use crate::generator::GeneratedProblem;
+use crate::history_filter::{HistoryFilter, IterationFacts};
+use crate::inspector::{InspectorState, StateEdit};
+use crate::interior::{warm_start, InteriorPointError, InteriorPointProblem, SolverOptions};
+use crate::logging;
+use crate::model::{SolverMethod, ValidatedProblem};
+use crate::narration;
+use crate::notation::Notation;
+use crate::palette::{self, Palette};
+use crate::phase_one::analytic_center;
+use crate::pretty;
+use crate::profile::{self, RenderStats};
+use crate::rank;
+use crate::replay::{Recording, Replay, RunEvent, RECORDING_FILE};
+use crate::report::{now_ms, SolveReport, SolveStatus, Timings};
+use crate::repro::{export_repro, ReproIteration};
+use crate::route::{self, Route};
+use crate::session::{RunStep, SessionRun, SessionSnapshot, SESSION_FILE};
+use crate::simplex::SimplexError;
+use crate::theory::iteration_bound;
+use crate::worker::{SolverWorker, WorkerInput, WorkerOutput, WORKER_SCRIPT};
 
+mod analysis_panel;
+mod assignment_view;
+mod augmented_view;
+mod barrier_view;
+mod batch_view;
+mod context;
+mod course_pack_view;
+mod dashboard;
+mod exercise_view;
+mod expected_view;
+mod export_menu;
+mod fractional_view;
+mod generator_view;
+mod history_view;
+mod hsd_view;
 mod input_form;
+mod inspector_view;
 mod interior_view;
+mod log_chart;
+mod log_console;
+mod matrix_view;
+mod network_graph;
+mod network_view;
+mod notation_view;
+mod parametric_view;
+mod path_plot;
+mod profile_overlay;
+mod qp_view;
+mod race_view;
+mod regression_form;
+mod regression_view;
+mod replay_view;
+mod report_view;
+mod results_panel;
+mod revised_simplex_view;
+mod run;
+mod scenario_view;
+mod sensitivity_view;
+mod session_view;
+mod settings_bar;
+mod simplex_view;
+mod transportation_form;
+mod transportation_view;
+mod what_if_view;
 
-use input_form::{InputForm, InputFormData};
+use analysis_panel::AnalysisPanel;
+use assignment_view::AssignmentPanel;
+use barrier_view::BarrierView;
+use batch_view::BatchPanel;
+use context::{HistoryContext, ProblemContext, RunRecord, SettingChange, SettingsContext};
+use course_pack_view::CoursePackPanel;
+use dashboard::AnalysisDashboard;
+use expected_view::ExpectedIterationsPanel;
+use export_menu::ExportMenu;
+use generator_view::GeneratorPanel;
+use history_view::SessionHistory;
+use hsd_view::HsdView;
+use input_form::InputForm;
+use inspector_view::StateInspector;
 use interior_view::InteriorPointView;
+use log_console::LogConsole;
+use network_view::NetworkPanel;
+use parametric_view::ParametricPanel;
+use profile_overlay::ProfileOverlay;
+use qp_view::QpView;
+use race_view::RacePanel;
+use regression_form::RegressionForm;
+use replay_view::ReplayControls;
+use results_panel::ResultsPanel;
+use revised_simplex_view::RevisedSimplexView;
+use run::{ActiveRun, AffineHistory, History, StepError};
+use scenario_view::ScenarioPanel;
+use session_view::SessionControls;
+use settings_bar::SettingsBar;
+use simplex_view::SimplexView;
+use transportation_form::TransportationForm;
+use what_if_view::WhatIfPanel;
+
+/// Which input the app shows: the general LP form, the transportation table,
+/// the regression data, or one of the self-contained assignment and network
+/// panels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputMode {
+    General,
+    Transportation,
+    Regression,
+    Assignment,
+    Network,
+}
 
 pub struct App {
+    /// Page shown, from the URL fragment.
+    route: Route,
+
+    input_mode: InputMode,
+
     problem_size: Option<(usize, usize)>,
 
-    current_problem: Option<InteriorPointProblem>,
+    /// The method running on the active model, with its iteration cards.
+    run: Option<ActiveRun>,
+
+    model: Option<Rc<ValidatedProblem>>,
+
+    options: SolverOptions,
+
+    /// Sent with every settings context; kept so the context compares equal
+    /// across renders until a setting actually changes.
+    on_setting_change: Callback<SettingChange>,
+
+    /// Where the solver worker's messages go.
+    on_worker_output: Callback<WorkerOutput>,
+
+    /// The background run "Solve Completely" started; dropping it stops
+    /// the worker.
+    solver_worker: Option<WorkerBridge<SolverWorker>>,
+
+    /// Symbols the headers use; kept across runs.
+    notation: Notation,
+
+    /// Imported course pack, restored from localStorage on load.
+    course_pack: Option<CoursePack>,
+
+    /// Pack problem loaded into the form; an optimal run marks it completed.
+    pack_problem: Option<usize>,
+    /// Generated problem loaded into the form, with its known optimum in
+    /// the notes; replaces the pack problem until another is selected.
+    generated: Option<course_pack::PackProblem>,
+
+    completed_problems: Vec<String>,
+
+    /// Standard-form x of the last optimal run; survives `reset_run` so an
+    /// edited model can start from it.
+    last_solution: Option<DVector<f64>>,
 
-    interior_iterations: Vec<InteriorPointIteration>,
+    /// Whether affine scaling starts from `last_solution` on the next submit.
+    warm_start: bool,
 
-    maximize: bool,
+    /// Whether each new step is also read aloud.
+    narrate: bool,
+
+    /// Small-screen iteration cards; on by default on narrow viewports.
+    compact: bool,
+
+    /// Colors of plots and highlights, remembered in localStorage.
+    palette: Palette,
+
+    /// Description of the newest step, announced through an ARIA live region.
+    narration: Option<String>,
+
+    /// Steps already described and whether the outcome was, so a repaint
+    /// does not repeat them.
+    narrated: (usize, bool),
 
     done: bool,
 
+    timings: Timings,
+
+    report: Option<Rc<SolveReport>>,
+
+    /// Runs finished this session, for the history page.
+    runs: Rc<Vec<RunRecord>>,
+
+    /// JSON from the last "Export minimal repro", shown in case the
+    /// clipboard write is refused.
+    repro: Option<String>,
+
+    /// Object URL of the last saved session or recording, revoked when
+    /// replaced.
+    session_url: Option<ObjectUrl>,
+
+    /// Inputs of the active run and every action taken on it since.
+    recording: Option<Recording>,
+
+    /// Replay of a recording, one step per tick of the replay controls.
+    replay: Option<Replay>,
+
+    /// How the last replay ended: the iterations it matched, or how it
+    /// diverged.
+    replay_outcome: Option<Result<usize, String>>,
+
+    /// Constraints the last failed factorization traced to dependent rows
+    /// of A, marked in the form.
+    flagged_constraints: Vec<(String, String)>,
+
+    /// Search text filtering the iteration cards; kept across runs.
+    history_filter: String,
+
+    /// Notes attached to iterations of the active run, by index.
+    notes: BTreeMap<usize, String>,
+
+    /// Panic message of a solver call that crashed; the iterations before
+    /// it are kept on screen.
+    crash: Option<String>,
+
     error_message: Option<String>,
+
+    /// Whether the developer profile overlay is shown (Alt+Shift+P).
+    profiling: bool,
+
+    render_stats: RenderStats,
+
+    /// When the update being rendered started, while profiling.
+    update_started: Option<f64>,
+
+    /// Whether the offer to open the dashboard was closed for this run.
+    dashboard_dismissed: bool,
+
+    /// Whether the solver-state inspector is shown (Alt+Shift+I).
+    inspecting: bool,
+
+    /// Why the inspector refused the last edit.
+    inspector_error: Option<String>,
+
+    /// Keeps the page-wide shortcut listeners alive.
+    _profile_shortcut: Option<Closure<dyn Fn(KeyboardEvent)>>,
+
+    _inspector_shortcut: Option<Closure<dyn Fn(KeyboardEvent)>>,
+
+    /// Keeps the fragment listener alive.
+    _route_listener: Option<Closure<dyn Fn()>>,
 }
 
 pub enum Msg {
+    Navigate(Route),
+    SetInputMode(InputMode),
     SetProblemSize(usize, usize),
-    StartInteriorPoint {
-        a: DMatrix<f64>,
-        b: DVector<f64>,
-        c: DVector<f64>,
-        alpha: f64,
-        initial: Vec<f64>,
-        maximize: bool,
-    },
+    /// Starts the model's method; the flag asks affine scaling to
+    /// warm-start from the last optimal solution.
+    Start(Box<ValidatedProblem>, bool),
     NextStep,
+    /// Runs affine scaling from the current step to the end.
+    SolveCompletely,
+    /// Stops the background run, keeping the steps it has taken.
+    StopSolving,
+    /// A step or the outcome of the background run.
+    Worker(WorkerOutput),
+    CenteringStep,
     Reset,
     SetInitialPoint(DVector<f64>),
+    ChangeSetting(SettingChange),
+    SetWarmStart(bool),
+    ImportCoursePack(CoursePack, String),
+    SelectPackProblem(usize),
+    /// Loads a generated problem into the form in place of a pack problem.
+    LoadGenerated(GeneratedProblem),
+    RemoveCoursePack,
+    ExportRepro,
+    ToggleProfile,
+    ToggleInspector,
+    /// Closes the offer to open the dashboard until the next run.
+    DismissDashboard,
+    /// Hand edit of the affine-scaling run from the inspector.
+    EditState(StateEdit),
+    FilterHistory(String),
+    /// Scrolls the card of this iteration into view.
+    JumpToIteration(usize),
+    /// Sets the note on an iteration; empty text removes it.
+    SetNote(usize, String),
+    /// Downloads the model, settings and iterations as a session file.
+    SaveSession,
+    RestoreSession(Rc<SessionSnapshot>),
+    /// Replays the active run from its recording.
+    ReplayRun,
+    ReplayRecording(Rc<Recording>),
+    ReplayTick,
+    StopReplay,
+    /// Downloads the recording of the active run.
+    SaveRecording,
 }
 
 impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let profile_shortcut = profile::listen_for_toggle(profile::PROFILE_KEY, move || {
+            link.send_message(Msg::ToggleProfile)
+        });
+        let link = ctx.link().clone();
+        let inspector_shortcut = profile::listen_for_toggle(profile::INSPECTOR_KEY, move || {
+            link.send_message(Msg::ToggleInspector)
+        });
+        let link = ctx.link().clone();
+        let route_listener =
+            route::listen_for_changes(move |route| link.send_message(Msg::Navigate(route)));
+        let course_pack = course_pack::load_saved_pack()
+            .and_then(|text| course_pack::parse_course_pack(&text).ok());
+        let completed_problems = course_pack
+            .as_ref()
+            .map(course_pack::load_completed)
+            .unwrap_or_default();
         Self {
+            route: route::current_route(),
+            input_mode: InputMode::General,
             problem_size: None,
-            current_problem: None,
-            interior_iterations: vec![],
-            maximize: true, // default
+            run: None,
+            model: None,
+            options: SolverOptions::default(),
+            on_setting_change: ctx.link().callback(Msg::ChangeSetting),
+            on_worker_output: ctx.link().callback(Msg::Worker),
+            solver_worker: None,
+            notation: Notation::default(),
+            course_pack,
+            pack_problem: None,
+            generated: None,
+            completed_problems,
+            last_solution: None,
+            warm_start: false,
+            narrate: false,
+            compact: narrow_viewport(),
+            palette: palette::load_palette(),
+            narration: None,
+            narrated: (0, false),
             done: false,
+            timings: Timings::default(),
+            report: None,
+            runs: Rc::default(),
+            repro: None,
+            session_url: None,
+            recording: None,
+            replay: None,
+            replay_outcome: None,
+            flagged_constraints: vec![],
+            history_filter: String::new(),
+            notes: BTreeMap::new(),
+            crash: None,
             error_message: None,
+            profiling: false,
+            render_stats: RenderStats::default(),
+            update_started: None,
+            dashboard_dismissed: false,
+            inspecting: false,
+            inspector_error: None,
+            _profile_shortcut: profile_shortcut,
+            _inspector_shortcut: inspector_shortcut,
+            _route_listener: route_listener,
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::SetProblemSize(vars, cons) => {
-                log::info!(
-                    "User changed problem size: {} variables, {} constraints",
-                    vars,
-                    cons
-                );
-                self.problem_size = Some((vars, cons));
+        if self.profiling {
+            self.update_started = Some(now_ms());
+        }
+        if matches!(msg, Msg::NextStep | Msg::CenteringStep | Msg::ReplayTick) {
+            crash::set_context(self.export_repro());
+        }
+        match crash::guard(|| self.handle(msg)) {
+            Ok(changed) => {
+                self.narrate_progress();
+                changed
+            }
+            Err(message) => {
+                log::error!("Solver panicked: {}", message);
+                self.done = true;
+                self.crash = Some(message);
                 true
             }
-            Msg::StartInteriorPoint {
-                a,
-                b,
-                c,
-                alpha,
-                initial,
-                maximize,
-            } => {
-                let final_n = a.ncols();
+        }
+    }
 
-                let feasible_x = if initial.len() == final_n {
-                    DVector::from_vec(initial.clone())
-                } else {
-                    let mut new_init = vec![1.0; final_n];
-                    for (i, val) in initial.iter().enumerate() {
-                        if i < final_n {
-                            new_init[i] = val.max(1e-4);
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if let Some(start) = self.update_started.take() {
+            self.render_stats.record(now_ms() - start);
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let warm = self.warm_start && self.last_solution.is_some();
+        let on_submit =
+            link.callback(move |problem: ValidatedProblem| Msg::Start(Box::new(problem), warm));
+        let problem_context = self.problem_context();
+        let (filter, shown) = self.filtered_iterations();
+        html! {
+            <ContextProvider<SettingsContext> context={self.settings_context()}>
+            <ContextProvider<ProblemContext> context={problem_context.clone()}>
+            <ContextProvider<HistoryContext> context={self.history_context()}>
+            <div class={classes!("app-container", self.palette.class())}>
+                <h1>{ "Interior-Point Solver" }</h1>
+
+                { self.render_route_tabs() }
+
+                <p
+                    class={if self.narrate { "narration" } else { "narration visually-hidden" }}
+                    aria-live="polite"
+                >
+                    { self.narration.clone().unwrap_or_default() }
+                </p>
+
+                <section class="route-page" hidden={self.route != Route::Solve}>
+                    <CoursePackPanel
+                        pack={self.course_pack.clone()}
+                        completed={self.completed_problems.clone()}
+                        active={self.pack_problem}
+                        on_import={link.callback(|(pack, text)| Msg::ImportCoursePack(pack, text))}
+                        on_select={link.callback(Msg::SelectPackProblem)}
+                        on_remove={link.callback(|_| Msg::RemoveCoursePack)}
+                    />
+
+                    <div>
+                        <button class="back-button" onclick={link.callback(|_| Msg::Reset)}>
+                            { "Reset / Clear" }
+                        </button>
+
+                        <SessionControls
+                            can_save={self.model.is_some() && self.solver_worker.is_none()}
+                            on_save={link.callback(|_| Msg::SaveSession)}
+                            on_restore={link.callback(Msg::RestoreSession)}
+                        />
+
+                        <ReplayControls
+                            can_record={self.recording.is_some() && self.solver_worker.is_none()}
+                            progress={self.replay.as_ref().map(Replay::progress)}
+                            outcome={self.replay_outcome.clone()}
+                            on_replay={link.callback(|_| Msg::ReplayRun)}
+                            on_save={link.callback(|_| Msg::SaveRecording)}
+                            on_load={link.callback(Msg::ReplayRecording)}
+                            on_tick={link.callback(|_| Msg::ReplayTick)}
+                            on_stop={link.callback(|_| Msg::StopReplay)}
+                        />
+
+                        <div class="input-mode-selector">
+                            <label>
+                                <input
+                                    type="radio"
+                                    name="input_mode"
+                                    checked={self.input_mode == InputMode::General}
+                                    oninput={link.callback(|_| Msg::SetInputMode(InputMode::General))}
+                                />
+                                { "General LP" }
+                            </label>
+                            <label>
+                                <input
+                                    type="radio"
+                                    name="input_mode"
+                                    checked={self.input_mode == InputMode::Transportation}
+                                    oninput={link.callback(|_| Msg::SetInputMode(InputMode::Transportation))}
+                                />
+                                { "Transportation Problem" }
+                            </label>
+                            <label>
+                                <input
+                                    type="radio"
+                                    name="input_mode"
+                                    checked={self.input_mode == InputMode::Regression}
+                                    oninput={link.callback(|_| Msg::SetInputMode(InputMode::Regression))}
+                                />
+                                { "L1 / Chebyshev Regression" }
+                            </label>
+                            <label>
+                                <input
+                                    type="radio"
+                                    name="input_mode"
+                                    checked={self.input_mode == InputMode::Assignment}
+                                    oninput={link.callback(|_| Msg::SetInputMode(InputMode::Assignment))}
+                                />
+                                { "Assignment Problem" }
+                            </label>
+                            <label>
+                                <input
+                                    type="radio"
+                                    name="input_mode"
+                                    checked={self.input_mode == InputMode::Network}
+                                    oninput={link.callback(|_| Msg::SetInputMode(InputMode::Network))}
+                                />
+                                { "Min-Cost Flow" }
+                            </label>
+                        </div>
+
+                        {
+                            match self.input_mode {
+                                InputMode::General => html! {
+                                    <>
+                                        <GeneratorPanel on_generate={link.callback(Msg::LoadGenerated)} />
+                                        <InputForm
+                                            on_submit={on_submit}
+                                            notation={self.notation.clone()}
+                                            preset={self.generated.clone().or_else(|| self.pack_preset())}
+                                            flagged_constraints={self.flagged_constraints.clone()}
+                                            on_size_change={link.callback(|(vars, cons)| Msg::SetProblemSize(vars, cons))}
+                                        />
+                                    </>
+                                },
+                                InputMode::Transportation => html! {
+                                    <TransportationForm on_submit={on_submit} notation={self.notation.clone()} />
+                                },
+                                InputMode::Regression => html! {
+                                    <RegressionForm on_submit={on_submit} notation={self.notation.clone()} />
+                                },
+                                InputMode::Assignment => html! {
+                                    <AssignmentPanel options={self.options.clone()} notation={self.notation.clone()} />
+                                },
+                                InputMode::Network => html! {
+                                    <NetworkPanel options={self.options.clone()} />
+                                },
+                            }
                         }
-                    }
-                    DVector::from_vec(new_init)
-                };
 
-                let sign = if maximize { 1.0 } else { -1.0 };
-                let new_c = c.map(|val| val * sign);
-
-                let problem = InteriorPointProblem {
-                    a_matrix: a,
-                    b_vector: b,
-                    c_vector: new_c,
-                    x_vector: feasible_x,
-                    alpha,
-                    constraint_types: vec![],
-                    is_augmented: false,
-                };
+                        {
+                            if self.last_solution.is_some()
+                                && matches!(self.input_mode, InputMode::General | InputMode::Transportation)
+                            {
+                                html! {
+                                    <label class="warm-start">
+                                        <input
+                                            type="checkbox"
+                                            checked={self.warm_start}
+                                            onchange={link.callback(|e: Event| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                Msg::SetWarmStart(input.checked())
+                                            })}
+                                        />
+                                        { "Warm start: begin affine scaling at the last optimal x, projected back into the interior, instead of the initial point" }
+                                    </label>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+
+                        <button class="next-step-button" onclick={link.callback(|_| Msg::NextStep)} disabled={self.solver_worker.is_some() || self.replay.is_some()}>
+                            { "Next Interior-Point Step" }
+                        </button>
+
+                        {
+                            if self.solver_worker.is_some() {
+                                html! {
+                                    <>
+                                        <span class="solving-indicator" role="status">
+                                            { format!("Solving in the background… {} iterations so far", self.run.as_ref().map_or(0, ActiveRun::len)) }
+                                        </span>
+                                        <button class="stop-solving-button" onclick={link.callback(|_| Msg::StopSolving)}>
+                                            { "Stop" }
+                                        </button>
+                                    </>
+                                }
+                            } else if self.affine().is_some() && !self.done && self.replay.is_none() {
+                                html! {
+                                    <button class="solve-completely-button" onclick={link.callback(|_| Msg::SolveCompletely)}>
+                                        { "Solve Completely" }
+                                    </button>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+
+                        {
+                            if self.run.as_ref().is_some_and(ActiveRun::can_center)
+                                && !self.done
+                                && self.solver_worker.is_none()
+                                && self.replay.is_none()
+                            {
+                                html! {
+                                    <button class="centering-button" onclick={link.callback(|_| Msg::CenteringStep)}>
+                                        { "Take a Centering Step" }
+                                    </button>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+
+                        {
+                            if self.model.is_some() {
+                                html! {
+                                    <>
+                                        <button class="export-repro-button" onclick={link.callback(|_| Msg::ExportRepro)}>
+                                            { "Export minimal repro" }
+                                        </button>
+                                        <ExportMenu />
+                                    </>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+                    </div>
 
-                self.current_problem = Some(problem);
-                self.interior_iterations.clear();
-                self.done = false;
-                self.maximize = maximize;
-                self.error_message = None; // Clear any previous errors
-
-                // Automatically perform the first iteration (Iteration 0)
-                if let Some(problem) = &mut self.current_problem {
-                    match perform_interior_point_iteration(problem) {
-                        Ok(iter_data) => {
-                            self.interior_iterations.push(iter_data);
+                    {
+                        if let Some(repro) = &self.repro {
+                            html! {
+                                <div class="repro-export">
+                                    <p>{ "Copied to the clipboard. Labels are stripped and coefficients rounded; paste this into a bug report:" }</p>
+                                    <pre>{ repro }</pre>
+                                </div>
+                            }
+                        } else {
+                            html! {}
                         }
-                        Err(InteriorPointError::NoImprovement) => {
-                            self.done = true;
-                            self.error_message = Some("The algorithm converged immediately or found no improvement direction. This might indicate the initial point is already optimal, or the problem constraints are inconsistent.".to_string());
+                    }
+
+                    { self.render_crash(ctx) }
+
+                    { self.render_history_filter(ctx, &filter, &shown) }
+
+                    <ExpectedIterationsPanel />
+
+                    <ResultsPanel />
+
+                    {
+                        if let Some(error) = &self.error_message {
+                            html! {
+                                <div class="error-message">
+                                    <div class="error-icon">{ "⚠️" }</div>
+                                    <h3>{ "Problem Detected" }</h3>
+                                    <p>{ error }</p>
+                                    <div class="error-actions">
+                                        <p><strong>{ "What to try:" }</strong></p>
+                                        <ul>
+                                            <li>{ "Check that your constraints are consistent and don't contradict each other" }</li>
+                                            <li>{ "Ensure your initial point satisfies all constraints and is positive" }</li>
+                                            <li>{ "Verify your constraint matrix is well-formed" }</li>
+                                            <li>{ format!("Try different initial values or adjust the step size ({})", self.notation.step()) }</li>
+                                        </ul>
+                                        <button onclick={link.callback(|_| Msg::Reset)}>
+                                            { "← Go Back and Try Again" }
+                                        </button>
+                                    </div>
+                                </div>
+                            }
+                        } else if let Some(run) = &self.run {
+                            self.render_iterations(ctx, run, &shown, &problem_context)
+                        } else {
+                            html! {
+                                <div class="no-problem-message">
+                                    <div class="message-icon">{ "📊" }</div>
+                                    <h3>{ "Ready to Solve" }</h3>
+                                    <p>{ "Configure your linear programming problem above and press \"Solve\" to begin the interior-point algorithm visualization." }</p>
+                                </div>
+                            }
                         }
-                        Err(InteriorPointError::NotFeasible) => {
-                            self.done = true;
-                            self.error_message = Some("The problem appears to be infeasible. Please check your constraints and initial point to ensure they form a valid feasible region.".to_string());
+                    }
+                </section>
+
+                <section class="route-page" hidden={self.route != Route::Dashboard}>
+                    <AnalysisDashboard />
+                </section>
+
+                <section class="route-page" hidden={self.route != Route::Analyze}>
+                    <AnalysisPanel />
+
+                    <RacePanel />
+
+                    <BatchPanel />
+
+                    <ParametricPanel />
+
+                    <WhatIfPanel />
+
+                    <ScenarioPanel />
+                </section>
+
+                <section class="route-page" hidden={self.route != Route::History}>
+                    <SessionHistory />
+                </section>
+
+                <section class="route-page" hidden={self.route != Route::Settings}>
+                    <div class="route-settings">
+                        <SettingsBar />
+                        <LogConsole />
+                    </div>
+                </section>
+
+                { self.render_dashboard_offer(ctx) }
+
+                {
+                    if self.inspecting {
+                        html! {
+                            <StateInspector
+                                state={self.affine().map(|history| InspectorState::capture(&history.problem))}
+                                error={self.inspector_error.clone()}
+                                on_edit={link.callback(Msg::EditState)}
+                                on_close={link.callback(|_| Msg::ToggleInspector)}
+                            />
                         }
-                        Err(InteriorPointError::SingularMatrix(msg)) => {
-                            self.done = true;
-                            self.error_message = Some(format!("Mathematical error: {}. This usually means the constraint matrix is ill-conditioned or the problem is degenerate. Try adjusting your constraints or initial point.", msg));
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    if self.profiling {
+                        html! {
+                            <ProfileOverlay
+                                render={self.render_stats.clone()}
+                                on_close={link.callback(|_| Msg::ToggleProfile)}
+                            />
                         }
+                    } else {
+                        html! {}
                     }
                 }
+            </div>
+            </ContextProvider<HistoryContext>>
+            </ContextProvider<ProblemContext>>
+            </ContextProvider<SettingsContext>>
+        }
+    }
+}
+
+impl App {
+    fn problem_context(&self) -> ProblemContext {
+        ProblemContext {
+            model: self.model.clone(),
+            report: self.report.clone(),
+            error: self.error_message.clone(),
+        }
+    }
+
+    fn settings_context(&self) -> SettingsContext {
+        SettingsContext {
+            options: self.options.clone(),
+            notation: self.notation.clone(),
+            compact: self.compact,
+            palette: self.palette,
+            narrate: self.narrate,
+            on_change: self.on_setting_change.clone(),
+        }
+    }
+
+    fn history_context(&self) -> HistoryContext {
+        HistoryContext {
+            path: Rc::new(self.iterate_path()),
+            interior: self.run.as_ref().is_some_and(ActiveRun::is_interior),
+            done: self.done,
+            timings: self.timings.clone(),
+            runs: self.runs.clone(),
+            notes: Rc::new(self.notes.clone()),
+        }
+    }
 
+    /// The cards of `run`, hiding those `shown` filters out.
+    fn render_iterations(
+        &self,
+        ctx: &Context<Self>,
+        run: &ActiveRun,
+        shown: &[bool],
+        problem_context: &ProblemContext,
+    ) -> Html {
+        let card = |i: usize, view: Html| {
+            html! {
+                <div id={iteration_anchor(i)} hidden={!shown.get(i).copied().unwrap_or(true)}>
+                    { view }
+                    { self.render_note(ctx, i) }
+                </div>
+            }
+        };
+        let cards: Vec<Html> = match run {
+            ActiveRun::Affine(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    let iteration_data = iteration_data.expand();
+                    card(
+                        i,
+                        html! {
+                            <InteriorPointView
+                                iteration={i}
+                                compact={self.compact}
+                                notation={self.notation.clone()}
+                                active_rows={problem_context.active_rows(&iteration_data.current_x)}
+                                constraint_labels={problem_context.constraint_labels()}
+                                constraint_groups={problem_context.constraint_groups()}
+                                iteration_data={Some(iteration_data)}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+            ActiveRun::Barrier(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    card(
+                        i,
+                        html! {
+                            <BarrierView
+                                iteration={i}
+                                compact={self.compact}
+                                notation={self.notation.clone()}
+                                active_rows={problem_context.active_rows(&iteration_data.current_x)}
+                                constraint_labels={problem_context.constraint_labels()}
+                                iteration_data={Some(iteration_data.clone())}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+            ActiveRun::Simplex(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    card(
+                        i,
+                        html! {
+                            <SimplexView
+                                iteration={i}
+                                compact={self.compact}
+                                iteration_data={Some(iteration_data.clone())}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+            ActiveRun::Revised(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    card(
+                        i,
+                        html! {
+                            <RevisedSimplexView
+                                iteration={i}
+                                compact={self.compact}
+                                notation={self.notation.clone()}
+                                iteration_data={Some(iteration_data.clone())}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+            ActiveRun::Quadratic(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    card(
+                        i,
+                        html! {
+                            <QpView
+                                iteration={i}
+                                compact={self.compact}
+                                notation={self.notation.clone()}
+                                q_matrix={history.problem.q_matrix.clone()}
+                                iteration_data={Some(iteration_data.clone())}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+            ActiveRun::SelfDual(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, iteration_data)| {
+                    card(
+                        i,
+                        html! {
+                            <HsdView
+                                iteration={i}
+                                compact={self.compact}
+                                notation={self.notation.clone()}
+                                active_rows={problem_context.active_rows(&iteration_data.current_x)}
+                                constraint_labels={problem_context.constraint_labels()}
+                                iteration_data={Some(iteration_data.clone())}
+                            />
+                        },
+                    )
+                })
+                .collect(),
+        };
+        html! {
+            <div class="iterations">
+                { for cards }
+            </div>
+        }
+    }
+
+    /// Once a run is optimal, a note on the solver page pointing at the
+    /// dashboard, so the results are not only at the end of the cards.
+    fn render_dashboard_offer(&self, ctx: &Context<Self>) -> Html {
+        let Some(report) = &self.report else {
+            return html! {};
+        };
+        if !self.done
+            || self.dashboard_dismissed
+            || self.route != Route::Solve
+            || report.status != SolveStatus::Optimal
+        {
+            return html! {};
+        }
+        html! {
+            <div class="dashboard-offer" role="status">
+                <span>{ format!("Optimal after {} iterations, Z = {:.4}.", report.iterations, report.objective) }</span>
+                <a href={Route::Dashboard.href()}>{ "Open the analysis dashboard" }</a>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::DismissDashboard)}
+                    title="Dismiss"
+                    aria-label="Dismiss"
+                >
+                    { "×" }
+                </button>
+            </div>
+        }
+    }
+
+    /// Links to the pages; following one changes the fragment, which comes
+    /// back as `Msg::Navigate`.
+    fn render_route_tabs(&self) -> Html {
+        html! {
+            <nav class="route-tabs">
+                {
+                    for Route::ALL.into_iter().map(|route| {
+                        let current = route == self.route;
+                        html! {
+                            <a
+                                href={route.href()}
+                                class={classes!("route-tab", current.then_some("active"))}
+                                aria-current={current.then_some("page")}
+                            >
+                                { route.label() }
+                            </a>
+                        }
+                    })
+                }
+            </nav>
+        }
+    }
+
+    /// Applies one message; `update` runs this under the crash guard.
+    fn handle(&mut self, msg: Msg) -> bool {
+        match msg {
+            Msg::Navigate(route) => {
+                if self.route == route {
+                    return false;
+                }
+                self.route = route;
+                true
+            }
+            Msg::SetInputMode(mode) => {
+                if self.input_mode == mode {
+                    return false;
+                }
+                self.input_mode = mode;
+                self.reset_run();
+                true
+            }
+            Msg::SetProblemSize(vars, cons) => {
+                log::info!(
+                    "User changed problem size: {} variables, {} constraints",
+                    vars,
+                    cons
+                );
+                self.problem_size = Some((vars, cons));
+                true
+            }
+            Msg::Start(model, warm) => {
+                let Some(x) = self.start_point(&model, warm) else {
+                    return true;
+                };
+                match ActiveRun::new(&model, x, &self.options) {
+                    Ok(run) => self.open_run(*model, run),
+                    Err(e) => {
+                        self.reset_run();
+                        self.begin_run(*model, None);
+                        self.fail_start(&e);
+                    }
+                }
                 true
             }
             Msg::NextStep => {
-                if let Some(problem) = &mut self.current_problem {
-                    if self.done {
-                        log::info!(
-                            "User clicked NextStep but solver is marked done (no improvement)."
-                        );
-                        return false;
+                if self.solver_worker.is_some() {
+                    return false;
+                }
+                if self.done {
+                    log::info!("User clicked NextStep but solver is marked done (no improvement).");
+                    return false;
+                }
+                self.record(RunEvent::Step);
+                let Some(run) = &mut self.run else {
+                    return false;
+                };
+                if let Some(e) = run.step(&mut self.timings) {
+                    self.finish_run(e);
+                }
+                true
+            }
+            Msg::SolveCompletely => {
+                let Some(history) = self.affine() else {
+                    return false;
+                };
+                if self.done || self.solver_worker.is_some() {
+                    return false;
+                }
+                let on_output = self.on_worker_output.clone();
+                let bridge = SolverWorker::spawner()
+                    .callback(move |output| on_output.emit(output))
+                    .spawn(WORKER_SCRIPT);
+                bridge.send(WorkerInput::Solve(Box::new(history.problem.clone())));
+                self.solver_worker = Some(bridge);
+                true
+            }
+            Msg::StopSolving => {
+                if let Some(bridge) = &self.solver_worker {
+                    bridge.send(WorkerInput::Cancel);
+                }
+                false
+            }
+            Msg::Worker(output) => {
+                // Output of a run the user has since reset.
+                if self.solver_worker.is_none() {
+                    return false;
+                }
+                match output {
+                    WorkerOutput::Step {
+                        iteration,
+                        elapsed_ms,
+                    } => {
+                        self.timings.record(elapsed_ms);
+                        if let Some(history) = self.affine_mut() {
+                            history.iterations.push(*iteration);
+                        }
+                        self.record(RunEvent::Step);
                     }
-
-                    log::info!(
-                        "Performing next step with current x = {:?}",
-                        problem.x_vector
-                    );
-
-                    match perform_interior_point_iteration(problem) {
-                        Ok(iter_data) => {
+                    WorkerOutput::Finished {
+                        status,
+                        ray,
+                        problem,
+                    } => {
+                        self.solver_worker = None;
+                        if self.options.logs_summary() {
                             log::info!(
-                                "Iteration snapshot => D = diag(x) =>\n{:?}",
-                                iter_data.d_matrix
+                                "Solved completely: {} after {} iterations",
+                                status.label(),
+                                problem.iterations
                             );
-                            log::info!("A~ =>\n{:?}", iter_data.a_tilde_matrix);
-                            log::info!("c~ => {:?}", iter_data.c_tilde_vector);
-                            log::info!("P =>\n{:?}", iter_data.p_matrix);
-                            log::info!("P c~ => {:?}", iter_data.cp_vector);
-                            log::info!("Updated x => {:?}", iter_data.current_x);
-
-                            self.interior_iterations.push(iter_data);
-                            true
                         }
-                        Err(InteriorPointError::NoImprovement) => {
-                            log::info!("No improvement => probably at optimum.");
-                            self.done = true;
-                            true
+                        if let Some(history) = self.affine_mut() {
+                            history.problem = *problem;
                         }
-                        Err(e) => {
-                            log::error!("Interior point iteration error: {:?}", e);
-                            self.done = true;
-                            true
+                        // A stopped run keeps its steps and can be stepped
+                        // or solved on from where it was.
+                        if status != SolveStatus::Cancelled {
+                            // The step that found the run finished.
+                            self.record(RunEvent::Step);
+                            self.finish_with_ray(status, ray);
                         }
                     }
-                } else {
-                    false
                 }
+                true
+            }
+            Msg::CenteringStep => {
+                if self.done || self.solver_worker.is_some() {
+                    return false;
+                }
+                let start = now_ms();
+                let Some(result) = self.run.as_mut().and_then(ActiveRun::center) else {
+                    return false;
+                };
+                self.timings.record(now_ms() - start);
+                self.record(RunEvent::Centering);
+                if let Err(e) = result {
+                    log::error!("Centering step error: {:?}", e);
+                    self.finish(SolveStatus::from(&e));
+                }
+                true
             }
             Msg::Reset => {
                 log::info!("User clicked Reset.");
                 self.problem_size = None;
-                self.current_problem = None;
-                self.interior_iterations.clear();
-                self.done = false;
-                self.error_message = None;
+                self.reset_run();
+                true
+            }
+            Msg::ChangeSetting(change) => self.change_setting(change),
+            Msg::SetWarmStart(warm) => {
+                self.warm_start = warm;
+                true
+            }
+            Msg::ToggleProfile => {
+                self.profiling = !self.profiling;
+                true
+            }
+            Msg::DismissDashboard => {
+                self.dashboard_dismissed = true;
+                true
+            }
+            Msg::ToggleInspector => {
+                self.inspecting = !self.inspecting;
+                self.inspector_error = None;
+                true
+            }
+            Msg::EditState(edit) => {
+                if self.solver_worker.is_some() {
+                    self.inspector_error =
+                        Some("The run is solving in the background; edit it once it stops".into());
+                    return true;
+                }
+                let Some(history) = self.affine_mut() else {
+                    return false;
+                };
+                match edit.apply(&mut history.problem) {
+                    Ok(()) => {
+                        log::info!("Inspector edit: {:?}", edit);
+                        self.record(RunEvent::Edit(edit));
+                        self.inspector_error = None;
+                        // The edited state may step on from where the old
+                        // one stopped.
+                        self.done = false;
+                        self.report = None;
+                    }
+                    Err(message) => self.inspector_error = Some(message),
+                }
+                true
+            }
+            Msg::FilterHistory(text) => {
+                self.history_filter = text;
+                true
+            }
+            Msg::SetNote(index, text) => {
+                let text = text.trim().to_string();
+                if text.is_empty() {
+                    self.notes.remove(&index);
+                } else {
+                    self.notes.insert(index, text);
+                }
+                // A finished run is already in the history; keep its notes
+                // there current.
+                if self.done && self.model.is_some() {
+                    let notes = self.note_list();
+                    if let Some(run) = Rc::make_mut(&mut self.runs).last_mut() {
+                        run.notes = notes;
+                    }
+                }
+                true
+            }
+            Msg::JumpToIteration(index) => {
+                scroll_to_iteration(index);
+                false
+            }
+            Msg::ImportCoursePack(pack, text) => {
+                course_pack::save_pack(&text);
+                self.completed_problems = course_pack::load_completed(&pack);
+                self.course_pack = Some(pack);
+                self.pack_problem = None;
+                true
+            }
+            Msg::SelectPackProblem(k) => {
+                self.pack_problem = Some(k);
+                self.generated = None;
+                self.input_mode = InputMode::General;
+                true
+            }
+            Msg::LoadGenerated(problem) => {
+                self.generated = Some(problem.to_pack_problem());
+                self.pack_problem = None;
+                true
+            }
+            Msg::RemoveCoursePack => {
+                course_pack::forget_pack();
+                self.course_pack = None;
+                self.pack_problem = None;
+                self.completed_problems.clear();
+                true
+            }
+            Msg::ExportRepro => {
+                let Some(repro) = self.export_repro() else {
+                    return false;
+                };
+                if let Some(window) = web_sys::window() {
+                    // Fire and forget: the JSON stays on screen if this fails.
+                    let _ = window.navigator().clipboard().write_text(&repro);
+                }
+                log::info!("Exported repro: {}", repro);
+                self.repro = Some(repro);
+                true
+            }
+            Msg::SaveSession => {
+                let Some(snapshot) = self.session_snapshot() else {
+                    return false;
+                };
+                let json = snapshot.to_json();
+                log::info!(
+                    "Saved a session with {} iterations, {} bytes",
+                    snapshot.iteration_count(),
+                    json.len()
+                );
+                self.session_url =
+                    export::download(json.as_bytes(), SESSION_FILE, "application/json");
+                false
+            }
+            Msg::RestoreSession(snapshot) => {
+                self.restore_session(&snapshot);
+                true
+            }
+            Msg::ReplayRun => {
+                let Some(recording) = self.current_recording() else {
+                    return false;
+                };
+                self.begin_replay(recording);
+                true
+            }
+            Msg::ReplayRecording(recording) => {
+                self.begin_replay((*recording).clone());
+                true
+            }
+            Msg::ReplayTick => self.replay_step(),
+            Msg::StopReplay => {
+                self.replay = None;
                 true
             }
+            Msg::SaveRecording => {
+                let Some(recording) = self.current_recording() else {
+                    return false;
+                };
+                let json = recording.to_json();
+                log::info!(
+                    "Saved a recording of {} steps, {} bytes",
+                    recording.step_count(),
+                    json.len()
+                );
+                self.session_url =
+                    export::download(json.as_bytes(), RECORDING_FILE, "application/json");
+                false
+            }
             Msg::SetInitialPoint(x) => {
-                log::info!("User set initial x manually to {:?}", x);
-                if let Some(prob) = &mut self.current_problem {
-                    prob.x_vector = x;
+                log::info!("User set initial x manually: {}", pretty::vector("x", &x));
+                if let Some(history) = self.affine_mut() {
+                    history.problem.set_start(x.clone());
+                    self.record(RunEvent::SetStart(x));
                 }
                 true
             }
         }
     }
 
-    fn view(&self, ctx: &Context<Self>) -> Html {
-        let link = ctx.link();
-        html! {
-            <div class="app-container">
-                <h1>{ "Interior-Point Solver" }</h1>
+    /// Hands the solver options to the run in progress.
+    fn share_options(&mut self) {
+        if let Some(run) = &mut self.run {
+            run.set_options(&self.options);
+        }
+    }
 
-                <div>
-                    <button class="back-button" onclick={link.callback(|_| Msg::Reset)}>
-                        { "Reset / Clear" }
-                    </button>
+    fn affine(&self) -> Option<&AffineHistory> {
+        self.run.as_ref().and_then(ActiveRun::affine)
+    }
 
-                    <InputForm
-                        on_submit={
-                            link.callback(
-                                |input: InputFormData| match input {
-                                    InputFormData::InteriorPointInput(a, b, c, alpha, initial, maximize, is_augmented) => {
-                                        Msg::StartInteriorPoint {
-                                            a, b, c, alpha, initial, maximize
-                                        }
-                                    }
-                                    _ => Msg::Reset,
-                                }
-                            )
+    fn affine_mut(&mut self) -> Option<&mut AffineHistory> {
+        self.run.as_mut().and_then(ActiveRun::affine_mut)
+    }
+
+    fn change_setting(&mut self, change: SettingChange) -> bool {
+        match change {
+            SettingChange::LogVerbosity(level) => {
+                self.options.log_verbosity = level;
+                logging::set_verbosity(level);
+                self.share_options();
+            }
+            SettingChange::Precision(precision) => {
+                self.options.precision = precision;
+                self.share_options();
+            }
+            SettingChange::HideProjection(hide) => {
+                self.options.hide_projection = hide;
+                self.share_options();
+            }
+            SettingChange::CrossCheck(check) => {
+                self.options.cross_check = check;
+                self.share_options();
+            }
+            SettingChange::ScaleFloor(floor) => {
+                self.options.scale_floor = floor;
+                self.share_options();
+            }
+            SettingChange::NormalShift(shift) => {
+                self.options.normal_shift = shift;
+                self.share_options();
+            }
+            SettingChange::Stopping(stopping) => {
+                self.options.stopping = stopping;
+                self.share_options();
+            }
+            SettingChange::Schedule(schedule) => {
+                self.options.schedule = schedule.sanitized();
+                self.share_options();
+            }
+            SettingChange::Norm(norm) => {
+                self.options.norm = norm;
+                self.share_options();
+            }
+            SettingChange::Notation(notation) => self.notation = notation,
+            SettingChange::Narration(narrate) => {
+                self.narrate = narrate;
+                match (&self.narration, narrate) {
+                    (Some(text), true) => narration::speak(text),
+                    (_, false) => narration::silence(),
+                    _ => {}
+                }
+            }
+            SettingChange::Compact(compact) => self.compact = compact,
+            SettingChange::Palette(choice) => {
+                self.palette = choice;
+                palette::save_palette(choice);
+            }
+        }
+        if let Some(recording) = &mut self.recording {
+            recording.record_options(&self.options);
+        }
+        true
+    }
+
+    /// Replaces every solver option at once, as a restored session or a
+    /// replayed settings change does.
+    fn set_options(&mut self, options: SolverOptions) {
+        self.options = options;
+        logging::set_verbosity(self.options.log_verbosity);
+        self.share_options();
+        if let Some(recording) = &mut self.recording {
+            recording.record_options(&self.options);
+        }
+    }
+
+    /// Where a run on `model` begins: Phase 1's analytic center when the
+    /// form asks for it, the form's initial point otherwise, moved to the
+    /// last optimum for a warm affine-scaling start. `None` when Phase 1
+    /// failed, explained by `find_start`.
+    fn start_point(&mut self, model: &ValidatedProblem, warm: bool) -> Option<DVector<f64>> {
+        let n = model.a.ncols();
+        let mut x = match model.method {
+            // The simplex methods start from a basis and the embedding
+            // anywhere positive, so there is no Phase 1 even when the form
+            // asks for one.
+            SolverMethod::Simplex | SolverMethod::RevisedSimplex | SolverMethod::SelfDual => {
+                return Some(initial_point(&model.initial, n));
+            }
+            _ if model.options.find_start => self.find_start(model, model.options.alpha)?,
+            _ => initial_point(&model.initial, n),
+        };
+        if model.method != SolverMethod::AffineScaling || !warm {
+            return Some(x);
+        }
+        if let Some(previous) = &self.last_solution {
+            match warm_start(&model.a, &model.b, previous, &x) {
+                Some(warm_x) => {
+                    if self.options.logs_summary() {
+                        log::info!(
+                            "Warm start from the last solution: {}",
+                            pretty::vector("x", &warm_x)
+                        );
+                    }
+                    x = warm_x;
+                }
+                None => log::warn!("The model changed size since the last solution; starting cold"),
+            }
+        }
+        Some(x)
+    }
+
+    /// Runs Phase 1 and centers the result for the automatic start; on failure
+    /// the run is reset with an explanation and `None` is returned.
+    fn find_start(&mut self, model: &ValidatedProblem, alpha: f64) -> Option<DVector<f64>> {
+        match analytic_center(&model.a, &model.b, alpha, 500, &self.options) {
+            Ok(center) => {
+                log::info!(
+                    "Analytic center after {} Phase 1 and {} Newton iterations: {}",
+                    center.phase_one_iterations,
+                    center.newton_iterations,
+                    pretty::vector("x", &center.x)
+                );
+                Some(center.x)
+            }
+            Err(e) => {
+                self.reset_run();
+                self.done = true;
+                self.error_message = Some(match &e {
+                    InteriorPointError::NotFeasible { rows } => format!(
+                        "Phase 1 could not drive the artificial variables to zero, so the constraints have no strictly positive solution. {}",
+                        match rows.is_empty() {
+                            true => "Check your constraints for contradictions.".to_string(),
+                            false => format!("It could not satisfy {}; check them for contradictions.", constraint_names(&e.rows(), Some(model))),
                         }
-                        on_size_change={link.callback(|(vars, cons)| Msg::SetProblemSize(vars, cons))}
-                    />
+                    ),
+                    e => describe_start_error(e, Some(model)),
+                });
+                self.flagged_constraints = flagged_constraints(&e, Some(model));
+                None
+            }
+        }
+    }
 
-                    <button class="next-step-button" onclick={link.callback(|_| Msg::NextStep)}>
-                        { "Next Interior-Point Step" }
-                    </button>
-                </div>
+    /// The current model and last iterate as repro JSON, if a model is loaded.
+    fn export_repro(&self) -> Option<String> {
+        let model = self.model.as_ref()?;
+        let path = self.iterate_path();
+        Some(export_repro(
+            model,
+            &self.options,
+            &ReproIteration {
+                index: path.len(),
+                status: self.report.as_ref().map(|report| &report.status),
+                x: path.last(),
+                notes: &self.notes,
+            },
+        ))
+    }
+
+    /// The run as a session file can hold it, unless there is none or it
+    /// is still solving in the worker.
+    fn session_snapshot(&self) -> Option<SessionSnapshot> {
+        if self.solver_worker.is_some() {
+            return None;
+        }
+        let model = self.model.as_deref()?.clone();
+        let run = match self.affine() {
+            Some(history) => SessionRun::AffineScaling {
+                problem: Box::new(history.problem.clone()),
+                iterations: history.iterations.clone(),
+            },
+            None => SessionRun::Replayed {
+                steps: self.run_steps(),
+            },
+        };
+        Some(SessionSnapshot {
+            model,
+            options: self.options.clone(),
+            run,
+            notes: self.notes.clone(),
+            timings: self.timings.clone(),
+            status: self.report.as_ref().map(|report| report.status.clone()),
+            ray: self
+                .report
+                .as_ref()
+                .and_then(|report| report.ray.as_ref())
+                .map(|ray| ray.direction.clone()),
+            error_message: self.error_message.clone(),
+        })
+    }
+
+    /// What produced each card of the active run, the start included.
+    fn run_steps(&self) -> Vec<RunStep> {
+        self.run.as_ref().map(ActiveRun::steps).unwrap_or_default()
+    }
+
+    /// Puts the app back where `snapshot` was saved. Affine scaling comes
+    /// back as stored; the other methods are started again and stepped as
+    /// before, which gives the same iterations since they are
+    /// deterministic.
+    fn restore_session(&mut self, snapshot: &SessionSnapshot) {
+        self.set_options(snapshot.options.clone());
+        match &snapshot.run {
+            SessionRun::AffineScaling {
+                problem,
+                iterations,
+            } => {
+                self.reset_run();
+                self.model = Some(Rc::new(snapshot.model.clone()));
+                self.run = Some(ActiveRun::Affine(History {
+                    problem: (**problem).clone(),
+                    iterations: iterations.clone(),
+                }));
+                self.notes = snapshot.notes.clone();
+                self.timings = snapshot.timings.clone();
+                if let Some(status) = &snapshot.status {
+                    self.finish_with_ray(status.clone(), snapshot.ray.clone());
+                }
+            }
+            SessionRun::Replayed { steps } => {
+                self.handle(Msg::Start(Box::new(snapshot.model.clone()), false));
+                self.notes = snapshot.notes.clone();
+                for step in steps.iter().skip(1) {
+                    self.handle(match step {
+                        RunStep::Step => Msg::NextStep,
+                        RunStep::Centering => Msg::CenteringStep,
+                    });
+                }
+                // The step that ended the run left no card.
+                if snapshot.status.is_some() && !self.done {
+                    self.handle(Msg::NextStep);
+                }
+                self.timings = snapshot.timings.clone();
+                if let Some(report) = &mut self.report {
+                    Rc::make_mut(report).timings = snapshot.timings.clone();
+                }
+                let status = self.report.as_ref().map(|report| &report.status);
+                if self.step_count() != steps.len() || status != snapshot.status.as_ref() {
+                    log::warn!(
+                        "Restored run took {} steps where the saved one took {}",
+                        self.step_count(),
+                        steps.len()
+                    );
+                    self.error_message = Some(format!(
+                        "The restored run took {} steps where the saved one took {}, so it no longer matches the session. Was it saved by another version?",
+                        self.step_count(),
+                        steps.len()
+                    ));
+                    return;
+                }
+            }
+        }
+        if snapshot.error_message.is_some() {
+            self.done = true;
+            self.error_message = snapshot.error_message.clone();
+        }
+    }
+
+    /// Makes `run` the active run on `model` and takes its first card.
+    fn open_run(&mut self, model: ValidatedProblem, mut run: ActiveRun) {
+        self.reset_run();
+        self.begin_run(model, run.affine().map(|history| &history.problem));
+        let error = run.open(&mut self.timings);
+        // The embedding can certify infeasibility or unboundedness on its
+        // first step as on any other.
+        let certifies = matches!(run, ActiveRun::SelfDual(_));
+        self.run = Some(run);
+        match error {
+            None => {}
+            Some(StepError::Interior(e))
+                if certifies && !matches!(e, InteriorPointError::SingularMatrix { .. }) =>
+            {
+                self.finish_interior(e)
+            }
+            Some(e) => self.fail_start(&e),
+        }
+    }
+
+    /// Makes `model` the active one and starts recording the run on it;
+    /// `start` is affine scaling's state before its first step.
+    fn begin_run(&mut self, model: ValidatedProblem, start: Option<&InteriorPointProblem>) {
+        self.recording = Some(Recording::new(model.clone(), self.options.clone(), start));
+        self.model = Some(Rc::new(model));
+    }
+
+    fn record(&mut self, event: RunEvent) {
+        if let Some(recording) = &mut self.recording {
+            recording.events.push(event);
+        }
+    }
+
+    /// The recording of the active run with the iterates it produced, to
+    /// check a replay against.
+    fn current_recording(&self) -> Option<Recording> {
+        if self.solver_worker.is_some() {
+            return None;
+        }
+        let mut recording = self.recording.clone()?;
+        recording.path = self
+            .iterate_path()
+            .iter()
+            .map(|x| x.as_slice().to_vec())
+            .collect();
+        Some(recording)
+    }
+
+    /// Starts the recorded run again from its inputs; the replay controls
+    /// then tick through its actions.
+    fn begin_replay(&mut self, recording: Recording) {
+        log::info!(
+            "Replaying {} steps of a {} run",
+            recording.step_count(),
+            recording.model.method.label()
+        );
+        self.set_options(recording.options.clone());
+        match &recording.start {
+            Some(problem) => {
+                let mut problem = (**problem).clone();
+                problem.options = self.options.clone();
+                self.open_run(
+                    recording.model.clone(),
+                    ActiveRun::Affine(History::new(problem)),
+                );
+            }
+            None => {
+                self.handle(Msg::Start(Box::new(recording.model.clone()), false));
+            }
+        }
+        let replay = Replay::new(recording);
+        self.replay_outcome = None;
+        match replay.check(&self.iterate_path()) {
+            Ok(()) if replay.is_finished() => {
+                self.replay_outcome = Some(Ok(self.iterate_path().len()));
+            }
+            Ok(()) => self.replay = Some(replay),
+            Err(e) => self.replay_outcome = Some(Err(format!("The replay diverged: {}", e))),
+        }
+    }
+
+    /// Takes the next recorded step of the replay and checks the iterate
+    /// it gives against the recording.
+    fn replay_step(&mut self) -> bool {
+        let Some(events) = self
+            .replay
+            .as_mut()
+            .and_then(|replay| replay.next_events().map(<[RunEvent]>::to_vec))
+        else {
+            return false;
+        };
+        for event in events {
+            match event {
+                RunEvent::Step => {
+                    self.handle(Msg::NextStep);
+                }
+                RunEvent::Centering => {
+                    self.handle(Msg::CenteringStep);
+                }
+                RunEvent::Options(options) => self.set_options(options),
+                RunEvent::Edit(edit) => {
+                    self.handle(Msg::EditState(edit));
+                }
+                RunEvent::SetStart(x) => {
+                    self.handle(Msg::SetInitialPoint(x));
+                }
+            }
+        }
+        let path = self.iterate_path();
+        let Some(replay) = &self.replay else {
+            return true;
+        };
+        match replay.check(&path) {
+            Err(e) => {
+                log::warn!("Replay diverged: {}", e);
+                self.replay_outcome = Some(Err(format!("The replay diverged: {}", e)));
+                self.replay = None;
+            }
+            Ok(()) if replay.is_finished() => {
+                log::info!(
+                    "Replay matched the recording over {} iterations",
+                    path.len()
+                );
+                self.replay_outcome = Some(Ok(path.len()));
+                self.replay = None;
+            }
+            Ok(()) => {}
+        }
+        true
+    }
+
+    fn reset_run(&mut self) {
+        self.solver_worker = None;
+        self.run = None;
+        self.done = false;
+        self.timings = Timings::default();
+        self.report = None;
+        self.repro = None;
+        self.dashboard_dismissed = false;
+        self.notes.clear();
+        self.crash = None;
+        self.error_message = None;
+        self.model = None;
+        self.narrated = (0, false);
+        self.flagged_constraints.clear();
+        self.recording = None;
+        self.replay = None;
+        self.replay_outcome = None;
+    }
+
+    /// When A has dependent rows, the relations among them, with the rows
+    /// involved marked in the form. Called after a factorization failed.
+    fn diagnose_rank(&mut self) -> Option<String> {
+        let model = self.model.as_ref()?;
+        let deficiency = rank::diagnose(&model.a, &model.b)?;
+        self.flagged_constraints = deficiency
+            .dependent_rows()
+            .iter()
+            .filter_map(|&i| model.constraint_labels.get(i).cloned())
+            .map(|label| (label, "Linearly dependent on other constraints".to_string()))
+            .collect();
+        Some(deficiency.describe(&model.constraint_labels))
+    }
+
+    /// `message` for a failed start, followed by the dependent rows of A
+    /// when `singular` says a factorization failed.
+    fn explain_failure(&mut self, message: String, singular: bool) -> String {
+        match singular.then(|| self.diagnose_rank()).flatten() {
+            Some(note) => format!("{} {}", message, note),
+            None => message,
+        }
+    }
+
+    fn step_count(&self) -> usize {
+        self.run.as_ref().map_or(0, ActiveRun::len)
+    }
 
+    /// Describes the step just taken, and the outcome once the run finishes,
+    /// for the live region and, when enabled, for speech.
+    fn narrate_progress(&mut self) {
+        let state = (self.step_count(), self.report.is_some());
+        if state == self.narrated {
+            return;
+        }
+        let mut sentences = vec![];
+        if state.0 > self.narrated.0 {
+            sentences.extend(self.describe_latest_step());
+        }
+        if let (Some(report), false) = (&self.report, self.narrated.1) {
+            sentences.push(narration::outcome(report));
+        }
+        self.narrated = state;
+        if sentences.is_empty() {
+            return;
+        }
+        let text = sentences.join(" ");
+        if self.narrate {
+            narration::speak(&text);
+        }
+        self.narration = Some(text);
+    }
+
+    fn describe_latest_step(&self) -> Option<String> {
+        let model = self.model.as_ref()?;
+        let original = model.augmentation.original_variables;
+        let offset = model
+            .presolve
+            .as_ref()
+            .map_or(0.0, |presolve| presolve.objective_offset);
+        let objective = |x: &DVector<f64>| {
+            let n = model.c.len().min(x.len());
+            model.c.rows(0, n).dot(&x.rows(0, n)) + offset
+        };
+        self.run.as_ref()?.describe_latest(objective, original)
+    }
+
+    /// Iterates of the active run, oldest first.
+    fn iterate_path(&self) -> Vec<DVector<f64>> {
+        self.run.as_ref().map(ActiveRun::path).unwrap_or_default()
+    }
+
+    fn note_list(&self) -> Vec<(usize, String)> {
+        self.notes
+            .iter()
+            .map(|(&index, text)| (index, text.clone()))
+            .collect()
+    }
+
+    /// Text box under iteration card `index` for the user's note on it.
+    fn render_note(&self, ctx: &Context<Self>, index: usize) -> Html {
+        html! {
+            <textarea
+                class="iteration-note"
+                rows="2"
+                placeholder={format!("Note on iteration {}", index)}
+                value={self.notes.get(&index).cloned().unwrap_or_default()}
+                onchange={ctx.link().callback(move |e: Event| {
+                    let input: HtmlTextAreaElement = e.target_unchecked_into();
+                    Msg::SetNote(index, input.value())
+                })}
+            />
+        }
+    }
+
+    /// What the search over the history can test, one entry per iteration
+    /// of the active run. Z is in the model's own sense.
+    fn iteration_facts(&self) -> Vec<IterationFacts> {
+        let Some(model) = &self.model else {
+            return vec![];
+        };
+        let sign = model.sense.sign();
+        let user_objective = |x: &DVector<f64>| {
+            if x.len() == model.c.len() {
+                model.c.dot(x)
+            } else {
+                f64::NAN
+            }
+        };
+        self.run
+            .as_ref()
+            .map(|run| run.facts(user_objective, sign))
+            .unwrap_or_default()
+    }
+
+    /// The parsed history search and which iteration cards pass it; every
+    /// card passes while the search is empty or does not parse.
+    fn filtered_iterations(&self) -> (Result<HistoryFilter, String>, Vec<bool>) {
+        let labels = self
+            .model
+            .as_ref()
+            .map(|model| model.variable_labels.clone())
+            .unwrap_or_default();
+        let filter = HistoryFilter::parse(&self.history_filter, &labels);
+        let shown = self
+            .iteration_facts()
+            .iter()
+            .map(|facts| filter.as_ref().map_or(true, |filter| filter.matches(facts)))
+            .collect();
+        (filter, shown)
+    }
+
+    /// Search box over the iteration cards, with a button per match that
+    /// scrolls to it.
+    fn render_history_filter(
+        &self,
+        ctx: &Context<Self>,
+        filter: &Result<HistoryFilter, String>,
+        shown: &[bool],
+    ) -> Html {
+        if shown.is_empty() || self.error_message.is_some() {
+            return html! {};
+        }
+        let link = ctx.link();
+        let matches: Vec<usize> = (0..shown.len()).filter(|&i| shown[i]).collect();
+        html! {
+            <div class="history-filter">
+                <label>{ "Find iterations: " }
+                    <input
+                        type="search"
+                        placeholder="objective > 10, x3 < 0.01, warnings present"
+                        value={self.history_filter.clone()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            Msg::FilterHistory(input.value())
+                        })}
+                    />
+                </label>
                 {
-                    if let Some(error) = &self.error_message {
-                        html! {
-                            <div class="error-message">
-                                <div class="error-icon">{ "⚠️" }</div>
-                                <h3>{ "Problem Detected" }</h3>
-                                <p>{ error }</p>
-                                <div class="error-actions">
-                                    <p><strong>{ "What to try:" }</strong></p>
-                                    <ul>
-                                        <li>{ "Check that your constraints are consistent and don't contradict each other" }</li>
-                                        <li>{ "Ensure your initial point satisfies all constraints and is positive" }</li>
-                                        <li>{ "Verify your constraint matrix is well-formed" }</li>
-                                        <li>{ "Try different initial values or adjust the step size (α)" }</li>
-                                    </ul>
-                                    <button onclick={link.callback(|_| Msg::Reset)}>
-                                        { "← Go Back and Try Again" }
-                                    </button>
-                                </div>
-                            </div>
-                        }
-                    } else if let Some(_prob) = &self.current_problem {
-                        html! {
-                            <div class="iterations">
+                    match filter {
+                        Err(message) => html! { <p class="report-warning">{ message }</p> },
+                        Ok(filter) if filter.is_empty() => html! {},
+                        Ok(_) => html! {
+                            <p class="race-hint">
+                                { format!("{} of {} iterations match", matches.len(), shown.len()) }
                                 {
-                                    for self.interior_iterations.iter().enumerate().map(|(i, iteration_data)| {
-                                                    html! {
-                                                        <InteriorPointView
-                                                            iteration={i}
-                                                            iteration_data={Some(iteration_data.clone())}
-                                                        />
-                                                    }
-                                                })
+                                    for matches.iter().map(|&i| html! {
+                                        <button class="history-jump" onclick={link.callback(move |_| Msg::JumpToIteration(i))}>
+                                            { i }
+                                        </button>
+                                    })
                                 }
-                            </div>
-                        }
-                    } else {
-                        html! {
-                            <div class="no-problem-message">
-                                <div class="message-icon">{ "📊" }</div>
-                                <h3>{ "Ready to Solve" }</h3>
-                                <p>{ "Configure your linear programming problem above and press \"Solve\" to begin the interior-point algorithm visualization." }</p>
-                            </div>
-                        }
+                            </p>
+                        },
                     }
                 }
             </div>
         }
     }
+
+    /// Recovery screen after a solver panic, above the iterations that
+    /// completed before it.
+    fn render_crash(&self, ctx: &Context<Self>) -> Html {
+        let Some(message) = &self.crash else {
+            return html! {};
+        };
+        let link = ctx.link();
+        html! {
+            <div class="error-message crash-screen">
+                <div class="error-icon">{ "💥" }</div>
+                <h3>{ "The solver crashed" }</h3>
+                <p>{ message }</p>
+                <div class="error-actions">
+                    <p>{ "The iterations completed before the crash are kept below. Exporting a repro captures the model and the last iterate for a bug report." }</p>
+                    <button onclick={link.callback(|_| Msg::ExportRepro)}>
+                        { "Export minimal repro" }
+                    </button>
+                    <button onclick={link.callback(|_| Msg::Reset)}>
+                        { "← Go Back and Try Again" }
+                    </button>
+                </div>
+            </div>
+        }
+    }
+
+    /// Sense of the model being solved; a fresh app reports as maximization.
+    fn pack_preset(&self) -> Option<course_pack::PackProblem> {
+        let pack = self.course_pack.as_ref()?;
+        pack.problems.get(self.pack_problem?).cloned()
+    }
+
+    /// Records the loaded pack problem as completed in this browser.
+    fn complete_pack_problem(&mut self) {
+        let (Some(pack), Some(k)) = (&self.course_pack, self.pack_problem) else {
+            return;
+        };
+        let Some(problem) = pack.problems.get(k) else {
+            return;
+        };
+        if !self.completed_problems.contains(&problem.name) {
+            self.completed_problems.push(problem.name.clone());
+            course_pack::save_completed(pack, &self.completed_problems);
+        }
+    }
+
+    /// Marks the active run as finished and assembles its report.
+    fn finish(&mut self, status: SolveStatus) {
+        self.finish_with_ray(status, None);
+    }
+
+    /// Ends the run on the error its last step stopped with: an optimum, a
+    /// certificate, or a breakdown.
+    fn finish_run(&mut self, error: StepError) {
+        match error {
+            StepError::Interior(e) => self.finish_interior(e),
+            StepError::Simplex(SimplexError::Optimal) => {
+                log::info!("No improving reduced cost => basis is optimal.");
+                self.finish(SolveStatus::Optimal);
+            }
+            StepError::Simplex(e) => {
+                log::error!("Simplex iteration error: {:?}", e);
+                self.finish_simplex_error(e);
+            }
+        }
+    }
+
+    /// Ends an interior run on the optimum or certificate it stopped with,
+    /// marking the constraints a breakdown traced.
+    fn finish_interior(&mut self, error: InteriorPointError) {
+        match &error {
+            InteriorPointError::Stopped(reason) => {
+                log::info!("Stopping criterion met: {}.", reason.label())
+            }
+            InteriorPointError::Unbounded(_) => {
+                log::info!("Improving ray found => unbounded.")
+            }
+            _ => {
+                log::error!("Interior point iteration error: {}", error);
+                self.flagged_constraints = flagged_constraints(&error, self.model.as_deref());
+            }
+        }
+        let status = SolveStatus::from(&error);
+        let ray = match error {
+            InteriorPointError::Unbounded(direction) => Some(direction),
+            _ => None,
+        };
+        self.finish_with_ray(status, ray);
+    }
+
+    /// Ends a run that failed before or on its first step, explaining why;
+    /// dependent rows of A are listed when a factorization failed.
+    fn fail_start(&mut self, error: &StepError) {
+        self.done = true;
+        let (message, singular) = match error {
+            StepError::Interior(e) => (
+                describe_start_error(e, self.model.as_deref()),
+                matches!(e, InteriorPointError::SingularMatrix { .. }),
+            ),
+            StepError::Simplex(e) => (
+                describe_simplex_error(e),
+                matches!(e, SimplexError::SingularBasis),
+            ),
+        };
+        self.error_message = Some(self.explain_failure(message, singular));
+        if let (StepError::Interior(e), false) = (error, singular) {
+            self.flagged_constraints = flagged_constraints(e, self.model.as_deref());
+        }
+    }
+
+    /// Ends a simplex run that broke down, explaining why; dependent rows of
+    /// A are listed when the basis went singular.
+    fn finish_simplex_error(&mut self, error: SimplexError) {
+        self.finish(SolveStatus::from(&error));
+        let singular = matches!(error, SimplexError::SingularBasis);
+        self.error_message = Some(self.explain_failure(describe_simplex_error(&error), singular));
+    }
+
+    /// `finish` with the improving ray the run stopped on when the objective
+    /// is unbounded.
+    fn finish_with_ray(&mut self, status: SolveStatus, ray: Option<DVector<f64>>) {
+        self.done = true;
+        let Some(run) = &self.run else {
+            return;
+        };
+        let mut report = run.report(status, ray);
+        let mu_decrease = run.mu_decrease();
+
+        report.timings = self.timings.clone();
+        if matches!(report.status, SolveStatus::Failed(_)) {
+            if let Some(note) = self.diagnose_rank() {
+                report.warnings.push(note);
+            }
+        }
+        if let Some(model) = &self.model {
+            report.bound = Some(iteration_bound(
+                model.method,
+                model.a.nrows(),
+                model.a.ncols(),
+                model.options.mu,
+                mu_decrease,
+            ));
+            report.complete(model, &self.options);
+        }
+        if report.status == SolveStatus::Optimal {
+            self.complete_pack_problem();
+            self.last_solution = Some(report.solution.clone());
+        }
+        if let Some(model) = &self.model {
+            let mut run = RunRecord::new(model, &report);
+            run.notes = self.note_list();
+            Rc::make_mut(&mut self.runs).push(run);
+        }
+        self.report = Some(Rc::new(report));
+    }
+}
+
+/// Viewports narrower than this, phones and portrait tablets, start with
+/// compact iteration cards.
+const COMPACT_WIDTH: f64 = 900.0;
+
+/// Element id of the card of iteration `index`, for jumping to it.
+fn iteration_anchor(index: usize) -> String {
+    format!("iteration-{}", index)
+}
+
+/// Scrolls the card of iteration `index` into view, if it is on the page.
+fn scroll_to_iteration(index: usize) {
+    let card = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(&iteration_anchor(index)));
+    if let Some(card) = card {
+        card.scroll_into_view();
+    }
+}
+
+fn narrow_viewport() -> bool {
+    web_sys::window()
+        .and_then(|window| window.inner_width().ok())
+        .and_then(|width| width.as_f64())
+        .is_some_and(|width| width < COMPACT_WIDTH)
+}
+
+fn initial_point(initial: &[f64], final_n: usize) -> DVector<f64> {
+    if initial.len() == final_n {
+        DVector::from_vec(initial.to_vec())
+    } else {
+        let mut new_init = vec![1.0; final_n];
+        for (i, val) in initial.iter().enumerate() {
+            if i < final_n {
+                new_init[i] = val.max(1e-4);
+            }
+        }
+        DVector::from_vec(new_init)
+    }
+}
+
+fn describe_start_error(error: &InteriorPointError, model: Option<&ValidatedProblem>) -> String {
+    match error {
+        InteriorPointError::NoImprovement => "The algorithm converged immediately or found no improvement direction. This might indicate the initial point is already optimal, or the problem constraints are inconsistent.".to_string(),
+        InteriorPointError::Stopped(reason) => format!("The solver stopped before its first step: {}. The initial point may already be optimal, or the stopping tolerances are too loose.", reason.label()),
+        InteriorPointError::Unbounded(_) => "The objective is unbounded: the projected gradient points along a direction that stays feasible forever.".to_string(),
+        InteriorPointError::NotFeasible { rows } if rows.is_empty() => "The problem appears to be infeasible. Please check your constraints and initial point to ensure they form a valid feasible region.".to_string(),
+        InteriorPointError::NotFeasible { .. } => format!("The problem is infeasible: {} cannot all hold with x >= 0. Check them for contradictions.", constraint_names(&error.rows(), model)),
+        InteriorPointError::NotInterior { column, value } => format!("{} = {:.3e} is not strictly positive, so the barrier cannot start there. Pick an initial point with every entry above zero.", variable_name(*column, model), value),
+        InteriorPointError::SingularMatrix { matrix, iteration: Some(k), .. } if *k > 0 => format!("Mathematical error at iteration {}: {}. This usually means the constraint matrix is ill-conditioned or the problem is degenerate. Try adjusting your constraints or initial point.", k, matrix),
+        InteriorPointError::SingularMatrix { matrix, .. } => format!("Mathematical error: {}. This usually means the constraint matrix is ill-conditioned or the problem is degenerate. Try adjusting your constraints or initial point.", matrix),
+        InteriorPointError::DimensionMismatch { .. } => format!("The model's dimensions do not match: {}.", error),
+        InteriorPointError::NumericalBreakdown { quantity, index, value, .. } => {
+            let entry = match *quantity {
+                "x" | "D" | "c~" | "P c~" | "P 1" => format!(" (the entry of {})", variable_name(*index, model)),
+                "y" => format!(" (the entry of {})", constraint_names(&[*index], model)),
+                _ => String::new(),
+            };
+            format!("{} came out as {}{} on the first step, so the run was stopped before it filled the cards with garbage. Coefficients far apart in size are the usual cause; try the Equilibrate option.", quantity, value, entry)
+        }
+    }
+}
+
+/// The constraints `error` is about, each with why, for the form to mark.
+fn flagged_constraints(
+    error: &InteriorPointError,
+    model: Option<&ValidatedProblem>,
+) -> Vec<(String, String)> {
+    let why = match error {
+        InteriorPointError::NotFeasible { .. } => {
+            "Cannot hold together with the other marked constraints"
+        }
+        InteriorPointError::SingularMatrix { .. } => "Linearly dependent on other constraints",
+        _ => return vec![],
+    };
+    error
+        .rows()
+        .iter()
+        .map(|&i| (constraint_names(&[i], model), why.to_string()))
+        .collect()
+}
+
+/// The labels of `rows`, as the form shows them.
+fn constraint_names(rows: &[usize], model: Option<&ValidatedProblem>) -> String {
+    rows.iter()
+        .map(|&i| {
+            model
+                .and_then(|model| model.constraint_labels.get(i).cloned())
+                .unwrap_or_else(|| format!("row {}", i + 1))
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn variable_name(column: usize, model: Option<&ValidatedProblem>) -> String {
+    model
+        .and_then(|model| model.variable_labels.get(column).cloned())
+        .unwrap_or_else(|| format!("x{}", column + 1))
+}
+
+fn describe_simplex_error(error: &SimplexError) -> String {
+    match error {
+        SimplexError::Optimal => "The starting tableau is already optimal.".to_string(),
+        SimplexError::Unbounded(j) => format!("The objective is unbounded: x{} can increase forever without violating any constraint.", j + 1),
+        SimplexError::SingularBasis => "The basis matrix became singular during a pivot; the constraint matrix may have dependent rows.".to_string(),
+        SimplexError::Infeasible(row) => format!("No x >= 0 satisfies the constraints: row {} cannot be made feasible.", row + 1),
+        SimplexError::NotDualFeasible => "The dual simplex needs a starting basis with no positive reduced cost.".to_string(),
+        SimplexError::PivotLimit => "The simplex method stopped after too many pivots; the model may be cycling.".to_string(),
+        SimplexError::NoInitialBasis => "The simplex method needs an identity column (a slack or artificial) in every row to start from. In \"Already Augmented\" mode, include one per row, or switch to Auto-Augment which adds them for you.".to_string(),
+    }
 }
@@ -0,0 +1,133 @@
+use yew::prelude::*;
+
+use crate::network::Arc;
+
+const SIZE: f64 = 380.0;
+const RADIUS: f64 = 140.0;
+const NODE_RADIUS: f64 = 16.0;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub supplies: Vec<f64>,
+    pub arcs: Vec<Arc>,
+    /// Flow on each arc, same order as `arcs`.
+    pub flows: Vec<f64>,
+    /// Arcs of the last pivot cycle.
+    #[prop_or_default]
+    pub highlighted: Vec<usize>,
+}
+
+/// Nodes on a circle with each arc drawn as an arrow whose width grows with
+/// its flow, labelled flow / capacity @ cost.
+pub struct NetworkGraph;
+
+impl Component for NetworkGraph {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let n = props.supplies.len();
+        let center = SIZE / 2.0;
+        let position = |i: usize| {
+            let angle =
+                std::f64::consts::TAU * i as f64 / n.max(1) as f64 - std::f64::consts::FRAC_PI_2;
+            (center + RADIUS * angle.cos(), center + RADIUS * angle.sin())
+        };
+        let max_flow = props
+            .flows
+            .iter()
+            .copied()
+            .fold(0.0_f64, f64::max)
+            .max(1e-9);
+
+        let arcs = props
+            .arcs
+            .iter()
+            .enumerate()
+            .filter(|(_, arc)| arc.from < n && arc.to < n)
+            .map(|(k, arc)| {
+                let (x1, y1) = position(arc.from);
+                let (x2, y2) = position(arc.to);
+                let length = ((x2 - x1).powi(2) + (y2 - y1).powi(2)).sqrt().max(1e-9);
+                let (ux, uy) = ((x2 - x1) / length, (y2 - y1) / length);
+                // Offset both directions of a pair sideways so i->j and j->i do
+                // not overlap, and stop at the node circles.
+                let (ox, oy) = (-uy * 5.0, ux * 5.0);
+                let start = (x1 + ux * NODE_RADIUS + ox, y1 + uy * NODE_RADIUS + oy);
+                let end = (
+                    x2 - ux * (NODE_RADIUS + 4.0) + ox,
+                    y2 - uy * (NODE_RADIUS + 4.0) + oy,
+                );
+                let flow = props.flows.get(k).copied().unwrap_or(0.0);
+                let width = 1.0 + 5.0 * flow / max_flow;
+                let capacity = arc.capacity.map_or("∞".to_string(), short);
+                let class = classes!(
+                    "network-arc",
+                    (flow > 1e-9).then_some("used"),
+                    props.highlighted.contains(&k).then_some("cycle")
+                );
+                html! {
+                    <g class={class}>
+                        <line
+                            x1={start.0.to_string()}
+                            y1={start.1.to_string()}
+                            x2={end.0.to_string()}
+                            y2={end.1.to_string()}
+                            stroke-width={format!("{:.2}", width)}
+                            marker-end="url(#network-arrow)"
+                        />
+                        <text
+                            x={((start.0 + end.0) / 2.0 + ox * 2.0).to_string()}
+                            y={((start.1 + end.1) / 2.0 + oy * 2.0).to_string()}
+                        >
+                            { format!("{}/{} @{}", short(flow), capacity, short(arc.cost)) }
+                        </text>
+                    </g>
+                }
+            });
+
+        let nodes = props.supplies.iter().enumerate().map(|(i, &supply)| {
+            let (x, y) = position(i);
+            let class = classes!(
+                "network-node",
+                (supply > 0.0).then_some("source"),
+                (supply < 0.0).then_some("sink")
+            );
+            html! {
+                <g class={class}>
+                    <circle cx={x.to_string()} cy={y.to_string()} r={NODE_RADIUS.to_string()} />
+                    <text class="node-label" x={x.to_string()} y={(y + 4.0).to_string()}>{ i + 1 }</text>
+                    <text class="node-supply" x={x.to_string()} y={(y + NODE_RADIUS + 14.0).to_string()}>
+                        { format!("b = {}", supply) }
+                    </text>
+                </g>
+            }
+        });
+
+        html! {
+            <div class="network-graph">
+                <h4>{ "Flows" }</h4>
+                <svg viewBox={format!("0 0 {} {}", SIZE, SIZE)} width={SIZE.to_string()} height={SIZE.to_string()}>
+                    <defs>
+                        <marker id="network-arrow" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="4" markerHeight="4" orient="auto-start-reverse">
+                            <path d="M 0 0 L 10 5 L 0 10 z" />
+                        </marker>
+                    </defs>
+                    { for arcs }
+                    { for nodes }
+                </svg>
+            </div>
+        }
+    }
+}
+
+/// At most two decimals, without trailing zeros.
+fn short(value: f64) -> String {
+    let text = format!("{:.2}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
@@ -0,0 +1,410 @@
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use super::network_graph::NetworkGraph;
+use crate::interior::SolverOptions;
+use crate::network::{
+    perform_network_simplex_iteration, Arc, NetworkError, NetworkIteration, NetworkProblem,
+    NetworkSimplexProblem,
+};
+use crate::report::SolveStatus;
+
+/// Largest node count the panel offers.
+const MAX_NODES: usize = 8;
+
+/// Pivots "Solve to End" takes before giving up; Dantzig's rule can cycle on
+/// degenerate trees.
+const ITERATION_LIMIT: usize = 500;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub options: SolverOptions,
+}
+
+pub enum Msg {
+    SetNodes(usize),
+    UpdateSupply(usize, f64),
+    AddArc,
+    RemoveArc(usize),
+    UpdateArcFrom(usize, usize),
+    UpdateArcTo(usize, usize),
+    UpdateArcCapacity(usize, Option<f64>),
+    UpdateArcCost(usize, f64),
+    Start,
+    NextStep,
+    SolveToEnd,
+}
+
+/// Min-cost flow input and a step-by-step network simplex run, laid out like
+/// the other solvers: start, then one pivot per click.
+pub struct NetworkPanel {
+    supplies: Vec<f64>,
+    arcs: Vec<Arc>,
+    problem: Option<NetworkSimplexProblem>,
+    iterations: Vec<NetworkIteration>,
+    status: Option<SolveStatus>,
+    errors: Vec<String>,
+}
+
+impl Component for NetworkPanel {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let arc = |from, to, capacity, cost| Arc {
+            from,
+            to,
+            capacity: Some(capacity),
+            cost,
+        };
+        Self {
+            supplies: vec![4.0, 0.0, 0.0, -4.0],
+            arcs: vec![
+                arc(0, 1, 4.0, 2.0),
+                arc(0, 2, 2.0, 2.0),
+                arc(1, 2, 2.0, 1.0),
+                arc(1, 3, 3.0, 3.0),
+                arc(2, 3, 5.0, 1.0),
+            ],
+            problem: None,
+            iterations: vec![],
+            status: None,
+            errors: vec![],
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetNodes(count) => {
+                let count = count.clamp(2, MAX_NODES);
+                self.supplies.resize(count, 0.0);
+                for arc in &mut self.arcs {
+                    arc.from = arc.from.min(count - 1);
+                    arc.to = arc.to.min(count - 1);
+                }
+            }
+            Msg::UpdateSupply(i, val) => {
+                if let Some(supply) = self.supplies.get_mut(i) {
+                    *supply = val;
+                }
+            }
+            Msg::AddArc => self.arcs.push(Arc {
+                from: 0,
+                to: 1,
+                capacity: None,
+                cost: 1.0,
+            }),
+            Msg::RemoveArc(k) => {
+                if k < self.arcs.len() {
+                    self.arcs.remove(k);
+                }
+            }
+            Msg::UpdateArcFrom(k, node) => {
+                if let Some(arc) = self.arcs.get_mut(k) {
+                    arc.from = node;
+                }
+            }
+            Msg::UpdateArcTo(k, node) => {
+                if let Some(arc) = self.arcs.get_mut(k) {
+                    arc.to = node;
+                }
+            }
+            Msg::UpdateArcCapacity(k, capacity) => {
+                if let Some(arc) = self.arcs.get_mut(k) {
+                    arc.capacity = capacity;
+                }
+            }
+            Msg::UpdateArcCost(k, cost) => {
+                if let Some(arc) = self.arcs.get_mut(k) {
+                    arc.cost = cost;
+                }
+            }
+            Msg::Start => {
+                let problem = self.network();
+                self.iterations.clear();
+                self.status = None;
+                match problem.validate() {
+                    Ok(()) => {
+                        let mut solver = NetworkSimplexProblem::new(&problem);
+                        solver.options = ctx.props().options.clone();
+                        self.problem = Some(solver);
+                        self.errors.clear();
+                    }
+                    Err(errors) => {
+                        self.problem = None;
+                        self.errors = errors;
+                    }
+                }
+                return true;
+            }
+            Msg::NextStep => {
+                self.step();
+                return true;
+            }
+            Msg::SolveToEnd => {
+                while self.problem.is_some() && self.status.is_none() {
+                    if self.iterations.len() >= ITERATION_LIMIT {
+                        self.status = Some(SolveStatus::IterationLimit);
+                        break;
+                    }
+                    self.step();
+                }
+                return true;
+            }
+        }
+        // Editing the network discards the current run.
+        self.problem = None;
+        self.iterations.clear();
+        self.status = None;
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let running = self.problem.is_some() && self.status.is_none();
+        html! {
+            <div class="input-form network-panel">
+                <div class="size-selectors">
+                    <div>
+                        <label>{"Nodes: "}
+                            <input
+                                type="number"
+                                min="2"
+                                max={MAX_NODES.to_string()}
+                                value={self.supplies.len().to_string()}
+                                oninput={link.callback(|e: InputEvent| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    Msg::SetNodes(input.value().parse().unwrap_or(2))
+                                })}
+                            />
+                        </label>
+                    </div>
+                </div>
+
+                <div class="network-supplies">
+                    <span>{ "Supply b (negative = demand):" }</span>
+                    {
+                        for self.supplies.iter().enumerate().map(|(i, supply)| html! {
+                            <label>
+                                { format!("{}: ", i + 1) }
+                                <input
+                                    type="number"
+                                    step="1"
+                                    value={supply.to_string()}
+                                    oninput={link.callback(move |e: InputEvent| {
+                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                        Msg::UpdateSupply(i, input.value().parse().unwrap_or(0.0))
+                                    })}
+                                />
+                            </label>
+                        })
+                    }
+                </div>
+
+                { self.render_arc_table(ctx) }
+
+                {
+                    if self.errors.is_empty() {
+                        html! {}
+                    } else {
+                        html! {
+                            <ul class="validation-errors">
+                                { for self.errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                            </ul>
+                        }
+                    }
+                }
+
+                <div class="network-controls">
+                    <button onclick={link.callback(|_| Msg::Start)}>{ "Start Network Simplex" }</button>
+                    <button disabled={!running} onclick={link.callback(|_| Msg::NextStep)}>{ "Next Pivot" }</button>
+                    <button disabled={!running} onclick={link.callback(|_| Msg::SolveToEnd)}>{ "Solve to End" }</button>
+                </div>
+
+                { self.render_run() }
+            </div>
+        }
+    }
+}
+
+impl NetworkPanel {
+    fn network(&self) -> NetworkProblem {
+        NetworkProblem {
+            supplies: self.supplies.clone(),
+            arcs: self.arcs.clone(),
+        }
+    }
+
+    fn step(&mut self) {
+        let Some(problem) = &mut self.problem else {
+            return;
+        };
+        match perform_network_simplex_iteration(problem) {
+            Ok(iteration) => self.iterations.push(iteration),
+            Err(NetworkError::Optimal) => {
+                self.status = Some(if problem.active_artificials().is_empty() {
+                    SolveStatus::Optimal
+                } else {
+                    SolveStatus::Infeasible
+                });
+            }
+            Err(NetworkError::Unbounded(_)) => self.status = Some(SolveStatus::Unbounded),
+        }
+    }
+
+    fn render_arc_table(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let nodes = self.supplies.len();
+        let node_input = |k: usize, value: usize, to: bool| {
+            html! {
+                <input
+                    type="number"
+                    min="1"
+                    max={nodes.to_string()}
+                    value={(value + 1).to_string()}
+                    oninput={link.callback(move |e: InputEvent| {
+                        let input: HtmlInputElement = e.target_unchecked_into();
+                        let node = input.value().parse::<usize>().unwrap_or(1).clamp(1, nodes) - 1;
+                        if to { Msg::UpdateArcTo(k, node) } else { Msg::UpdateArcFrom(k, node) }
+                    })}
+                />
+            }
+        };
+        html! {
+            <>
+            <table class="transportation-table network-arcs">
+                <thead>
+                    <tr>
+                        <th>{ "Arc" }</th>
+                        <th>{ "From" }</th>
+                        <th>{ "To" }</th>
+                        <th>{ "Capacity (blank = ∞)" }</th>
+                        <th>{ "Cost" }</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        for self.arcs.iter().enumerate().map(|(k, arc)| html! {
+                            <tr>
+                                <th>{ k + 1 }</th>
+                                <td>{ node_input(k, arc.from, false) }</td>
+                                <td>{ node_input(k, arc.to, true) }</td>
+                                <td>
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        step="1"
+                                        value={arc.capacity.map(|cap| cap.to_string()).unwrap_or_default()}
+                                        oninput={link.callback(move |e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateArcCapacity(k, input.value().parse().ok())
+                                        })}
+                                    />
+                                </td>
+                                <td>
+                                    <input
+                                        type="number"
+                                        step="0.1"
+                                        value={arc.cost.to_string()}
+                                        oninput={link.callback(move |e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateArcCost(k, input.value().parse().unwrap_or(0.0))
+                                        })}
+                                    />
+                                </td>
+                                <td>
+                                    <button onclick={link.callback(move |_| Msg::RemoveArc(k))}>{ "✕" }</button>
+                                </td>
+                            </tr>
+                        })
+                    }
+                </tbody>
+            </table>
+            <button onclick={link.callback(|_| Msg::AddArc)}>{ "Add Arc" }</button>
+            </>
+        }
+    }
+
+    fn render_run(&self) -> Html {
+        let Some(problem) = &self.problem else {
+            return html! {};
+        };
+        let user_arcs = problem.user_arcs;
+        let arc_name = |k: usize| {
+            if k < user_arcs {
+                let arc = &problem.arcs[k];
+                format!("{}→{}", arc.from + 1, arc.to + 1)
+            } else {
+                let node = k - user_arcs + 1;
+                format!("artificial at {}", node)
+            }
+        };
+        let highlighted = self
+            .iterations
+            .last()
+            .map(|it| it.cycle.clone())
+            .unwrap_or_default();
+
+        html! {
+            <div class="network-run">
+                <div class="barrier-summary">
+                    <span>{ format!("{} pivots", self.iterations.len()) }</span>
+                    <span>{ format!("Cost = {:.4}", problem.objective()) }</span>
+                    <span class={if self.status == Some(SolveStatus::Optimal) { "report-status optimal" } else { "report-status" }}>
+                        { self.status.as_ref().map_or("running…", |status| status.label()) }
+                    </span>
+                </div>
+                {
+                    if self.status == Some(SolveStatus::Infeasible) {
+                        html! {
+                            <p class="report-warning">
+                                { "Artificial arcs still carry flow: the demands cannot be met within the capacities." }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <NetworkGraph
+                    supplies={self.supplies.clone()}
+                    arcs={problem.arcs[..user_arcs].to_vec()}
+                    flows={problem.flows[..user_arcs].to_vec()}
+                    highlighted={highlighted}
+                />
+                <div class="network-iterations">
+                    {
+                        for self.iterations.iter().enumerate().map(|(i, it)| html! {
+                            <div class="interior-point-view network-iteration">
+                                <h4>{ format!("Pivot {}", i + 1) }</h4>
+                                <div class="barrier-summary">
+                                    <span>{ format!("enters: {}", arc_name(it.entering)) }</span>
+                                    <span>{ format!("leaves: {}", arc_name(it.leaving)) }</span>
+                                    <span>{ format!("θ = {:.4}", it.theta) }</span>
+                                    <span>{ format!("cost = {:.4}", it.objective) }</span>
+                                </div>
+                                <p class="network-cycle">
+                                    { format!(
+                                        "Cycle: {}",
+                                        it.cycle.iter().map(|&k| arc_name(k)).collect::<Vec<_>>().join(", ")
+                                    ) }
+                                </p>
+                                <p class="network-cycle">
+                                    { format!(
+                                        "Potentials π: {}",
+                                        it.potentials[..problem.nodes]
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(v, p)| format!("{}: {:.2}", v + 1, p))
+                                            .collect::<Vec<_>>()
+                                            .join(", ")
+                                    ) }
+                                </p>
+                            </div>
+                        })
+                    }
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use crate::notation::{IterateStyle, Notation};
+
+const ITERATE_STYLES: [IterateStyle; 3] = [
+    IterateStyle::Plain,
+    IterateStyle::Superscript,
+    IterateStyle::Subscript,
+];
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub notation: Notation,
+    pub on_change: Callback<Notation>,
+}
+
+/// Settings for the symbols used in headers, so instructors can match their
+/// textbook. Stateless: every edit is sent up as a whole new `Notation`.
+pub struct NotationPanel;
+
+impl Component for NotationPanel {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let notation = &props.notation;
+        let symbol_input = |label: &str, value: &str, set: fn(&mut Notation, String)| {
+            let current = notation.clone();
+            let on_change = props.on_change.clone();
+            html! {
+                <label>{ label }
+                    <input
+                        type="text"
+                        size="4"
+                        value={value.to_string()}
+                        oninput={Callback::from(move |e: InputEvent| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let mut next = current.clone();
+                            set(&mut next, input.value());
+                            on_change.emit(next);
+                        })}
+                    />
+                </label>
+            }
+        };
+        let current = notation.clone();
+        let on_change = props.on_change.clone();
+        let on_iterate = Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            let k = select.value().parse::<usize>().unwrap_or(0);
+            on_change.emit(Notation {
+                iterate: ITERATE_STYLES[k.min(ITERATE_STYLES.len() - 1)],
+                ..current.clone()
+            });
+        });
+        let on_reset = {
+            let on_change = props.on_change.clone();
+            Callback::from(move |_| on_change.emit(Notation::default()))
+        };
+
+        html! {
+            <details class="notation-panel">
+                <summary>{ "Notation" }</summary>
+                <div class="notation-fields">
+                    { symbol_input("Duals ", &notation.dual, |n, v| n.dual = v) }
+                    { symbol_input("Step size ", &notation.step, |n, v| n.step = v) }
+                    { symbol_input("Barrier ", &notation.barrier, |n, v| n.barrier = v) }
+                    <label>{ "Iterates " }
+                        <select onchange={on_iterate}>
+                            {
+                                for ITERATE_STYLES.iter().enumerate().map(|(k, style)| html! {
+                                    <option value={k.to_string()} selected={*style == notation.iterate}>
+                                        { style.label() }
+                                    </option>
+                                })
+                            }
+                        </select>
+                    </label>
+                    <button onclick={on_reset}>{ "Reset" }</button>
+                </div>
+                <p class="race-hint">
+                    { format!(
+                        "Headers will read e.g. \"{} = B^{{-T}} c_B\", \"step size ({})\", \"{} = {:.1}\" and \"Current {}\".",
+                        notation.dual(),
+                        notation.step(),
+                        notation.barrier(),
+                        0.5,
+                        notation.iterate(1)
+                    ) }
+                </p>
+            </details>
+        }
+    }
+}
@@ -0,0 +1,340 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, ProblemContext, SettingsContext};
+use crate::model::ValidatedProblem;
+use crate::parametric::{analyze, Parameter, ParametricAnalysis};
+
+const WIDTH: f64 = 480.0;
+const HEIGHT: f64 = 260.0;
+const PAD: f64 = 48.0;
+
+/// Grid points a run solves; each one is a full solve of the model.
+const DEFAULT_STEPS: usize = 21;
+const MAX_STEPS: usize = 101;
+
+pub enum Msg {
+    ProblemChanged(ProblemContext),
+    SettingsChanged(SettingsContext),
+    SetParameter(Parameter),
+    SetFrom(f64),
+    SetTo(f64),
+    SetSteps(usize),
+    Run,
+}
+
+/// Varies one objective coefficient or right-hand side over a range and plots
+/// the optimal value against it. The analysis is disabled until a model has
+/// been submitted.
+pub struct ParametricPanel {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+    ),
+    parameter: Parameter,
+    from: f64,
+    to: f64,
+    steps: usize,
+    analysis: Option<ParametricAnalysis>,
+}
+
+impl Component for ParametricPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::ProblemChanged);
+        let (settings, settings_handle) = subscribe(ctx, Msg::SettingsChanged);
+        let mut panel = Self {
+            problem,
+            settings,
+            _handles: (problem_handle, settings_handle),
+            parameter: Parameter::Objective(0),
+            from: 0.0,
+            to: 1.0,
+            steps: DEFAULT_STEPS,
+            analysis: None,
+        };
+        panel.center_range();
+        panel
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ProblemChanged(problem) => {
+                let new_model = problem.model != self.problem.model;
+                self.problem = problem;
+                if new_model {
+                    self.parameter = Parameter::Objective(0);
+                    self.center_range();
+                    self.analysis = None;
+                }
+            }
+            Msg::SettingsChanged(settings) => self.settings = settings,
+            Msg::SetParameter(parameter) => {
+                self.parameter = parameter;
+                self.center_range();
+                self.analysis = None;
+            }
+            Msg::SetFrom(from) => self.from = from,
+            Msg::SetTo(to) => self.to = to,
+            Msg::SetSteps(steps) => self.steps = steps.clamp(2, MAX_STEPS),
+            Msg::Run => {
+                let Some(model) = &self.problem.model else {
+                    return false;
+                };
+                self.analysis = Some(analyze(
+                    model,
+                    self.parameter,
+                    self.from,
+                    self.to,
+                    self.steps,
+                    &self.settings.options,
+                ));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let Some(model) = &self.problem.model else {
+            return html! {
+                <div class="race-panel parametric-panel">
+                    <h3>{ "Parametric Analysis" }</h3>
+                    <p class="race-hint">{ "Solve a problem first; the analysis re-solves the submitted model." }</p>
+                </div>
+            };
+        };
+        let columns = model.augmentation.original_variables.min(model.c.len());
+        let rows = model.constraint_labels.len();
+        let parameters: Vec<Parameter> = (0..columns)
+            .map(Parameter::Objective)
+            .chain((0..rows).map(Parameter::Rhs))
+            .collect();
+        let options = parameters.clone();
+
+        html! {
+            <div class="race-panel parametric-panel">
+                <h3>{ "Parametric Analysis" }</h3>
+                <div class="race-controls">
+                    <select onchange={link.callback(move |e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let k = select.value().parse::<usize>().unwrap_or(0);
+                        Msg::SetParameter(options.get(k).copied().unwrap_or(Parameter::Objective(0)))
+                    })}>
+                        {
+                            for parameters.iter().enumerate().map(|(k, parameter)| html! {
+                                <option value={k.to_string()} selected={*parameter == self.parameter}>
+                                    { parameter.label(model) }
+                                </option>
+                            })
+                        }
+                    </select>
+                    <label>{ "from " }
+                        <input
+                            type="number"
+                            step="0.1"
+                            value={self.from.to_string()}
+                            oninput={link.callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetFrom(input.value().parse().unwrap_or(0.0))
+                            })}
+                        />
+                    </label>
+                    <label>{ "to " }
+                        <input
+                            type="number"
+                            step="0.1"
+                            value={self.to.to_string()}
+                            oninput={link.callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetTo(input.value().parse().unwrap_or(0.0))
+                            })}
+                        />
+                    </label>
+                    <label>{ "points " }
+                        <input
+                            type="number"
+                            min="2"
+                            max={MAX_STEPS.to_string()}
+                            value={self.steps.to_string()}
+                            oninput={link.callback(|e: InputEvent| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetSteps(input.value().parse().unwrap_or(DEFAULT_STEPS))
+                            })}
+                        />
+                    </label>
+                    <button disabled={self.from >= self.to} onclick={link.callback(|_| Msg::Run)}>
+                        { "Run Analysis" }
+                    </button>
+                </div>
+                {
+                    if let Some(analysis) = &self.analysis {
+                        self.render_analysis(model, analysis)
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl ParametricPanel {
+    /// Centers the range on the parameter's current value, one unit or 100%
+    /// to either side, whichever is wider.
+    fn center_range(&mut self) {
+        let Some(model) = &self.problem.model else {
+            return;
+        };
+        let current = self.parameter.current(model);
+        let spread = current.abs().max(1.0);
+        self.from = current - spread;
+        self.to = current + spread;
+    }
+
+    fn render_analysis(&self, model: &ValidatedProblem, analysis: &ParametricAnalysis) -> Html {
+        let solved: Vec<(f64, f64)> = analysis
+            .points
+            .iter()
+            .filter_map(|point| point.objective.map(|z| (point.value, z)))
+            .collect();
+        let label = analysis.parameter.label(model);
+        let sense = if model.sense.is_maximize() {
+            "max"
+        } else {
+            "min"
+        };
+        let unsolved = analysis.points.len() - solved.len();
+
+        html! {
+            <div class="log-chart parametric-chart">
+                <h4>{ format!("Optimal value {} Z against {}", sense, label) }</h4>
+                { Self::render_plot(analysis, &solved) }
+                {
+                    if unsolved > 0 {
+                        html! {
+                            <p class="race-hint">
+                                { format!("{} of {} points have no optimum (infeasible, unbounded or not converged); they are marked on the axis.", unsolved, analysis.points.len()) }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if analysis.breakpoints.is_empty() {
+                        html! { <p class="race-hint">{ "No breakpoints: the optimal value is linear over this range." }</p> }
+                    } else {
+                        html! {
+                            <p class="parametric-breakpoints">
+                                { format!(
+                                    "Breakpoints at {} = {}",
+                                    label,
+                                    analysis
+                                        .breakpoints
+                                        .iter()
+                                        .map(|t| format!("{:.4}", t))
+                                        .collect::<Vec<_>>()
+                                        .join(", ")
+                                ) }
+                            </p>
+                        }
+                    }
+                }
+            </div>
+        }
+    }
+
+    fn render_plot(analysis: &ParametricAnalysis, solved: &[(f64, f64)]) -> Html {
+        let (Some(first), Some(last)) = (analysis.points.first(), analysis.points.last()) else {
+            return html! {};
+        };
+        let (left, right) = (first.value, last.value);
+        let (mut bottom, mut top) = solved
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &(_, z)| {
+                (lo.min(z), hi.max(z))
+            });
+        if solved.is_empty() {
+            (bottom, top) = (0.0, 1.0);
+        }
+        if top - bottom < 1e-9 {
+            bottom -= 1.0;
+            top += 1.0;
+        }
+        let to_svg = |t: f64, z: f64| {
+            (
+                PAD + (t - left) / (right - left).max(1e-12) * (WIDTH - 2.0 * PAD),
+                PAD + (top - z) / (top - bottom) * (HEIGHT - 2.0 * PAD),
+            )
+        };
+        let polyline = solved
+            .iter()
+            .map(|&(t, z)| {
+                let (x, y) = to_svg(t, z);
+                format!("{:.2},{:.2}", x, y)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        html! {
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} width={WIDTH.to_string()} height={HEIGHT.to_string()}>
+                {
+                    for [bottom, top].iter().map(|&z| {
+                        let (_, y) = to_svg(left, z);
+                        html! {
+                            <g class="gridline">
+                                <line x1={PAD.to_string()} y1={y.to_string()} x2={(WIDTH - PAD).to_string()} y2={y.to_string()} />
+                                <text x={(PAD - 6.0).to_string()} y={(y + 4.0).to_string()} text-anchor="end">
+                                    { format!("{:.4}", z) }
+                                </text>
+                            </g>
+                        }
+                    })
+                }
+                {
+                    for analysis.breakpoints.iter().map(|&t| {
+                        let (x, _) = to_svg(t, bottom);
+                        html! {
+                            <g class="parametric-breakpoint">
+                                <line x1={x.to_string()} y1={PAD.to_string()} x2={x.to_string()} y2={(HEIGHT - PAD).to_string()} />
+                                <text x={x.to_string()} y={(PAD - 8.0).to_string()} text-anchor="middle">
+                                    { format!("{:.3}", t) }
+                                </text>
+                            </g>
+                        }
+                    })
+                }
+                <polyline class="log-line" points={polyline} />
+                {
+                    for analysis.points.iter().map(|point| match point.objective {
+                        Some(z) => {
+                            let (x, y) = to_svg(point.value, z);
+                            html! {
+                                <circle class="log-point" cx={x.to_string()} cy={y.to_string()} r="3">
+                                    <title>{ format!("{:.4} → Z = {:.4}", point.value, z) }</title>
+                                </circle>
+                            }
+                        }
+                        None => {
+                            let (x, _) = to_svg(point.value, bottom);
+                            html! {
+                                <circle class="parametric-unsolved" cx={x.to_string()} cy={(HEIGHT - PAD).to_string()} r="3">
+                                    <title>{ format!("{:.4}: {}", point.value, point.status.label()) }</title>
+                                </circle>
+                            }
+                        }
+                    })
+                }
+                <text class="axis-label" x={(WIDTH / 2.0).to_string()} y={(HEIGHT - 12.0).to_string()} text-anchor="middle">
+                    { format!("{:.4} … {:.4}", left, right) }
+                </text>
+            </svg>
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use nalgebra::{DMatrix, DVector};
+use yew::prelude::*;
+
+use crate::plot::{clip_line, path_bounds};
+
+const SIZE: f64 = 360.0;
+const PAD: f64 = 30.0;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Constraint rows over the two original variables.
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub constraint_labels: Vec<String>,
+    /// (x1, x2) of every iterate, oldest first.
+    pub path: Vec<(f64, f64)>,
+    /// Constraints active at the last iterate.
+    #[prop_or_default]
+    pub active_rows: Vec<usize>,
+}
+
+/// Constraint lines and the iterate path in the (x1, x2) plane, for models
+/// with two decision variables.
+pub struct PathPlot;
+
+impl Component for PathPlot {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let (x_max, y_max) = path_bounds(&props.a, &props.b, &props.path);
+        let to_svg = |x: f64, y: f64| {
+            (
+                PAD + x / x_max * (SIZE - 2.0 * PAD),
+                SIZE - PAD - y / y_max * (SIZE - 2.0 * PAD),
+            )
+        };
+
+        let lines = (0..props.a.nrows()).filter_map(|i| {
+            let (p, q) = clip_line(
+                props.a[(i, 0)],
+                props.a[(i, 1)],
+                props.b[i],
+                x_max,
+                y_max,
+            )?;
+            let (x1, y1) = to_svg(p.0, p.1);
+            let (x2, y2) = to_svg(q.0, q.1);
+            let label = props
+                .constraint_labels
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("C{}", i + 1));
+            let class = classes!(
+                "constraint-line",
+                props.active_rows.contains(&i).then_some("active")
+            );
+            Some(html! {
+                <g class={class}>
+                    <line x1={x1.to_string()} y1={y1.to_string()} x2={x2.to_string()} y2={y2.to_string()} />
+                    <text x={(x1 + 4.0).to_string()} y={(y1 - 4.0).to_string()}>{ label }</text>
+                </g>
+            })
+        });
+
+        let points: Vec<(f64, f64)> = props.path.iter().map(|&(x, y)| to_svg(x, y)).collect();
+        let polyline = points
+            .iter()
+            .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let last = points.len().saturating_sub(1);
+        let (origin_x, origin_y) = to_svg(0.0, 0.0);
+
+        html! {
+            <div class="path-plot">
+                <h4>{ "Iterate path in (x1, x2)" }</h4>
+                <svg viewBox={format!("0 0 {} {}", SIZE, SIZE)} width={SIZE.to_string()} height={SIZE.to_string()}>
+                    <line class="axis" x1={origin_x.to_string()} y1={origin_y.to_string()} x2={(SIZE - PAD).to_string()} y2={origin_y.to_string()} />
+                    <line class="axis" x1={origin_x.to_string()} y1={origin_y.to_string()} x2={origin_x.to_string()} y2={PAD.to_string()} />
+                    <text class="axis-label" x={(SIZE - PAD).to_string()} y={(origin_y + 16.0).to_string()}>{ format!("x1 = {:.2}", x_max) }</text>
+                    <text class="axis-label" x={(origin_x + 4.0).to_string()} y={(PAD - 8.0).to_string()}>{ format!("x2 = {:.2}", y_max) }</text>
+                    { for lines }
+                    <polyline class="iterate-path" points={polyline} />
+                    {
+                        for points.iter().enumerate().map(|(k, (x, y))| html! {
+                            <circle
+                                class={classes!("iterate", (k == last).then_some("current"))}
+                                cx={x.to_string()}
+                                cy={y.to_string()}
+                                r={if k == last { "5" } else { "3" }}
+                            />
+                        })
+                    }
+                </svg>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, HistoryContext};
+use crate::profile::{self, RenderStats};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub render: RenderStats,
+    pub on_close: Callback<()>,
+}
+
+pub enum Msg {
+    HistoryChanged(HistoryContext),
+}
+
+/// Developer overlay with render and solver timings, DOM size and memory,
+/// toggled with Alt+Shift+P. DOM size and memory are read when the overlay
+/// repaints, i.e. after the update before the current one. Solver times come
+/// from the history context.
+pub struct ProfileOverlay {
+    history: HistoryContext,
+    _history_handle: ContextHandle<HistoryContext>,
+}
+
+impl Component for ProfileOverlay {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (history, history_handle) = subscribe(ctx, Msg::HistoryChanged);
+        Self {
+            history,
+            _history_handle: history_handle,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::HistoryChanged(history) => {
+                self.history = history;
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let solver = &self.history.timings;
+        let steps = &solver.per_iteration_ms;
+        let memory = profile::memory_estimate();
+        let solver_mean = (!steps.is_empty()).then(|| solver.total_ms / steps.len() as f64);
+
+        html! {
+            <aside class="profile-overlay" aria-label="Performance profile">
+                <header>
+                    <strong>{ "Profile" }</strong>
+                    <button onclick={props.on_close.reform(|_| ())} title="Close (Alt+Shift+P)">{ "×" }</button>
+                </header>
+                <table>
+                    <tbody>
+                        { Self::row("Render, last", millis(props.render.last_ms())) }
+                        { Self::row("Render, mean", millis(props.render.mean_ms())) }
+                        { Self::row("Render, worst", millis(props.render.max_ms())) }
+                        { Self::row("Updates", props.render.updates.to_string()) }
+                        { Self::row("Solver, last step", millis(steps.last().copied())) }
+                        { Self::row("Solver, mean step", millis(solver_mean)) }
+                        { Self::row("Solver, total", format!("{:.1} ms in {} steps", solver.total_ms, steps.len())) }
+                        { Self::row("DOM elements", profile::dom_node_count().map_or("n/a".to_string(), |count| count.to_string())) }
+                        { Self::row("Wasm memory", bytes(memory.wasm_bytes)) }
+                        { Self::row("JS heap", bytes(memory.js_heap_bytes)) }
+                    </tbody>
+                </table>
+            </aside>
+        }
+    }
+}
+
+impl ProfileOverlay {
+    fn row(label: &str, value: String) -> Html {
+        html! {
+            <tr>
+                <th>{ label }</th>
+                <td>{ value }</td>
+            </tr>
+        }
+    }
+}
+
+fn millis(ms: Option<f64>) -> String {
+    ms.map_or("n/a".to_string(), |ms| format!("{:.2} ms", ms))
+}
+
+fn bytes(bytes: Option<f64>) -> String {
+    bytes.map_or("n/a".to_string(), |bytes| {
+        format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+    })
+}
@@ -0,0 +1,78 @@
+use crate::notation::Notation;
+use crate::qp::QpIteration;
+use nalgebra::DMatrix;
+use yew::prelude::*;
+
+use super::interior_view::InteriorPointView;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub iteration: usize,
+
+    /// Q as entered, shown next to every iteration.
+    pub q_matrix: DMatrix<f64>,
+
+    #[prop_or_default]
+    pub iteration_data: Option<QpIteration>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+pub struct QpView;
+
+impl Component for QpView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let it = props.iteration_data.as_ref();
+
+        let hessian = it.map(|iter| &iter.hessian);
+        let gradient = it.map(|iter| &iter.gradient);
+        let newton_direction = it.map(|iter| &iter.newton_direction);
+        let current_x = it.map(|iter| &iter.current_x);
+        let mu = props.notation.barrier();
+        let compact = props.compact;
+
+        html! {
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Iteration {}", props.iteration) }</h3>
+
+                {
+                    if let Some(iter) = it {
+                        html! {
+                            <div class="barrier-summary">
+                                <span>{ format!("{} = {:.6}", mu, iter.mu) }</span>
+                                <span>{ format!("c^T x - ½ x^T Q x = {:.4}", iter.objective) }</span>
+                                <span>{ format!("step t = {:.4}", iter.step_length) }</span>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <div class="matrix-container">
+                    { InteriorPointView::matrix_card("Q", Some(&props.q_matrix), InteriorPointView::render_matrix(Some(&props.q_matrix)), compact) }
+                    { InteriorPointView::matrix_card(&format!("Q + {} X^{{-2}}", mu), hessian, InteriorPointView::render_matrix(hessian), compact) }
+                    { InteriorPointView::vector_card(&format!("g = c - Q x + {} X^{{-1}} 1", mu), gradient, compact) }
+                    { InteriorPointView::vector_card("Newton direction Δx", newton_direction, compact) }
+                    { InteriorPointView::vector_card(&format!("Current {}", props.notation.iterate(props.iteration)), current_x, compact) }
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use gloo_timers::callback::Interval;
+use web_sys::HtmlSelectElement;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, ProblemContext, SettingsContext};
+use crate::model::SolverMethod;
+use crate::race::{Race, RaceLane, RaceResult};
+use crate::report::SolveStatus;
+
+/// Milliseconds between race ticks; slow enough to watch the counters move.
+const TICK_MS: u32 = 60;
+
+const TRACE_WIDTH: f64 = 260.0;
+const TRACE_HEIGHT: f64 = 80.0;
+
+pub enum Msg {
+    ProblemChanged(ProblemContext),
+    SettingsChanged(SettingsContext),
+    SetMethod(usize, SolverMethod),
+    Start,
+    Tick,
+    Stop,
+    ClearHistory,
+}
+
+/// Runs two methods on the current model side by side and keeps the results
+/// of earlier races for comparison. The race is disabled until a model has
+/// been submitted.
+pub struct RacePanel {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+    ),
+    methods: [SolverMethod; 2],
+    race: Option<Race>,
+    interval: Option<Interval>,
+    history: Vec<RaceResult>,
+    error: Option<String>,
+}
+
+impl Component for RacePanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::ProblemChanged);
+        let (settings, settings_handle) = subscribe(ctx, Msg::SettingsChanged);
+        Self {
+            problem,
+            settings,
+            _handles: (problem_handle, settings_handle),
+            methods: [SolverMethod::AffineScaling, SolverMethod::Simplex],
+            race: None,
+            interval: None,
+            history: vec![],
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ProblemChanged(problem) => {
+                self.problem = problem;
+                true
+            }
+            Msg::SettingsChanged(settings) => {
+                self.settings = settings;
+                true
+            }
+            Msg::SetMethod(lane, method) => {
+                self.methods[lane] = method;
+                true
+            }
+            Msg::Start => {
+                let Some(model) = &self.problem.model else {
+                    return false;
+                };
+                self.interval = None;
+                match Race::new(self.methods, model, &self.settings.options) {
+                    Ok(race) => {
+                        self.race = Some(race);
+                        self.error = None;
+                        let link = ctx.link().clone();
+                        self.interval =
+                            Some(Interval::new(TICK_MS, move || link.send_message(Msg::Tick)));
+                    }
+                    Err(e) => {
+                        self.race = None;
+                        self.error = Some(e);
+                    }
+                }
+                true
+            }
+            Msg::Tick => {
+                let Some(race) = &mut self.race else {
+                    self.interval = None;
+                    return false;
+                };
+                race.tick();
+                if race.is_finished() {
+                    self.interval = None;
+                    self.history.push(race.result());
+                }
+                true
+            }
+            Msg::Stop => {
+                if self.interval.take().is_some() {
+                    if let Some(race) = &mut self.race {
+                        for lane in &mut race.lanes {
+                            lane.status.get_or_insert(SolveStatus::Cancelled);
+                        }
+                        self.history.push(race.result());
+                    }
+                }
+                true
+            }
+            Msg::ClearHistory => {
+                self.history.clear();
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let has_model = self.problem.model.is_some();
+        let running = self.interval.is_some();
+
+        html! {
+            <div class="race-panel">
+                <h3>{ "Algorithm Race" }</h3>
+                <div class="race-controls">
+                    { for (0..2).map(|lane| self.method_select(ctx, lane)) }
+                    <button disabled={!has_model || running} onclick={link.callback(|_| Msg::Start)}>
+                        { "Start Race" }
+                    </button>
+                    <button disabled={!running} onclick={link.callback(|_| Msg::Stop)}>
+                        { "Stop" }
+                    </button>
+                </div>
+                {
+                    if !has_model {
+                        html! { <p class="race-hint">{ "Solve a problem first; the race reuses the submitted model." }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if let Some(error) = &self.error {
+                        html! { <p class="report-warning">{ error }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if let Some(race) = &self.race {
+                        html! {
+                            <div class="race-lanes">
+                                { for race.lanes.iter().map(Self::render_lane) }
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                { self.render_history(ctx) }
+            </div>
+        }
+    }
+}
+
+impl RacePanel {
+    fn method_select(&self, ctx: &Context<Self>, lane: usize) -> Html {
+        let current = self.methods[lane];
+        html! {
+            <label>{ format!("Lane {}: ", lane + 1) }
+                <select onchange={ctx.link().callback(move |e: Event| {
+                    let select: HtmlSelectElement = e.target_unchecked_into();
+                    let index: usize = select.value().parse().unwrap_or(0);
+                    Msg::SetMethod(lane, SolverMethod::ALL[index.min(SolverMethod::ALL.len() - 1)])
+                })}>
+                    {
+                        for SolverMethod::ALL.iter().enumerate().map(|(i, method)| html! {
+                            <option value={i.to_string()} selected={*method == current}>
+                                { method.label() }
+                            </option>
+                        })
+                    }
+                </select>
+            </label>
+        }
+    }
+
+    fn render_lane(lane: &RaceLane) -> Html {
+        let status = match &lane.status {
+            Some(status) => status.label().to_string(),
+            None => "running…".to_string(),
+        };
+        html! {
+            <div class="race-lane">
+                <h4>{ lane.method.label() }</h4>
+                <div class="barrier-summary">
+                    <span>{ format!("{} iterations", lane.iterations) }</span>
+                    <span>{ format!("{:.2} ms", lane.elapsed_ms) }</span>
+                    <span>{ status }</span>
+                </div>
+                { Self::render_trace(&lane.trace) }
+                <p class="race-objective">
+                    { format!("Z = {:.4}", lane.trace.last().copied().unwrap_or(0.0)) }
+                </p>
+            </div>
+        }
+    }
+
+    /// Objective after each iteration as a sparkline.
+    fn render_trace(trace: &[f64]) -> Html {
+        if trace.len() < 2 {
+            return html! {};
+        }
+        let finite: Vec<f64> = trace.iter().copied().filter(|v| v.is_finite()).collect();
+        let low = finite.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = finite.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = if high > low { high - low } else { 1.0 };
+        let last = (trace.len() - 1) as f64;
+        let points = trace
+            .iter()
+            .enumerate()
+            .filter(|(_, v)| v.is_finite())
+            .map(|(k, v)| {
+                format!(
+                    "{:.2},{:.2}",
+                    k as f64 / last * TRACE_WIDTH,
+                    TRACE_HEIGHT - (v - low) / range * TRACE_HEIGHT
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        html! {
+            <svg class="race-trace" viewBox={format!("0 -4 {} {}", TRACE_WIDTH, TRACE_HEIGHT + 8.0)}>
+                <polyline points={points} />
+            </svg>
+        }
+    }
+
+    fn render_history(&self, ctx: &Context<Self>) -> Html {
+        if self.history.is_empty() {
+            return html! {};
+        }
+        let winner_name = |result: &RaceResult, winner: Option<usize>| {
+            winner
+                .map(|i| result.entries[i].method.label().to_string())
+                .unwrap_or_else(|| "—".to_string())
+        };
+        html! {
+            <div class="race-history">
+                <h4>{ "Saved races" }</h4>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "#" }</th>
+                            <th>{ "Lanes (iterations / ms / Z)" }</th>
+                            <th>{ "Fewest iterations" }</th>
+                            <th>{ "Fastest" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        for self.history.iter().enumerate().map(|(k, result)| html! {
+                            <tr>
+                                <td>{ k + 1 }</td>
+                                <td>
+                                {
+                                    for result.entries.iter().map(|entry| html! {
+                                        <div>
+                                            { format!(
+                                                "{}: {} / {:.2} / {:.4} ({})",
+                                                entry.method.label(),
+                                                entry.iterations,
+                                                entry.elapsed_ms,
+                                                entry.objective,
+                                                entry.status.label()
+                                            ) }
+                                        </div>
+                                    })
+                                }
+                                </td>
+                                <td>{ winner_name(result, result.winner_by_iterations) }</td>
+                                <td>{ winner_name(result, result.winner_by_time) }</td>
+                            </tr>
+                        })
+                    }
+                    </tbody>
+                </table>
+                <button onclick={ctx.link().callback(|_| Msg::ClearHistory)}>{ "Clear" }</button>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+use super::input_form::FormState;
+use crate::equilibration::Scaling;
+use crate::interior::StepStrategy;
+use crate::model::{SolverMethod, StartOptions, ValidatedProblem};
+use crate::notation::Notation;
+use crate::regression::{parse_points, RegressionKind, RegressionProblem};
+
+/// Points fitted by y = 1 + 2 x except the last, an outlier that pulls the
+/// Chebyshev fit far more than the L1 one.
+const SAMPLE_POINTS: &str = "x, y\n0, 1.1\n1, 2.9\n2, 5.2\n3, 6.8\n4, 9.1\n5, 16\n";
+
+/// Methods the form offers. Each coefficient is free and split as b+ - b-,
+/// so b+ and b- can grow together at no cost; the log barrier rewards that
+/// growth and runs off along the ray, while the vertex methods and affine
+/// scaling follow the objective only.
+const METHODS: [(SolverMethod, &str, &str); 3] = [
+    (SolverMethod::Simplex, "simplex", "Primal Simplex (tableau)"),
+    (
+        SolverMethod::RevisedSimplex,
+        "revised",
+        "Revised Simplex (LU)",
+    ),
+    (SolverMethod::AffineScaling, "affine", "Affine Scaling"),
+];
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub on_submit: Callback<ValidatedProblem>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+}
+
+/// Pasted data points fitted by L1 or Chebyshev regression. The data is
+/// turned into its LP on every edit and the derived A, b and c are shown
+/// before solving.
+pub struct RegressionForm {
+    text: String,
+    kind: RegressionKind,
+    intercept: bool,
+    solver_method: SolverMethod,
+    alpha: f64,
+    state: FormState,
+}
+
+pub enum Msg {
+    SetText(String),
+    SetKind(RegressionKind),
+    SetIntercept(bool),
+    SetSolverMethod(SolverMethod),
+    UpdateAlpha(f64),
+    Submit,
+}
+
+impl Component for RegressionForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let mut form = Self {
+            text: SAMPLE_POINTS.to_string(),
+            kind: RegressionKind::default(),
+            intercept: true,
+            solver_method: SolverMethod::Simplex,
+            alpha: 0.5,
+            state: FormState::Editing(vec![]),
+        };
+        form.revalidate();
+        form
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let is_edit = !matches!(msg, Msg::Submit);
+        let changed = match msg {
+            Msg::SetText(text) => {
+                self.text = text;
+                true
+            }
+            Msg::SetKind(kind) => {
+                self.kind = kind;
+                true
+            }
+            Msg::SetIntercept(intercept) => {
+                self.intercept = intercept;
+                true
+            }
+            Msg::SetSolverMethod(method) => {
+                self.solver_method = method;
+                true
+            }
+            Msg::UpdateAlpha(a) => {
+                self.alpha = a.clamp(0.0, 1.0);
+                true
+            }
+            Msg::Submit => match &self.state {
+                FormState::Validated(problem) | FormState::Submitted(problem) => {
+                    let problem = problem.clone();
+                    ctx.props().on_submit.emit(problem.clone());
+                    self.state = FormState::Submitted(problem);
+                    true
+                }
+                FormState::Editing(_) => false,
+            },
+        };
+        if changed && is_edit {
+            self.revalidate();
+        }
+        changed
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="input-form regression-form">
+                <label class="regression-data">
+                    { "Data points, one per line: x values, then y" }
+                    <textarea
+                        rows="8"
+                        value={self.text.clone()}
+                        oninput={link.callback(|e: InputEvent| {
+                            let input: HtmlTextAreaElement = e.target_unchecked_into();
+                            Msg::SetText(input.value())
+                        })}
+                    />
+                </label>
+
+                <div class="solver-method">
+                    <label>{"Fit: "}
+                        <select
+                            onchange={link.callback(|e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                Msg::SetKind(RegressionKind::from_key(&select.value()).unwrap_or_default())
+                            })}>
+                            {
+                                for RegressionKind::ALL.into_iter().map(|kind| html! {
+                                    <option value={kind.key()} selected={self.kind == kind}>{ kind.label() }</option>
+                                })
+                            }
+                        </select>
+                    </label>
+                    <label>
+                        <input
+                            type="checkbox"
+                            checked={self.intercept}
+                            onchange={link.callback(|e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                Msg::SetIntercept(input.checked())
+                            })}
+                        />
+                        { " Intercept b0" }
+                    </label>
+                    <label>{"Method: "}
+                        <select
+                            onchange={link.callback(|e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                let method = METHODS
+                                    .into_iter()
+                                    .find(|(_, key, _)| *key == select.value())
+                                    .map_or(SolverMethod::Simplex, |(method, _, _)| method);
+                                Msg::SetSolverMethod(method)
+                            })}>
+                            {
+                                for METHODS.into_iter().map(|(method, key, label)| html! {
+                                    <option value={key} selected={self.solver_method == method}>{ label }</option>
+                                })
+                            }
+                        </select>
+                    </label>
+                </div>
+
+                {
+                    if self.solver_method.is_interior() {
+                        html! {
+                            <div class="alpha-selector">
+                                <label>{ format!("Step Size ({}): ", ctx.props().notation.step()) }
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        max="1"
+                                        step="0.1"
+                                        value={self.alpha.to_string()}
+                                        oninput={link.callback(|e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateAlpha(input.value().parse().unwrap_or(0.5))
+                                        })}
+                                    />
+                                </label>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    match &self.state {
+                        FormState::Editing(errors) => html! {
+                            <ul class="validation-errors">
+                                { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                            </ul>
+                        },
+                        FormState::Validated(problem) | FormState::Submitted(problem) => {
+                            Self::render_derived(problem)
+                        }
+                    }
+                }
+
+                <button
+                    disabled={matches!(self.state, FormState::Editing(_))}
+                    onclick={link.callback(|_| Msg::Submit)}
+                >
+                    { if matches!(self.state, FormState::Submitted(_)) { "Solve Again" } else { "Solve" } }
+                </button>
+            </div>
+        }
+    }
+}
+
+impl RegressionForm {
+    fn revalidate(&mut self) {
+        self.state = match self.validate() {
+            Ok(problem) => FormState::Validated(problem),
+            Err(errors) => FormState::Editing(errors),
+        };
+    }
+
+    fn validate(&self) -> Result<ValidatedProblem, Vec<String>> {
+        let mut errors = vec![];
+        if self.solver_method.is_interior() && self.alpha <= 0.0 {
+            errors.push("The step size α must be greater than 0.".to_string());
+        }
+        let (points, y) = parse_points(&self.text)?;
+        let problem = RegressionProblem {
+            kind: self.kind,
+            points,
+            y,
+            intercept: self.intercept,
+        };
+        if let Err(mut data_errors) = problem.validate() {
+            errors.append(&mut data_errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(problem.to_validated(
+            self.solver_method,
+            StartOptions {
+                alpha: self.alpha,
+                mu: 1.0,
+                find_start: false,
+                step_strategy: StepStrategy::default(),
+                scaling: Scaling::default(),
+            },
+        ))
+    }
+
+    /// The LP the data turned into: min c^T x subject to A x = b, x >= 0,
+    /// with one column per variable and one row per constraint.
+    fn render_derived(problem: &ValidatedProblem) -> Html {
+        let (rows, cols) = problem.a.shape();
+        let label = |j: usize| problem.variable_labels.get(j).cloned().unwrap_or_default();
+        let cell = |val: f64| {
+            if val == 0.0 {
+                html! { <td class="derived-zero">{ "·" }</td> }
+            } else {
+                html! { <td>{ format!("{}", (val * 1e4).round() / 1e4) }</td> }
+            }
+        };
+        html! {
+            <details class="derived-lp" open=true>
+                <summary>{ format!("Derived LP: {} constraints, {} variables", rows, cols) }</summary>
+                <div class="derived-lp-scroll">
+                    <table class="matrix derived-lp-table">
+                        <thead>
+                            <tr>
+                                <th></th>
+                                { for (0..cols).map(|j| html! { <th>{ label(j) }</th> }) }
+                                <th></th>
+                                <th>{ "b" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr class="derived-objective">
+                                <th>{ "min c" }</th>
+                                { for problem.c.iter().map(|&val| cell(val)) }
+                                <td></td>
+                                <td></td>
+                            </tr>
+                            {
+                                for (0..rows).map(|i| html! {
+                                    <tr>
+                                        <th>{ problem.constraint_labels.get(i).cloned().unwrap_or_default() }</th>
+                                        { for (0..cols).map(|j| cell(problem.a[(i, j)])) }
+                                        <td>{ "=" }</td>
+                                        <td>{ format!("{}", problem.b[i]) }</td>
+                                    </tr>
+                                })
+                            }
+                        </tbody>
+                    </table>
+                </div>
+            </details>
+        }
+    }
+}
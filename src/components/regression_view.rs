@@ -0,0 +1,94 @@
+use nalgebra::DVector;
+use yew::prelude::*;
+
+use crate::regression::RegressionLayout;
+
+/// Residuals below this count as points the fit passes through.
+const RESIDUAL_EPSILON: f64 = 1e-6;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub layout: RegressionLayout,
+    pub solution: DVector<f64>,
+}
+
+/// The fitted coefficients read back from a solved regression LP, with the
+/// residual of every point and the norm the fit minimized.
+pub struct RegressionView;
+
+impl Component for RegressionView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let layout = &props.layout;
+        let coefficients = layout.coefficients(&props.solution);
+        let residuals = layout.residuals(&coefficients);
+        let fitted = &layout.y - &residuals;
+        let features = layout.points.ncols();
+
+        let terms: Vec<String> = coefficients
+            .iter()
+            .enumerate()
+            .map(|(j, val)| {
+                let column = j + usize::from(!layout.intercept);
+                if column == 0 {
+                    format!("{:.4}", val)
+                } else if features == 1 {
+                    format!("{:.4} x", val)
+                } else {
+                    format!("{:.4} x{}", val, column)
+                }
+            })
+            .collect();
+
+        html! {
+            <div class="regression-view">
+                <h3>{ layout.kind.label() }</h3>
+                <p class="regression-equation">{ format!("y = {}", terms.join(" + ").replace("+ -", "- ")) }</p>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Point" }</th>
+                            { for (1..=features).map(|j| html! { <th>{ if features == 1 { "x".to_string() } else { format!("x{}", j) } }</th> }) }
+                            <th>{ "y" }</th>
+                            <th>{ "Fitted" }</th>
+                            <th>{ "Residual" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for (0..layout.y.len()).map(|i| {
+                                let on_fit = residuals[i].abs() <= RESIDUAL_EPSILON;
+                                html! {
+                                    <tr>
+                                        <th>{ format!("P{}", i + 1) }</th>
+                                        { for (0..features).map(|j| html! { <td>{ layout.points[(i, j)] }</td> }) }
+                                        <td>{ layout.y[i] }</td>
+                                        <td>{ format!("{:.4}", fitted[i]) }</td>
+                                        <td class={classes!(on_fit.then_some("on-fit"))}>
+                                            { if on_fit { "0".to_string() } else { format!("{:.4}", residuals[i]) } }
+                                        </td>
+                                    </tr>
+                                }
+                            })
+                        }
+                    </tbody>
+                </table>
+                <div class="barrier-summary">
+                    <span>{ format!("{} = {:.4}", layout.kind.loss_label(), layout.loss(&residuals)) }</span>
+                    <span>{ format!("{} of {} points on the fit", residuals.iter().filter(|r| r.abs() <= RESIDUAL_EPSILON).count(), residuals.len()) }</span>
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::rc::Rc;
+
+use gloo_file::callbacks::{read_as_text, FileReader};
+use gloo_timers::callback::Interval;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::replay::Recording;
+
+/// Milliseconds between replayed iterations; slow enough to talk over.
+const TICK_MS: u32 = 700;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Whether the active run has a recording to replay or save.
+    pub can_record: bool,
+    /// Steps replayed and in all while a replay runs.
+    pub progress: Option<(usize, usize)>,
+    /// How the last replay ended: the iterations it matched, or where it
+    /// went astray.
+    pub outcome: Option<Result<usize, String>>,
+    pub on_replay: Callback<()>,
+    pub on_save: Callback<()>,
+    pub on_load: Callback<Rc<Recording>>,
+    /// Called once per tick while a replay runs.
+    pub on_tick: Callback<()>,
+    pub on_stop: Callback<()>,
+}
+
+pub enum Msg {
+    LoadFile(web_sys::File),
+    Loaded(Result<String, String>),
+}
+
+/// "Replay": runs the recorded actions of a run again from its exact
+/// inputs, one iteration per tick, for lecture recordings and bug reports.
+/// Owns the tick; the app owns the run.
+pub struct ReplayControls {
+    error: Option<String>,
+    tick: Option<Interval>,
+    /// Keeps a file read in flight alive.
+    _reader: Option<FileReader>,
+}
+
+impl Component for ReplayControls {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut controls = Self {
+            error: None,
+            tick: None,
+            _reader: None,
+        };
+        controls.sync_tick(ctx);
+        controls
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.sync_tick(ctx);
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadFile(file) => {
+                let link = ctx.link().clone();
+                self._reader = Some(read_as_text(&file.into(), move |result| {
+                    link.send_message(Msg::Loaded(result.map_err(|e| e.to_string())))
+                }));
+                return false;
+            }
+            Msg::Loaded(result) => {
+                self._reader = None;
+                match result.and_then(|text| Recording::from_json(&text)) {
+                    Ok(recording) => {
+                        self.error = None;
+                        ctx.props().on_load.emit(Rc::new(recording));
+                    }
+                    Err(e) => self.error = Some(format!("Could not load the recording: {}", e)),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let props = ctx.props();
+        html! {
+            <div class="replay-controls">
+                {
+                    if let Some((done, total)) = props.progress {
+                        html! {
+                            <>
+                                <span class="solving-indicator" role="status">
+                                    { format!("Replaying… step {} of {}", done, total) }
+                                </span>
+                                <button onclick={props.on_stop.reform(|_| ())}>{ "Stop replay" }</button>
+                            </>
+                        }
+                    } else {
+                        html! {
+                            <>
+                                <button
+                                    disabled={!props.can_record}
+                                    title="Run this problem again from its exact inputs, one iteration per tick"
+                                    onclick={props.on_replay.reform(|_| ())}
+                                >
+                                    { "Replay" }
+                                </button>
+                                <button disabled={!props.can_record} onclick={props.on_save.reform(|_| ())}>
+                                    { "Save recording" }
+                                </button>
+                                <label class="replay-load">{ "Replay a recording: " }
+                                    <input
+                                        type="file"
+                                        accept=".json,application/json"
+                                        onchange={link.batch_callback(|e: Event| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            let file = input.files().and_then(|files| files.get(0));
+                                            input.set_value("");
+                                            file.map(Msg::LoadFile)
+                                        })}
+                                    />
+                                </label>
+                            </>
+                        }
+                    }
+                }
+                {
+                    match (&self.error, &props.outcome) {
+                        (Some(error), _) | (None, Some(Err(error))) => {
+                            html! { <span class="report-warning">{ error }</span> }
+                        }
+                        (None, Some(Ok(iterations))) => html! {
+                            <span class="replay-matched">
+                                { format!("Replay matched the recording: all {} iterations identical", iterations) }
+                            </span>
+                        },
+                        (None, None) => html! {},
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl ReplayControls {
+    /// Ticks while a replay runs, and only then.
+    fn sync_tick(&mut self, ctx: &Context<Self>) {
+        match (ctx.props().progress, &self.tick) {
+            (Some(_), None) => {
+                let on_tick = ctx.props().on_tick.clone();
+                self.tick = Some(Interval::new(TICK_MS, move || on_tick.emit(())));
+            }
+            (None, Some(_)) => self.tick = None,
+            _ => {}
+        }
+    }
+}
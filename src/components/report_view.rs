@@ -0,0 +1,297 @@
+use crate::cross_check::CrossCheck;
+use crate::milp::{MilpSolution, MilpStatus};
+use crate::notation::Notation;
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+use yew::prelude::*;
+
+use super::interior_view::InteriorPointView;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub report: SolveReport,
+
+    #[prop_or(true)]
+    pub maximize: bool,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+}
+
+pub struct SolveReportView;
+
+impl Component for SolveReportView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let report = &props.report;
+
+        let status_class = match report.status {
+            SolveStatus::Optimal => "report-status optimal",
+            _ => "report-status",
+        };
+
+        html! {
+            <div class="solve-report">
+                <h3>{ "Solve Report" }</h3>
+                <div class="barrier-summary">
+                    <span class={status_class}>{ report.status.label() }</span>
+                    <span>{ format!("{} Z = {:.4}", if props.maximize { "max" } else { "min" }, report.objective) }</span>
+                    <span>{ format!("{} iterations", report.iterations) }</span>
+                    <span>{ format!("{:.2} ms", report.timings.total_ms) }</span>
+                    {
+                        if let Some((norm, residual)) = report.residual {
+                            html! { <span>{ format!("{} = {}", norm.format("A x - b"), pretty::entry(residual)) }</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    {
+                        if let Some(reason) = report.stop_reason {
+                            html! { <span>{ format!("stopped on {}", reason.label()) }</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    { Self::render_cross_check_badge(report) }
+                </div>
+                {
+                    if let SolveStatus::Failed(reason) = &report.status {
+                        html! { <p class="report-warning">{ reason }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                { for report.warnings.iter().map(|w| html! { <p class="report-warning">{ w }</p> }) }
+                {
+                    if let Some(CrossCheck::Disagrees { reference, difference }) = report.cross_check {
+                        html! {
+                            <p class="report-warning">
+                                { format!(
+                                    "The simplex reaches Z = {:.6} on the same model, {:.2e} from this run: the iterates lost accuracy. Try scaling, double-double arithmetic or a smaller α.",
+                                    reference,
+                                    difference.abs()
+                                ) }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                { Self::render_scaling(report) }
+                { Self::render_ray(report) }
+                { Self::render_bound(report) }
+                { Self::render_goals(report) }
+                { Self::render_integer(report, props.maximize) }
+                <div class="matrix-container">
+                    <div class="matrix-box">
+                        <h4>{"Solution x"}</h4>
+                        { InteriorPointView::render_vector(Some(&report.solution)) }
+                    </div>
+                    {
+                        if let Some(original) = &report.original_solution {
+                            html! {
+                                <div class="matrix-box">
+                                    <h4>{"Original variables (bounds restored)"}</h4>
+                                    { InteriorPointView::render_vector(Some(original)) }
+                                </div>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <div class="matrix-box">
+                        <h4>{ format!("Dual estimate {}", props.notation.dual()) }</h4>
+                        { InteriorPointView::render_vector(report.duals.as_ref()) }
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl SolveReportView {
+    /// Whether the simplex reached the same objective, when it was asked.
+    fn render_cross_check_badge(report: &SolveReport) -> Html {
+        match &report.cross_check {
+            Some(CrossCheck::Agrees {
+                reference,
+                difference,
+            }) => html! {
+                <span
+                    class="report-status optimal cross-check"
+                    title={format!("The simplex reaches Z = {:.6}; the difference is {:.2e}.", reference, difference.abs())}
+                >
+                    { "✓ Simplex agrees" }
+                </span>
+            },
+            Some(CrossCheck::Disagrees { .. }) => html! {
+                <span class="report-status cross-check">{ "✗ Simplex disagrees" }</span>
+            },
+            Some(CrossCheck::NoReference(reason)) => html! {
+                <span class="cross-check" title={format!("No comparison: {}.", reason)}>{ "Simplex check unavailable" }</span>
+            },
+            None => html! {},
+        }
+    }
+
+    /// How equilibration changed the coefficient range of A.
+    fn render_scaling(report: &SolveReport) -> Html {
+        let Some(scaling) = &report.scaling else {
+            return html! {};
+        };
+        html! {
+            <p class="race-hint">
+                { format!(
+                    "{} scaling brought max |a_ij| / min |a_ij| from {:.2e} to {:.2e}. x, Z and the duals are in the original units.",
+                    scaling.method.label(),
+                    scaling.spread_before,
+                    scaling.spread_after
+                ) }
+            </p>
+        }
+    }
+
+    /// The direction affine scaling found along which Z improves forever.
+    fn render_ray(report: &SolveReport) -> Html {
+        let Some(ray) = &report.ray else {
+            return html! {};
+        };
+        let support: Vec<String> = ray
+            .direction
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| **val > 1e-9)
+            .map(|(j, val)| format!("x{} ({:.4})", j + 1, val))
+            .collect();
+        html! {
+            <div class="improving-ray">
+                <h4>{ "Improving ray d" }</h4>
+                <p>
+                    { format!(
+                        "A d = 0 and d ≥ 0, so x + t d stays feasible for every t ≥ 0 while Z changes by {:.4} per unit of t. Growing along: {}.",
+                        ray.rate,
+                        support.join(", ")
+                    ) }
+                </p>
+                <div class="matrix-box">
+                    { InteriorPointView::render_vector(Some(&ray.direction)) }
+                </div>
+            </div>
+        }
+    }
+
+    /// Predicted iteration count from theory against what the run took.
+    fn render_bound(report: &SolveReport) -> Html {
+        let Some(bound) = &report.bound else {
+            return html! {};
+        };
+        let within = (report.iterations as f64) <= bound.predicted;
+        html! {
+            <div class="theory-bound">
+                <div class="barrier-summary">
+                    <span>{ format!("Theory: {}", bound.formula) }</span>
+                    <span>{ format!("predicted ≤ {:.0}", bound.predicted) }</span>
+                    {
+                        if let Some(expected) = bound.expected {
+                            html! { <span>{ format!("expected ≈ {:.0}", expected) }</span> }
+                        } else {
+                            html! {}
+                        }
+                    }
+                    <span class={if within { "report-status optimal" } else { "report-status" }}>
+                        { format!("actual {}", report.iterations) }
+                    </span>
+                </div>
+                <p class="theory-note">{ bound.note }</p>
+            </div>
+        }
+    }
+
+    /// Each goal's own value and its weighted share of Z.
+    fn render_goals(report: &SolveReport) -> Html {
+        if report.goals.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="goal-report">
+                <h4>{ "Goal achievement" }</h4>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Goal" }</th>
+                            <th>{ "Weight w" }</th>
+                            <th>{ "Value cᵀx" }</th>
+                            <th>{ "w · cᵀx" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for report.goals.iter().enumerate().map(|(k, goal)| html! {
+                                <tr>
+                                    <th>{ format!("G{}", k + 1) }</th>
+                                    <td>{ format!("{:.4}", goal.weight) }</td>
+                                    <td>{ format!("{:.4}", goal.value) }</td>
+                                    <td>{ format!("{:.4}", goal.weight * goal.value) }</td>
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+
+    /// Relaxation optimum next to the branch-and-bound integer optimum.
+    fn render_integer(report: &SolveReport, maximize: bool) -> Html {
+        let Some(integer) = &report.integer else {
+            return html! {};
+        };
+        let sense = if maximize { "max" } else { "min" };
+        let solution_box = |title: &str, solution: Option<&MilpSolution>| match solution {
+            Some(solution) => html! {
+                <div class="matrix-box">
+                    <h4>{ format!("{}: {} Z = {:.4}", title, sense, solution.objective) }</h4>
+                    { InteriorPointView::render_vector(Some(&solution.x)) }
+                </div>
+            },
+            None => html! {
+                <div class="matrix-box">
+                    <h4>{ title.to_string() }</h4>
+                    <p>{ "(None)" }</p>
+                </div>
+            },
+        };
+        html! {
+            <div class="integer-report">
+                <div class="barrier-summary">
+                    <span class={if integer.status == MilpStatus::Optimal { "report-status optimal" } else { "report-status" }}>
+                        { integer.status.label() }
+                    </span>
+                    <span>{ format!("{} branch-and-bound nodes", integer.nodes) }</span>
+                </div>
+                {
+                    if let MilpStatus::Failed(reason) = &integer.status {
+                        html! { <p class="report-warning">{ reason }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                <div class="matrix-container">
+                    { solution_box("LP relaxation", integer.relaxation.as_ref()) }
+                    { solution_box("Integer optimum", integer.incumbent.as_ref()) }
+                </div>
+            </div>
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::augmented_view::AugmentedComparisonView;
+use super::context::{subscribe, ProblemContext, SettingsContext};
+use super::fractional_view::FractionalView;
+use super::regression_view::RegressionView;
+use super::report_view::SolveReportView;
+use super::transportation_view::TransportationView;
+
+pub enum Msg {
+    ProblemChanged(ProblemContext),
+    SettingsChanged(SettingsContext),
+}
+
+/// The outcome of a finished run: the report, the augmented solution next
+/// to the original one, and the flow matrix, fitted line or ratio for
+/// transportation, regression and fractional models.
+/// Hidden while the run has an error.
+pub struct ResultsPanel {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+    ),
+}
+
+impl Component for ResultsPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::ProblemChanged);
+        let (settings, settings_handle) = subscribe(ctx, Msg::SettingsChanged);
+        Self {
+            problem,
+            settings,
+            _handles: (problem_handle, settings_handle),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ProblemChanged(problem) => self.problem = problem,
+            Msg::SettingsChanged(settings) => self.settings = settings,
+        }
+        true
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        if self.problem.error.is_some() {
+            return html! {};
+        }
+        html! {
+            <>
+                { self.render_report() }
+                { self.render_augmented_comparison() }
+                { self.render_flow_matrix() }
+                { self.render_regression_fit() }
+                { self.render_fractional() }
+            </>
+        }
+    }
+}
+
+impl ResultsPanel {
+    fn render_report(&self) -> Html {
+        let Some(report) = &self.problem.report else {
+            return html! {};
+        };
+        html! {
+            <SolveReportView report={(**report).clone()} maximize={self.problem.maximize()} notation={self.settings.notation.clone()} />
+        }
+    }
+
+    /// The augmented optimum next to the user's variables and constraints.
+    /// Only for models the app augmented itself; presolve and Charnes–Cooper
+    /// change the variables, so the original rows no longer apply to x.
+    fn render_augmented_comparison(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        let augmentation = &model.augmentation;
+        let added = augmentation.slack_columns.len() + augmentation.artificial_columns.len();
+        if model.is_augmented
+            || added == 0
+            || model.presolve.is_some()
+            || model.fractional.is_some()
+            || report.solution.len() != model.a.ncols()
+        {
+            return html! {};
+        }
+        html! {
+            <AugmentedComparisonView
+                augmentation={augmentation.clone()}
+                variable_labels={model.variable_labels.clone()}
+                constraint_labels={model.constraint_labels.clone()}
+                constraint_signs={model.constraint_signs.clone()}
+                solution={report.solution.clone()}
+                checks={model.constraint_checks(&report.solution)}
+            />
+        }
+    }
+
+    /// Flow matrix of a solved transportation model.
+    fn render_flow_matrix(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        match &model.transportation {
+            Some(layout) => html! {
+                <TransportationView layout={layout.clone()} solution={report.solution.clone()} />
+            },
+            None => html! {},
+        }
+    }
+
+    /// Coefficients and residuals of a solved regression model.
+    fn render_regression_fit(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        match &model.regression {
+            Some(layout) => html! {
+                <RegressionView layout={layout.clone()} solution={report.solution.clone()} />
+            },
+            None => html! {},
+        }
+    }
+
+    /// x and the ratio read back from the Charnes–Cooper optimum.
+    fn render_fractional(&self) -> Html {
+        let (Some(model), Some(report)) = (&self.problem.model, &self.problem.report) else {
+            return html! {};
+        };
+        match &model.fractional {
+            Some(layout) => html! {
+                <FractionalView
+                    layout={layout.clone()}
+                    solution={report.original_solution.clone().unwrap_or_else(|| report.solution.clone())}
+                />
+            },
+            None => html! {},
+        }
+    }
+}
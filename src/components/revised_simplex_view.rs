@@ -0,0 +1,88 @@
+use crate::notation::Notation;
+use crate::revised_simplex::RevisedSimplexIteration;
+use yew::prelude::*;
+
+use super::interior_view::InteriorPointView;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub iteration: usize,
+
+    #[prop_or_default]
+    pub iteration_data: Option<RevisedSimplexIteration>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+pub struct RevisedSimplexView;
+
+impl Component for RevisedSimplexView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let it = props.iteration_data.as_ref();
+        let y = props.notation.dual();
+        let compact = props.compact;
+
+        html! {
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Basis {}", props.iteration) }</h3>
+
+                {
+                    if let Some(iter) = it {
+                        html! {
+                            <>
+                                <div class="barrier-summary">
+                                    <span>{ format!("Z = {:.4}", iter.objective) }</span>
+                                    <span>{ Self::describe_pivot(iter) }</span>
+                                    <span>{ format!("{} eta updates since LU", iter.eta_count) }</span>
+                                </div>
+                                <p class="basis-list">
+                                    { "Basis: " }
+                                    { iter.basis.iter().map(|j| format!("x{}", j + 1)).collect::<Vec<_>>().join(", ") }
+                                </p>
+                                <div class="matrix-container">
+                                    { InteriorPointView::vector_card("x_B = B^{-1} b", Some(&iter.basic_values), compact) }
+                                    { InteriorPointView::vector_card(&format!("{} = B^{{-T}} c_B", y), Some(&iter.duals), compact) }
+                                    { InteriorPointView::vector_card(&format!("Reduced costs c - A^T {}", y), Some(&iter.reduced_costs), compact) }
+                                    { InteriorPointView::vector_card("d = B^{-1} a_q", iter.direction.as_ref(), compact) }
+                                    { InteriorPointView::vector_card(&format!("Current vertex {}", props.notation.iterate(props.iteration)), Some(&iter.current_x), compact) }
+                                </div>
+                            </>
+                        }
+                    } else {
+                        html! { <p>{"(Not available)"}</p> }
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl RevisedSimplexView {
+    fn describe_pivot(iter: &RevisedSimplexIteration) -> String {
+        match (iter.entering, iter.leaving_row) {
+            (Some(j), Some(i)) => format!(
+                "next pivot: x{} enters, x{} leaves",
+                j + 1,
+                iter.basis[i] + 1
+            ),
+            (Some(j), None) => format!("x{} can increase without bound", j + 1),
+            _ => "optimal: no positive reduced cost".to_string(),
+        }
+    }
+}
@@ -0,0 +1,424 @@
+use nalgebra::DVector;
+
+use crate::barrier::{perform_barrier_centering_step, BarrierProblem};
+use crate::history_filter::IterationFacts;
+use crate::hsd::HsdProblem;
+use crate::interior::{
+    perform_centering_iteration, InteriorPointError, InteriorPointProblem, SolverOptions,
+    CONDITION_WARNING,
+};
+use crate::model::{SolverMethod, ValidatedProblem};
+use crate::narration;
+use crate::qp::QpProblem;
+use crate::report::{now_ms, SolveReport, SolveStatus, Timings};
+use crate::revised_simplex::RevisedSimplexProblem;
+use crate::session::RunStep;
+use crate::simplex::{SimplexError, SimplexProblem};
+use crate::snapshot::CompactInteriorPointIteration;
+use crate::solver::Solver;
+
+/// A solver and the cards of the iterations it has taken, oldest first.
+/// Affine scaling keeps its cards compacted.
+pub struct History<S: Solver, I = <S as Solver>::Iteration> {
+    pub problem: S,
+    pub iterations: Vec<I>,
+}
+
+impl<S: Solver, I: From<S::Iteration>> History<S, I> {
+    pub fn new(problem: S) -> Self {
+        Self {
+            problem,
+            iterations: vec![],
+        }
+    }
+
+    /// One step, timed into `timings`. The iteration is kept; the error
+    /// the method stopped with comes back.
+    fn step(&mut self, timings: &mut Timings) -> Option<S::Error> {
+        let start = now_ms();
+        let result = self.problem.step();
+        timings.record(now_ms() - start);
+        match result {
+            Ok(iteration) => {
+                self.iterations.push(iteration.into());
+                None
+            }
+            Err(e) => Some(e),
+        }
+    }
+}
+
+/// Affine scaling's history, the one the worker, inspector and sessions
+/// handle in full.
+pub type AffineHistory = History<InteriorPointProblem, CompactInteriorPointIteration>;
+
+/// Why a step ended the run, from an interior or a simplex method.
+pub enum StepError {
+    Interior(InteriorPointError),
+    Simplex(SimplexError),
+}
+
+impl From<InteriorPointError> for StepError {
+    fn from(error: InteriorPointError) -> Self {
+        StepError::Interior(error)
+    }
+}
+
+impl From<SimplexError> for StepError {
+    fn from(error: SimplexError) -> Self {
+        StepError::Simplex(error)
+    }
+}
+
+/// The run on the solver page, whichever method the model asked for,
+/// stepped through `Solver` like a race lane.
+pub enum ActiveRun {
+    Affine(AffineHistory),
+    Barrier(History<BarrierProblem>),
+    Simplex(History<SimplexProblem>),
+    Revised(History<RevisedSimplexProblem>),
+    Quadratic(History<QpProblem>),
+    SelfDual(History<HsdProblem>),
+}
+
+impl ActiveRun {
+    /// Sets up the model's method with no iterations taken. The primal
+    /// interior methods start at `x`, the self-dual embedding from it as
+    /// well; the simplex methods start from their slack basis instead.
+    pub fn new(
+        model: &ValidatedProblem,
+        x: DVector<f64>,
+        options: &SolverOptions,
+    ) -> Result<Self, StepError> {
+        let (a, b, c) = (&model.a, &model.b, model.internal_c());
+        Ok(match model.method {
+            SolverMethod::AffineScaling => ActiveRun::Affine(History::new(
+                InteriorPointProblem::new(
+                    a.clone(),
+                    b.clone(),
+                    c,
+                    x,
+                    model.options.alpha,
+                    options.clone(),
+                )?
+                .with_strategy(model.options.step_strategy)
+                .with_scaling(model.options.scaling),
+            )),
+            SolverMethod::LogBarrier => {
+                ActiveRun::Barrier(History::new(BarrierProblem::new(model, x, options)))
+            }
+            SolverMethod::QuadraticBarrier => {
+                ActiveRun::Quadratic(History::new(QpProblem::new(model, x, options)))
+            }
+            SolverMethod::SelfDual => ActiveRun::SelfDual(History::new(HsdProblem::new(
+                a.clone(),
+                b.clone(),
+                c,
+                x,
+                model.options.alpha,
+                options.clone(),
+            ))),
+            SolverMethod::Simplex => {
+                let mut problem = SimplexProblem::new(a, b, &c)?;
+                problem.options = options.clone();
+                ActiveRun::Simplex(History::new(problem))
+            }
+            SolverMethod::RevisedSimplex => {
+                let mut problem = RevisedSimplexProblem::new(a, b, &c)?;
+                problem.options = options.clone();
+                ActiveRun::Revised(History::new(problem))
+            }
+        })
+    }
+
+    /// The first card: the starting tableau or basis of the simplex
+    /// methods, the first step of the others.
+    pub fn open(&mut self, timings: &mut Timings) -> Option<StepError> {
+        match self {
+            ActiveRun::Simplex(history) => {
+                history.iterations.push(history.problem.snapshot());
+                None
+            }
+            ActiveRun::Revised(history) => match history.problem.snapshot() {
+                Ok(first) => {
+                    history.iterations.push(first);
+                    None
+                }
+                Err(e) => Some(e.into()),
+            },
+            _ => self.step(timings),
+        }
+    }
+
+    /// Takes one step, timed into `timings`; the error comes back once the
+    /// method stops, an optimum included.
+    pub fn step(&mut self, timings: &mut Timings) -> Option<StepError> {
+        match self {
+            ActiveRun::Affine(history) => history.step(timings).map(StepError::from),
+            ActiveRun::Barrier(history) => history.step(timings).map(StepError::from),
+            ActiveRun::Quadratic(history) => history.step(timings).map(StepError::from),
+            ActiveRun::SelfDual(history) => history.step(timings).map(StepError::from),
+            ActiveRun::Simplex(history) => history.step(timings).map(StepError::from),
+            ActiveRun::Revised(history) => history.step(timings).map(StepError::from),
+        }
+    }
+
+    pub fn can_center(&self) -> bool {
+        matches!(self, ActiveRun::Affine(_) | ActiveRun::Barrier(_))
+    }
+
+    /// Takes a centering step, or `None` for a method without one.
+    pub fn center(&mut self) -> Option<Result<(), InteriorPointError>> {
+        match self {
+            ActiveRun::Affine(history) => Some(
+                perform_centering_iteration(&mut history.problem)
+                    .map(|iteration| history.iterations.push(iteration.into())),
+            ),
+            ActiveRun::Barrier(history) => Some(
+                perform_barrier_centering_step(&mut history.problem)
+                    .map(|iteration| history.iterations.push(iteration)),
+            ),
+            _ => None,
+        }
+    }
+
+    pub fn affine(&self) -> Option<&AffineHistory> {
+        match self {
+            ActiveRun::Affine(history) => Some(history),
+            _ => None,
+        }
+    }
+
+    pub fn affine_mut(&mut self) -> Option<&mut AffineHistory> {
+        match self {
+            ActiveRun::Affine(history) => Some(history),
+            _ => None,
+        }
+    }
+
+    /// Whether the run moves through the interior, so its path can be drawn.
+    pub fn is_interior(&self) -> bool {
+        !matches!(self, ActiveRun::Simplex(_) | ActiveRun::Revised(_))
+    }
+
+    /// Cards taken so far, the first included.
+    pub fn len(&self) -> usize {
+        match self {
+            ActiveRun::Affine(history) => history.iterations.len(),
+            ActiveRun::Barrier(history) => history.iterations.len(),
+            ActiveRun::Quadratic(history) => history.iterations.len(),
+            ActiveRun::SelfDual(history) => history.iterations.len(),
+            ActiveRun::Simplex(history) => history.iterations.len(),
+            ActiveRun::Revised(history) => history.iterations.len(),
+        }
+    }
+
+    /// Iterates of the run, oldest first.
+    pub fn path(&self) -> Vec<DVector<f64>> {
+        fn xs<'a>(xs: impl Iterator<Item = &'a DVector<f64>>) -> Vec<DVector<f64>> {
+            xs.cloned().collect()
+        }
+        match self {
+            ActiveRun::Affine(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+            ActiveRun::Barrier(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+            ActiveRun::Quadratic(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+            ActiveRun::SelfDual(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+            ActiveRun::Simplex(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+            ActiveRun::Revised(history) => xs(history.iterations.iter().map(|it| &it.current_x)),
+        }
+    }
+
+    /// What produced each card, the start included.
+    pub fn steps(&self) -> Vec<RunStep> {
+        let kind = |centering: bool| match centering {
+            true => RunStep::Centering,
+            false => RunStep::Step,
+        };
+        match self {
+            ActiveRun::Affine(history) => history
+                .iterations
+                .iter()
+                .map(|it| kind(it.centering))
+                .collect(),
+            ActiveRun::Barrier(history) => history
+                .iterations
+                .iter()
+                .map(|it| kind(it.centering))
+                .collect(),
+            _ => vec![RunStep::Step; self.len()],
+        }
+    }
+
+    pub fn set_options(&mut self, options: &SolverOptions) {
+        match self {
+            ActiveRun::Affine(history) => history.problem.options = options.clone(),
+            ActiveRun::Barrier(history) => history.problem.options = options.clone(),
+            ActiveRun::Quadratic(history) => history.problem.options = options.clone(),
+            ActiveRun::SelfDual(history) => history.problem.options = options.clone(),
+            ActiveRun::Simplex(history) => history.problem.options = options.clone(),
+            ActiveRun::Revised(history) => history.problem.options = options.clone(),
+        }
+    }
+
+    /// Factor by which the barrier methods shrink mu, for the iteration
+    /// bound; the others are bounded as if it were halved.
+    pub fn mu_decrease(&self) -> f64 {
+        match self {
+            ActiveRun::Barrier(history) => history.problem.mu_decrease,
+            ActiveRun::Quadratic(history) => history.problem.mu_decrease,
+            _ => 0.5,
+        }
+    }
+
+    /// The solver's report at the current iterate, with the improving ray
+    /// an unbounded run stopped on. The starting tableau of the simplex
+    /// methods is not an iteration.
+    pub fn report(&self, status: SolveStatus, ray: Option<DVector<f64>>) -> SolveReport {
+        match self {
+            ActiveRun::Affine(history) => {
+                let mut report = history
+                    .problem
+                    .report(status, history.iterations.len(), ray);
+                let conditions = history.iterations.iter().map(|iter| iter.condition);
+                if let Some(first) = conditions.clone().position(|cond| cond > CONDITION_WARNING) {
+                    report.warnings.push(format!(
+                        "A~ A~^T is ill-conditioned from iteration {} on (cond up to {:.2e}); the projections lost accuracy",
+                        first,
+                        conditions.fold(0.0, f64::max)
+                    ));
+                }
+                report
+            }
+            ActiveRun::Barrier(history) => history.problem.report(status, history.iterations.len()),
+            ActiveRun::Quadratic(history) => {
+                history.problem.report(status, history.iterations.len())
+            }
+            ActiveRun::SelfDual(history) => {
+                history
+                    .problem
+                    .report(status, history.iterations.len(), ray)
+            }
+            ActiveRun::Simplex(history) => history
+                .problem
+                .report(status, history.iterations.len().saturating_sub(1)),
+            ActiveRun::Revised(history) => history
+                .problem
+                .report(status, history.iterations.len().saturating_sub(1)),
+        }
+    }
+
+    /// The newest card read out. `objective` gives Z in the model's sense
+    /// at a standard-form x; `original` counts the model's own variables.
+    pub fn describe_latest(
+        &self,
+        objective: impl Fn(&DVector<f64>) -> f64,
+        original: usize,
+    ) -> Option<String> {
+        let k = self.len().checked_sub(1)?;
+        Some(match self {
+            ActiveRun::Affine(history) => {
+                let iter = &history.iterations[k];
+                narration::interior_step(
+                    k,
+                    &iter.current_x,
+                    objective(&iter.current_x),
+                    iter.centering,
+                    original,
+                )
+            }
+            ActiveRun::Barrier(history) => {
+                let iter = &history.iterations[k];
+                narration::barrier_step(k, iter, objective(&iter.current_x), original)
+            }
+            ActiveRun::Quadratic(history) => {
+                narration::qp_step(k, &history.iterations[k], original)
+            }
+            ActiveRun::SelfDual(history) => {
+                let iter = &history.iterations[k];
+                narration::hsd_step(k, iter, objective(&iter.current_x), original)
+            }
+            ActiveRun::Simplex(history) => {
+                let iter = &history.iterations[k];
+                narration::simplex_step(k, iter, objective(&iter.current_x), original)
+            }
+            ActiveRun::Revised(history) => {
+                let iter = &history.iterations[k];
+                narration::revised_step(k, iter, objective(&iter.current_x), original)
+            }
+        })
+    }
+
+    /// What the history search can test, one entry per card. `objective`
+    /// gives Z in the model's sense at a standard-form x, `sign` turns the
+    /// solvers' maximized objective into it.
+    pub fn facts(
+        &self,
+        objective: impl Fn(&DVector<f64>) -> f64,
+        sign: f64,
+    ) -> Vec<IterationFacts> {
+        let facts = |index: usize, objective: f64, x: DVector<f64>| IterationFacts {
+            index,
+            objective,
+            x,
+            step: None,
+            mu: None,
+            condition: None,
+            warning: false,
+        };
+        match self {
+            ActiveRun::Affine(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| IterationFacts {
+                    step: it.step.as_ref().map(|step| step.length),
+                    condition: Some(it.condition),
+                    warning: it.condition > CONDITION_WARNING,
+                    ..facts(i, objective(&it.current_x), it.current_x.clone())
+                })
+                .collect(),
+            ActiveRun::Barrier(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| IterationFacts {
+                    step: Some(it.step_length),
+                    mu: Some(it.mu),
+                    ..facts(i, objective(&it.current_x), it.current_x.clone())
+                })
+                .collect(),
+            ActiveRun::Quadratic(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| IterationFacts {
+                    step: Some(it.step_length),
+                    mu: Some(it.mu),
+                    ..facts(i, it.objective * sign, it.current_x.clone())
+                })
+                .collect(),
+            ActiveRun::SelfDual(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| IterationFacts {
+                    step: Some(it.step_length),
+                    mu: Some(it.mu),
+                    ..facts(i, objective(&it.current_x), it.current_x.clone())
+                })
+                .collect(),
+            ActiveRun::Simplex(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| facts(i, it.objective * sign, it.current_x.clone()))
+                .collect(),
+            ActiveRun::Revised(history) => history
+                .iterations
+                .iter()
+                .enumerate()
+                .map(|(i, it)| facts(i, it.objective * sign, it.current_x.clone()))
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,304 @@
+use web_sys::HtmlInputElement;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, ProblemContext, SettingsContext};
+use crate::model::ValidatedProblem;
+use crate::report::SolveStatus;
+use crate::scenario::{solve_all, Scenario, ScenarioComparison, Target};
+
+pub enum Msg {
+    Problem(ProblemContext),
+    Settings(SettingsContext),
+    Add,
+    Remove(usize),
+    Rename(usize, String),
+    /// Sets or, with `None`, clears an override of scenario `k`.
+    SetValue(usize, Target, Option<f64>),
+    SolveAll,
+}
+
+/// Named variations of the submitted model, such as optimistic, pessimistic
+/// and base cases, solved together and compared side by side. Scenarios are
+/// kept when a new model is submitted, minus overrides of rows or columns
+/// it no longer has.
+pub struct ScenarioPanel {
+    problem: ProblemContext,
+    settings: SettingsContext,
+    _handles: (
+        ContextHandle<ProblemContext>,
+        ContextHandle<SettingsContext>,
+    ),
+    scenarios: Vec<Scenario>,
+    comparison: Option<ScenarioComparison>,
+}
+
+impl Component for ScenarioPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::Problem);
+        let (settings, settings_handle) = subscribe(ctx, Msg::Settings);
+        Self {
+            problem,
+            settings,
+            _handles: (problem_handle, settings_handle),
+            scenarios: vec![],
+            comparison: None,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Problem(problem) => {
+                let new_model = problem.model != self.problem.model;
+                self.problem = problem;
+                if new_model {
+                    if let Some(model) = &self.problem.model {
+                        for scenario in &mut self.scenarios {
+                            scenario.retain_valid(model);
+                        }
+                    }
+                    self.comparison = None;
+                }
+            }
+            Msg::Settings(settings) => self.settings = settings,
+            Msg::Add => {
+                let name = match self.scenarios.len() {
+                    0 => "Optimistic".to_string(),
+                    1 => "Pessimistic".to_string(),
+                    k => format!("Scenario {}", k + 1),
+                };
+                self.scenarios.push(Scenario::new(name));
+            }
+            Msg::Remove(k) => {
+                self.scenarios.remove(k);
+                self.comparison = None;
+            }
+            Msg::Rename(k, name) => self.scenarios[k].name = name,
+            Msg::SetValue(k, target, value) => {
+                self.scenarios[k].set(target, value);
+                self.comparison = None;
+            }
+            Msg::SolveAll => {
+                let Some(model) = &self.problem.model else {
+                    return false;
+                };
+                self.comparison = Some(solve_all(model, &self.scenarios, &self.settings.options));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let Some(model) = &self.problem.model else {
+            return html! {
+                <div class="race-panel scenario-panel">
+                    <h3>{ "Scenarios" }</h3>
+                    <p class="race-hint">{ "Solve a problem first; scenarios are variations of the submitted model." }</p>
+                </div>
+            };
+        };
+
+        html! {
+            <div class="race-panel scenario-panel">
+                <h3>{ "Scenarios" }</h3>
+                <p class="race-hint">
+                    { "Each scenario overrides some coefficients, right-hand sides or bounds of the submitted model; blank cells keep the base value." }
+                </p>
+                {
+                    if model.is_augmented {
+                        html! { <p class="race-hint">{ "Bounds add rows to the model, so they need Auto-Augment." }</p> }
+                    } else {
+                        html! {}
+                    }
+                }
+                { self.render_editor(ctx, model) }
+                <div class="race-controls">
+                    <button onclick={link.callback(|_| Msg::Add)}>{ "Add Scenario" }</button>
+                    <button onclick={link.callback(|_| Msg::SolveAll)}>
+                        { if self.scenarios.is_empty() { "Solve Base" } else { "Solve All" } }
+                    </button>
+                </div>
+                {
+                    if let Some(comparison) = &self.comparison {
+                        Self::render_comparison(model, comparison)
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl ScenarioPanel {
+    /// One row per target, one column of overrides per scenario.
+    fn render_editor(&self, ctx: &Context<Self>, model: &ValidatedProblem) -> Html {
+        if self.scenarios.is_empty() {
+            return html! {};
+        }
+        let link = ctx.link();
+        let targets: Vec<Target> = Target::all(model)
+            .into_iter()
+            .filter(|target| !model.is_augmented || matches!(target, Target::Coefficient(_)))
+            .collect();
+
+        html! {
+            <div class="race-history scenario-editor">
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "Parameter" }</th>
+                            <th>{ "Base" }</th>
+                            {
+                                for self.scenarios.iter().enumerate().map(|(k, scenario)| html! {
+                                    <th>
+                                        <input
+                                            type="text"
+                                            class="scenario-name"
+                                            value={scenario.name.clone()}
+                                            oninput={link.callback(move |e: InputEvent| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                Msg::Rename(k, input.value())
+                                            })}
+                                        />
+                                        <button
+                                            class="scenario-remove"
+                                            title="Remove scenario"
+                                            onclick={link.callback(move |_| Msg::Remove(k))}
+                                        >
+                                            { "×" }
+                                        </button>
+                                    </th>
+                                })
+                            }
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        for targets.into_iter().map(|target| {
+                            let base = target
+                                .current(model)
+                                .map_or_else(|| "—".to_string(), |value| value.to_string());
+                            html! {
+                                <tr>
+                                    <td>{ target.label(model) }</td>
+                                    <td>{ base.clone() }</td>
+                                    {
+                                        for self.scenarios.iter().enumerate().map(|(k, scenario)| html! {
+                                            <td>
+                                                <input
+                                                    type="number"
+                                                    step="any"
+                                                    placeholder={base.clone()}
+                                                    value={scenario.value(target).map(|value| value.to_string()).unwrap_or_default()}
+                                                    onchange={link.callback(move |e: Event| {
+                                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                                        Msg::SetValue(k, target, input.value().parse().ok())
+                                                    })}
+                                                />
+                                            </td>
+                                        })
+                                    }
+                                </tr>
+                            }
+                        })
+                    }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+
+    /// Objective, change against the base, and solution of every case.
+    fn render_comparison(model: &ValidatedProblem, comparison: &ScenarioComparison) -> Html {
+        let base = comparison.base_objective();
+        let sense = if model.sense.is_maximize() {
+            "max Z"
+        } else {
+            "min Z"
+        };
+
+        html! {
+            <div class="race-history scenario-comparison">
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{ "Scenario" }</th>
+                            <th>{ "Status" }</th>
+                            <th>{ sense }</th>
+                            <th>{ "Δ vs base" }</th>
+                            <th title="Dual simplex pivots from the base optimum, for scenarios that only change right-hand sides">{ "Pivots" }</th>
+                            { for comparison.variable_labels.iter().map(|label| html! { <th>{ label }</th> }) }
+                        </tr>
+                    </thead>
+                    <tbody>
+                    {
+                        for comparison.outcomes.iter().map(|outcome| {
+                            let delta = match (outcome.objective, base) {
+                                (Some(z), Some(base)) => format!("{:+.4}", z - base),
+                                _ => "—".to_string(),
+                            };
+                            html! {
+                                <tr>
+                                    <td>{ &outcome.name }</td>
+                                    <td title={failure(&outcome.status)}>{ outcome.status.label() }</td>
+                                    <td>{ outcome.objective.map_or_else(|| "—".to_string(), |z| format!("{:.4}", z)) }</td>
+                                    <td>{ delta }</td>
+                                    <td>{ outcome.pivots.map_or_else(|| "—".to_string(), |pivots| pivots.to_string()) }</td>
+                                    {
+                                        for (0..comparison.variable_labels.len()).map(|j| html! {
+                                            <td>
+                                                {
+                                                    outcome
+                                                        .solution
+                                                        .as_ref()
+                                                        .and_then(|x| x.get(j))
+                                                        .map_or_else(|| "—".to_string(), |v| format!("{:.4}", v))
+                                                }
+                                            </td>
+                                        })
+                                    }
+                                </tr>
+                            }
+                        })
+                    }
+                    </tbody>
+                </table>
+                {
+                    if comparison.outcomes.iter().any(|outcome| outcome.pivots.is_some()) {
+                        html! {
+                            <p class="race-hint">
+                                { "Scenarios that change only right-hand sides keep the base optimal basis dual feasible, so the dual simplex re-optimizes from it; Pivots counts the pivots that took." }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if comparison.outcomes.iter().any(|outcome| outcome.objective.is_none()) {
+                        html! {
+                            <p class="race-hint">
+                                { "A dash means the case has no optimum; hover a failed status for the reason." }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+/// Why a case failed, for the status tooltip.
+fn failure(status: &SolveStatus) -> Option<String> {
+    match status {
+        SolveStatus::Failed(reason) => Some(reason.clone()),
+        _ => None,
+    }
+}
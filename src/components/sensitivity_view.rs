@@ -0,0 +1,155 @@
+use yew::prelude::*;
+
+use crate::notation::Notation;
+use crate::sensitivity::{Range, SensitivityReport};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub sensitivity: SensitivityReport,
+    pub variable_labels: Vec<String>,
+    pub constraint_labels: Vec<String>,
+    /// Rows and columns are the presolved ones, so right-hand sides are in
+    /// the shifted variables.
+    #[prop_or_default]
+    pub presolved: bool,
+    #[prop_or_default]
+    pub notation: Notation,
+}
+
+/// Shadow prices, reduced costs and the allowable ranges of c and b at the
+/// optimal basis.
+pub struct SensitivityView;
+
+impl Component for SensitivityView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let sensitivity = &props.sensitivity;
+        let label = |labels: &[String], k: usize, prefix: &str| {
+            labels
+                .get(k)
+                .cloned()
+                .unwrap_or_else(|| format!("{}{}", prefix, k + 1))
+        };
+
+        html! {
+            <div class="solve-report sensitivity-report">
+                <h3>{ "Sensitivity" }</h3>
+                {
+                    if sensitivity.degenerate {
+                        html! {
+                            <p class="report-warning">
+                                { "The optimal basis is degenerate: shadow prices may only hold in one direction, and another optimal basis can give different ranges." }
+                            </p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                {
+                    if props.presolved {
+                        html! {
+                            <p class="race-hint">{ "Ranges are for the presolved model; right-hand sides are after the bound shift." }</p>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+                <h4>{ "Objective coefficients" }</h4>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Variable" }</th>
+                            <th>{ "Value" }</th>
+                            <th>{ "Reduced cost" }</th>
+                            <th>{ "c" }</th>
+                            <th>{ "Allowable decrease" }</th>
+                            <th>{ "Allowable increase" }</th>
+                            <th>{ "Range of c" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for sensitivity.variables.iter().enumerate().map(|(j, variable)| html! {
+                                <tr class={if variable.basic { "sensitivity-basic" } else { "" }}>
+                                    <th>{ label(&props.variable_labels, j, "x") }</th>
+                                    <td>{ format!("{:.4}", variable.value) }</td>
+                                    <td>{ format!("{:.4}", variable.reduced_cost) }</td>
+                                    { Self::render_range(&variable.objective) }
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+                <h4>{ "Right-hand sides" }</h4>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Constraint" }</th>
+                            <th>{ format!("Shadow price {}", props.notation.dual()) }</th>
+                            <th>{ "b" }</th>
+                            <th>{ "Allowable decrease" }</th>
+                            <th>{ "Allowable increase" }</th>
+                            <th>{ "Range of b" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for sensitivity.constraints.iter().enumerate().map(|(i, constraint)| html! {
+                                <tr>
+                                    <th>{ label(&props.constraint_labels, i, "Row ") }</th>
+                                    <td>{ format!("{:.4}", constraint.shadow_price) }</td>
+                                    { Self::render_range(&constraint.rhs) }
+                                </tr>
+                            })
+                        }
+                    </tbody>
+                </table>
+            </div>
+        }
+    }
+}
+
+impl SensitivityView {
+    /// Current value, allowable decrease and increase, and the interval they
+    /// span, as four cells.
+    fn render_range(range: &Range) -> Html {
+        let (low, high) = range.bounds();
+        html! {
+            <>
+                <td>{ format!("{:.4}", range.current) }</td>
+                <td>{ amount(range.decrease) }</td>
+                <td>{ amount(range.increase) }</td>
+                <td>{ format!("[{}, {}]", bound(low), bound(high)) }</td>
+            </>
+        }
+    }
+}
+
+fn amount(val: f64) -> String {
+    if val.is_finite() {
+        format!("{:.4}", val)
+    } else {
+        "∞".to_string()
+    }
+}
+
+fn bound(val: f64) -> String {
+    if val.is_finite() {
+        format!("{:.4}", val)
+    } else if val > 0.0 {
+        "∞".to_string()
+    } else {
+        "-∞".to_string()
+    }
+}
@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use gloo_file::callbacks::{read_as_text, FileReader};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+use crate::session::SessionSnapshot;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    /// Whether there is a run to save; off while one solves in the worker.
+    pub can_save: bool,
+    pub on_save: Callback<()>,
+    pub on_restore: Callback<Rc<SessionSnapshot>>,
+}
+
+pub enum Msg {
+    LoadFile(web_sys::File),
+    Loaded(Result<String, String>),
+}
+
+/// "Save session" and "Restore session": the whole run as a JSON file, so
+/// a demo can be paused and picked up later where it stopped.
+pub struct SessionControls {
+    error: Option<String>,
+    /// Keeps a file read in flight alive.
+    _reader: Option<FileReader>,
+}
+
+impl Component for SessionControls {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            error: None,
+            _reader: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadFile(file) => {
+                let link = ctx.link().clone();
+                self._reader = Some(read_as_text(&file.into(), move |result| {
+                    link.send_message(Msg::Loaded(result.map_err(|e| e.to_string())))
+                }));
+                return false;
+            }
+            Msg::Loaded(result) => {
+                self._reader = None;
+                match result.and_then(|text| SessionSnapshot::from_json(&text)) {
+                    Ok(snapshot) => {
+                        log::info!(
+                            "Restoring a session with {} iterations",
+                            snapshot.iteration_count()
+                        );
+                        self.error = None;
+                        ctx.props().on_restore.emit(Rc::new(snapshot));
+                    }
+                    Err(e) => self.error = Some(format!("Could not restore the session: {}", e)),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let on_save = ctx.props().on_save.reform(|_| ());
+        html! {
+            <div class="session-controls">
+                <button disabled={!ctx.props().can_save} onclick={on_save}>
+                    { "Save session" }
+                </button>
+                <label class="session-restore">{ "Restore session: " }
+                    <input
+                        type="file"
+                        accept=".json,application/json"
+                        onchange={link.batch_callback(|e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let file = input.files().and_then(|files| files.get(0));
+                            // The same file can be restored again later.
+                            input.set_value("");
+                            file.map(Msg::LoadFile)
+                        })}
+                    />
+                </label>
+                {
+                    if let Some(error) = &self.error {
+                        html! { <span class="report-warning">{ error }</span> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        }
+    }
+}
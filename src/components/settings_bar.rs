@@ -0,0 +1,323 @@
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, SettingChange, SettingsContext};
+use super::notation_view::NotationPanel;
+use crate::interior::{LogVerbosity, DEFAULT_NORMAL_SHIFT, DEFAULT_SCALE_FLOOR};
+use crate::norm::Norm;
+use crate::palette::Palette;
+use crate::precision::Precision;
+use crate::schedule::AlphaSchedule;
+use crate::stopping::StoppingCriteria;
+
+pub enum Msg {
+    SettingsChanged(SettingsContext),
+}
+
+/// Log level, arithmetic, residual norm, whether P is shown, the simplex cross-check, regularization, stopping
+/// criteria, the α schedule, narration, card size, colors and notation, edited in place through the settings
+/// context.
+pub struct SettingsBar {
+    settings: SettingsContext,
+    _settings_handle: ContextHandle<SettingsContext>,
+}
+
+impl Component for SettingsBar {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (settings, settings_handle) = subscribe(ctx, Msg::SettingsChanged);
+        Self {
+            settings,
+            _settings_handle: settings_handle,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SettingsChanged(settings) => {
+                self.settings = settings;
+                true
+            }
+        }
+    }
+
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        let settings = &self.settings;
+        let change = |to_change: fn(bool) -> SettingChange| {
+            settings.on_change.reform(move |e: Event| {
+                let input: HtmlInputElement = e.target_unchecked_into();
+                to_change(input.checked())
+            })
+        };
+        html! {
+            <>
+                <label class="log-verbosity">{ "Log: " }
+                    <select onchange={settings.on_change.reform(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        SettingChange::LogVerbosity(match select.value().as_str() {
+                            "off" => LogVerbosity::Off,
+                            "full" => LogVerbosity::Full,
+                            _ => LogVerbosity::Summary,
+                        })
+                    })}>
+                        <option value="off" selected={settings.options.log_verbosity == LogVerbosity::Off}>{ "Off" }</option>
+                        <option value="summary" selected={settings.options.log_verbosity == LogVerbosity::Summary}>{ "Summary" }</option>
+                        <option value="full" selected={settings.options.log_verbosity == LogVerbosity::Full}>{ "Full matrices" }</option>
+                    </select>
+                </label>
+
+                <label class="log-verbosity" title="Double-double carries about 32 digits through the affine-scaling steps, for ill-conditioned models where f64 drifts.">{ "Arithmetic: " }
+                    <select onchange={settings.on_change.reform(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let k = select.value().parse::<usize>().unwrap_or(0);
+                        SettingChange::Precision(Precision::ALL[k.min(Precision::ALL.len() - 1)])
+                    })}>
+                        {
+                            for Precision::ALL.iter().enumerate().map(|(k, choice)| html! {
+                                <option value={k.to_string()} selected={*choice == settings.options.precision}>
+                                    { choice.label() }
+                                </option>
+                            })
+                        }
+                    </select>
+                </label>
+
+                <label class="log-verbosity" title="Norm of the residuals on the cards and in the report, and of the step-norm stopping test.">{ "Norm: " }
+                    <select onchange={settings.on_change.reform(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let k = select.value().parse::<usize>().unwrap_or(0);
+                        SettingChange::Norm(Norm::ALL[k.min(Norm::ALL.len() - 1)])
+                    })}>
+                        {
+                            for Norm::ALL.iter().enumerate().map(|(k, choice)| html! {
+                                <option value={k.to_string()} selected={*choice == settings.options.norm}>
+                                    { choice.label() }
+                                </option>
+                            })
+                        }
+                    </select>
+                </label>
+
+                <label class="log-verbosity" title="P is n×n; the steps never need it, so hiding it skips forming it.">
+                    <input
+                        type="checkbox"
+                        checked={!settings.options.hide_projection}
+                        onchange={change(|show| SettingChange::HideProjection(!show))}
+                    />
+                    { "Show P on cards" }
+                </label>
+
+                <label class="log-verbosity" title="After an interior-point optimum, the tableau simplex solves the same model and the report shows whether the objectives agree.">
+                    <input
+                        type="checkbox"
+                        checked={settings.options.cross_check}
+                        onchange={change(SettingChange::CrossCheck)}
+                    />
+                    { "Cross-check with simplex" }
+                </label>
+
+                <label class="log-verbosity">
+                    <input
+                        type="checkbox"
+                        checked={settings.narrate}
+                        onchange={change(SettingChange::Narration)}
+                    />
+                    { "Read steps aloud" }
+                </label>
+
+                <label class="log-verbosity">
+                    <input
+                        type="checkbox"
+                        checked={settings.compact}
+                        onchange={change(SettingChange::Compact)}
+                    />
+                    { "Compact cards" }
+                </label>
+
+                <label class="log-verbosity">{ "Colors: " }
+                    <select onchange={settings.on_change.reform(|e: Event| {
+                        let select: HtmlSelectElement = e.target_unchecked_into();
+                        let k = select.value().parse::<usize>().unwrap_or(0);
+                        SettingChange::Palette(Palette::ALL[k.min(Palette::ALL.len() - 1)])
+                    })}>
+                        {
+                            for Palette::ALL.iter().enumerate().map(|(k, choice)| html! {
+                                <option value={k.to_string()} selected={*choice == settings.palette}>
+                                    { choice.label() }
+                                </option>
+                            })
+                        }
+                    </select>
+                </label>
+
+                <details class="advanced-settings">
+                    <summary>{ "Advanced" }</summary>
+                    <label class="log-verbosity" title="Least entry of D = diag(x); keeps A~ from losing columns as x reaches the boundary.">{ "Floor of D: " }
+                        <input
+                            type="number"
+                            min="0"
+                            step="any"
+                            value={settings.options.scale_floor.to_string()}
+                            onchange={settings.on_change.reform(|e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                SettingChange::ScaleFloor(input.value().parse::<f64>().map_or(DEFAULT_SCALE_FLOOR, |val| val.max(0.0)))
+                            })}
+                        />
+                    </label>
+                    <label class="log-verbosity" title="Shift added to the diagonal of A~ A~^T, relative to its largest entry, when it will not factor otherwise (dependent rows).">{ "Normal-matrix shift: " }
+                        <input
+                            type="number"
+                            min="0"
+                            step="any"
+                            value={settings.options.normal_shift.to_string()}
+                            onchange={settings.on_change.reform(|e: Event| {
+                                let input: HtmlInputElement = e.target_unchecked_into();
+                                SettingChange::NormalShift(input.value().parse::<f64>().map_or(DEFAULT_NORMAL_SHIFT, |val| val.max(0.0)))
+                            })}
+                        />
+                    </label>
+                    { Self::render_stopping(settings) }
+                    { Self::render_schedule(settings) }
+                </details>
+
+                <NotationPanel
+                    notation={settings.notation.clone()}
+                    on_change={settings.on_change.reform(SettingChange::Notation)}
+                />
+            </>
+        }
+    }
+}
+
+impl SettingsBar {
+    /// One input per stopping criterion of affine scaling; clearing an input
+    /// turns its criterion off.
+    fn render_stopping(settings: &SettingsContext) -> Html {
+        let stopping = settings.options.stopping;
+        let input = |title: &'static str,
+                     label: &'static str,
+                     value: Option<String>,
+                     set: fn(&mut StoppingCriteria, &str)| {
+            html! {
+                <label class="log-verbosity" {title}>{ label }
+                    <input
+                        type="number"
+                        min="0"
+                        step="any"
+                        placeholder="off"
+                        value={value.unwrap_or_default()}
+                        onchange={settings.on_change.reform(move |e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let mut changed = stopping;
+                            set(&mut changed, input.value().trim());
+                            SettingChange::Stopping(changed)
+                        })}
+                    />
+                </label>
+            }
+        };
+        html! {
+            <fieldset class="stopping-criteria">
+                <legend>{ "Stop affine scaling when" }</legend>
+                {
+                    input(
+                        "‖Δx‖ of the next step, relative to 1 + ‖x‖, in the norm chosen above.",
+                        "step norm ≤ ",
+                        stopping.step_norm.map(|tol| tol.to_string()),
+                        |criteria, text| criteria.step_norm = parse_tolerance(text),
+                    )
+                }
+                {
+                    input(
+                        "Objective gain of the next step, relative to 1 + |Z|.",
+                        "objective change ≤ ",
+                        stopping.objective_change.map(|tol| tol.to_string()),
+                        |criteria, text| criteria.objective_change = parse_tolerance(text),
+                    )
+                }
+                {
+                    input(
+                        "Gap between Z and the bound of the dual estimate, relative to 1 + |Z|; checked once the estimate is dual feasible.",
+                        "duality gap ≤ ",
+                        stopping.duality_gap.map(|tol| tol.to_string()),
+                        |criteria, text| criteria.duality_gap = parse_tolerance(text),
+                    )
+                }
+                {
+                    input(
+                        "Steps before the run stops with the iteration limit.",
+                        "iterations reach ",
+                        stopping.max_iterations.map(|limit| limit.to_string()),
+                        |criteria, text| criteria.max_iterations = text.parse().ok(),
+                    )
+                }
+            </fieldset>
+        }
+    }
+
+    /// The factors of the α schedule step rule. Values that make no sense
+    /// as factors fall back to their defaults when applied.
+    fn render_schedule(settings: &SettingsContext) -> Html {
+        let schedule = settings.options.schedule;
+        let input = |title: &'static str,
+                     label: &'static str,
+                     value: f64,
+                     set: fn(&mut AlphaSchedule, f64)| {
+            html! {
+                <label class="log-verbosity" {title}>{ label }
+                    <input
+                        type="number"
+                        min="0"
+                        step="any"
+                        value={value.to_string()}
+                        onchange={settings.on_change.reform(move |e: Event| {
+                            let input: HtmlInputElement = e.target_unchecked_into();
+                            let mut changed = schedule;
+                            set(&mut changed, input.value().trim().parse().unwrap_or(f64::NAN));
+                            SettingChange::Schedule(changed)
+                        })}
+                    />
+                </label>
+            }
+        };
+        let alpha = settings.notation.step();
+        html! {
+            <fieldset class="stopping-criteria">
+                <legend>{ format!("{} schedule step rule", alpha) }</legend>
+                {
+                    input(
+                        "Factor the fraction grows by after a step that delivered the objective gain it promised; at least 1.",
+                        "grow by ",
+                        schedule.grow,
+                        |schedule, val| schedule.grow = val,
+                    )
+                }
+                {
+                    input(
+                        "Factor the fraction shrinks by after a rejected step, before retrying; between 0 and 1.",
+                        "shrink by ",
+                        schedule.shrink,
+                        |schedule, val| schedule.shrink = val,
+                    )
+                }
+                {
+                    input(
+                        "Largest fraction of the way to the boundary the schedule grows to; at most 1.",
+                        "at most ",
+                        schedule.max,
+                        |schedule, val| schedule.max = val,
+                    )
+                }
+            </fieldset>
+        }
+    }
+}
+
+/// A nonnegative tolerance, or `None` (criterion off) for blank or invalid
+/// text.
+fn parse_tolerance(text: &str) -> Option<f64> {
+    text.parse::<f64>().ok().filter(|tol| *tol >= 0.0)
+}
@@ -0,0 +1,121 @@
+use crate::simplex::SimplexIteration;
+use yew::prelude::*;
+
+use super::interior_view::InteriorPointView;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub iteration: usize,
+
+    #[prop_or_default]
+    pub iteration_data: Option<SimplexIteration>,
+    /// Small-screen layout: vectors inline, matrices folded.
+    #[prop_or_default]
+    pub compact: bool,
+}
+
+pub struct SimplexView;
+
+impl Component for SimplexView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let it = props.iteration_data.as_ref();
+        let compact = props.compact;
+
+        html! {
+            <div class={classes!("interior-point-view", compact.then_some("compact"))}>
+                <h3>{ format!("Tableau {}", props.iteration) }</h3>
+
+                {
+                    if let Some(iter) = it {
+                        html! {
+                            <>
+                                <div class="barrier-summary">
+                                    <span>{ format!("Z = {:.4}", iter.objective) }</span>
+                                    <span>{ Self::describe_pivot(iter) }</span>
+                                </div>
+                                <div class="matrix-container">
+                                    { InteriorPointView::matrix_card("Tableau [A | b] / reduced costs", Some(&iter.tableau), Self::render_tableau(iter), compact) }
+                                    { InteriorPointView::vector_card("Current vertex x", Some(&iter.current_x), compact) }
+                                </div>
+                            </>
+                        }
+                    } else {
+                        html! { <p>{"(Not available)"}</p> }
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl SimplexView {
+    fn describe_pivot(iter: &SimplexIteration) -> String {
+        match (iter.entering, iter.leaving_row) {
+            (Some(j), Some(i)) => format!(
+                "next pivot: x{} enters, x{} leaves",
+                j + 1,
+                iter.basis[i] + 1
+            ),
+            (Some(j), None) => format!("x{} can increase without bound", j + 1),
+            _ => "optimal: no negative reduced cost".to_string(),
+        }
+    }
+
+    fn render_tableau(iter: &SimplexIteration) -> Html {
+        let (rows, cols) = iter.tableau.shape();
+        let m = rows - 1;
+        html! {
+            <table class="matrix tableau">
+                <thead>
+                    <tr>
+                        <th>{"basis"}</th>
+                        { for (0..cols - 1).map(|j| html! { <th>{ format!("x{}", j + 1) }</th> }) }
+                        <th>{"rhs"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                {
+                    for (0..rows).map(|r| {
+                        let label = if r < m {
+                            format!("x{}", iter.basis[r] + 1)
+                        } else {
+                            "z".to_string()
+                        };
+                        html! {
+                            <tr>
+                                <th>{ label }</th>
+                                {
+                                    for (0..cols).map(|c| {
+                                        let in_row = iter.leaving_row == Some(r);
+                                        let in_col = iter.entering == Some(c);
+                                        let class = match (in_row, in_col) {
+                                            (true, true) => "pivot",
+                                            (true, false) | (false, true) => "pivot-line",
+                                            _ => "",
+                                        };
+                                        html! {
+                                            <td class={class}>{ format!("{:.4}", iter.tableau[(r, c)]) }</td>
+                                        }
+                                    })
+                                }
+                            </tr>
+                        }
+                    })
+                }
+                </tbody>
+            </table>
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use nalgebra::DMatrix;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+use super::input_form::FormState;
+use crate::equilibration::Scaling;
+use crate::interior::StepStrategy;
+use crate::model::{SolverMethod, StartOptions, ValidatedProblem};
+use crate::notation::Notation;
+use crate::transportation::TransportationProblem;
+
+/// Largest table the form offers; beyond this the grid stops fitting on screen.
+const MAX_SIDE: usize = 6;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub on_submit: Callback<ValidatedProblem>,
+    /// Symbols for the headers, from the notation settings.
+    #[prop_or_default]
+    pub notation: Notation,
+}
+
+/// Supply/demand/cost table entry for transportation problems. The table is
+/// converted to a standard-form LP on every edit, the same way `InputForm`
+/// validates the general model.
+pub struct TransportationForm {
+    sources: usize,
+    destinations: usize,
+    supply: Vec<f64>,
+    demand: Vec<f64>,
+    costs: Vec<Vec<f64>>,
+    solver_method: SolverMethod,
+    alpha: f64,
+    initial_mu: f64,
+    state: FormState,
+}
+
+pub enum Msg {
+    SetSources(usize),
+    SetDestinations(usize),
+    UpdateSupply(usize, f64),
+    UpdateDemand(usize, f64),
+    UpdateCost(usize, usize, f64),
+    SetSolverMethod(SolverMethod),
+    UpdateAlpha(f64),
+    UpdateInitialMu(f64),
+    Submit,
+}
+
+impl Component for TransportationForm {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let mut form = Self {
+            sources: 3,
+            destinations: 3,
+            supply: vec![20.0, 30.0, 25.0],
+            demand: vec![10.0, 25.0, 30.0],
+            costs: vec![
+                vec![8.0, 6.0, 10.0],
+                vec![9.0, 12.0, 13.0],
+                vec![14.0, 9.0, 16.0],
+            ],
+            solver_method: SolverMethod::Simplex,
+            alpha: 0.5,
+            initial_mu: 1.0,
+            state: FormState::Editing(vec![]),
+        };
+        form.revalidate();
+        form
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let is_edit = !matches!(msg, Msg::Submit);
+        let changed = match msg {
+            Msg::SetSources(count) => {
+                self.sources = count.clamp(1, MAX_SIDE);
+                self.resize();
+                true
+            }
+            Msg::SetDestinations(count) => {
+                self.destinations = count.clamp(1, MAX_SIDE);
+                self.resize();
+                true
+            }
+            Msg::UpdateSupply(i, val) => {
+                if i < self.supply.len() {
+                    self.supply[i] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::UpdateDemand(j, val) => {
+                if j < self.demand.len() {
+                    self.demand[j] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::UpdateCost(i, j, val) => {
+                if i < self.costs.len() && j < self.costs[i].len() {
+                    self.costs[i][j] = val;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::SetSolverMethod(method) => {
+                self.solver_method = method;
+                true
+            }
+            Msg::UpdateAlpha(a) => {
+                self.alpha = a.clamp(0.0, 1.0);
+                true
+            }
+            Msg::UpdateInitialMu(mu) => {
+                self.initial_mu = mu;
+                true
+            }
+            Msg::Submit => match &self.state {
+                FormState::Validated(problem) | FormState::Submitted(problem) => {
+                    let problem = problem.clone();
+                    ctx.props().on_submit.emit(problem.clone());
+                    self.state = FormState::Submitted(problem);
+                    true
+                }
+                FormState::Editing(_) => false,
+            },
+        };
+        if changed && is_edit {
+            self.revalidate();
+        }
+        changed
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        html! {
+            <div class="input-form transportation-form">
+                <div class="size-selectors">
+                    <div>
+                        <label>{"Sources: "}
+                            <input
+                                type="number"
+                                min="1"
+                                max={MAX_SIDE.to_string()}
+                                value={self.sources.to_string()}
+                                oninput={link.callback(|e: InputEvent| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    Msg::SetSources(input.value().parse().unwrap_or(1))
+                                })}
+                            />
+                        </label>
+                    </div>
+                    <div>
+                        <label>{"Destinations: "}
+                            <input
+                                type="number"
+                                min="1"
+                                max={MAX_SIDE.to_string()}
+                                value={self.destinations.to_string()}
+                                oninput={link.callback(|e: InputEvent| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    Msg::SetDestinations(input.value().parse().unwrap_or(1))
+                                })}
+                            />
+                        </label>
+                    </div>
+                </div>
+
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th>{ "Cost" }</th>
+                            { for (1..=self.destinations).map(|j| html! { <th>{ format!("D{}", j) }</th> }) }
+                            <th>{ "Supply" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for (0..self.sources).map(|i| html! {
+                                <tr>
+                                    <th>{ format!("S{}", i + 1) }</th>
+                                    {
+                                        for (0..self.destinations).map(|j| html! {
+                                            <td>
+                                                <input
+                                                    type="number"
+                                                    step="0.1"
+                                                    value={self.costs[i][j].to_string()}
+                                                    oninput={link.callback(move |e: InputEvent| {
+                                                        let input: HtmlInputElement = e.target_unchecked_into();
+                                                        Msg::UpdateCost(i, j, input.value().parse().unwrap_or(0.0))
+                                                    })}
+                                                />
+                                            </td>
+                                        })
+                                    }
+                                    <td>
+                                        <input
+                                            type="number"
+                                            min="0"
+                                            step="1"
+                                            value={self.supply[i].to_string()}
+                                            oninput={link.callback(move |e: InputEvent| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                Msg::UpdateSupply(i, input.value().parse().unwrap_or(0.0))
+                                            })}
+                                        />
+                                    </td>
+                                </tr>
+                            })
+                        }
+                        <tr>
+                            <th>{ "Demand" }</th>
+                            {
+                                for (0..self.destinations).map(|j| html! {
+                                    <td>
+                                        <input
+                                            type="number"
+                                            min="0"
+                                            step="1"
+                                            value={self.demand[j].to_string()}
+                                            oninput={link.callback(move |e: InputEvent| {
+                                                let input: HtmlInputElement = e.target_unchecked_into();
+                                                Msg::UpdateDemand(j, input.value().parse().unwrap_or(0.0))
+                                            })}
+                                        />
+                                    </td>
+                                })
+                            }
+                            <td>{ self.balance_note() }</td>
+                        </tr>
+                    </tbody>
+                </table>
+
+                <div class="solver-method">
+                    <label>{"Method: "}
+                        <select
+                            onchange={link.callback(|e: Event| {
+                                let select: HtmlSelectElement = e.target_unchecked_into();
+                                match select.value().as_str() {
+                                    "affine" => Msg::SetSolverMethod(SolverMethod::AffineScaling),
+                                    "barrier" => Msg::SetSolverMethod(SolverMethod::LogBarrier),
+                                    "revised" => Msg::SetSolverMethod(SolverMethod::RevisedSimplex),
+                                    _ => Msg::SetSolverMethod(SolverMethod::Simplex),
+                                }
+                            })}>
+                            <option value="simplex" selected={self.solver_method == SolverMethod::Simplex}>
+                                {"Primal Simplex (tableau)"}
+                            </option>
+                            <option value="revised" selected={self.solver_method == SolverMethod::RevisedSimplex}>
+                                {"Revised Simplex (LU)"}
+                            </option>
+                            <option value="affine" selected={self.solver_method == SolverMethod::AffineScaling}>
+                                {"Affine Scaling"}
+                            </option>
+                            <option value="barrier" selected={self.solver_method == SolverMethod::LogBarrier}>
+                                {"Log Barrier (central path)"}
+                            </option>
+                        </select>
+                    </label>
+                    {
+                        if self.solver_method == SolverMethod::LogBarrier {
+                            html! {
+                                <label>{ format!("Initial {}: ", ctx.props().notation.barrier()) }
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        step="0.1"
+                                        value={self.initial_mu.to_string()}
+                                        oninput={link.callback(|e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateInitialMu(input.value().parse().unwrap_or(1.0))
+                                        })}
+                                    />
+                                </label>
+                            }
+                        } else {
+                            html! {}
+                        }
+                    }
+                </div>
+
+                {
+                    if self.solver_method.is_interior() {
+                        html! {
+                            <div class="alpha-selector">
+                                <label>{ format!("Step Size ({}): ", ctx.props().notation.step()) }
+                                    <input
+                                        type="number"
+                                        min="0"
+                                        max="1"
+                                        step="0.1"
+                                        value={self.alpha.to_string()}
+                                        oninput={link.callback(|e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::UpdateAlpha(input.value().parse().unwrap_or(0.5))
+                                        })}
+                                    />
+                                </label>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                {
+                    if let FormState::Editing(errors) = &self.state {
+                        html! {
+                            <ul class="validation-errors">
+                                { for errors.iter().map(|e| html! { <li>{ e }</li> }) }
+                            </ul>
+                        }
+                    } else {
+                        html! {}
+                    }
+                }
+
+                <button
+                    disabled={matches!(self.state, FormState::Editing(_))}
+                    onclick={link.callback(|_| Msg::Submit)}
+                >
+                    { if matches!(self.state, FormState::Submitted(_)) { "Solve Again" } else { "Solve" } }
+                </button>
+            </div>
+        }
+    }
+}
+
+impl TransportationForm {
+    fn resize(&mut self) {
+        self.supply.resize(self.sources, 0.0);
+        self.demand.resize(self.destinations, 0.0);
+        self.costs
+            .resize(self.sources, vec![0.0; self.destinations]);
+        for row in self.costs.iter_mut() {
+            row.resize(self.destinations, 0.0);
+        }
+    }
+
+    fn revalidate(&mut self) {
+        self.state = match self.validate() {
+            Ok(problem) => FormState::Validated(problem),
+            Err(errors) => FormState::Editing(errors),
+        };
+    }
+
+    fn problem(&self) -> TransportationProblem {
+        TransportationProblem {
+            supply: self.supply.clone(),
+            demand: self.demand.clone(),
+            costs: DMatrix::from_fn(self.sources, self.destinations, |i, j| self.costs[i][j]),
+        }
+    }
+
+    fn validate(&self) -> Result<ValidatedProblem, Vec<String>> {
+        let mut errors = vec![];
+        if self.solver_method.is_interior() && self.alpha <= 0.0 {
+            errors.push("The step size α must be greater than 0.".to_string());
+        }
+        let problem = self.problem();
+        if let Err(mut table_errors) = problem.validate() {
+            errors.append(&mut table_errors);
+        }
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+        Ok(problem.to_validated(
+            self.solver_method,
+            StartOptions {
+                alpha: self.alpha,
+                mu: self.initial_mu,
+                find_start: false,
+                step_strategy: StepStrategy::default(),
+                scaling: Scaling::default(),
+            },
+        ))
+    }
+
+    /// Totals, and which side gets a dummy to balance them.
+    fn balance_note(&self) -> String {
+        let supply: f64 = self.supply.iter().sum();
+        let demand: f64 = self.demand.iter().sum();
+        let note = if supply > demand {
+            " (dummy destination added)"
+        } else if demand > supply {
+            " (dummy source added)"
+        } else {
+            ""
+        };
+        format!("{} / {}{}", supply, demand, note)
+    }
+}
@@ -0,0 +1,101 @@
+use nalgebra::DVector;
+use yew::prelude::*;
+
+use crate::transportation::TransportationLayout;
+
+/// Shipments below this are shown as empty cells.
+const FLOW_EPSILON: f64 = 1e-6;
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub layout: TransportationLayout,
+    pub solution: DVector<f64>,
+}
+
+/// The solved transportation plan as a flow matrix, with row and column
+/// totals against supply and demand and the cost of each route used.
+pub struct TransportationView;
+
+impl Component for TransportationView {
+    type Message = ();
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let layout = &props.layout;
+        let flows = layout.flows(&props.solution);
+        let (m, n) = flows.shape();
+
+        let source_name = |i: usize| {
+            if layout.dummy_source && i + 1 == m {
+                "Dummy".to_string()
+            } else {
+                format!("S{}", i + 1)
+            }
+        };
+        let destination_name = |j: usize| {
+            if layout.dummy_destination && j + 1 == n {
+                "Dummy".to_string()
+            } else {
+                format!("D{}", j + 1)
+            }
+        };
+
+        html! {
+            <div class="transportation-view">
+                <h3>{ "Flow Matrix" }</h3>
+                <table class="transportation-table">
+                    <thead>
+                        <tr>
+                            <th></th>
+                            { for (0..n).map(|j| html! { <th>{ destination_name(j) }</th> }) }
+                            <th>{ "Shipped / Supply" }</th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            for (0..m).map(|i| html! {
+                                <tr>
+                                    <th>{ source_name(i) }</th>
+                                    {
+                                        for (0..n).map(|j| {
+                                            let flow = flows[(i, j)];
+                                            if flow > FLOW_EPSILON {
+                                                html! {
+                                                    <td class="flow-used">
+                                                        { format!("{:.2}", flow) }
+                                                        <span class="flow-cost">{ format!(" @ {}", layout.costs[(i, j)]) }</span>
+                                                    </td>
+                                                }
+                                            } else {
+                                                html! { <td class="flow-empty">{ "·" }</td> }
+                                            }
+                                        })
+                                    }
+                                    <td>{ format!("{:.2} / {}", flows.row(i).sum(), layout.supply[i]) }</td>
+                                </tr>
+                            })
+                        }
+                        <tr>
+                            <th>{ "Received / Demand" }</th>
+                            { for (0..n).map(|j| html! { <td>{ format!("{:.2} / {}", flows.column(j).sum(), layout.demand[j]) }</td> }) }
+                            <td></td>
+                        </tr>
+                    </tbody>
+                </table>
+                <div class="barrier-summary">
+                    <span>{ format!("Total cost = {:.4}", layout.total_cost(&flows)) }</span>
+                    <span>{ format!("{} routes used", flows.iter().filter(|&&f| f > FLOW_EPSILON).count()) }</span>
+                </div>
+            </div>
+        }
+    }
+}
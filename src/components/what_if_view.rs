@@ -0,0 +1,219 @@
+use nalgebra::DVector;
+use web_sys::HtmlInputElement;
+use yew::context::ContextHandle;
+use yew::prelude::*;
+
+use super::context::{subscribe, ProblemContext};
+use crate::dual_simplex::{optimal_basis, resolve_rhs, supports_rhs_resolve, RhsResolve};
+use crate::model::ValidatedProblem;
+use crate::report::SolveStatus;
+
+/// Slider positions per unit of the range; fine enough to land on whole
+/// numbers for the usual textbook right-hand sides.
+const SLIDER_STEPS: f64 = 200.0;
+
+pub enum Msg {
+    ProblemChanged(ProblemContext),
+    SetRhs(usize, f64),
+    Reset,
+}
+
+/// Sliders over the right-hand sides of the submitted model. Every move is
+/// re-solved by the dual simplex from the optimal basis of the previous
+/// position, with its pivot count next to what a solve from scratch takes.
+pub struct WhatIfPanel {
+    problem: ProblemContext,
+    _problem_handle: ContextHandle<ProblemContext>,
+    /// Right-hand sides as submitted, the centers of the slider ranges.
+    base: Vec<f64>,
+    rhs: Vec<f64>,
+    /// Optimal basis of the last position, found on the first move.
+    basis: Option<Vec<usize>>,
+    result: Option<Result<RhsResolve, String>>,
+    /// Dual simplex pivots over every move since the last reset.
+    total_pivots: usize,
+}
+
+impl Component for WhatIfPanel {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (problem, problem_handle) = subscribe(ctx, Msg::ProblemChanged);
+        let mut panel = Self {
+            problem,
+            _problem_handle: problem_handle,
+            base: vec![],
+            rhs: vec![],
+            basis: None,
+            result: None,
+            total_pivots: 0,
+        };
+        panel.reset();
+        panel
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ProblemChanged(problem) => {
+                let new_model = problem.model != self.problem.model;
+                self.problem = problem;
+                if new_model {
+                    self.reset();
+                }
+            }
+            Msg::SetRhs(i, value) => {
+                let Some(model) = self.problem.model.clone() else {
+                    return false;
+                };
+                self.rhs[i] = value;
+                self.resolve(&model);
+            }
+            Msg::Reset => self.reset(),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let model = match &self.problem.model {
+            Some(model) if supports_rhs_resolve(model) => model,
+            Some(_) => {
+                return html! {
+                    <div class="race-panel what-if-panel">
+                        <h3>{ "What If: Right-Hand Sides" }</h3>
+                        <p class="race-hint">{ "The dual simplex re-solve needs a linear model without integer variables or presolve." }</p>
+                    </div>
+                };
+            }
+            None => {
+                return html! {
+                    <div class="race-panel what-if-panel">
+                        <h3>{ "What If: Right-Hand Sides" }</h3>
+                        <p class="race-hint">{ "Solve a problem first; the sliders vary its right-hand sides." }</p>
+                    </div>
+                };
+            }
+        };
+
+        html! {
+            <div class="race-panel what-if-panel">
+                <h3>{ "What If: Right-Hand Sides" }</h3>
+                <p class="race-hint">
+                    { "Changing b leaves every reduced cost as it was, so the last optimal basis stays dual feasible and the dual simplex only has to repair the basic variables that turned negative." }
+                </p>
+                <div class="what-if-sliders">
+                    {
+                        for self.rhs.iter().enumerate().map(|(i, &value)| {
+                            let (low, high) = slider_range(self.base[i]);
+                            html! {
+                                <label>
+                                    <span class="what-if-label">{ model.constraint_labels[i].clone() }</span>
+                                    <input
+                                        type="range"
+                                        min={low.to_string()}
+                                        max={high.to_string()}
+                                        step={((high - low) / SLIDER_STEPS).to_string()}
+                                        value={value.to_string()}
+                                        oninput={link.callback(move |e: InputEvent| {
+                                            let input: HtmlInputElement = e.target_unchecked_into();
+                                            Msg::SetRhs(i, input.value().parse().unwrap_or(value))
+                                        })}
+                                    />
+                                    <span class="what-if-value">{ format!("b = {:.4}", value) }</span>
+                                </label>
+                            }
+                        })
+                    }
+                </div>
+                <div class="race-controls">
+                    <button disabled={self.result.is_none()} onclick={link.callback(|_| Msg::Reset)}>
+                        { "Reset" }
+                    </button>
+                </div>
+                { self.render_result(model) }
+            </div>
+        }
+    }
+}
+
+impl WhatIfPanel {
+    /// Back to the submitted right-hand sides and a basis found afresh.
+    fn reset(&mut self) {
+        self.base = self.problem.model.as_ref().map_or(vec![], |model| {
+            model.original_rows().1.iter().copied().collect()
+        });
+        self.rhs = self.base.clone();
+        self.basis = None;
+        self.result = None;
+        self.total_pivots = 0;
+    }
+
+    fn resolve(&mut self, model: &ValidatedProblem) {
+        let basis = match self.basis.take() {
+            Some(basis) => basis,
+            None => match optimal_basis(model) {
+                Ok(basis) => basis,
+                Err(e) => {
+                    self.result = Some(Err(format!(
+                        "The submitted model has no optimal basis to start from ({}).",
+                        SolveStatus::from(&e).label()
+                    )));
+                    return;
+                }
+            },
+        };
+        match resolve_rhs(model, &basis, &DVector::from_vec(self.rhs.clone())) {
+            Ok(resolved) => {
+                self.total_pivots += resolved.pivots;
+                self.basis = Some(resolved.basis.clone());
+                self.result = Some(Ok(resolved));
+            }
+            Err(e) => {
+                // The old basis is still dual feasible for the next move.
+                self.basis = Some(basis);
+                self.result = Some(Err(format!(
+                    "{} for these right-hand sides.",
+                    SolveStatus::from(&e).label()
+                )));
+            }
+        }
+    }
+
+    fn render_result(&self, model: &ValidatedProblem) -> Html {
+        let resolved = match &self.result {
+            None => return html! {},
+            Some(Err(e)) => return html! { <p class="report-warning">{ e }</p> },
+            Some(Ok(resolved)) => resolved,
+        };
+        let n = model.augmentation.original_variables.min(resolved.x.len());
+        let cold = match resolved.cold_pivots {
+            Some(pivots) => format!("{} from scratch", pivots),
+            None => "no slack start from scratch".to_string(),
+        };
+        html! {
+            <>
+                <div class="barrier-summary">
+                    <span>{ format!("{} Z = {:.4}", if model.sense.is_maximize() { "max" } else { "min" }, resolved.objective) }</span>
+                    <span>{ format!("{} dual simplex pivots ({})", resolved.pivots, cold) }</span>
+                    <span>{ format!("{} pivots over all moves", self.total_pivots) }</span>
+                </div>
+                <p class="race-hint">
+                    {
+                        (0..n)
+                            .map(|j| format!("{} = {:.4}", model.variable_labels[j], resolved.x[j]))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    }
+                </p>
+            </>
+        }
+    }
+}
+
+/// The submitted value plus or minus its size, or one unit when that is
+/// smaller.
+fn slider_range(base: f64) -> (f64, f64) {
+    let spread = base.abs().max(1.0);
+    (base - spread, base + spread)
+}
@@ -0,0 +1,73 @@
+use nalgebra::DVector;
+
+use crate::norm::Norm;
+
+/// Errors below this are rounding in x*, not progress of the method.
+const ERROR_FLOOR: f64 = 1e-12;
+
+/// Ratios e_{k+1} / e_k the rate estimate looks at, from the end of the run.
+const RATE_WINDOW: usize = 5;
+
+/// A last ratio below this, with the ratios still shrinking, counts as
+/// superlinear.
+const SUPERLINEAR_RATIO: f64 = 0.1;
+
+/// ‖x_k - x*‖ for every iterate of `path`.
+pub fn error_curve(path: &[DVector<f64>], exact: &DVector<f64>, norm: Norm) -> Vec<f64> {
+    path.iter()
+        .filter(|x| x.len() == exact.len())
+        .map(|x| norm.of(&(x - exact)))
+        .collect()
+}
+
+/// How fast an error curve falls near its end.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConvergenceRate {
+    /// e_{k+1} ≈ rate · e_k: a straight line on a log axis.
+    Linear(f64),
+    /// The ratio e_{k+1} / e_k itself heads to zero: the curve bends down.
+    Superlinear,
+    /// Fewer than three errors above the floor, or an error that grew.
+    Undetermined,
+}
+
+impl ConvergenceRate {
+    pub fn describe(&self) -> String {
+        match self {
+            ConvergenceRate::Linear(rate) => {
+                format!("Linear convergence, error × {:.3} per iteration", rate)
+            }
+            ConvergenceRate::Superlinear => {
+                "Superlinear convergence, the error ratio itself shrinks to zero".to_string()
+            }
+            ConvergenceRate::Undetermined => {
+                "No steady decrease to estimate a rate from".to_string()
+            }
+        }
+    }
+}
+
+/// Estimates the rate from the last `RATE_WINDOW` ratios e_{k+1} / e_k of
+/// the errors above the floor: their geometric mean when they hold steady,
+/// superlinear when they keep shrinking to below `SUPERLINEAR_RATIO`.
+pub fn estimate_rate(errors: &[f64]) -> ConvergenceRate {
+    let significant: Vec<f64> = errors
+        .iter()
+        .copied()
+        .take_while(|&e| e > ERROR_FLOOR)
+        .collect();
+    if significant.len() < 3 {
+        return ConvergenceRate::Undetermined;
+    }
+    let ratios: Vec<f64> = significant.windows(2).map(|w| w[1] / w[0]).collect();
+    let recent = &ratios[ratios.len().saturating_sub(RATE_WINDOW)..];
+    if recent.iter().any(|&r| r >= 1.0) {
+        return ConvergenceRate::Undetermined;
+    }
+    let shrinking = recent.windows(2).all(|w| w[1] <= w[0]);
+    if shrinking && recent[recent.len() - 1] < SUPERLINEAR_RATIO {
+        return ConvergenceRate::Superlinear;
+    }
+    let mean = recent.iter().map(|r| r.ln()).sum::<f64>() / recent.len() as f64;
+    ConvergenceRate::Linear(mean.exp())
+}
@@ -0,0 +1,458 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::equilibration::Scaling;
+use crate::interior::StepStrategy;
+use crate::json::{self, Json};
+use crate::model::{
+    big_m_form, default_variable_labels, ConstraintSign, Sense, SolverMethod, StartOptions,
+    ValidatedProblem,
+};
+
+/// localStorage key of the last imported pack, so the sidebar survives a
+/// reload.
+#[cfg(feature = "web")]
+const PACK_KEY: &str = "ipsolver.course-pack";
+
+/// Prefix of the per-pack key listing the completed problem names.
+#[cfg(feature = "web")]
+const COMPLETED_KEY: &str = "ipsolver.course-pack.completed.";
+
+/// A set of named problems an instructor hands out as one JSON file:
+///
+/// ```text
+/// { "title": "Week 3",
+///   "problems": [
+///     { "name": "Wyndor", "notes": "Read off the shadow prices.",
+///       "sense": "max", "c": [3, 5],
+///       "a": [[1, 0], [0, 2], [3, 2]], "b": [4, 12, 18],
+///       "signs": ["<=", "<=", "<="],
+///       "groups": ["plant", "plant", "plant"],
+///       "settings": { "method": "simplex", "alpha": 0.5 } } ] }
+/// ```
+///
+/// A problem uses the field names of the minimal repro export, so an exported
+/// repro can be pasted into a pack as it is (`options` is read like
+/// `settings`).
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoursePack {
+    pub title: String,
+    pub problems: Vec<PackProblem>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackProblem {
+    /// Unique within the pack; completion is recorded by name.
+    pub name: String,
+    pub notes: String,
+    pub sense: Sense,
+    pub a: Vec<Vec<f64>>,
+    pub b: Vec<f64>,
+    pub c: Vec<f64>,
+    pub signs: Vec<ConstraintSign>,
+    /// Constraint group of each row; blank for ungrouped rows.
+    pub groups: Vec<String>,
+    pub initial: Option<Vec<f64>>,
+    /// Indices of integer variables.
+    pub integer: Vec<usize>,
+    pub q: Option<Vec<Vec<f64>>>,
+    pub settings: PackSettings,
+}
+
+/// Recommended solver settings; anything missing keeps the form's default.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackSettings {
+    pub method: Option<SolverMethod>,
+    pub alpha: Option<f64>,
+    pub mu: Option<f64>,
+    pub find_start: Option<bool>,
+    pub step_strategy: Option<StepStrategy>,
+    pub scaling: Option<Scaling>,
+}
+
+pub fn parse_course_pack(text: &str) -> Result<CoursePack, String> {
+    let root = json::parse(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    let title = root
+        .get("title")
+        .and_then(Json::as_str)
+        .unwrap_or("Course pack")
+        .to_string();
+    let problems = root
+        .get("problems")
+        .and_then(Json::as_array)
+        .ok_or("The pack needs a \"problems\" array")?;
+    if problems.is_empty() {
+        return Err("The pack has no problems".to_string());
+    }
+
+    let mut parsed: Vec<PackProblem> = Vec::with_capacity(problems.len());
+    for (k, problem) in problems.iter().enumerate() {
+        let problem = parse_problem(problem, k).map_err(|e| format!("Problem {}: {}", k + 1, e))?;
+        if parsed.iter().any(|other| other.name == problem.name) {
+            return Err(format!(
+                "Problem {}: the name \"{}\" is used twice",
+                k + 1,
+                problem.name
+            ));
+        }
+        parsed.push(problem);
+    }
+    Ok(CoursePack {
+        title,
+        problems: parsed,
+    })
+}
+
+/// One problem on its own in the format of a pack's problems, such as a
+/// minimal repro export.
+pub fn parse_pack_problem(text: &str) -> Result<PackProblem, String> {
+    let root = json::parse(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    parse_problem(&root, 0)
+}
+
+impl PackProblem {
+    /// The problem as the form would hand it to the pack's method: Big-M
+    /// augmented, with the form's defaults for settings the pack leaves
+    /// out. Bounds are not presolved.
+    pub fn to_validated(&self) -> ValidatedProblem {
+        let (m, n) = (self.b.len(), self.c.len());
+        let a = DMatrix::from_fn(m, n, |i, j| self.a[i][j]);
+        let initial = self.initial.clone().unwrap_or_else(|| vec![1.0; n]);
+        let form = big_m_form(
+            &a,
+            &DVector::from_column_slice(&self.b),
+            &DVector::from_column_slice(&self.c),
+            &self.signs,
+            &initial,
+            self.sense,
+        );
+        let method = self.settings.method.unwrap_or(SolverMethod::AffineScaling);
+        // Slack and artificial columns carry no quadratic cost.
+        let quadratic = self.q.as_ref().map(|q| {
+            let cols = form.a.ncols();
+            DMatrix::from_fn(
+                cols,
+                cols,
+                |i, j| {
+                    if i < n && j < n {
+                        q[i][j]
+                    } else {
+                        0.0
+                    }
+                },
+            )
+        });
+        ValidatedProblem {
+            method,
+            sense: self.sense,
+            variable_labels: default_variable_labels(&form.augmentation, form.a.ncols()),
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            quadratic,
+            initial: form.initial,
+            constraint_signs: self.signs.clone(),
+            integer_variables: (0..n).map(|j| self.integer.contains(&j)).collect(),
+            constraint_labels: (1..=m).map(|i| format!("C{}", i)).collect(),
+            constraint_groups: self
+                .groups
+                .iter()
+                .map(|group| Some(group.clone()).filter(|name| !name.is_empty()))
+                .collect(),
+            options: StartOptions {
+                alpha: self.settings.alpha.unwrap_or(0.5),
+                mu: self.settings.mu.unwrap_or(1.0),
+                find_start: self.settings.find_start.unwrap_or(true) && method.is_interior(),
+                step_strategy: self.settings.step_strategy.unwrap_or_default(),
+                scaling: self.settings.scaling.unwrap_or_default(),
+            },
+            is_augmented: false,
+            augmentation: form.augmentation,
+            transportation: None,
+            regression: None,
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        }
+    }
+}
+
+fn parse_problem(value: &Json, k: usize) -> Result<PackProblem, String> {
+    let c = numbers(value.get("c").ok_or("missing \"c\"")?, "c")?;
+    let n = c.len();
+    if n == 0 {
+        return Err("\"c\" is empty".to_string());
+    }
+    let a = rows(value.get("a").ok_or("missing \"a\"")?, "a", n)?;
+    let b = numbers(value.get("b").ok_or("missing \"b\"")?, "b")?;
+    if b.len() != a.len() {
+        return Err(format!(
+            "\"a\" has {} rows but \"b\" has {} entries",
+            a.len(),
+            b.len()
+        ));
+    }
+    let signs = match value.get("signs") {
+        Some(signs) => {
+            let signs = signs.as_array().ok_or("\"signs\" must be an array")?;
+            signs
+                .iter()
+                .map(|sign| {
+                    sign.as_str()
+                        .and_then(|symbol| match symbol {
+                            "≤" => Some(ConstraintSign::LessEqual),
+                            "≥" => Some(ConstraintSign::GreaterEqual),
+                            symbol => ConstraintSign::from_symbol(symbol),
+                        })
+                        .ok_or("\"signs\" entries must be \"<=\", \">=\" or \"=\"")
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => vec![ConstraintSign::LessEqual; b.len()],
+    };
+    if signs.len() != b.len() {
+        return Err(format!(
+            "\"signs\" has {} entries for {} constraints",
+            signs.len(),
+            b.len()
+        ));
+    }
+    let groups = match value.get("groups") {
+        Some(groups) => {
+            let groups = groups.as_array().ok_or("\"groups\" must be an array")?;
+            groups
+                .iter()
+                .map(|group| match group {
+                    Json::Null => Ok(String::new()),
+                    group => group
+                        .as_str()
+                        .map(|name| name.trim().to_string())
+                        .ok_or("\"groups\" entries must be names or null"),
+                })
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        None => vec![String::new(); b.len()],
+    };
+    if groups.len() != b.len() {
+        return Err(format!(
+            "\"groups\" has {} entries for {} constraints",
+            groups.len(),
+            b.len()
+        ));
+    }
+    let sense = match value.get("sense").and_then(Json::as_str) {
+        None | Some("max") | Some("maximize") => Sense::Maximize,
+        Some("min") | Some("minimize") => Sense::Minimize,
+        Some(other) => return Err(format!("unknown sense \"{}\"", other)),
+    };
+    let initial = match value.get("initial") {
+        Some(initial) => {
+            let initial = numbers(initial, "initial")?;
+            if initial.len() != n {
+                return Err(format!(
+                    "\"initial\" has {} entries for {} variables",
+                    initial.len(),
+                    n
+                ));
+            }
+            Some(initial)
+        }
+        None => None,
+    };
+    let integer = match value.get("integer") {
+        Some(integer) => numbers(integer, "integer")?
+            .into_iter()
+            .map(|j| {
+                if j >= 0.0 && j.fract() == 0.0 && (j as usize) < n {
+                    Ok(j as usize)
+                } else {
+                    Err(format!("\"integer\" index {} is not a variable", j))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        None => vec![],
+    };
+    let q = match value.get("q") {
+        Some(q) => {
+            let q = rows(q, "q", n)?;
+            if q.len() != n {
+                return Err(format!("\"q\" must be {0}×{0}", n));
+            }
+            Some(q)
+        }
+        None => None,
+    };
+    let settings = match value.get("settings").or_else(|| value.get("options")) {
+        Some(settings) => parse_settings(settings)?,
+        None => PackSettings::default(),
+    };
+    // Repros carry the method at the top level.
+    let method = match value.get("method") {
+        Some(method) => Some(parse_method(method)?),
+        None => settings.method,
+    };
+
+    Ok(PackProblem {
+        name: value
+            .get("name")
+            .and_then(Json::as_str)
+            .map_or_else(|| format!("Problem {}", k + 1), str::to_string),
+        notes: value
+            .get("notes")
+            .and_then(Json::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        sense,
+        a,
+        b,
+        c,
+        signs,
+        groups,
+        initial,
+        integer,
+        q,
+        settings: PackSettings { method, ..settings },
+    })
+}
+
+fn parse_settings(value: &Json) -> Result<PackSettings, String> {
+    let number = |key: &str| match value.get(key) {
+        Some(val) => val
+            .as_f64()
+            .map(Some)
+            .ok_or(format!("setting \"{}\" must be a number", key)),
+        None => Ok(None),
+    };
+    let alpha = number("alpha")?;
+    if alpha.is_some_and(|alpha| !(alpha > 0.0 && alpha <= 1.0)) {
+        return Err("setting \"alpha\" must be in (0, 1]".to_string());
+    }
+    let mu = number("mu")?;
+    if mu.is_some_and(|mu| mu <= 0.0 || mu.is_nan()) {
+        return Err("setting \"mu\" must be positive".to_string());
+    }
+    Ok(PackSettings {
+        method: value.get("method").map(parse_method).transpose()?,
+        alpha,
+        mu,
+        find_start: value.get("find_start").and_then(Json::as_bool),
+        step_strategy: match value.get("step") {
+            Some(step) => Some(
+                step.as_str()
+                    .and_then(StepStrategy::from_key)
+                    .ok_or("setting \"step\" must be \"short\", \"long\" or \"adaptive\"")?,
+            ),
+            None => None,
+        },
+        scaling: match value.get("scaling") {
+            Some(scaling) => Some(
+                scaling
+                    .as_str()
+                    .and_then(Scaling::from_key)
+                    .ok_or("setting \"scaling\" must be \"none\", \"geometric\" or \"ruiz\"")?,
+            ),
+            None => None,
+        },
+    })
+}
+
+fn parse_method(value: &Json) -> Result<SolverMethod, String> {
+    let name = value.as_str().ok_or("\"method\" must be a string")?;
+    method_from_name(name).ok_or(format!("unknown method \"{}\"", name))
+}
+
+/// Accepts the method labels the repro export writes as well as short names.
+pub fn method_from_name(name: &str) -> Option<SolverMethod> {
+    match name.to_ascii_lowercase().as_str() {
+        "affine" | "affine scaling" => Some(SolverMethod::AffineScaling),
+        "barrier" | "log barrier" => Some(SolverMethod::LogBarrier),
+        "simplex" | "primal simplex" => Some(SolverMethod::Simplex),
+        "revised" | "revised simplex" => Some(SolverMethod::RevisedSimplex),
+        "qp" | "quadratic barrier" => Some(SolverMethod::QuadraticBarrier),
+        "self-dual" | "self-dual embedding" => Some(SolverMethod::SelfDual),
+        _ => None,
+    }
+}
+
+fn numbers(value: &Json, field: &str) -> Result<Vec<f64>, String> {
+    value
+        .as_array()
+        .ok_or(format!("\"{}\" must be an array of numbers", field))?
+        .iter()
+        .map(|val| {
+            val.as_f64()
+                .filter(|val| val.is_finite())
+                .ok_or(format!("\"{}\" must contain only numbers", field))
+        })
+        .collect()
+}
+
+fn rows(value: &Json, field: &str, columns: usize) -> Result<Vec<Vec<f64>>, String> {
+    let rows = value
+        .as_array()
+        .ok_or(format!("\"{}\" must be an array of rows", field))?;
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let row = numbers(row, field)?;
+            if row.len() == columns {
+                Ok(row)
+            } else {
+                Err(format!(
+                    "row {} of \"{}\" has {} entries, expected {}",
+                    i + 1,
+                    field,
+                    row.len(),
+                    columns
+                ))
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "web")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// The pack JSON saved by the last import, if any.
+#[cfg(feature = "web")]
+pub fn load_saved_pack() -> Option<String> {
+    storage()?.get_item(PACK_KEY).ok()?
+}
+
+#[cfg(feature = "web")]
+pub fn save_pack(text: &str) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(PACK_KEY, text);
+    }
+}
+
+#[cfg(feature = "web")]
+pub fn forget_pack() {
+    if let Some(storage) = storage() {
+        let _ = storage.remove_item(PACK_KEY);
+    }
+}
+
+/// Names of the problems of `pack` solved in this browser.
+#[cfg(feature = "web")]
+pub fn load_completed(pack: &CoursePack) -> Vec<String> {
+    storage()
+        .and_then(|storage| {
+            storage
+                .get_item(&format!("{}{}", COMPLETED_KEY, pack.title))
+                .ok()?
+        })
+        .map(|names| names.lines().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "web")]
+pub fn save_completed(pack: &CoursePack, completed: &[String]) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(
+            &format!("{}{}", COMPLETED_KEY, pack.title),
+            &completed.join("\n"),
+        );
+    }
+}
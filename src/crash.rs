@@ -0,0 +1,86 @@
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    /// Message of the most recent panic, captured by the hook before unwinding.
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+    /// Repro JSON of the run that was stepping when a panic hit, for the
+    /// fallback screen when the panic cannot be caught.
+    static CONTEXT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Logs panics to the browser console with their message and location, and
+/// remembers the message for `guard`.
+///
+/// In wasm builds panics abort, so `guard` never gets to return; the hook
+/// then replaces the page with a recovery screen that still carries the repro
+/// set by `set_context`, instead of leaving a frozen app behind.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        #[cfg(feature = "web")]
+        console_error_panic_hook::hook(info);
+        let message = match info.payload().downcast_ref::<&str>() {
+            Some(text) => text.to_string(),
+            None => info
+                .payload()
+                .downcast_ref::<String>()
+                .cloned()
+                .unwrap_or_else(|| "unknown panic".to_string()),
+        };
+        let message = match info.location() {
+            Some(location) => format!("{} at {}:{}", message, location.file(), location.line()),
+            None => message,
+        };
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        show_fallback(&message);
+        LAST_PANIC.with(|last| *last.borrow_mut() = Some(message));
+    }));
+}
+
+/// Records what to offer as a repro if the next solver call panics.
+pub fn set_context(repro: Option<String>) {
+    CONTEXT.with(|context| *context.borrow_mut() = repro);
+}
+
+/// Runs `f`, turning a panic into `Err` with the panic message where the
+/// target supports unwinding.
+pub fn guard<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    LAST_PANIC.with(|last| last.borrow_mut().take());
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|_| {
+        LAST_PANIC
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_else(|| "unknown panic".to_string())
+    })
+}
+
+/// Writes a static recovery screen over the app; nothing in Rust runs after
+/// an aborting panic, so it cannot be a component.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn show_fallback(message: &str) {
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+    let Some(body) = document.body() else {
+        return;
+    };
+    let escape = |text: &str| {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    };
+    let repro = CONTEXT.with(|context| context.borrow().clone());
+    let repro_html = match repro {
+        Some(repro) => format!(
+            "<p>Copy this minimal repro into a bug report:</p><pre>{}</pre>",
+            escape(&repro)
+        ),
+        None => String::new(),
+    };
+    body.set_inner_html(&format!(
+        "<div class=\"error-message crash-screen\"><div class=\"error-icon\">💥</div>\
+         <h3>The solver crashed</h3><p>{}</p>{}\
+         <button onclick=\"location.reload()\">Reload</button></div>",
+        escape(message),
+        repro_html
+    ));
+}
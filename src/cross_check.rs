@@ -0,0 +1,104 @@
+use serde::Serialize;
+
+use crate::interior::{LogVerbosity, SolverOptions};
+use crate::model::ValidatedProblem;
+use crate::report::SolveStatus;
+use crate::simplex::SimplexProblem;
+use crate::solver::Solver;
+
+/// Largest gap between the two objectives, relative to the larger of 1 and
+/// the simplex objective, that still counts as agreement. Interior-point
+/// runs stop short of the vertex, so it is looser than the stopping tests.
+pub const CROSS_CHECK_TOLERANCE: f64 = 1e-4;
+
+/// Pivots the reference simplex gets before giving up.
+pub const CROSS_CHECK_PIVOT_LIMIT: usize = 500;
+
+/// An interior-point optimum compared with the one the tableau simplex
+/// finds on the same model. Objectives are in the sense of the report they
+/// are attached to.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossCheck {
+    Agrees {
+        reference: f64,
+        difference: f64,
+    },
+    /// The objectives differ by more than `CROSS_CHECK_TOLERANCE`: one of
+    /// the two runs lost accuracy, most likely the interior-point one.
+    Disagrees {
+        reference: f64,
+        difference: f64,
+    },
+    /// The simplex ended without an optimum to compare with, for the
+    /// reason given.
+    NoReference(String),
+}
+
+impl CrossCheck {
+    /// Solves `model` with the tableau simplex and compares its optimum
+    /// with `objective`, c^T x of the interior-point run in the solvers'
+    /// maximization form.
+    pub fn run(model: &ValidatedProblem, options: &SolverOptions, objective: f64) -> Self {
+        let mut options = options.clone();
+        // The reference run is not the one being studied; keep it out of
+        // the log.
+        options.log_verbosity = LogVerbosity::Off;
+        let mut simplex = match SimplexProblem::init(model, &options) {
+            Ok(simplex) => simplex,
+            Err(e) => {
+                return CrossCheck::NoReference(format!("the simplex could not start ({})", e))
+            }
+        };
+        for _ in 0..CROSS_CHECK_PIVOT_LIMIT {
+            if let Err(e) = simplex.step() {
+                return match SolveStatus::from(&e) {
+                    SolveStatus::Optimal => Self::compare(simplex.objective(), objective),
+                    status => CrossCheck::NoReference(format!(
+                        "the simplex ended {}",
+                        status.label().to_lowercase()
+                    )),
+                };
+            }
+        }
+        CrossCheck::NoReference(format!(
+            "the simplex had no optimum after {} pivots",
+            CROSS_CHECK_PIVOT_LIMIT
+        ))
+    }
+
+    fn compare(reference: f64, objective: f64) -> Self {
+        let difference = objective - reference;
+        if difference.abs() <= CROSS_CHECK_TOLERANCE * reference.abs().max(1.0) {
+            CrossCheck::Agrees {
+                reference,
+                difference,
+            }
+        } else {
+            CrossCheck::Disagrees {
+                reference,
+                difference,
+            }
+        }
+    }
+
+    /// Applies `f` to the simplex objective, for reports that flip the
+    /// sign of the objective or add presolve's offset to it.
+    pub fn map_reference(&mut self, f: impl Fn(f64) -> f64) {
+        match self {
+            CrossCheck::Agrees {
+                reference,
+                difference,
+            }
+            | CrossCheck::Disagrees {
+                reference,
+                difference,
+            } => {
+                let objective = f(*reference + *difference);
+                *reference = f(*reference);
+                *difference = objective - *reference;
+            }
+            CrossCheck::NoReference(_) => {}
+        }
+    }
+}
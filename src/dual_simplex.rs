@@ -0,0 +1,217 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::{LogVerbosity, SolverOptions};
+use crate::model::ValidatedProblem;
+use crate::revised_simplex::{perform_revised_simplex_iteration, RevisedSimplexProblem};
+use crate::simplex::{SimplexError, PIVOT_TOLERANCE};
+
+/// Pivots either simplex may take before a re-solve gives up; Dantzig
+/// pricing can cycle on degenerate models.
+const MAX_PIVOTS: usize = 500;
+
+/// Dual simplex for maximize c^T x subject to A x = b, x >= 0, started from
+/// `basis`, which must be dual feasible (no nonbasic reduced cost above
+/// zero) but may be primal infeasible. That is what an optimal basis
+/// becomes when only b changes: c_j - c_B^T B^-1 a_j does not involve b,
+/// while B^-1 b can turn negative.
+///
+/// Each pivot drops the most negative basic variable and brings in the
+/// column that keeps every reduced cost nonpositive. Returns the new
+/// optimal basis and the number of pivots it took.
+pub fn dual_simplex(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    c: &DVector<f64>,
+    mut basis: Vec<usize>,
+) -> Result<(Vec<usize>, usize), SimplexError> {
+    let feasibility = PIVOT_TOLERANCE * (1.0 + b.amax());
+    for pivots in 0..=MAX_PIVOTS {
+        let basis_matrix =
+            DMatrix::from_columns(&basis.iter().map(|&j| a.column(j)).collect::<Vec<_>>());
+        let lu = basis_matrix.clone().lu();
+        let lu_transpose = basis_matrix.transpose().lu();
+        let basic_values = lu.solve(b).ok_or(SimplexError::SingularBasis)?;
+        let basic_costs = DVector::from_iterator(basis.len(), basis.iter().map(|&j| c[j]));
+        let y = lu_transpose
+            .solve(&basic_costs)
+            .ok_or(SimplexError::SingularBasis)?;
+        let reduced = c - a.transpose() * &y;
+        if pivots == 0
+            && (0..a.ncols()).any(|j| !basis.contains(&j) && reduced[j] > PIVOT_TOLERANCE)
+        {
+            return Err(SimplexError::NotDualFeasible);
+        }
+
+        let Some(row) = basic_values
+            .iter()
+            .enumerate()
+            .filter(|(_, &value)| value < -feasibility)
+            .min_by(|(_, p), (_, q)| p.total_cmp(q))
+            .map(|(i, _)| i)
+        else {
+            return Ok((basis, pivots));
+        };
+
+        // Row `row` of B^-1 A, from B^T w = e_row.
+        let mut unit = DVector::zeros(basis.len());
+        unit[row] = 1.0;
+        let w = lu_transpose
+            .solve(&unit)
+            .ok_or(SimplexError::SingularBasis)?;
+        let pivot_row = a.transpose() * w;
+
+        // Dual ratio test: min d_j / alpha_j over alpha_j < 0, both <= 0.
+        let entering = (0..a.ncols())
+            .filter(|j| !basis.contains(j) && pivot_row[*j] < -PIVOT_TOLERANCE)
+            .map(|j| (j, reduced[j].min(0.0) / pivot_row[j]))
+            .min_by(|(_, p), (_, q)| p.total_cmp(q))
+            .map(|(j, _)| j)
+            .ok_or(SimplexError::Infeasible(row))?;
+        basis[row] = entering;
+    }
+    Err(SimplexError::PivotLimit)
+}
+
+/// The basic solution of `basis`: x_B = B^-1 b, every other entry 0.
+pub fn basic_solution(a: &DMatrix<f64>, b: &DVector<f64>, basis: &[usize]) -> Option<DVector<f64>> {
+    let basis_matrix =
+        DMatrix::from_columns(&basis.iter().map(|&j| a.column(j)).collect::<Vec<_>>());
+    let basic_values = basis_matrix.lu().solve(b)?;
+    let mut x = DVector::zeros(a.ncols());
+    for (i, &j) in basis.iter().enumerate() {
+        x[j] = basic_values[i];
+    }
+    Some(x)
+}
+
+/// Runs the revised simplex from the slack and artificial basis to the
+/// optimum, returning the optimal basis and the pivots it took.
+pub fn solve_from_scratch(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    c: &DVector<f64>,
+) -> Result<(Vec<usize>, usize), SimplexError> {
+    let mut problem = RevisedSimplexProblem::new(a, b, c)?;
+    problem.options = SolverOptions {
+        log_verbosity: LogVerbosity::Off,
+        ..SolverOptions::default()
+    };
+    for pivots in 0..=MAX_PIVOTS {
+        match perform_revised_simplex_iteration(&mut problem) {
+            Ok(_) => {}
+            Err(SimplexError::Optimal) => return Ok((problem.basis, pivots)),
+            Err(e) => return Err(e),
+        }
+    }
+    Err(SimplexError::PivotLimit)
+}
+
+/// An LP re-solved for a new right-hand side.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RhsResolve {
+    /// Optimal basis for the new b, the warm start of the next re-solve.
+    pub basis: Vec<usize>,
+    /// Optimum in the solver space.
+    pub x: DVector<f64>,
+    /// Optimal value in the user's sense.
+    pub objective: f64,
+    /// Dual simplex pivots from the previous optimal basis.
+    pub pivots: usize,
+    /// Revised simplex pivots the same b takes from the slack basis;
+    /// `None` when that start does not exist for the new b.
+    pub cold_pivots: Option<usize>,
+}
+
+/// Whether `model` can be re-solved by the dual simplex: a linear model
+/// without integer variables or presolve. Presolve solves in x' = x - l,
+/// where every row's b became b - A l, and A no longer has the fixed
+/// columns that shift needs.
+pub fn supports_rhs_resolve(model: &ValidatedProblem) -> bool {
+    model.quadratic.is_none() && !model.has_integer_variables() && model.presolve.is_none()
+}
+
+/// Optimal basis of `model` as submitted, the warm start for re-solves
+/// after right-hand-side edits.
+pub fn optimal_basis(model: &ValidatedProblem) -> Result<Vec<usize>, SimplexError> {
+    solve_from_scratch(&model.a, &model.b, &model.internal_c()).map(|(basis, _)| basis)
+}
+
+/// The solver-space b of `model` with user right-hand sides `rhs`, as
+/// `ValidatedProblem::original_rows` reports them. A keeps the columns it
+/// was built with, so the previous basis still indexes the same variables.
+pub fn solver_rhs(model: &ValidatedProblem, rhs: &DVector<f64>) -> DVector<f64> {
+    DVector::from_fn(rhs.len(), |i, _| {
+        let multiplier = model
+            .augmentation
+            .row_multipliers
+            .get(i)
+            .copied()
+            .unwrap_or(1.0);
+        multiplier * rhs[i]
+    })
+}
+
+/// Re-solves `model` for the user right-hand sides `rhs` with the dual
+/// simplex, starting from `basis`, an optimal basis for some other b.
+/// `SimplexError::Infeasible` when no x fits the new b, including when an
+/// artificial variable stays positive.
+pub fn resolve_rhs(
+    model: &ValidatedProblem,
+    basis: &[usize],
+    rhs: &DVector<f64>,
+) -> Result<RhsResolve, SimplexError> {
+    let b = solver_rhs(model, rhs);
+    let c = model.internal_c();
+    let (basis, pivots) = dual_simplex(&model.a, &b, &c, basis.to_vec())?;
+    let x = basic_solution(&model.a, &b, &basis).ok_or(SimplexError::SingularBasis)?;
+    if let Some(&row) = model.active_artificials(&x, 1e-6).first() {
+        return Err(SimplexError::Infeasible(row));
+    }
+    let cold_pivots = solve_from_scratch(&model.a, &b, &c)
+        .ok()
+        .map(|(_, pivots)| pivots);
+
+    Ok(RhsResolve {
+        objective: model.sense.sign() * c.dot(&x),
+        basis,
+        x,
+        pivots,
+        cold_pivots,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_models::wyndor;
+
+    #[test]
+    fn repairs_the_basis_after_a_rhs_change() {
+        let model = wyndor();
+        let basis = optimal_basis(&model).unwrap();
+
+        // 3 x1 + 2 x2 <= 24 is the top of its ranging interval: same basis.
+        let resolved =
+            resolve_rhs(&model, &basis, &DVector::from_vec(vec![4.0, 12.0, 24.0])).unwrap();
+        assert_eq!(resolved.pivots, 0);
+        assert!((resolved.objective - 42.0).abs() < 1e-9);
+
+        // Past it x1 <= 4 binds instead and the slack of row 3 enters.
+        let resolved =
+            resolve_rhs(&model, &basis, &DVector::from_vec(vec![4.0, 12.0, 30.0])).unwrap();
+        assert!(resolved.pivots > 0);
+        assert!((resolved.objective - 42.0).abs() < 1e-9);
+        assert!((resolved.x[0] - 4.0).abs() < 1e-9 && (resolved.x[1] - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reports_a_rhs_nothing_fits() {
+        let model = wyndor();
+        let basis = optimal_basis(&model).unwrap();
+        let result = resolve_rhs(&model, &basis, &DVector::from_vec(vec![-1.0, 12.0, 18.0]));
+        assert!(
+            matches!(result, Err(SimplexError::Infeasible(_))),
+            "{result:?}"
+        );
+    }
+}
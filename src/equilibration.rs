@@ -0,0 +1,264 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+/// Row and column scaling applied to A before affine scaling starts.
+/// Serialized by `key`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Scaling {
+    #[default]
+    #[serde(rename = "none")]
+    None,
+    /// Divides each row, then each column, by the geometric mean of its
+    /// largest and smallest nonzero magnitude, for a few passes. Evens out
+    /// models whose coefficients span many orders of magnitude.
+    #[serde(rename = "geometric")]
+    GeometricMean,
+    /// Ruiz's method: divides each row and column by the square root of its
+    /// largest magnitude until every row and column peaks near 1.
+    #[serde(rename = "ruiz")]
+    Ruiz,
+}
+
+impl Scaling {
+    pub const ALL: [Scaling; 3] = [Scaling::None, Scaling::GeometricMean, Scaling::Ruiz];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Scaling::None => "None",
+            Scaling::GeometricMean => "Geometric mean",
+            Scaling::Ruiz => "Ruiz",
+        }
+    }
+
+    /// Name used in repro exports and course packs.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Scaling::None => "none",
+            Scaling::GeometricMean => "geometric",
+            Scaling::Ruiz => "ruiz",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scaling| scaling.key() == key)
+    }
+}
+
+/// Passes of the geometric-mean scaling; later ones change little.
+const GEOMETRIC_PASSES: usize = 4;
+
+/// Most Ruiz passes, and how far from 1 the largest entry of a row or column
+/// may stay before they stop.
+const RUIZ_PASSES: usize = 20;
+const RUIZ_TOLERANCE: f64 = 0.5;
+
+/// A' = R A C with R = diag(row) and C = diag(column). The solver works on
+/// A' x' = R b with cost C c, where x = C x'; c'^T x' = c^T x, so objectives
+/// need no conversion. Every factor is a power of two, so scaling and
+/// unscaling are exact.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Equilibration {
+    pub method: Scaling,
+    #[serde(with = "crate::schema::vector")]
+    pub row: DVector<f64>,
+    #[serde(with = "crate::schema::vector")]
+    pub column: DVector<f64>,
+    /// Ratio of the largest to the smallest nonzero |a_ij| before scaling.
+    pub spread_before: f64,
+    /// The same ratio for A'.
+    pub spread_after: f64,
+}
+
+impl Equilibration {
+    /// Scaling factors for `a` under `method`; all ones for `Scaling::None`.
+    pub fn compute(a: &DMatrix<f64>, method: Scaling) -> Self {
+        let (m, n) = a.shape();
+        let mut row = DVector::from_element(m, 1.0);
+        let mut column = DVector::from_element(n, 1.0);
+        let scaled = |row: &DVector<f64>, column: &DVector<f64>| {
+            DMatrix::from_fn(m, n, |i, j| (row[i] * a[(i, j)] * column[j]).abs())
+        };
+
+        match method {
+            Scaling::None => {}
+            Scaling::GeometricMean => {
+                for _ in 0..GEOMETRIC_PASSES {
+                    let current = scaled(&row, &column);
+                    for (i, r) in current.row_iter().enumerate() {
+                        if let Some((small, large)) = nonzero_range(r.iter()) {
+                            row[i] /= (small * large).sqrt();
+                        }
+                    }
+                    let current = scaled(&row, &column);
+                    for (j, col) in current.column_iter().enumerate() {
+                        if let Some((small, large)) = nonzero_range(col.iter()) {
+                            column[j] /= (small * large).sqrt();
+                        }
+                    }
+                }
+            }
+            Scaling::Ruiz => {
+                for _ in 0..RUIZ_PASSES {
+                    let current = scaled(&row, &column);
+                    let row_peaks: Vec<f64> = current.row_iter().map(|r| r.amax()).collect();
+                    let column_peaks: Vec<f64> =
+                        current.column_iter().map(|col| col.amax()).collect();
+                    let settled = row_peaks
+                        .iter()
+                        .chain(column_peaks.iter())
+                        .all(|&peak| peak == 0.0 || (1.0 - peak).abs() <= RUIZ_TOLERANCE);
+                    if settled {
+                        break;
+                    }
+                    for (i, &peak) in row_peaks.iter().enumerate() {
+                        if peak > 0.0 {
+                            row[i] /= peak.sqrt();
+                        }
+                    }
+                    for (j, &peak) in column_peaks.iter().enumerate() {
+                        if peak > 0.0 {
+                            column[j] /= peak.sqrt();
+                        }
+                    }
+                }
+            }
+        }
+
+        let row = row.map(nearest_power_of_two);
+        let column = column.map(nearest_power_of_two);
+        let spread_after = spread(&scaled(&row, &column));
+        Self {
+            method,
+            row,
+            column,
+            spread_before: spread(a),
+            spread_after,
+        }
+    }
+
+    /// Whether every factor is 1, so scaling would change nothing.
+    pub fn is_identity(&self) -> bool {
+        self.row.iter().chain(self.column.iter()).all(|&f| f == 1.0)
+    }
+
+    /// R A C.
+    pub fn scale_matrix(&self, a: &DMatrix<f64>) -> DMatrix<f64> {
+        DMatrix::from_fn(a.nrows(), a.ncols(), |i, j| {
+            self.row[i] * a[(i, j)] * self.column[j]
+        })
+    }
+
+    /// R^-1 A' C^-1.
+    pub fn unscale_matrix(&self, a: &DMatrix<f64>) -> DMatrix<f64> {
+        DMatrix::from_fn(a.nrows(), a.ncols(), |i, j| {
+            a[(i, j)] / (self.row[i] * self.column[j])
+        })
+    }
+
+    /// R b.
+    pub fn scale_rhs(&self, b: &DVector<f64>) -> DVector<f64> {
+        b.component_mul(&self.row)
+    }
+
+    pub fn unscale_rhs(&self, b: &DVector<f64>) -> DVector<f64> {
+        b.component_div(&self.row)
+    }
+
+    /// C c.
+    pub fn scale_cost(&self, c: &DVector<f64>) -> DVector<f64> {
+        c.component_mul(&self.column)
+    }
+
+    pub fn unscale_cost(&self, c: &DVector<f64>) -> DVector<f64> {
+        c.component_div(&self.column)
+    }
+
+    /// y = R y', a dual estimate of the scaled model for the original
+    /// rows; A'^T y' = C A^T R y'.
+    pub fn unscale_duals(&self, y: &DVector<f64>) -> DVector<f64> {
+        y.component_mul(&self.row)
+    }
+
+    /// x' = C^-1 x, a point in the original units moved into the scaled model.
+    pub fn scale_x(&self, x: &DVector<f64>) -> DVector<f64> {
+        x.component_div(&self.column)
+    }
+
+    /// x = C x', a point or direction of the scaled model in original units.
+    pub fn unscale_x(&self, x: &DVector<f64>) -> DVector<f64> {
+        x.component_mul(&self.column)
+    }
+}
+
+/// Smallest and largest nonzero magnitude.
+fn nonzero_range<'a>(values: impl Iterator<Item = &'a f64>) -> Option<(f64, f64)> {
+    values
+        .map(|val| val.abs())
+        .filter(|&val| val > 0.0)
+        .fold(None, |range, val| match range {
+            None => Some((val, val)),
+            Some((small, large)) => Some((f64::min(small, val), f64::max(large, val))),
+        })
+}
+
+/// Largest over smallest nonzero |a_ij|; 1 for a zero matrix.
+pub fn spread(a: &DMatrix<f64>) -> f64 {
+    nonzero_range(a.iter()).map_or(1.0, |(small, large)| large / small)
+}
+
+fn nearest_power_of_two(factor: f64) -> f64 {
+    if factor.is_finite() && factor > 0.0 {
+        2f64.powi(factor.log2().round() as i32)
+    } else {
+        1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::{ConstraintSign, SolverMethod};
+    use crate::test_models::{assert_optimum, solve};
+
+    /// Wyndor's rows with the first one multiplied by 10^4.
+    fn lopsided() -> DMatrix<f64> {
+        DMatrix::from_row_slice(3, 2, &[1e4, 0.0, 0.0, 2.0, 3.0, 2.0])
+    }
+
+    #[test]
+    fn factors_are_exact_powers_of_two_that_even_out_a() {
+        let a = lopsided();
+        for method in [Scaling::GeometricMean, Scaling::Ruiz] {
+            let scaling = Equilibration::compute(&a, method);
+            assert!(
+                scaling.spread_after < scaling.spread_before / 10.0,
+                "{method:?}: {} -> {}",
+                scaling.spread_before,
+                scaling.spread_after
+            );
+            for &factor in scaling.row.iter().chain(scaling.column.iter()) {
+                assert_eq!(factor.log2().fract(), 0.0, "{method:?}: {factor}");
+            }
+            assert_eq!(scaling.unscale_matrix(&scaling.scale_matrix(&a)), a);
+        }
+        assert!(Equilibration::compute(&a, Scaling::None).is_identity());
+    }
+
+    #[test]
+    fn scaled_affine_run_reaches_the_unscaled_optimum() {
+        for scaling in [Scaling::GeometricMean, Scaling::Ruiz] {
+            let model = InteriorPointProblemBuilder::new()
+                .objective(&[3.0, 5.0])
+                .constraint(&[1e4, 0.0], ConstraintSign::LessEqual, 4e4)
+                .constraint(&[0.0, 2.0], ConstraintSign::LessEqual, 12.0)
+                .constraint(&[3.0, 2.0], ConstraintSign::LessEqual, 18.0)
+                .maximize()
+                .scaling(scaling)
+                .model()
+                .unwrap();
+            let report = solve(&model, SolverMethod::AffineScaling);
+            assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-3);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::model::ValidatedProblem;
+use crate::report::{SolveReport, SolveStatus};
+
+/// Relative slack below which a constraint counts as binding at the optimum;
+/// interior methods stop just short of the boundary.
+const BINDING_SLACK: f64 = 1e-4;
+
+/// Relative tolerance for numeric answers, loose enough for two decimals.
+const ANSWER_TOLERANCE: f64 = 5e-3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Answer {
+    Number(f64),
+    /// Options to pick from; any index in `correct` is accepted.
+    Choice {
+        options: Vec<String>,
+        correct: Vec<usize>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Response {
+    Number(f64),
+    Choice(usize),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Question {
+    pub prompt: String,
+    pub answer: Answer,
+    /// Shown once the student has answered, right or wrong.
+    pub explanation: String,
+}
+
+impl Question {
+    pub fn check(&self, response: &Response) -> bool {
+        match (&self.answer, response) {
+            (Answer::Number(expected), Response::Number(given)) => {
+                (given - expected).abs() <= ANSWER_TOLERANCE * (1.0 + expected.abs())
+            }
+            (Answer::Choice { correct, .. }, Response::Choice(k)) => correct.contains(k),
+            _ => false,
+        }
+    }
+}
+
+/// Short questions about the solved model, answered from the report: the
+/// optimal value, one variable, which constraint binds, and the shadow price
+/// and reduced cost when sensitivity analysis ran. Empty unless the solve
+/// reached an optimum.
+pub fn generate(model: &ValidatedProblem, report: &SolveReport) -> Vec<Question> {
+    if report.status != SolveStatus::Optimal {
+        return vec![];
+    }
+    let mut questions = vec![];
+    let sense = if model.sense.is_maximize() {
+        "maximum"
+    } else {
+        "minimum"
+    };
+    questions.push(Question {
+        prompt: format!("What is the {} value of the objective Z?", sense),
+        answer: Answer::Number(report.objective),
+        explanation: format!("The solver stopped at Z = {:.4}.", report.objective),
+    });
+
+    let variable_label = |j: usize| {
+        model
+            .variable_labels
+            .get(j)
+            .cloned()
+            .unwrap_or_else(|| format!("x{}", j + 1))
+    };
+    let original = model
+        .augmentation
+        .original_variables
+        .min(report.solution.len());
+    // The largest variable is rarely a guess of zero.
+    if let Some(j) =
+        (0..original).max_by(|&p, &q| report.solution[p].total_cmp(&report.solution[q]))
+    {
+        questions.push(Question {
+            prompt: format!("What is the value of {} at the optimum?", variable_label(j)),
+            answer: Answer::Number(report.solution[j]),
+            explanation: format!(
+                "{} = {:.4} in the reported solution.",
+                variable_label(j),
+                report.solution[j]
+            ),
+        });
+    }
+
+    let labels = &model.constraint_labels;
+    let binding: Vec<usize> = model
+        .constraint_slacks(&report.solution)
+        .iter()
+        .enumerate()
+        .filter(|(_, slack)| slack.is_none_or(|s| s.abs() < BINDING_SLACK))
+        .map(|(i, _)| i)
+        .collect();
+    if !binding.is_empty() && binding.len() < labels.len() {
+        let names: Vec<&str> = binding.iter().map(|&i| labels[i].as_str()).collect();
+        questions.push(Question {
+            prompt: "Which constraint is binding (holds with equality) at the optimum?".to_string(),
+            answer: Answer::Choice {
+                options: labels.clone(),
+                correct: binding.clone(),
+            },
+            explanation: format!(
+                "Binding: {}. Every other constraint has slack left.",
+                names.join(", ")
+            ),
+        });
+    }
+
+    if let Some(sensitivity) = &report.sensitivity {
+        // Ask about the binding row that is worth the most.
+        let priced = binding
+            .iter()
+            .copied()
+            .filter(|&i| i < sensitivity.constraints.len())
+            .max_by(|&p, &q| {
+                let price = |i: usize| sensitivity.constraints[i].shadow_price.abs();
+                price(p).total_cmp(&price(q))
+            });
+        if let Some(i) = priced {
+            let price = sensitivity.constraints[i].shadow_price;
+            questions.push(Question {
+                prompt: format!(
+                    "What is the shadow price of {}, the change in Z per unit increase of its right-hand side?",
+                    labels[i]
+                ),
+                answer: Answer::Number(price),
+                explanation: format!(
+                    "Raising the right-hand side of {} by one changes Z by {:.4}, as long as it stays within its allowable range.",
+                    labels[i], price
+                ),
+            });
+        }
+        let nonbasic = sensitivity
+            .variables
+            .iter()
+            .position(|variable| !variable.basic);
+        if let Some(j) = nonbasic {
+            let cost = sensitivity.variables[j].reduced_cost;
+            questions.push(Question {
+                prompt: format!(
+                    "{} is zero at the optimum. By how much does Z change if {} is forced up to 1?",
+                    variable_label(j),
+                    variable_label(j)
+                ),
+                answer: Answer::Number(cost),
+                explanation: format!(
+                    "The reduced cost of {} is {:.4}; each unit forced into the solution changes Z by that much.",
+                    variable_label(j),
+                    cost
+                ),
+            });
+        }
+    }
+    questions
+}
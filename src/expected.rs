@@ -0,0 +1,122 @@
+use nalgebra::DVector;
+
+/// Iterates from a hand calculation or a textbook, one x per iteration.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExpectedIterations {
+    /// Iteration number and the x expected after it, in file order.
+    pub rows: Vec<(usize, Vec<f64>)>,
+}
+
+/// How far the app's iterate is from the expected one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deviation {
+    pub iteration: usize,
+    pub expected: Vec<f64>,
+    /// The app's x over the same columns; `None` when the run has not
+    /// reached this iteration.
+    pub actual: Option<Vec<f64>>,
+    /// Largest |actual - expected| and the zero-based column it is in.
+    pub worst: Option<(usize, f64)>,
+}
+
+impl Deviation {
+    pub fn within(&self, tolerance: f64) -> bool {
+        self.worst.is_some_and(|(_, gap)| gap <= tolerance)
+    }
+}
+
+/// Reads comma-, semicolon- or tab-separated rows of x values. Blank lines
+/// and lines starting with `#` are skipped. A first line that is not all
+/// numbers is a header; when its first cell is "iteration", "iter" or "k"
+/// the first column holds iteration numbers, otherwise rows are iterations
+/// 0, 1, 2, … in order.
+pub fn parse_expected_csv(text: &str) -> Result<ExpectedIterations, String> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(k, line)| (k + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .peekable();
+
+    let mut numbered = false;
+    if let Some(&(_, first)) = lines.peek() {
+        let cells = split_cells(first);
+        if cells.iter().any(|cell| cell.parse::<f64>().is_err()) {
+            numbered = matches!(cells[0].to_lowercase().as_str(), "iteration" | "iter" | "k");
+            lines.next();
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (line_number, line) in lines {
+        let values = split_cells(line)
+            .iter()
+            .map(|cell| {
+                cell.parse::<f64>()
+                    .map_err(|_| format!("line {}: \"{}\" is not a number", line_number, cell))
+            })
+            .collect::<Result<Vec<f64>, String>>()?;
+        let (iteration, x) = if numbered {
+            let Some((&k, x)) = values.split_first() else {
+                continue;
+            };
+            if k < 0.0 || k.fract() != 0.0 {
+                return Err(format!(
+                    "line {}: iteration \"{}\" is not a whole number",
+                    line_number, k
+                ));
+            }
+            (k as usize, x.to_vec())
+        } else {
+            (rows.len(), values)
+        };
+        if x.is_empty() {
+            return Err(format!("line {}: no x values", line_number));
+        }
+        rows.push((iteration, x));
+    }
+
+    if rows.is_empty() {
+        return Err("no rows of x values".to_string());
+    }
+    Ok(ExpectedIterations { rows })
+}
+
+fn split_cells(line: &str) -> Vec<&str> {
+    line.split([',', ';', '\t']).map(str::trim).collect()
+}
+
+impl ExpectedIterations {
+    /// Compares each expected row with `path[iteration]`, the app's x after
+    /// that iteration, over the columns the row gives. A row may list only
+    /// the decision variables and leave out the slacks after them.
+    pub fn compare(&self, path: &[DVector<f64>]) -> Vec<Deviation> {
+        self.rows
+            .iter()
+            .map(|(iteration, expected)| {
+                let actual: Option<Vec<f64>> = path
+                    .get(*iteration)
+                    .map(|x| x.iter().take(expected.len()).copied().collect());
+                let worst = actual.as_ref().map(|actual| {
+                    expected
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &value)| {
+                            let gap = actual.get(j).map_or(f64::INFINITY, |&x| (x - value).abs());
+                            (j, gap)
+                        })
+                        .fold(
+                            (0, 0.0),
+                            |worst, entry| if entry.1 > worst.1 { entry } else { worst },
+                        )
+                });
+                Deviation {
+                    iteration: *iteration,
+                    expected: expected.clone(),
+                    actual,
+                    worst,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,806 @@
+use std::collections::BTreeMap;
+
+#[cfg(feature = "web")]
+use gloo_file::{Blob, ObjectUrl};
+use nalgebra::{DMatrix, DVector};
+#[cfg(feature = "web")]
+use wasm_bindgen::JsCast;
+
+use crate::interior::SolverOptions;
+use crate::model::{ConstraintSign, ValidatedProblem};
+use crate::plot::{clip_line, path_bounds};
+use crate::pretty;
+use crate::report::SolveReport;
+use crate::repro::{export_repro, list, number, object, string, ReproIteration};
+use crate::schema;
+use crate::zip::stored_zip;
+
+/// Name the browser saves the bundle of every format under.
+pub const BUNDLE_FILE: &str = "ipsolver-bundle.zip";
+
+const CHART_WIDTH: f64 = 480.0;
+const CHART_HEIGHT: f64 = 240.0;
+const PATH_SIZE: f64 = 360.0;
+const PAD: f64 = 40.0;
+
+/// Everything an export is written from: the model, the options it ran
+/// with and the run so far.
+pub struct ExportRun<'a> {
+    pub model: &'a ValidatedProblem,
+    pub options: &'a SolverOptions,
+    /// `None` while the run has not finished.
+    pub report: Option<&'a SolveReport>,
+    /// Standard-form x after each step, oldest first.
+    pub path: &'a [DVector<f64>],
+    pub notes: &'a BTreeMap<usize, String>,
+}
+
+/// One file format the run can be exported in.
+pub trait Exporter {
+    /// Entry in the export menu.
+    fn label(&self) -> &'static str;
+    /// Name of the download, and of the file in the bundle.
+    fn file_name(&self) -> &'static str;
+    fn mime_type(&self) -> &'static str;
+    /// The file for `run`, or `None` when the format has nothing to show
+    /// for it.
+    fn export(&self, run: &ExportRun) -> Option<String>;
+}
+
+/// Every format, in menu order. The export menu and the bundle both read
+/// this list, so a new format is an `Exporter` and one more entry here.
+pub const EXPORTERS: &[&dyn Exporter] = &[
+    &JsonSession,
+    &IterationsCsv,
+    &LatexReport,
+    &MarkdownReport,
+    &NumpyScript,
+    &ObjectiveSvg,
+    &PathSvg,
+];
+
+/// A zip of every format that has something to show for `run`, for
+/// handing in the whole run at once.
+pub fn bundle(run: &ExportRun) -> Vec<u8> {
+    let files: Vec<(String, Vec<u8>)> = EXPORTERS
+        .iter()
+        .filter_map(|exporter| {
+            let text = exporter.export(run)?;
+            Some((exporter.file_name().to_string(), text.into_bytes()))
+        })
+        .collect();
+    stored_zip(&files)
+}
+
+/// Hands `bytes` to the browser as a download named `file_name`. The URL
+/// must outlive the click, so the caller keeps it until the next download.
+#[cfg(feature = "web")]
+pub fn download(bytes: &[u8], file_name: &str, mime_type: &str) -> Option<ObjectUrl> {
+    let document = web_sys::window()?.document()?;
+    let url = ObjectUrl::from(Blob::new_with_options(bytes, Some(mime_type)));
+    let link = document.create_element("a").ok()?;
+    link.set_attribute("href", &url).ok()?;
+    link.set_attribute("download", file_name).ok()?;
+    link.dyn_into::<web_sys::HtmlElement>().ok()?.click();
+    Some(url)
+}
+
+pub struct JsonSession;
+
+impl Exporter for JsonSession {
+    fn label(&self) -> &'static str {
+        "Session (JSON)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "session.json"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/json"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(session_json(run))
+    }
+}
+
+pub struct IterationsCsv;
+
+impl Exporter for IterationsCsv {
+    fn label(&self) -> &'static str {
+        "Iterations (CSV)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "iterations.csv"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "text/csv"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(iterations_csv(run))
+    }
+}
+
+pub struct LatexReport;
+
+impl Exporter for LatexReport {
+    fn label(&self) -> &'static str {
+        "Report (LaTeX)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "report.tex"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "application/x-tex"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(latex_report(run))
+    }
+}
+
+pub struct MarkdownReport;
+
+impl Exporter for MarkdownReport {
+    fn label(&self) -> &'static str {
+        "Report (Markdown)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "report.md"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "text/markdown"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(markdown_report(run))
+    }
+}
+
+pub struct NumpyScript;
+
+impl Exporter for NumpyScript {
+    fn label(&self) -> &'static str {
+        "Arrays (NumPy script)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "iterations.py"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "text/x-python"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(numpy_script(run))
+    }
+}
+
+pub struct ObjectiveSvg;
+
+impl Exporter for ObjectiveSvg {
+    fn label(&self) -> &'static str {
+        "Objective plot (SVG)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "objective.svg"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/svg+xml"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        Some(objective_svg(run))
+    }
+}
+
+/// Only for models with two decision variables.
+pub struct PathSvg;
+
+impl Exporter for PathSvg {
+    fn label(&self) -> &'static str {
+        "Iterate path (SVG)"
+    }
+
+    fn file_name(&self) -> &'static str {
+        "path.svg"
+    }
+
+    fn mime_type(&self) -> &'static str {
+        "image/svg+xml"
+    }
+
+    fn export(&self, run: &ExportRun) -> Option<String> {
+        path_svg(run)
+    }
+}
+
+/// Decision variables shown in the CSV, report and plots.
+fn user_columns(model: &ValidatedProblem) -> usize {
+    model.augmentation.original_variables.min(model.a.ncols())
+}
+
+fn variable_label(model: &ValidatedProblem, j: usize) -> String {
+    model
+        .variable_labels
+        .get(j)
+        .cloned()
+        .unwrap_or_else(|| format!("x{}", j + 1))
+}
+
+/// c^T x in the model's own sense, as the history search reports it.
+fn objective(model: &ValidatedProblem, x: &DVector<f64>) -> f64 {
+    if x.len() == model.c.len() {
+        model.c.dot(x)
+    } else {
+        f64::NAN
+    }
+}
+
+/// The repro export with the labels, the report and every iterate added
+/// back; numbers keep the repro's rounding.
+fn session_json(run: &ExportRun) -> String {
+    let model = run.model;
+    let repro = export_repro(
+        model,
+        run.options,
+        &ReproIteration {
+            index: run.path.len(),
+            status: run.report.map(|report| &report.status),
+            x: run.path.last(),
+            notes: run.notes,
+        },
+    );
+    let labels = |names: &[String]| {
+        format!(
+            "[{}]",
+            names
+                .iter()
+                .map(|name| string(name))
+                .collect::<Vec<_>>()
+                .join(",")
+        )
+    };
+    let report = run.report.map_or("null".to_string(), |report| {
+        serde_json::to_string(report).expect("a report serializes to JSON")
+    });
+    let iterations: Vec<String> = run
+        .path
+        .iter()
+        .map(|x| {
+            object(&[
+                ("objective", number(objective(model, x))),
+                ("x", list(x.iter().copied())),
+            ])
+        })
+        .collect();
+    object(&[
+        ("model", repro),
+        ("variables", labels(&model.variable_labels)),
+        ("constraints", labels(&model.constraint_labels)),
+        ("report", report),
+        ("iterations", format!("[{}]", iterations.join(","))),
+        ("solver_options", schema::to_json("options", run.options)),
+    ])
+}
+
+/// Quotes a CSV cell when it holds a separator, quote or line break.
+fn csv_cell(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// One row per iteration: its number, the decision variables and Z.
+fn iterations_csv(run: &ExportRun) -> String {
+    let n = user_columns(run.model);
+    let mut header = vec!["iteration".to_string()];
+    header.extend((0..n).map(|j| csv_cell(&variable_label(run.model, j))));
+    header.push("objective".to_string());
+    let mut lines = vec![header.join(",")];
+    for (k, x) in run.path.iter().enumerate() {
+        let mut row = vec![k.to_string()];
+        row.extend(x.iter().take(n).map(|val| val.to_string()));
+        row.push(objective(run.model, x).to_string());
+        lines.push(row.join(","));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Escapes the characters LaTeX treats as commands in running text.
+fn latex_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '\\' => out.push_str("\\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                out.push('\\');
+                out.push(ch);
+            }
+            '~' => out.push_str("\\textasciitilde{}"),
+            '^' => out.push_str("\\textasciicircum{}"),
+            ch => out.push(ch),
+        }
+    }
+    out
+}
+
+/// a_1 x_1 + a_2 x_2 + ..., skipping zero coefficients, with x_j written
+/// by `variable` for j from 1.
+fn linear_expression(
+    coefficients: impl Iterator<Item = f64>,
+    variable: impl Fn(usize) -> String,
+) -> String {
+    let terms: Vec<(f64, usize)> = coefficients
+        .enumerate()
+        .filter(|(_, a)| *a != 0.0)
+        .map(|(j, a)| (a, j + 1))
+        .collect();
+    if terms.is_empty() {
+        return "0".to_string();
+    }
+    terms
+        .iter()
+        .enumerate()
+        .map(|(k, &(a, j))| {
+            let sign = match (k, a < 0.0) {
+                (0, true) => "-",
+                (0, false) => "",
+                (_, true) => " - ",
+                (_, false) => " + ",
+            };
+            let magnitude = if a.abs() == 1.0 {
+                String::new()
+            } else {
+                format!("{} ", pretty::entry(a.abs()))
+            };
+            format!("{}{}{}", sign, magnitude, variable(j))
+        })
+        .collect()
+}
+
+fn latex_variable(j: usize) -> String {
+    format!("x_{{{}}}", j)
+}
+
+/// `<=`, `>=` or `=` for row `i`, as the user entered it.
+fn constraint_sign(model: &ValidatedProblem, i: usize) -> ConstraintSign {
+    model
+        .constraint_signs
+        .get(i)
+        .copied()
+        .unwrap_or(ConstraintSign::LessEqual)
+}
+
+/// A standalone article: the model, the result and the iteration table.
+fn latex_report(run: &ExportRun) -> String {
+    let model = run.model;
+    let n = user_columns(model);
+    let (a, b) = model.original_rows();
+    let mut tex = vec![
+        "\\documentclass{article}".to_string(),
+        "\\usepackage{amsmath}".to_string(),
+        "\\begin{document}".to_string(),
+        format!("\\section*{{{}}}", latex_text(model.method.label())),
+        "\\subsection*{Model}".to_string(),
+        "\\begin{align*}".to_string(),
+        format!(
+            "\\{}\\quad & {} \\\\",
+            if model.sense.is_maximize() {
+                "max"
+            } else {
+                "min"
+            },
+            linear_expression(model.c.iter().take(n).copied(), latex_variable)
+        ),
+    ];
+    for i in 0..a.nrows() {
+        let sign = match constraint_sign(model, i) {
+            ConstraintSign::LessEqual => "\\le",
+            ConstraintSign::GreaterEqual => "\\ge",
+            ConstraintSign::Equal => "=",
+        };
+        let lead = if i == 0 { "\\text{s.t.}\\quad " } else { "" };
+        tex.push(format!(
+            "{}& {} {} {} \\\\",
+            lead,
+            linear_expression(a.row(i).iter().copied(), latex_variable),
+            sign,
+            pretty::entry(b[i])
+        ));
+    }
+    tex.push("& x \\ge 0".to_string());
+    tex.push("\\end{align*}".to_string());
+    tex.push(format!(
+        "Variables: {}.",
+        (0..n)
+            .map(|j| format!(
+                "$x_{{{}}}$ = {}",
+                j + 1,
+                latex_text(&variable_label(model, j))
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    tex.push("\\subsection*{Result}".to_string());
+    match run.report {
+        Some(report) => {
+            let solution = report
+                .original_solution
+                .as_ref()
+                .unwrap_or(&report.solution);
+            tex.push(format!(
+                "{} after {} iterations, $Z = {}$.",
+                latex_text(report.status.label()),
+                report.iterations,
+                pretty::entry(report.objective)
+            ));
+            tex.push(format!(
+                "\\[ {} \\]",
+                (0..n.min(solution.len()))
+                    .map(|j| format!("x_{{{}}} = {}", j + 1, pretty::entry(solution[j])))
+                    .collect::<Vec<_>>()
+                    .join(",\\quad ")
+            ));
+        }
+        None => tex.push("The run had not finished when this report was written.".to_string()),
+    }
+
+    tex.push("\\subsection*{Iterations}".to_string());
+    tex.push(format!("\\begin{{tabular}}{{r{}r}}", "r".repeat(n)));
+    tex.push(format!(
+        "$k$ & {} & $Z$ \\\\ \\hline",
+        (1..=n)
+            .map(|j| format!("$x_{{{}}}$", j))
+            .collect::<Vec<_>>()
+            .join(" & ")
+    ));
+    for (k, x) in run.path.iter().enumerate() {
+        tex.push(format!(
+            "{} & {} & {} \\\\",
+            k,
+            x.iter()
+                .take(n)
+                .map(|&val| pretty::entry(val))
+                .collect::<Vec<_>>()
+                .join(" & "),
+            pretty::entry(objective(model, x))
+        ));
+    }
+    tex.push("\\end{tabular}".to_string());
+    tex.push("\\end{document}".to_string());
+    tex.join("\n") + "\n"
+}
+
+/// Escapes the characters a Markdown table or emphasis would swallow.
+fn markdown_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for ch in text.chars() {
+        if matches!(ch, '\\' | '|' | '*' | '_' | '`') {
+            out.push('\\');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+fn markdown_row(cells: impl IntoIterator<Item = String>) -> String {
+    format!("| {} |", cells.into_iter().collect::<Vec<_>>().join(" | "))
+}
+
+/// The LaTeX report as Markdown, for course forums and READMEs.
+fn markdown_report(run: &ExportRun) -> String {
+    let model = run.model;
+    let n = user_columns(model);
+    let (a, b) = model.original_rows();
+    let plain_variable = |j: usize| format!("x{}", j);
+    let mut md = vec![
+        format!("# {}", markdown_text(model.method.label())),
+        String::new(),
+        "## Model".to_string(),
+        String::new(),
+        format!(
+            "{} Z = {}",
+            if model.sense.is_maximize() {
+                "max"
+            } else {
+                "min"
+            },
+            linear_expression(model.c.iter().take(n).copied(), plain_variable)
+        ),
+        String::new(),
+        "subject to".to_string(),
+        String::new(),
+    ];
+    for i in 0..a.nrows() {
+        let label = model
+            .constraint_labels
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| format!("C{}", i + 1));
+        md.push(format!(
+            "- {}: {} {} {}",
+            markdown_text(&label),
+            linear_expression(a.row(i).iter().copied(), plain_variable),
+            constraint_sign(model, i).symbol(),
+            pretty::entry(b[i])
+        ));
+    }
+    md.push("- x >= 0".to_string());
+    md.push(String::new());
+    md.push(format!(
+        "Variables: {}.",
+        (0..n)
+            .map(|j| format!("x{} = {}", j + 1, markdown_text(&variable_label(model, j))))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+
+    md.push(String::new());
+    md.push("## Result".to_string());
+    md.push(String::new());
+    match run.report {
+        Some(report) => {
+            let solution = report
+                .original_solution
+                .as_ref()
+                .unwrap_or(&report.solution);
+            md.push(format!(
+                "{} after {} iterations, Z = {}.",
+                markdown_text(report.status.label()),
+                report.iterations,
+                pretty::entry(report.objective)
+            ));
+            md.push(String::new());
+            md.push(markdown_row(["Variable".to_string(), "Value".to_string()]));
+            md.push(markdown_row(["---".to_string(), "---:".to_string()]));
+            for j in 0..n.min(solution.len()) {
+                md.push(markdown_row([
+                    markdown_text(&variable_label(model, j)),
+                    pretty::entry(solution[j]),
+                ]));
+            }
+        }
+        None => md.push("The run had not finished when this report was written.".to_string()),
+    }
+
+    md.push(String::new());
+    md.push("## Iterations".to_string());
+    md.push(String::new());
+    let mut header = vec!["k".to_string()];
+    header.extend((1..=n).map(plain_variable));
+    header.push("Z".to_string());
+    md.push(markdown_row(header));
+    md.push(markdown_row((0..n + 2).map(|_| "---:".to_string())));
+    for (k, x) in run.path.iter().enumerate() {
+        let mut row = vec![k.to_string()];
+        row.extend(x.iter().take(n).map(|&val| pretty::entry(val)));
+        row.push(pretty::entry(objective(model, x)));
+        md.push(markdown_row(row));
+    }
+    md.join("\n") + "\n"
+}
+
+/// A Python literal for `val`, at full precision.
+fn python_number(val: f64) -> String {
+    if val.is_nan() {
+        "np.nan".to_string()
+    } else if val.is_infinite() {
+        format!("{}np.inf", if val < 0.0 { "-" } else { "" })
+    } else {
+        format!("{:?}", val)
+    }
+}
+
+fn python_list(values: impl Iterator<Item = f64>) -> String {
+    format!(
+        "[{}]",
+        values.map(python_number).collect::<Vec<_>>().join(", ")
+    )
+}
+
+fn python_matrix(rows: impl Iterator<Item = String>, columns: usize) -> String {
+    let rows: Vec<String> = rows.collect();
+    if rows.is_empty() {
+        format!("np.empty((0, {}))", columns)
+    } else {
+        format!("np.array([\n    {},\n])", rows.join(",\n    "))
+    }
+}
+
+/// The standard form the solver ran on and every iterate as NumPy arrays,
+/// at full precision, for checking a run by hand in Python.
+fn numpy_script(run: &ExportRun) -> String {
+    let model = run.model;
+    let a: &DMatrix<f64> = &model.a;
+    let columns = a.ncols();
+    let x_rows = run
+        .path
+        .iter()
+        .filter(|x| x.len() == columns)
+        .map(|x| python_list(x.iter().copied()));
+    [
+        format!(
+            "# {} run on the standard form A x = b, x >= 0.",
+            model.method.label()
+        ),
+        "import numpy as np".to_string(),
+        String::new(),
+        format!(
+            "A = {}",
+            python_matrix(
+                a.row_iter().map(|row| python_list(row.iter().copied())),
+                columns
+            )
+        ),
+        format!("b = np.array({})", python_list(model.b.iter().copied())),
+        format!("c = np.array({})", python_list(model.c.iter().copied())),
+        format!(
+            "sense = {:?}",
+            if model.sense.is_maximize() {
+                "max"
+            } else {
+                "min"
+            }
+        ),
+        format!(
+            "# Decision variables are the first {} columns.",
+            user_columns(model)
+        ),
+        String::new(),
+        "# x after each iteration, one row per iteration.".to_string(),
+        format!("X = {}", python_matrix(x_rows, columns)),
+        "Z = X @ c".to_string(),
+    ]
+    .join("\n")
+        + "\n"
+}
+
+fn svg_document(width: f64, height: f64, body: &str) -> String {
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {w} {h}\" width=\"{w}\" height=\"{h}\" font-family=\"sans-serif\" font-size=\"11\">\n{body}</svg>\n",
+        w = width,
+        h = height,
+        body = body
+    )
+}
+
+fn svg_polyline(points: &[(f64, f64)], stroke: &str) -> String {
+    let points = points
+        .iter()
+        .map(|(x, y)| format!("{:.2},{:.2}", x, y))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>\n",
+        points, stroke
+    )
+}
+
+/// Z per iteration on a linear axis.
+fn objective_svg(run: &ExportRun) -> String {
+    let values: Vec<f64> = run
+        .path
+        .iter()
+        .map(|x| objective(run.model, x))
+        .filter(|z| z.is_finite())
+        .collect();
+    let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let mut high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if high <= low {
+        high = low + 1.0;
+    }
+    let span = (values.len().max(2) - 1) as f64;
+    let points: Vec<(f64, f64)> = values
+        .iter()
+        .enumerate()
+        .map(|(k, &z)| {
+            (
+                PAD + k as f64 / span * (CHART_WIDTH - 2.0 * PAD),
+                PAD + (high - z) / (high - low) * (CHART_HEIGHT - 2.0 * PAD),
+            )
+        })
+        .collect();
+    let mut body = format!(
+        "<text x=\"{}\" y=\"20\">Objective Z per iteration</text>\n",
+        PAD
+    );
+    for (val, y) in [(high, PAD), (low, CHART_HEIGHT - PAD)] {
+        body.push_str(&format!(
+            "<line x1=\"{p}\" y1=\"{y}\" x2=\"{x2}\" y2=\"{y}\" stroke=\"#cbd5e1\"/>\n<text x=\"{t}\" y=\"{ty}\" text-anchor=\"end\">{v}</text>\n",
+            p = PAD,
+            y = y,
+            x2 = CHART_WIDTH - PAD,
+            t = PAD - 6.0,
+            ty = y + 4.0,
+            v = pretty::entry(val)
+        ));
+    }
+    if !points.is_empty() {
+        body.push_str(&svg_polyline(&points, "#2563eb"));
+    }
+    body.push_str(&format!(
+        "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\">iteration</text>\n",
+        CHART_WIDTH / 2.0,
+        CHART_HEIGHT - 8.0
+    ));
+    svg_document(CHART_WIDTH, CHART_HEIGHT, &body)
+}
+
+/// The constraint lines and iterate path in (x1, x2), for models with two
+/// decision variables.
+fn path_svg(run: &ExportRun) -> Option<String> {
+    let model = run.model;
+    if model.augmentation.original_variables != 2 || run.path.is_empty() {
+        return None;
+    }
+    let (a, b) = model.original_rows();
+    let path: Vec<(f64, f64)> = run.path.iter().map(|x| (x[0], x[1])).collect();
+    let (x_max, y_max) = path_bounds(&a, &b, &path);
+    let to_svg = |x: f64, y: f64| {
+        (
+            PAD + x / x_max * (PATH_SIZE - 2.0 * PAD),
+            PATH_SIZE - PAD - y / y_max * (PATH_SIZE - 2.0 * PAD),
+        )
+    };
+
+    let (origin_x, origin_y) = to_svg(0.0, 0.0);
+    let mut body = format!(
+        "<text x=\"{p}\" y=\"20\">Iterate path in (x1, x2)</text>\n<line x1=\"{ox}\" y1=\"{oy}\" x2=\"{end}\" y2=\"{oy}\" stroke=\"#334155\"/>\n<line x1=\"{ox}\" y1=\"{oy}\" x2=\"{ox}\" y2=\"{p}\" stroke=\"#334155\"/>\n",
+        p = PAD,
+        ox = origin_x,
+        oy = origin_y,
+        end = PATH_SIZE - PAD
+    );
+    for i in 0..a.nrows() {
+        let Some((p, q)) = clip_line(a[(i, 0)], a[(i, 1)], b[i], x_max, y_max) else {
+            continue;
+        };
+        let (x1, y1) = to_svg(p.0, p.1);
+        let (x2, y2) = to_svg(q.0, q.1);
+        let label = model
+            .constraint_labels
+            .get(i)
+            .cloned()
+            .unwrap_or_else(|| format!("C{}", i + 1));
+        body.push_str(&format!(
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"#94a3b8\"/>\n<text x=\"{:.2}\" y=\"{:.2}\">{}</text>\n",
+            x1,
+            y1,
+            x2,
+            y2,
+            x1 + 4.0,
+            y1 - 4.0,
+            svg_text(&label)
+        ));
+    }
+    let points: Vec<(f64, f64)> = path.iter().map(|&(x, y)| to_svg(x, y)).collect();
+    body.push_str(&svg_polyline(&points, "#dc2626"));
+    for (x, y) in &points {
+        body.push_str(&format!(
+            "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3\" fill=\"#dc2626\"/>\n",
+            x, y
+        ));
+    }
+    Some(svg_document(PATH_SIZE, PATH_SIZE, &body))
+}
+
+fn svg_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,230 @@
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ConstraintSign;
+use crate::reformulation::{AffineExpression, LinearModel};
+
+/// Values of t below this mean the optimum is only approached as x grows
+/// without bound, and x = y / t cannot be read back.
+const SCALE_EPSILON: f64 = 1e-9;
+
+/// (c^T x + c0) / (d^T x + d0), where the denominator must stay positive on
+/// the feasible region.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FractionalObjective {
+    pub numerator: AffineExpression,
+    pub denominator: AffineExpression,
+}
+
+/// How a Charnes–Cooper model maps back to the user's variables.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FractionalLayout {
+    pub objective: FractionalObjective,
+    /// Columns that are y = t x; every column except `scale_column`.
+    pub variables: usize,
+    /// Column of t = 1 / (d^T x + d0).
+    pub scale_column: usize,
+    /// Names of x, before the substitution.
+    pub variable_labels: Vec<String>,
+    /// The substitution and the rows it produced, for display.
+    pub steps: Vec<String>,
+}
+
+/// The ratio at a point read back from the transformed LP.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FractionalSolution {
+    pub t: f64,
+    /// x = y / t; `None` when t is 0.
+    pub x: Option<DVector<f64>>,
+    pub numerator: f64,
+    pub denominator: f64,
+}
+
+impl FractionalSolution {
+    pub fn ratio(&self) -> f64 {
+        self.numerator / self.denominator
+    }
+}
+
+impl FractionalLayout {
+    /// Back-transforms a solution (y, t) of the LP.
+    pub fn solve_back(&self, solution: &DVector<f64>) -> FractionalSolution {
+        let t = solution.get(self.scale_column).copied().unwrap_or(0.0);
+        let y = DVector::from_fn(self.variables, |j, _| {
+            let column = if j < self.scale_column { j } else { j + 1 };
+            solution.get(column).copied().unwrap_or(0.0)
+        });
+        let x = (t > SCALE_EPSILON).then(|| y / t);
+        let value = |e: &AffineExpression| x.as_ref().map_or(f64::NAN, |x| e.value(x.as_slice()));
+        FractionalSolution {
+            t,
+            numerator: value(&self.objective.numerator),
+            denominator: value(&self.objective.denominator),
+            x,
+        }
+    }
+}
+
+/// The Charnes–Cooper transformation of max or min (c^T x + c0) / (d^T x + d0)
+/// over `model`: with t = 1 / (d^T x + d0) and y = t x, the ratio becomes
+/// c^T y + c0 t, each row a x (sign) b becomes a y - b t (sign) 0, and
+/// d^T y + d0 t = 1 fixes the scale. The objective row of `model` is
+/// replaced; any columns it already has past the user's variables are
+/// scaled like the rest.
+pub fn charnes_cooper(
+    model: LinearModel,
+    objective: &FractionalObjective,
+) -> Result<(LinearModel, FractionalLayout), String> {
+    let denominator = &objective.denominator;
+    if denominator.constant <= 0.0 && denominator.coefficients.iter().all(|&d| d <= 0.0) {
+        return Err(
+            "The denominator is never positive for x >= 0, so t = 1 / (d^T x + d0) does not exist."
+                .to_string(),
+        );
+    }
+    let (m, n) = model.a.shape();
+    let start_denominator = denominator.value(&model.initial);
+    // Any t > 0 keeps y = t x0 strictly positive; the exact one also
+    // satisfies the normalization row when the start is feasible.
+    let t0 = if start_denominator > 0.0 {
+        1.0 / start_denominator
+    } else {
+        1.0
+    };
+
+    let a = model.a.clone().insert_column(n, 0.0);
+    let mut a = a.insert_row(m, 0.0);
+    for i in 0..m {
+        a[(i, n)] = -model.b[i];
+    }
+    for (j, &d) in denominator.coefficients.iter().enumerate() {
+        a[(m, j)] = d;
+    }
+    a[(m, n)] = denominator.constant;
+
+    let mut b = DVector::zeros(m + 1);
+    b[m] = 1.0;
+    let mut c = DVector::zeros(n + 1);
+    for (j, &value) in objective.numerator.coefficients.iter().enumerate() {
+        c[j] = value;
+    }
+    c[n] = objective.numerator.constant;
+
+    let mut signs = model.signs.clone();
+    signs.push(ConstraintSign::Equal);
+    let mut initial: Vec<f64> = model.initial.iter().map(|&x| t0 * x).collect();
+    initial.push(t0);
+    let mut variable_labels: Vec<String> = model
+        .variable_labels
+        .iter()
+        .map(|label| match label.strip_prefix('x') {
+            Some(index) => format!("y{}", index),
+            None => format!("t·{}", label),
+        })
+        .collect();
+    variable_labels.push("t".to_string());
+    let mut constraint_labels = model.constraint_labels.clone();
+    constraint_labels.push("N".to_string());
+
+    let transformed = LinearModel {
+        a,
+        b,
+        c,
+        signs,
+        initial,
+        variable_labels,
+        constraint_labels,
+    };
+    let steps = vec![
+        format!(
+            "t = 1 / ({}) > 0 and y = t x",
+            linear_text(denominator, &model.variable_labels)
+        ),
+        format!(
+            "({}) / ({}) = {}",
+            linear_text(&objective.numerator, &model.variable_labels),
+            linear_text(denominator, &model.variable_labels),
+            linear_text(
+                &AffineExpression {
+                    coefficients: transformed.c.iter().copied().collect(),
+                    constant: 0.0,
+                },
+                &transformed.variable_labels
+            )
+        ),
+        "Each row a x (sign) b, multiplied by t, becomes a y - b t (sign) 0".to_string(),
+        transformed.describe_rows()[m].clone(),
+    ];
+    let layout = FractionalLayout {
+        objective: objective.clone(),
+        variables: n,
+        scale_column: n,
+        variable_labels: model.variable_labels,
+        steps,
+    };
+    Ok((transformed, layout))
+}
+
+fn linear_text(e: &AffineExpression, labels: &[String]) -> String {
+    crate::reformulation::linear_text(&e.coefficients, labels, e.constant)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Sense, SolverMethod};
+    use crate::test_models::{from_linear, solve};
+    use nalgebra::DMatrix;
+
+    /// x1 + x2 <= 4 over x1, x2 >= 0.
+    fn budget() -> LinearModel {
+        LinearModel {
+            a: DMatrix::from_row_slice(1, 2, &[1.0, 1.0]),
+            b: DVector::from_vec(vec![4.0]),
+            c: DVector::zeros(2),
+            signs: vec![ConstraintSign::LessEqual],
+            initial: vec![1.0, 1.0],
+            variable_labels: vec!["x1".to_string(), "x2".to_string()],
+            constraint_labels: vec!["C1".to_string()],
+        }
+    }
+
+    /// max (x1 + 2 x2) / (x1 + x2 + 1): with s = x1 + x2 the best ratio is
+    /// 2 s / (s + 1) at x1 = 0, rising in s, so it is 8 / 5 at (0, 4).
+    #[test]
+    fn transformed_lp_finds_the_best_ratio() {
+        let objective = FractionalObjective {
+            numerator: AffineExpression {
+                coefficients: vec![1.0, 2.0],
+                constant: 0.0,
+            },
+            denominator: AffineExpression {
+                coefficients: vec![1.0, 1.0],
+                constant: 1.0,
+            },
+        };
+        let (linear, layout) = charnes_cooper(budget(), &objective).unwrap();
+        let report = solve(
+            &from_linear(&linear, Sense::Maximize),
+            SolverMethod::Simplex,
+        );
+
+        let back = layout.solve_back(&report.solution);
+        assert!((back.t - 0.2).abs() < 1e-9, "t = {}", back.t);
+        assert!((back.ratio() - 1.6).abs() < 1e-9);
+        let x = back.x.unwrap();
+        assert!(x[0].abs() < 1e-9 && (x[1] - 4.0).abs() < 1e-9, "{x}");
+    }
+
+    #[test]
+    fn rejects_a_denominator_that_is_never_positive() {
+        let objective = FractionalObjective {
+            numerator: AffineExpression::zeros(2),
+            denominator: AffineExpression {
+                coefficients: vec![-1.0, 0.0],
+                constant: 0.0,
+            },
+        };
+        assert!(charnes_cooper(budget(), &objective).is_err());
+    }
+}
@@ -0,0 +1,296 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::equilibration::Scaling;
+use crate::interior::{SolverOptions, StepStrategy};
+use crate::model::{
+    big_m_form, default_variable_labels, ConstraintSign, Sense, SolverMethod, StartOptions,
+    ValidatedProblem,
+};
+use crate::race::RaceSolver;
+use crate::report::SolveStatus;
+
+/// Iterations a fuzz run gives each solver before counting it as stopped
+/// by the limit.
+pub const FUZZ_ITERATION_LIMIT: usize = 300;
+
+/// SplitMix64: tiny, seedable and good enough to scatter coefficients. The
+/// same seed gives the same corpus on every platform.
+#[derive(Clone, Debug)]
+pub struct SplitMix(u64);
+
+impl SplitMix {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1), from the top 53 bits.
+    pub fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in [lo, hi).
+    pub fn range(&mut self, lo: f64, hi: f64) -> f64 {
+        lo + (hi - lo) * self.unit()
+    }
+
+    /// Uniform in 0..n.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+/// A way of making an LP hard on the numerics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pathology {
+    /// A row repeated with a perturbation of 1e-12 to 1e-6: A A^T is
+    /// nearly singular from the first step.
+    NearParallel,
+    /// Rows scaled by up to 1e12 and columns by up to 1e6.
+    Huge,
+    /// Rows scaled by down to 1e-12 and columns by down to 1e-6.
+    Tiny,
+    /// A row that is an exact combination of others, with b consistent.
+    RankDeficient,
+}
+
+impl Pathology {
+    pub const ALL: [Pathology; 4] = [
+        Pathology::NearParallel,
+        Pathology::Huge,
+        Pathology::Tiny,
+        Pathology::RankDeficient,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Pathology::NearParallel => "near-parallel rows",
+            Pathology::Huge => "huge coefficients",
+            Pathology::Tiny => "tiny coefficients",
+            Pathology::RankDeficient => "rank-deficient A",
+        }
+    }
+}
+
+/// One generated model: max c^T x over A x <= b, x >= 0, with b chosen so
+/// `x0` is strictly inside. The first row bounds sum(x) and is exempt from
+/// the row scaling, so every case has an optimum and "unbounded" is a
+/// wrong answer.
+#[derive(Clone, Debug)]
+pub struct FuzzCase {
+    pub pathology: Pathology,
+    pub seed: u64,
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    pub x0: DVector<f64>,
+}
+
+impl FuzzCase {
+    /// An m×n model with `pathology`, reproducible from `seed`.
+    pub fn generate(pathology: Pathology, seed: u64, m: usize, n: usize) -> Self {
+        let mut rng = SplitMix::new(seed);
+        // Row 0 bounds sum(x), so most cases have an optimum to find
+        // rather than a ray.
+        let mut a = DMatrix::from_fn(m, n, |i, _| if i == 0 { 1.0 } else { rng.range(-1.0, 1.0) });
+        match pathology {
+            Pathology::NearParallel if m >= 2 => {
+                let source = rng.below(m - 1);
+                let scale = 10f64.powf(rng.range(-12.0, -6.0));
+                let copy = a.row(source).map(|val| val + scale * rng.range(-1.0, 1.0));
+                a.set_row(m - 1, &copy);
+            }
+            Pathology::Huge | Pathology::Tiny => {
+                let sign = if pathology == Pathology::Huge {
+                    1.0
+                } else {
+                    -1.0
+                };
+                for i in 1..m {
+                    let factor = 10f64.powf(sign * rng.range(0.0, 12.0));
+                    a.row_mut(i).scale_mut(factor);
+                }
+                for j in 0..n {
+                    let factor = 10f64.powf(sign * rng.range(0.0, 6.0));
+                    a.column_mut(j).scale_mut(factor);
+                }
+            }
+            Pathology::RankDeficient if m >= 2 => {
+                let mut combination = DVector::zeros(n).transpose();
+                for i in 0..m - 1 {
+                    combination += rng.range(-2.0, 2.0) * a.row(i);
+                }
+                a.set_row(m - 1, &combination);
+            }
+            _ => {}
+        }
+        let x0 = DVector::from_fn(n, |_, _| rng.range(0.5, 2.0));
+        let slack = DVector::from_fn(m, |_, _| rng.range(0.1, 1.0));
+        let b = &a * &x0 + slack;
+        let c = DVector::from_fn(n, |_, _| rng.range(-1.0, 1.0));
+        Self {
+            pathology,
+            seed,
+            a,
+            b,
+            c,
+            x0,
+        }
+    }
+
+    /// The case as the form would hand it to `method`: Big-M augmented,
+    /// starting from `x0` and its slacks.
+    pub fn to_validated(
+        &self,
+        method: SolverMethod,
+        step_strategy: StepStrategy,
+    ) -> ValidatedProblem {
+        let (m, n) = self.a.shape();
+        let signs = vec![ConstraintSign::LessEqual; m];
+        let x0: Vec<f64> = self.x0.iter().copied().collect();
+        let form = big_m_form(&self.a, &self.b, &self.c, &signs, &x0, Sense::Maximize);
+        ValidatedProblem {
+            method,
+            sense: Sense::Maximize,
+            variable_labels: default_variable_labels(&form.augmentation, form.a.ncols()),
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            quadratic: None,
+            initial: form.initial,
+            constraint_signs: signs,
+            integer_variables: vec![false; n],
+            constraint_labels: (1..=m).map(|i| format!("C{}", i)).collect(),
+            constraint_groups: vec![None; m],
+            options: StartOptions {
+                alpha: 0.5,
+                mu: 1.0,
+                find_start: false,
+                step_strategy,
+                scaling: Scaling::default(),
+            },
+            is_augmented: false,
+            augmentation: form.augmentation,
+            transportation: None,
+            regression: None,
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        }
+    }
+}
+
+/// How one solver ended on one case.
+#[derive(Clone, Debug, PartialEq)]
+pub enum FuzzOutcome {
+    /// Stopped with a status; `Failed` included.
+    Status(SolveStatus),
+    /// The solver could not even be set up, with the message it gave.
+    Rejected(String),
+    /// An iterate or the objective became NaN or infinite without the
+    /// solver noticing.
+    NonFinite { iteration: usize },
+    /// The solver panicked, with the panic message.
+    Panicked(String),
+}
+
+impl FuzzOutcome {
+    /// Panics and unnoticed NaN are bugs, and so is calling a case
+    /// unbounded or infeasible: every case is feasible at `x0` and bounded
+    /// by its first row. Everything else is a structured answer.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            FuzzOutcome::NonFinite { .. }
+                | FuzzOutcome::Panicked(_)
+                | FuzzOutcome::Status(SolveStatus::Unbounded | SolveStatus::Infeasible)
+        )
+    }
+}
+
+/// Runs `method` on `case` for up to `FUZZ_ITERATION_LIMIT` iterations,
+/// catching panics and checking every iterate is finite. Panics are only
+/// caught natively; in wasm they abort.
+pub fn run_case(
+    case: &FuzzCase,
+    method: SolverMethod,
+    step_strategy: StepStrategy,
+    options: &SolverOptions,
+) -> FuzzOutcome {
+    let model = case.to_validated(method, step_strategy);
+    let run = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut solver = match RaceSolver::new(method, &model, options) {
+            Ok(solver) => solver,
+            Err(message) => return FuzzOutcome::Rejected(message),
+        };
+        for iteration in 0..FUZZ_ITERATION_LIMIT {
+            if let Err(status) = solver.step() {
+                return FuzzOutcome::Status(status);
+            }
+            let finite = solver.current_x().iter().all(|val| val.is_finite())
+                && solver.objective().is_finite();
+            if !finite {
+                return FuzzOutcome::NonFinite { iteration };
+            }
+        }
+        FuzzOutcome::Status(SolveStatus::IterationLimit)
+    }));
+    run.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|text| text.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic without a message".to_string());
+        FuzzOutcome::Panicked(message)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::LogVerbosity;
+
+    /// Huge-coefficient cases on which pivots too small for their columns
+    /// once made the simplex methods call an optimum unbounded or
+    /// infeasible.
+    #[test]
+    fn huge_coefficients_get_structured_answers() {
+        let options = SolverOptions {
+            log_verbosity: LogVerbosity::Off,
+            ..SolverOptions::default()
+        };
+        for seed in [115, 139, 157, 170, 206] {
+            let m = 2 + (seed % 4) as usize;
+            let n = m + 1 + (seed % 3) as usize;
+            let case = FuzzCase::generate(Pathology::Huge, seed, m, n);
+            for method in [
+                SolverMethod::AffineScaling,
+                SolverMethod::LogBarrier,
+                SolverMethod::SelfDual,
+                SolverMethod::Simplex,
+                SolverMethod::RevisedSimplex,
+            ] {
+                let outcome = run_case(&case, method, StepStrategy::LongStep, &options);
+                assert!(
+                    !outcome.is_failure(),
+                    "seed {}, {}: {:?}",
+                    seed,
+                    method.label(),
+                    outcome
+                );
+                if !method.is_interior() {
+                    assert_eq!(outcome, FuzzOutcome::Status(SolveStatus::Optimal));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::builder::InteriorPointProblemBuilder;
+use crate::course_pack::{PackProblem, PackSettings};
+use crate::fuzz::SplitMix;
+use crate::model::{ConstraintSign, Sense, ValidatedProblem};
+
+/// Largest coefficient of A; entries are integers in 1..=9 either way round.
+const MAX_COEFFICIENT: u64 = 9;
+
+/// Size and sparsity of a generated LP.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratorOptions {
+    pub constraints: usize,
+    pub variables: usize,
+    /// Chance that an entry of A below the first row is nonzero, in (0, 1].
+    pub density: f64,
+    pub seed: u64,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            constraints: 3,
+            variables: 3,
+            density: 0.6,
+            seed: 1,
+        }
+    }
+}
+
+/// max c^T x over A x <= b, x >= 0, built backwards from an optimal pair
+/// so its answer is known before any solver runs. Every number is a small
+/// integer, so the answer can be checked by hand and is exact in f64.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GeneratedProblem {
+    pub seed: u64,
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    /// An optimal solution; others may tie with it.
+    pub x_star: DVector<f64>,
+    /// Optimal duals, one per row.
+    pub y_star: DVector<f64>,
+    /// The optimal value c^T x* = b^T y*.
+    pub objective: f64,
+}
+
+/// A random LP with a known optimum. Picks x* >= 0 and y* >= 0 first, then
+/// makes them optimal through complementary slackness:
+///
+/// * rows where y*_i > 0 are tight at x* (b_i = (A x*)_i), the rest get
+///   slack;
+/// * c = A^T y* - s with s_j = 0 where x*_j > 0 and s_j > 0 elsewhere, so
+///   y* is dual feasible.
+///
+/// Complementarity is strict, the way interior-point methods like it. The
+/// first row has every variable with a positive coefficient, which keeps
+/// the region bounded, and b > 0 so small positive x are strictly inside.
+pub fn generate(options: &GeneratorOptions) -> Result<GeneratedProblem, String> {
+    let (m, n) = (options.constraints, options.variables);
+    if m == 0 || n == 0 {
+        return Err("a problem needs at least one constraint and one variable".to_string());
+    }
+    if !(options.density > 0.0 && options.density <= 1.0) {
+        return Err("the density must be in (0, 1]".to_string());
+    }
+
+    let mut rng = SplitMix::new(options.seed);
+
+    let mut a = DMatrix::zeros(m, n);
+    for j in 0..n {
+        a[(0, j)] = integer(&mut rng, 1, MAX_COEFFICIENT);
+    }
+    for i in 1..m {
+        for j in 0..n {
+            if rng.unit() < options.density {
+                let sign = if rng.below(4) == 0 { -1.0 } else { 1.0 };
+                a[(i, j)] = sign * integer(&mut rng, 1, MAX_COEFFICIENT);
+            }
+        }
+        if a.row(i).iter().all(|&val| val == 0.0) {
+            let j = rng.below(n);
+            a[(i, j)] = integer(&mut rng, 1, MAX_COEFFICIENT);
+        }
+    }
+
+    let mut x_star = DVector::from_fn(n, |_, _| {
+        if rng.below(2) == 1 {
+            integer(&mut rng, 1, 5)
+        } else {
+            0.0
+        }
+    });
+    if x_star.iter().all(|&val| val == 0.0) {
+        x_star[rng.below(n)] = integer(&mut rng, 1, 5);
+    }
+
+    // A row that x* pushes below zero is turned round, so every row it
+    // touches can be tight with b > 0.
+    for i in 1..m {
+        if a.row(i).dot(&x_star.transpose()) < 0.0 {
+            a.row_mut(i).neg_mut();
+        }
+    }
+    let activity = &a * &x_star;
+    let mut y_star = DVector::from_fn(m, |i, _| {
+        if activity[i] > 0.0 && rng.below(2) == 1 {
+            integer(&mut rng, 1, 3)
+        } else {
+            0.0
+        }
+    });
+    if y_star.iter().all(|&val| val == 0.0) {
+        // Row 0 covers every variable, so x* makes it positive.
+        y_star[0] = integer(&mut rng, 1, 3);
+    }
+
+    let b = DVector::from_fn(m, |i, _| {
+        if y_star[i] > 0.0 {
+            activity[i]
+        } else {
+            activity[i] + integer(&mut rng, 1, 5)
+        }
+    });
+    let reduced = DVector::from_fn(n, |j, _| {
+        if x_star[j] > 0.0 {
+            0.0
+        } else {
+            integer(&mut rng, 1, 3)
+        }
+    });
+    let c = a.tr_mul(&y_star) - reduced;
+    let objective = c.dot(&x_star);
+
+    Ok(GeneratedProblem {
+        seed: options.seed,
+        a,
+        b,
+        c,
+        x_star,
+        y_star,
+        objective,
+    })
+}
+
+/// Uniform over the integers lo..=hi.
+fn integer(rng: &mut SplitMix, lo: u64, hi: u64) -> f64 {
+    (lo + rng.next_u64() % (hi - lo + 1)) as f64
+}
+
+impl GeneratedProblem {
+    /// The problem in a builder, for other settings or extra rows.
+    pub fn builder(&self) -> InteriorPointProblemBuilder {
+        let mut builder = InteriorPointProblemBuilder::new()
+            .objective(self.c.as_slice())
+            .maximize();
+        for i in 0..self.a.nrows() {
+            let row: Vec<f64> = self.a.row(i).iter().copied().collect();
+            builder = builder.constraint(&row, ConstraintSign::LessEqual, self.b[i]);
+        }
+        builder
+    }
+
+    /// The validated model, for `interior::solve` or any other method.
+    pub fn model(&self) -> ValidatedProblem {
+        self.builder()
+            .model()
+            .expect("generated problems are well formed")
+    }
+
+    /// The problem as a course-pack entry, to load into the form. The notes
+    /// give the known optimum.
+    pub fn to_pack_problem(&self) -> PackProblem {
+        let (m, n) = self.a.shape();
+        let x_star = self
+            .x_star
+            .iter()
+            .enumerate()
+            .map(|(j, val)| format!("x{} = {}", j + 1, val))
+            .collect::<Vec<_>>()
+            .join(", ");
+        PackProblem {
+            name: format!("Generated #{}", self.seed),
+            notes: format!(
+                "Random {}×{} problem from seed {}. Its optimum is Z* = {} at {}.",
+                m, n, self.seed, self.objective, x_star
+            ),
+            sense: Sense::Maximize,
+            a: (0..m)
+                .map(|i| self.a.row(i).iter().copied().collect())
+                .collect(),
+            b: self.b.iter().copied().collect(),
+            c: self.c.iter().copied().collect(),
+            signs: vec![ConstraintSign::LessEqual; m],
+            groups: vec![String::new(); m],
+            initial: None,
+            integer: vec![],
+            q: None,
+            settings: PackSettings {
+                find_start: Some(true),
+                ..PackSettings::default()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SolverMethod;
+    use crate::test_models::solve;
+
+    #[test]
+    fn certificate_proves_the_optimum() {
+        for seed in 1..=20 {
+            let options = GeneratorOptions {
+                constraints: 4,
+                variables: 5,
+                seed,
+                ..GeneratorOptions::default()
+            };
+            let p = generate(&options).unwrap();
+            // Primal and dual feasible with equal objectives.
+            assert!((&p.a * &p.x_star - &p.b).max() <= 0.0, "seed {seed}");
+            assert!((p.a.tr_mul(&p.y_star) - &p.c).min() >= 0.0, "seed {seed}");
+            assert!(p.x_star.min() >= 0.0 && p.y_star.min() >= 0.0);
+            assert_eq!(p.b.dot(&p.y_star), p.objective, "seed {seed}");
+            assert_eq!(
+                generate(&options).unwrap(),
+                p,
+                "seed {seed} is reproducible"
+            );
+        }
+    }
+
+    #[test]
+    fn solvers_reach_the_known_value() {
+        let p = generate(&GeneratorOptions::default()).unwrap();
+        for method in [SolverMethod::Simplex, SolverMethod::AffineScaling] {
+            let report = solve(&p.model(), method);
+            assert!(
+                (report.objective - p.objective).abs() < 1e-4 * (1.0 + p.objective.abs()),
+                "{method:?}: {} instead of {}",
+                report.objective,
+                p.objective
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_empty_sizes_and_bad_density() {
+        let empty = GeneratorOptions {
+            constraints: 0,
+            ..GeneratorOptions::default()
+        };
+        assert!(generate(&empty).is_err());
+        let dense = GeneratorOptions {
+            density: 1.5,
+            ..GeneratorOptions::default()
+        };
+        assert!(generate(&dense).is_err());
+    }
+}
@@ -0,0 +1,184 @@
+use nalgebra::DVector;
+
+/// What a search over the iteration history can ask about one step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IterationFacts {
+    pub index: usize,
+    /// Z in the model's own sense.
+    pub objective: f64,
+    pub x: DVector<f64>,
+    /// Step length t, for the methods that take one.
+    pub step: Option<f64>,
+    /// Barrier parameter of the barrier methods.
+    pub mu: Option<f64>,
+    /// cond(A~ A~^T) of an affine-scaling step.
+    pub condition: Option<f64>,
+    /// Whether the step raised a warning, such as an ill-conditioned
+    /// projection.
+    pub warning: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Field {
+    Objective,
+    /// Zero-based column of x.
+    Variable(usize),
+    Iteration,
+    Step,
+    Mu,
+    Condition,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Comparison {
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    /// A bare "=" is tried only when none of these occur, since it is part
+    /// of "<=", ">=" and "!=".
+    const SYMBOLS: [(&'static str, Comparison); 8] = [
+        ("<=", Comparison::LessEqual),
+        (">=", Comparison::GreaterEqual),
+        ("!=", Comparison::NotEqual),
+        ("==", Comparison::Equal),
+        ("≤", Comparison::LessEqual),
+        ("≥", Comparison::GreaterEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    /// Equality allows the rounding the cards display with.
+    fn holds(&self, left: f64, right: f64) -> bool {
+        let close = (left - right).abs() <= 1e-9 * (1.0 + right.abs());
+        match self {
+            Comparison::Less => left < right,
+            Comparison::LessEqual => left <= right || close,
+            Comparison::Greater => left > right,
+            Comparison::GreaterEqual => left >= right || close,
+            Comparison::Equal => close,
+            Comparison::NotEqual => !close,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Condition {
+    Compare(Field, Comparison, f64),
+    Warning(bool),
+}
+
+/// A conjunction of conditions on an iteration, parsed from text such as
+/// "objective > 10 and x3 < 0.01" or "warnings present".
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HistoryFilter {
+    conditions: Vec<Condition>,
+}
+
+impl HistoryFilter {
+    /// Parses conditions separated by "and" or commas. Fields are
+    /// `objective` (or `z`), `x1`, `x2`, … or a variable label, `iteration`
+    /// (or `k`), `step` (or `t`), `mu` and `cond`, compared with `<`, `<=`,
+    /// `>`, `>=`, `=` or `!=`. "warnings present" and "no warnings" test for
+    /// warnings. Empty text matches everything.
+    pub fn parse(text: &str, variable_labels: &[String]) -> Result<Self, String> {
+        let normalized = text.replace(" and ", ",").replace(" AND ", ",");
+        let conditions = normalized
+            .split(',')
+            .map(str::trim)
+            .filter(|part| !part.is_empty())
+            .map(|part| parse_condition(part, variable_labels))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { conditions })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.conditions.is_empty()
+    }
+
+    /// Whether every condition holds. A condition on something the
+    /// iteration does not have, such as mu for affine scaling, fails.
+    pub fn matches(&self, facts: &IterationFacts) -> bool {
+        self.conditions.iter().all(|condition| match condition {
+            Condition::Warning(present) => facts.warning == *present,
+            Condition::Compare(field, comparison, value) => {
+                let actual = match field {
+                    Field::Objective => Some(facts.objective),
+                    Field::Variable(j) => facts.x.get(*j).copied(),
+                    Field::Iteration => Some(facts.index as f64),
+                    Field::Step => facts.step,
+                    Field::Mu => facts.mu,
+                    Field::Condition => facts.condition,
+                };
+                actual.is_some_and(|actual| comparison.holds(actual, *value))
+            }
+        })
+    }
+}
+
+fn parse_condition(text: &str, variable_labels: &[String]) -> Result<Condition, String> {
+    let lower = text.to_lowercase();
+    match lower.as_str() {
+        "warning" | "warnings" | "warnings present" | "warning present" | "has warnings" => {
+            return Ok(Condition::Warning(true))
+        }
+        "no warning" | "no warnings" | "warnings absent" => return Ok(Condition::Warning(false)),
+        _ => {}
+    }
+
+    let Some((at, symbol, comparison)) = Comparison::SYMBOLS
+        .iter()
+        .filter_map(|&(symbol, comparison)| text.find(symbol).map(|at| (at, symbol, comparison)))
+        .min_by_key(|&(at, symbol, _)| (at, std::cmp::Reverse(symbol.len())))
+        .or_else(|| text.find('=').map(|at| (at, "=", Comparison::Equal)))
+    else {
+        return Err(format!(
+            "\"{}\" has no comparison; try something like \"objective > 10\"",
+            text
+        ));
+    };
+
+    let name = text[..at].trim();
+    let number = text[at + symbol.len()..].trim();
+    let value = number
+        .parse::<f64>()
+        .map_err(|_| format!("\"{}\" is not a number", number))?;
+    Ok(Condition::Compare(
+        parse_field(name, variable_labels)?,
+        comparison,
+        value,
+    ))
+}
+
+fn parse_field(name: &str, variable_labels: &[String]) -> Result<Field, String> {
+    let lower = name.to_lowercase();
+    let field = match lower.as_str() {
+        "objective" | "obj" | "z" => Field::Objective,
+        "iteration" | "iter" | "k" => Field::Iteration,
+        "step" | "t" => Field::Step,
+        "mu" | "μ" => Field::Mu,
+        "cond" | "condition" => Field::Condition,
+        _ => {
+            if let Some(j) = variable_labels.iter().position(|label| label == name) {
+                Field::Variable(j)
+            } else if let Some(j) = lower
+                .strip_prefix('x')
+                .and_then(|digits| digits.parse::<usize>().ok())
+                .filter(|&j| j >= 1)
+            {
+                Field::Variable(j - 1)
+            } else {
+                return Err(format!(
+                    "unknown field \"{}\"; use objective, x1, x2, …, iteration, step, mu or cond",
+                    name
+                ));
+            }
+        }
+    };
+    Ok(field)
+}
@@ -0,0 +1,501 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::{check_finite, improving_ray, InteriorPointError, SolverOptions};
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::pretty;
+use crate::report::{ImprovingRay, SolveReport, SolveStatus};
+use crate::solver::{user_point, Solver};
+use crate::stopping::StopReason;
+use crate::theory::STOPPING_TOLERANCE;
+
+/// Fraction σ of the current μ each Newton step aims for; the residuals and
+/// μ then shrink by 1 - t (1 - σ) for a step of length t.
+pub const CENTERING: f64 = 0.1;
+
+/// τ below this multiple of max(1, κ) means the embedding is heading for a
+/// certificate rather than a solution.
+const TAU_VANISHED: f64 = 1e-8;
+
+/// μ below this multiple of its starting value is exhausted: further steps
+/// only lose accuracy in A X Z^-1 A^T.
+const MU_EXHAUSTED: f64 = 1e-8;
+
+/// Relative residuals accepted as optimal once μ is exhausted; badly scaled
+/// data rarely gets the full `STOPPING_TOLERANCE`.
+pub(crate) const NEAR_OPTIMAL: f64 = 1e-5;
+
+/// Relative size of each (A^T y)_j below which, and of b^T y above which, y
+/// counts as a Farkas certificate.
+const FARKAS_TOLERANCE: f64 = 1e-7;
+
+/// τ/κ beyond which the card calls the direction settled either way.
+const DECISIVE_RATIO: f64 = 1e3;
+
+/// Where the τ and κ of an iterate point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indicator {
+    /// τ ≫ κ: x/τ and y/τ approach an optimal pair.
+    Optimum,
+    /// κ ≫ τ: x and y approach a certificate that the primal or the dual
+    /// has no feasible point.
+    Certificate,
+    /// Neither dominates yet.
+    Undecided,
+}
+
+impl Indicator {
+    pub fn from_ratio(tau: f64, kappa: f64) -> Self {
+        if tau > DECISIVE_RATIO * kappa {
+            Indicator::Optimum
+        } else if kappa > DECISIVE_RATIO * tau {
+            Indicator::Certificate
+        } else {
+            Indicator::Undecided
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Indicator::Optimum => "τ ≫ κ: heading for an optimum, read off as x/τ",
+            Indicator::Certificate => {
+                "κ ≫ τ: heading for a certificate of infeasibility or unboundedness"
+            }
+            Indicator::Undecided => "τ and κ still comparable: no verdict yet",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct HsdIteration {
+    pub mu: f64,
+    pub tau: f64,
+    pub kappa: f64,
+    pub step_length: f64,
+    /// ‖A x - b τ‖ / τ, relative to 1 + ‖b‖.
+    pub primal_residual: f64,
+    /// ‖A^T y - z - c τ‖ / τ, relative to 1 + ‖c‖.
+    pub dual_residual: f64,
+    /// |b^T y - c^T x| / τ, relative to 1 + |c^T x / τ|.
+    pub gap: f64,
+    /// x / τ, the primal estimate.
+    pub current_x: DVector<f64>,
+    /// y / τ, the dual estimate.
+    pub duals: DVector<f64>,
+}
+
+impl HsdIteration {
+    pub fn indicator(&self) -> Indicator {
+        Indicator::from_ratio(self.tau, self.kappa)
+    }
+}
+
+impl fmt::Debug for HsdIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "HsdIteration: mu = {}, tau = {}, kappa = {}, step = {}",
+            pretty::entry(self.mu),
+            pretty::entry(self.tau),
+            pretty::entry(self.kappa),
+            pretty::entry(self.step_length)
+        )?;
+        writeln!(
+            f,
+            "residuals: primal {}, dual {}, gap {}",
+            pretty::entry(self.primal_residual),
+            pretty::entry(self.dual_residual),
+            pretty::entry(self.gap)
+        )?;
+        writeln!(f, "{}", pretty::vector("x / tau", &self.current_x))?;
+        write!(f, "{}", pretty::vector("y / tau", &self.duals))
+    }
+}
+
+/// Homogeneous self-dual embedding of max c^T x, A x = b, x >= 0 and its
+/// dual min b^T y, A^T y - z = c, z >= 0:
+///
+///   A x - b τ = 0,  A^T y - z - c τ = 0,  b^T y - c^T x + κ = 0,
+///
+/// with x, z, τ, κ >= 0. The embedding always has a solution with
+/// x_j z_j = 0 and τ κ = 0, so the iteration can start anywhere positive and
+/// needs no Phase 1. At the limit either τ > 0, and (x, y) / τ is optimal,
+/// or κ > 0, and x or y proves the primal or the dual infeasible.
+pub struct HsdProblem {
+    pub a_matrix: DMatrix<f64>,
+    pub b_vector: DVector<f64>,
+    pub c_vector: DVector<f64>,
+    pub x_vector: DVector<f64>,
+    pub y_vector: DVector<f64>,
+    pub z_vector: DVector<f64>,
+    pub tau: f64,
+    pub kappa: f64,
+    /// Fraction of the way to the boundary each step goes.
+    pub alpha: f64,
+    pub options: SolverOptions,
+    /// Newton steps taken, for naming the step that broke down.
+    pub iterations: usize,
+    /// μ at the start, which `MU_EXHAUSTED` is relative to.
+    pub start_mu: f64,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
+}
+
+impl HsdProblem {
+    /// Starts at `x` (floored to stay positive), y = 0, z = 1, τ = κ = 1;
+    /// `x` need not satisfy A x = b.
+    pub fn new(
+        a: DMatrix<f64>,
+        b: DVector<f64>,
+        c: DVector<f64>,
+        x: DVector<f64>,
+        alpha: f64,
+        options: SolverOptions,
+    ) -> Self {
+        let (m, n) = a.shape();
+        let mut problem = Self {
+            x_vector: x.map(|val| val.max(1e-2)),
+            y_vector: DVector::zeros(m),
+            z_vector: DVector::from_element(n, 1.0),
+            tau: 1.0,
+            kappa: 1.0,
+            a_matrix: a,
+            b_vector: b,
+            c_vector: c,
+            alpha,
+            options,
+            iterations: 0,
+            start_mu: 1.0,
+            finished: false,
+        };
+        problem.start_mu = problem.mu();
+        problem
+    }
+
+    /// (x^T z + τ κ) / (n + 1).
+    pub fn mu(&self) -> f64 {
+        (self.x_vector.dot(&self.z_vector) + self.tau * self.kappa)
+            / (self.x_vector.len() + 1) as f64
+    }
+
+    /// x / τ, in the solver space.
+    pub fn current_x(&self) -> DVector<f64> {
+        &self.x_vector / self.tau
+    }
+
+    /// y / τ, duals of the maximization form.
+    pub fn duals(&self) -> DVector<f64> {
+        &self.y_vector / self.tau
+    }
+
+    /// Report at the current iterate; `ray` is the improving direction an
+    /// unbounded run stopped on. An infeasible run names the y that proves
+    /// it instead of checking A x = b, which x / τ cannot meet.
+    pub fn report(
+        &self,
+        status: SolveStatus,
+        iterations: usize,
+        ray: Option<DVector<f64>>,
+    ) -> SolveReport {
+        let mut report = SolveReport::new(status, iterations, self.current_x(), &self.c_vector);
+        report.duals = Some(self.duals());
+        report.ray = ray.map(|direction| ImprovingRay::new(direction, &self.c_vector));
+        if report.status == SolveStatus::Infeasible {
+            let y: Vec<String> = self
+                .y_vector
+                .iter()
+                .map(|val| format!("{:.4}", val))
+                .collect();
+            report.warnings.push(format!(
+                "κ took over from τ: y = ({}) has A^T y >= 0 and b^T y < 0, so no x >= 0 solves A x = b",
+                y.join(", ")
+            ));
+        } else {
+            report.check_feasibility(&self.a_matrix, &self.b_vector, self.options.norm);
+        }
+        report
+    }
+
+    fn residuals(&self) -> (DVector<f64>, DVector<f64>, f64) {
+        let (a, b, c) = (&self.a_matrix, &self.b_vector, &self.c_vector);
+        let primal = a * &self.x_vector - b * self.tau;
+        let dual = a.transpose() * &self.y_vector - &self.z_vector - c * self.tau;
+        let gap = b.dot(&self.y_vector) - c.dot(&self.x_vector) + self.kappa;
+        (primal, dual, gap)
+    }
+
+    /// Relative primal, dual and gap residuals of (x, y) / τ.
+    fn relative_residuals(&self) -> (f64, f64, f64) {
+        let (primal, dual, _) = self.residuals();
+        let tau = self.tau;
+        let objective = self.c_vector.dot(&self.x_vector) / tau;
+        (
+            primal.norm() / tau / (1.0 + self.b_vector.norm()),
+            dual.norm() / tau / (1.0 + self.c_vector.norm()),
+            (self.b_vector.dot(&self.y_vector) / tau - objective).abs() / (1.0 + objective.abs()),
+        )
+    }
+
+    /// Stops on an optimum or a certificate, before the step that would
+    /// only polish it.
+    fn verdict(&self) -> Result<(), InteriorPointError> {
+        let (primal, dual, gap) = self.relative_residuals();
+        let worst = primal.max(dual).max(gap);
+        if worst <= STOPPING_TOLERANCE {
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+        let exhausted = self.mu() < MU_EXHAUSTED * self.start_mu;
+        let vanished = self.tau <= TAU_VANISHED * self.kappa.max(1.0);
+        if !exhausted && !vanished {
+            return Ok(());
+        }
+        if !vanished && worst <= NEAR_OPTIMAL {
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+        // τ ≈ 0: A x = 0, A^T y = z >= 0 and b^T y - c^T x = -κ < 0, so y
+        // or x proves the primal or the dual has no feasible point. Each is
+        // checked before it is believed: rounding also drives τ down.
+        if self.is_farkas_certificate() {
+            let scale = self.y_vector.amax();
+            let rows = (0..self.y_vector.len())
+                .filter(|&i| self.y_vector[i].abs() > FARKAS_TOLERANCE * scale)
+                .map(|i| (i, self.y_vector[i]))
+                .collect();
+            return Err(InteriorPointError::NotFeasible { rows });
+        }
+        if let Some(ray) = improving_ray(&self.a_matrix, &self.c_vector, &self.x_vector) {
+            return Err(InteriorPointError::Unbounded(ray));
+        }
+        if !vanished {
+            return Ok(());
+        }
+        Err(InteriorPointError::singular(
+            "A X Z^-1 A^T lost accuracy: τ vanished without a certificate of infeasibility or unboundedness",
+        ))
+    }
+
+    /// Whether y proves A x = b has no solution x >= 0: A^T y >= 0 and
+    /// b^T y < 0, each entry measured against its own terms.
+    fn is_farkas_certificate(&self) -> bool {
+        let (a, b, y) = (&self.a_matrix, &self.b_vector, &self.y_vector);
+        let reduced = a.transpose() * y;
+        let terms = a.abs().transpose() * y.abs();
+        let nonnegative = reduced
+            .iter()
+            .zip(terms.iter())
+            .all(|(r, t)| *r >= -FARKAS_TOLERANCE * t);
+        nonnegative && b.dot(y) < -FARKAS_TOLERANCE * b.abs().dot(&y.abs())
+    }
+}
+
+/// Change of every part of the embedding in one Newton step.
+struct Direction {
+    dx: DVector<f64>,
+    dy: DVector<f64>,
+    dz: DVector<f64>,
+    d_tau: f64,
+    d_kappa: f64,
+}
+
+/// Newton direction (dx, dy, dz, dτ, dκ) towards the point of the embedding
+/// with residuals scaled by 1 - σ and complementarity σ μ.
+///
+/// Eliminating dz and dκ leaves (A D A^T) dy = (A D c - b) dτ + A D h + η r_p
+/// with D = X Z^{-1}, so dy = p dτ + q for two solves with the same matrix,
+/// and the gap row then fixes dτ.
+fn newton_direction(problem: &HsdProblem, sigma: f64) -> Result<Direction, InteriorPointError> {
+    let (a, b, c) = (&problem.a_matrix, &problem.b_vector, &problem.c_vector);
+    let (x, z) = (&problem.x_vector, &problem.z_vector);
+    let (tau, kappa) = (problem.tau, problem.kappa);
+    let eta = 1.0 - sigma;
+    let target = sigma * problem.mu();
+    let (r_primal, r_dual, r_gap) = problem.residuals();
+
+    let d = x.component_div(z);
+    let h = x.map(|xj| target / xj) - z - &r_dual * eta;
+    let a_d = a * DMatrix::from_diagonal(&d);
+    let normal = (&a_d * a.transpose()).lu();
+    let singular = || InteriorPointError::singular("Cannot factor (A X Z^-1 A^T)");
+    let p = normal.solve(&(&a_d * c - b)).ok_or_else(singular)?;
+    let q = normal
+        .solve(&(&a_d * &h + &r_primal * eta))
+        .ok_or_else(singular)?;
+    let u = d.component_mul(&(c - a.transpose() * &p));
+    let v = d.component_mul(&(&h - a.transpose() * &q));
+
+    let denominator = b.dot(&p) - c.dot(&u) - kappa / tau;
+    if denominator.abs() < f64::EPSILON {
+        return Err(singular());
+    }
+    let d_tau = (-eta * r_gap - b.dot(&q) + c.dot(&v) - (target - tau * kappa) / tau) / denominator;
+    let dx = u * d_tau + v;
+    let dy = p * d_tau + q;
+    let dz = x.map(|xj| target / xj) - z - z.component_mul(&dx).component_div(x);
+    let d_kappa = (target - tau * kappa - kappa * d_tau) / tau;
+    Ok(Direction {
+        dx,
+        dy,
+        dz,
+        d_tau,
+        d_kappa,
+    })
+}
+
+/// Largest t <= 1 keeping every pair of (value, change) positive, damped by
+/// `alpha` when the boundary is closer than a full step.
+fn step_length(pairs: impl Iterator<Item = (f64, f64)>, alpha: f64) -> f64 {
+    let to_boundary = pairs
+        .filter(|&(_, change)| change < 0.0)
+        .map(|(value, change)| -value / change)
+        .fold(f64::INFINITY, f64::min);
+    (alpha * to_boundary).min(1.0)
+}
+
+impl Solver for HsdProblem {
+    type Iteration = HsdIteration;
+    type Error = InteriorPointError;
+
+    /// The embedding needs no feasible start, so Phase 1 is skipped.
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        Ok(HsdProblem::new(
+            model.a.clone(),
+            model.b.clone(),
+            model.internal_c(),
+            user_point(model),
+            model.options.alpha,
+            options.clone(),
+        ))
+    }
+
+    fn step(&mut self) -> Result<HsdIteration, InteriorPointError> {
+        let result = perform_hsd_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.current_x()
+    }
+}
+
+pub fn perform_hsd_iteration(problem: &mut HsdProblem) -> Result<HsdIteration, InteriorPointError> {
+    problem.verdict()?;
+    let Direction {
+        dx,
+        dy,
+        dz,
+        d_tau,
+        d_kappa,
+    } = newton_direction(problem, CENTERING)?;
+    let pairs = problem
+        .x_vector
+        .iter()
+        .zip(dx.iter())
+        .chain(problem.z_vector.iter().zip(dz.iter()))
+        .map(|(&value, &change)| (value, change))
+        .chain([(problem.tau, d_tau), (problem.kappa, d_kappa)]);
+    let step = step_length(pairs, problem.alpha);
+
+    let x = &problem.x_vector + &dx * step;
+    let z = &problem.z_vector + &dz * step;
+    check_finite(
+        problem.iterations,
+        &[
+            ("Δx", dx.as_slice()),
+            ("Δy", dy.as_slice()),
+            ("x", x.as_slice()),
+        ],
+    )?;
+    problem.x_vector = x;
+    problem.z_vector = z;
+    problem.y_vector += dy * step;
+    problem.tau += d_tau * step;
+    problem.kappa += d_kappa * step;
+    problem.iterations += 1;
+
+    let mu = problem.mu();
+    if problem.options.logs_full() {
+        logging::dump_vector!("x", &problem.x_vector);
+    }
+    if problem.options.logs_summary() {
+        tracing::info!(
+            step,
+            tau = problem.tau,
+            kappa = problem.kappa,
+            mu,
+            "Self-dual step t = {:.4}, tau = {:.3e}, kappa = {:.3e}, mu = {:.3e}",
+            step,
+            problem.tau,
+            problem.kappa,
+            mu
+        );
+    }
+
+    let (primal_residual, dual_residual, gap) = problem.relative_residuals();
+    Ok(HsdIteration {
+        mu,
+        tau: problem.tau,
+        kappa: problem.kappa,
+        step_length: step,
+        primal_residual,
+        dual_residual,
+        gap,
+        current_x: problem.current_x(),
+        duals: problem.duals(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{DMatrix, DVector};
+
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::{ConstraintSign, Sense, SolverMethod, ValidatedProblem};
+    use crate::report::SolveStatus;
+    use crate::test_models::{assert_optimum, covering, solve, wyndor};
+
+    #[test]
+    fn reaches_the_wyndor_optimum() {
+        let report = solve(&wyndor(), SolverMethod::SelfDual);
+        assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-5);
+    }
+
+    #[test]
+    fn minimizes_over_big_m_artificials() {
+        let report = solve(&covering(), SolverMethod::SelfDual);
+        assert_optimum(&report, 9.0, &[3.0, 1.0], 1e-5);
+    }
+
+    #[test]
+    fn proves_infeasibility_with_a_farkas_vector() {
+        // x1 + x2 = -1 has no solution x >= 0.
+        let model = ValidatedProblem::standard_form(
+            DMatrix::from_row_slice(1, 2, &[1.0, 1.0]),
+            DVector::from_vec(vec![-1.0]),
+            DVector::from_vec(vec![1.0, 1.0]),
+            Sense::Maximize,
+            vec![1.0, 1.0],
+            0.5,
+        );
+        let report = solve(&model, SolverMethod::SelfDual);
+        assert_eq!(report.status, SolveStatus::Infeasible);
+    }
+
+    #[test]
+    fn finds_an_improving_ray() {
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 1.0])
+            .constraint(&[1.0, -1.0], ConstraintSign::LessEqual, 1.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let report = solve(&model, SolverMethod::SelfDual);
+        assert_eq!(report.status, SolveStatus::Unbounded);
+    }
+}
@@ -0,0 +1,148 @@
+use nalgebra::{DMatrix, DVector};
+use nalgebra_sparse::CsrMatrix;
+use serde::{Deserialize, Serialize};
+
+use crate::interior::{InteriorPointProblem, StepStrategy};
+use crate::pretty;
+use crate::schema;
+use crate::stopping::StopReason;
+
+/// One hand edit of an affine-scaling run, applied between steps. Entries
+/// are in the space the solver works in, i.e. after equilibration.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StateEdit {
+    A { row: usize, col: usize, value: f64 },
+    B { row: usize, value: f64 },
+    C { col: usize, value: f64 },
+    X { col: usize, value: f64 },
+    Alpha(f64),
+    AdaptiveFraction(f64),
+    Strategy(StepStrategy),
+    IsAugmented(bool),
+}
+
+impl StateEdit {
+    /// Writes the edit into `problem`. Rejects entries that would break the
+    /// next step outright: NaN anywhere, x_j <= 0, and fractions outside
+    /// (0, 1]. A sparse copy of A is rebuilt so both stay the same matrix.
+    pub fn apply(&self, problem: &mut InteriorPointProblem) -> Result<(), String> {
+        let (m, n) = problem.a_matrix.shape();
+        let finite = |value: f64| {
+            if value.is_finite() {
+                Ok(value)
+            } else {
+                Err("entries must be finite numbers".to_string())
+            }
+        };
+        let fraction = |value: f64, name: &str| {
+            if value > 0.0 && value <= 1.0 {
+                Ok(value)
+            } else {
+                Err(format!("{} must be in (0, 1]", name))
+            }
+        };
+        match *self {
+            StateEdit::A { row, col, value } if row < m && col < n => {
+                problem.a_matrix[(row, col)] = finite(value)?;
+                if problem.sparse_a.is_some() {
+                    problem.sparse_a = Some(CsrMatrix::from(&problem.a_matrix));
+                }
+            }
+            StateEdit::B { row, value } if row < m => problem.b_vector[row] = finite(value)?,
+            StateEdit::C { col, value } if col < n => problem.c_vector[col] = finite(value)?,
+            StateEdit::X { col, value } if col < n => {
+                if finite(value)? <= 0.0 {
+                    return Err(format!("x{} must stay positive", col + 1));
+                }
+                // The double-double iterate restarts from x_vector once the
+                // two disagree.
+                problem.x_vector[col] = value;
+            }
+            StateEdit::Alpha(value) => problem.alpha = fraction(value, "α")?,
+            StateEdit::AdaptiveFraction(value) => {
+                problem.adaptive_fraction = fraction(value, "the adaptive fraction")?
+            }
+            StateEdit::Strategy(strategy) => problem.strategy = strategy,
+            StateEdit::IsAugmented(augmented) => problem.is_augmented = augmented,
+            _ => return Err(format!("{:?} is outside the {}×{} model", self, m, n)),
+        }
+        // Whatever stopped the run was about the old state.
+        problem.stop_reason = None;
+        problem.finished = false;
+        Ok(())
+    }
+}
+
+/// Everything an `InteriorPointProblem` holds, copied so a view can show it
+/// and compare it across renders.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InspectorState {
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    pub x: DVector<f64>,
+    pub alpha: f64,
+    pub adaptive_fraction: f64,
+    pub strategy: StepStrategy,
+    pub is_augmented: bool,
+    pub constraint_types: Vec<String>,
+    pub iterations: usize,
+    pub stop_reason: Option<StopReason>,
+    /// Whether A is also kept in CSR form.
+    pub sparse: bool,
+    /// Whether a double-double iterate is kept next to x.
+    pub extended: bool,
+    /// Whether A, b, c and x are equilibrated rather than the user's.
+    pub scaled: bool,
+    /// The solver options, as `{:#?}` prints them.
+    pub options: String,
+    /// The whole problem as a schema document, to reload it elsewhere.
+    pub json: String,
+}
+
+impl InspectorState {
+    pub fn capture(problem: &InteriorPointProblem) -> Self {
+        Self {
+            a: problem.a_matrix.clone(),
+            b: problem.b_vector.clone(),
+            c: problem.c_vector.clone(),
+            x: problem.x_vector.clone(),
+            alpha: problem.alpha,
+            adaptive_fraction: problem.adaptive_fraction,
+            strategy: problem.strategy,
+            is_augmented: problem.is_augmented,
+            constraint_types: problem.constraint_types.clone(),
+            iterations: problem.iterations,
+            stop_reason: problem.stop_reason,
+            sparse: problem.sparse_a.is_some(),
+            extended: problem.extended_x.is_some(),
+            scaled: problem.equilibration.is_some(),
+            options: format!("{:#?}", problem.options),
+            json: schema::to_json("problem", problem),
+        }
+    }
+
+    /// The whole state as text, for pasting into a bug report.
+    pub fn dump(&self) -> String {
+        let flag = |on: bool| if on { "yes" } else { "no" };
+        format!(
+            "iterations = {}\nstop reason = {}\nalpha = {}\nadaptive fraction = {}\nstrategy = {}\nis_augmented = {}\nsparse A = {}\ndouble-double x = {}\nequilibrated = {}\nconstraint types = [{}]\n{}\n{}\n{}\n{}\noptions = {}",
+            self.iterations,
+            self.stop_reason.map_or("none", |reason| reason.label()),
+            self.alpha,
+            self.adaptive_fraction,
+            self.strategy.label(),
+            flag(self.is_augmented),
+            flag(self.sparse),
+            flag(self.extended),
+            flag(self.scaled),
+            self.constraint_types.join(", "),
+            pretty::matrix("A", &self.a),
+            pretty::vector("b", &self.b),
+            pretty::vector("c", &self.c),
+            pretty::vector("x", &self.x),
+            self.options
+        )
+    }
+}
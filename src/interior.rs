@@ -1,37 +1,819 @@
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops::ControlFlow;
+
 use nalgebra::{DMatrix, DVector};
+use nalgebra_sparse::CsrMatrix;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::cancel::CancellationToken;
+use crate::equilibration::{Equilibration, Scaling};
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::norm::{Norm, Residuals};
+use crate::precision::{self, DoubleDouble, Precision, Real};
+use crate::pretty;
+use crate::rank;
+use crate::report::{now_ms, ImprovingRay, SolveReport, SolveStatus, Timings};
+use crate::schedule::{AlphaSchedule, MIN_SCHEDULED_ALPHA};
+use crate::solver::{starting_point, Solver};
+use crate::sparse;
+use crate::stopping::{StepMeasures, StopReason, StoppingCriteria};
 
-#[derive(Clone, PartialEq)]
+/// D, A~, L and P are empty (0×0) when the step was computed on the sparse
+/// path, which never forms them. P alone is empty when it was hidden or A
+/// has more than `PROJECTION_DISPLAY_LIMIT` columns.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub struct InteriorPointIteration {
+    #[serde(with = "crate::schema::matrix")]
     pub d_matrix: DMatrix<f64>,
+    #[serde(with = "crate::schema::matrix")]
     pub a_tilde_matrix: DMatrix<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub c_tilde_vector: DVector<f64>,
+    /// Cholesky factor of the normal matrix, A~ A~^T = L L^T.
+    #[serde(with = "crate::schema::matrix")]
+    pub l_matrix: DMatrix<f64>,
+    /// Condition number of A~ A~^T; above `CONDITION_WARNING` the projection
+    /// has lost most of its accuracy.
+    pub condition: f64,
+    #[serde(with = "crate::schema::matrix")]
     pub p_matrix: DMatrix<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub cp_vector: DVector<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub current_x: DVector<f64>,
+    /// A centering step: `cp_vector` holds P 1 instead of P c~.
+    pub centering: bool,
+    /// How the step length was chosen; `None` for centering steps.
+    pub step: Option<StepChoice>,
+    /// Floor of D and diagonal shift this step was computed with.
+    pub regularization: Regularization,
+    pub residuals: Residuals,
+    /// Dual estimate y with (A~ A~^T) y = A~ c~, from this step's D, in the
+    /// rows of the model as entered.
+    #[serde(with = "crate::schema::vector")]
+    pub duals: DVector<f64>,
+    /// b^T y - c^T x at `current_x`. Once y is dual feasible (A^T y >= c)
+    /// it bounds how far c^T x is from the optimum.
+    pub duality_gap: f64,
+}
+
+impl fmt::Debug for InteriorPointIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.centering { " (centering)" } else { "" };
+        writeln!(f, "InteriorPointIteration{}", kind)?;
+        if let Some(step) = &self.step {
+            writeln!(
+                f,
+                "{}: fraction {}, t = {}",
+                step.strategy.label(),
+                pretty::entry(step.fraction),
+                pretty::entry(step.length)
+            )?;
+        }
+        writeln!(f, "{}", pretty::matrix("D", &self.d_matrix))?;
+        writeln!(f, "{}", pretty::matrix("A~", &self.a_tilde_matrix))?;
+        writeln!(f, "{}", pretty::vector("c~", &self.c_tilde_vector))?;
+        writeln!(f, "{}", pretty::matrix("L", &self.l_matrix))?;
+        writeln!(f, "cond(A~ A~^T) = {}", pretty::entry(self.condition))?;
+        writeln!(f, "{}", pretty::matrix("P", &self.p_matrix))?;
+        writeln!(f, "{}", pretty::vector("P c~", &self.cp_vector))?;
+        writeln!(f, "{}", pretty::vector("y", &self.duals))?;
+        writeln!(f, "duality gap = {}", pretty::entry(self.duality_gap))?;
+        write!(f, "{}", pretty::vector("x", &self.current_x))
+    }
+}
+
+/// How much each iteration writes to the log. Dumping whole matrices is
+/// noticeably slow in wasm, so only `Full` does it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogVerbosity {
+    Off,
+    #[default]
+    Summary,
+    Full,
+}
+
+/// How affine scaling picks the step length t in x~ = 1 + t P c~. Serialized
+/// by `key`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepStrategy {
+    /// t = alpha / |P c~|: the scaled point stays in the ball of radius alpha
+    /// around 1 (the Dikin ellipsoid), the step the convergence proofs use.
+    /// Safe, but slow once far from the boundary.
+    #[serde(rename = "short")]
+    ShortStep,
+    /// A fraction alpha of the ratio-test step, the longest one that keeps
+    /// x >= 0 along the direction.
+    #[default]
+    #[serde(rename = "long")]
+    LongStep,
+    /// Long steps whose fraction starts at alpha and closes half the gap to
+    /// `ADAPTIVE_FRACTION_LIMIT` every iteration: cautious while far from the
+    /// optimum, aggressive in the slow tail.
+    #[serde(rename = "adaptive")]
+    Adaptive,
+    /// Backtracking from just short of the boundary, halving t until the
+    /// step raises the computed c^T x by enough, keeps x positive and does
+    /// not push A x off b.
+    #[serde(rename = "line-search")]
+    LineSearch,
+    /// Long steps whose fraction is tuned by `SolverOptions::schedule`:
+    /// a step failing the line-search tests is rejected and retried with a
+    /// smaller fraction, one that delivers its promised gain lets the next
+    /// fraction grow.
+    #[serde(rename = "schedule")]
+    Scheduled,
+}
+
+impl StepStrategy {
+    pub const ALL: [StepStrategy; 5] = [
+        StepStrategy::ShortStep,
+        StepStrategy::LongStep,
+        StepStrategy::Adaptive,
+        StepStrategy::LineSearch,
+        StepStrategy::Scheduled,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StepStrategy::ShortStep => "Short step",
+            StepStrategy::LongStep => "Long step",
+            StepStrategy::Adaptive => "Adaptive",
+            StepStrategy::LineSearch => "Line search",
+            StepStrategy::Scheduled => "α schedule",
+        }
+    }
+
+    /// Name used in repro exports and course packs.
+    pub fn key(&self) -> &'static str {
+        match self {
+            StepStrategy::ShortStep => "short",
+            StepStrategy::LongStep => "long",
+            StepStrategy::Adaptive => "adaptive",
+            StepStrategy::LineSearch => "line-search",
+            StepStrategy::Scheduled => "schedule",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|strategy| strategy.key() == key)
+    }
+}
+
+/// Fraction of the way to the boundary the adaptive strategy works up to.
+pub const ADAPTIVE_FRACTION_LIMIT: f64 = 0.95;
+
+/// Largest fraction of the ratio-test step any step takes, so x stays
+/// strictly positive even for alpha = 1.
+const MAX_STEP_FRACTION: f64 = 0.995;
+
+/// Fraction of the gain t c^T dx a line-search or scheduled step must
+/// actually deliver (the Armijo constant).
+const SUFFICIENT_GAIN: f64 = 1e-4;
+
+/// Growth of |A x - b|, relative to 1 + |b|, a line-search or scheduled
+/// step may cause.
+const ALLOWED_DRIFT: f64 = 1e-9;
+
+/// Halvings the line search tries before settling for the shortest step.
+const LINE_SEARCH_MAX_TRIALS: usize = 30;
+
+/// Columns above which P is not formed even for display: the n×n matrix
+/// would cost more than the step itself and is unreadable on a card.
+pub const PROJECTION_DISPLAY_LIMIT: usize = 50;
+
+/// Condition number of A~ A~^T above which an iteration is flagged: solves
+/// with the normal matrix then keep only about 16 - log10(cond) digits.
+pub const CONDITION_WARNING: f64 = 1e12;
+
+/// The step an iteration took, for display.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StepChoice {
+    pub strategy: StepStrategy,
+    /// alpha for the short and long steps; the current fraction for
+    /// adaptive; t over the ratio-test step for the line search.
+    pub fraction: f64,
+    /// t in x~ = 1 + t P c~.
+    pub length: f64,
+    /// The ratio-test step that would put a variable on the boundary.
+    pub to_boundary: f64,
+    /// Every t the line search or the α schedule tried, longest first; the
+    /// last is the one taken. Empty for the other strategies.
+    pub trials: Vec<f64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolverOptions {
+    pub log_verbosity: LogVerbosity,
+    /// Arithmetic of the affine-scaling direction and update.
+    pub precision: Precision,
+    /// Leaves P off the iteration cards. The steps never need it, so it is
+    /// then not formed at all.
+    pub hide_projection: bool,
+    /// Least entry of D = diag(x).
+    pub scale_floor: f64,
+    /// Shift added to the diagonal of A~ A~^T, relative to its largest
+    /// entry, when A~ has dependent rows: the normal matrix then will not
+    /// factor, and a plain QR factorization projects out one direction too
+    /// many.
+    pub normal_shift: f64,
+    /// When affine scaling stops.
+    pub stopping: StoppingCriteria,
+    /// Norm of the displayed residuals and the step-norm test.
+    pub norm: Norm,
+    /// How the α schedule step rule grows and shrinks its fraction.
+    pub schedule: AlphaSchedule,
+    /// After an interior-point run reaches an optimum, solves the model
+    /// again with the tableau simplex and compares the objectives.
+    pub cross_check: bool,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        Self {
+            log_verbosity: LogVerbosity::default(),
+            precision: Precision::default(),
+            hide_projection: false,
+            scale_floor: DEFAULT_SCALE_FLOOR,
+            normal_shift: DEFAULT_NORMAL_SHIFT,
+            stopping: StoppingCriteria::default(),
+            norm: Norm::default(),
+            schedule: AlphaSchedule::default(),
+            cross_check: false,
+        }
+    }
 }
 
+/// Default `SolverOptions::scale_floor`.
+pub const DEFAULT_SCALE_FLOOR: f64 = 1e-8;
+
+/// Default `SolverOptions::normal_shift`; enough to factor A~ A~^T when A has
+/// dependent rows.
+pub const DEFAULT_NORMAL_SHIFT: f64 = 1e-8;
+
+/// The regularization one step used, kept with it for display.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Regularization {
+    pub floor: f64,
+    /// The shift allowed by the options.
+    pub shift: f64,
+    /// The shift the factorization actually needed; `None` when the dense
+    /// path's QR factorization needed none, which it only does for
+    /// dependent rows.
+    pub applied_shift: Option<f64>,
+}
+
+impl SolverOptions {
+    pub fn regularization(&self) -> Regularization {
+        Regularization {
+            floor: self.scale_floor,
+            shift: self.normal_shift,
+            applied_shift: None,
+        }
+    }
+
+    pub fn logs_summary(&self) -> bool {
+        self.log_verbosity >= LogVerbosity::Summary
+    }
+
+    pub fn logs_full(&self) -> bool {
+        self.log_verbosity >= LogVerbosity::Full
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct InteriorPointProblem {
+    #[serde(with = "crate::schema::matrix")]
     pub a_matrix: DMatrix<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub b_vector: DVector<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub c_vector: DVector<f64>,
+    #[serde(with = "crate::schema::vector")]
     pub x_vector: DVector<f64>,
     pub alpha: f64,
     pub constraint_types: Vec<String>,
     pub is_augmented: bool,
+    pub options: SolverOptions,
+    pub strategy: StepStrategy,
+    /// Fraction the adaptive and scheduled strategies use next; starts at
+    /// `alpha`.
+    pub adaptive_fraction: f64,
+    /// CSR copy of A for large, mostly zero models; the steps then project
+    /// through it instead of forming the dense A D and P.
+    #[serde(with = "crate::schema::sparse")]
+    pub sparse_a: Option<CsrMatrix<f64>>,
+    /// The iterate in double-double when `options.precision` asks for it;
+    /// `x_vector` is its rounding.
+    #[serde(with = "crate::schema::option_vector")]
+    pub extended_x: Option<DVector<DoubleDouble>>,
+    /// Row and column scaling applied by `with_scaling`. A, b, c and x above
+    /// are then those of the scaled model; iterations and reports convert
+    /// back to the original units.
+    pub equilibration: Option<Equilibration>,
+    /// Affine-scaling steps taken, counted against
+    /// `options.stopping.max_iterations`.
+    pub iterations: usize,
+    /// The criterion that stopped the run, once one has.
+    pub stop_reason: Option<StopReason>,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
 }
 
-#[derive(Debug)]
+impl InteriorPointProblem {
+    /// Checks that A is m×n, b has m entries and c and x have n, so a
+    /// malformed model fails here with a message instead of panicking inside
+    /// nalgebra mid-iteration.
+    pub fn new(
+        a_matrix: DMatrix<f64>,
+        b_vector: DVector<f64>,
+        c_vector: DVector<f64>,
+        x_vector: DVector<f64>,
+        alpha: f64,
+        options: SolverOptions,
+    ) -> Result<Self, InteriorPointError> {
+        let sparse_a = sparse::prefers_sparse(&a_matrix).then(|| CsrMatrix::from(&a_matrix));
+        let problem = Self {
+            a_matrix,
+            b_vector,
+            c_vector,
+            x_vector,
+            alpha,
+            constraint_types: vec![],
+            is_augmented: false,
+            options,
+            strategy: StepStrategy::default(),
+            adaptive_fraction: alpha,
+            sparse_a,
+            extended_x: None,
+            equilibration: None,
+            iterations: 0,
+            stop_reason: None,
+            finished: false,
+        };
+        problem.check_dimensions()?;
+        Ok(problem)
+    }
+
+    /// The double-double iterate, restarted from `x_vector` when that was
+    /// set from outside since the last step.
+    fn extended_iterate(&mut self) -> DVector<DoubleDouble> {
+        match &self.extended_x {
+            Some(x) if x.map(Real::to_f64) == self.x_vector => x.clone(),
+            _ => self.x_vector.map(DoubleDouble::from_f64),
+        }
+    }
+
+    pub fn with_strategy(mut self, strategy: StepStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_stopping(mut self, stopping: StoppingCriteria) -> Self {
+        self.options.stopping = stopping;
+        self
+    }
+
+    /// Equilibrates A with `method` before the first step. Keeps the model
+    /// as it is when the scaling would change nothing. In exact arithmetic
+    /// affine scaling takes the same steps either way, since D absorbs the
+    /// column factors and P ignores the row factors; equilibrating changes
+    /// only how rounding enters the projection.
+    pub fn with_scaling(mut self, method: Scaling) -> Self {
+        let scaling = Equilibration::compute(&self.a_matrix, method);
+        if scaling.is_identity() {
+            return self;
+        }
+        if self.options.logs_summary() {
+            tracing::info!(
+                method = method.label(),
+                spread_before = scaling.spread_before,
+                spread_after = scaling.spread_after,
+                "{} scaling: |a_ij| spread {:.3e} -> {:.3e}",
+                method.label(),
+                scaling.spread_before,
+                scaling.spread_after
+            );
+        }
+        self.a_matrix = scaling.scale_matrix(&self.a_matrix);
+        self.b_vector = scaling.scale_rhs(&self.b_vector);
+        self.c_vector = scaling.scale_cost(&self.c_vector);
+        self.x_vector = scaling.scale_x(&self.x_vector);
+        self.sparse_a = self
+            .sparse_a
+            .as_ref()
+            .map(|_| CsrMatrix::from(&self.a_matrix));
+        self.equilibration = Some(scaling);
+        self
+    }
+
+    /// Steps a run to the end may still take: what is left of the stopping
+    /// criteria's `max_iterations`, or `SOLVE_ITERATION_LIMIT` without one.
+    pub fn remaining_iterations(&self) -> usize {
+        self.options
+            .stopping
+            .max_iterations
+            .map_or(SOLVE_ITERATION_LIMIT, |limit| {
+                limit.saturating_sub(self.iterations)
+            })
+    }
+
+    /// The iterate in the units the model was entered in.
+    pub fn original_x(&self) -> DVector<f64> {
+        self.to_original(&self.x_vector)
+    }
+
+    fn to_original(&self, x: &DVector<f64>) -> DVector<f64> {
+        match &self.equilibration {
+            Some(scaling) => scaling.unscale_x(x),
+            None => x.clone(),
+        }
+    }
+
+    /// KKT residuals at the current iterate for the dual estimate `y` of
+    /// the scaled model, and ‖x - previous‖, all in the original units.
+    /// Row scaling is undone on A x - b and column scaling on s = A^T y - c,
+    /// so they read the same with or without equilibration; X s needs
+    /// neither, as x' = C^-1 x and s' = C s.
+    fn residuals(&self, previous: &DVector<f64>, y: &DVector<f64>) -> Residuals {
+        let norm = self.options.norm;
+        let residual = &self.a_matrix * &self.x_vector - &self.b_vector;
+        let slack = self.a_matrix.tr_mul(y) - &self.c_vector;
+        let complementarity = norm.of(&self.x_vector.component_mul(&slack));
+        let (residual, slack) = match &self.equilibration {
+            Some(scaling) => (scaling.unscale_rhs(&residual), scaling.unscale_cost(&slack)),
+            None => (residual, slack),
+        };
+        Residuals {
+            norm,
+            primal: norm.of(&residual),
+            dual: norm.of(&slack.map(|s| s.min(0.0))),
+            complementarity,
+            step: norm.of(&self.to_original(&(&self.x_vector - previous))),
+        }
+    }
+
+    /// `y` of the scaled model moved back to the original rows, with the
+    /// gap b^T y - c^T x at the current iterate. The gap is the same in
+    /// either model, since b'^T y' = b^T R y' and c'^T x' = c^T x.
+    fn dual_estimate(&self, y: DVector<f64>) -> (DVector<f64>, f64) {
+        let gap = self.b_vector.dot(&y) - self.c_vector.dot(&self.x_vector);
+        let y = match &self.equilibration {
+            Some(scaling) => scaling.unscale_duals(&y),
+            None => y,
+        };
+        (y, gap)
+    }
+
+    /// P for the iteration card, or 0×0 when it is hidden or too large to
+    /// be worth forming.
+    fn displayed_projection(&self, q: &DMatrix<f64>) -> DMatrix<f64> {
+        if self.options.hide_projection || q.nrows() > PROJECTION_DISPLAY_LIMIT {
+            DMatrix::zeros(0, 0)
+        } else {
+            projection_matrix(q)
+        }
+    }
+
+    /// Restarts from `x`, given in the original units.
+    pub fn set_start(&mut self, x: DVector<f64>) {
+        self.x_vector = match &self.equilibration {
+            Some(scaling) => scaling.scale_x(&x),
+            None => x,
+        };
+    }
+
+    /// A, b and c as entered, before any scaling.
+    pub fn original_model(&self) -> (DMatrix<f64>, DVector<f64>, DVector<f64>) {
+        match &self.equilibration {
+            Some(scaling) => (
+                scaling.unscale_matrix(&self.a_matrix),
+                scaling.unscale_rhs(&self.b_vector),
+                scaling.unscale_cost(&self.c_vector),
+            ),
+            None => (
+                self.a_matrix.clone(),
+                self.b_vector.clone(),
+                self.c_vector.clone(),
+            ),
+        }
+    }
+
+    /// Report at the current iterate, in the original units; `ray` is an
+    /// improving direction already converted back.
+    pub fn report(
+        &self,
+        status: SolveStatus,
+        iterations: usize,
+        ray: Option<DVector<f64>>,
+    ) -> SolveReport {
+        let (a, b, c) = self.original_model();
+        let x = self.original_x();
+        let mut report = SolveReport::new(status, iterations, x.clone(), &c);
+        report.duals = estimate_duals(&a, &c, &x);
+        report.ray = ray.map(|direction| ImprovingRay::new(direction, &c));
+        report.check_feasibility(&a, &b, self.options.norm);
+        report.scaling = self.equilibration.clone();
+        report.stop_reason = self.stop_reason;
+        report
+    }
+
+    fn stop(&mut self, reason: StopReason) -> InteriorPointError {
+        self.stop_reason = Some(reason);
+        InteriorPointError::Stopped(reason)
+    }
+
+    /// Step length t along P c~ under the chosen strategy, given the
+    /// direction dx = D P c~ and the ratio-test step `to_boundary`.
+    /// Advances the adaptive fraction.
+    fn choose_step(
+        &mut self,
+        cp: &DVector<f64>,
+        dx: &DVector<f64>,
+        to_boundary: f64,
+    ) -> StepChoice {
+        let (fraction, length, trials) = match self.strategy {
+            StepStrategy::ShortStep => (self.alpha, self.alpha / cp.norm(), vec![]),
+            StepStrategy::LongStep => (self.alpha, self.alpha * to_boundary, vec![]),
+            StepStrategy::Adaptive => {
+                let fraction = self.adaptive_fraction;
+                if fraction < ADAPTIVE_FRACTION_LIMIT {
+                    self.adaptive_fraction += (ADAPTIVE_FRACTION_LIMIT - fraction) / 2.0;
+                }
+                (fraction, fraction * to_boundary, vec![])
+            }
+            StepStrategy::LineSearch => {
+                let trials = self.line_search(dx, to_boundary);
+                let length = trials[trials.len() - 1];
+                (length / to_boundary, length, trials)
+            }
+            StepStrategy::Scheduled => {
+                let (fraction, trials) = self.scheduled_step(dx, to_boundary);
+                (fraction, fraction * to_boundary, trials)
+            }
+        };
+        StepChoice {
+            strategy: self.strategy,
+            fraction,
+            length: length.min(MAX_STEP_FRACTION * to_boundary),
+            to_boundary,
+            trials,
+        }
+    }
+
+    /// Backtracks along dx from `MAX_STEP_FRACTION` of `to_boundary`,
+    /// halving t until `step_progress` accepts x + t dx. Returns the
+    /// lengths tried; the last is accepted, or is simply the shortest when
+    /// `LINE_SEARCH_MAX_TRIALS` halvings found none.
+    fn line_search(&self, dx: &DVector<f64>, to_boundary: f64) -> Vec<f64> {
+        let mut trials = Vec::new();
+        let mut length = MAX_STEP_FRACTION * to_boundary;
+        for _ in 0..LINE_SEARCH_MAX_TRIALS {
+            trials.push(length);
+            if self.step_progress(dx, length).is_some() {
+                break;
+            }
+            length /= 2.0;
+        }
+        if self.options.logs_summary() && trials.len() > 1 {
+            tracing::info!(
+                length,
+                trials = trials.len(),
+                "Line search: accepted t = {:.4e} after {} trials",
+                length,
+                trials.len()
+            );
+        }
+        trials
+    }
+
+    /// The fraction of `to_boundary` the α schedule steps with, and the
+    /// lengths it tried. Shrinks the fraction after every step
+    /// `step_progress` rejects, down to `MIN_SCHEDULED_ALPHA`, and leaves
+    /// the fraction for the next iteration where the schedule puts it.
+    fn scheduled_step(&mut self, dx: &DVector<f64>, to_boundary: f64) -> (f64, Vec<f64>) {
+        let schedule = self.options.schedule;
+        let mut fraction = self.adaptive_fraction.min(MAX_STEP_FRACTION);
+        let mut trials = Vec::new();
+        loop {
+            trials.push(fraction * to_boundary);
+            match self.step_progress(dx, fraction * to_boundary) {
+                Some(progress) => {
+                    self.adaptive_fraction = schedule.after_accepted(fraction, progress);
+                    break;
+                }
+                None if fraction <= MIN_SCHEDULED_ALPHA => {
+                    self.adaptive_fraction = fraction;
+                    break;
+                }
+                None => fraction = schedule.after_rejected(fraction),
+            }
+        }
+        if self.options.logs_summary() && trials.len() > 1 {
+            tracing::info!(
+                rejected = trials.len() - 1,
+                alpha = fraction,
+                "α schedule: {} rejected step{}, α = {:.4}",
+                trials.len() - 1,
+                if trials.len() == 2 { "" } else { "s" },
+                fraction
+            );
+        }
+        (fraction, trials)
+    }
+
+    /// Gain in c^T x at x + t dx over the t c^T dx the direction promises,
+    /// or `None` when the step is rejected: some x_i is no longer positive,
+    /// the gain is under `SUFFICIENT_GAIN` of the promise, or A x moves
+    /// more than `ALLOWED_DRIFT` further off b. Checks the objective
+    /// actually gained rather than trusting t |P c~|^2, which rounding in
+    /// P c~ can overstate.
+    fn step_progress(&self, dx: &DVector<f64>, length: f64) -> Option<f64> {
+        let drift = |x: &DVector<f64>| (&self.a_matrix * x - &self.b_vector).amax();
+        let allowed = drift(&self.x_vector) + ALLOWED_DRIFT * (1.0 + self.b_vector.amax());
+        let x = &self.x_vector + length * dx;
+        let promised = length * self.c_vector.dot(dx);
+        let progress = (self.c_vector.dot(&x) - self.c_vector.dot(&self.x_vector)) / promised;
+        (x.min() > 0.0 && progress >= SUFFICIENT_GAIN && drift(&x) <= allowed).then_some(progress)
+    }
+
+    pub fn check_dimensions(&self) -> Result<(), InteriorPointError> {
+        let (m, n) = self.a_matrix.shape();
+        let mismatch = |vector: &'static str, len: usize, expected: usize| {
+            InteriorPointError::DimensionMismatch {
+                vector,
+                len,
+                expected,
+                shape: (m, n),
+            }
+        };
+        if self.b_vector.len() != m {
+            return Err(mismatch("b", self.b_vector.len(), m));
+        }
+        if self.c_vector.len() != n {
+            return Err(mismatch("c", self.c_vector.len(), n));
+        }
+        if self.x_vector.len() != n {
+            return Err(mismatch("x", self.x_vector.len(), n));
+        }
+        Ok(())
+    }
+}
+
+/// Why a step was not taken, with what the UI needs to point at the rows,
+/// columns and values involved.
+#[derive(Debug, Error)]
 pub enum InteriorPointError {
+    #[error("no improving direction is left")]
     NoImprovement,
-    NotFeasible,
-    SingularMatrix(String),
+    /// Affine scaling met one of its stopping criteria.
+    #[error("stopping criterion met: {}", .0.label())]
+    Stopped(StopReason),
+    /// The objective improves without bound along this ray: A d = 0, d >= 0
+    /// and c^T d > 0. Scaled so its largest entry is 1.
+    #[error("the objective is unbounded along an improving ray")]
+    Unbounded(DVector<f64>),
+    /// No x > 0 satisfies A x = b. `rows` are the rows that conflict, when
+    /// the solver can tell, each with how much: the residual (b - A x)_i
+    /// Phase 1 could not remove, or the weight y_i of a Farkas certificate.
+    #[error("no strictly positive x satisfies A x = b{}", describe_rows(rows))]
+    NotFeasible { rows: Vec<(usize, f64)> },
+    /// x_`column` reached `value` <= 0, leaving the interior a barrier
+    /// method needs.
+    #[error("x{} = {value:e} left the interior", column + 1)]
+    NotInterior { column: usize, value: f64 },
+    /// `matrix` could not be factored. `iteration` is the step it failed
+    /// on and `rows` the rows of A that depend on others, once known.
+    #[error("{matrix}{}", iteration.map_or(String::new(), |k| format!(" (iteration {})", k)))]
+    SingularMatrix {
+        matrix: String,
+        iteration: Option<usize>,
+        rows: Vec<usize>,
+    },
+    /// `vector` has `len` entries where the `shape` of A needs `expected`.
+    #[error(
+        "{vector} has {len} entries but A is {}×{}, so it needs {expected} (one per {})",
+        shape.0,
+        shape.1,
+        if *vector == "b" { "row" } else { "column" }
+    )]
+    DimensionMismatch {
+        vector: &'static str,
+        len: usize,
+        expected: usize,
+        shape: (usize, usize),
+    },
+    /// Entry `index` of `quantity` (column-major for matrices) in the step
+    /// after `iteration` steps is `value`, a NaN or infinity, which would
+    /// otherwise carry into every later x.
+    #[error("{quantity} became {value} at entry {index} in iteration {iteration}")]
+    NumericalBreakdown {
+        iteration: usize,
+        quantity: &'static str,
+        index: usize,
+        value: f64,
+    },
+}
+
+fn describe_rows(rows: &[(usize, f64)]) -> String {
+    let rows: Vec<String> = rows
+        .iter()
+        .map(|(i, residual)| format!("row {} (residual {:.3e})", i + 1, residual))
+        .collect();
+    match rows.is_empty() {
+        true => String::new(),
+        false => format!(": {}", rows.join(", ")),
+    }
+}
+
+impl InteriorPointError {
+    /// A factorization of `matrix` failed; the caller fills in where.
+    pub fn singular(matrix: impl Into<String>) -> Self {
+        InteriorPointError::SingularMatrix {
+            matrix: matrix.into(),
+            iteration: None,
+            rows: vec![],
+        }
+    }
+
+    /// The step the error stopped the run at, if it says.
+    pub fn iteration(&self) -> Option<usize> {
+        match self {
+            InteriorPointError::NumericalBreakdown { iteration, .. } => Some(*iteration),
+            InteriorPointError::SingularMatrix { iteration, .. } => *iteration,
+            _ => None,
+        }
+    }
+
+    /// Rows of A the error is about, ascending.
+    pub fn rows(&self) -> Vec<usize> {
+        match self {
+            InteriorPointError::NotFeasible { rows } => rows.iter().map(|&(i, _)| i).collect(),
+            InteriorPointError::SingularMatrix { rows, .. } => rows.clone(),
+            _ => vec![],
+        }
+    }
+
+    /// Adds the step and the dependent rows of `a` to a failed
+    /// factorization that does not name them yet.
+    fn locate(mut self, step: usize, a: &DMatrix<f64>, b: &DVector<f64>) -> Self {
+        if let InteriorPointError::SingularMatrix {
+            iteration, rows, ..
+        } = &mut self
+        {
+            iteration.get_or_insert(step);
+            if rows.is_empty() {
+                *rows =
+                    rank::diagnose(a, b).map_or(vec![], |deficiency| deficiency.dependent_rows());
+            }
+        }
+        self
+    }
+}
+
+/// Fails on the first of the named `quantities` with a NaN or infinite
+/// entry.
+pub(crate) fn check_finite(
+    iteration: usize,
+    quantities: &[(&'static str, &[f64])],
+) -> Result<(), InteriorPointError> {
+    for &(quantity, values) in quantities {
+        if let Some(index) = values.iter().position(|val| !val.is_finite()) {
+            tracing::error!(
+                iteration,
+                quantity,
+                index,
+                "{} is not finite at iteration {}",
+                quantity,
+                iteration
+            );
+            return Err(InteriorPointError::NumericalBreakdown {
+                iteration,
+                quantity,
+                index,
+                value: values[index],
+            });
+        }
+    }
+    Ok(())
 }
 
-pub fn create_d_matrix(x: &DVector<f64>) -> DMatrix<f64> {
+/// Relative size of each (A d)_i below which, and of c^T d above which, a
+/// step direction d >= 0 counts as an improving ray.
+const UNBOUNDED_RAY_TOLERANCE: f64 = 1e-7;
+
+/// D = diag(x), with every entry raised to at least `floor` so A~ keeps
+/// its columns as x approaches the boundary.
+pub fn create_d_matrix(x: &DVector<f64>, floor: f64) -> DMatrix<f64> {
     let n = x.len();
     let mut d = DMatrix::zeros(n, n);
     for i in 0..n {
-        d[(i, i)] = x[i].max(1e-8);
+        d[(i, i)] = x[i].max(floor);
     }
     d
 }
@@ -44,67 +826,814 @@ pub fn calculate_c_tilde(c: &DVector<f64>, d: &DMatrix<f64>) -> DVector<f64> {
     d * c
 }
 
+/// P = I - A~^T (A~ A~^T)^{-1} A~, computed as I - Q Q^T from a QR
+/// factorization A~^T = Q R. Near a degenerate vertex A~ A~^T is too
+/// ill-conditioned to invert accurately, and the error in A~ P c~ then grows
+/// with the step length; Q is orthonormal however small the entries of x
+/// get.
 pub fn calculate_p_matrix(a_tilde: &DMatrix<f64>) -> Result<DMatrix<f64>, InteriorPointError> {
-    let n = a_tilde.ncols();
-    let i_n = DMatrix::identity(n, n);
+    calculate_projection(a_tilde).map(|(p, _)| p)
+}
+
+/// P together with the Cholesky factor L of A~ A~^T, both from the QR
+/// factorization behind `calculate_p_matrix`: A~ A~^T = R^T R, so L is R^T
+/// with each column's sign flipped to make the diagonal nonnegative. Reading
+/// L off R avoids forming A~ A~^T, which squares the condition number of A~.
+pub fn calculate_projection(
+    a_tilde: &DMatrix<f64>,
+) -> Result<(DMatrix<f64>, DMatrix<f64>), InteriorPointError> {
+    let (q, l) = calculate_range_basis(a_tilde)?;
+    Ok((projection_matrix(&q), l))
+}
 
-    let a_tilde_t = a_tilde.transpose();
-    let mtx = a_tilde * &a_tilde_t + DMatrix::identity(a_tilde.nrows(), a_tilde.nrows()) * 1e-8;
+/// The thin factor Q of A~^T = Q R, an orthonormal basis of the row space of
+/// A~, and the Cholesky factor L of A~ A~^T read off R as in
+/// `calculate_projection`. With Q, P v = v - Q (Q^T v) costs two n×m
+/// products, so P itself is never needed.
+pub fn calculate_range_basis(
+    a_tilde: &DMatrix<f64>,
+) -> Result<(DMatrix<f64>, DMatrix<f64>), InteriorPointError> {
+    let qr = a_tilde.transpose().qr();
+    let q = qr.q();
+    let mut l = qr.r().transpose();
+    for (k, mut column) in l.column_iter_mut().enumerate() {
+        if column[k] < 0.0 {
+            column.neg_mut();
+        }
+    }
+    if q.iter().chain(l.iter()).all(|val| val.is_finite()) {
+        Ok((q, l))
+    } else {
+        Err(InteriorPointError::singular(
+            "A_tilde has entries that are not finite",
+        ))
+    }
+}
 
-    let mtx_inv = mtx.try_inverse().ok_or_else(|| {
-        InteriorPointError::SingularMatrix("Cannot invert (A_tilde * A_tilde^T)".to_string())
-    })?;
+/// A diagonal entry l_kk of L at most this share of the length of row k of
+/// A~ marks that row as dependent on the ones before it: QR leaves such a
+/// row rounding, not zero. Measured row by row, rows that are merely short,
+/// as near a vertex or with coefficients far apart, stay above it.
+pub(crate) const DEPENDENT_ROW_TOLERANCE: f64 = 1e-10;
 
-    let p = i_n - a_tilde_t * mtx_inv * a_tilde;
-    Ok(p)
+/// Q and L of `calculate_range_basis`, and the relative shift behind them.
+pub type RangeBasis = (DMatrix<f64>, DMatrix<f64>, Option<f64>);
+
+/// `calculate_range_basis`, or when A~ has dependent rows and `shift` is
+/// positive, the same factors of the stacked [A~^T; s I], with s^2 = `shift`
+/// times the largest entry of A~ A~^T. Then L L^T = A~ A~^T + s^2 I, and the
+/// top n rows Q of the orthonormal factor are A~^T R^{-1}, so I - Q Q^T is
+/// the shifted projection the sparse path and double-double factor. The
+/// shift used is `None` when there was no need for one.
+pub fn regularized_range_basis(
+    a_tilde: &DMatrix<f64>,
+    shift: f64,
+) -> Result<RangeBasis, InteriorPointError> {
+    let (q, l) = calculate_range_basis(a_tilde)?;
+    let (m, n) = a_tilde.shape();
+    let largest = a_tilde
+        .row_iter()
+        .map(|row| row.norm_squared())
+        .fold(0.0, f64::max);
+    let dependent = l
+        .diagonal()
+        .iter()
+        .zip(a_tilde.row_iter())
+        .any(|(l_kk, row)| l_kk.abs() <= DEPENDENT_ROW_TOLERANCE * row.norm());
+    if !dependent || shift <= 0.0 || largest == 0.0 {
+        return Ok((q, l, None));
+    }
+    let mut stacked = DMatrix::zeros(n + m, m);
+    stacked.rows_mut(0, n).copy_from(&a_tilde.transpose());
+    stacked
+        .rows_mut(n, m)
+        .fill_diagonal((shift * largest.max(1.0)).sqrt());
+    let (q, l) = calculate_range_basis(&stacked.transpose())?;
+    Ok((q.rows(0, n).into_owned(), l, Some(shift)))
+}
+
+/// P v = v - Q (Q^T v) for the basis Q of `calculate_range_basis`.
+pub fn project_onto_null_space(q: &DMatrix<f64>, v: &DVector<f64>) -> DVector<f64> {
+    v - q * q.tr_mul(v)
+}
+
+/// y with (A~ A~^T) y = A~ v, by L^T y = Q^T v for the factors of
+/// `calculate_range_basis`; A~^T y is the part of v that P removes.
+pub fn range_multipliers(q: &DMatrix<f64>, l: &DMatrix<f64>, v: &DVector<f64>) -> DVector<f64> {
+    l.transpose()
+        .solve_upper_triangular(&q.tr_mul(v))
+        .unwrap_or_else(|| DVector::zeros(l.ncols()))
+}
+
+/// P = I - Q Q^T, formed only to be shown.
+fn projection_matrix(q: &DMatrix<f64>) -> DMatrix<f64> {
+    let n = q.nrows();
+    DMatrix::identity(n, n) - q * q.transpose()
+}
+
+/// 2-norm condition number of A~ A~^T = L L^T, (σ_max(L) / σ_min(L))^2,
+/// from the singular values of the m×m factor; infinite when L is singular.
+pub fn normal_condition(l: &DMatrix<f64>) -> f64 {
+    if l.is_empty() {
+        return 1.0;
+    }
+    let sigma = l.clone().singular_values();
+    let (largest, smallest) = (sigma.max(), sigma.min());
+    if smallest > 0.0 {
+        (largest / smallest).powi(2)
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn warn_if_ill_conditioned(condition: f64) {
+    if condition > CONDITION_WARNING {
+        tracing::warn!(
+            condition,
+            "cond(A~ A~^T) = {:.3e}: the projection is losing accuracy",
+            condition
+        );
+    }
 }
 
 pub fn calculate_cp_vector(p: &DMatrix<f64>, c_tilde: &DVector<f64>) -> DVector<f64> {
     p * c_tilde
 }
 
+/// The nonnegative part of the step direction D P c~, scaled to a largest
+/// entry of 1, when it is a ray along which c^T x grows forever. When P c~ >= 0
+/// the direction itself is one. When the iterate runs off along a ray, the
+/// shrinking components vanish next to the growing ones and what remains is
+/// the ray.
+pub(crate) fn improving_ray(
+    a: &DMatrix<f64>,
+    c: &DVector<f64>,
+    direction: &DVector<f64>,
+) -> Option<DVector<f64>> {
+    let scale = direction.max();
+    if scale <= 0.0 || !scale.is_finite() {
+        return None;
+    }
+    let ray = direction.map(|val| (val / scale).max(0.0));
+    // Each row is measured against its own terms |a_i| d, not the largest
+    // entry of A: with rows scaled 1e12 apart, a residual that is rounding
+    // in the large rows would swamp a real violation of the small ones.
+    let residual = a * &ray;
+    let terms = a.abs() * &ray;
+    let in_null_space = residual
+        .iter()
+        .zip(terms.iter())
+        .all(|(r, t)| r.abs() <= UNBOUNDED_RAY_TOLERANCE * t);
+    let gain = c.dot(&ray);
+    (in_null_space && gain > UNBOUNDED_RAY_TOLERANCE * (1.0 + c.amax())).then_some(ray)
+}
+
+/// Ratio test: the largest t with x + t dx >= 0, i.e. the minimum of
+/// x_i / -dx_i over the decreasing components. None when no component
+/// decreases, so the direction never reaches the boundary.
+pub fn ratio_test(x: &DVector<f64>, dx: &DVector<f64>) -> Option<f64> {
+    x.iter()
+        .zip(dx.iter())
+        .filter(|(_, &step)| step < 0.0)
+        .map(|(&xi, &step)| xi.max(0.0) / -step)
+        .reduce(f64::min)
+}
+
+impl Solver for InteriorPointProblem {
+    type Iteration = InteriorPointIteration;
+    type Error = InteriorPointError;
+
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        Ok(InteriorPointProblem::new(
+            model.a.clone(),
+            model.b.clone(),
+            model.internal_c(),
+            starting_point(model, options)?,
+            model.options.alpha,
+            options.clone(),
+        )
+        .map_err(|e| e.to_string())?
+        .with_strategy(model.options.step_strategy)
+        .with_scaling(model.options.scaling))
+    }
+
+    fn step(&mut self) -> Result<InteriorPointIteration, InteriorPointError> {
+        let result = perform_interior_point_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.original_x()
+    }
+}
+
 pub fn perform_interior_point_iteration(
     problem: &mut InteriorPointProblem,
 ) -> Result<InteriorPointIteration, InteriorPointError> {
-    log::info!("Iteration start: x = {:?}", problem.x_vector);
+    affine_step(problem)
+        .map_err(|e| e.locate(problem.iterations, &problem.a_matrix, &problem.b_vector))
+}
 
-    let d = create_d_matrix(&problem.x_vector);
+fn affine_step(
+    problem: &mut InteriorPointProblem,
+) -> Result<InteriorPointIteration, InteriorPointError> {
+    problem.check_dimensions()?;
+    if problem
+        .options
+        .stopping
+        .out_of_iterations(problem.iterations)
+    {
+        return Err(problem.stop(StopReason::IterationLimit));
+    }
+    let _span = tracing::info_span!("affine_step", iteration = problem.iterations).entered();
+    if problem.options.logs_full() {
+        logging::dump_vector!("x", &problem.x_vector);
+    }
+
+    // The diagonal of D.
+    let mut regularization = problem.options.regularization();
+    let scale = problem.x_vector.map(|val| val.max(regularization.floor));
+    let c_tilde = scale.component_mul(&problem.c_vector);
+
+    // Long steps multiply any error in A~ P c~ = 0 by t, which grows as x
+    // approaches a vertex and A~ loses columns to the floor of D.
+    // Projecting a second time removes what rounding left of A~ c~.
+    let (d, a_tilde, l, condition, p, cp, y) = match &problem.sparse_a {
+        Some(a) => {
+            let normal = sparse::NormalFactor::new(a, &scale, regularization.shift)?;
+            regularization.applied_shift = Some(normal.shift());
+            let cp = normal.project(&normal.project(&c_tilde));
+            let y = normal.multipliers(&c_tilde);
+            let empty = DMatrix::zeros(0, 0);
+            let condition = normal.condition();
+            (
+                empty.clone(),
+                empty.clone(),
+                empty.clone(),
+                condition,
+                empty,
+                cp,
+                y,
+            )
+        }
+        None => {
+            let d = create_d_matrix(&problem.x_vector, regularization.floor);
+            let a_tilde = calculate_a_tilde(&problem.a_matrix, &d);
+            let (q, l, applied_shift) = regularized_range_basis(&a_tilde, regularization.shift)?;
+            regularization.applied_shift = applied_shift;
+            let cp = project_onto_null_space(&q, &project_onto_null_space(&q, &c_tilde));
+            let y = range_multipliers(&q, &l, &c_tilde);
+            let condition = normal_condition(&l);
+            let p = problem.displayed_projection(&q);
+            (d, a_tilde, l, condition, p, cp, y)
+        }
+    };
+    warn_if_ill_conditioned(condition);
 
-    let a_tilde = calculate_a_tilde(&problem.a_matrix, &d);
-    let c_tilde = calculate_c_tilde(&problem.c_vector, &d);
+    // In double-double the direction is recomputed from the wide iterate;
+    // the f64 matrices above stay for display.
+    let extended = match problem.options.precision {
+        Precision::Double => None,
+        Precision::DoubleDouble => {
+            let x = problem.extended_iterate();
+            let cp = precision::projected_direction(
+                &problem.a_matrix.map(DoubleDouble::from_f64),
+                &problem.c_vector.map(DoubleDouble::from_f64),
+                &x,
+                &regularization,
+            )?;
+            Some((x, cp))
+        }
+    };
+    let cp = match &extended {
+        Some((_, cp)) => cp.map(Real::to_f64),
+        None => cp,
+    };
+    check_finite(
+        problem.iterations,
+        &[
+            ("D", scale.as_slice()),
+            ("A~", a_tilde.as_slice()),
+            ("c~", c_tilde.as_slice()),
+            ("L", l.as_slice()),
+            ("P", p.as_slice()),
+            ("P c~", cp.as_slice()),
+            ("y", y.as_slice()),
+        ],
+    )?;
 
-    let p = calculate_p_matrix(&a_tilde)?;
+    // The direction in the original variables: x + t dx = D (1 + t P c~).
+    let dx = scale.component_mul(&cp);
+    if let Some(ray) = improving_ray(&problem.a_matrix, &problem.c_vector, &dx) {
+        tracing::warn!("The step direction is an improving ray: the objective is unbounded");
+        let ray = problem.to_original(&ray);
+        return Err(InteriorPointError::Unbounded(&ray / ray.max()));
+    }
+    let Some(to_boundary) = ratio_test(&problem.x_vector, &dx) else {
+        tracing::warn!("No component of the direction decreases: nothing left to improve");
+        return Err(problem.stop(StopReason::NoDecrease));
+    };
 
-    let cp = calculate_cp_vector(&p, &c_tilde);
+    let step = problem.choose_step(&cp, &dx, to_boundary);
+    let factor = step.length;
+    // The step raises c^T x by c~^T (t P c~) = t |P c~|^2.
+    let measures = StepMeasures::measure(
+        problem.options.norm,
+        &problem.x_vector,
+        &problem.c_vector,
+        &dx,
+        &cp,
+        factor,
+    );
+    if let Some(reason) = problem.options.stopping.met_by(&measures) {
+        tracing::info!(
+            reason = reason.label(),
+            step_norm = measures.step_norm,
+            objective_change = measures.objective_change,
+            "Stopped: {} (step {:.3e}, objective change {:.3e})",
+            reason.label(),
+            measures.step_norm,
+            measures.objective_change
+        );
+        return Err(problem.stop(reason));
+    }
 
-    let mut v = 0.0_f64;
-    for &val in cp.iter() {
-        if val < 0.0 && val.abs() > v {
-            v = val.abs();
+    let new_x = match extended {
+        Some((x, cp)) => {
+            let wide = precision::step_along(&x, &cp, factor, regularization.floor);
+            let rounded = wide.map(Real::to_f64);
+            problem.extended_x = Some(wide);
+            rounded
         }
+        None => &problem.x_vector + factor * &dx,
+    };
+    check_finite(problem.iterations, &[("x", new_x.as_slice())])?;
+
+    if problem.options.logs_full() {
+        logging::dump_matrix!("D = diag(x)", &d);
+        logging::dump_matrix!("A~", &a_tilde);
+        logging::dump_vector!("c~", &c_tilde);
+        logging::dump_matrix!("L", &l);
+        logging::dump_matrix!("P", &p);
+        logging::dump_vector!("P c~", &cp);
+        logging::dump_vector!("Updated x", &new_x);
     }
-    if v < 1e-8 {
-        log::warn!("Step size too small or no negative direction: v = {}", v);
-        return Err(InteriorPointError::NoImprovement);
+    if problem.options.logs_summary() {
+        let objective = problem.c_vector.dot(&new_x);
+        tracing::info!(
+            strategy = step.strategy.label(),
+            factor,
+            objective,
+            min_x = new_x.min(),
+            "{}: factor {:.4}, objective {:.6}, min x {:.3e}",
+            step.strategy.label(),
+            factor,
+            objective,
+            new_x.min()
+        );
     }
 
-    let factor = (problem.alpha / v).min(0.5).max(1e-3);
+    let previous = std::mem::replace(&mut problem.x_vector, new_x);
+    problem.iterations += 1;
+    let residuals = problem.residuals(&previous, &y);
+    let (duals, duality_gap) = problem.dual_estimate(y);
+
+    Ok(InteriorPointIteration {
+        d_matrix: d,
+        a_tilde_matrix: a_tilde,
+        c_tilde_vector: c_tilde,
+        l_matrix: l,
+        condition,
+        p_matrix: p,
+        cp_vector: cp,
+        current_x: problem.original_x(),
+        centering: false,
+        step: Some(step),
+        regularization,
+        residuals,
+        duals,
+        duality_gap,
+    })
+}
+
+/// Newton step towards the analytic center, maximizing sum(ln x) over
+/// A x = b instead of the objective. In scaled space the gradient of the
+/// barrier at x~ = 1 is 1 and its Hessian is -I, so the step is x~ = 1 + t P 1
+/// with t = min(1, alpha / max(-P 1)) keeping x~ positive.
+pub fn perform_centering_iteration(
+    problem: &mut InteriorPointProblem,
+) -> Result<InteriorPointIteration, InteriorPointError> {
+    centering_step(problem)
+        .map_err(|e| e.locate(problem.iterations, &problem.a_matrix, &problem.b_vector))
+}
 
+fn centering_step(
+    problem: &mut InteriorPointProblem,
+) -> Result<InteriorPointIteration, InteriorPointError> {
+    let _span = tracing::info_span!("centering_step", iteration = problem.iterations).entered();
+    let mut regularization = problem.options.regularization();
+    let scale = problem.x_vector.map(|val| val.max(regularization.floor));
+    let c_tilde = scale.component_mul(&problem.c_vector);
     let ones = DVector::from_element(problem.x_vector.len(), 1.0);
-    let new_x_tilde = &ones + factor * &cp;
 
-    let new_x = (&d * &new_x_tilde).column(0).into_owned();
+    let (d, a_tilde, l, condition, p, p_ones, y) = match &problem.sparse_a {
+        Some(a) => {
+            let normal = sparse::NormalFactor::new(a, &scale, regularization.shift)?;
+            regularization.applied_shift = Some(normal.shift());
+            let p_ones = normal.project(&ones);
+            let y = normal.multipliers(&c_tilde);
+            let empty = DMatrix::zeros(0, 0);
+            let condition = normal.condition();
+            (
+                empty.clone(),
+                empty.clone(),
+                empty.clone(),
+                condition,
+                empty,
+                p_ones,
+                y,
+            )
+        }
+        None => {
+            let d = create_d_matrix(&problem.x_vector, regularization.floor);
+            let a_tilde = calculate_a_tilde(&problem.a_matrix, &d);
+            let (q, l, applied_shift) = regularized_range_basis(&a_tilde, regularization.shift)?;
+            regularization.applied_shift = applied_shift;
+            let p_ones = project_onto_null_space(&q, &ones);
+            let y = range_multipliers(&q, &l, &c_tilde);
+            let condition = normal_condition(&l);
+            let p = problem.displayed_projection(&q);
+            (d, a_tilde, l, condition, p, p_ones, y)
+        }
+    };
+    warn_if_ill_conditioned(condition);
+
+    let v = p_ones.iter().fold(0.0_f64, |acc, &val| acc.max(-val));
+    let step = if v > 0.0 {
+        (problem.alpha / v).min(1.0)
+    } else {
+        1.0
+    };
 
-    problem.x_vector = new_x.clone();
+    let new_x = scale.component_mul(&(&ones + &p_ones * step));
+    check_finite(
+        problem.iterations,
+        &[
+            ("D", scale.as_slice()),
+            ("A~", a_tilde.as_slice()),
+            ("L", l.as_slice()),
+            ("P", p.as_slice()),
+            ("P 1", p_ones.as_slice()),
+            ("y", y.as_slice()),
+            ("x", new_x.as_slice()),
+        ],
+    )?;
 
-    log::info!("Updated x: {:?}", new_x);
+    if problem.options.logs_full() {
+        logging::dump_vector!("P 1", &p_ones);
+        logging::dump_vector!("Centered x", &new_x);
+    }
+    if problem.options.logs_summary() {
+        tracing::info!(
+            step,
+            p_ones_norm = p_ones.norm(),
+            min_x = new_x.min(),
+            "Centering step t = {:.4}, |P 1| = {:.3e}, min x {:.3e}",
+            step,
+            p_ones.norm(),
+            new_x.min()
+        );
+    }
+
+    let previous = std::mem::replace(&mut problem.x_vector, new_x);
+    let residuals = problem.residuals(&previous, &y);
+    let (duals, duality_gap) = problem.dual_estimate(y);
 
     Ok(InteriorPointIteration {
         d_matrix: d,
         a_tilde_matrix: a_tilde,
         c_tilde_vector: c_tilde,
+        l_matrix: l,
+        condition,
         p_matrix: p,
-        cp_vector: cp,
-        current_x: new_x,
+        cp_vector: p_ones,
+        current_x: problem.original_x(),
+        centering: true,
+        step: None,
+        regularization,
+        residuals,
+        duals,
+        duality_gap,
     })
 }
+
+/// Dual estimate y = (A X^2 A^T)^{-1} A X^2 c at the current iterate, by a
+/// Cholesky factorization of the normal matrix and two triangular solves.
+pub fn estimate_duals(
+    a: &DMatrix<f64>,
+    c: &DVector<f64>,
+    x: &DVector<f64>,
+) -> Option<DVector<f64>> {
+    let d = create_d_matrix(x, DEFAULT_SCALE_FLOOR);
+    let a_d2 = a * &d * &d;
+    let normal = &a_d2 * a.transpose();
+    normal.cholesky().map(|factor| factor.solve(&(a_d2 * c)))
+}
+
+/// Least weight the warm start gives the cold start; a converged x sits on
+/// the boundary, where affine scaling can barely move.
+pub const WARM_START_BLEND: f64 = 0.01;
+
+/// Starting point near `previous`, the optimum of an earlier version of the
+/// model: `previous` is projected onto A x = b and blended with `interior`, a
+/// strictly positive point of the new model, just enough to be positive
+/// again. `None` when the dimensions changed or A A^T is singular.
+pub fn warm_start(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    previous: &DVector<f64>,
+    interior: &DVector<f64>,
+) -> Option<DVector<f64>> {
+    if previous.len() != a.ncols() || interior.len() != a.ncols() {
+        return None;
+    }
+    // Least-norm correction x + A^T (A A^T)^{-1} (b - A x). With A^T = Q R
+    // and L = R^T, A^T (A A^T)^{-1} = Q L^{-1}: one triangular solve, and
+    // A A^T, which squares the condition number of A, is never formed.
+    let residual = b - a * previous;
+    let (q, l) = calculate_range_basis(a).ok()?;
+    let correction = l.solve_lower_triangular(&residual)?;
+    let projected = previous + q * correction;
+
+    // (1 - t) x_j + t p_j > 0 needs t > -x_j / (p_j - x_j) where x_j <= 0.
+    let needed = projected
+        .iter()
+        .zip(interior.iter())
+        .filter(|(&x, _)| x <= 0.0)
+        .map(|(&x, &p)| -x / (p - x))
+        .fold(0.0, f64::max);
+    let t = (needed * 1.1).clamp(WARM_START_BLEND, 1.0);
+    Some(projected * (1.0 - t) + interior * t)
+}
+
+/// Iterates until the solver stops, `max_iterations` is reached or `cancel` is
+/// triggered, returning the report together with every iteration computed.
+pub fn run_to_convergence(
+    problem: &mut InteriorPointProblem,
+    max_iterations: usize,
+    cancel: Option<&CancellationToken>,
+) -> (SolveReport, Vec<InteriorPointIteration>) {
+    run_observed(problem, max_iterations, cancel, &mut |_| {
+        ControlFlow::Continue(())
+    })
+}
+
+/// `run_to_convergence` that hands every iteration to `observer` as soon as
+/// it is computed, so a caller can log or plot the run as it goes. Returning
+/// `ControlFlow::Break` stops the run there with `SolveStatus::Cancelled`;
+/// the iteration it was shown is kept.
+pub fn run_with_observer(
+    problem: &mut InteriorPointProblem,
+    max_iterations: usize,
+    mut observer: impl FnMut(&InteriorPointIteration) -> ControlFlow<()>,
+) -> (SolveReport, Vec<InteriorPointIteration>) {
+    run_observed(problem, max_iterations, None, &mut observer)
+}
+
+fn run_observed(
+    problem: &mut InteriorPointProblem,
+    max_iterations: usize,
+    cancel: Option<&CancellationToken>,
+    observer: &mut dyn FnMut(&InteriorPointIteration) -> ControlFlow<()>,
+) -> (SolveReport, Vec<InteriorPointIteration>) {
+    let mut timings = Timings::default();
+    let mut history = Vec::new();
+    let mut status = SolveStatus::IterationLimit;
+    let mut ray = None;
+
+    while history.len() < max_iterations {
+        if cancel.is_some_and(|token| token.is_cancelled()) {
+            if problem.options.logs_summary() {
+                tracing::info!(
+                    iterations = history.len(),
+                    "Solve cancelled after {} iterations",
+                    history.len()
+                );
+            }
+            status = SolveStatus::Cancelled;
+            break;
+        }
+
+        let start = now_ms();
+        let result = perform_interior_point_iteration(problem);
+        timings.record(now_ms() - start);
+        match result {
+            Ok(iter_data) => {
+                let flow = observer(&iter_data);
+                history.push(iter_data);
+                if flow.is_break() {
+                    if problem.options.logs_summary() {
+                        tracing::info!(
+                            iterations = history.len(),
+                            "Solve stopped by its observer after {} iterations",
+                            history.len()
+                        );
+                    }
+                    status = SolveStatus::Cancelled;
+                    break;
+                }
+            }
+            Err(e) => {
+                status = SolveStatus::from(&e);
+                if let InteriorPointError::Unbounded(direction) = e {
+                    ray = Some(direction);
+                }
+                break;
+            }
+        }
+    }
+    if status == SolveStatus::IterationLimit {
+        problem.stop_reason = Some(StopReason::IterationLimit);
+    }
+
+    let mut report = problem.report(status, history.len(), ray);
+    report.timings = timings;
+    (report, history)
+}
+
+/// Affine scaling as an iterator: every `next` takes one step, so a run can
+/// be streamed, cut short with `take(n)` or gathered with `collect()`. The
+/// error the run stops with is yielded once; after it the iterator is done.
+pub struct InteriorPointRun {
+    problem: InteriorPointProblem,
+}
+
+impl InteriorPointRun {
+    pub fn new(problem: InteriorPointProblem) -> Self {
+        Self { problem }
+    }
+
+    /// The state after the steps taken so far, e.g. for `report`.
+    pub fn problem(&self) -> &InteriorPointProblem {
+        &self.problem
+    }
+
+    pub fn into_problem(self) -> InteriorPointProblem {
+        self.problem
+    }
+}
+
+impl Iterator for InteriorPointRun {
+    type Item = Result<InteriorPointIteration, InteriorPointError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.problem.is_done() {
+            return None;
+        }
+        Some(self.problem.step())
+    }
+}
+
+impl FusedIterator for InteriorPointRun {}
+
+/// Steps `solve` allows when the stopping criteria set no iteration limit.
+pub const SOLVE_ITERATION_LIMIT: usize = 1000;
+
+/// A complete affine-scaling run: the final x in the solver space, c^T x in
+/// the model's own sense with any presolve shift added back, and every
+/// iteration on the way.
+#[derive(Clone, Debug)]
+pub struct Solution {
+    pub x: DVector<f64>,
+    pub objective: f64,
+    pub iterations: usize,
+    pub snapshots: Vec<InteriorPointIteration>,
+    /// Duals, residual, scaling and the stopping criterion that fired, with
+    /// everything `SolveReport::complete` adds.
+    pub report: SolveReport,
+}
+
+/// Why `solve` has no optimum to return.
+#[derive(Clone, Debug)]
+pub enum SolveError {
+    /// The run could not be set up: Phase 1 failed or A, b, c and x do not
+    /// fit together.
+    Setup(String),
+    /// The run ended on something other than an optimum; `status` says what,
+    /// and `run` keeps the iterations up to there.
+    NoOptimum {
+        status: SolveStatus,
+        run: Box<Solution>,
+    },
+}
+
+/// Runs affine scaling on `model` from its starting point to the end, with
+/// the model's step rule and scaling. Stops on the criteria in `options`,
+/// after at most their `max_iterations` or `SOLVE_ITERATION_LIMIT` steps.
+pub fn solve(model: &ValidatedProblem, options: &SolverOptions) -> Result<Solution, SolveError> {
+    solve_with_observer(model, options, |_| ControlFlow::Continue(()))
+}
+
+/// `solve` that shows `observer` each iteration as it is computed, as in
+/// `run_with_observer`. A run the observer breaks off ends in
+/// `SolveError::NoOptimum` with `SolveStatus::Cancelled` and the iterations
+/// up to there.
+pub fn solve_with_observer(
+    model: &ValidatedProblem,
+    options: &SolverOptions,
+    observer: impl FnMut(&InteriorPointIteration) -> ControlFlow<()>,
+) -> Result<Solution, SolveError> {
+    let mut problem = InteriorPointProblem::init(model, options).map_err(SolveError::Setup)?;
+    solve_from_with_observer(&mut problem, model, observer)
+}
+
+/// `solve` for a run already under way, such as the one the step-by-step
+/// view is showing; only the iterations still to come are returned.
+pub fn solve_from(
+    problem: &mut InteriorPointProblem,
+    model: &ValidatedProblem,
+) -> Result<Solution, SolveError> {
+    solve_from_with_observer(problem, model, |_| ControlFlow::Continue(()))
+}
+
+/// `solve_from` with an observer, as in `solve_with_observer`.
+pub fn solve_from_with_observer(
+    problem: &mut InteriorPointProblem,
+    model: &ValidatedProblem,
+    observer: impl FnMut(&InteriorPointIteration) -> ControlFlow<()>,
+) -> Result<Solution, SolveError> {
+    let limit = problem.remaining_iterations();
+    let (mut report, snapshots) = run_with_observer(problem, limit, observer);
+    report.complete(model, &problem.options);
+    let status = report.status.clone();
+    let run = Solution {
+        x: report.solution.clone(),
+        objective: report.objective,
+        iterations: snapshots.len(),
+        snapshots,
+        report,
+    };
+    match status {
+        SolveStatus::Optimal => Ok(run),
+        status => Err(SolveError::NoOptimum {
+            status,
+            run: Box::new(run),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SolverMethod;
+    use crate::test_models::{assert_optimum, solve, wyndor};
+
+    #[test]
+    fn every_step_rule_reaches_the_wyndor_optimum() {
+        for strategy in StepStrategy::ALL {
+            let mut model = wyndor();
+            model.options.step_strategy = strategy;
+            let report = solve(&model, SolverMethod::AffineScaling);
+            assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-3);
+        }
+    }
+
+    #[test]
+    fn shift_keeps_dependent_rows_from_dropping_a_direction() {
+        // Row 2 is twice row 1; max x1 + 2 x2 is 5 at (3, 1, 0, 0).
+        let a = DMatrix::from_row_slice(
+            3,
+            4,
+            &[1.0, 1.0, 1.0, 0.0, 2.0, 2.0, 2.0, 0.0, 1.0, 3.0, 0.0, 1.0],
+        );
+        let b = DVector::from_vec(vec![4.0, 8.0, 6.0]);
+        let c = DVector::from_vec(vec![1.0, 2.0, 0.0, 0.0]);
+        let x = DVector::from_vec(vec![1.0, 1.0, 2.0, 2.0]);
+        let mut problem =
+            InteriorPointProblem::new(a, b, c.clone(), x, 0.5, SolverOptions::default()).unwrap();
+        let mut shifts = vec![];
+        while let Ok(iteration) = perform_interior_point_iteration(&mut problem) {
+            shifts.push(iteration.regularization.applied_shift);
+        }
+        assert!(shifts
+            .iter()
+            .all(|shift| *shift == Some(DEFAULT_NORMAL_SHIFT)));
+        assert!((c.dot(&problem.x_vector) - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn warm_start_is_feasible_and_positive() {
+        let a = DMatrix::from_row_slice(2, 4, &[1.0, 1.0, 1.0, 0.0, 1.0, 3.0, 0.0, 1.0]);
+        let b = DVector::from_vec(vec![4.0, 6.0]);
+        let previous = DVector::from_vec(vec![3.5, 1.2, 0.0, 0.0]);
+        let interior = DVector::from_vec(vec![1.0, 1.0, 2.0, 2.0]);
+        let x = warm_start(&a, &b, &previous, &interior).unwrap();
+        assert!((&a * &x - &b).amax() < 1e-12);
+        assert!(x.iter().all(|&val| val > 0.0));
+    }
+}
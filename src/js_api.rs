@@ -0,0 +1,245 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+
+use crate::interior::{
+    run_to_convergence, InteriorPointError, InteriorPointProblem, SolverOptions, StepStrategy,
+};
+use crate::model::{Sense, ValidatedProblem};
+use crate::phase_one::analytic_center;
+use crate::report::SolveStatus;
+use crate::schema::matrix_from_rows;
+use crate::solver::Solver;
+
+/// α when the page gives none, as in the input form.
+const DEFAULT_ALPHA: f64 = 0.5;
+
+/// Steps Phase 1 may take to find a start when the page gives none.
+const PHASE_ONE_LIMIT: usize = 500;
+
+/// Options a page passes to `solve_lp`; every field may be left out.
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct JsOptions {
+    /// Minimize c^T x instead of maximizing it.
+    minimize: bool,
+    alpha: Option<f64>,
+    /// Strictly positive start with A x0 = b; Phase 1 finds one otherwise.
+    x0: Option<Vec<f64>>,
+    /// Step rule, by the keys of `StepStrategy::key`.
+    strategy: Option<String>,
+    max_iterations: Option<usize>,
+    /// Solver options in the schema of `crate::schema`; fields left out keep
+    /// their defaults.
+    solver: SolverOptions,
+}
+
+/// What `solve_lp` returns.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsSolution {
+    status: String,
+    x: Vec<f64>,
+    objective: f64,
+    iterations: usize,
+    duals: Option<Vec<f64>>,
+    /// x after every step, for pages that plot the path.
+    path: Vec<Vec<f64>>,
+    /// What the app would warn about, such as a residual left in A x = b.
+    warnings: Vec<String>,
+}
+
+/// A run between two `step_lp` calls: the model, the iterate and what the
+/// steps carry from one to the next. A page starts one from `a`, `b`, `c`
+/// and `x` and passes each result back in until `status` is set.
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsState {
+    a: Vec<Vec<f64>>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    x: Vec<f64>,
+    #[serde(default)]
+    minimize: bool,
+    #[serde(default = "default_alpha")]
+    alpha: f64,
+    #[serde(default)]
+    strategy: Option<String>,
+    /// Fraction the adaptive and scheduled rules use next; `alpha` at first.
+    #[serde(default)]
+    adaptive_fraction: Option<f64>,
+    #[serde(default)]
+    iterations: usize,
+    /// c^T x after the last step.
+    #[serde(default)]
+    objective: Option<f64>,
+    /// Why the run stopped, once it has; stepping it again changes nothing.
+    #[serde(default)]
+    status: Option<String>,
+    /// Solver options in the schema of `crate::schema`.
+    #[serde(default)]
+    solver: SolverOptions,
+}
+
+fn default_alpha() -> f64 {
+    DEFAULT_ALPHA
+}
+
+/// Solves max (or, with `options.minimize`, min) c^T x subject to A x = b,
+/// x >= 0 by affine scaling. `a` is an array of rows; `options` may be
+/// `undefined`. Returns `{ status, x, objective, iterations, duals, path,
+/// warnings }` and throws a message when the input does not describe such a
+/// problem.
+#[wasm_bindgen(js_name = solve_lp)]
+pub fn solve_lp(a: JsValue, b: JsValue, c: JsValue, options: JsValue) -> Result<JsValue, JsValue> {
+    let a: Vec<Vec<f64>> = from_js(a, "a")?;
+    let b: Vec<f64> = from_js(b, "b")?;
+    let c: Vec<f64> = from_js(c, "c")?;
+    let options: JsOptions = if options.is_undefined() || options.is_null() {
+        JsOptions::default()
+    } else {
+        from_js(options, "options")?
+    };
+
+    let a = rows_to_matrix(&a)?;
+    let b = DVector::from_vec(b);
+    let alpha = options.alpha.unwrap_or(DEFAULT_ALPHA);
+    let mut solver_options = options.solver;
+    if options.max_iterations.is_some() {
+        solver_options.stopping.max_iterations = options.max_iterations;
+    }
+    let x0 = match options.x0 {
+        Some(x0) => DVector::from_vec(x0),
+        None => analytic_center(&a, &b, alpha, PHASE_ONE_LIMIT, &solver_options)
+            .map(|center| center.x)
+            .map_err(|e| error(format!("Phase 1 failed: {}", e)))?,
+    };
+    let sense = if options.minimize {
+        Sense::Minimize
+    } else {
+        Sense::Maximize
+    };
+    let model = ValidatedProblem::standard_form(
+        a.clone(),
+        b.clone(),
+        DVector::from_vec(c.clone()),
+        sense,
+        x0.iter().copied().collect(),
+        alpha,
+    );
+    let mut problem = build(
+        a,
+        b,
+        internal_c(c, options.minimize),
+        x0,
+        alpha,
+        options.strategy.as_deref(),
+        solver_options.clone(),
+    )?;
+
+    let limit = problem.remaining_iterations();
+    let (mut report, history) = run_to_convergence(&mut problem, limit, None);
+    report.complete(&model, &solver_options);
+    to_js(&JsSolution {
+        status: report.status.label().to_string(),
+        x: report.solution.iter().copied().collect(),
+        objective: report.objective,
+        iterations: report.iterations,
+        duals: report.duals.map(|y| y.iter().copied().collect()),
+        path: history
+            .iter()
+            .map(|iteration| iteration.current_x.iter().copied().collect())
+            .collect(),
+        warnings: report.warnings,
+    })
+}
+
+/// Takes one affine-scaling step of the run `state` describes and returns
+/// the state after it, with `x`, `iterations` and `objective` updated and
+/// `status` set once the run has stopped.
+#[wasm_bindgen(js_name = step_lp)]
+pub fn step_lp(state: JsValue) -> Result<JsValue, JsValue> {
+    let mut state: JsState = from_js(state, "state")?;
+    if state.status.is_some() {
+        return to_js(&state);
+    }
+
+    let mut problem = build(
+        rows_to_matrix(&state.a)?,
+        DVector::from_vec(state.b.clone()),
+        internal_c(state.c.clone(), state.minimize),
+        DVector::from_vec(state.x.clone()),
+        state.alpha,
+        state.strategy.as_deref(),
+        state.solver.clone(),
+    )?;
+    problem.adaptive_fraction = state.adaptive_fraction.unwrap_or(state.alpha);
+    problem.iterations = state.iterations;
+
+    match problem.step() {
+        Ok(_) => {
+            state.x = problem.x_vector.iter().copied().collect();
+            state.adaptive_fraction = Some(problem.adaptive_fraction);
+            state.iterations = problem.iterations;
+            let objective = problem.c_vector.dot(&problem.x_vector);
+            state.objective = Some(if state.minimize {
+                -objective
+            } else {
+                objective
+            });
+        }
+        Err(e) => {
+            if let InteriorPointError::DimensionMismatch { .. } = &e {
+                return Err(error(e.to_string()));
+            }
+            state.status = Some(SolveStatus::from(&e).label().to_string());
+        }
+    }
+    to_js(&state)
+}
+
+/// Affine scaling on A x = b from `x0`, with the step rule named `strategy`.
+fn build(
+    a: DMatrix<f64>,
+    b: DVector<f64>,
+    c: DVector<f64>,
+    x0: DVector<f64>,
+    alpha: f64,
+    strategy: Option<&str>,
+    options: SolverOptions,
+) -> Result<InteriorPointProblem, JsValue> {
+    let strategy = match strategy {
+        Some(key) => StepStrategy::from_key(key)
+            .ok_or_else(|| error(format!("unknown step strategy \"{}\"", key)))?,
+        None => StepStrategy::default(),
+    };
+    let problem =
+        InteriorPointProblem::new(a, b, c, x0, alpha, options).map_err(|e| error(e.to_string()))?;
+    Ok(problem.with_strategy(strategy))
+}
+
+/// The solvers maximize; a minimization runs on -c.
+fn internal_c(c: Vec<f64>, minimize: bool) -> DVector<f64> {
+    let c = DVector::from_vec(c);
+    if minimize {
+        -c
+    } else {
+        c
+    }
+}
+
+fn rows_to_matrix(rows: &[Vec<f64>]) -> Result<DMatrix<f64>, JsValue> {
+    matrix_from_rows(rows).map_err(|e| error(format!("a: {}", e)))
+}
+
+fn from_js<T: for<'de> Deserialize<'de>>(value: JsValue, name: &str) -> Result<T, JsValue> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| error(format!("{}: {}", name, e)))
+}
+
+fn to_js<T: Serialize>(value: &T) -> Result<JsValue, JsValue> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| error(e.to_string()))
+}
+
+fn error(message: String) -> JsValue {
+    JsValue::from_str(&message)
+}
@@ -1,13 +1,105 @@
+//! An interior-point LP solver with a Yew front end.
+//!
+//! The web app lives behind the default `web` feature: `components`, the
+//! routing, profiling and service-worker glue, the background solver
+//! worker, and the browser storage, download and speech helpers. Built with
+//! `--no-default-features` the crate is the headless core, with no yew,
+//! wasm-bindgen or web-sys, so the solvers run from native programs and
+//! tests. The `js` feature adds `js_api`, the same solver as plain
+//! functions for other pages to call.
+
+#[cfg(feature = "web")]
 use wasm_bindgen::prelude::*;
+#[cfg(feature = "web")]
 use yew::Renderer;
 
+pub mod assignment;
+pub mod barrier;
+pub mod batch;
+pub mod builder;
+pub mod cancel;
+#[cfg(feature = "web")]
 pub mod components;
+pub mod convergence;
+pub mod course_pack;
+pub mod crash;
+pub mod cross_check;
+pub mod dual_simplex;
+pub mod equilibration;
+pub mod exercises;
+pub mod expected;
+pub mod export;
+pub mod fractional;
+pub mod fuzz;
+pub mod generator;
+pub mod history_filter;
+pub mod hsd;
+pub mod inspector;
 pub mod interior;
+#[cfg(feature = "js")]
+pub mod js_api;
+pub mod json;
+pub mod logging;
+pub mod milp;
+pub mod model;
+pub mod multistart;
+pub mod narration;
+pub mod network;
+pub mod norm;
+pub mod notation;
+pub mod palette;
+pub mod parametric;
+pub mod phase_one;
+pub mod plot;
+pub mod precision;
+pub mod presolve;
+pub mod pretty;
+#[cfg(feature = "web")]
+pub mod profile;
+#[cfg(feature = "web")]
+pub mod pwa;
+pub mod qp;
+pub mod race;
+pub mod rank;
+pub mod reformulation;
+pub mod regression;
+pub mod replay;
+pub mod report;
+pub mod repro;
+pub mod revised_simplex;
+#[cfg(feature = "web")]
+pub mod route;
+pub mod scenario;
+pub mod schedule;
+pub mod schema;
+pub mod sensitivity;
+pub mod session;
+pub mod simplex;
+pub mod snapshot;
+pub mod solver;
+pub mod sparse;
+pub mod stopping;
+pub mod symmetry;
+#[cfg(test)]
+mod test_models;
+pub mod theory;
+pub mod transportation;
+#[cfg(feature = "web")]
+pub mod worker;
+pub mod zip;
 
+#[cfg(feature = "web")]
 pub use components::App;
 
+#[cfg(feature = "web")]
 #[wasm_bindgen(start)]
 pub fn run_app() {
-    wasm_logger::init(wasm_logger::Config::default());
+    // The solver worker links this library too; it has no page to render.
+    if web_sys::window().is_none() {
+        return;
+    }
+    logging::init();
+    crash::install_panic_hook();
+    pwa::register_service_worker();
     Renderer::<App>::new().render();
 }
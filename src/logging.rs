@@ -0,0 +1,262 @@
+use std::collections::VecDeque;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::subscriber::Interest;
+use tracing::{Event, Level, Metadata, Subscriber};
+use tracing_log::NormalizeEvent;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Registry;
+
+use crate::interior::LogVerbosity;
+use crate::report::now_ms;
+
+/// Writes a whole matrix as a debug record, which only `Full` verbosity
+/// keeps. Callers check `SolverOptions::logs_full` first so the formatting
+/// is skipped otherwise.
+macro_rules! dump_matrix {
+    ($name:expr, $matrix:expr) => {{
+        let matrix: &nalgebra::DMatrix<f64> = $matrix;
+        tracing::debug!(
+            matrix = $name,
+            rows = matrix.nrows(),
+            cols = matrix.ncols(),
+            "{}",
+            $crate::pretty::matrix($name, matrix)
+        );
+    }};
+}
+
+/// `dump_matrix` for a vector or a slice of values.
+macro_rules! dump_vector {
+    ($name:expr, $values:expr) => {{
+        let values: &[f64] = $values.as_slice();
+        tracing::debug!(
+            vector = $name,
+            len = values.len(),
+            "{}",
+            $crate::pretty::values($name, values)
+        );
+    }};
+}
+
+pub(crate) use {dump_matrix, dump_vector};
+
+/// Records the in-page console keeps; older ones are dropped first.
+pub const LOG_CAPACITY: usize = 1000;
+
+/// One solver diagnostic as the console shows it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LogRecord {
+    /// Increases by one per record, so a viewer can tell what is new.
+    pub seq: u64,
+    pub time_ms: f64,
+    pub level: Level,
+    /// Module the record came from, e.g. `ipsolver::interior`.
+    pub target: String,
+    /// Spans the record was emitted in, outermost first, e.g.
+    /// `affine_step{iteration=3}`.
+    pub context: String,
+    pub message: String,
+    /// Structured fields other than the message, as `name=value`.
+    pub fields: Vec<(String, String)>,
+}
+
+static RECORDS: Mutex<VecDeque<LogRecord>> = Mutex::new(VecDeque::new());
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Most detailed level recorded, as an index into `LEVELS`.
+static MAX_LEVEL: AtomicU8 = AtomicU8::new(2);
+
+/// From most to least severe.
+pub const LEVELS: [Level; 5] = [
+    Level::ERROR,
+    Level::WARN,
+    Level::INFO,
+    Level::DEBUG,
+    Level::TRACE,
+];
+
+/// Routes `log` and `tracing` records through `LogLayer`. Call once, before
+/// anything logs; later calls do nothing.
+pub fn init() {
+    if tracing_log::LogTracer::init().is_err() {
+        return;
+    }
+    let subscriber = Registry::default().with(LogLayer);
+    let _ = tracing::subscriber::set_global_default(subscriber);
+}
+
+/// Records up to the level the solvers' verbosity asks for: warnings and
+/// errors only when it is off, step summaries at `Summary` and the matrix
+/// dumps, which are debug records, at `Full`.
+pub fn set_verbosity(verbosity: LogVerbosity) {
+    let level = match verbosity {
+        LogVerbosity::Off => Level::WARN,
+        LogVerbosity::Summary => Level::INFO,
+        LogVerbosity::Full => Level::DEBUG,
+    };
+    let index = LEVELS.iter().position(|&l| l == level).unwrap_or(2);
+    MAX_LEVEL.store(index as u8, Ordering::Relaxed);
+}
+
+fn max_level() -> Level {
+    LEVELS[MAX_LEVEL.load(Ordering::Relaxed) as usize]
+}
+
+/// The records kept, oldest first.
+pub fn records() -> Vec<LogRecord> {
+    lock().iter().cloned().collect()
+}
+
+/// `seq` of the next record, which changes whenever one is added.
+pub fn next_seq() -> u64 {
+    NEXT_SEQ.load(Ordering::Relaxed)
+}
+
+pub fn clear() {
+    lock().clear();
+}
+
+fn lock() -> std::sync::MutexGuard<'static, VecDeque<LogRecord>> {
+    // A panic while holding the lock leaves the records as they were.
+    RECORDS
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Keeps every record at or above the chosen level for the console and
+/// echoes it to the browser console, or to stderr natively.
+pub struct LogLayer;
+
+/// A span's fields, formatted once when it opens.
+struct SpanFields(String);
+
+impl<S> Layer<S> for LogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    // The level can change at any time, so no callsite is cached as off.
+    fn register_callsite(&self, _metadata: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, metadata: &Metadata<'_>, _ctx: Context<'_, S>) -> bool {
+        *metadata.level() <= max_level()
+    }
+
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut fields = FieldCollector::default();
+        attrs.record(&mut fields);
+        let formatted = fields
+            .fields
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join(",");
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(SpanFields(formatted));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        // Records bridged from `log` carry their real target separately.
+        let normalized = event.normalized_metadata();
+        let metadata = normalized.as_ref().unwrap_or_else(|| event.metadata());
+
+        let mut fields = FieldCollector::default();
+        event.record(&mut fields);
+        let context = ctx
+            .event_scope(event)
+            .map(|scope| {
+                scope
+                    .from_root()
+                    .map(|span| match span.extensions().get::<SpanFields>() {
+                        Some(SpanFields(f)) if !f.is_empty() => format!("{}{{{}}}", span.name(), f),
+                        _ => span.name().to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(":")
+            })
+            .unwrap_or_default();
+
+        let record = LogRecord {
+            seq: NEXT_SEQ.fetch_add(1, Ordering::Relaxed),
+            time_ms: now_ms(),
+            level: *metadata.level(),
+            target: metadata.target().to_string(),
+            context,
+            message: fields.message,
+            fields: fields.fields,
+        };
+        echo(&record);
+        let mut records = lock();
+        if records.len() == LOG_CAPACITY {
+            records.pop_front();
+        }
+        records.push_back(record);
+    }
+}
+
+impl fmt::Display for LogRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:>5} {}", self.level, self.target)?;
+        if !self.context.is_empty() {
+            write!(f, " {}", self.context)?;
+        }
+        write!(f, ": {}", self.message)?;
+        for (name, value) in &self.fields {
+            write!(f, " {}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "web")]
+fn echo(record: &LogRecord) {
+    use web_sys::console;
+
+    let text = wasm_bindgen::JsValue::from_str(&record.to_string());
+    match record.level {
+        Level::ERROR => console::error_1(&text),
+        Level::WARN => console::warn_1(&text),
+        Level::INFO => console::info_1(&text),
+        _ => console::debug_1(&text),
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn echo(record: &LogRecord) {
+    eprintln!("{}", record);
+}
+
+/// The message and the other fields of a record or span.
+#[derive(Default)]
+struct FieldCollector {
+    message: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else if !field.name().starts_with("log.") {
+            self.fields
+                .push((field.name().to_string(), value.to_string()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else if !field.name().starts_with("log.") {
+            self.fields
+                .push((field.name().to_string(), format!("{:?}", value)));
+        }
+    }
+}
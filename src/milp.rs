@@ -0,0 +1,344 @@
+use nalgebra::{DMatrix, DVector};
+use serde::Serialize;
+
+use crate::interior::{LogVerbosity, SolverOptions};
+use crate::model::{ValidatedProblem, BIG_M_SCALE};
+use crate::schema;
+use crate::simplex::{perform_simplex_iteration, SimplexError, SimplexProblem};
+
+/// Values closer than this to an integer count as integral.
+pub const INTEGRALITY_TOLERANCE: f64 = 1e-6;
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MilpSolution {
+    #[serde(serialize_with = "schema::vector::serialize")]
+    pub x: DVector<f64>,
+    pub objective: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MilpStatus {
+    Optimal,
+    Infeasible,
+    Unbounded,
+    NodeLimit,
+    /// A node's relaxation ran out of simplex pivots.
+    PivotLimit,
+    Failed(String),
+}
+
+impl MilpStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            MilpStatus::Optimal => "Integer optimum found",
+            MilpStatus::Infeasible => "No integer solution",
+            MilpStatus::Unbounded => "Relaxation unbounded",
+            MilpStatus::NodeLimit => "Node limit reached",
+            MilpStatus::PivotLimit => "Pivot limit reached at a node",
+            MilpStatus::Failed(_) => "Failed",
+        }
+    }
+}
+
+/// LP relaxation optimum next to the best integer solution branch and bound
+/// found for it.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct MilpResult {
+    pub status: MilpStatus,
+    pub relaxation: Option<MilpSolution>,
+    pub incumbent: Option<MilpSolution>,
+    pub nodes: usize,
+}
+
+/// maximize c^T x subject to A x = b, x >= 0 and x_j integer wherever
+/// `integer[j]` is set.
+///
+/// `artificial_columns` are Big-M artificials of the augmented model; a node
+/// whose relaxation keeps one of them positive is infeasible.
+pub struct MilpProblem {
+    pub a_matrix: DMatrix<f64>,
+    pub b_vector: DVector<f64>,
+    pub c_vector: DVector<f64>,
+    pub integer: Vec<bool>,
+    pub artificial_columns: Vec<usize>,
+    pub big_m: f64,
+    pub max_nodes: usize,
+    pub options: SolverOptions,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Bound {
+    Upper(usize, f64),
+    Lower(usize, f64),
+}
+
+enum NodeOutcome {
+    Solved(MilpSolution),
+    Infeasible,
+    Unbounded,
+}
+
+impl MilpProblem {
+    /// Solves the LP relaxation with the branching bounds added as rows:
+    /// x_j + s = k for upper bounds and x_j - s + a = k (a penalized by M)
+    /// for lower bounds, so the slack basis still exists.
+    fn solve_node(&self, bounds: &[Bound]) -> Result<NodeOutcome, SimplexError> {
+        let (m, n) = self.a_matrix.shape();
+        let lower_count = bounds
+            .iter()
+            .filter(|b| matches!(b, Bound::Lower(..)))
+            .count();
+        let rows = m + bounds.len();
+        let cols = n + bounds.len() + lower_count;
+
+        let mut a = DMatrix::zeros(rows, cols);
+        a.view_mut((0, 0), (m, n)).copy_from(&self.a_matrix);
+        let mut b = DVector::zeros(rows);
+        b.rows_mut(0, m).copy_from(&self.b_vector);
+        let mut c = DVector::zeros(cols);
+        c.rows_mut(0, n).copy_from(&self.c_vector);
+
+        let mut artificials = self.artificial_columns.clone();
+        let mut next_artificial = n + bounds.len();
+        for (k, bound) in bounds.iter().enumerate() {
+            let row = m + k;
+            let slack = n + k;
+            match *bound {
+                Bound::Upper(j, value) => {
+                    a[(row, j)] = 1.0;
+                    a[(row, slack)] = 1.0;
+                    b[row] = value;
+                }
+                Bound::Lower(j, value) => {
+                    a[(row, j)] = 1.0;
+                    a[(row, slack)] = -1.0;
+                    a[(row, next_artificial)] = 1.0;
+                    b[row] = value;
+                    c[next_artificial] = -self.big_m;
+                    artificials.push(next_artificial);
+                    next_artificial += 1;
+                }
+            }
+        }
+
+        let mut simplex = SimplexProblem::new(&a, &b, &c)?;
+        simplex.options.log_verbosity = LogVerbosity::Off;
+        // Bland's rule ends cycling, but it can take exponentially many
+        // pivots: cap them.
+        for _ in 0..(50 * (rows + cols)) {
+            match perform_simplex_iteration(&mut simplex) {
+                Ok(_) => {}
+                Err(SimplexError::Optimal) => {
+                    let x = simplex.current_x();
+                    if artificials.iter().any(|&j| x[j] > INTEGRALITY_TOLERANCE) {
+                        return Ok(NodeOutcome::Infeasible);
+                    }
+                    let x = x.rows(0, n).into_owned();
+                    return Ok(NodeOutcome::Solved(MilpSolution {
+                        objective: self.c_vector.dot(&x),
+                        x,
+                    }));
+                }
+                Err(SimplexError::Unbounded(_)) => return Ok(NodeOutcome::Unbounded),
+                Err(e) => return Err(e),
+            }
+        }
+        Err(SimplexError::PivotLimit)
+    }
+
+    /// Integer-flagged variable farthest from an integer, if any.
+    fn branching_variable(&self, x: &DVector<f64>) -> Option<usize> {
+        self.integer
+            .iter()
+            .enumerate()
+            .filter(|&(j, &flag)| flag && j < x.len())
+            .map(|(j, _)| (j, (x[j] - x[j].round()).abs()))
+            .filter(|&(_, frac)| frac > INTEGRALITY_TOLERANCE)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(j, _)| j)
+    }
+}
+
+/// Depth-first branch and bound over the simplex relaxation.
+pub fn branch_and_bound(problem: &MilpProblem) -> MilpResult {
+    let mut result = MilpResult {
+        status: MilpStatus::Infeasible,
+        relaxation: None,
+        incumbent: None,
+        nodes: 0,
+    };
+
+    let mut stack: Vec<Vec<Bound>> = vec![vec![]];
+    while let Some(bounds) = stack.pop() {
+        if result.nodes >= problem.max_nodes {
+            tracing::warn!("Branch and bound stopped after {} nodes", result.nodes);
+            result.status = MilpStatus::NodeLimit;
+            return result;
+        }
+        result.nodes += 1;
+
+        let solution = match problem.solve_node(&bounds) {
+            Ok(NodeOutcome::Solved(solution)) => solution,
+            Ok(NodeOutcome::Infeasible) => continue,
+            Ok(NodeOutcome::Unbounded) => {
+                if bounds.is_empty() {
+                    result.status = MilpStatus::Unbounded;
+                    return result;
+                }
+                continue;
+            }
+            Err(SimplexError::PivotLimit) => {
+                tracing::warn!("Node {} ran out of simplex pivots", result.nodes);
+                result.status = MilpStatus::PivotLimit;
+                return result;
+            }
+            Err(e) => {
+                result.status = MilpStatus::Failed(format!("{:?}", e));
+                return result;
+            }
+        };
+        if bounds.is_empty() {
+            result.relaxation = Some(solution.clone());
+        }
+
+        // Prune nodes that cannot beat the incumbent.
+        if let Some(best) = &result.incumbent {
+            if solution.objective <= best.objective + INTEGRALITY_TOLERANCE {
+                continue;
+            }
+        }
+
+        match problem.branching_variable(&solution.x) {
+            None => {
+                if problem.options.logs_summary() {
+                    tracing::info!(
+                        "New incumbent at node {}: objective {:.6}",
+                        result.nodes,
+                        solution.objective
+                    );
+                }
+                result.incumbent = Some(solution);
+            }
+            Some(j) => {
+                let value = solution.x[j];
+                let mut down = bounds.clone();
+                down.push(Bound::Upper(j, value.floor()));
+                let mut up = bounds;
+                up.push(Bound::Lower(j, value.ceil()));
+                stack.push(up);
+                stack.push(down);
+            }
+        }
+    }
+
+    if result.incumbent.is_some() {
+        result.status = MilpStatus::Optimal;
+    }
+    result
+}
+
+/// Branch and bound on the augmented model, with the slack and artificial
+/// columns left continuous.
+pub fn solve_integer(model: &ValidatedProblem, options: &SolverOptions) -> MilpResult {
+    let mut integer = model.integer_variables.clone();
+    integer.resize(model.a.ncols(), false);
+    let c = model.internal_c();
+    let big_m = model
+        .augmentation
+        .big_m
+        .unwrap_or_else(|| BIG_M_SCALE * c.amax().max(1.0));
+    branch_and_bound(&MilpProblem {
+        a_matrix: model.a.clone(),
+        b_vector: model.b.clone(),
+        c_vector: c,
+        integer,
+        artificial_columns: model
+            .augmentation
+            .artificial_columns
+            .iter()
+            .map(|&(_, col)| col)
+            .collect(),
+        big_m,
+        max_nodes: 1000,
+        options: options.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::{ConstraintSign, SolverMethod};
+    use crate::test_models::solve;
+
+    /// Runs the simplex on `model` with every original variable integer and
+    /// returns the branch-and-bound result, in the model's sense.
+    fn integer_result(mut model: ValidatedProblem) -> MilpResult {
+        model.integer_variables = vec![true; model.integer_variables.len()];
+        solve(&model, SolverMethod::Simplex)
+            .integer
+            .expect("integer variables get branch and bound")
+    }
+
+    fn assert_solution(solution: Option<&MilpSolution>, objective: f64, x: &[f64]) {
+        let solution = solution.expect("a solution");
+        assert!(
+            (solution.objective - objective).abs() < 1e-6,
+            "objective {} instead of {}",
+            solution.objective,
+            objective
+        );
+        for (j, &want) in x.iter().enumerate() {
+            assert!((solution.x[j] - want).abs() < 1e-6, "x = {}", solution.x);
+        }
+    }
+
+    #[test]
+    fn branches_to_the_integer_optimum_below_the_relaxation() {
+        // max 5 x1 + 8 x2 s.t. x1 + x2 <= 6, 5 x1 + 9 x2 <= 45: the
+        // relaxation peaks at (2.25, 3.75) with 41.25, the integers at
+        // (0, 5) with 40.
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[5.0, 8.0])
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 6.0)
+            .constraint(&[5.0, 9.0], ConstraintSign::LessEqual, 45.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let result = integer_result(model);
+        assert_eq!(result.status, MilpStatus::Optimal);
+        assert_solution(result.relaxation.as_ref(), 41.25, &[2.25, 3.75]);
+        assert_solution(result.incumbent.as_ref(), 40.0, &[0.0, 5.0]);
+        assert!(result.nodes > 1);
+    }
+
+    #[test]
+    fn minimizes_through_lower_bound_branches() {
+        // min 3 x1 + 2 x2 s.t. 2 x1 + 3 x2 >= 7: the relaxation stops at
+        // x2 = 7/3, and only the branch x2 >= 3 holds the optimum 6.
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[3.0, 2.0])
+            .constraint(&[2.0, 3.0], ConstraintSign::GreaterEqual, 7.0)
+            .minimize()
+            .model()
+            .unwrap();
+        let result = integer_result(model);
+        assert_eq!(result.status, MilpStatus::Optimal);
+        assert_solution(result.relaxation.as_ref(), 14.0 / 3.0, &[0.0, 7.0 / 3.0]);
+        assert_solution(result.incumbent.as_ref(), 6.0, &[0.0, 3.0]);
+    }
+
+    #[test]
+    fn reports_no_integer_solution() {
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 1.0])
+            .constraint(&[2.0, 2.0], ConstraintSign::Equal, 1.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let result = integer_result(model);
+        assert_eq!(result.status, MilpStatus::Infeasible);
+        assert!(result.incumbent.is_none());
+    }
+}
@@ -0,0 +1,577 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::equilibration::Scaling;
+use crate::fractional::FractionalLayout;
+use crate::interior::StepStrategy;
+use crate::presolve::Presolve;
+use crate::regression::RegressionLayout;
+use crate::transportation::TransportationLayout;
+
+/// A constraint counts as active once its slack, relative to 1 + |b_i|, drops
+/// below this.
+pub const ACTIVE_SLACK_THRESHOLD: f64 = 0.05;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverMethod {
+    AffineScaling,
+    LogBarrier,
+    Simplex,
+    RevisedSimplex,
+    QuadraticBarrier,
+    SelfDual,
+}
+
+impl SolverMethod {
+    pub const ALL: [SolverMethod; 6] = [
+        SolverMethod::AffineScaling,
+        SolverMethod::LogBarrier,
+        SolverMethod::Simplex,
+        SolverMethod::RevisedSimplex,
+        SolverMethod::QuadraticBarrier,
+        SolverMethod::SelfDual,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SolverMethod::AffineScaling => "Affine Scaling",
+            SolverMethod::LogBarrier => "Log Barrier",
+            SolverMethod::Simplex => "Primal Simplex",
+            SolverMethod::RevisedSimplex => "Revised Simplex",
+            SolverMethod::QuadraticBarrier => "Quadratic Barrier",
+            SolverMethod::SelfDual => "Self-Dual Embedding",
+        }
+    }
+
+    pub fn is_interior(&self) -> bool {
+        matches!(
+            self,
+            SolverMethod::AffineScaling
+                | SolverMethod::LogBarrier
+                | SolverMethod::QuadraticBarrier
+                | SolverMethod::SelfDual
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sense {
+    Maximize,
+    Minimize,
+}
+
+impl Sense {
+    /// Factor turning the user's objective into the solvers' maximization form.
+    pub fn sign(&self) -> f64 {
+        match self {
+            Sense::Maximize => 1.0,
+            Sense::Minimize => -1.0,
+        }
+    }
+
+    pub fn is_maximize(&self) -> bool {
+        *self == Sense::Maximize
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintSign {
+    LessEqual,
+    GreaterEqual,
+    Equal,
+}
+
+impl ConstraintSign {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ConstraintSign::LessEqual => "<=",
+            ConstraintSign::GreaterEqual => ">=",
+            ConstraintSign::Equal => "=",
+        }
+    }
+
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "<=" => Some(ConstraintSign::LessEqual),
+            ">=" => Some(ConstraintSign::GreaterEqual),
+            "=" => Some(ConstraintSign::Equal),
+            _ => None,
+        }
+    }
+
+    /// Direction of the inequality after multiplying the row by -1.
+    pub fn flipped(&self) -> Self {
+        match self {
+            ConstraintSign::LessEqual => ConstraintSign::GreaterEqual,
+            ConstraintSign::GreaterEqual => ConstraintSign::LessEqual,
+            ConstraintSign::Equal => ConstraintSign::Equal,
+        }
+    }
+}
+
+/// How the user's model was turned into the solvers' A x = b, x >= 0 form.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Augmentation {
+    /// Columns 0..original_variables are the user's decision variables.
+    pub original_variables: usize,
+    /// (row, column) of each slack or surplus variable.
+    pub slack_columns: Vec<(usize, usize)>,
+    /// (row, column) of each Big-M artificial variable.
+    pub artificial_columns: Vec<(usize, usize)>,
+    /// +1 or -1 per row; rows with a negative right-hand side are negated.
+    pub row_multipliers: Vec<f64>,
+    pub big_m: Option<f64>,
+}
+
+/// Artificial variables cost M = BIG_M_SCALE * max(1, |c|_inf, max_j |c_j| /
+/// |a_j|_inf) in the objective. Pricing column j out takes a dual of about
+/// |c_j| / |a_j|_inf, and M has to outbid the duals, or an optimum keeps an
+/// artificial rather than pay for a column with small coefficients.
+pub const BIG_M_SCALE: f64 = 1e3;
+
+/// A constraint may miss by this much, relative to 1 + |b_i|, and still hold.
+pub const CONSTRAINT_CHECK_TOLERANCE: f64 = 1e-6;
+
+/// One user constraint a_i x (sign) b_i evaluated at a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConstraintCheck {
+    pub label: String,
+    pub lhs: f64,
+    pub sign: ConstraintSign,
+    pub rhs: f64,
+    pub holds: bool,
+    /// Column of the row's slack or surplus variable, if it got one.
+    pub slack_column: Option<usize>,
+}
+
+/// Solver-space model: A x = b, x >= 0 with the columns added to get there.
+pub struct StandardForm {
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    pub initial: Vec<f64>,
+    pub augmentation: Augmentation,
+}
+
+/// Auto-augment with the Big-M method. Rows are first scaled so b >= 0;
+/// then "<=" rows get a slack, ">=" rows a surplus plus an artificial, and
+/// "=" rows an artificial. Artificials are penalized by M in the objective,
+/// so every constraint mix has an identity starting basis.
+///
+/// `c` is in the user's sense; the penalty is applied against it.
+pub fn big_m_form(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    c: &DVector<f64>,
+    signs: &[ConstraintSign],
+    initial: &[f64],
+    sense: Sense,
+) -> StandardForm {
+    let (m, vars) = a.shape();
+
+    // Normalize each row so its right-hand side is nonnegative, flipping
+    // the inequality direction along with it.
+    let rows: Vec<(f64, ConstraintSign)> = (0..m)
+        .map(|i| {
+            let sign = signs[i];
+            if b[i] < 0.0 {
+                (-1.0, sign.flipped())
+            } else {
+                (1.0, sign)
+            }
+        })
+        .collect();
+
+    let slack_count = rows
+        .iter()
+        .filter(|(_, sign)| *sign != ConstraintSign::Equal)
+        .count();
+    let artificial_count = rows
+        .iter()
+        .filter(|(_, sign)| *sign != ConstraintSign::LessEqual)
+        .count();
+    let n = vars + slack_count + artificial_count;
+
+    let mut a_matrix = DMatrix::zeros(m, n);
+    let mut b_vector = DVector::zeros(m);
+    let mut initial = initial.to_vec();
+    initial.resize(n, 1.0);
+
+    let mut slack_col = vars;
+    let mut artificial_col = vars + slack_count;
+    let mut augmentation = Augmentation {
+        original_variables: vars,
+        row_multipliers: rows.iter().map(|&(multiplier, _)| multiplier).collect(),
+        ..Augmentation::default()
+    };
+
+    for (i, &(multiplier, sign)) in rows.iter().enumerate() {
+        for j in 0..vars {
+            a_matrix[(i, j)] = multiplier * a[(i, j)];
+        }
+        b_vector[i] = multiplier * b[i];
+
+        // Value the row's basic column needs so that A x = b holds at the
+        // user's initial point; fall back to 1.0 when that is not positive.
+        let lhs: f64 = (0..vars).map(|j| a_matrix[(i, j)] * initial[j]).sum();
+        let gap = b_vector[i] - lhs;
+
+        match sign {
+            ConstraintSign::LessEqual => {
+                a_matrix[(i, slack_col)] = 1.0;
+                initial[slack_col] = if gap > 1e-6 { gap } else { 1.0 };
+                augmentation.slack_columns.push((i, slack_col));
+                slack_col += 1;
+            }
+            ConstraintSign::GreaterEqual => {
+                a_matrix[(i, slack_col)] = -1.0;
+                a_matrix[(i, artificial_col)] = 1.0;
+                augmentation.slack_columns.push((i, slack_col));
+                augmentation.artificial_columns.push((i, artificial_col));
+                // surplus = 1 keeps the artificial b - lhs + 1 positive
+                // whenever the row is satisfied at the initial point.
+                initial[slack_col] = if -gap > 0.0 { -gap + 1.0 } else { 1.0 };
+                initial[artificial_col] = gap + initial[slack_col];
+                slack_col += 1;
+                artificial_col += 1;
+            }
+            ConstraintSign::Equal => {
+                a_matrix[(i, artificial_col)] = 1.0;
+                augmentation.artificial_columns.push((i, artificial_col));
+                initial[artificial_col] = if gap > 1e-6 { gap } else { 1.0 };
+                artificial_col += 1;
+            }
+        }
+    }
+
+    // Extend objective function with zeros for slack variables and the
+    // Big-M penalty for artificials, against the optimization direction.
+    let big_m = BIG_M_SCALE
+        * (0..vars).fold(1.0_f64, |acc, j| {
+            let column = a.column(j).amax();
+            let dual = if column > 0.0 {
+                c[j].abs() / column.min(1.0)
+            } else {
+                c[j].abs()
+            };
+            acc.max(dual)
+        });
+    let penalty = -sense.sign() * big_m;
+    let mut c_vec: Vec<f64> = c.iter().copied().collect();
+    c_vec.resize(vars + slack_count, 0.0);
+    c_vec.resize(n, penalty);
+    augmentation.big_m = Some(big_m);
+
+    StandardForm {
+        a: a_matrix,
+        b: b_vector,
+        c: DVector::from_vec(c_vec),
+        initial,
+        augmentation,
+    }
+}
+
+/// Labels x1..xn for the original columns, then s_k and a_k for the slack
+/// and artificial columns in the order they were added.
+pub fn default_variable_labels(augmentation: &Augmentation, columns: usize) -> Vec<String> {
+    let mut labels: Vec<String> = (1..=augmentation.original_variables)
+        .map(|j| format!("x{}", j))
+        .collect();
+    labels.resize(columns, String::new());
+    for (k, &(_, col)) in augmentation.slack_columns.iter().enumerate() {
+        labels[col] = format!("s{}", k + 1);
+    }
+    for (k, &(_, col)) in augmentation.artificial_columns.iter().enumerate() {
+        labels[col] = format!("a{}", k + 1);
+    }
+    labels
+}
+
+/// One objective of a weighted goal program, over the user's variables.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Goal {
+    pub weight: f64,
+    #[serde(with = "crate::schema::vector")]
+    pub coefficients: DVector<f64>,
+}
+
+/// The single objective sum_k w_k c_k a weighted goal program is solved as.
+pub fn weighted_objective(goals: &[Goal]) -> DVector<f64> {
+    let n = goals
+        .iter()
+        .map(|goal| goal.coefficients.len())
+        .max()
+        .unwrap_or(0);
+    let mut c = DVector::zeros(n);
+    for goal in goals {
+        c.rows_mut(0, goal.coefficients.len())
+            .axpy(goal.weight, &goal.coefficients, 1.0);
+    }
+    c
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct StartOptions {
+    pub alpha: f64,
+    pub mu: f64,
+    pub find_start: bool,
+    /// Step-length rule of affine scaling.
+    pub step_strategy: StepStrategy,
+    /// Equilibration of A before affine scaling starts.
+    pub scaling: Scaling,
+}
+
+/// A model that passed validation, ready to hand to one of the solvers.
+///
+/// `a`, `b`, `c` and `initial` are already in the augmented solver space; `c`
+/// is in the user's sense (see `sense`), while signs and labels describe the
+/// model as the user entered it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatedProblem {
+    pub method: SolverMethod,
+    pub sense: Sense,
+    #[serde(with = "crate::schema::matrix")]
+    pub a: DMatrix<f64>,
+    #[serde(with = "crate::schema::vector")]
+    pub b: DVector<f64>,
+    #[serde(with = "crate::schema::vector")]
+    pub c: DVector<f64>,
+    /// Symmetric PSD Q of a quadratic objective, padded with zeros for the
+    /// slack and artificial columns. The solvers maximize c^T x - 1/2 x^T Q x.
+    #[serde(with = "crate::schema::option_matrix")]
+    pub quadratic: Option<DMatrix<f64>>,
+    pub initial: Vec<f64>,
+    pub constraint_signs: Vec<ConstraintSign>,
+    /// Integrality flag per original variable.
+    pub integer_variables: Vec<bool>,
+    pub variable_labels: Vec<String>,
+    pub constraint_labels: Vec<String>,
+    /// Named group of each row in `constraint_labels`, such as "capacity" or
+    /// "demand"; rows sharing one form a block of the matrix.
+    pub constraint_groups: Vec<Option<String>>,
+    pub options: StartOptions,
+    pub is_augmented: bool,
+    pub augmentation: Augmentation,
+    /// Set when the model was built from a transportation table.
+    pub transportation: Option<TransportationLayout>,
+    /// Set when the model was built from regression data.
+    pub regression: Option<RegressionLayout>,
+    /// Set when the objective was a ratio rewritten by Charnes–Cooper.
+    pub fractional: Option<FractionalLayout>,
+    /// Singleton rows turned into bounds; `a`, `b`, `c` are then over the
+    /// shifted, non-fixed variables and labels name the reduced rows.
+    pub presolve: Option<Presolve>,
+    /// Objectives `c` was combined from; empty for a single objective.
+    pub goals: Vec<Goal>,
+}
+
+impl ValidatedProblem {
+    /// A x = b, x >= 0 exactly as given, as in "Already Augmented" mode:
+    /// affine scaling from `initial` with α = `alpha`, every row an equation
+    /// and no column added.
+    pub fn standard_form(
+        a: DMatrix<f64>,
+        b: DVector<f64>,
+        c: DVector<f64>,
+        sense: Sense,
+        initial: Vec<f64>,
+        alpha: f64,
+    ) -> Self {
+        let (m, n) = a.shape();
+        let augmentation = Augmentation {
+            original_variables: n,
+            row_multipliers: vec![1.0; m],
+            ..Augmentation::default()
+        };
+        Self {
+            method: SolverMethod::AffineScaling,
+            sense,
+            a,
+            b,
+            c,
+            quadratic: None,
+            initial,
+            constraint_signs: vec![ConstraintSign::Equal; m],
+            integer_variables: vec![false; n],
+            variable_labels: default_variable_labels(&augmentation, n),
+            constraint_labels: (1..=m).map(|i| format!("C{}", i)).collect(),
+            constraint_groups: vec![None; m],
+            options: StartOptions {
+                alpha,
+                mu: 1.0,
+                find_start: false,
+                step_strategy: StepStrategy::default(),
+                scaling: Scaling::default(),
+            },
+            is_augmented: true,
+            augmentation,
+            transportation: None,
+            regression: None,
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        }
+    }
+
+    /// Objective in the solvers' maximization form.
+    pub fn internal_c(&self) -> DVector<f64> {
+        self.c.map(|val| val * self.sense.sign())
+    }
+
+    /// Artificial variables that are still positive in `x`; any such value at
+    /// the optimum means the original constraints cannot all be satisfied.
+    pub fn active_artificials(&self, x: &DVector<f64>, tolerance: f64) -> Vec<usize> {
+        self.augmentation
+            .artificial_columns
+            .iter()
+            .filter(|&&(_, col)| col < x.len() && x[col] > tolerance)
+            .map(|&(row, _)| row)
+            .collect()
+    }
+
+    pub fn has_integer_variables(&self) -> bool {
+        self.integer_variables.iter().any(|&flag| flag)
+    }
+
+    /// The constraints as the user entered them: original columns only, with
+    /// any row negation for a negative right-hand side undone.
+    pub fn original_rows(&self) -> (DMatrix<f64>, DVector<f64>) {
+        let m = self.a.nrows();
+        let n = self.augmentation.original_variables.min(self.a.ncols());
+        let multiplier = |i: usize| {
+            self.augmentation
+                .row_multipliers
+                .get(i)
+                .copied()
+                .unwrap_or(1.0)
+        };
+        let a = DMatrix::from_fn(m, n, |i, j| multiplier(i) * self.a[(i, j)]);
+        let b = DVector::from_fn(m, |i, _| multiplier(i) * self.b[i]);
+        (a, b)
+    }
+
+    /// Relative slack of each user constraint at `x`, (b_i - a_i x) / (1 + |b_i|)
+    /// oriented so that it is nonnegative when the constraint holds. Equality
+    /// rows are always tight and have no slack.
+    pub fn constraint_slacks(&self, x: &DVector<f64>) -> Vec<Option<f64>> {
+        let (a, b) = self.original_rows();
+        let n = a.ncols().min(x.len());
+        (0..a.nrows())
+            .map(|i| {
+                let lhs: f64 = (0..n).map(|j| a[(i, j)] * x[j]).sum();
+                let gap = (b[i] - lhs) / (1.0 + b[i].abs());
+                match self.constraint_signs.get(i) {
+                    Some(ConstraintSign::LessEqual) => Some(gap),
+                    Some(ConstraintSign::GreaterEqual) => Some(-gap),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Each user constraint evaluated at `x` in the original variables, with
+    /// the slack or surplus column augmentation gave it.
+    pub fn constraint_checks(&self, x: &DVector<f64>) -> Vec<ConstraintCheck> {
+        let (a, b) = self.original_rows();
+        let n = a.ncols().min(x.len());
+        (0..a.nrows())
+            .map(|i| {
+                let lhs: f64 = (0..n).map(|j| a[(i, j)] * x[j]).sum();
+                let sign = self
+                    .constraint_signs
+                    .get(i)
+                    .copied()
+                    .unwrap_or(ConstraintSign::Equal);
+                let tolerance = CONSTRAINT_CHECK_TOLERANCE * (1.0 + b[i].abs());
+                let holds = match sign {
+                    ConstraintSign::LessEqual => lhs <= b[i] + tolerance,
+                    ConstraintSign::GreaterEqual => lhs >= b[i] - tolerance,
+                    ConstraintSign::Equal => (lhs - b[i]).abs() <= tolerance,
+                };
+                ConstraintCheck {
+                    label: self
+                        .constraint_labels
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_else(|| format!("Row {}", i + 1)),
+                    lhs,
+                    sign,
+                    rhs: b[i],
+                    holds,
+                    slack_column: self
+                        .augmentation
+                        .slack_columns
+                        .iter()
+                        .find(|&&(row, _)| row == i)
+                        .map(|&(_, col)| col),
+                }
+            })
+            .collect()
+    }
+
+    /// Rows whose relative slack at `x` is below `threshold`.
+    pub fn active_constraints(&self, x: &DVector<f64>, threshold: f64) -> Vec<usize> {
+        self.constraint_slacks(x)
+            .iter()
+            .enumerate()
+            .filter(|(_, slack)| slack.is_some_and(|s| s < threshold))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::report::SolveStatus;
+    use crate::test_models::{assert_optimum, covering, solve};
+
+    #[test]
+    fn big_m_outbids_the_dual_of_a_small_coefficient() {
+        // min x1 s.t. 1e-4 x1 >= 1: the row's dual is 1e4, so an M sized
+        // from |c| alone leaves the artificial in at x1 = 0.
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0])
+            .constraint(&[1e-4], ConstraintSign::GreaterEqual, 1.0)
+            .minimize()
+            .model()
+            .unwrap();
+        assert!(model.augmentation.big_m.unwrap() > 1e4);
+        for method in [
+            SolverMethod::Simplex,
+            SolverMethod::RevisedSimplex,
+            SolverMethod::AffineScaling,
+        ] {
+            let report = solve(&model, method);
+            assert_optimum(&report, 1e4, &[1e4], 1e-3);
+        }
+    }
+
+    #[test]
+    fn every_method_drives_the_artificials_to_zero() {
+        for method in [
+            SolverMethod::AffineScaling,
+            SolverMethod::Simplex,
+            SolverMethod::RevisedSimplex,
+        ] {
+            assert_optimum(&solve(&covering(), method), 9.0, &[3.0, 1.0], 1e-3);
+        }
+    }
+
+    #[test]
+    fn leftover_artificial_is_reported_infeasible() {
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0])
+            .constraint(&[1.0], ConstraintSign::LessEqual, 1.0)
+            .constraint(&[1.0], ConstraintSign::GreaterEqual, 2.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let report = solve(&model, SolverMethod::Simplex);
+        assert_eq!(report.status, SolveStatus::Infeasible);
+    }
+}
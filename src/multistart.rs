@@ -0,0 +1,191 @@
+use nalgebra::DVector;
+
+use crate::interior::{run_to_convergence, warm_start, InteriorPointProblem, SolverOptions};
+use crate::model::ValidatedProblem;
+use crate::phase_one::analytic_center;
+use crate::report::SolveStatus;
+
+/// Iterations each start gets.
+pub const MULTI_START_ITERATION_LIMIT: usize = 500;
+
+/// Decision variables whose low/high choices span the corners; 2^3 = 8
+/// corners at most, so the whole report stays a click away.
+const CORNER_VARIABLES: usize = 3;
+
+/// A corner pulls each of its variables to this multiple of the analytic
+/// center, low or high, before the point is moved back onto A x = b.
+const CORNER_LOW: f64 = 0.1;
+const CORNER_HIGH: f64 = 10.0;
+
+/// Objectives closer than this, relative to 1 + |best|, agree.
+const OBJECTIVE_AGREEMENT: f64 = 1e-6;
+
+/// Solutions further apart than this, relative to 1 + ‖x‖, are different
+/// points even when their objectives agree.
+const SOLUTION_AGREEMENT: f64 = 1e-4;
+
+/// One affine-scaling run of a multi-start report.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StartRun {
+    /// Where the run started, such as "Analytic center" or "Corner (lo, hi)".
+    pub label: String,
+    pub status: SolveStatus,
+    pub iterations: usize,
+    /// c^T x in the user's sense.
+    pub objective: f64,
+    /// Solution in the solver space.
+    pub x: DVector<f64>,
+}
+
+/// What the runs say about the optimum together.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Agreement {
+    /// Every run that finished reached the same objective at the same point.
+    Unique,
+    /// The objectives agree but the points do not: the optimum is a face,
+    /// not a vertex. `spread` is the largest distance between two solutions.
+    AlternateOptima { spread: f64 },
+    /// The objectives differ, so some run stopped early or lost accuracy.
+    /// `spread` is the largest difference between two objectives.
+    Disagree { spread: f64 },
+    /// No run reached an optimum.
+    NoOptimum,
+}
+
+/// Affine scaling from several deterministic starts, to see whether the
+/// optimum depends on where the method begins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MultiStartReport {
+    pub runs: Vec<StartRun>,
+    pub agreement: Agreement,
+}
+
+impl MultiStartReport {
+    /// Runs affine scaling from the user's point, the analytic center and
+    /// the corners of a box around the center, with the model's step rule
+    /// and scaling. `None` for quadratic models and when no strictly
+    /// positive point of A x = b can be found.
+    pub fn run(model: &ValidatedProblem, options: &SolverOptions) -> Option<Self> {
+        if model.quadratic.is_some() {
+            return None;
+        }
+        let (a, b) = (&model.a, &model.b);
+        let center = analytic_center(a, b, model.options.alpha, 500, options)
+            .ok()?
+            .x;
+        let mut starts = vec![("Analytic center".to_string(), center.clone())];
+        let user = DVector::from_iterator(
+            a.ncols(),
+            (0..a.ncols()).map(|j| model.initial.get(j).copied().unwrap_or(1.0)),
+        );
+        if let Some(x) = warm_start(a, b, &user, &center) {
+            starts.insert(0, ("Your point".to_string(), x));
+        }
+        let corner_variables = model.augmentation.original_variables.min(CORNER_VARIABLES);
+        for pattern in 0..1usize << corner_variables {
+            let mut target = center.clone();
+            let mut sides = vec![];
+            for j in 0..corner_variables {
+                let high = pattern & (1 << j) != 0;
+                target[j] *= if high { CORNER_HIGH } else { CORNER_LOW };
+                sides.push(if high { "hi" } else { "lo" });
+            }
+            if let Some(x) = warm_start(a, b, &target, &center) {
+                starts.push((format!("Corner ({})", sides.join(", ")), x));
+            }
+        }
+
+        let runs: Vec<StartRun> = starts
+            .into_iter()
+            .filter_map(|(label, x)| {
+                let mut problem = InteriorPointProblem::new(
+                    a.clone(),
+                    b.clone(),
+                    model.internal_c(),
+                    x,
+                    model.options.alpha,
+                    options.clone(),
+                )
+                .ok()?
+                .with_strategy(model.options.step_strategy)
+                .with_scaling(model.options.scaling);
+                let (report, _) =
+                    run_to_convergence(&mut problem, MULTI_START_ITERATION_LIMIT, None);
+                Some(StartRun {
+                    label,
+                    objective: model.c.dot(&report.solution),
+                    status: report.status,
+                    iterations: report.iterations,
+                    x: report.solution,
+                })
+            })
+            .collect();
+        let agreement = agreement(&runs);
+        Some(Self { runs, agreement })
+    }
+
+    /// Runs that did not reach an optimum.
+    pub fn troubled(&self) -> Vec<&StartRun> {
+        self.runs
+            .iter()
+            .filter(|run| run.status != SolveStatus::Optimal)
+            .collect()
+    }
+
+    pub fn describe(&self) -> String {
+        let verdict = match &self.agreement {
+            Agreement::Unique => {
+                "Every run reached the same optimum at the same point, so it is most likely unique.".to_string()
+            }
+            Agreement::AlternateOptima { spread } => format!(
+                "The runs agree on the objective but end up to {:.3e} apart: there are alternate optima, and where the method lands depends on where it starts.",
+                spread
+            ),
+            Agreement::Disagree { spread } => format!(
+                "The objectives differ by up to {:.3e}: some run stopped early or lost accuracy. Tighten the stopping criteria or equilibrate.",
+                spread
+            ),
+            Agreement::NoOptimum => "No run reached an optimum.".to_string(),
+        };
+        let troubled = self.troubled();
+        if troubled.is_empty() || self.agreement == Agreement::NoOptimum {
+            return verdict;
+        }
+        let labels: Vec<&str> = troubled.iter().map(|run| run.label.as_str()).collect();
+        format!(
+            "{} {} did not reach an optimum.",
+            verdict,
+            labels.join(", ")
+        )
+    }
+}
+
+/// Compares the runs that reached an optimum.
+fn agreement(runs: &[StartRun]) -> Agreement {
+    let optimal: Vec<&StartRun> = runs
+        .iter()
+        .filter(|run| run.status == SolveStatus::Optimal)
+        .collect();
+    let Some(first) = optimal.first() else {
+        return Agreement::NoOptimum;
+    };
+    let (low, high) = optimal
+        .iter()
+        .fold((first.objective, first.objective), |(lo, hi), run| {
+            (lo.min(run.objective), hi.max(run.objective))
+        });
+    let scale = 1.0 + low.abs().max(high.abs());
+    if high - low > OBJECTIVE_AGREEMENT * scale {
+        return Agreement::Disagree { spread: high - low };
+    }
+    let spread = optimal
+        .iter()
+        .flat_map(|p| optimal.iter().map(move |q| (&p.x - &q.x).norm()))
+        .fold(0.0, f64::max);
+    let size = 1.0 + optimal.iter().map(|run| run.x.norm()).fold(0.0, f64::max);
+    if spread > SOLUTION_AGREEMENT * size {
+        Agreement::AlternateOptima { spread }
+    } else {
+        Agreement::Unique
+    }
+}
@@ -0,0 +1,185 @@
+use nalgebra::DVector;
+
+use crate::barrier::BarrierIteration;
+use crate::hsd::{HsdIteration, Indicator};
+use crate::qp::QpIteration;
+use crate::report::{SolveReport, SolveStatus};
+use crate::revised_simplex::RevisedSimplexIteration;
+use crate::simplex::SimplexIteration;
+
+/// Speaking rate of the narration; slightly slow so the numbers are easy to
+/// follow in a lecture hall.
+#[cfg(feature = "web")]
+const SPEECH_RATE: f32 = 0.9;
+
+/// A number as it should be read aloud: at most two decimals, no trailing
+/// zeros and no "minus zero".
+fn spoken(val: f64) -> String {
+    let text = format!("{:.2}", val);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" | "" => "0".to_string(),
+        text => text.replace('-', "minus "),
+    }
+}
+
+/// The nonzero entries among the first `original` variables, the ones the
+/// user typed in.
+fn point(x: &DVector<f64>, original: usize) -> String {
+    let nonzero: Vec<String> = x
+        .iter()
+        .take(original)
+        .enumerate()
+        .filter(|(_, val)| spoken(**val) != "0")
+        .map(|(j, val)| format!("x{} is {}", j + 1, spoken(*val)))
+        .collect();
+    if nonzero.is_empty() {
+        "All variables are zero.".to_string()
+    } else if nonzero.len() < original.min(x.len()) {
+        format!("{}; the others are zero.", nonzero.join(", "))
+    } else {
+        format!("{}.", nonzero.join(", "))
+    }
+}
+
+fn pivot(entering: Option<usize>, leaving: Option<usize>) -> String {
+    match (entering, leaving) {
+        (Some(j), Some(i)) => format!("Next, x{} enters the basis and x{} leaves.", j + 1, i + 1),
+        (Some(j), None) => format!(
+            "x{} can increase without bound, so the problem is unbounded.",
+            j + 1
+        ),
+        _ => "No reduced cost can improve the objective, so this basis is optimal.".to_string(),
+    }
+}
+
+pub fn interior_step(
+    k: usize,
+    x: &DVector<f64>,
+    objective: f64,
+    centering: bool,
+    original: usize,
+) -> String {
+    let kind = if k == 0 {
+        "Starting point."
+    } else if centering {
+        "Centering step: moved away from the boundary without following the gradient."
+    } else {
+        "Moved along the projected gradient in the rescaled space."
+    };
+    format!(
+        "Iteration {}. {} The objective is {}. {}",
+        k,
+        kind,
+        spoken(objective),
+        point(x, original)
+    )
+}
+
+pub fn barrier_step(k: usize, iter: &BarrierIteration, objective: f64, original: usize) -> String {
+    let kind = if iter.centering {
+        "Centering Newton step at fixed mu"
+    } else {
+        "Newton step"
+    };
+    format!(
+        "Iteration {}. {} with step length {}, barrier parameter mu is {}. The objective is {}. {}",
+        k,
+        kind,
+        spoken(iter.step_length),
+        spoken(iter.mu),
+        spoken(objective),
+        point(&iter.current_x, original)
+    )
+}
+
+pub fn simplex_step(k: usize, iter: &SimplexIteration, objective: f64, original: usize) -> String {
+    format!(
+        "Tableau {}. The objective is {}. {} {}",
+        k,
+        spoken(objective),
+        point(&iter.current_x, original),
+        pivot(iter.entering, iter.leaving_row.map(|row| iter.basis[row]))
+    )
+}
+
+pub fn revised_step(
+    k: usize,
+    iter: &RevisedSimplexIteration,
+    objective: f64,
+    original: usize,
+) -> String {
+    format!(
+        "Basis {}. The objective is {}. {} {}",
+        k,
+        spoken(objective),
+        point(&iter.current_x, original),
+        pivot(iter.entering, iter.leaving_row.map(|row| iter.basis[row]))
+    )
+}
+
+pub fn qp_step(k: usize, iter: &QpIteration, original: usize) -> String {
+    format!(
+        "Iteration {}. Newton step with step length {}, barrier parameter mu is {}. The objective is {}. {}",
+        k,
+        spoken(iter.step_length),
+        spoken(iter.mu),
+        spoken(iter.objective),
+        point(&iter.current_x, original)
+    )
+}
+
+pub fn hsd_step(k: usize, iter: &HsdIteration, objective: f64, original: usize) -> String {
+    let heading = match iter.indicator() {
+        Indicator::Optimum => "tau dominates kappa, so the run is heading for an optimum",
+        Indicator::Certificate => {
+            "kappa dominates tau, so the run is heading for proof of infeasibility or unboundedness"
+        }
+        Indicator::Undecided => "tau and kappa are still comparable",
+    };
+    format!(
+        "Iteration {}. Self-dual Newton step with step length {}; tau is {} and kappa is {}: {}. The objective is {}. {}",
+        k,
+        spoken(iter.step_length),
+        spoken(iter.tau),
+        spoken(iter.kappa),
+        heading,
+        spoken(objective),
+        point(&iter.current_x, original)
+    )
+}
+
+/// Closing sentence once a run has finished.
+pub fn outcome(report: &SolveReport) -> String {
+    match &report.status {
+        SolveStatus::Optimal => format!(
+            "Solved. The optimal objective is {} after {} iterations.",
+            spoken(report.objective),
+            report.iterations
+        ),
+        SolveStatus::Failed(reason) => format!("The solver stopped: {}.", reason),
+        status => format!("{} after {} iterations.", status.label(), report.iterations),
+    }
+}
+
+/// Reads `text` aloud with the browser's speech synthesis, interrupting any
+/// sentence still being spoken. Does nothing where the API is missing.
+#[cfg(feature = "web")]
+pub fn speak(text: &str) {
+    let Some(synthesis) = web_sys::window().and_then(|window| window.speech_synthesis().ok())
+    else {
+        return;
+    };
+    synthesis.cancel();
+    if let Ok(utterance) = web_sys::SpeechSynthesisUtterance::new_with_text(text) {
+        utterance.set_rate(SPEECH_RATE);
+        synthesis.speak(&utterance);
+    }
+}
+
+#[cfg(feature = "web")]
+pub fn silence() {
+    if let Some(synthesis) = web_sys::window().and_then(|window| window.speech_synthesis().ok()) {
+        synthesis.cancel();
+    }
+}
@@ -0,0 +1,406 @@
+use crate::interior::SolverOptions;
+use crate::logging;
+
+/// Reduced costs closer to zero than this do not qualify an arc to enter.
+const REDUCED_COST_TOLERANCE: f64 = 1e-9;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Arc {
+    pub from: usize,
+    pub to: usize,
+    /// Upper bound on the flow; `None` for an uncapacitated arc.
+    pub capacity: Option<f64>,
+    pub cost: f64,
+}
+
+/// Minimum-cost flow: send flow along `arcs` so every node i ends up with net
+/// outflow `supplies[i]` (negative for demand), at least cost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkProblem {
+    pub supplies: Vec<f64>,
+    pub arcs: Vec<Arc>,
+}
+
+impl NetworkProblem {
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        let n = self.supplies.len();
+        if n < 2 || self.arcs.is_empty() {
+            errors.push("The network needs at least two nodes and one arc.".to_string());
+            return Err(errors);
+        }
+        let total: f64 = self.supplies.iter().sum();
+        if total.abs() > 1e-9 {
+            errors.push(format!(
+                "Supplies and demands must balance, but they sum to {}.",
+                total
+            ));
+        }
+        for (k, arc) in self.arcs.iter().enumerate() {
+            if arc.from >= n || arc.to >= n || arc.from == arc.to {
+                errors.push(format!(
+                    "Arc {} must join two different nodes among 1..{}.",
+                    k + 1,
+                    n
+                ));
+            }
+            if arc.capacity.is_some_and(|cap| cap < 0.0 || cap.is_nan()) {
+                errors.push(format!("Arc {} has a negative capacity.", k + 1));
+            }
+            if !arc.cost.is_finite() {
+                errors.push(format!("Arc {} needs a finite cost.", k + 1));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Which bound a non-tree arc sits at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ArcState {
+    Tree,
+    Lower,
+    Upper,
+}
+
+/// One pivot: `entering` joins the spanning tree, flow `theta` is pushed
+/// around `cycle`, and `leaving` drops out (it may be `entering` itself when
+/// that arc just moves to its other bound).
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkIteration {
+    pub entering: usize,
+    pub leaving: usize,
+    pub theta: f64,
+    /// Arcs of the pivot cycle, entering arc first.
+    pub cycle: Vec<usize>,
+    /// Node potentials and reduced costs the entering arc was chosen from.
+    pub potentials: Vec<f64>,
+    pub reduced_costs: Vec<f64>,
+    pub flows: Vec<f64>,
+    pub objective: f64,
+}
+
+#[derive(Debug)]
+pub enum NetworkError {
+    Optimal,
+    /// A negative-cost cycle of uncapacitated arcs, starting at this arc.
+    Unbounded(usize),
+}
+
+/// Network simplex state for a `NetworkProblem`.
+///
+/// An extra root node is joined to every node by an artificial arc with cost
+/// `big_m`, carrying that node's supply or demand; those arcs form the first
+/// spanning tree. Arcs `0..arcs` are the user's, the rest are artificial.
+pub struct NetworkSimplexProblem {
+    pub nodes: usize,
+    pub arcs: Vec<Arc>,
+    pub user_arcs: usize,
+    pub flows: Vec<f64>,
+    pub states: Vec<ArcState>,
+    pub big_m: f64,
+    pub options: SolverOptions,
+}
+
+impl NetworkSimplexProblem {
+    pub fn new(problem: &NetworkProblem) -> Self {
+        let n = problem.supplies.len();
+        let root = n;
+        let big_m = 1.0 + problem.arcs.iter().map(|arc| arc.cost.abs()).sum::<f64>();
+
+        let mut arcs = problem.arcs.clone();
+        let mut flows = vec![0.0; arcs.len()];
+        let mut states = vec![ArcState::Lower; arcs.len()];
+        for (i, &supply) in problem.supplies.iter().enumerate() {
+            let (from, to) = if supply >= 0.0 { (i, root) } else { (root, i) };
+            arcs.push(Arc {
+                from,
+                to,
+                capacity: None,
+                cost: big_m,
+            });
+            flows.push(supply.abs());
+            states.push(ArcState::Tree);
+        }
+
+        Self {
+            nodes: n,
+            arcs,
+            user_arcs: problem.arcs.len(),
+            flows,
+            states,
+            big_m,
+            options: SolverOptions::default(),
+        }
+    }
+
+    pub fn objective(&self) -> f64 {
+        self.arcs
+            .iter()
+            .zip(&self.flows)
+            .map(|(arc, flow)| arc.cost * flow)
+            .sum()
+    }
+
+    /// Artificial arcs still carrying flow; at the optimum this means the
+    /// supplies cannot be routed to the demands.
+    pub fn active_artificials(&self) -> Vec<usize> {
+        (self.user_arcs..self.arcs.len())
+            .filter(|&k| self.flows[k] > REDUCED_COST_TOLERANCE)
+            .collect()
+    }
+
+    /// Parent arc and depth of every node in the spanning tree, rooted at the
+    /// artificial root, with potentials pi so tree arcs have zero reduced cost
+    /// c_uv + pi_u - pi_v.
+    fn tree(&self) -> (Vec<Option<usize>>, Vec<usize>, Vec<f64>) {
+        let total = self.nodes + 1;
+        let mut adjacent: Vec<Vec<usize>> = vec![vec![]; total];
+        for (k, arc) in self.arcs.iter().enumerate() {
+            if self.states[k] == ArcState::Tree {
+                adjacent[arc.from].push(k);
+                adjacent[arc.to].push(k);
+            }
+        }
+        let mut parent = vec![None; total];
+        let mut depth = vec![0; total];
+        let mut potentials = vec![0.0; total];
+        let mut visited = vec![false; total];
+        let mut stack = vec![self.nodes];
+        visited[self.nodes] = true;
+        while let Some(u) = stack.pop() {
+            for &k in &adjacent[u] {
+                let arc = &self.arcs[k];
+                let v = if arc.from == u { arc.to } else { arc.from };
+                if visited[v] {
+                    continue;
+                }
+                visited[v] = true;
+                parent[v] = Some(k);
+                depth[v] = depth[u] + 1;
+                potentials[v] = if arc.from == u {
+                    potentials[u] + arc.cost
+                } else {
+                    potentials[u] - arc.cost
+                };
+                stack.push(v);
+            }
+        }
+        (parent, depth, potentials)
+    }
+
+    fn reduced_costs(&self, potentials: &[f64]) -> Vec<f64> {
+        self.arcs
+            .iter()
+            .map(|arc| arc.cost + potentials[arc.from] - potentials[arc.to])
+            .collect()
+    }
+
+    /// Dantzig's rule: the arc whose reduced cost most improves the objective,
+    /// at its lower bound with c~ < 0 or at its upper bound with c~ > 0.
+    fn choose_entering(&self, reduced_costs: &[f64]) -> Option<usize> {
+        (0..self.arcs.len())
+            .filter_map(|k| {
+                let violation = match self.states[k] {
+                    ArcState::Lower => -reduced_costs[k],
+                    ArcState::Upper => reduced_costs[k],
+                    ArcState::Tree => 0.0,
+                };
+                (violation > REDUCED_COST_TOLERANCE).then_some((k, violation))
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(k, _)| k)
+    }
+
+    /// Room to push more flow through arc `k` in the given direction.
+    fn residual(&self, k: usize, forward: bool) -> f64 {
+        if forward {
+            self.arcs[k]
+                .capacity
+                .map_or(f64::INFINITY, |cap| cap - self.flows[k])
+        } else {
+            self.flows[k]
+        }
+    }
+}
+
+pub fn perform_network_simplex_iteration(
+    problem: &mut NetworkSimplexProblem,
+) -> Result<NetworkIteration, NetworkError> {
+    let (parent, depth, potentials) = problem.tree();
+    let reduced_costs = problem.reduced_costs(&potentials);
+    let entering = problem
+        .choose_entering(&reduced_costs)
+        .ok_or(NetworkError::Optimal)?;
+
+    // Push flow from `source` to `sink` through the entering arc, then back
+    // along the tree path from `sink` to `source`.
+    let arc = &problem.arcs[entering];
+    let entering_forward = problem.states[entering] == ArcState::Lower;
+    let (source, sink) = if entering_forward {
+        (arc.from, arc.to)
+    } else {
+        (arc.to, arc.from)
+    };
+
+    let step_up = |node: usize| {
+        let k = parent[node].expect("every non-root node has a parent arc");
+        let arc = &problem.arcs[k];
+        let up = if arc.from == node { arc.to } else { arc.from };
+        (k, up)
+    };
+    // Walking up from `sink` follows the cycle direction; walking up from
+    // `source` runs against it.
+    let mut from_sink = vec![];
+    let mut from_source = vec![];
+    let (mut a, mut b) = (sink, source);
+    while a != b {
+        if depth[a] >= depth[b] {
+            let (k, up) = step_up(a);
+            from_sink.push((k, problem.arcs[k].from == a));
+            a = up;
+        } else {
+            let (k, up) = step_up(b);
+            from_source.push((k, problem.arcs[k].to == b));
+            b = up;
+        }
+    }
+    let mut cycle: Vec<(usize, bool)> = vec![(entering, entering_forward)];
+    cycle.extend(from_sink);
+    cycle.extend(from_source.into_iter().rev());
+
+    let mut theta = f64::INFINITY;
+    let mut leaving = entering;
+    for &(k, forward) in &cycle {
+        let room = problem.residual(k, forward);
+        if room < theta {
+            theta = room;
+            leaving = k;
+        }
+    }
+    if theta.is_infinite() {
+        return Err(NetworkError::Unbounded(entering));
+    }
+
+    for &(k, forward) in &cycle {
+        if forward {
+            problem.flows[k] += theta;
+        } else {
+            problem.flows[k] -= theta;
+        }
+    }
+    let leaving_state = match problem.arcs[leaving].capacity {
+        Some(cap) if problem.flows[leaving] >= cap - REDUCED_COST_TOLERANCE => ArcState::Upper,
+        _ => ArcState::Lower,
+    };
+    problem.states[entering] = ArcState::Tree;
+    problem.states[leaving] = leaving_state;
+
+    if problem.options.logs_summary() {
+        tracing::info!(
+            entering,
+            leaving,
+            theta,
+            "Network simplex: arc {} enters, arc {} leaves, theta = {}",
+            entering + 1,
+            leaving + 1,
+            theta
+        );
+    }
+    if problem.options.logs_full() {
+        logging::dump_vector!("Potentials", &potentials);
+        logging::dump_vector!("Flows", &problem.flows);
+    }
+
+    Ok(NetworkIteration {
+        entering,
+        leaving,
+        theta,
+        cycle: cycle.into_iter().map(|(k, _)| k).collect(),
+        potentials,
+        reduced_costs,
+        flows: problem.flows.clone(),
+        objective: problem.objective(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn arc(from: usize, to: usize, capacity: Option<f64>, cost: f64) -> Arc {
+        Arc {
+            from,
+            to,
+            capacity,
+            cost,
+        }
+    }
+
+    /// Pivots to the end; `Err` carries the arc of an unbounded cycle.
+    fn solve(problem: &NetworkProblem) -> (NetworkSimplexProblem, Result<(), usize>) {
+        let mut simplex = NetworkSimplexProblem::new(problem);
+        for _ in 0..100 {
+            match perform_network_simplex_iteration(&mut simplex) {
+                Ok(_) => {}
+                Err(NetworkError::Optimal) => return (simplex, Ok(())),
+                Err(NetworkError::Unbounded(k)) => return (simplex, Err(k)),
+            }
+        }
+        panic!("network simplex did not stop");
+    }
+
+    #[test]
+    fn fills_the_cheap_path_to_capacity() {
+        // 4 units from node 1 to node 3: 3 along 1-2-3 at cost 2, the
+        // last one directly at cost 3, for 9 in all.
+        let problem = NetworkProblem {
+            supplies: vec![4.0, 0.0, -4.0],
+            arcs: vec![
+                arc(0, 1, Some(3.0), 1.0),
+                arc(1, 2, None, 1.0),
+                arc(0, 2, None, 3.0),
+            ],
+        };
+        assert!(problem.validate().is_ok());
+        let (simplex, result) = solve(&problem);
+        assert!(result.is_ok());
+        assert_eq!(&simplex.flows[..3], &[3.0, 3.0, 1.0]);
+        assert_eq!(simplex.objective(), 9.0);
+        assert!(simplex.active_artificials().is_empty());
+    }
+
+    #[test]
+    fn short_capacity_leaves_artificial_flow() {
+        let problem = NetworkProblem {
+            supplies: vec![4.0, -4.0],
+            arcs: vec![arc(0, 1, Some(2.0), 1.0)],
+        };
+        let (simplex, result) = solve(&problem);
+        assert!(result.is_ok());
+        assert!(!simplex.active_artificials().is_empty());
+    }
+
+    #[test]
+    fn negative_uncapacitated_cycle_is_unbounded() {
+        let problem = NetworkProblem {
+            supplies: vec![1.0, -1.0],
+            arcs: vec![arc(0, 1, None, -1.0), arc(1, 0, None, -1.0)],
+        };
+        assert!(solve(&problem).1.is_err());
+    }
+
+    #[test]
+    fn unbalanced_supplies_are_rejected() {
+        let problem = NetworkProblem {
+            supplies: vec![2.0, -1.0],
+            arcs: vec![arc(0, 1, None, 1.0)],
+        };
+        let errors = problem.validate().unwrap_err();
+        assert!(errors[0].contains("balance"), "{:?}", errors);
+    }
+}
@@ -0,0 +1,79 @@
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+/// Vector norm used for displayed residuals and the step-norm stopping
+/// test. Courses differ on which one they mean by |r|.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Norm {
+    /// Largest |v_i|.
+    #[default]
+    Infinity,
+    /// Sum of |v_i|.
+    One,
+    /// Euclidean length.
+    Two,
+}
+
+impl Norm {
+    pub const ALL: [Norm; 3] = [Norm::Infinity, Norm::One, Norm::Two];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Norm::Infinity => "∞-norm (largest entry)",
+            Norm::One => "1-norm (sum of entries)",
+            Norm::Two => "2-norm (Euclidean)",
+        }
+    }
+
+    /// Subscript shown after ‖·‖.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Norm::Infinity => "∞",
+            Norm::One => "1",
+            Norm::Two => "2",
+        }
+    }
+
+    /// Name used in repro exports.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Norm::Infinity => "inf",
+            Norm::One => "1",
+            Norm::Two => "2",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|norm| norm.key() == key)
+    }
+
+    pub fn of(&self, v: &DVector<f64>) -> f64 {
+        match self {
+            Norm::Infinity => v.amax(),
+            Norm::One => v.iter().map(|val| val.abs()).sum(),
+            Norm::Two => v.norm(),
+        }
+    }
+
+    /// "‖name‖∞", for labels.
+    pub fn format(&self, name: &str) -> String {
+        format!("‖{}‖{}", name, self.symbol())
+    }
+}
+
+/// KKT residuals of one step, measured in the chosen norm. With
+/// s = A^T y - c for the step's dual estimate y, an optimal pair has all
+/// three of primal, dual and complementarity at zero.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Residuals {
+    pub norm: Norm,
+    /// ‖A x - b‖ after the step, in the units the model was entered in.
+    pub primal: f64,
+    /// ‖min(s, 0)‖, how far y is from dual feasibility A^T y >= c.
+    pub dual: f64,
+    /// ‖X s‖, the products x_j s_j that vanish at the optimum.
+    pub complementarity: f64,
+    /// ‖Δx‖, how far the step moved x.
+    pub step: f64,
+}
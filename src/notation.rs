@@ -0,0 +1,126 @@
+/// How the k-th iterate is written in headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IterateStyle {
+    /// Just `x`; the iteration number is in the heading.
+    Plain,
+    /// `x^(k)`, as in most interior-point texts.
+    Superscript,
+    /// `x_k`.
+    Subscript,
+}
+
+impl IterateStyle {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IterateStyle::Plain => "x",
+            IterateStyle::Superscript => "x^(k)",
+            IterateStyle::Subscript => "x_k",
+        }
+    }
+}
+
+/// Symbols the headers and explanations use, so the app can match an
+/// instructor's textbook. The defaults are the symbols the app always showed;
+/// a symbol left empty falls back to its default.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Notation {
+    /// Dual variables, `y` by default; many texts use `λ` or `π`.
+    pub dual: String,
+    /// Step size of affine scaling, `α` by default.
+    pub step: String,
+    /// Barrier parameter, `μ` by default.
+    pub barrier: String,
+    pub iterate: IterateStyle,
+}
+
+impl Default for Notation {
+    fn default() -> Self {
+        Self {
+            dual: "y".to_string(),
+            step: "α".to_string(),
+            barrier: "μ".to_string(),
+            iterate: IterateStyle::Plain,
+        }
+    }
+}
+
+/// Greek letters an instructor is likely to pick, with their LaTeX macros.
+const GREEK: &[(char, &str)] = &[
+    ('α', "\\alpha"),
+    ('β', "\\beta"),
+    ('γ', "\\gamma"),
+    ('δ', "\\delta"),
+    ('ε', "\\varepsilon"),
+    ('η', "\\eta"),
+    ('θ', "\\theta"),
+    ('λ', "\\lambda"),
+    ('μ', "\\mu"),
+    ('ν', "\\nu"),
+    ('π', "\\pi"),
+    ('ρ', "\\rho"),
+    ('σ', "\\sigma"),
+    ('τ', "\\tau"),
+    ('ω', "\\omega"),
+    ('Δ', "\\Delta"),
+];
+
+impl Notation {
+    pub fn dual(&self) -> &str {
+        or_default(&self.dual, "y")
+    }
+
+    pub fn step(&self) -> &str {
+        or_default(&self.step, "α")
+    }
+
+    pub fn barrier(&self) -> &str {
+        or_default(&self.barrier, "μ")
+    }
+
+    /// The iterate at iteration `k`, e.g. `x^(3)` or `x_3`.
+    pub fn iterate(&self, k: usize) -> String {
+        match self.iterate {
+            IterateStyle::Plain => "x".to_string(),
+            IterateStyle::Superscript => format!("x^({})", k),
+            IterateStyle::Subscript => format!("x_{}", k),
+        }
+    }
+
+    /// `symbol` typeset for LaTeX: Greek letters become macros and the
+    /// iterate forms `x^(k)` and `x_k` become `x^{(k)}` and `x_{k}`.
+    pub fn latex(&self, symbol: &str) -> String {
+        let mut out = String::new();
+        let mut chars = symbol.chars().peekable();
+        while let Some(ch) = chars.next() {
+            if let Some((_, macro_name)) = GREEK.iter().find(|(letter, _)| *letter == ch) {
+                out.push_str(macro_name);
+                // Keep "\lambdax" from reading as one macro.
+                if chars.peek().is_some_and(|next| next.is_ascii_alphabetic()) {
+                    out.push(' ');
+                }
+            } else if ch == '^' || ch == '_' {
+                let script: String = chars.by_ref().collect();
+                out.push(ch);
+                out.push('{');
+                out.push_str(&script);
+                out.push('}');
+            } else {
+                out.push(ch);
+            }
+        }
+        out
+    }
+
+    /// `symbol` inline in Markdown: wrapped as inline math so renderers with
+    /// math support typeset it like the LaTeX export.
+    pub fn markdown(&self, symbol: &str) -> String {
+        format!("${}$", self.latex(symbol))
+    }
+}
+
+fn or_default<'a>(symbol: &'a str, default: &'a str) -> &'a str {
+    match symbol.trim() {
+        "" => default,
+        symbol => symbol,
+    }
+}
@@ -0,0 +1,69 @@
+/// localStorage key of the chosen palette; someone who needs a colorblind-safe
+/// palette should not have to pick it on every visit.
+#[cfg(feature = "web")]
+const PALETTE_KEY: &str = "ipsolver.palette";
+
+/// Colors of the plots, graphs and table highlights. Each palette is a class
+/// on the app container that sets the CSS custom properties those styles read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Palette {
+    #[default]
+    Standard,
+    /// Okabe–Ito colors, distinguishable with any common color vision
+    /// deficiency.
+    ColorblindSafe,
+    /// Black and saturated primaries on white, for projectors and low vision.
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [
+        Palette::Standard,
+        Palette::ColorblindSafe,
+        Palette::HighContrast,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Palette::Standard => "Standard",
+            Palette::ColorblindSafe => "Colorblind-safe",
+            Palette::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn class(&self) -> &'static str {
+        match self {
+            Palette::Standard => "palette-standard",
+            Palette::ColorblindSafe => "palette-colorblind",
+            Palette::HighContrast => "palette-high-contrast",
+        }
+    }
+
+    #[cfg(feature = "web")]
+    fn from_class(class: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|palette| palette.class() == class)
+    }
+}
+
+#[cfg(feature = "web")]
+fn storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+/// The palette saved in this browser, or the standard one.
+#[cfg(feature = "web")]
+pub fn load_palette() -> Palette {
+    storage()
+        .and_then(|storage| storage.get_item(PALETTE_KEY).ok()?)
+        .and_then(|class| Palette::from_class(&class))
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "web")]
+pub fn save_palette(palette: Palette) {
+    if let Some(storage) = storage() {
+        let _ = storage.set_item(PALETTE_KEY, palette.class());
+    }
+}
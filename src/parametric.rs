@@ -0,0 +1,333 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::SolverOptions;
+use crate::model::{big_m_form, ConstraintSign, ValidatedProblem};
+use crate::race::{RaceSolver, RACE_ITERATION_LIMIT};
+use crate::report::SolveStatus;
+
+/// Relative change in slope that counts as a breakpoint; the interior
+/// methods only approach the optimum, so a tighter test would flag noise.
+const SLOPE_TOLERANCE: f64 = 1e-2;
+
+/// The coefficient a parametric analysis varies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Parameter {
+    /// c_j of original column j.
+    Objective(usize),
+    /// b_i of constraint i, as the user entered it.
+    Rhs(usize),
+}
+
+impl Parameter {
+    pub fn label(&self, model: &ValidatedProblem) -> String {
+        match *self {
+            Parameter::Objective(j) => format!("c of {}", model.variable_labels[j]),
+            Parameter::Rhs(i) => format!("b of {}", model.constraint_labels[i]),
+        }
+    }
+
+    /// The coefficient's value in `model`.
+    pub fn current(&self, model: &ValidatedProblem) -> f64 {
+        match *self {
+            Parameter::Objective(j) => model.c[j],
+            Parameter::Rhs(i) => model.original_rows().1[i],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParametricPoint {
+    pub value: f64,
+    pub status: SolveStatus,
+    /// Optimal value in the user's sense. Also set at the iteration limit,
+    /// where affine scaling usually stops close to the optimum; `None` when
+    /// the point has no optimum.
+    pub objective: Option<f64>,
+}
+
+/// Optimal value over a grid of parameter values, with the points where the
+/// piecewise-linear value function bends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParametricAnalysis {
+    pub parameter: Parameter,
+    pub points: Vec<ParametricPoint>,
+    /// Parameter values where the slope of the optimal value changes, or
+    /// where the problem turns infeasible or unbounded.
+    pub breakpoints: Vec<f64>,
+}
+
+/// Re-solves `model` with `parameter` set to each of `steps` evenly spaced
+/// values in `[from, to]`, using the model's own method.
+pub fn analyze(
+    model: &ValidatedProblem,
+    parameter: Parameter,
+    from: f64,
+    to: f64,
+    steps: usize,
+    options: &SolverOptions,
+) -> ParametricAnalysis {
+    let steps = steps.max(2);
+    let points: Vec<ParametricPoint> = (0..steps)
+        .map(|k| {
+            let value = from + (to - from) * k as f64 / (steps - 1) as f64;
+            solve_at(model, parameter, value, options)
+        })
+        .collect();
+    let breakpoints = find_breakpoints(&points);
+    ParametricAnalysis {
+        parameter,
+        points,
+        breakpoints,
+    }
+}
+
+/// `model` with `parameter` replaced by `value`.
+fn with_parameter(model: &ValidatedProblem, parameter: Parameter, value: f64) -> ValidatedProblem {
+    if model.is_augmented {
+        let mut model = model.clone();
+        match parameter {
+            Parameter::Objective(j) => model.c[j] = value,
+            Parameter::Rhs(i) => model.b[i] = value,
+        }
+        return model;
+    }
+
+    let (a, mut b) = model.original_rows();
+    let mut c = model.c.rows(0, a.ncols()).into_owned();
+    match parameter {
+        Parameter::Objective(j) => c[j] = value,
+        Parameter::Rhs(i) => b[i] = value,
+    }
+    rebuild(model, &a, &b, &c, &model.constraint_signs)
+}
+
+/// `model` with its user rows and objective replaced by `a x (signs) b` and
+/// `c`, over the original columns. The standard form is rebuilt, so a
+/// right-hand side that changes sign gets the right slack or artificial
+/// column. Only for auto-augmented models.
+pub(crate) fn rebuild(
+    model: &ValidatedProblem,
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    c: &DVector<f64>,
+    signs: &[ConstraintSign],
+) -> ValidatedProblem {
+    let mut model = model.clone();
+    let n = a.ncols();
+    let initial = &model.initial[..n.min(model.initial.len())];
+    let form = big_m_form(a, b, c, signs, initial, model.sense);
+    model.quadratic = model.quadratic.map(|q| {
+        let total = form.a.ncols();
+        let mut padded = DMatrix::zeros(total, total);
+        padded
+            .view_mut((0, 0), (n, n))
+            .copy_from(&q.view((0, 0), (n, n)));
+        padded
+    });
+    model.a = form.a;
+    model.b = form.b;
+    model.c = form.c;
+    model.constraint_signs = signs.to_vec();
+    model.initial = form.initial;
+    model.augmentation = form.augmentation;
+    model
+}
+
+/// Whether `model.initial` still satisfies A x = b, x > 0; the interior
+/// methods need that, and a new right-hand side usually breaks it.
+fn start_is_interior(model: &ValidatedProblem) -> bool {
+    let n = model.a.ncols();
+    if model.initial.len() < n || model.initial.iter().any(|&v| v <= 0.0) {
+        return false;
+    }
+    let x = DVector::from_column_slice(&model.initial[..n]);
+    (&model.a * x - &model.b).amax() <= 1e-6 * (1.0 + model.b.amax())
+}
+
+/// A modified copy of the submitted model, solved to the end.
+pub(crate) struct Resolved {
+    /// `Optimal`, or `IterationLimit` when the iterate is still usable.
+    pub status: SolveStatus,
+    /// Final iterate in the solver space.
+    pub x: DVector<f64>,
+    /// In the user's sense, including the presolve offset of the c the
+    /// model was presolved with.
+    pub objective: f64,
+}
+
+/// Solves `model` with its own method, finding a fresh start for the
+/// interior methods when the old one no longer fits. `Err` carries the
+/// status of a run that ended without an optimum.
+pub(crate) fn resolve(
+    mut model: ValidatedProblem,
+    options: &SolverOptions,
+) -> Result<Resolved, SolveStatus> {
+    if model.method.is_interior() && !start_is_interior(&model) {
+        model.options.find_start = true;
+    }
+    let mut solver = RaceSolver::new(model.method, &model, options).map_err(SolveStatus::Failed)?;
+
+    let mut status = SolveStatus::IterationLimit;
+    for _ in 0..RACE_ITERATION_LIMIT {
+        if let Err(stop) = solver.step() {
+            status = stop;
+            break;
+        }
+    }
+    if !matches!(status, SolveStatus::Optimal | SolveStatus::IterationLimit) {
+        return Err(status);
+    }
+    let x = solver.current_x();
+    if (&model.a * &x - &model.b).amax() > 1e-4 * (1.0 + model.b.amax()) {
+        return Err(SolveStatus::Failed(
+            "the iterate drifted off A x = b".to_string(),
+        ));
+    }
+    if !model.active_artificials(&x, 1e-6).is_empty() {
+        return Err(SolveStatus::Infeasible);
+    }
+
+    let mut objective = model.sense.sign() * solver.objective();
+    if let Some(presolve) = &model.presolve {
+        objective += presolve.objective_offset;
+    }
+    Ok(Resolved {
+        status,
+        x,
+        objective,
+    })
+}
+
+fn solve_at(
+    model: &ValidatedProblem,
+    parameter: Parameter,
+    value: f64,
+    options: &SolverOptions,
+) -> ParametricPoint {
+    let base = parameter.current(model);
+    match resolve(with_parameter(model, parameter, value), options) {
+        Ok(mut resolved) => {
+            // The bound shift took sum_j c_j l_j out of the objective, with
+            // the c_j of this point.
+            if let (Some(presolve), Parameter::Objective(k)) = (&model.presolve, parameter) {
+                let j = presolve.kept_columns[k];
+                resolved.objective += (value - base) * presolve.lower[j];
+            }
+            ParametricPoint {
+                value,
+                status: resolved.status,
+                objective: Some(resolved.objective),
+            }
+        }
+        Err(status) => ParametricPoint {
+            value,
+            status,
+            objective: None,
+        },
+    }
+}
+
+/// Kinks of the value function between solved points, located by
+/// intersecting the straight pieces on either side, plus every switch between
+/// solved and unsolved points.
+fn find_breakpoints(points: &[ParametricPoint]) -> Vec<f64> {
+    let slope = |k: usize| -> Option<f64> {
+        let (p, q) = (&points[k], &points[k + 1]);
+        Some((q.objective? - p.objective?) / (q.value - p.value))
+    };
+    let bends = |k: usize| match (slope(k - 1), slope(k)) {
+        (Some(left), Some(right)) => {
+            (right - left).abs() > SLOPE_TOLERANCE * (1.0 + left.abs().max(right.abs()))
+        }
+        _ => false,
+    };
+
+    let mut breakpoints = vec![];
+    let mut k = 1;
+    while k + 1 < points.len() {
+        if !bends(k) {
+            k += 1;
+            continue;
+        }
+        // A kink strictly inside a segment bends at both of its ends; group
+        // them and intersect the outer pieces.
+        let mut last = k;
+        while last + 2 < points.len() && bends(last + 1) {
+            last += 1;
+        }
+        let (left, right) = (slope(k - 1).unwrap_or(0.0), slope(last).unwrap_or(0.0));
+        let (p, q) = (&points[k], &points[last]);
+        let (yp, yq) = (p.objective.unwrap_or(0.0), q.objective.unwrap_or(0.0));
+        // Solve yp + left (t - p) = yq + right (t - q).
+        let at = if (left - right).abs() > f64::EPSILON {
+            (yq - yp + left * p.value - right * q.value) / (left - right)
+        } else {
+            p.value
+        };
+        breakpoints.push(at.clamp(p.value.min(q.value), p.value.max(q.value)));
+        k = last + 1;
+    }
+
+    for pair in points.windows(2) {
+        if pair[0].objective.is_some() != pair[1].objective.is_some() {
+            breakpoints.push((pair[0].value + pair[1].value) / 2.0);
+        }
+    }
+    breakpoints.sort_by(f64::total_cmp);
+    breakpoints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::LogVerbosity;
+    use crate::model::SolverMethod;
+    use crate::test_models::wyndor;
+
+    fn simplex_wyndor() -> ValidatedProblem {
+        let mut model = wyndor();
+        model.method = SolverMethod::Simplex;
+        model
+    }
+
+    fn options() -> SolverOptions {
+        SolverOptions {
+            log_verbosity: LogVerbosity::Off,
+            ..SolverOptions::default()
+        }
+    }
+
+    #[test]
+    fn rhs_breakpoints_are_the_ends_of_the_ranging_interval() {
+        // Z(b3) = 2.5 b3 up to 12, then 30 + (b3 - 12) up to 24, then 42.
+        let analysis = analyze(
+            &simplex_wyndor(),
+            Parameter::Rhs(2),
+            0.0,
+            36.0,
+            25,
+            &options(),
+        );
+        assert_eq!(analysis.breakpoints.len(), 2, "{:?}", analysis.breakpoints);
+        assert!((analysis.breakpoints[0] - 12.0).abs() < 1e-6);
+        assert!((analysis.breakpoints[1] - 24.0).abs() < 1e-6);
+        let last = analysis.points.last().unwrap();
+        assert!((last.objective.unwrap() - 42.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn objective_breakpoint_is_where_the_vertex_changes() {
+        // (2, 6) is optimal for c1 in [0, 7.5], (4, 3) beyond.
+        let analysis = analyze(
+            &simplex_wyndor(),
+            Parameter::Objective(0),
+            0.0,
+            15.0,
+            11,
+            &options(),
+        );
+        assert_eq!(analysis.breakpoints.len(), 1, "{:?}", analysis.breakpoints);
+        assert!((analysis.breakpoints[0] - 7.5).abs() < 1e-6);
+        assert!((analysis.points[0].objective.unwrap() - 30.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,212 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::{
+    calculate_a_tilde, calculate_c_tilde, calculate_range_basis, create_d_matrix,
+    project_onto_null_space, InteriorPointError, SolverOptions,
+};
+use crate::logging;
+
+pub struct PhaseOneResult {
+    pub x: DVector<f64>,
+    pub iterations: usize,
+    pub artificial_sum: f64,
+}
+
+/// Newton steps the analytic-center search takes at most. An unbounded
+/// region has no center, so the search must stop somewhere.
+pub const CENTER_ITERATIONS: usize = 50;
+
+/// Newton decrement below which the point counts as centered.
+const CENTER_TOLERANCE: f64 = 1e-6;
+
+/// How far past the Phase 1 point's largest entry the search may go. Newton
+/// steps grow geometrically along an unbounded direction, and a start far
+/// out along it is worse than the Phase 1 point.
+const CENTER_GROWTH: f64 = 10.0;
+
+/// Share of the largest residual a row must keep to be reported as part of
+/// an infeasibility.
+const UNSATISFIED_FRACTION: f64 = 1e-3;
+
+pub struct AnalyticCenter {
+    pub x: DVector<f64>,
+    pub phase_one_iterations: usize,
+    pub newton_iterations: usize,
+    /// Newton decrement at `x`; near zero at the center, and at least about
+    /// one when the region is unbounded and the search was cut off.
+    pub decrement: f64,
+}
+
+/// Approximates the analytic center of { x : A x = b, x > 0 }, the point
+/// maximizing sum(ln x_j), which sits well away from every boundary.
+///
+/// Phase 1 supplies a strictly positive start; damped Newton steps on the
+/// log barrier then follow the centering direction X P 1. The step 1 / (1 + λ)
+/// for decrement λ = |P 1| keeps every iterate strictly positive. On an
+/// unbounded region the search stops before x outgrows `CENTER_GROWTH` times
+/// the Phase 1 point.
+pub fn analytic_center(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    alpha: f64,
+    max_iterations: usize,
+    options: &SolverOptions,
+) -> Result<AnalyticCenter, InteriorPointError> {
+    let start = find_interior_point(a, b, alpha, max_iterations, options)?;
+    let n = a.ncols();
+    let mut x = start.x;
+    let limit = CENTER_GROWTH * (1.0 + x.amax());
+    let mut decrement = f64::INFINITY;
+    let mut iterations = 0;
+
+    while iterations < CENTER_ITERATIONS {
+        let d = create_d_matrix(&x, options.scale_floor);
+        let (q, _) = calculate_range_basis(&calculate_a_tilde(a, &d))?;
+        let direction = project_onto_null_space(&q, &DVector::from_element(n, 1.0));
+        decrement = direction.norm();
+        if decrement < CENTER_TOLERANCE {
+            break;
+        }
+        let step = 1.0 / (1.0 + decrement);
+        let next = d * (DVector::from_element(n, 1.0) + direction * step);
+        if next.amax() > limit {
+            break;
+        }
+        x = next;
+        iterations += 1;
+    }
+
+    if options.logs_summary() {
+        tracing::info!(
+            "Analytic center after {} Newton steps (decrement {:.2e})",
+            iterations,
+            decrement
+        );
+    }
+    if decrement >= 1.0 {
+        tracing::warn!("The feasible region looks unbounded; using an approximate center");
+    }
+    Ok(AnalyticCenter {
+        x,
+        phase_one_iterations: start.iterations,
+        newton_iterations: iterations,
+        decrement,
+    })
+}
+
+/// Finds a strictly positive x with A x = b by solving the auxiliary LP
+///
+///   minimize sum(a) subject to A x + S a = b, x >= 0, a >= 0
+///
+/// where S = diag(sign(b - A 1)) only has columns for rows violated at x = 1.
+/// Starting from x = 1, a = |b - A 1| is strictly interior, so the interior
+/// point iteration can drive the artificials to zero while x stays positive.
+pub fn find_interior_point(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    alpha: f64,
+    max_iterations: usize,
+    options: &SolverOptions,
+) -> Result<PhaseOneResult, InteriorPointError> {
+    let (m, n) = a.shape();
+    let ones = DVector::from_element(n, 1.0);
+    let residual = b - a * &ones;
+    let tolerance = 1e-9 * (1.0 + b.amax());
+
+    let violated: Vec<usize> = (0..m).filter(|&i| residual[i].abs() > tolerance).collect();
+    if violated.is_empty() {
+        return Ok(PhaseOneResult {
+            x: ones,
+            iterations: 0,
+            artificial_sum: 0.0,
+        });
+    }
+
+    let k = violated.len();
+    let mut a_aux = DMatrix::zeros(m, n + k);
+    a_aux.view_mut((0, 0), (m, n)).copy_from(a);
+    let mut x_aux = DVector::from_element(n + k, 1.0);
+    let mut c_aux = DVector::zeros(n + k);
+    for (col, &row) in violated.iter().enumerate() {
+        a_aux[(row, n + col)] = residual[row].signum();
+        x_aux[n + col] = residual[row].abs();
+        // The solver maximizes, so minimizing the artificials means c = -1.
+        c_aux[n + col] = -1.0;
+    }
+
+    let artificial_sum = |x: &DVector<f64>| x.rows(n, k).sum();
+    // D floors every entry, so an artificial stalls near half of the floor
+    // instead of reaching zero; remove_residual takes up the rest.
+    let stop = tolerance.max(options.scale_floor * k as f64);
+
+    for iteration in 0..max_iterations {
+        if artificial_sum(&x_aux) < stop {
+            if options.logs_summary() {
+                tracing::info!(
+                    "Phase 1 found an interior point after {} iterations",
+                    iteration
+                );
+            }
+            return Ok(PhaseOneResult {
+                x: remove_residual(a, b, x_aux.rows(0, n).into_owned()),
+                iterations: iteration,
+                artificial_sum: artificial_sum(&x_aux),
+            });
+        }
+
+        // Plain affine-scaling step: the most negative component of P c~
+        // moves a fraction alpha of the way to the boundary.
+        let d = create_d_matrix(&x_aux, options.scale_floor);
+        let a_tilde = calculate_a_tilde(&a_aux, &d);
+        let c_tilde = calculate_c_tilde(&c_aux, &d);
+        let (q, _) = calculate_range_basis(&a_tilde)?;
+        let cp = project_onto_null_space(&q, &c_tilde);
+
+        let v = cp.iter().fold(0.0_f64, |acc, &val| acc.max(-val));
+        if v < 1e-12 {
+            // The auxiliary optimum still uses artificials: A x = b has no
+            // nonnegative solution.
+            return Err(unsatisfied(a, b, &x_aux));
+        }
+
+        let x_tilde = DVector::from_element(n + k, 1.0) + cp * (alpha / v);
+        x_aux = d * x_tilde;
+
+        if options.logs_full() {
+            logging::dump_vector!("Phase 1 x", &x_aux);
+        }
+    }
+
+    tracing::warn!(
+        "Phase 1 stopped after {} iterations with artificial sum {:.3e}",
+        max_iterations,
+        artificial_sum(&x_aux)
+    );
+    Err(unsatisfied(a, b, &x_aux))
+}
+
+/// `NotFeasible` with the rows of A x = b the first n entries of `x_aux`
+/// still miss. Rows off by far less than the worst only absorb what the
+/// floor of D leaves, and are not part of the conflict.
+fn unsatisfied(a: &DMatrix<f64>, b: &DVector<f64>, x_aux: &DVector<f64>) -> InteriorPointError {
+    let residual = b - a * x_aux.rows(0, a.ncols());
+    let cutoff = UNSATISFIED_FRACTION * residual.amax();
+    let rows = (0..residual.len())
+        .filter(|&i| residual[i].abs() > cutoff)
+        .map(|i| (i, residual[i]))
+        .collect();
+    InteriorPointError::NotFeasible { rows }
+}
+
+/// Least-norm correction x + A^T (A A^T)^{-1} (b - A x), kept only if it leaves
+/// x strictly positive.
+fn remove_residual(a: &DMatrix<f64>, b: &DVector<f64>, x: DVector<f64>) -> DVector<f64> {
+    let residual = b - a * &x;
+    let correction = (a * a.transpose())
+        .try_inverse()
+        .map(|inv| a.transpose() * inv * residual);
+    match correction {
+        Some(dx) if (&x + &dx).iter().all(|&xi| xi > 0.0) => x + dx,
+        _ => x,
+    }
+}
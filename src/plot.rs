@@ -0,0 +1,49 @@
+use nalgebra::{DMatrix, DVector};
+
+/// Axis extents of an (x1, x2) plot covering `path` and every positive axis
+/// intercept of the rows of `a` x = `b`.
+pub fn path_bounds(a: &DMatrix<f64>, b: &DVector<f64>, path: &[(f64, f64)]) -> (f64, f64) {
+    let mut x_max = path.iter().fold(0.0_f64, |acc, p| acc.max(p.0));
+    let mut y_max = path.iter().fold(0.0_f64, |acc, p| acc.max(p.1));
+    for i in 0..a.nrows() {
+        let (a1, a2, b) = (a[(i, 0)], a[(i, 1)], b[i]);
+        if a1 != 0.0 && b / a1 > 0.0 {
+            x_max = x_max.max(b / a1);
+        }
+        if a2 != 0.0 && b / a2 > 0.0 {
+            y_max = y_max.max(b / a2);
+        }
+    }
+    let pad = |v: f64| if v > 0.0 { v * 1.1 } else { 1.0 };
+    (pad(x_max), pad(y_max))
+}
+
+/// Segment of a1 x + a2 y = b inside [0, x_max] x [0, y_max], if any.
+pub fn clip_line(
+    a1: f64,
+    a2: f64,
+    b: f64,
+    x_max: f64,
+    y_max: f64,
+) -> Option<((f64, f64), (f64, f64))> {
+    let mut hits = vec![];
+    if a2 != 0.0 {
+        hits.push((0.0, b / a2));
+        hits.push((x_max, (b - a1 * x_max) / a2));
+    }
+    if a1 != 0.0 {
+        hits.push((b / a1, 0.0));
+        hits.push(((b - a2 * y_max) / a1, y_max));
+    }
+    let eps = 1e-9 * (x_max + y_max);
+    let inside: Vec<(f64, f64)> = hits
+        .into_iter()
+        .filter(|&(x, y)| x >= -eps && x <= x_max + eps && y >= -eps && y <= y_max + eps)
+        .collect();
+    let first = *inside.first()?;
+    let second = inside
+        .iter()
+        .copied()
+        .find(|&(x, y)| (x - first.0).abs() + (y - first.1).abs() > eps)?;
+    Some((first, second))
+}
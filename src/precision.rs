@@ -0,0 +1,364 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::interior::{InteriorPointError, Regularization, DEPENDENT_ROW_TOLERANCE};
+
+/// Arithmetic the affine-scaling step runs in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Precision {
+    /// Plain f64, about 16 significant digits.
+    #[default]
+    Double,
+    /// An unevaluated sum of two f64s, about 32 significant digits and
+    /// roughly ten times slower. For ill-conditioned demos where rounding in
+    /// f64 visibly pulls the iterates off the true path.
+    DoubleDouble,
+}
+
+impl Precision {
+    pub const ALL: [Precision; 2] = [Precision::Double, Precision::DoubleDouble];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Precision::Double => "Double (f64)",
+            Precision::DoubleDouble => "Double-double",
+        }
+    }
+
+    /// Name used in repro exports.
+    pub fn key(&self) -> &'static str {
+        match self {
+            Precision::Double => "double",
+            Precision::DoubleDouble => "double-double",
+        }
+    }
+}
+
+/// A real scalar the generic solver functions can run in.
+pub trait Real:
+    Copy
+    + fmt::Debug
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + 'static
+{
+    fn from_f64(value: f64) -> Self;
+    /// Rounds to the nearest f64.
+    fn to_f64(self) -> f64;
+    fn sqrt(self) -> Self;
+
+    fn zero() -> Self {
+        Self::from_f64(0.0)
+    }
+
+    fn max(self, other: Self) -> Self {
+        if self < other {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl Real for f64 {
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> Self {
+        f64::sqrt(self)
+    }
+}
+
+/// hi + lo with |lo| <= ulp(hi) / 2, kept exact by the error-free
+/// transformations of Dekker and Knuth.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct DoubleDouble {
+    pub hi: f64,
+    pub lo: f64,
+}
+
+/// s + e = a + b exactly.
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    (s, (a - (s - bb)) + (b - bb))
+}
+
+/// s + e = a + b exactly, given |a| >= |b|.
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    (s, b - (s - a))
+}
+
+/// p + e = a b exactly.
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    (p, a.mul_add(b, -p))
+}
+
+impl DoubleDouble {
+    fn renormalized(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = quick_two_sum(hi, lo);
+        Self { hi, lo }
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (s, e) = two_sum(self.hi, other.hi);
+        let (t, f) = two_sum(self.lo, other.lo);
+        let (s, e) = quick_two_sum(s, e + t);
+        Self::renormalized(s, e + f)
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (p, e) = two_prod(self.hi, other.hi);
+        Self::renormalized(p, e + (self.hi * other.lo + self.lo * other.hi))
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    /// Long division: three f64 quotient digits, each taken from the
+    /// remainder the previous ones left.
+    fn div(self, other: Self) -> Self {
+        let q1 = self.hi / other.hi;
+        let r = self - other * Self::from_f64(q1);
+        let q2 = r.hi / other.hi;
+        let r = r - other * Self::from_f64(q2);
+        let q3 = r.hi / other.hi;
+        Self::renormalized(q1, q2) + Self::from_f64(q3)
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            hi: -self.hi,
+            lo: -self.lo,
+        }
+    }
+}
+
+impl PartialOrd for DoubleDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.hi.partial_cmp(&other.hi) {
+            Some(std::cmp::Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ordering => ordering,
+        }
+    }
+}
+
+impl Real for DoubleDouble {
+    fn from_f64(value: f64) -> Self {
+        Self { hi: value, lo: 0.0 }
+    }
+
+    fn to_f64(self) -> f64 {
+        self.hi + self.lo
+    }
+
+    /// One Newton step from the f64 root doubles its 53 correct bits.
+    fn sqrt(self) -> Self {
+        if self.hi <= 0.0 {
+            return Self::from_f64(self.hi.sqrt());
+        }
+        let q = Self::from_f64(self.hi.sqrt());
+        q + (self - q * q) / (Self::from_f64(2.0) * q)
+    }
+}
+
+/// P c~ for x~ = 1 + t P c~, in any `Real`: the residual of the least-squares
+/// solve (A~ A~^T) y = A~ c~, by a Cholesky factorization of the normal
+/// matrix. Projected twice, like the f64 step, so rounding leaves nothing of
+/// A~ c~ behind. Forming A~ A~^T squares the condition number, which the 32
+/// digits of double-double absorb where the f64 path needs QR.
+pub fn projected_direction<T: Real>(
+    a: &DMatrix<T>,
+    c: &DVector<T>,
+    x: &DVector<T>,
+    regularization: &Regularization,
+) -> Result<DVector<T>, InteriorPointError> {
+    let (m, n) = a.shape();
+    let floor = T::from_f64(regularization.floor);
+    let a_tilde = DMatrix::from_fn(m, n, |i, j| a[(i, j)] * x[j].max(floor));
+    let c_tilde = DVector::from_fn(n, |j, _| c[j] * x[j].max(floor));
+
+    let normal = DMatrix::from_fn(m, m, |i, j| {
+        (0..n).fold(T::zero(), |acc, k| acc + a_tilde[(i, k)] * a_tilde[(j, k)])
+    });
+    // Unshifted first; the shift of the options only for dependent rows,
+    // by the test of `regularized_range_basis`: l_kk is the distance of row
+    // k of A~ from the rows before it, whose length is sqrt(normal_kk).
+    let unshifted = cholesky(&normal, T::zero());
+    let dependent = unshifted.as_ref().is_none_or(|l| {
+        (0..m).any(|k| l[(k, k)] <= T::from_f64(DEPENDENT_ROW_TOLERANCE) * normal[(k, k)].sqrt())
+    });
+    let l = match unshifted {
+        Some(l) if !dependent || regularization.shift <= 0.0 => Some(l),
+        _ => {
+            let largest = (0..m).fold(T::from_f64(1.0), |acc, i| acc.max(normal[(i, i)]));
+            cholesky(&normal, T::from_f64(regularization.shift) * largest)
+        }
+    }
+    .ok_or_else(|| InteriorPointError::singular("A~ A~^T is not positive definite"))?;
+
+    let project = |v: &DVector<T>| {
+        // L z = A~ v, then L^T y = z.
+        let mut y = DVector::from_fn(m, |i, _| {
+            (0..n).fold(T::zero(), |acc, k| acc + a_tilde[(i, k)] * v[k])
+        });
+        for i in 0..m {
+            let sum = (0..i).fold(y[i], |acc, k| acc - l[(i, k)] * y[k]);
+            y[i] = sum / l[(i, i)];
+        }
+        for i in (0..m).rev() {
+            let sum = (i + 1..m).fold(y[i], |acc, k| acc - l[(k, i)] * y[k]);
+            y[i] = sum / l[(i, i)];
+        }
+        DVector::from_fn(n, |j, _| {
+            (0..m).fold(v[j], |acc, i| acc - a_tilde[(i, j)] * y[i])
+        })
+    };
+    Ok(project(&project(&c_tilde)))
+}
+
+/// Lower-triangular L with L L^T = normal + shift I, or `None` when that is
+/// not positive definite.
+fn cholesky<T: Real>(normal: &DMatrix<T>, shift: T) -> Option<DMatrix<T>> {
+    let m = normal.nrows();
+    let mut l = DMatrix::from_element(m, m, T::zero());
+    for j in 0..m {
+        for i in j..m {
+            let mut sum = normal[(i, j)];
+            for k in 0..j {
+                sum = sum - l[(i, k)] * l[(j, k)];
+            }
+            if i == j {
+                let sum = sum + shift;
+                if sum <= T::zero() {
+                    return None;
+                }
+                l[(j, j)] = sum.sqrt();
+            } else {
+                l[(i, j)] = sum / l[(j, j)];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// x + t D P c~, the affine-scaling update in the original variables.
+pub fn step_along<T: Real>(x: &DVector<T>, cp: &DVector<T>, t: f64, floor: f64) -> DVector<T> {
+    let floor = T::from_f64(floor);
+    let t = T::from_f64(t);
+    DVector::from_fn(x.len(), |j, _| x[j] + t * x[j].max(floor) * cp[j])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::{
+        perform_interior_point_iteration, InteriorPointProblem, SolverOptions, DEFAULT_NORMAL_SHIFT,
+    };
+
+    fn dd(value: f64) -> DoubleDouble {
+        DoubleDouble::from_f64(value)
+    }
+
+    #[test]
+    fn double_double_keeps_what_f64_rounds_away() {
+        let tiny = dd(1.0) + dd(1e-20) - dd(1.0);
+        assert_eq!(tiny.to_f64(), 1e-20);
+        let third = dd(1.0) / dd(3.0);
+        assert!((third * dd(3.0) - dd(1.0)).to_f64().abs() < 1e-31);
+        let root = dd(2.0).sqrt();
+        assert!((root * root - dd(2.0)).to_f64().abs() < 1e-31);
+    }
+
+    /// Row 2 is twice row 1, as in the f64 shift test of `interior`.
+    fn dependent_rows() -> (DMatrix<f64>, DVector<f64>, DVector<f64>) {
+        let a = DMatrix::from_row_slice(
+            3,
+            4,
+            &[1.0, 1.0, 1.0, 0.0, 2.0, 2.0, 2.0, 0.0, 1.0, 3.0, 0.0, 1.0],
+        );
+        let b = DVector::from_vec(vec![4.0, 8.0, 6.0]);
+        let c = DVector::from_vec(vec![1.0, 2.0, 0.0, 0.0]);
+        (a, b, c)
+    }
+
+    /// Directions from `a` at `x` with the default shift and with none.
+    fn with_and_without_shift(a: &DMatrix<f64>, c: &DVector<f64>, x: &[f64]) -> [DVector<f64>; 2] {
+        let (a, c) = (a.map(dd), c.map(dd));
+        let x = DVector::from_iterator(x.len(), x.iter().map(|&v| dd(v)));
+        [DEFAULT_NORMAL_SHIFT, 0.0].map(|shift| {
+            let regularization = Regularization {
+                floor: 1e-12,
+                shift,
+                applied_shift: None,
+            };
+            projected_direction(&a, &c, &x, &regularization)
+                .unwrap()
+                .map(Real::to_f64)
+        })
+    }
+
+    #[test]
+    fn only_dependent_rows_are_shifted() {
+        let (a, _, c) = dependent_rows();
+        let [shifted, exact] = with_and_without_shift(&a.clone().remove_row(1), &c, &[1.0; 4]);
+        assert_eq!(shifted, exact);
+        // Here the unshifted factorization goes through, on a pivot of
+        // 3e-16 for the repeated row; the row test still shifts it.
+        let [shifted, exact] = with_and_without_shift(&a, &c, &[1e-3, 2.0, 1.0, 1e-5]);
+        assert_ne!(shifted, exact);
+    }
+
+    #[test]
+    fn dependent_rows_are_shifted_and_reach_the_optimum() {
+        let (a, b, c) = dependent_rows();
+        let options = SolverOptions {
+            precision: Precision::DoubleDouble,
+            ..SolverOptions::default()
+        };
+        let x = DVector::from_vec(vec![1.0, 1.0, 2.0, 2.0]);
+        let mut problem = InteriorPointProblem::new(a, b, c.clone(), x, 0.5, options).unwrap();
+        let mut steps = 0;
+        while perform_interior_point_iteration(&mut problem).is_ok() {
+            steps += 1;
+        }
+        assert!(steps > 0);
+        assert!((c.dot(&problem.x_vector) - 5.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,435 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::model::ConstraintSign;
+
+/// Bounds closer than this are treated as equal, fixing the variable.
+const BOUND_TOLERANCE: f64 = 1e-9;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundKind {
+    Lower,
+    Upper,
+    Fixed,
+    /// Implied by x >= 0 or by a tighter bound on the same variable.
+    Redundant,
+}
+
+impl BoundKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BoundKind::Lower => "lower bound",
+            BoundKind::Upper => "upper bound",
+            BoundKind::Fixed => "fixed value",
+            BoundKind::Redundant => "redundant",
+        }
+    }
+}
+
+/// A singleton row a x_j (<=, >=, =) r turned into a bound on x_j.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundExtraction {
+    pub row: usize,
+    pub variable: usize,
+    pub sign: ConstraintSign,
+    /// r / a, with the sign flipped along with the inequality when a < 0.
+    pub value: f64,
+    pub kind: BoundKind,
+}
+
+/// Column `removed` equals `ratio` times column `kept` in every remaining row
+/// and in the objective, so only `x_kept + ratio * x_removed` matters. Keeping
+/// both would leave a whole edge of optimal solutions and a degenerate tie
+/// between them; presolve solves for the sum in column `kept` and returns
+/// `x_removed = 0`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMerge {
+    pub kept: usize,
+    pub removed: usize,
+    pub ratio: f64,
+}
+
+/// Singleton rows moved out of A, merged duplicate columns, and how the
+/// remaining problem is expressed in shifted variables x'_j = x_j - l_j over
+/// the columns that are neither fixed nor merged away.
+///
+/// Lower bounds and fixed values disappear from the working matrices
+/// entirely. The solvers only know x >= 0, so each upper bound comes back as
+/// one bound row x'_j <= u_j - l_j, however many rows implied it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Presolve {
+    pub extractions: Vec<BoundExtraction>,
+    pub merges: Vec<ColumnMerge>,
+    /// Rows of the user's model that stay in A, in order.
+    pub kept_rows: Vec<usize>,
+    /// Original variables that stay as columns, in order.
+    pub kept_columns: Vec<usize>,
+    /// Per original variable; the shift l_j (or the fixed value).
+    pub lower: Vec<f64>,
+    pub upper: Vec<Option<f64>>,
+    /// Row that produced each upper bound; labels the bound row.
+    pub upper_rows: Vec<Option<usize>>,
+    /// c^T l in the user's sense, the part of the objective the shift takes
+    /// out of the solvers.
+    pub objective_offset: f64,
+}
+
+/// The model after presolve, in the shifted variables.
+pub struct ReducedModel {
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    pub signs: Vec<ConstraintSign>,
+    pub initial: Vec<f64>,
+}
+
+impl Presolve {
+    /// Finds rows with exactly one nonzero coefficient and turns them into
+    /// bounds, then merges proportional columns among the rows that remain.
+    /// Integer variables get their bounds rounded inwards.
+    ///
+    /// Returns `None` when there is nothing to extract or merge, and an error message
+    /// when the bounds contradict each other.
+    pub fn extract(
+        a: &DMatrix<f64>,
+        b: &DVector<f64>,
+        c: &DVector<f64>,
+        signs: &[ConstraintSign],
+        integer: &[bool],
+    ) -> Result<Option<Presolve>, String> {
+        let (m, n) = a.shape();
+        let mut lower = vec![0.0; n];
+        let mut upper: Vec<Option<f64>> = vec![None; n];
+        let mut upper_rows = vec![None; n];
+        let mut fixed = vec![false; n];
+        let mut extractions = vec![];
+        let mut kept_rows = vec![];
+
+        for i in 0..m {
+            let mut nonzero = (0..n).filter(|&j| a[(i, j)] != 0.0);
+            let (Some(j), None) = (nonzero.next(), nonzero.next()) else {
+                kept_rows.push(i);
+                continue;
+            };
+            let coefficient = a[(i, j)];
+            let sign = if coefficient < 0.0 {
+                signs[i].flipped()
+            } else {
+                signs[i]
+            };
+            let mut value = b[i] / coefficient;
+            let is_integer = integer.get(j).copied().unwrap_or(false);
+            let kind = match sign {
+                ConstraintSign::GreaterEqual => {
+                    if is_integer {
+                        value = (value - BOUND_TOLERANCE).ceil();
+                    }
+                    if value > lower[j] {
+                        lower[j] = value;
+                        BoundKind::Lower
+                    } else {
+                        BoundKind::Redundant
+                    }
+                }
+                ConstraintSign::LessEqual => {
+                    if is_integer {
+                        value = (value + BOUND_TOLERANCE).floor();
+                    }
+                    if upper[j].is_none_or(|u| value < u) {
+                        upper[j] = Some(value);
+                        upper_rows[j] = Some(i);
+                        BoundKind::Upper
+                    } else {
+                        BoundKind::Redundant
+                    }
+                }
+                ConstraintSign::Equal => {
+                    if is_integer && (value - value.round()).abs() > BOUND_TOLERANCE {
+                        return Err(format!(
+                            "Constraint {} fixes integer variable x{} to {}, which is not an integer.",
+                            i + 1,
+                            j + 1,
+                            value
+                        ));
+                    }
+                    if fixed[j] && (value - lower[j]).abs() > BOUND_TOLERANCE {
+                        return Err(format!(
+                            "Constraint {} fixes x{} to {}, but another row already fixes it to {}.",
+                            i + 1,
+                            j + 1,
+                            value,
+                            lower[j]
+                        ));
+                    }
+                    fixed[j] = true;
+                    lower[j] = value;
+                    upper[j] = Some(value);
+                    upper_rows[j] = Some(i);
+                    BoundKind::Fixed
+                }
+            };
+            extractions.push(BoundExtraction {
+                row: i,
+                variable: j,
+                sign,
+                value,
+                kind,
+            });
+        }
+
+        for j in 0..n {
+            if let Some(u) = upper[j] {
+                if u < lower[j] - BOUND_TOLERANCE {
+                    return Err(format!(
+                        "The bounds on x{} contradict each other: {} <= x{} <= {}.",
+                        j + 1,
+                        lower[j],
+                        j + 1,
+                        u
+                    ));
+                }
+                if u - lower[j] <= BOUND_TOLERANCE {
+                    fixed[j] = true;
+                }
+            }
+        }
+
+        // A bound that a later row tightened ends up redundant after all.
+        for extraction in &mut extractions {
+            let j = extraction.variable;
+            let superseded = match extraction.kind {
+                BoundKind::Lower => extraction.value < lower[j],
+                BoundKind::Upper => upper_rows[j] != Some(extraction.row),
+                _ => false,
+            };
+            if superseded {
+                extraction.kind = BoundKind::Redundant;
+            }
+        }
+
+        let merges = find_duplicate_columns(a, c, &kept_rows, |j| {
+            !fixed[j] && upper[j].is_none() && !integer.get(j).copied().unwrap_or(false)
+        });
+        if extractions.is_empty() && merges.is_empty() {
+            return Ok(None);
+        }
+
+        // Leave models that would lose every row or every column alone; the
+        // solvers need at least one of each.
+        let kept_columns: Vec<usize> = (0..n)
+            .filter(|&j| !fixed[j] && merges.iter().all(|merge| merge.removed != j))
+            .collect();
+        let has_rows = !kept_rows.is_empty() || kept_columns.iter().any(|&j| upper[j].is_some());
+        if kept_columns.is_empty() || !has_rows {
+            return Ok(None);
+        }
+
+        let objective_offset = c.iter().zip(&lower).map(|(c, l)| c * l).sum();
+        Ok(Some(Presolve {
+            objective_offset,
+            extractions,
+            merges,
+            kept_rows,
+            kept_columns,
+            lower,
+            upper,
+            upper_rows,
+        }))
+    }
+
+    /// The kept rows over the kept columns in x' = x - l, followed by one
+    /// bound row per remaining upper bound.
+    pub fn reduce(
+        &self,
+        a: &DMatrix<f64>,
+        b: &DVector<f64>,
+        c: &DVector<f64>,
+        signs: &[ConstraintSign],
+        initial: &[f64],
+    ) -> ReducedModel {
+        let lower = DVector::from_vec(self.lower.clone());
+        let shifted_b = b - a * &lower;
+        let bounded: Vec<(usize, usize)> = self
+            .kept_columns
+            .iter()
+            .enumerate()
+            .filter(|(_, &j)| self.upper[j].is_some())
+            .map(|(k, &j)| (k, j))
+            .collect();
+
+        let rows = self.row_sources().len();
+        let columns = self.kept_columns.len();
+        let mut reduced_a = DMatrix::zeros(rows, columns);
+        let mut reduced_b = DVector::zeros(rows);
+        let mut reduced_signs = Vec::with_capacity(rows);
+        for (r, &i) in self.kept_rows.iter().enumerate() {
+            for (k, &j) in self.kept_columns.iter().enumerate() {
+                reduced_a[(r, k)] = a[(i, j)];
+            }
+            reduced_b[r] = shifted_b[i];
+            reduced_signs.push(signs[i]);
+        }
+        for (offset, &(k, j)) in bounded.iter().enumerate() {
+            let r = self.kept_rows.len() + offset;
+            reduced_a[(r, k)] = 1.0;
+            reduced_b[r] = self.upper[j].unwrap_or_default() - self.lower[j];
+            reduced_signs.push(ConstraintSign::LessEqual);
+        }
+
+        // Keep the user's start where it is still interior after the shift,
+        // otherwise sit halfway into the bounded range.
+        let initial = self
+            .kept_columns
+            .iter()
+            .map(|&j| {
+                let shifted = initial.get(j).copied().unwrap_or(1.0) - self.lower[j];
+                let range = self.upper[j].map(|u| u - self.lower[j]);
+                match range {
+                    Some(range) if !(shifted > 0.0 && shifted < range) => range / 2.0,
+                    None if shifted <= 0.0 => 1.0,
+                    _ => shifted,
+                }
+            })
+            .collect();
+
+        ReducedModel {
+            a: reduced_a,
+            b: reduced_b,
+            c: DVector::from_iterator(columns, self.kept_columns.iter().map(|&j| c[j])),
+            signs: reduced_signs,
+            initial,
+        }
+    }
+
+    /// The user's row behind each reduced row: the kept rows, then the row
+    /// that set each remaining upper bound.
+    pub fn row_sources(&self) -> Vec<usize> {
+        let bound_rows = self
+            .kept_columns
+            .iter()
+            .filter_map(|&j| self.upper[j].and(self.upper_rows[j]));
+        self.kept_rows.iter().copied().chain(bound_rows).collect()
+    }
+
+    /// Values of the original variables from a solution of the reduced
+    /// model, whose first columns are x' for the kept variables.
+    pub fn restore(&self, x: &DVector<f64>) -> DVector<f64> {
+        let mut restored = DVector::from_vec(self.lower.clone());
+        for (k, &j) in self.kept_columns.iter().enumerate() {
+            restored[j] += x.get(k).copied().unwrap_or(0.0);
+        }
+        restored
+    }
+}
+
+/// Pairs of columns that are positive multiples of each other over `rows`,
+/// with the objective in the same ratio. Only columns accepted by `eligible`
+/// take part: a bound or integrality on either side would not survive the
+/// merge.
+fn find_duplicate_columns(
+    a: &DMatrix<f64>,
+    c: &DVector<f64>,
+    rows: &[usize],
+    eligible: impl Fn(usize) -> bool,
+) -> Vec<ColumnMerge> {
+    let candidates: Vec<usize> = (0..a.ncols()).filter(|&j| eligible(j)).collect();
+    let mut merges: Vec<ColumnMerge> = vec![];
+    for (p, &j) in candidates.iter().enumerate() {
+        if merges.iter().any(|merge| merge.removed == j) {
+            continue;
+        }
+        for &k in &candidates[p + 1..] {
+            if merges.iter().any(|merge| merge.removed == k) {
+                continue;
+            }
+            let Some(ratio) = column_ratio(a, rows, j, k) else {
+                continue;
+            };
+            if (c[k] - ratio * c[j]).abs() <= BOUND_TOLERANCE * (1.0 + c[k].abs()) {
+                merges.push(ColumnMerge {
+                    kept: j,
+                    removed: k,
+                    ratio,
+                });
+            }
+        }
+    }
+    merges
+}
+
+/// lambda > 0 with column k = lambda * column j over `rows`, if there is one.
+fn column_ratio(a: &DMatrix<f64>, rows: &[usize], j: usize, k: usize) -> Option<f64> {
+    let pivot = rows.iter().copied().find(|&i| a[(i, j)] != 0.0)?;
+    let ratio = a[(pivot, k)] / a[(pivot, j)];
+    let proportional = rows.iter().all(|&i| {
+        (a[(i, k)] - ratio * a[(i, j)]).abs() <= BOUND_TOLERANCE * (1.0 + a[(i, k)].abs())
+    });
+    (ratio > 0.0 && proportional).then_some(ratio)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::SolverMethod;
+    use crate::test_models::{assert_optimum, solve};
+
+    #[test]
+    fn singleton_rows_become_bounds_and_the_optimum_survives() {
+        // Wyndor: x1 <= 4 and 2 x2 <= 12 are bounds, 3 x1 + 2 x2 <= 18 stays.
+        let a = DMatrix::from_row_slice(3, 2, &[1.0, 0.0, 0.0, 2.0, 3.0, 2.0]);
+        let b = DVector::from_vec(vec![4.0, 12.0, 18.0]);
+        let c = DVector::from_vec(vec![3.0, 5.0]);
+        let signs = [ConstraintSign::LessEqual; 3];
+        let presolve = Presolve::extract(&a, &b, &c, &signs, &[false, false])
+            .unwrap()
+            .expect("two rows are singletons");
+        assert_eq!(presolve.kept_rows, vec![2]);
+        assert_eq!(presolve.upper, vec![Some(4.0), Some(6.0)]);
+        assert_eq!(presolve.row_sources(), vec![2, 0, 1]);
+
+        let reduced = presolve.reduce(&a, &b, &c, &signs, &[1.0, 1.0]);
+        let mut builder = InteriorPointProblemBuilder::new()
+            .objective(reduced.c.as_slice())
+            .maximize();
+        for (i, sign) in reduced.signs.iter().enumerate() {
+            let row: Vec<f64> = reduced.a.row(i).iter().copied().collect();
+            builder = builder.constraint(&row, *sign, reduced.b[i]);
+        }
+        let report = solve(&builder.model().unwrap(), SolverMethod::Simplex);
+        assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-9);
+        let restored = presolve.restore(&report.solution);
+        assert_eq!(restored.as_slice(), &[2.0, 6.0]);
+    }
+
+    #[test]
+    fn proportional_columns_merge() {
+        // Column 2 and its cost are twice column 1's.
+        let a = DMatrix::from_row_slice(2, 3, &[1.0, 2.0, 1.0, 2.0, 4.0, 3.0]);
+        let b = DVector::from_vec(vec![4.0, 9.0]);
+        let c = DVector::from_vec(vec![1.0, 2.0, 1.0]);
+        let signs = [ConstraintSign::LessEqual; 2];
+        let presolve = Presolve::extract(&a, &b, &c, &signs, &[false; 3])
+            .unwrap()
+            .expect("columns 1 and 2 are proportional");
+        assert_eq!(presolve.merges.len(), 1);
+        let merge = &presolve.merges[0];
+        assert_eq!(
+            (merge.kept.min(merge.removed), merge.kept.max(merge.removed)),
+            (0, 1)
+        );
+        let scaled = a.column(merge.kept) * merge.ratio;
+        assert!((a.column(merge.removed) - scaled).amax() < 1e-12);
+        assert!((c[merge.removed] - merge.ratio * c[merge.kept]).abs() < 1e-12);
+    }
+
+    #[test]
+    fn contradictory_bounds_are_an_error() {
+        let a = DMatrix::from_row_slice(2, 2, &[1.0, 0.0, 1.0, 0.0]);
+        let b = DVector::from_vec(vec![1.0, 2.0]);
+        let c = DVector::from_vec(vec![1.0, 1.0]);
+        let signs = [ConstraintSign::LessEqual, ConstraintSign::GreaterEqual];
+        assert!(Presolve::extract(&a, &b, &c, &signs, &[false, false]).is_err());
+    }
+}
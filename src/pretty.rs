@@ -0,0 +1,120 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+/// Decimals per entry, the precision the iteration views show.
+const DECIMALS: usize = 4;
+
+/// Magnitudes outside [SMALL, LARGE) switch to scientific notation so one
+/// extreme entry does not widen every column.
+const SMALL: f64 = 1e-4;
+const LARGE: f64 = 1e6;
+
+/// One entry as it appears in every pretty-printed matrix or vector.
+pub fn entry(val: f64) -> String {
+    let magnitude = val.abs();
+    if val == 0.0 {
+        format!("{:.*}", DECIMALS, 0.0)
+    } else if !val.is_finite() || (SMALL..LARGE).contains(&magnitude) {
+        format!("{:.*}", DECIMALS, val)
+    } else {
+        format!("{:.*e}", DECIMALS - 1, val)
+    }
+}
+
+/// A labelled matrix with right-aligned columns, one row per line:
+///
+/// ```text
+/// A~ (2×3):
+///   [  1.0000  0.0000  -2.5000 ]
+///   [ 12.0000  1.0000   0.5000 ]
+/// ```
+pub struct PrettyMatrix<'a> {
+    label: &'a str,
+    matrix: &'a DMatrix<f64>,
+    columns: Option<&'a [String]>,
+}
+
+pub fn matrix<'a>(label: &'a str, matrix: &'a DMatrix<f64>) -> PrettyMatrix<'a> {
+    PrettyMatrix {
+        label,
+        matrix,
+        columns: None,
+    }
+}
+
+impl<'a> PrettyMatrix<'a> {
+    /// Adds a header line naming each column, e.g. the variable labels.
+    pub fn with_columns(mut self, labels: &'a [String]) -> Self {
+        self.columns = Some(labels);
+        self
+    }
+}
+
+impl fmt::Display for PrettyMatrix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.matrix.shape();
+        write!(f, "{} ({}×{}):", self.label, rows, cols)?;
+        if rows == 0 || cols == 0 {
+            return write!(f, " []");
+        }
+
+        let cells: Vec<Vec<String>> = (0..rows)
+            .map(|i| (0..cols).map(|j| entry(self.matrix[(i, j)])).collect())
+            .collect();
+        let header = |j: usize| {
+            self.columns
+                .and_then(|labels| labels.get(j))
+                .map_or("", String::as_str)
+        };
+        let widths: Vec<usize> = (0..cols)
+            .map(|j| {
+                cells
+                    .iter()
+                    .map(|row| row[j].chars().count())
+                    .chain(std::iter::once(header(j).chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        if self.columns.is_some() {
+            write!(f, "\n   ")?;
+            for (j, width) in widths.iter().enumerate() {
+                write!(f, " {:>width$}", header(j), width = width)?;
+            }
+        }
+        for row in &cells {
+            write!(f, "\n  [")?;
+            for (cell, width) in row.iter().zip(&widths) {
+                write!(f, " {:>width$}", cell, width = width)?;
+            }
+            write!(f, " ]")?;
+        }
+        Ok(())
+    }
+}
+
+/// A labelled vector on one line: `x (3) = [ 1.0000  0.5000  2.0000 ]`.
+pub struct PrettyVector<'a> {
+    label: &'a str,
+    values: &'a [f64],
+}
+
+pub fn vector<'a>(label: &'a str, vector: &'a DVector<f64>) -> PrettyVector<'a> {
+    values(label, vector.as_slice())
+}
+
+pub fn values<'a>(label: &'a str, values: &'a [f64]) -> PrettyVector<'a> {
+    PrettyVector { label, values }
+}
+
+impl fmt::Display for PrettyVector<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}) = [", self.label, self.values.len())?;
+        for &val in self.values {
+            write!(f, " {}", entry(val))?;
+        }
+        write!(f, " ]")
+    }
+}
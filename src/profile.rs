@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Render times kept for the average and the worst case; about a minute of
+/// stepping through a solve.
+const RENDER_HISTORY: usize = 120;
+
+/// Times of the app's recent updates, from the start of `update` to
+/// `rendered`, i.e. handling the message plus view and DOM patch.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RenderStats {
+    recent_ms: VecDeque<f64>,
+    pub updates: usize,
+}
+
+impl RenderStats {
+    pub fn record(&mut self, elapsed_ms: f64) {
+        if self.recent_ms.len() == RENDER_HISTORY {
+            self.recent_ms.pop_front();
+        }
+        self.recent_ms.push_back(elapsed_ms);
+        self.updates += 1;
+    }
+
+    pub fn last_ms(&self) -> Option<f64> {
+        self.recent_ms.back().copied()
+    }
+
+    pub fn mean_ms(&self) -> Option<f64> {
+        (!self.recent_ms.is_empty())
+            .then(|| self.recent_ms.iter().sum::<f64>() / self.recent_ms.len() as f64)
+    }
+
+    pub fn max_ms(&self) -> Option<f64> {
+        self.recent_ms.iter().copied().reduce(f64::max)
+    }
+}
+
+/// Physical key of the profile overlay's shortcut, Alt+Shift+P.
+pub const PROFILE_KEY: &str = "KeyP";
+
+/// Physical key of the solver-state inspector's shortcut, Alt+Shift+I.
+pub const INSPECTOR_KEY: &str = "KeyI";
+
+/// Whether a key press is Alt+Shift with the key `code`. Matched on the
+/// physical key because Alt changes `key` on macOS.
+pub fn is_toggle_shortcut(event: &web_sys::KeyboardEvent, code: &str) -> bool {
+    event.alt_key() && event.shift_key() && !event.ctrl_key() && event.code() == code
+}
+
+/// Calls `on_toggle` whenever Alt+Shift with the key `code` is pressed
+/// anywhere on the page. The listener lives as long as the returned closure.
+pub fn listen_for_toggle(
+    code: &'static str,
+    on_toggle: impl Fn() + 'static,
+) -> Option<Closure<dyn Fn(web_sys::KeyboardEvent)>> {
+    let document = web_sys::window()?.document()?;
+    let listener =
+        Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(move |event: web_sys::KeyboardEvent| {
+            if is_toggle_shortcut(&event, code) {
+                event.prevent_default();
+                on_toggle();
+            }
+        });
+    document
+        .add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref())
+        .ok()?;
+    Some(listener)
+}
+
+/// Elements currently in the document.
+pub fn dom_node_count() -> Option<u32> {
+    let document = web_sys::window()?.document()?;
+    Some(document.get_elements_by_tag_name("*").length())
+}
+
+/// Memory the app holds, in bytes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemoryEstimate {
+    /// Linear memory of the wasm module; it only grows, so this is the high
+    /// water mark of the Rust heap.
+    pub wasm_bytes: Option<f64>,
+    /// `performance.memory.usedJSHeapSize`, only reported by Chromium.
+    pub js_heap_bytes: Option<f64>,
+}
+
+pub fn memory_estimate() -> MemoryEstimate {
+    MemoryEstimate {
+        wasm_bytes: wasm_memory_bytes(),
+        js_heap_bytes: js_heap_bytes(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn wasm_memory_bytes() -> Option<f64> {
+    let memory: js_sys::WebAssembly::Memory = wasm_bindgen::memory().dyn_into().ok()?;
+    let buffer: js_sys::ArrayBuffer = memory.buffer().dyn_into().ok()?;
+    Some(buffer.byte_length() as f64)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn wasm_memory_bytes() -> Option<f64> {
+    None
+}
+
+fn js_heap_bytes() -> Option<f64> {
+    let window = web_sys::window()?;
+    let performance = js_sys::Reflect::get(&window, &JsValue::from_str("performance")).ok()?;
+    let memory = js_sys::Reflect::get(&performance, &JsValue::from_str("memory")).ok()?;
+    js_sys::Reflect::get(&memory, &JsValue::from_str("usedJSHeapSize"))
+        .ok()?
+        .as_f64()
+}
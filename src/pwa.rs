@@ -0,0 +1,29 @@
+use wasm_bindgen::prelude::*;
+
+/// Path of the service worker Trunk copies next to index.html (see
+/// `pwa/sw.js`); relative so the app also works under a public URL prefix.
+const SERVICE_WORKER: &str = "sw.js";
+
+/// Registers the service worker that caches the app for offline use, so it
+/// keeps working in exam rooms and labs without network access. Browsers
+/// only expose service workers on https and localhost; elsewhere this does
+/// nothing.
+pub fn register_service_worker() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let navigator = window.navigator();
+    let supported =
+        js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false);
+    if !supported {
+        return;
+    }
+    let on_error = Closure::<dyn FnMut(JsValue)>::new(|error: JsValue| {
+        log::warn!("Service worker registration failed: {:?}", error);
+    });
+    let _ = navigator
+        .service_worker()
+        .register(SERVICE_WORKER)
+        .catch(&on_error);
+    on_error.forget();
+}
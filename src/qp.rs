@@ -0,0 +1,342 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::barrier::{
+    calculate_step_length, gap_tolerance, has_stalled, mu_floor, relative_residuals, worst_of,
+};
+use crate::interior::{check_finite, improving_ray, InteriorPointError, SolverOptions};
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+use crate::solver::{starting_point, Solver};
+use crate::stopping::StopReason;
+
+#[derive(Clone, PartialEq)]
+pub struct QpIteration {
+    pub mu: f64,
+    pub objective: f64,
+    pub gradient: DVector<f64>,
+    /// Q + mu X^{-2}, the negated Hessian of the barrier subproblem.
+    pub hessian: DMatrix<f64>,
+    pub newton_direction: DVector<f64>,
+    pub step_length: f64,
+    pub current_x: DVector<f64>,
+}
+
+impl fmt::Debug for QpIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "QpIteration: mu = {}, objective = {}, step = {}",
+            pretty::entry(self.mu),
+            pretty::entry(self.objective),
+            pretty::entry(self.step_length)
+        )?;
+        writeln!(f, "{}", pretty::vector("gradient", &self.gradient))?;
+        writeln!(f, "{}", pretty::matrix("Q + mu X^-2", &self.hessian))?;
+        writeln!(
+            f,
+            "{}",
+            pretty::vector("Newton direction", &self.newton_direction)
+        )?;
+        write!(f, "{}", pretty::vector("x", &self.current_x))
+    }
+}
+
+/// Convex QP: maximize c^T x - 1/2 x^T Q x subject to A x = b, x >= 0, with
+/// Q positive semidefinite. Minimizing 1/2 x^T Q x + c^T x is the same
+/// problem with c negated, which is how the app passes minimization in.
+///
+/// Solved by the primal log-barrier method: each iteration takes a damped
+/// Newton step on c^T x - 1/2 x^T Q x + mu sum(ln x_i), then shrinks mu.
+pub struct QpProblem {
+    pub a_matrix: DMatrix<f64>,
+    pub b_vector: DVector<f64>,
+    pub c_vector: DVector<f64>,
+    pub q_matrix: DMatrix<f64>,
+    pub x_vector: DVector<f64>,
+    pub alpha: f64,
+    pub mu: f64,
+    pub mu_decrease: f64,
+    pub options: SolverOptions,
+    /// Newton steps taken, for naming the step that broke down.
+    pub iterations: usize,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
+}
+
+impl QpProblem {
+    /// The QP barrier on `model` from the interior point `x`; a model
+    /// without a quadratic term gets Q = 0.
+    pub fn new(model: &ValidatedProblem, x: DVector<f64>, options: &SolverOptions) -> Self {
+        let n = model.a.ncols();
+        QpProblem {
+            a_matrix: model.a.clone(),
+            b_vector: model.b.clone(),
+            c_vector: model.internal_c(),
+            q_matrix: model
+                .quadratic
+                .clone()
+                .unwrap_or_else(|| DMatrix::zeros(n, n)),
+            x_vector: x,
+            alpha: model.options.alpha,
+            mu: model.options.mu,
+            mu_decrease: 0.5,
+            options: options.clone(),
+            iterations: 0,
+            finished: false,
+        }
+    }
+
+    /// c^T x - 1/2 x^T Q x at the current iterate.
+    pub fn objective(&self) -> f64 {
+        quadratic_objective(&self.c_vector, &self.q_matrix, &self.x_vector)
+    }
+
+    /// Report at the current iterate, with the quadratic objective.
+    pub fn report(&self, status: SolveStatus, iterations: usize) -> SolveReport {
+        let mut report =
+            SolveReport::new(status, iterations, self.x_vector.clone(), &self.c_vector);
+        report.objective = self.objective();
+        report.check_feasibility(&self.a_matrix, &self.b_vector, self.options.norm);
+        report
+    }
+}
+
+pub fn quadratic_objective(c: &DVector<f64>, q: &DMatrix<f64>, x: &DVector<f64>) -> f64 {
+    c.dot(x) - 0.5 * x.dot(&(q * x))
+}
+
+/// Newton direction for the barrier subproblem, keeping A (x + dx) = b.
+///
+/// With M = Q + mu X^{-2}, the KKT system reduces to
+/// (A M^{-1} A^T) y = (b - A x) - A M^{-1} g and dx = M^{-1} (g + A^T y).
+/// M is positive definite for x > 0, so it is factored by Cholesky; the
+/// normal matrix is solved by LU rather than through inverses, which lose
+/// every digit once mu X^{-2} spans many orders of magnitude.
+pub fn calculate_qp_direction(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    x: &DVector<f64>,
+    gradient: &DVector<f64>,
+    hessian: &DMatrix<f64>,
+) -> Result<DVector<f64>, InteriorPointError> {
+    let m = hessian
+        .clone()
+        .cholesky()
+        .ok_or_else(|| InteriorPointError::singular("Cannot factor (Q + mu X^-2)"))?;
+
+    let m_inv_at = m.solve(&a.transpose());
+    let m_inv_g = m.solve(gradient);
+    let normal = a * &m_inv_at;
+    let residual = b - a * x;
+    let y = normal
+        .lu()
+        .solve(&(residual - a * &m_inv_g))
+        .ok_or_else(|| InteriorPointError::singular("Cannot solve (A M^-1 A^T)"))?;
+
+    Ok(m_inv_g + m_inv_at * y)
+}
+
+impl Solver for QpProblem {
+    type Iteration = QpIteration;
+    type Error = InteriorPointError;
+
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        Ok(QpProblem::new(
+            model,
+            starting_point(model, options)?,
+            options,
+        ))
+    }
+
+    fn step(&mut self) -> Result<QpIteration, InteriorPointError> {
+        let result = perform_qp_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.x_vector.clone()
+    }
+}
+
+pub fn perform_qp_iteration(problem: &mut QpProblem) -> Result<QpIteration, InteriorPointError> {
+    if problem.options.logs_full() {
+        tracing::debug!(mu = problem.mu, "QP iteration start");
+        logging::dump_vector!("x", &problem.x_vector);
+    }
+
+    if let Some(column) = problem.x_vector.iter().position(|&xi| xi <= 0.0) {
+        return Err(InteriorPointError::NotInterior {
+            column,
+            value: problem.x_vector[column],
+        });
+    }
+
+    if problem
+        .options
+        .stopping
+        .out_of_iterations(problem.iterations)
+    {
+        return Err(InteriorPointError::Stopped(StopReason::IterationLimit));
+    }
+    // x is optimal when it is optimal for the LP with the objective's
+    // gradient c - Q x at x, so the barrier's residuals apply unchanged.
+    let (a, b, q) = (&problem.a_matrix, &problem.b_vector, &problem.q_matrix);
+    let linearized = |x: &DVector<f64>| &problem.c_vector - q * x;
+    let tolerance = gap_tolerance(&problem.options);
+    let residuals = relative_residuals(a, b, &linearized(&problem.x_vector), &problem.x_vector);
+    if let Some((primal, dual, gap)) = residuals {
+        if worst_of((primal, dual, gap)) <= tolerance {
+            tracing::info!(
+                primal,
+                dual,
+                gap,
+                "QP converged: residuals {:.3e}, {:.3e}, gap {:.3e}",
+                primal,
+                dual,
+                gap
+            );
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+    }
+
+    let mu = problem.mu;
+    let n = problem.x_vector.len();
+    let floor = mu_floor(tolerance, problem.objective(), n);
+    let x = &problem.x_vector;
+    let gradient = linearized(x) + DVector::from_iterator(n, x.iter().map(|&xi| mu / xi));
+    let hessian = q + DMatrix::from_diagonal(&DVector::from_iterator(
+        n,
+        x.iter().map(|&xi| mu / (xi * xi)),
+    ));
+
+    let dx = calculate_qp_direction(a, b, x, &gradient, &hessian)?;
+    // Along a ray of A d = 0 the objective grows forever only where Q d = 0
+    // too: anywhere else the quadratic term bends it back down.
+    let rows = DMatrix::from_fn(a.nrows() + n, n, |i, j| {
+        if i < a.nrows() {
+            a[(i, j)]
+        } else {
+            q[(i - a.nrows(), j)]
+        }
+    });
+    if let Some(ray) = improving_ray(&rows, &problem.c_vector, &dx) {
+        tracing::warn!("The Newton direction is an improving ray: the objective is unbounded");
+        return Err(InteriorPointError::Unbounded(ray));
+    }
+
+    let step = calculate_step_length(x, &dx, problem.alpha);
+    let new_x = x + &dx * step;
+    check_finite(
+        problem.iterations,
+        &[("Newton direction", dx.as_slice()), ("x", new_x.as_slice())],
+    )?;
+
+    if let Some(current) = residuals.map(worst_of) {
+        let next = relative_residuals(a, b, &linearized(&new_x), &new_x).map(worst_of);
+        if mu * problem.mu_decrease <= floor && has_stalled(current, next) {
+            tracing::info!(
+                worst = current,
+                "QP stalled at mu = {:.3e}: worst residual {:.3e}",
+                mu,
+                current
+            );
+            return Err(InteriorPointError::Stopped(StopReason::DualityGap));
+        }
+    }
+
+    problem.x_vector = new_x.clone();
+    problem.iterations += 1;
+    problem.mu = (mu * problem.mu_decrease).max(floor.min(mu));
+
+    if problem.options.logs_full() {
+        logging::dump_matrix!("Q + mu X^-2", &hessian);
+        logging::dump_vector!("Newton direction", &dx);
+        logging::dump_vector!("Updated x", &new_x);
+    }
+    if problem.options.logs_summary() {
+        tracing::info!(
+            step,
+            objective = problem.objective(),
+            mu = problem.mu,
+            "QP step t = {:.4}, objective {:.6}, next mu = {:.3e}",
+            step,
+            problem.objective(),
+            problem.mu
+        );
+    }
+
+    Ok(QpIteration {
+        mu,
+        objective: problem.objective(),
+        gradient,
+        hessian,
+        newton_direction: dx,
+        step_length: step,
+        current_x: new_x,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::{ConstraintSign, SolverMethod, ValidatedProblem};
+    use crate::report::SolveStatus;
+    use crate::test_models::{assert_optimum, solve};
+
+    /// `model` with the quadratic term 1/2 x^T Q x over its original
+    /// variables, and no cost on the slack and artificial columns.
+    fn with_quadratic(mut model: ValidatedProblem, q: &[[f64; 2]; 2]) -> ValidatedProblem {
+        let cols = model.a.ncols();
+        model.quadratic = Some(nalgebra::DMatrix::from_fn(cols, cols, |i, j| {
+            if i < 2 && j < 2 {
+                q[i][j]
+            } else {
+                0.0
+            }
+        }));
+        model
+    }
+
+    #[test]
+    fn reaches_a_known_constrained_minimum() {
+        // min x1^2 + x2^2 - 2 x1 - 4 x2 s.t. x1 + x2 <= 2: the projection
+        // of the unconstrained minimum (1, 2) onto x1 + x2 = 2.
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[-2.0, -4.0])
+            .constraint(&[1.0, 1.0], ConstraintSign::LessEqual, 2.0)
+            .minimize()
+            .model()
+            .unwrap();
+        let model = with_quadratic(model, &[[2.0, 0.0], [0.0, 2.0]]);
+        let report = solve(&model, SolverMethod::QuadraticBarrier);
+        assert_optimum(&report, -4.5, &[0.5, 1.5], 1e-6);
+    }
+
+    #[test]
+    fn reports_an_unbounded_linear_objective() {
+        // Q = 0 leaves max x1 + x2 s.t. x1 - x2 <= 1, with the ray (1, 1).
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[1.0, 1.0])
+            .constraint(&[1.0, -1.0], ConstraintSign::LessEqual, 1.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let model = with_quadratic(model, &[[0.0, 0.0], [0.0, 0.0]]);
+        let report = solve(&model, SolverMethod::QuadraticBarrier);
+        assert_eq!(
+            report.status,
+            SolveStatus::Unbounded,
+            "{:?}",
+            report.warnings
+        );
+    }
+}
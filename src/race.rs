@@ -0,0 +1,222 @@
+use nalgebra::DVector;
+
+use crate::barrier::BarrierProblem;
+use crate::hsd::HsdProblem;
+use crate::interior::{InteriorPointProblem, SolverOptions};
+use crate::model::{SolverMethod, ValidatedProblem};
+use crate::qp::QpProblem;
+use crate::report::{now_ms, SolveReport, SolveStatus};
+use crate::revised_simplex::RevisedSimplexProblem;
+use crate::simplex::SimplexProblem;
+use crate::solver::Solver;
+
+/// Iterations after which a lane gives up.
+pub const RACE_ITERATION_LIMIT: usize = 500;
+
+/// One step of any solver, with its error read as the final status.
+fn advance<S: Solver>(solver: &mut S) -> Result<(), SolveStatus>
+where
+    for<'a> SolveStatus: From<&'a S::Error>,
+{
+    solver.step().map(|_| ()).map_err(|e| SolveStatus::from(&e))
+}
+
+/// One solver of any kind, stepped uniformly by a race.
+pub enum RaceSolver {
+    Affine(InteriorPointProblem),
+    Barrier(BarrierProblem),
+    Simplex(SimplexProblem),
+    Revised(RevisedSimplexProblem),
+    Quadratic(QpProblem),
+    SelfDual(HsdProblem),
+}
+
+impl RaceSolver {
+    /// Sets up `method` on `model` the same way the step-by-step view does.
+    pub fn new(
+        method: SolverMethod,
+        model: &ValidatedProblem,
+        options: &SolverOptions,
+    ) -> Result<Self, String> {
+        Ok(match method {
+            SolverMethod::AffineScaling => RaceSolver::Affine(Solver::init(model, options)?),
+            SolverMethod::LogBarrier => RaceSolver::Barrier(Solver::init(model, options)?),
+            SolverMethod::QuadraticBarrier => RaceSolver::Quadratic(Solver::init(model, options)?),
+            SolverMethod::SelfDual => RaceSolver::SelfDual(Solver::init(model, options)?),
+            SolverMethod::Simplex => RaceSolver::Simplex(Solver::init(model, options)?),
+            SolverMethod::RevisedSimplex => RaceSolver::Revised(Solver::init(model, options)?),
+        })
+    }
+
+    /// Takes one iteration; `Err` carries the final status once the solver stops.
+    pub fn step(&mut self) -> Result<(), SolveStatus> {
+        match self {
+            RaceSolver::Affine(problem) => advance(problem),
+            RaceSolver::Barrier(problem) => advance(problem),
+            RaceSolver::Quadratic(problem) => advance(problem),
+            RaceSolver::SelfDual(problem) => advance(problem),
+            RaceSolver::Simplex(problem) => advance(problem),
+            RaceSolver::Revised(problem) => advance(problem),
+        }
+    }
+
+    /// Current iterate in the solver space.
+    pub fn current_x(&self) -> DVector<f64> {
+        match self {
+            RaceSolver::Affine(problem) => problem.solution(),
+            RaceSolver::Barrier(problem) => problem.solution(),
+            RaceSolver::Quadratic(problem) => problem.solution(),
+            RaceSolver::SelfDual(problem) => problem.solution(),
+            RaceSolver::Simplex(problem) => problem.solution(),
+            RaceSolver::Revised(problem) => problem.solution(),
+        }
+    }
+
+    /// The solver's report at the current iterate, in the solvers'
+    /// maximization form; `SolveReport::complete` finishes it.
+    pub fn report(&self, status: SolveStatus, iterations: usize) -> SolveReport {
+        match self {
+            RaceSolver::Affine(problem) => problem.report(status, iterations, None),
+            RaceSolver::Barrier(problem) => problem.report(status, iterations),
+            RaceSolver::Quadratic(problem) => problem.report(status, iterations),
+            RaceSolver::SelfDual(problem) => problem.report(status, iterations, None),
+            RaceSolver::Simplex(problem) => problem.report(status, iterations),
+            RaceSolver::Revised(problem) => problem.report(status, iterations),
+        }
+    }
+
+    /// Current objective in the solvers' maximization form.
+    pub fn objective(&self) -> f64 {
+        match self {
+            RaceSolver::Affine(problem) => problem.c_vector.dot(&problem.x_vector),
+            RaceSolver::Barrier(problem) => problem.c_vector.dot(&problem.x_vector),
+            RaceSolver::Quadratic(problem) => problem.objective(),
+            RaceSolver::SelfDual(problem) => problem.c_vector.dot(&problem.current_x()),
+            RaceSolver::Simplex(problem) => problem.objective(),
+            RaceSolver::Revised(problem) => problem.c_vector.dot(&problem.current_x()),
+        }
+    }
+}
+
+pub struct RaceLane {
+    pub method: SolverMethod,
+    pub solver: RaceSolver,
+    pub iterations: usize,
+    pub elapsed_ms: f64,
+    /// Objective in the user's sense after every iteration, starting point first.
+    pub trace: Vec<f64>,
+    pub status: Option<SolveStatus>,
+    sense: f64,
+}
+
+impl RaceLane {
+    pub fn is_finished(&self) -> bool {
+        self.status.is_some()
+    }
+
+    fn tick(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        if self.iterations >= RACE_ITERATION_LIMIT {
+            self.status = Some(SolveStatus::IterationLimit);
+            return;
+        }
+        let start = now_ms();
+        let result = self.solver.step();
+        self.elapsed_ms += now_ms() - start;
+        match result {
+            Ok(()) => {
+                self.iterations += 1;
+                self.trace.push(self.sense * self.solver.objective());
+            }
+            Err(status) => self.status = Some(status),
+        }
+    }
+}
+
+/// Two solvers on the same model, advanced one iteration each per tick so
+/// their progress can be watched side by side.
+pub struct Race {
+    pub lanes: Vec<RaceLane>,
+}
+
+impl Race {
+    pub fn new(
+        methods: [SolverMethod; 2],
+        model: &ValidatedProblem,
+        options: &SolverOptions,
+    ) -> Result<Self, String> {
+        let sense = model.sense.sign();
+        let lanes = methods
+            .iter()
+            .map(|&method| {
+                let solver = RaceSolver::new(method, model, options)?;
+                Ok(RaceLane {
+                    method,
+                    trace: vec![sense * solver.objective()],
+                    solver,
+                    iterations: 0,
+                    elapsed_ms: 0.0,
+                    status: None,
+                    sense,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self { lanes })
+    }
+
+    pub fn tick(&mut self) {
+        for lane in &mut self.lanes {
+            lane.tick();
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.lanes.iter().all(RaceLane::is_finished)
+    }
+
+    /// Summary of a finished race; only lanes that reached an optimum can win.
+    pub fn result(&self) -> RaceResult {
+        let entries: Vec<RaceEntry> = self
+            .lanes
+            .iter()
+            .map(|lane| RaceEntry {
+                method: lane.method,
+                iterations: lane.iterations,
+                elapsed_ms: lane.elapsed_ms,
+                objective: lane.trace.last().copied().unwrap_or(0.0),
+                status: lane.status.clone().unwrap_or(SolveStatus::Cancelled),
+            })
+            .collect();
+        let winner = |key: fn(&RaceEntry) -> f64| {
+            entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.status == SolveStatus::Optimal)
+                .min_by(|a, b| key(a.1).total_cmp(&key(b.1)))
+                .map(|(i, _)| i)
+        };
+        RaceResult {
+            winner_by_iterations: winner(|entry| entry.iterations as f64),
+            winner_by_time: winner(|entry| entry.elapsed_ms),
+            entries,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceEntry {
+    pub method: SolverMethod,
+    pub iterations: usize,
+    pub elapsed_ms: f64,
+    pub objective: f64,
+    pub status: SolveStatus,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceResult {
+    pub entries: Vec<RaceEntry>,
+    pub winner_by_iterations: Option<usize>,
+    pub winner_by_time: Option<usize>,
+}
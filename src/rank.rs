@@ -0,0 +1,174 @@
+use nalgebra::{DMatrix, DVector};
+
+/// Singular values below this, relative to the largest, count as zero.
+const RANK_TOLERANCE: f64 = 1e-10;
+
+/// Weights below this, relative to the largest of their relation, are
+/// rounding rather than rows taking part in it.
+const WEIGHT_TOLERANCE: f64 = 1e-8;
+
+/// One linear relation sum_i w_i a_i = 0 among the rows of A.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RowDependency {
+    /// Rows taking part and their weights w_i, by row.
+    pub weights: Vec<(usize, f64)>,
+    /// Whether b obeys the same relation. The rows are then redundant:
+    /// one of them can go. Otherwise they contradict each other and no x
+    /// satisfies all of them.
+    pub consistent: bool,
+}
+
+/// Why A A^T is singular: A has fewer independent rows than rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RankDeficiency {
+    pub rank: usize,
+    pub dependencies: Vec<RowDependency>,
+}
+
+/// The rank of A and the relations among its rows, or `None` when A has
+/// full row rank.
+///
+/// The relations are the vectors w with A^T w = 0: the right singular
+/// vectors of A^T for its zero singular values. A^T gets zero rows
+/// appended when it has fewer rows than columns, since the SVD only
+/// returns as many singular vectors as the smaller dimension.
+pub fn diagnose(a: &DMatrix<f64>, b: &DVector<f64>) -> Option<RankDeficiency> {
+    let m = a.nrows();
+    if m == 0 {
+        return None;
+    }
+    let mut a_t = DMatrix::zeros(a.ncols().max(m), m);
+    a_t.view_mut((0, 0), (a.ncols(), m))
+        .copy_from(&a.transpose());
+    let svd = a_t.svd(false, true);
+    let v_t = svd.v_t?;
+    let largest = svd.singular_values.max();
+    if largest == 0.0 {
+        return None;
+    }
+
+    let null: Vec<usize> = (0..m)
+        .filter(|&k| svd.singular_values[k] <= RANK_TOLERANCE * largest)
+        .collect();
+    if null.is_empty() {
+        return None;
+    }
+    let scale_b = 1.0 + b.amax();
+    let dependencies = null
+        .iter()
+        .map(|&k| {
+            let w = v_t.row(k).transpose();
+            let peak = w.amax();
+            let weights: Vec<(usize, f64)> = w
+                .iter()
+                .enumerate()
+                .filter(|(_, val)| val.abs() > WEIGHT_TOLERANCE * peak)
+                .map(|(i, &val)| (i, val))
+                .collect();
+            let consistent = w.dot(b).abs() <= 1e-8 * scale_b * peak;
+            RowDependency {
+                weights,
+                consistent,
+            }
+        })
+        .collect();
+    Some(RankDeficiency {
+        rank: m - null.len(),
+        dependencies,
+    })
+}
+
+impl RowDependency {
+    /// "C3 = C1 + 2 C2": the last row written as a combination of the
+    /// others. Rows with a weight far below the largest are skipped as the
+    /// left-hand side, since dividing by it would blow up the coefficients.
+    pub fn describe(&self, labels: &[String]) -> String {
+        let label = |i: usize| {
+            labels
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("row {}", i + 1))
+        };
+        let peak = self.weights.iter().map(|w| w.1.abs()).fold(0.0, f64::max);
+        let Some(&(pivot, pivot_weight)) =
+            self.weights.iter().rev().find(|w| w.1.abs() >= 0.1 * peak)
+        else {
+            return String::new();
+        };
+        let terms: Vec<String> = self
+            .weights
+            .iter()
+            .filter(|&&(i, _)| i != pivot)
+            .enumerate()
+            .map(|(k, &(i, w))| {
+                let factor = -w / pivot_weight;
+                let sign = match (k, factor < 0.0) {
+                    (0, true) => "-",
+                    (0, false) => "",
+                    (_, true) => " - ",
+                    (_, false) => " + ",
+                };
+                let magnitude = factor.abs();
+                if (magnitude - 1.0).abs() < 1e-9 {
+                    format!("{}{}", sign, label(i))
+                } else {
+                    format!("{}{} {}", sign, coefficient(magnitude), label(i))
+                }
+            })
+            .collect();
+        let combination = if terms.is_empty() {
+            "0".to_string()
+        } else {
+            terms.concat()
+        };
+        format!("{} = {}", label(pivot), combination)
+    }
+}
+
+impl RankDeficiency {
+    /// Every row in some relation, ascending.
+    pub fn dependent_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self
+            .dependencies
+            .iter()
+            .flat_map(|dependency| dependency.weights.iter().map(|&(i, _)| i))
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+
+    /// One sentence per relation, for the error message.
+    pub fn describe(&self, labels: &[String]) -> String {
+        let relations: Vec<String> = self
+            .dependencies
+            .iter()
+            .map(|dependency| {
+                format!(
+                    "{} ({})",
+                    dependency.describe(labels),
+                    if dependency.consistent {
+                        "redundant: one of these rows can be removed"
+                    } else {
+                        "contradictory: the right-hand sides disagree, so the model is infeasible"
+                    }
+                )
+            })
+            .collect();
+        format!(
+            "The constraint matrix has rank {} with {} rows, so its rows are linearly dependent: {}.",
+            self.rank,
+            self.rank + self.dependencies.len(),
+            relations.join("; ")
+        )
+    }
+}
+
+/// Whole numbers without decimals, anything else to four places.
+fn coefficient(val: f64) -> String {
+    if (val - val.round()).abs() < 1e-9 {
+        format!("{}", val.round())
+    } else {
+        format!("{:.4}", val)
+    }
+}
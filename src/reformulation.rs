@@ -0,0 +1,516 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{ConstraintSign, Sense};
+
+/// A piecewise-linear function of the variables with a standard LP form,
+/// as long as it is used on its convex side.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TermKind {
+    /// |e(x)| of one expression.
+    #[default]
+    Abs,
+    /// The largest of several expressions.
+    Max,
+    /// The smallest of several expressions.
+    Min,
+}
+
+impl TermKind {
+    pub const ALL: [TermKind; 3] = [TermKind::Abs, TermKind::Max, TermKind::Min];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TermKind::Abs => "|·|",
+            TermKind::Max => "max",
+            TermKind::Min => "min",
+        }
+    }
+
+    /// |·| and max are convex; min is concave.
+    fn is_convex(&self) -> bool {
+        !matches!(self, TermKind::Min)
+    }
+}
+
+/// a^T x + constant over the user's variables.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AffineExpression {
+    pub coefficients: Vec<f64>,
+    pub constant: f64,
+}
+
+impl AffineExpression {
+    pub fn zeros(variables: usize) -> Self {
+        Self {
+            coefficients: vec![0.0; variables],
+            constant: 0.0,
+        }
+    }
+
+    pub fn value(&self, x: &[f64]) -> f64 {
+        self.coefficients
+            .iter()
+            .zip(x)
+            .map(|(a, x)| a * x)
+            .sum::<f64>()
+            + self.constant
+    }
+}
+
+/// Where a term appears in the model.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Placement {
+    /// Added to Z with this weight.
+    Objective(f64),
+    /// term (sign) rhs as a constraint of its own.
+    Constraint(ConstraintSign, f64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Term {
+    pub kind: TermKind,
+    /// Exactly one for |·|; one or more for max and min.
+    pub expressions: Vec<AffineExpression>,
+    pub placement: Placement,
+}
+
+impl Term {
+    pub fn new(kind: TermKind, variables: usize) -> Self {
+        Self {
+            kind,
+            expressions: vec![AffineExpression::zeros(variables)],
+            placement: Placement::Objective(1.0),
+        }
+    }
+
+    /// The term as the user would write it, e.g. `max(x1 - 2, 3x2)`.
+    pub fn describe(&self, labels: &[String]) -> String {
+        let parts: Vec<String> = self
+            .expressions
+            .iter()
+            .map(|e| linear_text(&e.coefficients, labels, e.constant))
+            .collect();
+        match self.kind {
+            TermKind::Abs => format!("|{}|", parts.join(", ")),
+            kind => format!("{}({})", kind.label(), parts.join(", ")),
+        }
+    }
+}
+
+/// Linear rows `a x (signs) b` and objective `c` over named variables, as
+/// the user enters them before augmentation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearModel {
+    pub a: DMatrix<f64>,
+    pub b: DVector<f64>,
+    pub c: DVector<f64>,
+    pub signs: Vec<ConstraintSign>,
+    pub initial: Vec<f64>,
+    pub variable_labels: Vec<String>,
+    pub constraint_labels: Vec<String>,
+}
+
+impl LinearModel {
+    /// `label: a_i x (sign) b_i` for every row.
+    pub fn describe_rows(&self) -> Vec<String> {
+        (0..self.a.nrows()).map(|i| self.describe_row(i)).collect()
+    }
+
+    pub fn describe_objective(&self, sense: Sense) -> String {
+        let sense = if sense.is_maximize() { "max" } else { "min" };
+        let c: Vec<f64> = self.c.iter().copied().collect();
+        format!(
+            "{} Z = {}",
+            sense,
+            linear_text(&c, &self.variable_labels, 0.0)
+        )
+    }
+
+    fn describe_row(&self, i: usize) -> String {
+        let row: Vec<f64> = self.a.row(i).iter().copied().collect();
+        format!(
+            "{}: {} {} {}",
+            self.constraint_labels[i],
+            linear_text(&row, &self.variable_labels, 0.0),
+            self.signs[i].symbol(),
+            self.b[i]
+        )
+    }
+
+    /// Appends a column named `label`, zero in every row, valued `start` in
+    /// the initial point. Returns its index.
+    fn add_variable(&mut self, label: String, cost: f64, start: f64) -> usize {
+        let j = self.a.ncols();
+        self.a = std::mem::replace(&mut self.a, DMatrix::zeros(0, 0)).insert_column(j, 0.0);
+        self.c = std::mem::replace(&mut self.c, DVector::zeros(0)).insert_row(j, cost);
+        self.initial.push(start);
+        self.variable_labels.push(label);
+        j
+    }
+
+    /// Appends `coefficients x + sum extra (sign) rhs`, where `extra` pairs
+    /// added columns with their coefficient. Returns the row's description.
+    fn add_row(
+        &mut self,
+        label: String,
+        coefficients: &[f64],
+        extra: &[(usize, f64)],
+        sign: ConstraintSign,
+        rhs: f64,
+    ) -> String {
+        let i = self.a.nrows();
+        self.a = std::mem::replace(&mut self.a, DMatrix::zeros(0, 0)).insert_row(i, 0.0);
+        for (j, &value) in coefficients.iter().enumerate() {
+            self.a[(i, j)] = value;
+        }
+        for &(j, value) in extra {
+            self.a[(i, j)] = value;
+        }
+        self.b = std::mem::replace(&mut self.b, DVector::zeros(0)).insert_row(i, rhs);
+        self.signs.push(sign);
+        self.constraint_labels.push(label);
+        self.describe_row(i)
+    }
+}
+
+/// How one term was written as an LP.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rewrite {
+    /// The term as entered, with where it was used.
+    pub term: String,
+    /// Variables it added.
+    pub variables: Vec<String>,
+    /// Rows it added, written out.
+    pub rows: Vec<String>,
+}
+
+/// Rewrites each term into linear rows and, for objective terms, an epigraph
+/// variable:
+///
+/// - a convex cost w·|e(x)| becomes w·t with t ≥ e(x), t ≥ -e(x);
+/// - a convex cost w·max_k e_k(x) becomes w·(t+ - t-) with t+ - t- ≥ e_k(x),
+///   the free t split like a regression coefficient; min is the mirror image;
+/// - |e(x)| ≤ r becomes -r ≤ e(x) ≤ r, max_k e_k(x) ≤ r becomes e_k(x) ≤ r
+///   for every k, and min_k e_k(x) ≥ r becomes e_k(x) ≥ r.
+///
+/// Anything else is not convex and is rejected with the reason.
+pub fn reformulate(
+    mut model: LinearModel,
+    terms: &[Term],
+    sense: Sense,
+) -> Result<(LinearModel, Vec<Rewrite>), String> {
+    let user_labels = model.variable_labels.clone();
+    let mut rewrites = vec![];
+    for (k, term) in terms.iter().enumerate() {
+        let name = format!("T{}", k + 1);
+        let text = term.describe(&user_labels);
+        if term.expressions.is_empty()
+            || (term.kind == TermKind::Abs && term.expressions.len() != 1)
+        {
+            return Err(format!(
+                "{}: |·| takes exactly one expression, max and min at least one.",
+                name
+            ));
+        }
+        let row_label = |r: usize| {
+            if term.expressions.len() == 1 && term.kind != TermKind::Abs {
+                name.clone()
+            } else {
+                format!("{}.{}", name, r + 1)
+            }
+        };
+        let values: Vec<f64> = term
+            .expressions
+            .iter()
+            .map(|e| e.value(&model.initial))
+            .collect();
+
+        let rewrite = match term.placement {
+            Placement::Objective(weight) => {
+                if weight == 0.0 {
+                    return Err(format!("{}: the weight of {} is 0.", name, text));
+                }
+                // A convex term must be a cost: weighted positively when
+                // minimizing, negatively when maximizing. A concave one is
+                // the reverse.
+                let cost = (weight > 0.0) != sense.is_maximize();
+                if cost != term.kind.is_convex() {
+                    return Err(format!(
+                        "{}: {} {} × {} is not convex, so it has no LP form. {}",
+                        name,
+                        if sense.is_maximize() {
+                            "maximizing"
+                        } else {
+                            "minimizing"
+                        },
+                        weight,
+                        text,
+                        if term.kind.is_convex() {
+                            "|·| and max terms can be minimized with a positive weight or maximized with a negative one."
+                        } else {
+                            "min terms can be maximized with a positive weight or minimized with a negative one."
+                        }
+                    ));
+                }
+                rewrite_objective(&mut model, term, weight, (&name, &text), &values, row_label)
+            }
+            Placement::Constraint(sign, rhs) => {
+                let convex_side = if term.kind.is_convex() {
+                    ConstraintSign::LessEqual
+                } else {
+                    ConstraintSign::GreaterEqual
+                };
+                if sign != convex_side {
+                    return Err(format!(
+                        "{}: {} {} {} is not convex, so it has no LP form; {} terms can only be bounded {}.",
+                        name,
+                        text,
+                        sign.symbol(),
+                        rhs,
+                        term.kind.label(),
+                        if term.kind.is_convex() { "above (<=)" } else { "below (>=)" }
+                    ));
+                }
+                let rows = if term.kind == TermKind::Abs {
+                    // -r <= e(x) <= r, the lower side with e negated.
+                    let e = &term.expressions[0];
+                    let negated: Vec<f64> = e.coefficients.iter().map(|a| -a).collect();
+                    vec![
+                        model.add_row(row_label(0), &e.coefficients, &[], sign, rhs - e.constant),
+                        model.add_row(row_label(1), &negated, &[], sign, rhs + e.constant),
+                    ]
+                } else {
+                    term.expressions
+                        .iter()
+                        .enumerate()
+                        .map(|(r, e)| {
+                            model.add_row(
+                                row_label(r),
+                                &e.coefficients,
+                                &[],
+                                sign,
+                                rhs - e.constant,
+                            )
+                        })
+                        .collect()
+                };
+                Rewrite {
+                    term: format!("{} {} {}", text, sign.symbol(), rhs),
+                    variables: vec![],
+                    rows,
+                }
+            }
+        };
+        rewrites.push(rewrite);
+    }
+    Ok((model, rewrites))
+}
+
+/// Adds the epigraph (or hypograph) variable of an objective term and the
+/// rows that tie it to the expressions.
+fn rewrite_objective(
+    model: &mut LinearModel,
+    term: &Term,
+    weight: f64,
+    (name, text): (&str, &str),
+    values: &[f64],
+    row_label: impl Fn(usize) -> String,
+) -> Rewrite {
+    // T3 adds t3, or t3+ and t3-.
+    let index = name.trim_start_matches('T');
+    let mut rows = vec![];
+    let variables = if term.kind == TermKind::Abs {
+        // t >= e(x) and t >= -e(x), i.e. e(x) - t <= -c and -e(x) - t <= c.
+        let e = &term.expressions[0];
+        let label = format!("t{}", index);
+        let t = model.add_variable(label.clone(), weight, values[0].abs() + 1.0);
+        let negated: Vec<f64> = e.coefficients.iter().map(|a| -a).collect();
+        rows.push(model.add_row(
+            row_label(0),
+            &e.coefficients,
+            &[(t, -1.0)],
+            ConstraintSign::LessEqual,
+            -e.constant,
+        ));
+        rows.push(model.add_row(
+            row_label(1),
+            &negated,
+            &[(t, -1.0)],
+            ConstraintSign::LessEqual,
+            e.constant,
+        ));
+        vec![label]
+    } else {
+        // t = t+ - t- is free; start it strictly on the feasible side of
+        // every row.
+        let (plus, minus) = if term.kind == TermKind::Max {
+            let largest = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            (largest.max(-1.0) + 2.0, 1.0)
+        } else {
+            let smallest = values.iter().copied().fold(f64::INFINITY, f64::min);
+            (1.0, (-smallest).max(-1.0) + 2.0)
+        };
+        let labels = [format!("t{}+", index), format!("t{}-", index)];
+        let t_plus = model.add_variable(labels[0].clone(), weight, plus);
+        let t_minus = model.add_variable(labels[1].clone(), -weight, minus);
+        let sign = if term.kind == TermKind::Max {
+            ConstraintSign::LessEqual
+        } else {
+            ConstraintSign::GreaterEqual
+        };
+        // e_k(x) - t+ + t- (sign) -c_k
+        for (r, e) in term.expressions.iter().enumerate() {
+            rows.push(model.add_row(
+                row_label(r),
+                &e.coefficients,
+                &[(t_plus, -1.0), (t_minus, 1.0)],
+                sign,
+                -e.constant,
+            ));
+        }
+        labels.to_vec()
+    };
+    Rewrite {
+        term: format!("{} × {} in Z", weight, text),
+        variables,
+        rows,
+    }
+}
+
+/// `2x1 - x2 + 3`, leaving out zero coefficients; `0` when nothing is left.
+pub(crate) fn linear_text(coefficients: &[f64], labels: &[String], constant: f64) -> String {
+    let mut text = String::new();
+    let mut push = |value: f64, label: &str| {
+        if value == 0.0 {
+            return;
+        }
+        let magnitude = value.abs();
+        let sign = match (text.is_empty(), value < 0.0) {
+            (true, true) => "-",
+            (true, false) => "",
+            (false, true) => " - ",
+            (false, false) => " + ",
+        };
+        let number = if magnitude == 1.0 && !label.is_empty() {
+            String::new()
+        } else {
+            magnitude.to_string()
+        };
+        text.push_str(&format!("{}{}{}", sign, number, label));
+    };
+    for (j, &value) in coefficients.iter().enumerate() {
+        push(value, &labels[j]);
+    }
+    push(constant, "");
+    if text.is_empty() {
+        "0".to_string()
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SolverMethod;
+    use crate::test_models::{assert_optimum, from_linear, solve};
+
+    /// `rows` over two variables with a zero objective and start (1, 1).
+    fn model(rows: &[([f64; 2], ConstraintSign, f64)]) -> LinearModel {
+        LinearModel {
+            a: DMatrix::from_fn(rows.len(), 2, |i, j| rows[i].0[j]),
+            b: DVector::from_iterator(rows.len(), rows.iter().map(|row| row.2)),
+            c: DVector::zeros(2),
+            signs: rows.iter().map(|row| row.1).collect(),
+            initial: vec![1.0, 1.0],
+            variable_labels: vec!["x1".to_string(), "x2".to_string()],
+            constraint_labels: (1..=rows.len()).map(|i| format!("C{}", i)).collect(),
+        }
+    }
+
+    fn term(kind: TermKind, expressions: &[[f64; 3]], placement: Placement) -> Term {
+        Term {
+            kind,
+            expressions: expressions
+                .iter()
+                .map(|e| AffineExpression {
+                    coefficients: e[..2].to_vec(),
+                    constant: e[2],
+                })
+                .collect(),
+            placement,
+        }
+    }
+
+    #[test]
+    fn absolute_value_cost_reaches_zero() {
+        // min |x1 - 3| subject to x1 + x2 <= 10: t1 >= ±(x1 - 3) pins x1.
+        let base = model(&[([1.0, 1.0], ConstraintSign::LessEqual, 10.0)]);
+        let abs = term(
+            TermKind::Abs,
+            &[[1.0, 0.0, -3.0]],
+            Placement::Objective(1.0),
+        );
+        let (linear, rewrites) = reformulate(base, &[abs], Sense::Minimize).unwrap();
+        assert_eq!(rewrites[0].variables, vec!["t1"]);
+        assert_eq!(rewrites[0].rows.len(), 2);
+
+        let report = solve(
+            &from_linear(&linear, Sense::Minimize),
+            SolverMethod::Simplex,
+        );
+        assert_optimum(&report, 0.0, &[3.0], 1e-9);
+    }
+
+    #[test]
+    fn max_cost_splits_a_free_epigraph_variable() {
+        // min max(x1, x2) subject to x1 + x2 >= 4 is 2, only at (2, 2).
+        let base = model(&[([1.0, 1.0], ConstraintSign::GreaterEqual, 4.0)]);
+        let max = term(
+            TermKind::Max,
+            &[[1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            Placement::Objective(1.0),
+        );
+        let (linear, rewrites) = reformulate(base, &[max], Sense::Minimize).unwrap();
+        assert_eq!(rewrites[0].variables, vec!["t1+", "t1-"]);
+
+        let report = solve(
+            &from_linear(&linear, Sense::Minimize),
+            SolverMethod::Simplex,
+        );
+        assert_optimum(&report, 2.0, &[2.0, 2.0], 1e-9);
+    }
+
+    #[test]
+    fn absolute_value_bound_becomes_two_rows() {
+        // max x1 + x2 subject to |x1 - 3| <= 1 and x2 <= 2: (4, 2), Z = 6.
+        let mut base = model(&[([0.0, 1.0], ConstraintSign::LessEqual, 2.0)]);
+        base.c = DVector::from_vec(vec![1.0, 1.0]);
+        let abs = term(
+            TermKind::Abs,
+            &[[1.0, 0.0, -3.0]],
+            Placement::Constraint(ConstraintSign::LessEqual, 1.0),
+        );
+        let (linear, _) = reformulate(base, &[abs], Sense::Maximize).unwrap();
+        assert_eq!(linear.a.shape(), (3, 2));
+
+        let report = solve(
+            &from_linear(&linear, Sense::Maximize),
+            SolverMethod::Simplex,
+        );
+        assert_optimum(&report, 6.0, &[4.0, 2.0], 1e-9);
+    }
+
+    #[test]
+    fn rejects_the_concave_side() {
+        let base = model(&[([1.0, 1.0], ConstraintSign::LessEqual, 10.0)]);
+        let abs = term(TermKind::Abs, &[[1.0, 0.0, 0.0]], Placement::Objective(1.0));
+        assert!(reformulate(base.clone(), std::slice::from_ref(&abs), Sense::Maximize).is_err());
+        let bound = Term {
+            placement: Placement::Constraint(ConstraintSign::GreaterEqual, 1.0),
+            ..abs
+        };
+        assert!(reformulate(base, &[bound], Sense::Minimize).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    big_m_form, Augmentation, ConstraintSign, Sense, SolverMethod, StandardForm, StartOptions,
+    ValidatedProblem,
+};
+
+/// Which norm of the residuals y - X b the fit minimizes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegressionKind {
+    /// Least absolute deviations: min sum |r_i|. Each residual is split as
+    /// r_i = u_i - v_i with u, v >= 0 and the objective is sum (u_i + v_i).
+    #[default]
+    LeastAbsolute,
+    /// Chebyshev (min-max): min t subject to -t <= r_i <= t, written with a
+    /// surplus s_i and a slack w_i per point.
+    Chebyshev,
+}
+
+impl RegressionKind {
+    pub const ALL: [RegressionKind; 2] = [RegressionKind::LeastAbsolute, RegressionKind::Chebyshev];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RegressionKind::LeastAbsolute => "L1 (least absolute deviations)",
+            RegressionKind::Chebyshev => "Chebyshev (min-max)",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            RegressionKind::LeastAbsolute => "l1",
+            RegressionKind::Chebyshev => "chebyshev",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.key() == key)
+    }
+
+    /// Name of the quantity the LP minimizes.
+    pub fn loss_label(&self) -> &'static str {
+        match self {
+            RegressionKind::LeastAbsolute => "Sum of |residuals|",
+            RegressionKind::Chebyshev => "Largest |residual|",
+        }
+    }
+}
+
+/// Fit y ~ X b to data points by linear programming.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionProblem {
+    pub kind: RegressionKind,
+    /// One row per point, one column per explanatory variable.
+    pub points: DMatrix<f64>,
+    pub y: DVector<f64>,
+    /// Fit a constant term b0 as well.
+    pub intercept: bool,
+}
+
+/// The data the LP was built from, kept on the model so the solution can be
+/// read back as coefficients and residuals.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegressionLayout {
+    pub kind: RegressionKind,
+    #[serde(with = "crate::schema::matrix")]
+    pub points: DMatrix<f64>,
+    #[serde(with = "crate::schema::vector")]
+    pub y: DVector<f64>,
+    pub intercept: bool,
+}
+
+impl RegressionLayout {
+    /// Number of fitted coefficients, the intercept included.
+    pub fn coefficient_count(&self) -> usize {
+        self.points.ncols() + usize::from(self.intercept)
+    }
+
+    /// X with a leading column of ones when there is an intercept.
+    pub fn design(&self) -> DMatrix<f64> {
+        if self.intercept {
+            self.points.clone().insert_column(0, 1.0)
+        } else {
+            self.points.clone()
+        }
+    }
+
+    /// b_j = b_j+ - b_j-, read from the first 2 p entries of x.
+    pub fn coefficients(&self, x: &DVector<f64>) -> DVector<f64> {
+        let p = self.coefficient_count();
+        DVector::from_fn(p, |j, _| {
+            x.get(j).copied().unwrap_or(0.0) - x.get(p + j).copied().unwrap_or(0.0)
+        })
+    }
+
+    pub fn residuals(&self, coefficients: &DVector<f64>) -> DVector<f64> {
+        &self.y - self.design() * coefficients
+    }
+
+    /// The norm of the residuals the fit minimizes.
+    pub fn loss(&self, residuals: &DVector<f64>) -> f64 {
+        match self.kind {
+            RegressionKind::LeastAbsolute => residuals.iter().map(|r| r.abs()).sum(),
+            RegressionKind::Chebyshev => residuals.amax(),
+        }
+    }
+
+    /// Symbol of coefficient j: b0 for the intercept, then b1, b2, ...
+    pub fn coefficient_name(&self, j: usize) -> String {
+        format!("b{}", j + usize::from(!self.intercept))
+    }
+}
+
+/// Reads data points, one per line, as the explanatory values followed by y.
+/// Fields may be separated by commas, semicolons, tabs or spaces; blank lines,
+/// `#` comments and a header line of column names are skipped.
+pub fn parse_points(text: &str) -> Result<(DMatrix<f64>, DVector<f64>), Vec<String>> {
+    let mut errors = vec![];
+    let mut rows: Vec<Vec<f64>> = vec![];
+    let mut width = None;
+    for (number, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let fields: Vec<&str> = line
+            .split(|ch: char| ch == ',' || ch == ';' || ch.is_whitespace())
+            .filter(|field| !field.is_empty())
+            .collect();
+        let values: Vec<Option<f64>> = fields
+            .iter()
+            .map(|field| field.parse::<f64>().ok().filter(|val| val.is_finite()))
+            .collect();
+        if rows.is_empty() && width.is_none() && values.iter().all(Option::is_none) {
+            width = Some(fields.len());
+            continue;
+        }
+        let Some(values) = values.into_iter().collect::<Option<Vec<f64>>>() else {
+            errors.push(format!(
+                "Line {}: every field must be a number.",
+                number + 1
+            ));
+            continue;
+        };
+        let expected = *width.get_or_insert(values.len());
+        if values.len() != expected {
+            errors.push(format!(
+                "Line {} has {} values but the data has {} columns.",
+                number + 1,
+                values.len(),
+                expected
+            ));
+            continue;
+        }
+        rows.push(values);
+    }
+    let columns = width.unwrap_or(0);
+    if errors.is_empty() && columns < 2 {
+        errors.push("Each point needs at least one x value followed by y.".to_string());
+    }
+    if errors.is_empty() && rows.is_empty() {
+        errors.push("Paste at least one data point.".to_string());
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    let points = DMatrix::from_fn(rows.len(), columns - 1, |i, j| rows[i][j]);
+    let y = DVector::from_iterator(rows.len(), rows.iter().map(|row| row[columns - 1]));
+    Ok((points, y))
+}
+
+impl RegressionProblem {
+    pub fn layout(&self) -> RegressionLayout {
+        RegressionLayout {
+            kind: self.kind,
+            points: self.points.clone(),
+            y: self.y.clone(),
+            intercept: self.intercept,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let p = self.layout().coefficient_count();
+        if self.points.nrows() < p {
+            return Err(vec![format!(
+                "{} coefficients need at least {} points; there are {}.",
+                p,
+                p,
+                self.points.nrows()
+            )]);
+        }
+        Ok(())
+    }
+
+    /// Standard LP form of the fit. Every coefficient is free, so it is split
+    /// as b_j = b_j+ - b_j-; the columns are b+, b-, then the residual
+    /// variables of `kind`.
+    ///
+    /// b+ = b- = 1 (the zero fit) with the residual variables set to match y
+    /// is strictly positive and satisfies every row, so the interior methods
+    /// start there. The simplex methods get Big-M artificials as their
+    /// starting basis, as for transportation problems.
+    pub fn to_validated(&self, method: SolverMethod, options: StartOptions) -> ValidatedProblem {
+        let layout = self.layout();
+        let design = layout.design();
+        let (m, p) = design.shape();
+        let y = &self.y;
+
+        let (rows, extra) = match self.kind {
+            RegressionKind::LeastAbsolute => (m, 2 * m),
+            RegressionKind::Chebyshev => (2 * m, 1 + 2 * m),
+        };
+        let n = 2 * p + extra;
+        let mut a = DMatrix::zeros(rows, n);
+        let mut b = DVector::zeros(rows);
+        let mut c = DVector::zeros(n);
+        let mut initial = vec![1.0; n];
+        let mut variable_labels: Vec<String> = (0..p)
+            .map(|j| format!("{}+", layout.coefficient_name(j)))
+            .chain((0..p).map(|j| format!("{}-", layout.coefficient_name(j))))
+            .collect();
+        let mut constraint_labels = vec![];
+
+        // x_i b+ - x_i b- in row `row` for point i.
+        let fit_row = |a: &mut DMatrix<f64>, row: usize, i: usize| {
+            for j in 0..p {
+                a[(row, j)] = design[(i, j)];
+                a[(row, p + j)] = -design[(i, j)];
+            }
+        };
+
+        match self.kind {
+            RegressionKind::LeastAbsolute => {
+                // x_i b + u_i - v_i = y_i
+                for i in 0..m {
+                    let (u, v) = (2 * p + i, 2 * p + m + i);
+                    fit_row(&mut a, i, i);
+                    a[(i, u)] = 1.0;
+                    a[(i, v)] = -1.0;
+                    b[i] = y[i];
+                    c[u] = 1.0;
+                    c[v] = 1.0;
+                    initial[u] = y[i].max(0.0) + 1.0;
+                    initial[v] = (-y[i]).max(0.0) + 1.0;
+                    constraint_labels.push(format!("P{}", i + 1));
+                }
+                variable_labels.extend((1..=m).map(|i| format!("u{}", i)));
+                variable_labels.extend((1..=m).map(|i| format!("v{}", i)));
+            }
+            RegressionKind::Chebyshev => {
+                // x_i b + t - s_i = y_i and x_i b - t + w_i = y_i
+                let t = 2 * p;
+                let bound = y.amax() + 1.0;
+                c[t] = 1.0;
+                initial[t] = bound;
+                for i in 0..m {
+                    let (s, w) = (t + 1 + i, t + 1 + m + i);
+                    fit_row(&mut a, i, i);
+                    a[(i, t)] = 1.0;
+                    a[(i, s)] = -1.0;
+                    b[i] = y[i];
+                    initial[s] = bound - y[i];
+                    fit_row(&mut a, m + i, i);
+                    a[(m + i, t)] = -1.0;
+                    a[(m + i, w)] = 1.0;
+                    b[m + i] = y[i];
+                    initial[w] = bound + y[i];
+                }
+                constraint_labels.extend((1..=m).map(|i| format!("P{} ≤ t", i)));
+                constraint_labels.extend((1..=m).map(|i| format!("P{} ≥ -t", i)));
+                variable_labels.push("t".to_string());
+                variable_labels.extend((1..=m).map(|i| format!("s{}", i)));
+                variable_labels.extend((1..=m).map(|i| format!("w{}", i)));
+            }
+        }
+        let signs = vec![ConstraintSign::Equal; rows];
+
+        let form = if method.is_interior() {
+            StandardForm {
+                a,
+                b,
+                c,
+                initial,
+                augmentation: Augmentation {
+                    original_variables: n,
+                    row_multipliers: vec![1.0; rows],
+                    ..Augmentation::default()
+                },
+            }
+        } else {
+            let form = big_m_form(&a, &b, &c, &signs, &initial, Sense::Minimize);
+            for (k, &(_, col)) in form.augmentation.artificial_columns.iter().enumerate() {
+                variable_labels.resize(col + 1, String::new());
+                variable_labels[col] = format!("a{}", k + 1);
+            }
+            form
+        };
+
+        ValidatedProblem {
+            method,
+            sense: Sense::Minimize,
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            quadratic: None,
+            initial: form.initial,
+            constraint_signs: signs,
+            integer_variables: vec![false; n],
+            variable_labels,
+            constraint_groups: vec![None; constraint_labels.len()],
+            constraint_labels,
+            options,
+            is_augmented: false,
+            augmentation: form.augmentation,
+            transportation: None,
+            regression: Some(layout),
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+use crate::inspector::StateEdit;
+use crate::interior::{InteriorPointProblem, SolverOptions};
+use crate::model::ValidatedProblem;
+use crate::schema;
+
+/// `kind` of a recording document.
+pub const RECORDING_KIND: &str = "recording";
+
+/// Suggested name of the downloaded recording.
+pub const RECORDING_FILE: &str = "ipsolver-recording.json";
+
+/// The exact inputs of a run and everything done to it afterwards, in
+/// order. Every solver is deterministic, so taking the same actions on the
+/// same inputs reproduces every number, which `Replay` checks as it goes.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub model: ValidatedProblem,
+    /// Settings the run started with; later changes are among the events.
+    pub options: SolverOptions,
+    /// Affine scaling's state before its first step. It fixes the start
+    /// Phase 1, the form or a warm start chose, which the model alone does
+    /// not.
+    pub start: Option<Box<InteriorPointProblem>>,
+    pub events: Vec<RunEvent>,
+    /// Iterate of each card of the recorded run, at full precision, to
+    /// check a replay against. JSON keeps every `f64` exactly.
+    #[serde(default)]
+    pub path: Vec<Vec<f64>>,
+}
+
+/// One action on a recorded run.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunEvent {
+    /// "Next step", or one step of "Solve Completely", including the one
+    /// that ends the run.
+    Step,
+    Centering,
+    /// A settings change; the options as they were afterwards.
+    Options(SolverOptions),
+    /// An inspector edit of the affine-scaling state.
+    Edit(StateEdit),
+    /// A hand-set affine-scaling iterate.
+    SetStart(#[serde(with = "schema::vector")] DVector<f64>),
+}
+
+impl RunEvent {
+    /// Whether the event takes a step, which a replay spends a tick on.
+    pub fn is_step(&self) -> bool {
+        matches!(self, RunEvent::Step | RunEvent::Centering)
+    }
+}
+
+impl Recording {
+    pub fn new(
+        model: ValidatedProblem,
+        options: SolverOptions,
+        start: Option<&InteriorPointProblem>,
+    ) -> Self {
+        Self {
+            model,
+            options,
+            start: start.map(|problem| Box::new(problem.clone())),
+            events: vec![],
+            path: vec![],
+        }
+    }
+
+    /// Records a settings change, unless `options` are the ones already in
+    /// effect.
+    pub fn record_options(&mut self, options: &SolverOptions) {
+        if self.current_options() != options {
+            self.events.push(RunEvent::Options(options.clone()));
+        }
+    }
+
+    /// Options in effect after the last event.
+    pub fn current_options(&self) -> &SolverOptions {
+        self.events
+            .iter()
+            .rev()
+            .find_map(|event| match event {
+                RunEvent::Options(options) => Some(options),
+                _ => None,
+            })
+            .unwrap_or(&self.options)
+    }
+
+    pub fn step_count(&self) -> usize {
+        self.events.iter().filter(|event| event.is_step()).count()
+    }
+
+    pub fn to_json(&self) -> String {
+        schema::to_json(RECORDING_KIND, self)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        schema::from_json(RECORDING_KIND, text)
+    }
+}
+
+/// A replay in progress: hands out the recorded events a step at a time
+/// and compares the iterates they produce with the recorded ones.
+pub struct Replay {
+    recording: Recording,
+    next: usize,
+    steps_taken: usize,
+}
+
+impl Replay {
+    pub fn new(recording: Recording) -> Self {
+        Self {
+            recording,
+            next: 0,
+            steps_taken: 0,
+        }
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// The events up to and including the next step, or `None` once all
+    /// have been handed out. Settings changes and edits between two steps
+    /// come together with the step after them.
+    pub fn next_events(&mut self) -> Option<&[RunEvent]> {
+        let events = &self.recording.events;
+        if self.next == events.len() {
+            return None;
+        }
+        let start = self.next;
+        self.next = events[start..]
+            .iter()
+            .position(RunEvent::is_step)
+            .map_or(events.len(), |k| start + k + 1);
+        self.steps_taken += 1;
+        Some(&events[start..self.next])
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next == self.recording.events.len()
+    }
+
+    /// Steps replayed so far and in all.
+    pub fn progress(&self) -> (usize, usize) {
+        (
+            self.steps_taken.min(self.recording.step_count()),
+            self.recording.step_count(),
+        )
+    }
+
+    /// Compares the iterates so far with the recorded path, bit for bit, so
+    /// even a last-place rounding difference or a zero of the other sign
+    /// counts; at the end the replay must also have as many. `Err` names
+    /// the first iteration that differs.
+    pub fn check(&self, path: &[DVector<f64>]) -> Result<(), String> {
+        let recorded = &self.recording.path;
+        if let Some(k) = path.iter().zip(recorded).position(|(x, expected)| {
+            !x.iter()
+                .map(|v| v.to_bits())
+                .eq(expected.iter().map(|v| v.to_bits()))
+        }) {
+            return Err(format!(
+                "iteration {} differs from the recording: the replay has x = {:?} where the recording has x = {:?}",
+                k,
+                path[k].as_slice(),
+                recorded[k]
+            ));
+        }
+        if path.len() > recorded.len() || (self.is_finished() && path.len() < recorded.len()) {
+            return Err(format!(
+                "the replay has {} iterations where the recording has {}",
+                path.len(),
+                recorded.len()
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_models::wyndor;
+
+    fn recording() -> Recording {
+        let mut recording = Recording::new(wyndor(), SolverOptions::default(), None);
+        recording.events.push(RunEvent::Step);
+        let mut options = SolverOptions::default();
+        options.hide_projection = !options.hide_projection;
+        recording.record_options(&options);
+        recording.record_options(&options);
+        recording.events.push(RunEvent::Centering);
+        recording.path = vec![vec![1.0, 0.1], vec![0.0, 0.3]];
+        recording
+    }
+
+    #[test]
+    fn settings_changes_ride_along_with_the_next_step() {
+        let recording = recording();
+        assert_eq!(recording.events.len(), 3, "the repeated change is dropped");
+        assert_eq!(recording.step_count(), 2);
+
+        let mut replay = Replay::new(recording);
+        assert_eq!(replay.next_events().unwrap(), &[RunEvent::Step]);
+        let next = replay.next_events().unwrap();
+        assert!(matches!(next, [RunEvent::Options(_), RunEvent::Centering]));
+        assert!(replay.is_finished() && replay.next_events().is_none());
+        assert_eq!(replay.progress(), (2, 2));
+    }
+
+    #[test]
+    fn check_compares_bit_for_bit() {
+        let mut replay = Replay::new(recording());
+        let path = |x: [f64; 2], y: [f64; 2]| {
+            vec![DVector::from_row_slice(&x), DVector::from_row_slice(&y)]
+        };
+        assert!(replay.check(&path([1.0, 0.1], [0.0, 0.3])[..1]).is_ok());
+
+        let next_up = f64::from_bits(0.1f64.to_bits() + 1);
+        let error = replay.check(&path([1.0, next_up], [0.0, 0.3])).unwrap_err();
+        assert!(error.starts_with("iteration 0 differs"), "{error}");
+        assert!(replay.check(&path([1.0, 0.1], [-0.0, 0.3])).is_err());
+
+        // Once every event is out, a short path is a mismatch too.
+        while replay.next_events().is_some() {}
+        assert!(replay.check(&path([1.0, 0.1], [0.0, 0.3])[..1]).is_err());
+        assert!(replay.check(&path([1.0, 0.1], [0.0, 0.3])).is_ok());
+    }
+}
@@ -0,0 +1,366 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::cross_check::CrossCheck;
+use crate::equilibration::Equilibration;
+use crate::interior::{InteriorPointError, SolverOptions};
+use crate::milp::{solve_integer, MilpResult};
+use crate::model::{Goal, ValidatedProblem};
+use crate::norm::Norm;
+use crate::presolve::Presolve;
+use crate::schema;
+use crate::sensitivity::{self, SensitivityReport};
+use crate::simplex::SimplexError;
+use crate::stopping::StopReason;
+use crate::theory::IterationBound;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolveStatus {
+    Optimal,
+    IterationLimit,
+    Infeasible,
+    Unbounded,
+    Cancelled,
+    Failed(String),
+}
+
+impl SolveStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            SolveStatus::Optimal => "Optimal",
+            SolveStatus::IterationLimit => "Iteration limit reached",
+            SolveStatus::Infeasible => "Infeasible",
+            SolveStatus::Unbounded => "Unbounded",
+            SolveStatus::Cancelled => "Cancelled",
+            SolveStatus::Failed(_) => "Failed",
+        }
+    }
+}
+
+impl From<&InteriorPointError> for SolveStatus {
+    fn from(error: &InteriorPointError) -> Self {
+        match error {
+            // The tolerances the run was given are met, or P c~ vanished
+            // with no ray: c is then constant on the feasible set.
+            InteriorPointError::Stopped(
+                StopReason::DualityGap
+                | StopReason::ObjectiveChange
+                | StopReason::StepNorm
+                | StopReason::NoDecrease,
+            ) => SolveStatus::Optimal,
+            InteriorPointError::Stopped(StopReason::IterationLimit) => SolveStatus::IterationLimit,
+            // A stall with no certificate of optimality.
+            InteriorPointError::NoImprovement => SolveStatus::Failed(error.to_string()),
+            // A Phase 1 residual or a Farkas certificate; leftover
+            // artificials are caught by `SolveReport::complete`.
+            InteriorPointError::NotFeasible { .. } => SolveStatus::Infeasible,
+            InteriorPointError::Unbounded(_) => SolveStatus::Unbounded,
+            // A barrier step that left x > 0 says nothing about the model.
+            InteriorPointError::NotInterior { .. }
+            | InteriorPointError::SingularMatrix { .. }
+            | InteriorPointError::DimensionMismatch { .. }
+            | InteriorPointError::NumericalBreakdown { .. } => {
+                SolveStatus::Failed(error.to_string())
+            }
+        }
+    }
+}
+
+impl From<&SimplexError> for SolveStatus {
+    fn from(error: &SimplexError) -> Self {
+        match error {
+            SimplexError::Optimal => SolveStatus::Optimal,
+            SimplexError::Unbounded(_) => SolveStatus::Unbounded,
+            SimplexError::NoInitialBasis => {
+                SolveStatus::Failed("no identity columns for a starting basis".to_string())
+            }
+            SimplexError::SingularBasis => {
+                SolveStatus::Failed("basis matrix became singular".to_string())
+            }
+            SimplexError::Infeasible(_) => SolveStatus::Infeasible,
+            SimplexError::NotDualFeasible => {
+                SolveStatus::Failed("the starting basis is not dual feasible".to_string())
+            }
+            SimplexError::PivotLimit => SolveStatus::IterationLimit,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Timings {
+    pub total_ms: f64,
+    pub per_iteration_ms: Vec<f64>,
+}
+
+impl Timings {
+    pub fn record(&mut self, elapsed_ms: f64) {
+        self.per_iteration_ms.push(elapsed_ms);
+        self.total_ms += elapsed_ms;
+    }
+}
+
+/// Outcome of a solve, independent of which algorithm produced it.
+/// Serialized as is for the JSON export and the JS API.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SolveReport {
+    pub status: SolveStatus,
+    pub iterations: usize,
+    #[serde(serialize_with = "schema::vector::serialize")]
+    pub solution: DVector<f64>,
+    #[serde(serialize_with = "schema::option_vector::serialize")]
+    pub duals: Option<DVector<f64>>,
+    pub objective: f64,
+    pub timings: Timings,
+    pub warnings: Vec<String>,
+    /// Branch-and-bound result when the model has integer variables.
+    pub integer: Option<MilpResult>,
+    /// Iteration count complexity theory predicts for the method used.
+    pub bound: Option<IterationBound>,
+    /// The solution in the user's variables when presolve shifted or removed
+    /// columns; `solution` is then in the reduced space.
+    #[serde(serialize_with = "schema::option_vector::serialize")]
+    pub original_solution: Option<DVector<f64>>,
+    /// Value of each objective of a weighted goal program at the solution.
+    pub goals: Vec<GoalAchievement>,
+    /// Shadow prices, reduced costs and ranges at an optimal LP basis.
+    pub sensitivity: Option<SensitivityReport>,
+    /// Direction of unboundedness found by affine scaling.
+    pub ray: Option<ImprovingRay>,
+    /// Row and column scaling affine scaling ran on; `solution` and `duals`
+    /// are already back in the original units.
+    pub scaling: Option<Equilibration>,
+    /// The stopping criterion that ended an affine-scaling run.
+    pub stop_reason: Option<StopReason>,
+    /// ‖A x - b‖ at the solution and the norm it was measured in, for the
+    /// solvers whose iterates can drift off A x = b.
+    pub residual: Option<(Norm, f64)>,
+    /// The objective compared with the simplex's on the same model, when
+    /// the cross-check setting is on.
+    pub cross_check: Option<CrossCheck>,
+}
+
+/// x + t d stays feasible for every t >= 0 while the objective keeps
+/// improving.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ImprovingRay {
+    /// d, in the standard-form variables of `solution`.
+    #[serde(serialize_with = "schema::vector::serialize")]
+    pub direction: DVector<f64>,
+    /// Change of the objective per unit of t.
+    pub rate: f64,
+}
+
+impl ImprovingRay {
+    pub fn new(direction: DVector<f64>, c: &DVector<f64>) -> Self {
+        let rate = c.dot(&direction);
+        Self { direction, rate }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct GoalAchievement {
+    pub weight: f64,
+    pub value: f64,
+}
+
+impl SolveReport {
+    pub fn new(
+        status: SolveStatus,
+        iterations: usize,
+        solution: DVector<f64>,
+        c: &DVector<f64>,
+    ) -> Self {
+        let objective = c.dot(&solution);
+        Self {
+            status,
+            iterations,
+            solution,
+            duals: None,
+            objective,
+            timings: Timings::default(),
+            warnings: vec![],
+            integer: None,
+            bound: None,
+            original_solution: None,
+            goals: vec![],
+            sensitivity: None,
+            ray: None,
+            scaling: None,
+            stop_reason: None,
+            residual: None,
+            cross_check: None,
+        }
+    }
+
+    /// Records ‖A x - b‖ in `norm` and adds a warning when the reported
+    /// solution violates A x = b or x >= 0.
+    pub fn check_feasibility(&mut self, a: &DMatrix<f64>, b: &DVector<f64>, norm: Norm) {
+        let residual = norm.of(&(a * &self.solution - b));
+        self.residual = Some((norm, residual));
+        if residual > 1e-6 {
+            self.warnings.push(format!(
+                "Solution violates A x = b: {} = {:.2e}",
+                norm.format("A x - b"),
+                residual
+            ));
+        }
+        if let Some(min) = self.solution.iter().copied().reduce(f64::min) {
+            if min < -1e-9 {
+                self.warnings
+                    .push(format!("Solution has a negative component ({:.2e})", min));
+            }
+        }
+    }
+
+    /// Turns the report a solver gave at the end of a run on `model` into
+    /// the one to show, the same for the app, the command line, the JSON
+    /// export and the JS API: adds the integer optimum, marks leftover
+    /// artificials infeasible, analyzes sensitivity and runs the cross-check
+    /// at an LP optimum, then moves the objective to the model's sense and
+    /// the solution to the user's variables and evaluates the goals.
+    pub fn complete(&mut self, model: &ValidatedProblem, options: &SolverOptions) {
+        if model.has_integer_variables() {
+            if model.quadratic.is_some() {
+                self.warnings
+                    .push("Integer restrictions are ignored for quadratic objectives.".to_string());
+            } else {
+                self.integer = Some(solve_integer(model, options));
+            }
+        }
+        self.check_artificials(model);
+        if self.status == SolveStatus::Optimal && model.quadratic.is_none() {
+            match sensitivity::analyze(model, &self.solution) {
+                Ok(sensitivity) => self.sensitivity = Some(sensitivity),
+                Err(reason) => self
+                    .warnings
+                    .push(format!("No sensitivity analysis: {}", reason)),
+            }
+            if options.cross_check && model.method.is_interior() {
+                self.cross_check = Some(CrossCheck::run(model, options, self.objective));
+            }
+        }
+        if !model.sense.is_maximize() {
+            self.negate_objective();
+        }
+        if let Some(presolve) = &model.presolve {
+            self.undo_presolve(presolve);
+        }
+        self.evaluate_goals(&model.goals);
+    }
+
+    /// Marks an optimum that still uses Big-M artificial variables
+    /// infeasible: the constraints they stand in for cannot all hold.
+    pub fn check_artificials(&mut self, model: &ValidatedProblem) {
+        let rows = model.active_artificials(&self.solution, 1e-6);
+        if rows.is_empty() {
+            return;
+        }
+        let names: Vec<&str> = rows
+            .iter()
+            .map(|&i| model.constraint_labels[i].as_str())
+            .collect();
+        self.warnings.push(format!(
+            "Artificial variables remain positive for {}: the original constraints cannot all be satisfied",
+            names.join(", ")
+        ));
+        if self.status == SolveStatus::Optimal {
+            self.status = SolveStatus::Infeasible;
+        }
+    }
+
+    /// Maps a report on the presolved model back to the user's variables:
+    /// adds the offset the shift took out of the objective and restores the
+    /// extracted bounds. Call after `negate_objective`.
+    pub fn undo_presolve(&mut self, presolve: &Presolve) {
+        self.objective += presolve.objective_offset;
+        if let Some(check) = &mut self.cross_check {
+            check.map_reference(|objective| objective + presolve.objective_offset);
+        }
+        self.original_solution = Some(presolve.restore(&self.solution));
+        if let Some(integer) = &mut self.integer {
+            for solution in [&mut integer.relaxation, &mut integer.incumbent]
+                .into_iter()
+                .flatten()
+            {
+                solution.objective += presolve.objective_offset;
+                solution.x = presolve.restore(&solution.x);
+            }
+        }
+    }
+
+    /// Evaluates each goal on the user's variables. Call after `undo_presolve`
+    /// so shifted or removed columns are back in place.
+    pub fn evaluate_goals(&mut self, goals: &[Goal]) {
+        let x = self.original_solution.as_ref().unwrap_or(&self.solution);
+        self.goals = goals
+            .iter()
+            .map(|goal| {
+                let n = goal.coefficients.len().min(x.len());
+                GoalAchievement {
+                    weight: goal.weight,
+                    value: goal.coefficients.rows(0, n).dot(&x.rows(0, n)),
+                }
+            })
+            .collect();
+    }
+
+    /// Converts a report computed on the internal maximization form back to a
+    /// minimization problem's sense.
+    pub fn negate_objective(&mut self) {
+        self.objective = -self.objective;
+        if let Some(duals) = &mut self.duals {
+            duals.neg_mut();
+        }
+        if let Some(ray) = &mut self.ray {
+            ray.rate = -ray.rate;
+        }
+        if let Some(check) = &mut self.cross_check {
+            check.map_reference(|objective| -objective);
+        }
+        if let Some(integer) = &mut self.integer {
+            for solution in [&mut integer.relaxation, &mut integer.incumbent]
+                .into_iter()
+                .flatten()
+            {
+                solution.objective = -solution.objective;
+            }
+        }
+    }
+}
+
+/// Milliseconds from an arbitrary origin, usable both in the browser and natively.
+pub fn now_ms() -> f64 {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+        js_sys::Date::now()
+    }
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_a_certificate_reports_infeasible() {
+        let left = InteriorPointError::NotInterior {
+            column: 1,
+            value: -1e-3,
+        };
+        assert_eq!(
+            SolveStatus::from(&left),
+            SolveStatus::Failed("x2 = -1e-3 left the interior".to_string())
+        );
+        let farkas = InteriorPointError::NotFeasible {
+            rows: vec![(0, 1.0)],
+        };
+        assert_eq!(SolveStatus::from(&farkas), SolveStatus::Infeasible);
+    }
+}
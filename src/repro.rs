@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::SolverOptions;
+use crate::model::ValidatedProblem;
+use crate::report::SolveStatus;
+use crate::stopping::StoppingCriteria;
+
+/// Significant digits kept for every exported coefficient.
+pub const REPRO_DIGITS: i32 = 6;
+
+/// The run state a bug report needs besides the model: how far the solver got
+/// and where it stood.
+pub struct ReproIteration<'a> {
+    pub index: usize,
+    pub status: Option<&'a SolveStatus>,
+    pub x: Option<&'a DVector<f64>>,
+    /// Notes the user attached to iterations of the run, by index.
+    pub notes: &'a BTreeMap<usize, String>,
+}
+
+/// Serializes the model, options and last iterate into one line of JSON.
+///
+/// Variable and constraint labels are dropped and every number is rounded to
+/// `REPRO_DIGITS` significant digits, so a report carries no names from the
+/// user's model and stays short enough to paste into an issue. Iteration
+/// notes are the exception: they are kept verbatim, since they were written
+/// to be shared.
+pub fn export_repro(
+    model: &ValidatedProblem,
+    options: &SolverOptions,
+    iteration: &ReproIteration,
+) -> String {
+    let (a, b) = model.original_rows();
+    let n = a.ncols();
+    let mut fields = vec![
+        ("method", string(model.method.label())),
+        (
+            "sense",
+            string(if model.sense.is_maximize() {
+                "max"
+            } else {
+                "min"
+            }),
+        ),
+        ("a", matrix(&a)),
+        ("b", list(b.iter().copied())),
+        ("c", list(model.c.iter().take(n).copied())),
+        (
+            "signs",
+            format!(
+                "[{}]",
+                model
+                    .constraint_signs
+                    .iter()
+                    .map(|sign| string(sign.symbol()))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            ),
+        ),
+        ("initial", list(model.initial.iter().take(n).copied())),
+    ];
+    if let Some(q) = &model.quadratic {
+        fields.push(("q", matrix(&q.view((0, 0), (n, n)).into_owned())));
+    }
+    if model.has_integer_variables() {
+        let integer: Vec<String> = model
+            .integer_variables
+            .iter()
+            .enumerate()
+            .filter(|(_, &flag)| flag)
+            .map(|(j, _)| j.to_string())
+            .collect();
+        fields.push(("integer", format!("[{}]", integer.join(","))));
+    }
+    if !model.goals.is_empty() {
+        // `c` above is the weighted sum; keep the goals it came from.
+        let goals: Vec<String> = model
+            .goals
+            .iter()
+            .map(|goal| {
+                object(&[
+                    ("weight", number(goal.weight)),
+                    ("c", list(goal.coefficients.iter().copied())),
+                ])
+            })
+            .collect();
+        fields.push(("goals", format!("[{}]", goals.join(","))));
+    }
+    if model.presolve.is_some() {
+        // Rows and columns above are what presolve left, not the user's input.
+        fields.push(("presolved", "true".to_string()));
+    }
+    fields.push((
+        "options",
+        object(&[
+            ("alpha", number(model.options.alpha)),
+            ("mu", number(model.options.mu)),
+            ("find_start", model.options.find_start.to_string()),
+            ("step", string(model.options.step_strategy.key())),
+            ("scaling", string(model.options.scaling.key())),
+            ("augmented", model.is_augmented.to_string()),
+            (
+                "log",
+                string(&format!("{:?}", options.log_verbosity).to_lowercase()),
+            ),
+            ("precision", string(options.precision.key())),
+            ("floor", number(options.scale_floor)),
+            ("shift", number(options.normal_shift)),
+            ("stopping", stopping(&options.stopping)),
+            (
+                "schedule",
+                object(&[
+                    ("grow", number(options.schedule.grow)),
+                    ("shrink", number(options.schedule.shrink)),
+                    ("max", number(options.schedule.max)),
+                ]),
+            ),
+            ("norm", string(options.norm.key())),
+        ]),
+    ));
+
+    let mut run = vec![("index", iteration.index.to_string())];
+    run.push((
+        "status",
+        iteration
+            .status
+            .map_or("null".to_string(), |status| string(status.label())),
+    ));
+    if let Some(x) = iteration.x {
+        run.push(("x", list(x.iter().copied())));
+    }
+    fields.push(("iteration", object(&run)));
+    if !iteration.notes.is_empty() {
+        let notes: Vec<String> = iteration
+            .notes
+            .iter()
+            .map(|(index, text)| {
+                object(&[("iteration", index.to_string()), ("text", string(text))])
+            })
+            .collect();
+        fields.push(("notes", format!("[{}]", notes.join(","))));
+    }
+
+    object(&fields)
+}
+
+/// Rounds to `REPRO_DIGITS` significant digits; zero and non-finite values
+/// pass through.
+fn round_significant(val: f64) -> f64 {
+    if val == 0.0 || !val.is_finite() {
+        return val;
+    }
+    let magnitude = val.abs().log10().floor() as i32;
+    let scale = 10f64.powi(REPRO_DIGITS - 1 - magnitude);
+    (val * scale).round() / scale
+}
+
+/// JSON has no infinities or NaN, so those become null.
+pub(crate) fn number(val: f64) -> String {
+    let rounded = round_significant(val);
+    if rounded.is_finite() {
+        // -0 would otherwise print as "-0".
+        format!("{}", rounded + 0.0)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Criteria that are off become null.
+fn stopping(criteria: &StoppingCriteria) -> String {
+    let tolerance = |tol: Option<f64>| tol.map_or("null".to_string(), number);
+    object(&[
+        ("step_norm", tolerance(criteria.step_norm)),
+        ("objective_change", tolerance(criteria.objective_change)),
+        ("duality_gap", tolerance(criteria.duality_gap)),
+        (
+            "max_iterations",
+            criteria
+                .max_iterations
+                .map_or("null".to_string(), |limit| limit.to_string()),
+        ),
+    ])
+}
+
+pub(crate) fn list(values: impl Iterator<Item = f64>) -> String {
+    format!("[{}]", values.map(number).collect::<Vec<_>>().join(","))
+}
+
+fn matrix(mat: &DMatrix<f64>) -> String {
+    format!(
+        "[{}]",
+        mat.row_iter()
+            .map(|row| list(row.iter().copied()))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
+
+pub(crate) fn string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            ch if (ch as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub(crate) fn object(fields: &[(&str, String)]) -> String {
+    format!(
+        "{{{}}}",
+        fields
+            .iter()
+            .map(|(key, value)| format!("{}:{}", string(key), value))
+            .collect::<Vec<_>>()
+            .join(",")
+    )
+}
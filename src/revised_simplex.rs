@@ -0,0 +1,414 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector, Dyn, LU};
+
+use crate::interior::SolverOptions;
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+use crate::simplex::{
+    equilibrated_scales, find_identity_basis, normalize_rows, pivot_tolerance, SimplexError,
+    PIVOT_TOLERANCE,
+};
+use crate::solver::Solver;
+
+/// State of the revised simplex method after a pivot, with the pivot the
+/// pricing and ratio test select next.
+#[derive(Clone, PartialEq)]
+pub struct RevisedSimplexIteration {
+    pub basis: Vec<usize>,
+    pub basic_values: DVector<f64>,
+    pub duals: DVector<f64>,
+    pub reduced_costs: DVector<f64>,
+    pub entering: Option<usize>,
+    pub direction: Option<DVector<f64>>,
+    pub leaving_row: Option<usize>,
+    pub eta_count: usize,
+    pub current_x: DVector<f64>,
+    pub objective: f64,
+}
+
+impl fmt::Debug for RevisedSimplexIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "RevisedSimplexIteration: objective = {}, basis = {:?}, entering = {:?}, leaving row = {:?}, {} eta factors",
+            pretty::entry(self.objective),
+            self.basis,
+            self.entering,
+            self.leaving_row,
+            self.eta_count
+        )?;
+        writeln!(f, "{}", pretty::vector("x_B", &self.basic_values))?;
+        writeln!(f, "{}", pretty::vector("y", &self.duals))?;
+        writeln!(
+            f,
+            "{}",
+            pretty::vector("reduced costs", &self.reduced_costs)
+        )?;
+        if let Some(direction) = &self.direction {
+            writeln!(f, "{}", pretty::vector("direction", direction))?;
+        }
+        write!(f, "{}", pretty::vector("x", &self.current_x))
+    }
+}
+
+/// Consecutive degenerate pivots after which pricing switches from Dantzig's
+/// rule, which can cycle, to Bland's, which cannot; the first pivot that
+/// moves x switches it back.
+pub const BLAND_AFTER_DEGENERATE_PIVOTS: usize = 5;
+
+struct Pricing {
+    duals: DVector<f64>,
+    reduced_costs: DVector<f64>,
+    entering: Option<usize>,
+}
+
+/// Column `row` of the basis replaced by `column`, stored as B_new = B_old E
+/// where E is the identity with column `row` set to B_old^{-1} a_q.
+struct Eta {
+    row: usize,
+    column: DVector<f64>,
+}
+
+/// Revised simplex for maximize c^T x subject to A x = b, x >= 0.
+///
+/// Instead of a full tableau only the basis B is kept, as an LU factorization
+/// of the last refactorized basis followed by product-form eta updates.
+pub struct RevisedSimplexProblem {
+    pub a_matrix: DMatrix<f64>,
+    pub b_vector: DVector<f64>,
+    pub c_vector: DVector<f64>,
+    pub basis: Vec<usize>,
+    pub basic_values: DVector<f64>,
+    pub row_signs: Vec<f64>,
+    pub refactor_interval: usize,
+    /// `equilibrated_scales` of A, which pivots are measured against.
+    pub column_scales: DVector<f64>,
+    pub options: SolverOptions,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
+    /// Pivots in a row that left x where it was.
+    pub degenerate_pivots: usize,
+    lu: LU<f64, Dyn, Dyn>,
+    lu_transpose: LU<f64, Dyn, Dyn>,
+    etas: Vec<Eta>,
+}
+
+impl RevisedSimplexProblem {
+    pub fn new(a: &DMatrix<f64>, b: &DVector<f64>, c: &DVector<f64>) -> Result<Self, SimplexError> {
+        let (a, b, row_signs) = normalize_rows(a, b);
+        let basis = find_identity_basis(&a).ok_or(SimplexError::NoInitialBasis)?;
+
+        let basis_matrix = Self::basis_matrix(&a, &basis);
+        let mut problem = Self {
+            lu: basis_matrix.clone().lu(),
+            lu_transpose: basis_matrix.transpose().lu(),
+            column_scales: equilibrated_scales(&a),
+            a_matrix: a,
+            basic_values: b.clone(),
+            b_vector: b,
+            c_vector: c.clone(),
+            basis,
+            row_signs,
+            refactor_interval: 20,
+            options: SolverOptions::default(),
+            finished: false,
+            degenerate_pivots: 0,
+            etas: vec![],
+        };
+        problem.refactor()?;
+        Ok(problem)
+    }
+
+    fn basis_matrix(a: &DMatrix<f64>, basis: &[usize]) -> DMatrix<f64> {
+        DMatrix::from_columns(&basis.iter().map(|&j| a.column(j)).collect::<Vec<_>>())
+    }
+
+    /// Recomputes the LU factors from scratch and drops accumulated etas.
+    pub fn refactor(&mut self) -> Result<(), SimplexError> {
+        let basis_matrix = Self::basis_matrix(&self.a_matrix, &self.basis);
+        self.lu = basis_matrix.clone().lu();
+        self.lu_transpose = basis_matrix.transpose().lu();
+        self.etas.clear();
+
+        self.basic_values = self
+            .lu
+            .solve(&self.b_vector)
+            .ok_or(SimplexError::SingularBasis)?;
+        Ok(())
+    }
+
+    /// FTRAN: solves B x = rhs.
+    pub fn solve_basis(&self, rhs: &DVector<f64>) -> Option<DVector<f64>> {
+        let mut x = self.lu.solve(rhs)?;
+        for eta in &self.etas {
+            let pivot = x[eta.row] / eta.column[eta.row];
+            for i in 0..x.len() {
+                if i != eta.row {
+                    x[i] -= eta.column[i] * pivot;
+                }
+            }
+            x[eta.row] = pivot;
+        }
+        Some(x)
+    }
+
+    /// BTRAN: solves B^T y = rhs.
+    pub fn solve_basis_transpose(&self, rhs: &DVector<f64>) -> Option<DVector<f64>> {
+        let mut y = rhs.clone();
+        for eta in self.etas.iter().rev() {
+            let others: f64 = (0..y.len())
+                .filter(|&i| i != eta.row)
+                .map(|i| eta.column[i] * y[i])
+                .sum();
+            y[eta.row] = (y[eta.row] - others) / eta.column[eta.row];
+        }
+        self.lu_transpose.solve(&y)
+    }
+
+    pub fn current_x(&self) -> DVector<f64> {
+        let mut x = DVector::zeros(self.a_matrix.ncols());
+        for (i, &j) in self.basis.iter().enumerate() {
+            x[j] = self.basic_values[i];
+        }
+        x
+    }
+
+    /// Report at the current basis, with its simplex multipliers.
+    pub fn report(&self, status: SolveStatus, iterations: usize) -> SolveReport {
+        let mut report = SolveReport::new(status, iterations, self.current_x(), &self.c_vector);
+        report.duals = self.duals();
+        report
+    }
+
+    /// Simplex multipliers mapped back to the user's (unflipped) rows.
+    pub fn duals(&self) -> Option<DVector<f64>> {
+        let y = self.solve_basis_transpose(&self.basic_costs())?;
+        Some(DVector::from_iterator(
+            y.len(),
+            y.iter().zip(self.row_signs.iter()).map(|(yi, s)| yi * s),
+        ))
+    }
+
+    fn basic_costs(&self) -> DVector<f64> {
+        DVector::from_iterator(
+            self.basis.len(),
+            self.basis.iter().map(|&j| self.c_vector[j]),
+        )
+    }
+
+    /// Whether pricing and the ratio test follow Bland's rule: after a run of
+    /// degenerate pivots, in case Dantzig's rule is cycling.
+    pub fn uses_bland(&self) -> bool {
+        self.degenerate_pivots >= BLAND_AFTER_DEGENERATE_PIVOTS
+    }
+
+    /// Dantzig pricing: the largest positive reduced cost enters. Under
+    /// Bland's rule the first positive one does.
+    fn price(&self) -> Result<Pricing, SimplexError> {
+        let y = self
+            .solve_basis_transpose(&self.basic_costs())
+            .ok_or(SimplexError::SingularBasis)?;
+
+        let reduced = &self.c_vector - self.a_matrix.transpose() * &y;
+        let mut entering: Option<(usize, f64)> = None;
+        for (j, &r) in reduced.iter().enumerate() {
+            if self.basis.contains(&j) {
+                continue;
+            }
+            if r > PIVOT_TOLERANCE && entering.is_none_or(|(_, best)| r > best) {
+                entering = Some((j, r));
+                if self.uses_bland() {
+                    break;
+                }
+            }
+        }
+        Ok(Pricing {
+            duals: y,
+            reduced_costs: reduced,
+            entering: entering.map(|(j, _)| j),
+        })
+    }
+
+    /// Minimum ratio test on the direction B^{-1} a_q of entering column `q`.
+    /// Ties go to the first row, or under Bland's rule to the basic variable
+    /// of lowest index.
+    fn ratio_test(&self, q: usize, direction: &DVector<f64>) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+        for (i, &d) in direction.iter().enumerate() {
+            if d > pivot_tolerance(&self.column_scales, q, self.basis[i]) {
+                let ratio = self.basic_values[i] / d;
+                let better = best.is_none_or(|(k, r)| {
+                    ratio < r - PIVOT_TOLERANCE
+                        || (self.uses_bland()
+                            && ratio <= r + PIVOT_TOLERANCE
+                            && self.basis[i] < self.basis[k])
+                });
+                if better {
+                    best = Some((i, ratio));
+                }
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    pub fn snapshot(&self) -> Result<RevisedSimplexIteration, SimplexError> {
+        let Pricing {
+            duals,
+            reduced_costs,
+            entering,
+        } = self.price()?;
+        let direction = match entering {
+            Some(q) => Some(
+                self.solve_basis(&self.a_matrix.column(q).into_owned())
+                    .ok_or(SimplexError::SingularBasis)?,
+            ),
+            None => None,
+        };
+        let leaving_row = entering
+            .zip(direction.as_ref())
+            .and_then(|(q, d)| self.ratio_test(q, d));
+        let current_x = self.current_x();
+
+        Ok(RevisedSimplexIteration {
+            basis: self.basis.clone(),
+            basic_values: self.basic_values.clone(),
+            duals,
+            reduced_costs,
+            entering,
+            direction,
+            leaving_row,
+            eta_count: self.etas.len(),
+            objective: self.c_vector.dot(&current_x),
+            current_x,
+        })
+    }
+}
+
+impl Solver for RevisedSimplexProblem {
+    type Iteration = RevisedSimplexIteration;
+    /// `SimplexError::Optimal` when no column improves.
+    type Error = SimplexError;
+
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        let mut problem = RevisedSimplexProblem::new(&model.a, &model.b, &model.internal_c())
+            .map_err(|e| format!("{:?}", e))?;
+        problem.options = options.clone();
+        Ok(problem)
+    }
+
+    fn step(&mut self) -> Result<RevisedSimplexIteration, SimplexError> {
+        let result = perform_revised_simplex_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.current_x()
+    }
+}
+
+pub fn perform_revised_simplex_iteration(
+    problem: &mut RevisedSimplexProblem,
+) -> Result<RevisedSimplexIteration, SimplexError> {
+    let entering = problem.price()?.entering.ok_or(SimplexError::Optimal)?;
+
+    let direction = problem
+        .solve_basis(&problem.a_matrix.column(entering).into_owned())
+        .ok_or(SimplexError::SingularBasis)?;
+    let leaving_row = problem
+        .ratio_test(entering, &direction)
+        .ok_or(SimplexError::Unbounded(entering))?;
+
+    if problem.options.logs_summary() {
+        tracing::info!(
+            "Revised simplex pivot: x{} enters, x{} leaves (row {})",
+            entering + 1,
+            problem.basis[leaving_row] + 1,
+            leaving_row + 1
+        );
+    }
+
+    let theta = problem.basic_values[leaving_row] / direction[leaving_row];
+    if theta > PIVOT_TOLERANCE {
+        problem.degenerate_pivots = 0;
+    } else {
+        problem.degenerate_pivots += 1;
+        if problem.degenerate_pivots == BLAND_AFTER_DEGENERATE_PIVOTS {
+            tracing::info!(
+                "{} degenerate pivots in a row: switching to Bland's rule",
+                BLAND_AFTER_DEGENERATE_PIVOTS
+            );
+        }
+    }
+    problem.basic_values -= &direction * theta;
+    problem.basic_values[leaving_row] = theta;
+    problem.basis[leaving_row] = entering;
+    problem.etas.push(Eta {
+        row: leaving_row,
+        column: direction,
+    });
+
+    if problem.etas.len() >= problem.refactor_interval {
+        if problem.options.logs_summary() {
+            tracing::info!(
+                "Refactorizing basis after {} eta updates",
+                problem.etas.len()
+            );
+        }
+        problem.refactor()?;
+    }
+
+    if problem.options.logs_full() {
+        logging::dump_vector!("x_B", &problem.basic_values);
+    }
+
+    problem.snapshot()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SolverMethod;
+    use crate::test_models::{assert_optimum, solve, wyndor};
+
+    #[test]
+    fn pivots_to_the_wyndor_vertex() {
+        let report = solve(&wyndor(), SolverMethod::RevisedSimplex);
+        assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-9);
+    }
+
+    #[test]
+    fn bland_rule_breaks_the_beale_cycle() {
+        // Beale's example, on which Dantzig's rule cycles through six
+        // degenerate bases; the optimum is 5/4.
+        let a = DMatrix::from_row_slice(
+            3,
+            7,
+            &[
+                0.25, -8.0, -1.0, 9.0, 1.0, 0.0, 0.0, //
+                0.5, -12.0, -0.5, 3.0, 0.0, 1.0, 0.0, //
+                0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0,
+            ],
+        );
+        let b = DVector::from_vec(vec![0.0, 0.0, 1.0]);
+        let c = DVector::from_vec(vec![0.75, -20.0, 0.5, -6.0, 0.0, 0.0, 0.0]);
+        let mut problem = RevisedSimplexProblem::new(&a, &b, &c).unwrap();
+        let mut pivots = 0;
+        let error = loop {
+            match perform_revised_simplex_iteration(&mut problem) {
+                Ok(_) => pivots += 1,
+                Err(error) => break error,
+            }
+            assert!(pivots < 50, "the revised simplex is cycling");
+        };
+        assert!(matches!(error, SimplexError::Optimal));
+        assert!((c.dot(&problem.current_x()) - 1.25).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,79 @@
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+/// Pages of the app, addressed by the URL fragment (`#/analyze`). Fragments
+/// need no server support, so deep links work on the static deployment under
+/// /ipsolver/, which serves index.html only at its root.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Route {
+    /// Model input, iteration cards and the report.
+    #[default]
+    Solve,
+    /// The solution, duals, activity, ranges, charts and exports of an
+    /// optimal run in one place.
+    Dashboard,
+    /// Sensitivity, plots, exercises, and the race and parametric panels.
+    Analyze,
+    /// Runs finished this session.
+    History,
+    Settings,
+}
+
+impl Route {
+    pub const ALL: [Route; 5] = [
+        Route::Solve,
+        Route::Dashboard,
+        Route::Analyze,
+        Route::History,
+        Route::Settings,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Route::Solve => "Solve",
+            Route::Dashboard => "Dashboard",
+            Route::Analyze => "Analyze",
+            Route::History => "History",
+            Route::Settings => "Settings",
+        }
+    }
+
+    /// The fragment, including `#`, that links to this page.
+    pub fn href(&self) -> &'static str {
+        match self {
+            Route::Solve => "#/",
+            Route::Dashboard => "#/dashboard",
+            Route::Analyze => "#/analyze",
+            Route::History => "#/history",
+            Route::Settings => "#/settings",
+        }
+    }
+
+    /// The page a fragment names; anything unknown, including no fragment,
+    /// is the solver.
+    pub fn from_hash(hash: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|route| route.href() == hash)
+            .unwrap_or_default()
+    }
+}
+
+/// The page the address bar currently points at.
+pub fn current_route() -> Route {
+    web_sys::window()
+        .and_then(|window| window.location().hash().ok())
+        .map_or(Route::Solve, |hash| Route::from_hash(&hash))
+}
+
+/// Calls `on_change` with the new page whenever the fragment changes, from
+/// a tab link or the back and forward buttons. The listener lives as long as
+/// the returned closure.
+pub fn listen_for_changes(on_change: impl Fn(Route) + 'static) -> Option<Closure<dyn Fn()>> {
+    let window = web_sys::window()?;
+    let listener = Closure::<dyn Fn()>::new(move || on_change(current_route()));
+    window
+        .add_event_listener_with_callback("hashchange", listener.as_ref().unchecked_ref())
+        .ok()?;
+    Some(listener)
+}
@@ -0,0 +1,314 @@
+use nalgebra::DVector;
+
+use crate::dual_simplex::{optimal_basis, resolve_rhs, supports_rhs_resolve};
+use crate::interior::SolverOptions;
+use crate::model::{ConstraintSign, ValidatedProblem};
+use crate::parametric::{rebuild, resolve, Parameter};
+use crate::report::SolveStatus;
+
+/// Name of the unmodified model in a comparison.
+pub const BASE_NAME: &str = "Base";
+
+/// A number a scenario can override.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// An objective coefficient or a right-hand side.
+    Coefficient(Parameter),
+    /// x_j ≥ value on column j.
+    LowerBound(usize),
+    /// x_j ≤ value on column j.
+    UpperBound(usize),
+}
+
+impl Target {
+    /// Every target of `model`: objective coefficients, right-hand sides,
+    /// then bounds for each variable.
+    pub fn all(model: &ValidatedProblem) -> Vec<Target> {
+        let columns = model.augmentation.original_variables.min(model.c.len());
+        let rows = model.constraint_labels.len();
+        (0..columns)
+            .map(|j| Target::Coefficient(Parameter::Objective(j)))
+            .chain((0..rows).map(|i| Target::Coefficient(Parameter::Rhs(i))))
+            .chain((0..columns).map(Target::LowerBound))
+            .chain((0..columns).map(Target::UpperBound))
+            .collect()
+    }
+
+    pub fn label(&self, model: &ValidatedProblem) -> String {
+        match *self {
+            Target::Coefficient(parameter) => parameter.label(model),
+            Target::LowerBound(j) => format!("lower bound of {}", variable_name(model, j)),
+            Target::UpperBound(j) => format!("upper bound of {}", variable_name(model, j)),
+        }
+    }
+
+    /// The value in `model`, in the user's variables; `None` for a bound the
+    /// model does not have.
+    pub fn current(&self, model: &ValidatedProblem) -> Option<f64> {
+        let original = |j: usize| {
+            let presolve = model.presolve.as_ref()?;
+            Some((presolve, presolve.kept_columns[j]))
+        };
+        match *self {
+            Target::Coefficient(parameter) => Some(parameter.current(model)),
+            Target::LowerBound(j) => Some(original(j).map_or(0.0, |(p, k)| p.lower[k])),
+            Target::UpperBound(j) => original(j).and_then(|(p, k)| p.upper[k]),
+        }
+    }
+}
+
+/// The user's name for column `j`; presolve labels shifted columns x_j'.
+fn variable_name(model: &ValidatedProblem, j: usize) -> &str {
+    model.variable_labels[j].trim_end_matches('\'')
+}
+
+/// A named variation of the submitted model, such as an optimistic or a
+/// pessimistic case. Targets it does not mention keep the model's values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scenario {
+    pub name: String,
+    pub changes: Vec<(Target, f64)>,
+}
+
+impl Scenario {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            changes: vec![],
+        }
+    }
+
+    /// The override for `target`, if the scenario has one.
+    pub fn value(&self, target: Target) -> Option<f64> {
+        self.changes
+            .iter()
+            .find(|(t, _)| *t == target)
+            .map(|&(_, value)| value)
+    }
+
+    /// Overrides `target`, or drops its override when `value` is `None`.
+    pub fn set(&mut self, target: Target, value: Option<f64>) {
+        self.changes.retain(|(t, _)| *t != target);
+        if let Some(value) = value {
+            self.changes.push((target, value));
+        }
+    }
+
+    /// Whether the scenario changes right-hand sides and nothing else, so
+    /// the base optimal basis stays dual feasible.
+    pub fn only_rhs(&self) -> bool {
+        !self.changes.is_empty()
+            && self
+                .changes
+                .iter()
+                .all(|(target, _)| matches!(target, Target::Coefficient(Parameter::Rhs(_))))
+    }
+
+    /// Drops overrides that name rows or columns `model` does not have,
+    /// so scenarios survive an edit of the model.
+    pub fn retain_valid(&mut self, model: &ValidatedProblem) {
+        let targets = Target::all(model);
+        self.changes.retain(|(target, _)| targets.contains(target));
+    }
+}
+
+/// `model` with the scenario's overrides applied. Bounds become extra rows,
+/// so they need an auto-augmented model.
+pub fn apply(model: &ValidatedProblem, scenario: &Scenario) -> Result<ValidatedProblem, String> {
+    let has_bounds = scenario
+        .changes
+        .iter()
+        .any(|(target, _)| !matches!(target, Target::Coefficient(_)));
+    if model.is_augmented {
+        if has_bounds {
+            return Err("bounds add rows, which needs Auto-Augment".to_string());
+        }
+        let mut model = model.clone();
+        for &(target, value) in &scenario.changes {
+            match target {
+                Target::Coefficient(Parameter::Objective(j)) => model.c[j] = value,
+                Target::Coefficient(Parameter::Rhs(i)) => model.b[i] = value,
+                Target::LowerBound(_) | Target::UpperBound(_) => {}
+            }
+        }
+        return Ok(model);
+    }
+
+    let (mut a, mut b) = model.original_rows();
+    let n = a.ncols();
+    let mut c = model.c.rows(0, n).into_owned();
+    let mut signs = model.constraint_signs.clone();
+    let mut labels = model.constraint_labels.clone();
+    for &(target, value) in &scenario.changes {
+        let (j, sign) = match target {
+            Target::Coefficient(Parameter::Objective(j)) => {
+                c[j] = value;
+                continue;
+            }
+            Target::Coefficient(Parameter::Rhs(i)) => {
+                b[i] = value;
+                continue;
+            }
+            Target::LowerBound(j) => (j, ConstraintSign::GreaterEqual),
+            Target::UpperBound(j) => (j, ConstraintSign::LessEqual),
+        };
+        // Presolved columns are shifted by their lower bound.
+        let shift = model
+            .presolve
+            .as_ref()
+            .map_or(0.0, |presolve| presolve.lower[presolve.kept_columns[j]]);
+        let m = a.nrows();
+        a = a.insert_row(m, 0.0);
+        a[(m, j)] = 1.0;
+        b = b.insert_row(m, value - shift);
+        signs.push(sign);
+        labels.push(format!(
+            "{} {} {}",
+            variable_name(model, j),
+            sign.symbol(),
+            value
+        ));
+    }
+    let mut model = rebuild(model, &a, &b, &c, &signs);
+    model.constraint_labels = labels;
+    Ok(model)
+}
+
+/// How one scenario solved.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioOutcome {
+    pub name: String,
+    pub status: SolveStatus,
+    /// Optimal value in the user's sense; `None` without an optimum.
+    pub objective: Option<f64>,
+    /// Values of the user's variables, with presolve undone.
+    pub solution: Option<DVector<f64>>,
+    /// Dual simplex pivots from the base optimum, for a scenario that only
+    /// changes right-hand sides.
+    pub pivots: Option<usize>,
+}
+
+/// The base model and every scenario, solved with the model's own method.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScenarioComparison {
+    /// Names of the entries of each solution.
+    pub variable_labels: Vec<String>,
+    /// The base model first, then the scenarios in order.
+    pub outcomes: Vec<ScenarioOutcome>,
+}
+
+impl ScenarioComparison {
+    /// Optimal value of the base model, the reference for the differences.
+    pub fn base_objective(&self) -> Option<f64> {
+        self.outcomes.first().and_then(|outcome| outcome.objective)
+    }
+}
+
+/// Solves `model` and each of `scenarios` in turn. Scenarios that only
+/// change right-hand sides of a linear model without presolve are re-solved
+/// by the dual simplex from the base model's optimal basis.
+pub fn solve_all(
+    model: &ValidatedProblem,
+    scenarios: &[Scenario],
+    options: &SolverOptions,
+) -> ScenarioComparison {
+    let base = Scenario::new(BASE_NAME);
+    let basis = (supports_rhs_resolve(model) && scenarios.iter().any(Scenario::only_rhs))
+        .then(|| optimal_basis(model).ok())
+        .flatten();
+    let outcomes = std::iter::once(&base)
+        .chain(scenarios)
+        .map(|scenario| match &basis {
+            Some(basis) if scenario.only_rhs() => resolve_scenario_rhs(model, scenario, basis),
+            _ => solve_scenario(model, scenario, options),
+        })
+        .collect();
+    let variable_labels = match &model.presolve {
+        Some(presolve) => (1..=presolve.lower.len())
+            .map(|j| format!("x{}", j))
+            .collect(),
+        None => {
+            let n = model.augmentation.original_variables.min(model.c.len());
+            model.variable_labels[..n].to_vec()
+        }
+    };
+    ScenarioComparison {
+        variable_labels,
+        outcomes,
+    }
+}
+
+fn solve_scenario(
+    model: &ValidatedProblem,
+    scenario: &Scenario,
+    options: &SolverOptions,
+) -> ScenarioOutcome {
+    let outcome = |status: SolveStatus, objective: Option<f64>, solution| ScenarioOutcome {
+        name: scenario.name.clone(),
+        status,
+        objective,
+        solution,
+        pivots: None,
+    };
+    let modified = match apply(model, scenario) {
+        Ok(modified) => modified,
+        Err(reason) => return outcome(SolveStatus::Failed(reason), None, None),
+    };
+    let resolved = match resolve(modified, options) {
+        Ok(resolved) => resolved,
+        Err(status) => return outcome(status, None, None),
+    };
+
+    let mut objective = resolved.objective;
+    let solution = match &model.presolve {
+        Some(presolve) => {
+            // The bound shift took sum_j c_j l_j out of the objective, with
+            // the scenario's c_j.
+            for &(target, value) in &scenario.changes {
+                if let Target::Coefficient(parameter @ Parameter::Objective(k)) = target {
+                    let j = presolve.kept_columns[k];
+                    objective += (value - parameter.current(model)) * presolve.lower[j];
+                }
+            }
+            presolve.restore(&resolved.x)
+        }
+        None => user_variables(model, &resolved.x),
+    };
+    outcome(resolved.status, Some(objective), Some(solution))
+}
+
+/// A right-hand-side-only scenario, by the dual simplex from `basis`.
+fn resolve_scenario_rhs(
+    model: &ValidatedProblem,
+    scenario: &Scenario,
+    basis: &[usize],
+) -> ScenarioOutcome {
+    let mut rhs = model.original_rows().1;
+    for &(target, value) in &scenario.changes {
+        if let Target::Coefficient(Parameter::Rhs(i)) = target {
+            rhs[i] = value;
+        }
+    }
+    match resolve_rhs(model, basis, &rhs) {
+        Ok(resolved) => ScenarioOutcome {
+            name: scenario.name.clone(),
+            status: SolveStatus::Optimal,
+            objective: Some(resolved.objective),
+            solution: Some(user_variables(model, &resolved.x)),
+            pivots: Some(resolved.pivots),
+        },
+        Err(e) => ScenarioOutcome {
+            name: scenario.name.clone(),
+            status: SolveStatus::from(&e),
+            objective: None,
+            solution: None,
+            pivots: None,
+        },
+    }
+}
+
+/// The original columns of a solver-space x.
+fn user_variables(model: &ValidatedProblem, x: &DVector<f64>) -> DVector<f64> {
+    let n = model.augmentation.original_variables.min(x.len());
+    x.rows(0, n).into_owned()
+}
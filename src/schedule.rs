@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+
+/// Share of its promised gain t c^T dx a step must deliver for α to grow.
+const GOOD_PROGRESS: f64 = 0.9;
+
+/// Least α the schedule shrinks to; a step at this fraction is taken even
+/// when it would be rejected, so an iteration always moves.
+pub const MIN_SCHEDULED_ALPHA: f64 = 1e-3;
+
+/// How the α schedule step rule tunes the fraction α of the ratio-test
+/// step between iterations, instead of keeping the α entered.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlphaSchedule {
+    /// Factor α grows by after a step that delivered the gain it promised.
+    pub grow: f64,
+    /// Factor α shrinks by after a rejected step, before the step is tried
+    /// again.
+    pub shrink: f64,
+    /// Largest α the schedule grows to.
+    pub max: f64,
+}
+
+impl Default for AlphaSchedule {
+    fn default() -> Self {
+        Self {
+            grow: DEFAULT_GROW,
+            shrink: DEFAULT_SHRINK,
+            max: DEFAULT_MAX_ALPHA,
+        }
+    }
+}
+
+/// Default `AlphaSchedule::grow`.
+pub const DEFAULT_GROW: f64 = 1.25;
+
+/// Default `AlphaSchedule::shrink`.
+pub const DEFAULT_SHRINK: f64 = 0.5;
+
+/// Default `AlphaSchedule::max`.
+pub const DEFAULT_MAX_ALPHA: f64 = 0.95;
+
+impl AlphaSchedule {
+    /// α for the next iteration after a step at `alpha` was accepted.
+    /// `progress` is the objective gain measured at the new point over the
+    /// gain t c^T dx the direction promised; α grows when it is at least
+    /// `GOOD_PROGRESS` and stays put otherwise.
+    pub fn after_accepted(&self, alpha: f64, progress: f64) -> f64 {
+        if progress >= GOOD_PROGRESS {
+            (alpha * self.grow).min(self.max)
+        } else {
+            alpha
+        }
+    }
+
+    /// α to retry with after a step at `alpha` was rejected.
+    pub fn after_rejected(&self, alpha: f64) -> f64 {
+        (alpha * self.shrink).max(MIN_SCHEDULED_ALPHA)
+    }
+
+    /// Keeps the factors meaningful whatever was typed: growth at least 1,
+    /// shrinking strictly between 0 and 1 and the cap within (0, 1].
+    pub fn sanitized(self) -> Self {
+        let valid =
+            |val: f64, default: f64, ok: bool| if val.is_finite() && ok { val } else { default };
+        Self {
+            grow: valid(self.grow, DEFAULT_GROW, self.grow >= 1.0),
+            shrink: valid(
+                self.shrink,
+                DEFAULT_SHRINK,
+                self.shrink > 0.0 && self.shrink < 1.0,
+            ),
+            max: valid(
+                self.max,
+                DEFAULT_MAX_ALPHA,
+                self.max > 0.0 && self.max <= 1.0,
+            ),
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use nalgebra::{DMatrix, DVector, Scalar};
+use nalgebra_sparse::{CooMatrix, CsrMatrix};
+use serde::de::{DeserializeOwned, Error};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Version of the JSON the solver state is written in. Documents carry it,
+/// and one from another version is refused rather than misread; bump it
+/// whenever a field is renamed, removed or changes meaning.
+///
+/// The schema is what `InteriorPointProblem`, `InteriorPointIteration`,
+/// `SolverOptions` and, for saved sessions, `ValidatedProblem` derive, with
+/// matrices as arrays of rows, vectors as arrays, step rules and scalings by
+/// the keys course packs use, and every other enum in snake_case:
+///
+/// ```text
+/// { "schema": 2, "kind": "problem",
+///   "data": { "a_matrix": [[1, 0, 1]], "b_vector": [4], "c_vector": [3, 0, 0],
+///             "x_vector": [1, 1, 3], "alpha": 0.5, "strategy": "long",
+///             "options": { "log_verbosity": "summary", ... }, ... } }
+/// ```
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// `value` as a versioned JSON document of the given `kind`.
+pub fn to_json<T: Serialize>(kind: &str, value: &T) -> String {
+    #[derive(Serialize)]
+    struct Document<'a, T> {
+        schema: u32,
+        kind: &'a str,
+        data: &'a T,
+    }
+    serde_json::to_string(&Document {
+        schema: SCHEMA_VERSION,
+        kind,
+        data: value,
+    })
+    .expect("solver state serializes to JSON")
+}
+
+/// Reads a document `to_json` wrote for `kind`.
+pub fn from_json<T: DeserializeOwned>(kind: &str, text: &str) -> Result<T, String> {
+    #[derive(Deserialize)]
+    struct Document {
+        schema: u32,
+        kind: String,
+        data: serde_json::Value,
+    }
+    let document: Document =
+        serde_json::from_str(text).map_err(|e| format!("Invalid JSON: {}", e))?;
+    if document.schema != SCHEMA_VERSION {
+        return Err(format!(
+            "schema version {} is not supported (expected {})",
+            document.schema, SCHEMA_VERSION
+        ));
+    }
+    if document.kind != kind {
+        return Err(format!(
+            "this is a \"{}\" document, not a \"{}\" one",
+            document.kind, kind
+        ));
+    }
+    serde_json::from_value(document.data).map_err(|e| format!("Invalid {}: {}", kind, e))
+}
+
+/// A matrix from its rows, refusing ragged ones.
+pub fn matrix_from_rows(rows: &[Vec<f64>]) -> Result<DMatrix<f64>, String> {
+    let cols = rows.first().map_or(0, Vec::len);
+    match rows.iter().position(|row| row.len() != cols) {
+        Some(i) => Err(format!(
+            "row {} has {} entries, row 1 has {}",
+            i + 1,
+            rows[i].len(),
+            cols
+        )),
+        None => Ok(DMatrix::from_fn(rows.len(), cols, |i, j| rows[i][j])),
+    }
+}
+
+/// `#[serde(with)]` for a dense matrix as an array of rows.
+pub mod matrix {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(mat: &DMatrix<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+        let rows: Vec<Vec<f64>> = mat
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect();
+        rows.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<DMatrix<f64>, D::Error> {
+        let rows = Vec::<Vec<f64>>::deserialize(deserializer)?;
+        matrix_from_rows(&rows).map_err(D::Error::custom)
+    }
+}
+
+/// `#[serde(with)]` for a vector as an array.
+pub mod vector {
+    use super::*;
+
+    pub fn serialize<T, S>(v: &DVector<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Scalar + Serialize,
+        S: Serializer,
+    {
+        v.as_slice().serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<DVector<T>, D::Error>
+    where
+        T: Scalar + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Vec::<T>::deserialize(deserializer).map(DVector::from_vec)
+    }
+}
+
+/// `#[serde(with)]` for an optional vector, `null` when absent.
+pub mod option_vector {
+    use super::*;
+
+    pub fn serialize<T, S>(v: &Option<DVector<T>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Scalar + Serialize,
+        S: Serializer,
+    {
+        v.as_ref().map(|v| v.as_slice()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<DVector<T>>, D::Error>
+    where
+        T: Scalar + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Option::<Vec<T>>::deserialize(deserializer).map(|v| v.map(DVector::from_vec))
+    }
+}
+
+/// `#[serde(with)]` for an optional dense matrix, `null` when absent.
+pub mod option_matrix {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        mat: &Option<DMatrix<f64>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        mat.as_ref()
+            .map(|mat| -> Vec<Vec<f64>> {
+                mat.row_iter()
+                    .map(|row| row.iter().copied().collect())
+                    .collect()
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<DMatrix<f64>>, D::Error> {
+        Option::<Vec<Vec<f64>>>::deserialize(deserializer)?
+            .map(|rows| matrix_from_rows(&rows).map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// `#[serde(with)]` for an optional CSR matrix, as its shape and the
+/// `[row, column, value]` of every stored entry.
+pub mod sparse {
+    use super::*;
+
+    #[derive(Serialize, Deserialize)]
+    struct Triplets {
+        nrows: usize,
+        ncols: usize,
+        entries: Vec<(usize, usize, f64)>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        mat: &Option<CsrMatrix<f64>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        mat.as_ref()
+            .map(|mat| Triplets {
+                nrows: mat.nrows(),
+                ncols: mat.ncols(),
+                entries: mat.triplet_iter().map(|(i, j, &v)| (i, j, v)).collect(),
+            })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<CsrMatrix<f64>>, D::Error> {
+        let Some(triplets) = Option::<Triplets>::deserialize(deserializer)? else {
+            return Ok(None);
+        };
+        let (rows, (cols, values)): (Vec<usize>, (Vec<usize>, Vec<f64>)) = triplets
+            .entries
+            .into_iter()
+            .map(|(i, j, v)| (i, (j, v)))
+            .unzip();
+        CooMatrix::try_from_triplets(triplets.nrows, triplets.ncols, rows, cols, values)
+            .map(|coo| Some(CsrMatrix::from(&coo)))
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::{
+        perform_interior_point_iteration, InteriorPointIteration, InteriorPointProblem,
+        SolverOptions,
+    };
+    use crate::model::ValidatedProblem;
+    use crate::solver::Solver;
+    use crate::test_models::wyndor;
+
+    #[test]
+    fn documents_round_trip_exactly() {
+        let model = wyndor();
+        let text = to_json("model", &model);
+        assert!(text.starts_with(&format!(
+            "{{\"schema\":{},\"kind\":\"model\"",
+            SCHEMA_VERSION
+        )));
+        assert_eq!(
+            from_json::<ValidatedProblem>("model", &text).unwrap(),
+            model
+        );
+
+        // An iterate with a long binary expansion comes back bit for bit.
+        let mut problem = InteriorPointProblem::init(&model, &SolverOptions::default()).unwrap();
+        let iteration = perform_interior_point_iteration(&mut problem).unwrap();
+        let text = to_json("iteration", &iteration);
+        let back: InteriorPointIteration = from_json("iteration", &text).unwrap();
+        assert!(back
+            .current_x
+            .iter()
+            .zip(iteration.current_x.iter())
+            .all(|(p, q)| p.to_bits() == q.to_bits()));
+    }
+
+    #[test]
+    fn refuses_other_versions_and_kinds() {
+        let text = to_json("model", &wyndor());
+        let old = text.replacen(
+            &format!("\"schema\":{}", SCHEMA_VERSION),
+            &format!("\"schema\":{}", SCHEMA_VERSION - 1),
+            1,
+        );
+        assert!(from_json::<ValidatedProblem>("model", &old)
+            .unwrap_err()
+            .contains("not supported"));
+        assert!(from_json::<ValidatedProblem>("session", &text)
+            .unwrap_err()
+            .contains("not a \"session\" one"));
+    }
+
+    #[test]
+    fn ragged_rows_are_not_a_matrix() {
+        let rows = vec![vec![1.0, 2.0], vec![3.0]];
+        assert_eq!(
+            matrix_from_rows(&rows).unwrap_err(),
+            "row 2 has 1 entries, row 1 has 2"
+        );
+    }
+}
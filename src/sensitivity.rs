@@ -0,0 +1,297 @@
+use nalgebra::{DMatrix, DVector};
+use serde::Serialize;
+
+use crate::model::ValidatedProblem;
+
+/// Relative tolerance for zero pivots, zero basic values and the optimality
+/// check on the reduced costs.
+const TOLERANCE: f64 = 1e-7;
+
+/// How far a coefficient can move, one at a time, before the optimal basis
+/// changes. `f64::INFINITY` when it can move without limit.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Range {
+    pub current: f64,
+    pub decrease: f64,
+    pub increase: f64,
+}
+
+impl Range {
+    /// `[current - decrease, current + increase]`.
+    pub fn bounds(&self) -> (f64, f64) {
+        (self.current - self.decrease, self.current + self.increase)
+    }
+
+    fn swapped(self) -> Self {
+        Range {
+            current: self.current,
+            decrease: self.increase,
+            increase: self.decrease,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct VariableSensitivity {
+    /// Value at the optimal vertex.
+    pub value: f64,
+    pub basic: bool,
+    /// c_j - y^T a_j in the user's sense; how much Z changes per unit the
+    /// variable is forced into the solution.
+    pub reduced_cost: f64,
+    /// Range of c_j over which the basis stays optimal.
+    pub objective: Range,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ConstraintSensitivity {
+    /// dZ/db_i in the user's sense.
+    pub shadow_price: f64,
+    /// Range of b_i over which the basis stays feasible.
+    pub rhs: Range,
+}
+
+/// Classic LP sensitivity analysis at an optimal basis, for the user's
+/// original variables and constraints.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SensitivityReport {
+    /// Columns of the optimal basis, in the solver's standard form.
+    pub basis: Vec<usize>,
+    pub variables: Vec<VariableSensitivity>,
+    pub constraints: Vec<ConstraintSensitivity>,
+    /// Some basic variable is zero: shadow prices then hold for one
+    /// direction only and other bases may give other ranges.
+    pub degenerate: bool,
+}
+
+/// Analyzes the optimum `x` of `model`, given in the solver's standard form.
+///
+/// `x` need not be a vertex: the interior methods stop near the optimum, so
+/// the basis is taken from the m largest independent components and checked
+/// for primal and dual feasibility before any range is computed.
+pub fn analyze(model: &ValidatedProblem, x: &DVector<f64>) -> Result<SensitivityReport, String> {
+    let (m, n) = model.a.shape();
+    if x.len() != n {
+        return Err(format!(
+            "the solution has {} entries for {} columns",
+            x.len(),
+            n
+        ));
+    }
+    if model.quadratic.is_some() {
+        return Err("ranging applies to linear objectives only".to_string());
+    }
+
+    let basis = choose_basis(model, x);
+    if basis.len() < m {
+        return Err("the constraint matrix does not have full row rank".to_string());
+    }
+    let b_matrix = DMatrix::from_fn(m, m, |i, k| model.a[(i, basis[k])]);
+    let b_inverse = b_matrix
+        .try_inverse()
+        .ok_or_else(|| "the optimal basis is singular".to_string())?;
+
+    let c = model.internal_c();
+    let x_b = &b_inverse * &model.b;
+    let scale = 1.0 + x_b.amax();
+    if x_b.iter().any(|&val| val < -TOLERANCE * scale) {
+        return Err("no feasible basis matches the solution".to_string());
+    }
+    let c_b = DVector::from_iterator(m, basis.iter().map(|&j| c[j]));
+    let y = b_inverse.transpose() * c_b;
+    let reduced = &c - model.a.transpose() * &y;
+    let cost_scale = 1.0 + c.amax();
+    let nonbasic = |j: &usize| !basis.contains(j);
+    if (0..n)
+        .filter(nonbasic)
+        .any(|j| reduced[j] > TOLERANCE * cost_scale)
+    {
+        return Err("the solution is not at an optimal basis yet".to_string());
+    }
+    let tableau = &b_inverse * &model.a;
+
+    let sign = model.sense.sign();
+    let original = model.augmentation.original_variables.min(n);
+    let variables = (0..original)
+        .map(|j| {
+            let position = basis.iter().position(|&col| col == j);
+            let (decrease, increase) = match position {
+                Some(r) => basic_cost_range(&tableau, &reduced, &basis, r),
+                None => (f64::INFINITY, (-reduced[j]).max(0.0)),
+            };
+            let range = Range {
+                current: model.c[j],
+                decrease,
+                increase,
+            };
+            VariableSensitivity {
+                value: position.map_or(0.0, |r| x_b[r].max(0.0)),
+                basic: position.is_some(),
+                reduced_cost: sign * reduced[j],
+                // Ranges were found on the maximized objective sign * c.
+                objective: if sign > 0.0 { range } else { range.swapped() },
+            }
+        })
+        .collect();
+
+    let constraints = (0..m)
+        .map(|i| {
+            let multiplier = model
+                .augmentation
+                .row_multipliers
+                .get(i)
+                .copied()
+                .unwrap_or(1.0);
+            let (decrease, increase) = rhs_range(&b_inverse.column(i).into_owned(), &x_b);
+            let range = Range {
+                current: multiplier * model.b[i],
+                decrease,
+                increase,
+            };
+            ConstraintSensitivity {
+                shadow_price: sign * multiplier * y[i],
+                // Ranges were found on the row as normalized for b >= 0.
+                rhs: if multiplier > 0.0 {
+                    range
+                } else {
+                    range.swapped()
+                },
+            }
+        })
+        .collect();
+
+    Ok(SensitivityReport {
+        degenerate: x_b.iter().any(|&val| val <= TOLERANCE * scale),
+        basis,
+        variables,
+        constraints,
+    })
+}
+
+/// Greedily picks m linearly independent columns, largest x first. Among
+/// zero components slacks come before decision variables and artificials
+/// last, which is the basis the simplex method ends on in degenerate cases.
+fn choose_basis(model: &ValidatedProblem, x: &DVector<f64>) -> Vec<usize> {
+    let (m, n) = model.a.shape();
+    let augmentation = &model.augmentation;
+    let rank = |j: usize| {
+        if augmentation.slack_columns.iter().any(|&(_, col)| col == j) {
+            0
+        } else if augmentation
+            .artificial_columns
+            .iter()
+            .any(|&(_, col)| col == j)
+        {
+            2
+        } else {
+            1
+        }
+    };
+    let zero = TOLERANCE * (1.0 + x.amax());
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&p, &q| {
+        let (xp, xq) = (x[p].max(0.0), x[q].max(0.0));
+        if xp > zero || xq > zero {
+            xq.total_cmp(&xp)
+        } else {
+            rank(p).cmp(&rank(q))
+        }
+    });
+
+    let mut basis = Vec::with_capacity(m);
+    for j in order {
+        if basis.len() == m {
+            break;
+        }
+        let mut columns = basis.clone();
+        columns.push(j);
+        let candidate = DMatrix::from_fn(m, columns.len(), |i, k| model.a[(i, columns[k])]);
+        if candidate.rank(TOLERANCE) == columns.len() {
+            basis = columns;
+        }
+    }
+    basis
+}
+
+/// Allowable (decrease, increase) of the cost of the variable basic in row
+/// `r`: every nonbasic reduced cost c_k - c_B^T B^-1 a_k must stay <= 0.
+fn basic_cost_range(
+    tableau: &DMatrix<f64>,
+    reduced: &DVector<f64>,
+    basis: &[usize],
+    r: usize,
+) -> (f64, f64) {
+    let mut decrease = f64::INFINITY;
+    let mut increase = f64::INFINITY;
+    for k in (0..tableau.ncols()).filter(|k| !basis.contains(k)) {
+        let alpha = tableau[(r, k)];
+        let d = reduced[k].min(0.0);
+        if alpha > TOLERANCE {
+            decrease = decrease.min(-d / alpha);
+        } else if alpha < -TOLERANCE {
+            increase = increase.min(d / alpha);
+        }
+    }
+    (decrease, increase)
+}
+
+/// Allowable (decrease, increase) of b_i: x_B + delta B^-1 e_i must stay >= 0.
+fn rhs_range(column: &DVector<f64>, x_b: &DVector<f64>) -> (f64, f64) {
+    let mut decrease = f64::INFINITY;
+    let mut increase = f64::INFINITY;
+    for (&direction, &value) in column.iter().zip(x_b.iter()) {
+        let value = value.max(0.0);
+        if direction > TOLERANCE {
+            decrease = decrease.min(value / direction);
+        } else if direction < -TOLERANCE {
+            increase = increase.min(value / -direction);
+        }
+    }
+    (decrease, increase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SolverMethod;
+    use crate::test_models::{solve, wyndor};
+
+    fn assert_range(range: &Range, lower: f64, upper: f64) {
+        let (low, high) = range.bounds();
+        let close = |got: f64, want: f64| got == want || (got - want).abs() < 1e-6;
+        assert!(
+            close(low, lower) && close(high, upper),
+            "[{}, {}] instead of [{}, {}]",
+            low,
+            high,
+            lower,
+            upper
+        );
+    }
+
+    /// The ranges Hillier and Lieberman give for Wyndor Glass.
+    fn assert_wyndor(report: &SensitivityReport) {
+        assert!(!report.degenerate);
+        assert_range(&report.variables[0].objective, 0.0, 7.5);
+        assert_range(&report.variables[1].objective, 2.0, f64::INFINITY);
+        let prices: Vec<f64> = report.constraints.iter().map(|c| c.shadow_price).collect();
+        for (price, want) in prices.iter().zip([0.0, 1.5, 1.0]) {
+            assert!((price - want).abs() < 1e-9, "shadow prices {:?}", prices);
+        }
+        assert_range(&report.constraints[0].rhs, 2.0, f64::INFINITY);
+        assert_range(&report.constraints[1].rhs, 6.0, 18.0);
+        assert_range(&report.constraints[2].rhs, 12.0, 24.0);
+    }
+
+    #[test]
+    fn ranges_wyndor_at_the_simplex_vertex() {
+        let report = solve(&wyndor(), SolverMethod::Simplex);
+        assert_wyndor(report.sensitivity.as_ref().expect("an optimal LP basis"));
+    }
+
+    #[test]
+    fn ranges_wyndor_from_an_interior_point_optimum() {
+        let report = solve(&wyndor(), SolverMethod::AffineScaling);
+        assert_wyndor(&analyze(&wyndor(), &report.solution).unwrap());
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+use crate::interior::{InteriorPointProblem, SolverOptions};
+use crate::model::ValidatedProblem;
+use crate::report::{SolveStatus, Timings};
+use crate::schema;
+use crate::snapshot::CompactInteriorPointIteration;
+
+/// `kind` of a saved session document.
+pub const SESSION_KIND: &str = "session";
+
+/// Suggested name of the downloaded session file.
+pub const SESSION_FILE: &str = "ipsolver-session.json";
+
+/// Everything needed to put the app back where a run stopped: the model,
+/// the settings, every iteration and the notes on them. Written with the
+/// versioned schema, so a session saved by another release is refused
+/// rather than misread.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SessionSnapshot {
+    pub model: ValidatedProblem,
+    pub options: SolverOptions,
+    pub run: SessionRun,
+    #[serde(default)]
+    pub notes: BTreeMap<usize, String>,
+    #[serde(default)]
+    pub timings: Timings,
+    /// How the run ended; `None` while it can still be stepped.
+    pub status: Option<SolveStatus>,
+    /// Improving direction of an unbounded affine-scaling run.
+    #[serde(default, with = "schema::option_vector")]
+    pub ray: Option<DVector<f64>>,
+    /// Explanation shown for a run that could not start or broke down.
+    #[serde(default)]
+    pub error_message: Option<String>,
+}
+
+/// The iterations of a saved run.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionRun {
+    /// Affine scaling is stored as it is: the inspector can edit its
+    /// iterate by hand, so the steps alone would not bring it back.
+    AffineScaling {
+        problem: Box<InteriorPointProblem>,
+        iterations: Vec<CompactInteriorPointIteration>,
+    },
+    /// Every other method is stored as the steps taken after its start,
+    /// which are deterministic, and taken again on restore.
+    Replayed { steps: Vec<RunStep> },
+}
+
+/// One button press of a replayed run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStep {
+    Step,
+    Centering,
+}
+
+impl SessionSnapshot {
+    pub fn to_json(&self) -> String {
+        schema::to_json(SESSION_KIND, self)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, String> {
+        schema::from_json(SESSION_KIND, text)
+    }
+
+    /// Iterations the run had when it was saved.
+    pub fn iteration_count(&self) -> usize {
+        match &self.run {
+            SessionRun::AffineScaling { iterations, .. } => iterations.len(),
+            SessionRun::Replayed { steps } => steps.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interior::perform_interior_point_iteration;
+    use crate::solver::Solver;
+    use crate::test_models::wyndor;
+
+    fn session(run: SessionRun) -> SessionSnapshot {
+        SessionSnapshot {
+            model: wyndor(),
+            options: SolverOptions::default(),
+            run,
+            notes: BTreeMap::from([(1, "x2 enters".to_string())]),
+            timings: Timings::default(),
+            status: None,
+            ray: None,
+            error_message: None,
+        }
+    }
+
+    #[test]
+    fn affine_run_comes_back_with_its_iterations() {
+        let mut problem = InteriorPointProblem::init(&wyndor(), &SolverOptions::default()).unwrap();
+        let iterations: Vec<CompactInteriorPointIteration> = (0..3)
+            .map(|_| (&perform_interior_point_iteration(&mut problem).unwrap()).into())
+            .collect();
+        let saved = session(SessionRun::AffineScaling {
+            problem: Box::new(problem.clone()),
+            iterations: iterations.clone(),
+        });
+
+        let restored = SessionSnapshot::from_json(&saved.to_json()).unwrap();
+        assert_eq!(restored.iteration_count(), 3);
+        assert_eq!(restored.model, saved.model);
+        assert_eq!(restored.notes, saved.notes);
+        let SessionRun::AffineScaling {
+            problem: restored_problem,
+            iterations: restored_iterations,
+        } = restored.run
+        else {
+            panic!("the affine run was restored as a replayed one");
+        };
+        assert_eq!(restored_iterations, iterations);
+        assert_eq!(restored_problem.x_vector, problem.x_vector);
+        assert_eq!(restored_problem.iterations, 3);
+    }
+
+    #[test]
+    fn other_methods_keep_only_their_steps() {
+        let steps = vec![RunStep::Step, RunStep::Centering, RunStep::Step];
+        let saved = session(SessionRun::Replayed {
+            steps: steps.clone(),
+        });
+        let restored = SessionSnapshot::from_json(&saved.to_json()).unwrap();
+        assert_eq!(restored.iteration_count(), 3);
+        assert!(matches!(restored.run, SessionRun::Replayed { steps: s } if s == steps));
+    }
+}
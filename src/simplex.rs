@@ -0,0 +1,392 @@
+use std::fmt;
+
+use nalgebra::{DMatrix, DVector};
+
+use crate::interior::SolverOptions;
+use crate::logging;
+use crate::model::ValidatedProblem;
+use crate::pretty;
+use crate::report::{SolveReport, SolveStatus};
+use crate::revised_simplex::BLAND_AFTER_DEGENERATE_PIVOTS;
+use crate::solver::Solver;
+
+/// Tableau after a pivot, together with the pivot the ratio test selects next
+/// (`None` once the tableau is optimal or the entering column is unbounded).
+#[derive(Clone, PartialEq)]
+pub struct SimplexIteration {
+    pub tableau: DMatrix<f64>,
+    pub basis: Vec<usize>,
+    pub entering: Option<usize>,
+    pub leaving_row: Option<usize>,
+    pub current_x: DVector<f64>,
+    pub objective: f64,
+}
+
+impl fmt::Debug for SimplexIteration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "SimplexIteration: objective = {}, basis = {:?}, entering = {:?}, leaving row = {:?}",
+            pretty::entry(self.objective),
+            self.basis,
+            self.entering,
+            self.leaving_row
+        )?;
+        writeln!(f, "{}", pretty::matrix("Tableau", &self.tableau))?;
+        write!(f, "{}", pretty::vector("x", &self.current_x))
+    }
+}
+
+/// Tableau for maximize c^T x subject to A x = b, x >= 0.
+///
+/// Rows 0..m hold [A | b] in the current basis; the last row holds the reduced
+/// costs z_j - c_j followed by the current objective value.
+pub struct SimplexProblem {
+    pub tableau: DMatrix<f64>,
+    pub basis: Vec<usize>,
+    pub initial_basis: Vec<usize>,
+    pub costs: DVector<f64>,
+    pub row_signs: Vec<f64>,
+    /// `equilibrated_scales` of A, which pivots are measured against.
+    pub column_scales: DVector<f64>,
+    pub options: SolverOptions,
+    /// Set once `Solver::step` returned an error: the run has stopped.
+    pub finished: bool,
+    /// Pivots in a row that left the objective where it was.
+    pub degenerate_pivots: usize,
+}
+
+#[derive(Debug)]
+pub enum SimplexError {
+    Optimal,
+    Unbounded(usize),
+    NoInitialBasis,
+    SingularBasis,
+    /// The dual simplex found basic row `usize` negative with no column
+    /// that can replace it: no x >= 0 satisfies A x = b.
+    Infeasible(usize),
+    /// The dual simplex was started from a basis with a positive reduced
+    /// cost.
+    NotDualFeasible,
+    PivotLimit,
+}
+
+pub(crate) const PIVOT_TOLERANCE: f64 = 1e-9;
+
+/// Size of each column of A once every row is divided by its largest
+/// entry. A slack column gets the reciprocal of its row's scale, so a model
+/// whose rows span many orders of magnitude keeps its columns comparable.
+pub(crate) fn equilibrated_scales(a: &DMatrix<f64>) -> DVector<f64> {
+    let row_scales: Vec<f64> = a
+        .row_iter()
+        .map(|row| match row.amax() {
+            scale if scale > 0.0 => scale,
+            _ => 1.0,
+        })
+        .collect();
+    DVector::from_fn(a.ncols(), |j, _| {
+        match (0..a.nrows()).fold(0.0_f64, |acc, i| acc.max(a[(i, j)].abs() / row_scales[i])) {
+            scale if scale > 0.0 => scale,
+            _ => 1.0,
+        }
+    })
+}
+
+/// Smallest entry of B^{-1} a_j, in the row where column `basic` is basic,
+/// that counts as a pivot. `PIVOT_TOLERANCE` applies to the equilibrated
+/// model, where the entry is scaled by `scales[basic] / scales[column]`; an
+/// absolute cut on the original entries throws real pivots away and reports
+/// a bounded problem with huge coefficients unbounded.
+pub(crate) fn pivot_tolerance(scales: &DVector<f64>, column: usize, basic: usize) -> f64 {
+    PIVOT_TOLERANCE * scales[column] / scales[basic]
+}
+
+/// Flips rows with a negative right-hand side so the slack basis is feasible,
+/// returning the row signs so duals can be mapped back to the original rows.
+pub fn normalize_rows(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+) -> (DMatrix<f64>, DVector<f64>, Vec<f64>) {
+    let signs: Vec<f64> = b
+        .iter()
+        .map(|&bi| if bi < 0.0 { -1.0 } else { 1.0 })
+        .collect();
+    let mut a = a.clone();
+    let mut b = b.clone();
+    for (i, &sign) in signs.iter().enumerate() {
+        if sign < 0.0 {
+            a.row_mut(i).neg_mut();
+            b[i] = -b[i];
+        }
+    }
+    (a, b, signs)
+}
+
+/// Picks, for every row, a distinct column of A equal to that unit vector.
+pub fn find_identity_basis(a: &DMatrix<f64>) -> Option<Vec<usize>> {
+    let (m, n) = a.shape();
+    let mut basis = Vec::with_capacity(m);
+    for i in 0..m {
+        let column = (0..n).find(|&j| {
+            !basis.contains(&j)
+                && (a[(i, j)] - 1.0).abs() < PIVOT_TOLERANCE
+                && (0..m).all(|k| k == i || a[(k, j)].abs() < PIVOT_TOLERANCE)
+        })?;
+        basis.push(column);
+    }
+    Some(basis)
+}
+
+impl SimplexProblem {
+    /// Builds the initial tableau, taking the starting basis from identity
+    /// columns of A (the slack columns added by auto-augmentation).
+    pub fn new(a: &DMatrix<f64>, b: &DVector<f64>, c: &DVector<f64>) -> Result<Self, SimplexError> {
+        let (m, n) = a.shape();
+        let (a, b, row_signs) = normalize_rows(a, b);
+        let basis = find_identity_basis(&a).ok_or(SimplexError::NoInitialBasis)?;
+
+        let mut tableau = DMatrix::zeros(m + 1, n + 1);
+        tableau.view_mut((0, 0), (m, n)).copy_from(&a);
+        tableau.view_mut((0, n), (m, 1)).copy_from(&b);
+
+        for j in 0..n {
+            tableau[(m, j)] = -c[j];
+        }
+        for (i, &j) in basis.iter().enumerate() {
+            let cost = tableau[(m, j)];
+            if cost != 0.0 {
+                for k in 0..=n {
+                    tableau[(m, k)] -= cost * tableau[(i, k)];
+                }
+            }
+        }
+
+        Ok(Self {
+            tableau,
+            initial_basis: basis.clone(),
+            basis,
+            costs: c.clone(),
+            row_signs,
+            column_scales: equilibrated_scales(&a),
+            options: SolverOptions::default(),
+            finished: false,
+            degenerate_pivots: 0,
+        })
+    }
+
+    pub fn num_variables(&self) -> usize {
+        self.tableau.ncols() - 1
+    }
+
+    pub fn current_x(&self) -> DVector<f64> {
+        let n = self.num_variables();
+        let mut x = DVector::zeros(n);
+        for (i, &j) in self.basis.iter().enumerate() {
+            x[j] = self.tableau[(i, n)];
+        }
+        x
+    }
+
+    pub fn objective(&self) -> f64 {
+        let (rows, cols) = self.tableau.shape();
+        self.tableau[(rows - 1, cols - 1)]
+    }
+
+    /// Whether pricing and the ratio test follow Bland's rule, as in the
+    /// revised simplex: after `BLAND_AFTER_DEGENERATE_PIVOTS` degenerate
+    /// pivots in a row, in case Dantzig's rule is cycling.
+    pub fn uses_bland(&self) -> bool {
+        self.degenerate_pivots >= BLAND_AFTER_DEGENERATE_PIVOTS
+    }
+
+    /// Report at the current basis, with its shadow prices.
+    pub fn report(&self, status: SolveStatus, iterations: usize) -> SolveReport {
+        let mut report = SolveReport::new(status, iterations, self.current_x(), &self.costs);
+        report.duals = Some(self.duals());
+        report
+    }
+
+    /// Shadow prices read off the reduced costs of the starting basis columns,
+    /// which were unit vectors in the original (row-sign adjusted) system.
+    pub fn duals(&self) -> DVector<f64> {
+        let m = self.basis.len();
+        DVector::from_iterator(
+            m,
+            self.initial_basis
+                .iter()
+                .zip(self.row_signs.iter())
+                .map(|(&j, &sign)| sign * (self.tableau[(m, j)] + self.costs[j])),
+        )
+    }
+
+    pub fn snapshot(&self) -> SimplexIteration {
+        let entering = choose_entering(self);
+        let leaving_row = entering.and_then(|j| choose_leaving(self, j));
+        SimplexIteration {
+            tableau: self.tableau.clone(),
+            basis: self.basis.clone(),
+            entering,
+            leaving_row,
+            current_x: self.current_x(),
+            objective: self.objective(),
+        }
+    }
+
+    fn pivot(&mut self, row: usize, col: usize) {
+        let (rows, cols) = self.tableau.shape();
+        let pivot = self.tableau[(row, col)];
+        for k in 0..cols {
+            self.tableau[(row, k)] /= pivot;
+        }
+        for i in 0..rows {
+            if i == row {
+                continue;
+            }
+            let factor = self.tableau[(i, col)];
+            if factor != 0.0 {
+                for k in 0..cols {
+                    self.tableau[(i, k)] -= factor * self.tableau[(row, k)];
+                }
+            }
+        }
+        self.basis[row] = col;
+    }
+}
+
+/// Dantzig's rule: the most negative reduced cost enters (lowest index on
+/// ties). Under Bland's rule the first negative one does.
+pub fn choose_entering(problem: &SimplexProblem) -> Option<usize> {
+    let m = problem.basis.len();
+    let mut best: Option<(usize, f64)> = None;
+    for j in 0..problem.num_variables() {
+        let reduced = problem.tableau[(m, j)];
+        if reduced < -PIVOT_TOLERANCE && best.is_none_or(|(_, b)| reduced < b) {
+            best = Some((j, reduced));
+            if problem.uses_bland() {
+                break;
+            }
+        }
+    }
+    best.map(|(j, _)| j)
+}
+
+/// Minimum ratio test over rows with a positive entry in the entering column.
+/// Ties go to the first row, or under Bland's rule to the basic variable of
+/// lowest index.
+pub fn choose_leaving(problem: &SimplexProblem, entering: usize) -> Option<usize> {
+    let n = problem.num_variables();
+    let mut best: Option<(usize, f64)> = None;
+    for (i, &basic) in problem.basis.iter().enumerate() {
+        let coeff = problem.tableau[(i, entering)];
+        if coeff > pivot_tolerance(&problem.column_scales, entering, basic) {
+            let ratio = problem.tableau[(i, n)] / coeff;
+            let better = best.is_none_or(|(k, r)| {
+                ratio < r - PIVOT_TOLERANCE
+                    || (problem.uses_bland()
+                        && ratio <= r + PIVOT_TOLERANCE
+                        && basic < problem.basis[k])
+            });
+            if better {
+                best = Some((i, ratio));
+            }
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+impl Solver for SimplexProblem {
+    type Iteration = SimplexIteration;
+    /// `SimplexError::Optimal` when no column improves.
+    type Error = SimplexError;
+
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String> {
+        let mut problem = SimplexProblem::new(&model.a, &model.b, &model.internal_c())
+            .map_err(|e| format!("{:?}", e))?;
+        problem.options = options.clone();
+        Ok(problem)
+    }
+
+    fn step(&mut self) -> Result<SimplexIteration, SimplexError> {
+        let result = perform_simplex_iteration(self);
+        self.finished = result.is_err();
+        result
+    }
+
+    fn is_done(&self) -> bool {
+        self.finished
+    }
+
+    fn solution(&self) -> DVector<f64> {
+        self.current_x()
+    }
+}
+
+pub fn perform_simplex_iteration(
+    problem: &mut SimplexProblem,
+) -> Result<SimplexIteration, SimplexError> {
+    let entering = choose_entering(problem).ok_or(SimplexError::Optimal)?;
+    let leaving_row = choose_leaving(problem, entering).ok_or(SimplexError::Unbounded(entering))?;
+
+    if problem.options.logs_summary() {
+        tracing::info!(
+            entering,
+            leaving_row,
+            "Simplex pivot: x{} enters, x{} leaves (row {})",
+            entering + 1,
+            problem.basis[leaving_row] + 1,
+            leaving_row + 1
+        );
+    }
+
+    let n = problem.num_variables();
+    let theta = problem.tableau[(leaving_row, n)] / problem.tableau[(leaving_row, entering)];
+    if theta > PIVOT_TOLERANCE {
+        problem.degenerate_pivots = 0;
+    } else {
+        problem.degenerate_pivots += 1;
+        if problem.degenerate_pivots == BLAND_AFTER_DEGENERATE_PIVOTS {
+            tracing::info!(
+                "{} degenerate pivots in a row: switching to Bland's rule",
+                BLAND_AFTER_DEGENERATE_PIVOTS
+            );
+        }
+    }
+    problem.pivot(leaving_row, entering);
+
+    if problem.options.logs_full() {
+        logging::dump_matrix!("Tableau", &problem.tableau);
+    }
+
+    Ok(problem.snapshot())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::builder::InteriorPointProblemBuilder;
+    use crate::model::{ConstraintSign, SolverMethod};
+    use crate::test_models::{assert_optimum, solve, wyndor};
+
+    #[test]
+    fn pivots_to_the_wyndor_vertex() {
+        let report = solve(&wyndor(), SolverMethod::Simplex);
+        assert_optimum(&report, 36.0, &[2.0, 6.0], 1e-9);
+        assert_eq!(report.iterations, 2);
+    }
+
+    #[test]
+    fn bland_rule_breaks_the_beale_cycle() {
+        // Beale's example: Dantzig's rule cycles through six degenerate
+        // bases at Z = 0; the optimum is Z = 1 at (1, 0, 1, 0).
+        let model = InteriorPointProblemBuilder::new()
+            .objective(&[10.0, -57.0, -9.0, -24.0])
+            .constraint(&[0.5, -5.5, -2.5, 9.0], ConstraintSign::LessEqual, 0.0)
+            .constraint(&[0.5, -1.5, -0.5, 1.0], ConstraintSign::LessEqual, 0.0)
+            .constraint(&[1.0, 0.0, 0.0, 0.0], ConstraintSign::LessEqual, 1.0)
+            .maximize()
+            .model()
+            .unwrap();
+        let report = solve(&model, SolverMethod::Simplex);
+        assert_optimum(&report, 1.0, &[1.0, 0.0, 1.0, 0.0], 1e-9);
+        assert!(report.iterations < 50, "{} pivots", report.iterations);
+    }
+}
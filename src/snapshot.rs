@@ -0,0 +1,288 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::interior::{InteriorPointIteration, Regularization, StepChoice};
+use crate::norm::Residuals;
+use crate::schema;
+
+/// Values are stored as integer multiples of this step, which is exactly the
+/// precision shown by the iteration view (`{:.4}`). Only what the cards
+/// display is stored this way; the iterate itself is kept exactly.
+const QUANTUM: f64 = 1e-4;
+
+/// Marker for entries that do not fit in an `i32` after quantization; the real
+/// value is kept at full precision in the overflow list.
+const OVERFLOW: i32 = i32::MIN;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Quantized {
+    values: Vec<i32>,
+    overflow: Vec<(usize, f64)>,
+}
+
+impl Quantized {
+    fn from_iter(values: impl Iterator<Item = f64>) -> Self {
+        let mut out = Quantized {
+            values: Vec::new(),
+            overflow: Vec::new(),
+        };
+        for (idx, val) in values.enumerate() {
+            let ticks = (val / QUANTUM).round_ties_even();
+            if ticks.is_finite() && ticks > i32::MIN as f64 && ticks <= i32::MAX as f64 {
+                out.values.push(ticks as i32);
+            } else {
+                out.values.push(OVERFLOW);
+                out.overflow.push((idx, val));
+            }
+        }
+        out.values.shrink_to_fit();
+        out.overflow.shrink_to_fit();
+        out
+    }
+
+    fn get(&self, idx: usize) -> f64 {
+        match self.values[idx] {
+            OVERFLOW => self
+                .overflow
+                .iter()
+                .find(|(i, _)| *i == idx)
+                .map(|(_, v)| *v)
+                .unwrap_or(f64::NAN),
+            ticks => ticks as f64 * QUANTUM,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+}
+
+fn quantize(val: f64) -> i64 {
+    (val / QUANTUM).round_ties_even() as i64
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CompactLayout {
+    Dense,
+    /// Only the diagonal is stored, e.g. D = diag(x).
+    Diagonal,
+    /// Only the upper triangle is stored, e.g. the projection matrix P.
+    Symmetric,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompactMatrix {
+    rows: usize,
+    cols: usize,
+    layout: CompactLayout,
+    data: Quantized,
+}
+
+impl CompactMatrix {
+    pub fn from_dmatrix(mat: &DMatrix<f64>) -> Self {
+        let (rows, cols) = mat.shape();
+        let layout = Self::detect_layout(mat);
+
+        let data = match layout {
+            CompactLayout::Dense => {
+                Quantized::from_iter((0..rows).flat_map(|r| (0..cols).map(move |c| mat[(r, c)])))
+            }
+            CompactLayout::Diagonal => Quantized::from_iter((0..rows).map(|i| mat[(i, i)])),
+            CompactLayout::Symmetric => {
+                Quantized::from_iter((0..rows).flat_map(|r| (r..cols).map(move |c| mat[(r, c)])))
+            }
+        };
+
+        Self {
+            rows,
+            cols,
+            layout,
+            data,
+        }
+    }
+
+    /// Picks the smallest layout that reproduces every entry at display precision.
+    fn detect_layout(mat: &DMatrix<f64>) -> CompactLayout {
+        let (rows, cols) = mat.shape();
+        if rows != cols || rows < 2 {
+            return CompactLayout::Dense;
+        }
+
+        let mut diagonal = true;
+        let mut symmetric = true;
+        for r in 0..rows {
+            for c in (r + 1)..cols {
+                let upper = mat[(r, c)];
+                let lower = mat[(c, r)];
+                if !upper.is_finite() || !lower.is_finite() {
+                    return CompactLayout::Dense;
+                }
+                if quantize(upper) != 0 || quantize(lower) != 0 {
+                    diagonal = false;
+                }
+                if quantize(upper) != quantize(lower) {
+                    symmetric = false;
+                }
+            }
+        }
+
+        if diagonal {
+            CompactLayout::Diagonal
+        } else if symmetric {
+            CompactLayout::Symmetric
+        } else {
+            CompactLayout::Dense
+        }
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn layout(&self) -> CompactLayout {
+        self.layout
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> f64 {
+        match self.layout {
+            CompactLayout::Dense => self.data.get(r * self.cols + c),
+            CompactLayout::Diagonal => {
+                if r == c {
+                    self.data.get(r)
+                } else {
+                    0.0
+                }
+            }
+            CompactLayout::Symmetric => {
+                let (r, c) = if r <= c { (r, c) } else { (c, r) };
+                // Row r of the packed upper triangle starts after
+                // n + (n - 1) + ... + (n - r + 1) = r*n - r(r-1)/2 entries.
+                let start = r * self.cols - r * r.saturating_sub(1) / 2;
+                self.data.get(start + (c - r))
+            }
+        }
+    }
+
+    pub fn to_dmatrix(&self) -> DMatrix<f64> {
+        DMatrix::from_fn(self.rows, self.cols, |r, c| self.get(r, c))
+    }
+
+    /// Approximate heap footprint of the stored entries, in bytes.
+    pub fn byte_size(&self) -> usize {
+        self.data.len() * std::mem::size_of::<i32>()
+            + self.data.overflow.len() * std::mem::size_of::<(usize, f64)>()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompactVector {
+    data: Quantized,
+}
+
+impl CompactVector {
+    pub fn from_dvector(v: &DVector<f64>) -> Self {
+        Self {
+            data: Quantized::from_iter(v.iter().copied()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.len() == 0
+    }
+
+    pub fn to_dvector(&self) -> DVector<f64> {
+        DVector::from_fn(self.data.len(), |i, _| self.data.get(i))
+    }
+
+    pub fn byte_size(&self) -> usize {
+        self.data.len() * std::mem::size_of::<i32>()
+            + self.data.overflow.len() * std::mem::size_of::<(usize, f64)>()
+    }
+}
+
+/// Stored form of an `InteriorPointIteration`, kept in the history so long
+/// in-browser runs don't hold every full-precision matrix in memory. The
+/// iterate is the exception: an absolute quantum would round coordinates
+/// near the boundary to zero, and the charts, the exports and the replay
+/// check all compute with it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompactInteriorPointIteration {
+    pub d_matrix: CompactMatrix,
+    pub a_tilde_matrix: CompactMatrix,
+    pub c_tilde_vector: CompactVector,
+    pub l_matrix: CompactMatrix,
+    pub condition: f64,
+    pub p_matrix: CompactMatrix,
+    pub cp_vector: CompactVector,
+    #[serde(with = "schema::vector")]
+    pub current_x: DVector<f64>,
+    pub centering: bool,
+    pub step: Option<StepChoice>,
+    pub regularization: Regularization,
+    pub residuals: Residuals,
+    pub duals: CompactVector,
+    pub duality_gap: f64,
+}
+
+impl CompactInteriorPointIteration {
+    pub fn expand(&self) -> InteriorPointIteration {
+        InteriorPointIteration {
+            d_matrix: self.d_matrix.to_dmatrix(),
+            a_tilde_matrix: self.a_tilde_matrix.to_dmatrix(),
+            c_tilde_vector: self.c_tilde_vector.to_dvector(),
+            l_matrix: self.l_matrix.to_dmatrix(),
+            condition: self.condition,
+            p_matrix: self.p_matrix.to_dmatrix(),
+            cp_vector: self.cp_vector.to_dvector(),
+            current_x: self.current_x.clone(),
+            centering: self.centering,
+            step: self.step.clone(),
+            regularization: self.regularization,
+            residuals: self.residuals,
+            duals: self.duals.to_dvector(),
+            duality_gap: self.duality_gap,
+        }
+    }
+
+    pub fn byte_size(&self) -> usize {
+        self.d_matrix.byte_size()
+            + self.a_tilde_matrix.byte_size()
+            + self.c_tilde_vector.byte_size()
+            + self.l_matrix.byte_size()
+            + self.p_matrix.byte_size()
+            + self.cp_vector.byte_size()
+            + self.current_x.len() * std::mem::size_of::<f64>()
+            + self.duals.byte_size()
+    }
+}
+
+impl From<&InteriorPointIteration> for CompactInteriorPointIteration {
+    fn from(iter: &InteriorPointIteration) -> Self {
+        Self {
+            d_matrix: CompactMatrix::from_dmatrix(&iter.d_matrix),
+            a_tilde_matrix: CompactMatrix::from_dmatrix(&iter.a_tilde_matrix),
+            c_tilde_vector: CompactVector::from_dvector(&iter.c_tilde_vector),
+            l_matrix: CompactMatrix::from_dmatrix(&iter.l_matrix),
+            condition: iter.condition,
+            p_matrix: CompactMatrix::from_dmatrix(&iter.p_matrix),
+            cp_vector: CompactVector::from_dvector(&iter.cp_vector),
+            current_x: iter.current_x.clone(),
+            centering: iter.centering,
+            step: iter.step.clone(),
+            regularization: iter.regularization,
+            residuals: iter.residuals,
+            duals: CompactVector::from_dvector(&iter.duals),
+            duality_gap: iter.duality_gap,
+        }
+    }
+}
+
+impl From<InteriorPointIteration> for CompactInteriorPointIteration {
+    fn from(iter: InteriorPointIteration) -> Self {
+        Self::from(&iter)
+    }
+}
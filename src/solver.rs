@@ -0,0 +1,52 @@
+use nalgebra::DVector;
+
+use crate::interior::SolverOptions;
+use crate::model::ValidatedProblem;
+use crate::phase_one::analytic_center;
+
+/// A step-by-step method: set up on a model, advanced one iteration at a
+/// time until a step reports why it stopped. The app, races and exports
+/// drive every method through this, so a new one plugs in by implementing
+/// it next to its problem type.
+pub trait Solver: Sized {
+    /// What one step records, for the iteration cards.
+    type Iteration;
+    /// Why the method stopped: convergence, a certificate or a breakdown.
+    type Error;
+
+    /// Sets the method up on `model` the way the step-by-step view does.
+    fn init(model: &ValidatedProblem, options: &SolverOptions) -> Result<Self, String>;
+
+    /// Takes one iteration. `Err` means the run is over and `is_done` turns
+    /// true; whether that is an optimum depends on the error.
+    fn step(&mut self) -> Result<Self::Iteration, Self::Error>;
+
+    fn is_done(&self) -> bool;
+
+    /// Current iterate in the solver space.
+    fn solution(&self) -> DVector<f64>;
+}
+
+/// Where the primal interior methods start: the analytic center when the
+/// model asks for Phase 1, the user's point otherwise.
+pub fn starting_point(
+    model: &ValidatedProblem,
+    options: &SolverOptions,
+) -> Result<DVector<f64>, String> {
+    if model.options.find_start {
+        analytic_center(&model.a, &model.b, model.options.alpha, 500, options)
+            .map(|center| center.x)
+            .map_err(|e| format!("Phase 1 failed: {}", e))
+    } else {
+        Ok(user_point(model))
+    }
+}
+
+/// The initial point from the form, 1 where it has no entry.
+pub fn user_point(model: &ValidatedProblem) -> DVector<f64> {
+    let n = model.a.ncols();
+    DVector::from_iterator(
+        n,
+        (0..n).map(|j| model.initial.get(j).copied().unwrap_or(1.0)),
+    )
+}
@@ -0,0 +1,106 @@
+use nalgebra::{Cholesky, DMatrix, DVector, Dyn};
+use nalgebra_sparse::CsrMatrix;
+
+use crate::interior::{normal_condition, InteriorPointError};
+
+/// Share of nonzero entries of A at or below which affine scaling works on a
+/// CSR copy of A instead of forming the dense A D and n×n projection P.
+pub const SPARSE_DENSITY: f64 = 0.2;
+
+/// Models with fewer columns stay dense: P fits easily and the iteration
+/// cards show D, A~ and P, which the sparse path never forms.
+pub const SPARSE_MIN_COLUMNS: usize = 50;
+
+/// Shift added to the diagonal of A~ A~^T, relative to its largest entry,
+/// that only guards against rounding. When it is not enough, the larger
+/// shift of the solver options, for dependent rows, is tried.
+const ROUNDING_SHIFT: f64 = 1e-15;
+
+/// Projections applied in a row: each one removes what the shifted factor
+/// left of A~ v, down to rounding once x is near a degenerate vertex.
+const REFINEMENT_STEPS: usize = 3;
+
+/// Fraction of the entries of `a` that are nonzero.
+pub fn density(a: &DMatrix<f64>) -> f64 {
+    if a.is_empty() {
+        return 1.0;
+    }
+    a.iter().filter(|&&val| val != 0.0).count() as f64 / a.len() as f64
+}
+
+pub fn prefers_sparse(a: &DMatrix<f64>) -> bool {
+    a.ncols() >= SPARSE_MIN_COLUMNS && density(a) <= SPARSE_DENSITY
+}
+
+/// Cholesky factor of A~ A~^T for A~ = A diag(scale), kept with A~ so the
+/// projection P = I - A~^T (A~ A~^T)^{-1} A~ can be applied without forming
+/// it. A~ keeps the sparsity of A and only the m×m normal matrix is dense.
+pub struct NormalFactor {
+    a_tilde: CsrMatrix<f64>,
+    a_tilde_t: CsrMatrix<f64>,
+    factor: Cholesky<f64, Dyn>,
+    /// Relative diagonal shift the factorization needed.
+    shift: f64,
+}
+
+impl NormalFactor {
+    pub fn new(
+        a: &CsrMatrix<f64>,
+        scale: &DVector<f64>,
+        shift: f64,
+    ) -> Result<Self, InteriorPointError> {
+        let mut a_tilde = a.clone();
+        for (_, j, val) in a_tilde.triplet_iter_mut() {
+            *val *= scale[j];
+        }
+        let a_tilde_t = a_tilde.transpose();
+        let m = a_tilde.nrows();
+        let normal = DMatrix::from(&(&a_tilde * &a_tilde_t));
+        let largest = normal.diagonal().max().max(1.0);
+        let (factor, shift) = [ROUNDING_SHIFT, shift.max(ROUNDING_SHIFT)]
+            .into_iter()
+            .find_map(|shift| {
+                (&normal + DMatrix::identity(m, m) * (shift * largest))
+                    .cholesky()
+                    .map(|factor| (factor, shift))
+            })
+            .ok_or_else(|| {
+                InteriorPointError::singular(
+                    "Cannot factor (A_tilde * A_tilde^T) for the sparse projection",
+                )
+            })?;
+        Ok(Self {
+            a_tilde,
+            a_tilde_t,
+            factor,
+            shift,
+        })
+    }
+
+    /// P v: y solves (A~ A~^T) y = A~ v and P v = v - A~^T y. The normal
+    /// equations square the conditioning of A~, which gets poor near a
+    /// degenerate vertex, so the projection is repeated on its own result.
+    pub fn project(&self, v: &DVector<f64>) -> DVector<f64> {
+        let mut projected = v.clone();
+        for _ in 0..REFINEMENT_STEPS {
+            let y = self.factor.solve(&(&self.a_tilde * &projected));
+            projected -= &self.a_tilde_t * &y;
+        }
+        projected
+    }
+
+    /// y with (A~ A~^T) y = A~ v, the multipliers of the projection.
+    pub fn multipliers(&self, v: &DVector<f64>) -> DVector<f64> {
+        self.factor.solve(&(&self.a_tilde * v))
+    }
+
+    pub fn shift(&self) -> f64 {
+        self.shift
+    }
+
+    /// Condition number of the factored matrix, including the diagonal shift
+    /// that made it factor.
+    pub fn condition(&self) -> f64 {
+        normal_condition(&self.factor.l())
+    }
+}
@@ -0,0 +1,131 @@
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+use crate::norm::Norm;
+
+/// When affine scaling stops. Each tolerance is relative to 1 + |c^T x|
+/// or 1 + ‖x‖; a criterion left `None` is not checked.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StoppingCriteria {
+    /// ‖Δx‖ of the next step, relative to 1 + ‖x‖, in the norm of
+    /// `SolverOptions::norm`.
+    pub step_norm: Option<f64>,
+    /// Objective gain of the next step, relative to 1 + |c^T x|. Long steps
+    /// reach 1e-9 in a few dozen iterations; without a bound they keep
+    /// pushing the nonbasic variables towards the floor of D.
+    pub objective_change: Option<f64>,
+    /// Gap between c^T x and the bound of the dual estimate, relative to
+    /// 1 + |c^T x|, once the estimate is dual feasible.
+    pub duality_gap: Option<f64>,
+    /// Steps taken before giving up.
+    pub max_iterations: Option<usize>,
+}
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        Self {
+            step_norm: None,
+            objective_change: Some(DEFAULT_OBJECTIVE_CHANGE),
+            duality_gap: None,
+            max_iterations: None,
+        }
+    }
+}
+
+/// Default `StoppingCriteria::objective_change`.
+pub const DEFAULT_OBJECTIVE_CHANGE: f64 = 1e-9;
+
+/// The criterion that ended a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    StepNorm,
+    ObjectiveChange,
+    DualityGap,
+    IterationLimit,
+    /// No component of the direction decreases, so there is no boundary
+    /// to step towards: x is already optimal or the direction is zero.
+    NoDecrease,
+}
+
+impl StopReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            StopReason::StepNorm => "step norm below tolerance",
+            StopReason::ObjectiveChange => "objective change below tolerance",
+            StopReason::DualityGap => "duality gap below tolerance",
+            StopReason::IterationLimit => "iteration limit",
+            StopReason::NoDecrease => "no component of the direction decreases",
+        }
+    }
+}
+
+/// What the next affine-scaling step would do, measured before it is
+/// taken.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StepMeasures {
+    /// ‖t dx‖ / (1 + ‖x‖).
+    pub step_norm: f64,
+    /// t |P c~|^2 / (1 + |c^T x|).
+    pub objective_change: f64,
+    /// (b^T y - c^T x) / (1 + |c^T x|) for the dual estimate y, or `None`
+    /// while y is not dual feasible and the difference bounds nothing.
+    pub duality_gap: Option<f64>,
+}
+
+impl StepMeasures {
+    /// `x` and `c` are the current iterate and internal (maximized) cost,
+    /// `dx` the direction in x, `cp` = P c~ and `t` the step length.
+    ///
+    /// With D = diag(x), P c~ = X (c - A^T y) = X s for the dual estimate y,
+    /// so the gap b^T y - c^T x = -x^T s is -sum(P c~), and y is feasible
+    /// for the maximization once s <= 0, i.e. every entry of P c~ <= 0.
+    pub fn measure(
+        norm: Norm,
+        x: &DVector<f64>,
+        c: &DVector<f64>,
+        dx: &DVector<f64>,
+        cp: &DVector<f64>,
+        t: f64,
+    ) -> Self {
+        let objective_scale = 1.0 + c.dot(x).abs();
+        let step_norm = t * norm.of(dx) / (1.0 + norm.of(x));
+        let objective_change = t * cp.norm_squared() / objective_scale;
+        // Entries of X s above this are rounding, not infeasibility.
+        let slack = 1e-12 * objective_scale;
+        let duality_gap = cp
+            .iter()
+            .all(|&v| v <= slack)
+            .then(|| -cp.sum() / objective_scale);
+        Self {
+            step_norm,
+            objective_change,
+            duality_gap,
+        }
+    }
+}
+
+impl StoppingCriteria {
+    /// The first criterion `measures` meets, in the order step norm,
+    /// objective change, duality gap.
+    pub fn met_by(&self, measures: &StepMeasures) -> Option<StopReason> {
+        let below = |tolerance: Option<f64>, value: Option<f64>| {
+            tolerance.zip(value).is_some_and(|(tol, val)| val <= tol)
+        };
+        if below(self.step_norm, Some(measures.step_norm)) {
+            Some(StopReason::StepNorm)
+        } else if below(self.objective_change, Some(measures.objective_change)) {
+            Some(StopReason::ObjectiveChange)
+        } else if below(self.duality_gap, measures.duality_gap) {
+            Some(StopReason::DualityGap)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `iterations` steps use up the iteration budget.
+    pub fn out_of_iterations(&self, iterations: usize) -> bool {
+        self.max_iterations.is_some_and(|limit| iterations >= limit)
+    }
+}
@@ -0,0 +1,170 @@
+use nalgebra::{DMatrix, DVector};
+
+use crate::model::ConstraintSign;
+
+/// Variables above which symmetry is not searched for: every pair is tried
+/// against every row, and larger models are not worked by hand anyway.
+pub const SYMMETRY_VARIABLE_LIMIT: usize = 20;
+
+/// Constraints above which symmetry is not searched for.
+pub const SYMMETRY_CONSTRAINT_LIMIT: usize = 40;
+
+/// Entries closer than this, relative to 1 + the larger, count as equal.
+const EQUAL_TOLERANCE: f64 = 1e-9;
+
+/// Variables and constraints that swapping variables maps onto each other.
+///
+/// Only single swaps x_j ↔ x_k are tried, together with the row permutation
+/// that restores the model. The swaps found within a group generate every
+/// permutation of it, so any optimum can be sorted within each group and
+/// the ordering rows of `breaking_rows` cut no optimal value off.
+/// Symmetries that only work moving several pairs at once, like swapping
+/// two rows of an assignment, are missed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Symmetry {
+    /// Groups of interchangeable variables, each ascending, by first member.
+    pub variable_groups: Vec<Vec<usize>>,
+    /// Groups of constraints the swaps move onto each other.
+    pub constraint_groups: Vec<Vec<usize>>,
+}
+
+/// The symmetry of max/min c^T x over `a x (signs) b`, x >= 0, or `None`
+/// when no two variables can be swapped or the model is too large to
+/// search. Integer variables only swap with integer variables.
+pub fn detect(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    c: &DVector<f64>,
+    signs: &[ConstraintSign],
+    integer: &[bool],
+) -> Option<Symmetry> {
+    let (m, n) = a.shape();
+    if n > SYMMETRY_VARIABLE_LIMIT || m > SYMMETRY_CONSTRAINT_LIMIT {
+        return None;
+    }
+    let is_integer = |j: usize| integer.get(j).copied().unwrap_or(false);
+    let mut variables = Partition::new(n);
+    let mut constraints = Partition::new(m);
+    for j in 0..n {
+        for k in j + 1..n {
+            if !equal(c[j], c[k]) || is_integer(j) != is_integer(k) {
+                continue;
+            }
+            if let Some(rows) = row_permutation(a, b, signs, j, k) {
+                variables.join(j, k);
+                for (i, &l) in rows.iter().enumerate() {
+                    constraints.join(i, l);
+                }
+            }
+        }
+    }
+    let variable_groups = variables.groups();
+    if variable_groups.is_empty() {
+        return None;
+    }
+    Some(Symmetry {
+        variable_groups,
+        constraint_groups: constraints.groups(),
+    })
+}
+
+impl Symmetry {
+    /// x_j1 >= x_j2 >= ... within each variable group, as (coefficients,
+    /// sign, rhs) rows over `variables` columns.
+    pub fn breaking_rows(&self, variables: usize) -> Vec<(Vec<f64>, ConstraintSign, f64)> {
+        self.variable_groups
+            .iter()
+            .flat_map(|group| group.windows(2))
+            .map(|pair| {
+                let mut row = vec![0.0; variables];
+                row[pair[0]] = 1.0;
+                row[pair[1]] = -1.0;
+                (row, ConstraintSign::GreaterEqual, 0.0)
+            })
+            .collect()
+    }
+}
+
+/// Where each row goes when columns `j` and `k` swap: row i becomes row
+/// `rows[i]`, which has the same sign and right-hand side and the swapped
+/// coefficients. `None` when some row has no such partner.
+fn row_permutation(
+    a: &DMatrix<f64>,
+    b: &DVector<f64>,
+    signs: &[ConstraintSign],
+    j: usize,
+    k: usize,
+) -> Option<Vec<usize>> {
+    let (m, n) = a.shape();
+    let swapped = |i: usize, col: usize| {
+        let col = if col == j {
+            k
+        } else if col == k {
+            j
+        } else {
+            col
+        };
+        a[(i, col)]
+    };
+    let mut taken = vec![false; m];
+    let mut rows = Vec::with_capacity(m);
+    for i in 0..m {
+        let partner = (0..m).find(|&l| {
+            !taken[l]
+                && signs[l] == signs[i]
+                && equal(b[l], b[i])
+                && (0..n).all(|col| equal(a[(l, col)], swapped(i, col)))
+        })?;
+        taken[partner] = true;
+        rows.push(partner);
+    }
+    Some(rows)
+}
+
+fn equal(p: f64, q: f64) -> bool {
+    (p - q).abs() <= EQUAL_TOLERANCE * (1.0 + p.abs().max(q.abs()))
+}
+
+/// Union-find over 0..n.
+struct Partition {
+    parent: Vec<usize>,
+}
+
+impl Partition {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn root(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    fn join(&mut self, i: usize, j: usize) {
+        let (i, j) = (self.root(i), self.root(j));
+        self.parent[i.max(j)] = i.min(j);
+    }
+
+    /// Classes with more than one member, each ascending, by first member.
+    fn groups(&mut self) -> Vec<Vec<usize>> {
+        let mut groups: Vec<Vec<usize>> = vec![];
+        let mut index: Vec<Option<usize>> = vec![None; self.parent.len()];
+        for i in 0..self.parent.len() {
+            let root = self.root(i);
+            match index[root] {
+                Some(g) => groups[g].push(i),
+                None => {
+                    index[root] = Some(groups.len());
+                    groups.push(vec![i]);
+                }
+            }
+        }
+        groups.retain(|group| group.len() > 1);
+        groups
+    }
+}
@@ -0,0 +1,102 @@
+//! Small LPs with known optima, and a run of any method on them the way the
+//! command-line front end does it, for the solver tests.
+
+use crate::builder::InteriorPointProblemBuilder;
+use crate::interior::{LogVerbosity, SolverOptions, SOLVE_ITERATION_LIMIT};
+use crate::model::{ConstraintSign, Sense, SolverMethod, ValidatedProblem};
+use crate::race::RaceSolver;
+use crate::reformulation::LinearModel;
+use crate::report::{SolveReport, SolveStatus};
+
+/// Hillier and Lieberman's Wyndor Glass: max 3 x1 + 5 x2 subject to x1 <= 4,
+/// 2 x2 <= 12 and 3 x1 + 2 x2 <= 18. Optimal at (2, 6) with Z = 36, where
+/// the shadow prices are (0, 1.5, 1).
+pub fn wyndor() -> ValidatedProblem {
+    InteriorPointProblemBuilder::new()
+        .objective(&[3.0, 5.0])
+        .constraint(&[1.0, 0.0], ConstraintSign::LessEqual, 4.0)
+        .constraint(&[0.0, 2.0], ConstraintSign::LessEqual, 12.0)
+        .constraint(&[3.0, 2.0], ConstraintSign::LessEqual, 18.0)
+        .maximize()
+        .model()
+        .expect("Wyndor is a valid model")
+}
+
+/// min 2 x1 + 3 x2 subject to x1 + x2 >= 4 and x1 + 3 x2 >= 6, which needs
+/// Big-M artificials. Optimal at (3, 1) with Z = 9.
+pub fn covering() -> ValidatedProblem {
+    InteriorPointProblemBuilder::new()
+        .objective(&[2.0, 3.0])
+        .constraint(&[1.0, 1.0], ConstraintSign::GreaterEqual, 4.0)
+        .constraint(&[1.0, 3.0], ConstraintSign::GreaterEqual, 6.0)
+        .minimize()
+        .model()
+        .expect("the covering model is valid")
+}
+
+/// The rows and objective of `linear` as a model, optimized in `sense`.
+pub fn from_linear(linear: &LinearModel, sense: Sense) -> ValidatedProblem {
+    let builder = InteriorPointProblemBuilder::new().objective(linear.c.as_slice());
+    let builder = match sense {
+        Sense::Maximize => builder.maximize(),
+        Sense::Minimize => builder.minimize(),
+    };
+    (0..linear.a.nrows())
+        .fold(builder, |builder, i| {
+            let row: Vec<f64> = linear.a.row(i).iter().copied().collect();
+            builder.constraint(&row, linear.signs[i], linear.b[i])
+        })
+        .model()
+        .expect("the linear model is valid")
+}
+
+/// Runs `method` on `model` to the end and completes the report.
+pub fn solve(model: &ValidatedProblem, method: SolverMethod) -> SolveReport {
+    let mut model = model.clone();
+    model.method = method;
+    model.options.find_start &= method.is_interior();
+    let options = SolverOptions {
+        log_verbosity: LogVerbosity::Off,
+        ..SolverOptions::default()
+    };
+    let mut solver = RaceSolver::new(method, &model, &options).expect("the solver sets up");
+    let mut steps = 0;
+    let status = loop {
+        if steps == SOLVE_ITERATION_LIMIT {
+            break SolveStatus::IterationLimit;
+        }
+        if let Err(status) = solver.step() {
+            break status;
+        }
+        steps += 1;
+    };
+    let mut report = solver.report(status, steps);
+    report.complete(&model, &options);
+    report
+}
+
+/// Asserts `report` is optimal with objective `objective` at the original
+/// variables `x`, each within `tolerance` relative to 1 + its size.
+pub fn assert_optimum(report: &SolveReport, objective: f64, x: &[f64], tolerance: f64) {
+    assert_eq!(report.status, SolveStatus::Optimal, "{:?}", report.warnings);
+    let close = |got: f64, want: f64| (got - want).abs() <= tolerance * (1.0 + want.abs());
+    assert!(
+        close(report.objective, objective),
+        "objective {} instead of {}",
+        report.objective,
+        objective
+    );
+    let solution = report
+        .original_solution
+        .as_ref()
+        .unwrap_or(&report.solution);
+    for (j, &want) in x.iter().enumerate() {
+        assert!(
+            close(solution[j], want),
+            "x{} = {} instead of {}",
+            j + 1,
+            solution[j],
+            want
+        );
+    }
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+
+use crate::model::SolverMethod;
+
+/// Tolerance the interior methods stop at: n * mu for the barrier method and
+/// the largest entry of -P c~ for affine scaling.
+pub const STOPPING_TOLERANCE: f64 = 1e-8;
+
+/// What complexity theory predicts for a run, shown next to the actual count
+/// once the solver stops.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct IterationBound {
+    /// The bound as a formula, e.g. "√n · ln(n μ₀ / ε) / 0.4".
+    pub formula: String,
+    /// The formula evaluated for this problem.
+    pub predicted: f64,
+    /// Iterations the bound should be compared against, if the method's own
+    /// parameters give a sharper figure than the textbook one.
+    pub expected: Option<f64>,
+    pub note: &'static str,
+}
+
+/// Evaluates the classical iteration bound for `method` on an m x n problem.
+///
+/// `mu` and `mu_decrease` are the barrier method's starting parameter and
+/// reduction factor; the other methods ignore them.
+pub fn iteration_bound(
+    method: SolverMethod,
+    m: usize,
+    n: usize,
+    mu: f64,
+    mu_decrease: f64,
+) -> IterationBound {
+    let nf = n as f64;
+    let eps = STOPPING_TOLERANCE;
+    match method {
+        SolverMethod::LogBarrier | SolverMethod::QuadraticBarrier => {
+            // Short-step path following shrinks mu by 1 - 0.4/sqrt(n) per step
+            // and needs ln(n mu0 / eps) / -ln(sigma) <= sqrt(n) ln(n mu0 / eps) / 0.4.
+            let log_gap = (nf * mu / eps).ln().max(0.0);
+            IterationBound {
+                formula: "√n · ln(n μ₀ / ε) / 0.4".to_string(),
+                predicted: (nf.sqrt() * log_gap / 0.4).ceil(),
+                expected: (mu_decrease > 0.0 && mu_decrease < 1.0)
+                    .then(|| (log_gap / -mu_decrease.ln()).ceil()),
+                note: "Short-step bound; the expected count uses this run's μ reduction factor, which is a long step and so carries no √n guarantee.",
+            }
+        }
+        SolverMethod::SelfDual => {
+            // The embedding of an n-column model has n + 1 complementary
+            // pairs and starts at mu = 1.
+            let pairs = nf + 1.0;
+            IterationBound {
+                formula: "√(n+1) · ln((n+1) / ε)".to_string(),
+                predicted: (pairs.sqrt() * (pairs / eps).ln()).ceil(),
+                expected: None,
+                note: "Bound for short-step path following on the self-dual embedding; the long steps taken here usually need far fewer.",
+            }
+        }
+        SolverMethod::AffineScaling => IterationBound {
+            formula: "n · ln(1 / ε) / δ, δ = 1/4".to_string(),
+            predicted: (nf * (1.0 / eps).ln() / 0.25).ceil(),
+            expected: None,
+            note: "Karmarkar's potential-reduction bound. Affine scaling itself has no polynomial bound, so treat this as a reference point rather than a guarantee.",
+        },
+        SolverMethod::Simplex | SolverMethod::RevisedSimplex => IterationBound {
+            formula: "C(n, m) bases worst case; ~ 2m to 3m pivots in practice".to_string(),
+            predicted: binomial(n, m),
+            expected: Some(3.0 * m as f64),
+            note: "Exponential in the worst case (Klee–Minty), yet usually a small multiple of the row count.",
+        },
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
@@ -0,0 +1,274 @@
+use nalgebra::{DMatrix, DVector};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    big_m_form, default_variable_labels, Augmentation, ConstraintSign, Sense, SolverMethod,
+    StandardForm, StartOptions, ValidatedProblem,
+};
+
+/// Ship from sources with `supply` to destinations with `demand` at minimum
+/// total cost, where `costs[(i, j)]` is the unit cost from source i to j.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransportationProblem {
+    pub supply: Vec<f64>,
+    pub demand: Vec<f64>,
+    pub costs: DMatrix<f64>,
+}
+
+/// The balanced table the LP was built from, kept on the model so results
+/// can be shown as a flow matrix.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransportationLayout {
+    pub supply: Vec<f64>,
+    pub demand: Vec<f64>,
+    #[serde(with = "crate::schema::matrix")]
+    pub costs: DMatrix<f64>,
+    /// A zero-cost source was added to cover excess demand.
+    pub dummy_source: bool,
+    /// A zero-cost destination was added to absorb excess supply.
+    pub dummy_destination: bool,
+}
+
+impl TransportationLayout {
+    pub fn sources(&self) -> usize {
+        self.supply.len()
+    }
+
+    pub fn destinations(&self) -> usize {
+        self.demand.len()
+    }
+
+    /// Shipments x_ij read from the first sources * destinations entries of x.
+    pub fn flows(&self, x: &DVector<f64>) -> DMatrix<f64> {
+        let n = self.destinations();
+        DMatrix::from_fn(self.sources(), n, |i, j| {
+            x.get(i * n + j).copied().unwrap_or(0.0)
+        })
+    }
+
+    pub fn total_cost(&self, flows: &DMatrix<f64>) -> f64 {
+        self.costs.component_mul(flows).sum()
+    }
+}
+
+impl TransportationProblem {
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = vec![];
+        if self.supply.is_empty() || self.demand.is_empty() {
+            errors.push("The table needs at least one source and one destination.".to_string());
+            return Err(errors);
+        }
+        if self.costs.shape() != (self.supply.len(), self.demand.len()) {
+            errors.push(format!(
+                "The cost table is {}x{} but there are {} sources and {} destinations.",
+                self.costs.nrows(),
+                self.costs.ncols(),
+                self.supply.len(),
+                self.demand.len()
+            ));
+        }
+        if self.costs.iter().any(|v| !v.is_finite()) {
+            errors.push("All costs must be finite numbers.".to_string());
+        }
+        for (i, &s) in self.supply.iter().enumerate() {
+            if !(s.is_finite() && s > 0.0) {
+                errors.push(format!("Supply of source {} must be positive.", i + 1));
+            }
+        }
+        for (j, &d) in self.demand.iter().enumerate() {
+            if !(d.is_finite() && d > 0.0) {
+                errors.push(format!("Demand of destination {} must be positive.", j + 1));
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Adds a zero-cost dummy source or destination so supply equals demand.
+    pub fn balanced(&self) -> TransportationLayout {
+        let total_supply: f64 = self.supply.iter().sum();
+        let total_demand: f64 = self.demand.iter().sum();
+        let tolerance = 1e-9 * total_supply.max(total_demand);
+
+        let mut supply = self.supply.clone();
+        let mut demand = self.demand.clone();
+        let mut costs = self.costs.clone();
+        let dummy_destination = total_supply > total_demand + tolerance;
+        let dummy_source = total_demand > total_supply + tolerance;
+        if dummy_destination {
+            demand.push(total_supply - total_demand);
+            let column = costs.ncols();
+            costs = costs.insert_column(column, 0.0);
+        }
+        if dummy_source {
+            supply.push(total_demand - total_supply);
+            let row = costs.nrows();
+            costs = costs.insert_row(row, 0.0);
+        }
+        TransportationLayout {
+            supply,
+            demand,
+            costs,
+            dummy_source,
+            dummy_destination,
+        }
+    }
+
+    /// Standard LP form of the balanced problem: one equality per source and
+    /// per destination, with the last destination row dropped because it is
+    /// implied by the others.
+    ///
+    /// The proportional plan x_ij = s_i d_j / total satisfies every row and is
+    /// strictly positive, so the interior methods start there without any
+    /// artificials. The simplex methods still get Big-M artificials as their
+    /// starting basis.
+    pub fn to_validated(&self, method: SolverMethod, options: StartOptions) -> ValidatedProblem {
+        let layout = self.balanced();
+        let (m, n) = (layout.sources(), layout.destinations());
+        let total: f64 = layout.supply.iter().sum();
+
+        let rows = m + n - 1;
+        let mut a = DMatrix::zeros(rows, m * n);
+        let mut b = DVector::zeros(rows);
+        for i in 0..m {
+            for j in 0..n {
+                a[(i, i * n + j)] = 1.0;
+            }
+            b[i] = layout.supply[i];
+        }
+        for j in 0..n - 1 {
+            for i in 0..m {
+                a[(m + j, i * n + j)] = 1.0;
+            }
+            b[m + j] = layout.demand[j];
+        }
+        let c = DVector::from_iterator(m * n, layout.costs.transpose().iter().copied());
+        let initial: Vec<f64> = (0..m * n)
+            .map(|k| layout.supply[k / n] * layout.demand[k % n] / total)
+            .collect();
+        let signs = vec![ConstraintSign::Equal; rows];
+
+        let form = if method.is_interior() {
+            StandardForm {
+                a,
+                b,
+                c,
+                initial,
+                augmentation: Augmentation {
+                    original_variables: m * n,
+                    row_multipliers: vec![1.0; rows],
+                    ..Augmentation::default()
+                },
+            }
+        } else {
+            big_m_form(&a, &b, &c, &signs, &initial, Sense::Minimize)
+        };
+        let mut variable_labels = default_variable_labels(&form.augmentation, form.a.ncols());
+        for i in 0..m {
+            for j in 0..n {
+                variable_labels[i * n + j] = format!("x{},{}", i + 1, j + 1);
+            }
+        }
+        let constraint_labels = (1..=m)
+            .map(|i| format!("S{}", i))
+            .chain((1..n).map(|j| format!("D{}", j)))
+            .collect();
+
+        ValidatedProblem {
+            method,
+            sense: Sense::Minimize,
+            a: form.a,
+            b: form.b,
+            c: form.c,
+            quadratic: None,
+            initial: form.initial,
+            constraint_signs: signs,
+            integer_variables: vec![false; m * n],
+            variable_labels,
+            constraint_groups: (0..rows)
+                .map(|i| Some(if i < m { "supply" } else { "demand" }.to_string()))
+                .collect(),
+            constraint_labels,
+            options,
+            is_augmented: false,
+            augmentation: form.augmentation,
+            transportation: Some(layout),
+            regression: None,
+            fractional: None,
+            presolve: None,
+            goals: vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::equilibration::Scaling;
+    use crate::interior::StepStrategy;
+    use crate::test_models::solve;
+
+    /// Two plants, three markets: 20 + 30 units cover demands 10, 25 and 15.
+    /// Plant 2 costs more everywhere, by the most on market 2, so plant 1
+    /// sends all 20 there and plant 2 covers the rest for a total of 465.
+    fn plants() -> TransportationProblem {
+        TransportationProblem {
+            supply: vec![20.0, 30.0],
+            demand: vec![10.0, 25.0, 15.0],
+            costs: DMatrix::from_row_slice(2, 3, &[8.0, 6.0, 10.0, 9.0, 12.0, 13.0]),
+        }
+    }
+
+    fn options() -> StartOptions {
+        StartOptions {
+            alpha: 0.5,
+            mu: 1.0,
+            find_start: false,
+            step_strategy: StepStrategy::default(),
+            scaling: Scaling::default(),
+        }
+    }
+
+    #[test]
+    fn ships_along_the_cheapest_plan() {
+        let expected = DMatrix::from_row_slice(2, 3, &[0.0, 20.0, 0.0, 10.0, 5.0, 15.0]);
+        for method in [SolverMethod::Simplex, SolverMethod::AffineScaling] {
+            let model = plants().to_validated(method, options());
+            let report = solve(&model, method);
+            let layout = model.transportation.as_ref().unwrap();
+            let solution = report
+                .original_solution
+                .as_ref()
+                .unwrap_or(&report.solution);
+            let flows = layout.flows(solution);
+            assert!(
+                (layout.total_cost(&flows) - 465.0).abs() < 1e-3,
+                "{method:?}"
+            );
+            assert!((flows - &expected).amax() < 1e-2, "{method:?}");
+        }
+    }
+
+    #[test]
+    fn excess_supply_goes_to_a_dummy_destination() {
+        let mut problem = plants();
+        problem.demand = vec![10.0, 25.0];
+        problem.costs = problem.costs.remove_column(2);
+        let layout = problem.balanced();
+        assert!(layout.dummy_destination && !layout.dummy_source);
+        assert_eq!(layout.demand, vec![10.0, 25.0, 15.0]);
+        assert_eq!(layout.costs.column(2).sum(), 0.0);
+    }
+
+    #[test]
+    fn rejects_mismatched_tables() {
+        let mut problem = plants();
+        problem.supply.push(-1.0);
+        let errors = problem.validate().unwrap_err();
+        assert_eq!(errors.len(), 2, "{errors:?}");
+        assert!(errors[1].contains("source 3"));
+    }
+}
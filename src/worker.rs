@@ -0,0 +1,150 @@
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use nalgebra::DVector;
+use serde::{Deserialize, Serialize};
+
+use crate::cancel::CancellationToken;
+use crate::interior::{InteriorPointError, InteriorPointProblem};
+use crate::logging;
+use crate::report::{now_ms, SolveStatus};
+use crate::snapshot::CompactInteriorPointIteration;
+use crate::solver::Solver;
+use crate::stopping::StopReason;
+
+/// Where Trunk writes the worker's script, next to the page (see the
+/// `data-type="worker"` link in index.html).
+pub const WORKER_SCRIPT: &str = "./solver_worker.js";
+
+#[derive(Serialize, Deserialize)]
+pub enum WorkerInput {
+    /// Runs this affine-scaling problem until a step stops it or its
+    /// iteration budget is spent.
+    Solve(Box<InteriorPointProblem>),
+    /// Stops the run before its next step; it finishes as
+    /// `SolveStatus::Cancelled` with the steps already sent.
+    Cancel,
+}
+
+#[derive(Serialize, Deserialize)]
+pub enum WorkerOutput {
+    /// One step, sent as soon as it is taken so its card shows while the
+    /// run goes on.
+    Step {
+        iteration: Box<CompactInteriorPointIteration>,
+        elapsed_ms: f64,
+    },
+    /// The run is over: why, the improving ray of an unbounded model, and
+    /// the problem as the last step left it.
+    Finished {
+        status: SolveStatus,
+        ray: Option<DVector<f64>>,
+        problem: Box<InteriorPointProblem>,
+    },
+}
+
+/// A run in progress and who asked for it.
+struct Run {
+    client: HandlerId,
+    problem: InteriorPointProblem,
+    steps_left: usize,
+    /// Set by `WorkerInput::Cancel`, checked before every step.
+    cancel: CancellationToken,
+}
+
+/// Takes one step of the run.
+pub enum Msg {
+    Step,
+}
+
+/// Affine scaling off the page's thread, so "Solve Completely" on a large
+/// model does not freeze the UI. Every step is its own message to the
+/// worker, which keeps it responsive to new input between steps.
+pub struct SolverWorker {
+    run: Option<Run>,
+}
+
+impl Worker for SolverWorker {
+    type Message = Msg;
+    type Input = WorkerInput;
+    type Output = WorkerOutput;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self { run: None }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, msg: Self::Message) {
+        let Msg::Step = msg;
+        let Some(run) = &mut self.run else {
+            return;
+        };
+        if run.cancel.is_cancelled() {
+            self.finish(scope, SolveStatus::Cancelled, None);
+            return;
+        }
+        if run.steps_left == 0 {
+            run.problem.stop_reason = Some(StopReason::IterationLimit);
+            self.finish(scope, SolveStatus::IterationLimit, None);
+            return;
+        }
+        let start = now_ms();
+        match run.problem.step() {
+            Ok(iteration) => {
+                let output = WorkerOutput::Step {
+                    iteration: Box::new((&iteration).into()),
+                    elapsed_ms: now_ms() - start,
+                };
+                scope.respond(run.client, output);
+                run.steps_left -= 1;
+                scope.send_message(Msg::Step);
+            }
+            Err(error) => {
+                let status = SolveStatus::from(&error);
+                let ray = match error {
+                    InteriorPointError::Unbounded(direction) => Some(direction),
+                    _ => None,
+                };
+                self.finish(scope, status, ray);
+            }
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        match msg {
+            WorkerInput::Solve(problem) => {
+                // A new run replaces one still going; its client is gone.
+                // The worker has its own log, which writes to the browser
+                // console at the verbosity of the page that sent the run.
+                logging::set_verbosity(problem.options.log_verbosity);
+                self.run = Some(Run {
+                    client: id,
+                    steps_left: problem.remaining_iterations(),
+                    problem: *problem,
+                    cancel: CancellationToken::new(),
+                });
+                scope.send_message(Msg::Step);
+            }
+            WorkerInput::Cancel => {
+                if let Some(run) = &self.run {
+                    run.cancel.cancel();
+                }
+            }
+        }
+    }
+}
+
+impl SolverWorker {
+    fn finish(
+        &mut self,
+        scope: &WorkerScope<Self>,
+        status: SolveStatus,
+        ray: Option<DVector<f64>>,
+    ) {
+        if let Some(run) = self.run.take() {
+            let output = WorkerOutput::Finished {
+                status,
+                ray,
+                problem: Box::new(run.problem),
+            };
+            scope.respond(run.client, output);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+/// Local file header signature, "PK\x03\x04".
+const LOCAL_HEADER: u32 = 0x0403_4b50;
+/// Central directory entry signature, "PK\x01\x02".
+const CENTRAL_HEADER: u32 = 0x0201_4b50;
+/// End of central directory signature, "PK\x05\x06".
+const END_OF_DIRECTORY: u32 = 0x0605_4b50;
+/// Version 2.0, the oldest every unzip tool reads.
+const VERSION: u16 = 20;
+/// General purpose flag bit 11: names are UTF-8.
+const UTF8_NAMES: u16 = 0x0800;
+/// 1980-01-01 in MS-DOS date format, the earliest date a zip can hold. A
+/// fixed date keeps the archive of one session byte for byte the same.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+/// Writes `files` as a zip archive with every entry stored uncompressed.
+/// The bundles it is used for are a few kilobytes of text, so deflate
+/// would not be worth a second implementation.
+pub fn stored_zip(files: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut directory = Vec::new();
+    for (name, data) in files {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+
+        put_u32(&mut out, LOCAL_HEADER);
+        put_entry_fields(&mut out, name, data, crc);
+        put_u16(&mut out, 0); // extra field length
+        out.extend_from_slice(name.as_bytes());
+        out.extend_from_slice(data);
+
+        put_u32(&mut directory, CENTRAL_HEADER);
+        put_u16(&mut directory, VERSION); // made by
+        put_entry_fields(&mut directory, name, data, crc);
+        put_u16(&mut directory, 0); // extra field length
+        put_u16(&mut directory, 0); // comment length
+        put_u16(&mut directory, 0); // disk number
+        put_u16(&mut directory, 0); // internal attributes
+        put_u32(&mut directory, 0); // external attributes
+        put_u32(&mut directory, offset);
+        directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = out.len() as u32;
+    out.extend_from_slice(&directory);
+    put_u32(&mut out, END_OF_DIRECTORY);
+    put_u16(&mut out, 0); // this disk
+    put_u16(&mut out, 0); // disk with the directory
+    put_u16(&mut out, files.len() as u16);
+    put_u16(&mut out, files.len() as u16);
+    put_u32(&mut out, directory.len() as u32);
+    put_u32(&mut out, directory_offset);
+    put_u16(&mut out, 0); // comment length
+    out
+}
+
+/// The fields the local header and the directory entry share, from
+/// "version needed" through the name length.
+fn put_entry_fields(out: &mut Vec<u8>, name: &str, data: &[u8], crc: u32) {
+    put_u16(out, VERSION);
+    put_u16(out, UTF8_NAMES);
+    put_u16(out, 0); // stored
+    put_u16(out, 0); // time
+    put_u16(out, DOS_DATE);
+    put_u32(out, crc);
+    put_u32(out, data.len() as u32);
+    put_u32(out, data.len() as u32);
+    put_u16(out, name.len() as u16);
+}
+
+fn put_u16(out: &mut Vec<u8>, val: u16) {
+    out.extend_from_slice(&val.to_le_bytes());
+}
+
+fn put_u32(out: &mut Vec<u8>, val: u32) {
+    out.extend_from_slice(&val.to_le_bytes());
+}
+
+/// CRC-32 as zip uses it (reflected polynomial 0xEDB88320), bit by bit.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u16_at(bytes: &[u8], at: usize) -> u16 {
+        u16::from_le_bytes([bytes[at], bytes[at + 1]])
+    }
+
+    fn u32_at(bytes: &[u8], at: usize) -> u32 {
+        u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn crc_matches_the_standard_check_value() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn archive_points_at_every_entry() {
+        let files = vec![
+            ("a.txt".to_string(), b"hello".to_vec()),
+            ("plots/b.svg".to_string(), b"<svg/>".to_vec()),
+        ];
+        let zip = stored_zip(&files);
+        assert_eq!(zip, stored_zip(&files), "the archive is reproducible");
+
+        // The end record is the last 22 bytes and leads to the directory.
+        let end = zip.len() - 22;
+        assert_eq!(u32_at(&zip, end), END_OF_DIRECTORY);
+        assert_eq!(u16_at(&zip, end + 10), 2);
+        let mut entry = u32_at(&zip, end + 16) as usize;
+        assert_eq!(u32_at(&zip, end + 12) as usize, end - entry);
+
+        for (name, data) in &files {
+            assert_eq!(u32_at(&zip, entry), CENTRAL_HEADER);
+            assert_eq!(u32_at(&zip, entry + 16), crc32(data));
+            let name_length = u16_at(&zip, entry + 28) as usize;
+            assert_eq!(&zip[entry + 46..entry + 46 + name_length], name.as_bytes());
+
+            // Its local header, then the name and the data stored as is.
+            let local = u32_at(&zip, entry + 42) as usize;
+            assert_eq!(u32_at(&zip, local), LOCAL_HEADER);
+            assert_eq!(u32_at(&zip, local + 18) as usize, data.len());
+            let start = local + 30 + name_length;
+            assert_eq!(&zip[start..start + data.len()], data.as_slice());
+            entry += 46 + name_length;
+        }
+        assert_eq!(entry, end);
+    }
+}